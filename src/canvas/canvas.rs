@@ -1,30 +1,155 @@
 use super::tool::{Pending, Tool};
 use super::tools::line::LinePending;
+use crate::canvas::effect;
+use crate::canvas::guide::GuideAxis;
 use crate::canvas::layer::{CanvasMessage, Layer, LayerVessel};
-use crate::canvas::style::Style;
+use crate::canvas::style::{Style, SymmetryMode};
 use crate::canvas::svg::SVG;
+use crate::canvas::tools::raster;
+use crate::canvas::tools::smudge;
+use crate::canvas::tools::stamp::{self, StampPending};
+use crate::canvas::tools::symmetry::SymmetryCopy;
+use crate::canvas::undo::UndoEntry;
 use crate::database;
 use crate::scene::{Globals, Message};
+use crate::scenes::data::debug::LogEntry;
 use crate::scenes::services;
 use crate::utils::errors::Error;
 use crate::utils::serde::Serialize;
-use crate::utils::theme::Theme;
+use crate::utils::theme::{self, Theme};
+use chrono::{DateTime, Local};
 use iced::advanced::layout::{Limits, Node};
 use iced::advanced::renderer::Quad;
 use iced::advanced::widget::{tree, Tree};
+use iced::advanced::Text;
 use iced::advanced::{Clipboard, Layout, Shell, Widget};
+use iced::alignment::{Horizontal, Vertical};
 use iced::event::Status;
-use iced::mouse::{Cursor, Interaction};
+use iced::mouse::{self, Cursor, Interaction};
 use iced::widget::canvas;
-use iced::{Color, Command, Element, Event, Length, Rectangle, Renderer, Size};
+use iced::widget::text::{LineHeight, Shaping};
+use iced::{
+    touch, Border, Color, Command, Element, Event, Font, Length, Point, Rectangle, Renderer, Size,
+    Transformation, Vector,
+};
 use json::object::Object;
 use json::JsonValue;
 use mongodb::bson::{Document, Uuid};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use svg::node::element::Group;
 
+/// The smallest zoom level of the drawing.
+const MIN_ZOOM: f32 = 0.1;
+
+/// The largest zoom level of the drawing.
+const MAX_ZOOM: f32 = 10.0;
+
+/// The thickness of the rulers along the top and left edges of the drawing area.
+const RULER_SIZE: f32 = 16.0;
+
+/// The distance, in drawing units, between two labeled marks of the rulers.
+const RULER_STEP: f32 = 100.0;
+
+/// The distance, in pixels of the screen, within which the cursor grabs or snaps to a guide.
+const GUIDE_DISTANCE: f32 = 5.0;
+
+/// How long after the application writes the drawing file changes to it are considered its own.
+const OWN_WRITE_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// The cursor of a collaborator working on the same drawing.
+#[derive(Debug, Clone)]
+pub struct CollabCursor {
+    /// The id of the user the cursor belongs to.
+    user_id: Uuid,
+
+    /// The name displayed next to the cursor.
+    name: String,
+
+    /// The position of the cursor, relative to the drawing area.
+    position: Point,
+
+    /// The color of the cursor; depends on the user.
+    color: Color,
+
+    /// The moment the cursor was last moved.
+    last_moved: Instant,
+}
+
+impl CollabCursor {
+    /// Creates a new cursor for the given user, at the given position.
+    pub fn new(user_id: Uuid, name: impl Into<String>, position: Point) -> Self {
+        let bytes = user_id.bytes();
+        let hue = u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / u16::MAX as f32;
+
+        CollabCursor {
+            user_id,
+            name: name.into(),
+            position,
+            color: Self::hue_to_color(hue),
+            last_moved: Instant::now(),
+        }
+    }
+
+    /// Converts the given hue into a saturated color.
+    fn hue_to_color(hue: f32) -> Color {
+        let channel = |offset: f32| -> f32 {
+            let value = ((hue + offset).fract() * 6.0 - 3.0).abs() - 1.0;
+            value.clamp(0.0, 1.0) * 0.8
+        };
+
+        Color::from_rgb(channel(0.0), channel(2.0 / 3.0), channel(1.0 / 3.0))
+    }
+
+    pub fn get_user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    /// Tells whether the cursor has not moved for longer than the given duration.
+    pub fn is_expired(&self, duration: Duration) -> bool {
+        self.last_moved.elapsed() > duration
+    }
+}
+
+/// A named checkpoint of the state of a drawing.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// The id of the snapshot.
+    id: Uuid,
+
+    /// The name given to the snapshot.
+    name: String,
+
+    /// The moment the snapshot was created.
+    created: DateTime<Local>,
+}
+
+impl Snapshot {
+    /// Creates a new snapshot with the given data.
+    pub fn new(id: Uuid, name: impl Into<String>, created: DateTime<Local>) -> Self {
+        Snapshot {
+            id,
+            name: name.into(),
+            created,
+        }
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_created(&self) -> &DateTime<Local> {
+        &self.created
+    }
+}
+
 /// The canvas structure.
 pub struct Canvas {
     /// The id of the drawing.
@@ -54,8 +179,8 @@ pub struct Canvas {
     /// A list of all the [tools](Tool).
     tools: Box<Vec<(Arc<dyn Tool>, Uuid)>>,
 
-    /// A list of the removed [tools](Tool).
-    undo_stack: Box<Vec<(Arc<dyn Tool>, Uuid)>>,
+    /// The times at which each [Tool] in the list was placed. Loaded tools have no time.
+    tool_times: Vec<Option<DateTime<Local>>>,
 
     /// The index where the [Tool] list was last saved.
     last_saved: usize,
@@ -80,6 +205,43 @@ pub struct Canvas {
 
     /// The [Style] applied to the current [Tool].
     style: Style,
+
+    /// Tells whether the drawing is shown side by side with its last saved state.
+    split_view: bool,
+
+    /// The position of the divider between the saved and the current state, as a ratio of the width.
+    split_position: f32,
+
+    /// The zoom level of the drawing.
+    zoom: f32,
+
+    /// The offset of the drawing from the top-left corner of the drawing area.
+    pan: Vector,
+
+    /// The guide lines placed on the drawing.
+    guides: Vec<GuideAxis>,
+
+    /// The id of this editing session; used to tell apart the tools placed by collaborators.
+    session: Uuid,
+
+    /// The cursors of the collaborators, identified by their editing sessions.
+    collab_cursors: HashMap<Uuid, CollabCursor>,
+
+    /// The snapshots of the drawing, ordered by their creation time.
+    snapshots: Vec<Snapshot>,
+
+    /// The moment the drawing was last saved automatically.
+    auto_saved: Option<Instant>,
+
+    /// The moment the drawing file was last written by the application. Changes to the file
+    /// shortly after it are not reloaded, since they are most likely the application's own.
+    written_to_disk: Option<Instant>,
+
+    /// The layers whose effects have to be rendered again.
+    outdated_effects: HashSet<Uuid>,
+
+    /// Tells whether the effects panel is expanded.
+    effects_expanded: bool,
 }
 
 impl Canvas {
@@ -95,7 +257,7 @@ impl Canvas {
             layers: Box::new(HashMap::from_iter(vec![])),
             current_layer: Uuid::new(),
             tools: Box::new(vec![]),
-            undo_stack: Box::new(vec![]),
+            tool_times: vec![],
             last_saved: 0,
             count_saved: 0,
             edited_layers: false,
@@ -104,6 +266,18 @@ impl Canvas {
             json_tools: None,
             current_tool: Box::new(LinePending::None),
             style: Style::default(),
+            split_view: false,
+            split_position: 0.5,
+            zoom: 1.0,
+            pan: Vector::new(0.0, 0.0),
+            guides: vec![],
+            session: Uuid::new(),
+            collab_cursors: HashMap::new(),
+            snapshots: vec![],
+            auto_saved: None,
+            written_to_disk: None,
+            outdated_effects: HashSet::new(),
+            effects_expanded: false,
         }
     }
 
@@ -148,6 +322,87 @@ impl Canvas {
         &self.current_tool
     }
 
+    /// Returns the history of the placed [tools](Tool), including the undone ones, as
+    /// (tool name, layer id, placement time) entries, together with the current position in it.
+    pub fn get_history(
+        &self,
+        globals: &Globals,
+    ) -> (Vec<(String, Uuid, Option<DateTime<Local>>)>, usize) {
+        let done = self
+            .tools
+            .iter()
+            .zip(self.tool_times.iter())
+            .map(|((tool, layer), time)| (tool.id(), *layer, *time));
+        let undone = globals
+            .get_redo_stack()
+            .iter()
+            .rev()
+            .filter_map(|entry| match entry {
+                UndoEntry::PlaceTool(tool, layer, time) => Some((tool.id(), *layer, *time)),
+                _ => None,
+            });
+
+        (done.chain(undone).collect(), self.tools.len())
+    }
+
+    /// Returns the states of the drawing after every "step" placed [tools](Tool), in the
+    /// order they were placed. The last state is always the current drawing.
+    pub fn get_timeline(&self, step: usize) -> Vec<svg::Document> {
+        let step = step.max(1);
+        let mut svg = SVG::new(&self.layer_order);
+        let (width, height) = self.svg.get_size();
+        svg.set_size(width, height);
+
+        let mut frames = vec![];
+        for (index, (tool, layer)) in self.tools.iter().enumerate() {
+            svg.add_tool(layer, Serialize::<Group>::serialize(tool.as_ref()));
+
+            if (index + 1) % step == 0 || index + 1 == self.tools.len() {
+                frames.push(svg.as_document());
+            }
+        }
+
+        frames
+    }
+
+    /// Returns the dimensions of the drawing area.
+    pub fn get_size(&self) -> (u32, u32) {
+        self.svg.get_size()
+    }
+
+    pub fn get_session(&self) -> Uuid {
+        self.session
+    }
+
+    pub fn get_snapshots(&self) -> &Vec<Snapshot> {
+        &self.snapshots
+    }
+
+    pub fn get_auto_saved(&self) -> Option<Instant> {
+        self.auto_saved
+    }
+
+    pub fn is_effects_expanded(&self) -> bool {
+        self.effects_expanded
+    }
+
+    /// Places the cursor of the collaborator with the given editing session.
+    pub fn set_collab_cursor(&mut self, session: Uuid, cursor: CollabCursor) {
+        self.collab_cursors.insert(session, cursor);
+    }
+
+    /// Removes the cursors of the collaborators that have not moved them for longer than the
+    /// given duration.
+    pub fn expire_collab_cursors(&mut self, duration: Duration) {
+        self.collab_cursors
+            .retain(|_, cursor| !cursor.is_expired(duration));
+    }
+
+    /// Removes the cursors of all collaborators.
+    pub fn clear_collab_cursors(&mut self) {
+        self.collab_cursors.clear();
+    }
+
     pub fn set_id(&mut self, id: impl Into<Uuid>) {
         self.id = id.into();
     }
@@ -160,22 +415,142 @@ impl Canvas {
         self.new_name = new_name.into();
     }
 
+    pub fn is_split_view(&self) -> bool {
+        self.split_view
+    }
+
     pub fn is_offline(&self) -> bool {
         self.json_tools.is_some()
     }
 
-    fn use_tool(&mut self, tool: &Arc<dyn Tool>) {
-        self.tools.push((tool.clone(), self.current_layer));
+    /// Returns the given [Tool] along with the copies required by the current [SymmetryMode].
+    /// The copies are placed around the center of the drawing area.
+    fn get_symmetry_copies(&self, tool: &Arc<dyn Tool>) -> Vec<Arc<dyn Tool>> {
+        let (width, height) = self.svg.get_size();
+        let center = Point::new(width as f32 / 2.0, height as f32 / 2.0);
+
+        let mut copies: Vec<Arc<dyn Tool>> = vec![tool.clone()];
+        match self.style.symmetry {
+            SymmetryMode::None => {}
+            SymmetryMode::Horizontal => {
+                copies.push(Arc::new(SymmetryCopy::mirrored(
+                    tool.clone(),
+                    center,
+                    true,
+                    false,
+                )));
+            }
+            SymmetryMode::Vertical => {
+                copies.push(Arc::new(SymmetryCopy::mirrored(
+                    tool.clone(),
+                    center,
+                    false,
+                    true,
+                )));
+            }
+            SymmetryMode::Radial(folds) => {
+                for fold in 1..folds {
+                    let angle = 2.0 * PI * fold as f32 / folds as f32;
+                    copies.push(Arc::new(SymmetryCopy::rotated(tool.clone(), center, angle)));
+                }
+            }
+        }
+
+        copies
+    }
+
+    /// Renders the drawing and blurs it along the given points, adding the result as a
+    /// [SmudgeTool](smudge::SmudgeTool) once it is ready.
+    fn smudge(&self, points: Vec<Point>, strength: f32) -> Command<Message> {
+        let Some(drawing) = self.get_timeline(self.tools.len()).pop() else {
+            return Command::none();
+        };
+
+        Command::perform(
+            smudge::create_smudge(drawing, points, strength),
+            |result| match result {
+                Ok(tool) => CanvasMessage::UseTool(Arc::new(tool)).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    fn use_tool(&mut self, tool: &Arc<dyn Tool>, globals: &mut Globals) {
+        for tool in self.get_symmetry_copies(tool) {
+            self.place_tool(tool.clone(), self.current_layer, Some(Local::now()));
+            globals.push_undo(UndoEntry::RemoveTool(tool));
+        }
+        globals.clear_redo();
+    }
+
+    /// Places a [Tool] on top of the given layer.
+    fn place_tool(&mut self, tool: Arc<dyn Tool>, layer: Uuid, time: Option<DateTime<Local>>) {
+        self.tools.push((tool.clone(), layer));
+        self.tool_times.push(time);
+        self.layers
+            .get_mut(&layer)
+            .unwrap()
+            .get_mut_tools()
+            .push(tool);
+        self.clear_cache(layer);
+    }
+
+    /// Removes the given placed [Tool], returning its layer and the time it was placed at.
+    fn remove_tool(&mut self, tool: &Arc<dyn Tool>) -> Option<(Uuid, Option<DateTime<Local>>)> {
+        let position = self
+            .tools
+            .iter()
+            .rposition(|(placed, _)| Arc::ptr_eq(placed, tool))?;
+        let (_, layer) = self.tools.remove(position);
+        let time = self.tool_times.remove(position);
+
+        let layer_tools = self.layers.get_mut(&layer).unwrap().get_mut_tools();
+        if let Some(layer_position) = layer_tools
+            .iter()
+            .rposition(|placed| Arc::ptr_eq(placed, tool))
+        {
+            layer_tools.remove(layer_position);
+        }
+
+        // Every tool after the removed one has to be saved again.
+        self.count_saved = self.count_saved.min(position);
+        self.clear_cache(layer);
+
+        Some((layer, time))
+    }
+
+    /// Adds a [Tool] that a collaborator has already saved. It is placed after the saved
+    /// [tools](Tool), so that it will not be saved again.
+    fn use_remote_tool(&mut self, tool: &Arc<dyn Tool>, layer: Uuid) {
+        if !self.layers.contains_key(&layer) {
+            return;
+        }
+
+        let position = self.count_saved.min(self.tools.len());
+        let layer_position = self.tools[..position]
+            .iter()
+            .filter(|(_, layer_id)| *layer_id == layer)
+            .count();
+
+        self.tools.insert(position, (tool.clone(), layer));
+        self.tool_times.insert(position, Some(Local::now()));
         self.layers
-            .get_mut(&self.current_layer)
+            .get_mut(&layer)
             .unwrap()
             .get_mut_tools()
-            .push(tool.clone());
-        self.undo_stack = Box::new(vec![]);
-        self.clear_cache(self.current_layer);
+            .insert(layer_position, tool.clone());
+        self.svg.insert_tool(
+            &layer,
+            position,
+            Serialize::<Group>::serialize(tool.boxed_clone().deref()),
+        );
+
+        self.count_saved += 1;
+        self.last_saved += 1;
+        self.clear_cache(layer);
     }
 
-    fn add_layer(&mut self) {
+    fn add_layer(&mut self, globals: &mut Globals) {
         let layer_id = Uuid::new();
 
         self.svg.add_layer(layer_id);
@@ -184,42 +559,113 @@ impl Canvas {
         self.current_tool = self.current_tool.dyn_default();
         self.current_layer = layer_id;
         self.edited_layers = true;
+
+        globals.push_undo(UndoEntry::RemoveLayer(layer_id));
+        globals.clear_redo();
+    }
+
+    fn move_layer(&mut self, from: usize, to: usize) {
+        if from >= self.layer_order.len() || to >= self.layer_order.len() {
+            return;
+        }
+
+        let layer_id = self.layer_order.remove(from);
+        self.layer_order.insert(to, layer_id);
+        self.svg.move_layer(from, to);
+        self.edited_layers = true;
+    }
+
+    fn remove_layer(&mut self, id: Uuid, globals: &mut Globals) {
+        if let Some(entry) = self.take_layer(id) {
+            globals.push_undo(entry);
+            globals.clear_redo();
+        }
     }
 
-    fn remove_layer(&mut self, id: Uuid, globals: &mut Globals) -> Command<Message> {
-        if let Some(ref mut json_tools) = self.json_tools {
-            json_tools.retain(|tool| {
+    /// Removes the [Layer] with the given id along with its [tools](Tool), returning the change
+    /// that restores it. The last remaining [Layer] cannot be removed.
+    fn take_layer(&mut self, id: Uuid) -> Option<UndoEntry> {
+        if self.layer_order.len() <= 1 {
+            return None;
+        }
+
+        let position = self
+            .layer_order
+            .iter()
+            .position(|layer_id| *layer_id == id)?;
+        let layer = self.layers.remove(&id)?;
+        self.layer_order.remove(position);
+
+        let mut json_tools = vec![];
+        if let Some(ref mut all_json_tools) = self.json_tools {
+            let mut index = 0;
+            all_json_tools.retain(|tool| {
+                let mut keep = false;
                 if let JsonValue::Object(object) = tool {
                     if let Some(json_value) = object.get("id") {
                         if let JsonValue::String(layer_id) = json_value {
-                            return layer_id.clone() != id.to_string();
+                            keep = layer_id.clone() != id.to_string();
                         }
                     }
                 }
 
-                false
+                if !keep {
+                    json_tools.push((index, tool.clone()));
+                }
+                index += 1;
+
+                keep
             });
         }
 
-        self.tools.retain(|(_, layer_id)| *layer_id != id);
-        self.undo_stack.retain(|(_, layer_id)| *layer_id != id);
-        self.layers.remove(&id);
-        self.layer_order.retain(|layer_id| *layer_id != id);
+        let mut tools = vec![];
+        let placed = std::mem::take(&mut *self.tools)
+            .into_iter()
+            .zip(std::mem::take(&mut self.tool_times));
+        for (index, ((tool, layer_id), time)) in placed.enumerate() {
+            if layer_id == id {
+                tools.push((index, tool, time));
+            } else {
+                self.tools.push((tool, layer_id));
+                self.tool_times.push(time);
+            }
+        }
 
         self.edited_layers = true;
         self.removed_layers.push(id);
 
         if self.current_layer == id {
-            self.update(globals, CanvasMessage::ActivateLayer(self.layer_order[0]))
-        } else {
-            Command::none()
+            self.current_tool = self.current_tool.dyn_default();
+            self.current_layer = self.layer_order[0];
         }
+
+        Some(UndoEntry::RestoreLayer {
+            id,
+            position,
+            name: layer.get_name().clone(),
+            visible: layer.is_visible(),
+            effects: layer.get_effects().to_vec(),
+            tools,
+            json_tools,
+        })
     }
 
-    fn save(&mut self, globals: &mut Globals) -> Command<Message> {
+    /// Tells whether the drawing has been changed since it was last saved.
+    fn has_unsaved_changes(&self) -> bool {
+        self.tools.len() > self.count_saved
+            || self.count_saved != self.last_saved
+            || self.edited_layers
+    }
+
+    fn save(&mut self, automatic: bool, globals: &mut Globals) -> Command<Message> {
+        if !self.has_unsaved_changes() {
+            return Command::perform(async {}, move |_| CanvasMessage::Saved { automatic }.into());
+        }
+
         let tools_svg = self.get_tools_svg();
-        if tools_svg.is_empty() && self.count_saved == self.last_saved && !self.edited_layers {
-            return Command::perform(async {}, |_| CanvasMessage::Saved.into());
+
+        if self.json_tools.is_some() {
+            self.written_to_disk = Some(Instant::now());
         }
 
         let delete_lower_bound = self.count_saved;
@@ -240,12 +686,14 @@ impl Canvas {
             .collect();
 
         let canvas_name = self.name.clone();
+        let canvas_size = self.svg.get_size();
+        let guides = self.guides.clone();
 
         let document = self.svg.as_document();
         let cache = globals.get_cache();
 
         if let Some(tools) = self.json_tools.clone() {
-            let tools_json = self.get_tools_json();
+            let tools_json = self.get_tools_json(self.count_saved);
 
             let save_image = Command::perform(
                 services::drawing::save_preview_offline(canvas_id, document.clone(), cache),
@@ -263,21 +711,23 @@ impl Canvas {
                     tools,
                     tools_json,
                     layers,
+                    canvas_size,
+                    guides,
                 ),
-                |result: Result<(), Error>| match result {
-                    Ok(_) => CanvasMessage::Saved.into(),
+                move |result: Result<(), Error>| match result {
+                    Ok(_) => CanvasMessage::Saved { automatic }.into(),
                     Err(err) => Message::Error(err),
                 },
             );
 
             Command::batch(vec![save_data, save_image])
         } else {
-            let tools_mongo = self.get_tools_serialized();
+            let tools_mongo = self.get_tools_serialized(self.count_saved);
             let removed_layers = self.removed_layers.clone();
             let layer_data = self
-                .layers
+                .layer_order
                 .iter()
-                .map(|(id, layer)| (*id, layer.get_name().clone()))
+                .map(|id| (*id, self.layers.get(id).unwrap().get_name().clone()))
                 .collect::<Vec<(Uuid, String)>>();
             let db = globals.get_db();
             let user_id = globals.get_user().unwrap().get_id();
@@ -301,17 +751,20 @@ impl Canvas {
                             database::drawing::update_drawing(
                                 &db,
                                 canvas_id,
+                                user_id,
                                 canvas_name.clone(),
                                 delete_lower_bound as u32,
                                 delete_upper_bound as u32,
                                 tools_mongo,
                                 removed_layers,
                                 layer_data,
+                                canvas_size,
+                                guides,
                             )
                             .await
                         },
                         move |result| match result {
-                            Ok(()) => CanvasMessage::Saved.into(),
+                            Ok(()) => CanvasMessage::Saved { automatic }.into(),
                             Err(err) => Message::Error(err),
                         },
                     ),
@@ -322,90 +775,360 @@ impl Canvas {
         }
     }
 
-    fn undo(&mut self) {
-        let opt = self.tools.pop();
-        if let Some((tool, layer)) = opt {
-            self.layers.get_mut(&layer).unwrap().get_mut_tools().pop();
-            self.undo_stack.push((tool.clone(), layer));
-
-            self.clear_cache(layer);
-        }
-
-        if self.count_saved > self.tools.len() {
-            self.count_saved = self.count_saved - 1;
+    /// Stores the current state of the drawing as a snapshot with the given name.
+    fn create_snapshot(&mut self, name: String, globals: &mut Globals) -> Command<Message> {
+        let snapshot = Snapshot::new(Uuid::new(), name, Local::now());
+        let canvas_id = self.id;
+        let layers: Vec<(Uuid, String)> = self
+            .layer_order
+            .iter()
+            .map(|id| (*id, self.layers.get(id).unwrap().get_name().clone()))
+            .collect();
+        let size = self.svg.get_size();
+
+        if self.json_tools.is_some() {
+            let tools = self.get_tools_json(0);
+
+            Command::perform(
+                async move {
+                    services::drawing::create_snapshot_offline(
+                        canvas_id, &snapshot, layers, tools, size,
+                    )
+                    .await
+                    .map(|_| snapshot)
+                },
+                |result| match result {
+                    Ok(snapshot) => CanvasMessage::SnapshotCreated(snapshot).into(),
+                    Err(err) => Message::Error(err),
+                },
+            )
+        } else if let Some(db) = globals.get_db() {
+            let tools = self.get_tools_serialized(0);
+
+            Command::perform(
+                async move {
+                    database::drawing::create_snapshot(
+                        &db, canvas_id, &snapshot, layers, tools, size,
+                    )
+                    .await
+                    .map(|_| snapshot)
+                },
+                |result| match result {
+                    Ok(snapshot) => CanvasMessage::SnapshotCreated(snapshot).into(),
+                    Err(err) => Message::Error(err),
+                },
+            )
+        } else {
+            Command::none()
         }
     }
 
-    fn redo(&mut self) {
-        let opt = self.undo_stack.pop();
+    /// Loads the list of snapshots of the drawing.
+    fn load_snapshots(&self, globals: &Globals) -> Command<Message> {
+        let canvas_id = self.id;
 
-        if let Some((tool, layer)) = opt {
-            self.tools.push((tool.clone(), layer));
-            self.layers
-                .get_mut(&layer)
-                .unwrap()
-                .get_mut_tools()
-                .push(tool.clone());
-            self.clear_cache(layer);
+        if self.json_tools.is_some() {
+            Command::perform(
+                services::drawing::get_snapshots_offline(canvas_id),
+                |result| match result {
+                    Ok(snapshots) => CanvasMessage::LoadedSnapshots(snapshots).into(),
+                    Err(err) => Message::Error(err),
+                },
+            )
+        } else if let Some(db) = globals.get_db() {
+            Command::perform(
+                async move { database::drawing::get_snapshots(&db, canvas_id).await },
+                |result| match result {
+                    Ok(snapshots) => CanvasMessage::LoadedSnapshots(snapshots).into(),
+                    Err(err) => Message::Error(err),
+                },
+            )
+        } else {
+            Command::none()
         }
     }
 
-    fn loaded(
-        &mut self,
-        layers: Vec<(Uuid, String)>,
-        tools: Vec<(Arc<dyn Tool>, Uuid)>,
-        json_tools: Option<Vec<JsonValue>>,
-    ) {
-        println!("{}", layers[0].1);
-
-        self.tools = Box::new(vec![]);
-        self.layers = Box::new(HashMap::from_iter(
-            layers
-                .iter()
-                .map(|(id, name)| (*id, Layer::new(name.clone()))),
-        ));
-        self.layer_order = layers.iter().map(|(id, _)| *id).collect();
-        self.svg = SVG::new(&self.layer_order);
-        self.current_layer = self.layer_order[0];
-
-        for (tool, layer) in tools {
-            self.tools.push((tool.clone(), layer));
-            self.layers
-                .get_mut(&layer)
-                .unwrap()
-                .get_mut_tools()
-                .push(tool.clone());
-            self.svg.add_tool(
-                &layer,
-                Serialize::<Group>::serialize(tool.boxed_clone().deref()),
-            );
+    /// Loads the drawing again from its file if it was changed by another program. Only applies
+    /// to drawings stored locally.
+    fn reload_from_disk(&mut self, globals: &mut Globals) -> Command<Message> {
+        let written_recently = self
+            .written_to_disk
+            .is_some_and(|written| written.elapsed() < OWN_WRITE_GRACE_PERIOD);
+        if self.json_tools.is_none() || written_recently {
+            return Command::none();
         }
 
-        self.count_saved = self.tools.len();
-        self.last_saved = self.count_saved;
+        globals.push_toast("File changed on disk. Reloaded.");
 
-        self.json_tools = json_tools;
+        let canvas_id = self.id;
+        Command::perform(
+            async move { services::drawing::get_drawing_offline(canvas_id).await },
+            |result| match result {
+                Ok((layers, tools, json_tools, size, guides)) => CanvasMessage::Loaded {
+                    layers,
+                    tools,
+                    json_tools: Some(json_tools),
+                    size,
+                    guides,
+                }
+                .into(),
+                Err(err) => Message::Error(err),
+            },
+        )
     }
 
-    /// Returns the new unsaved tools as mongodb [documents](Document).
-    fn get_tools_serialized(&self) -> Vec<Document> {
-        let mut vec = vec![];
+    /// Replaces the stored state of the drawing with the given snapshot, then reloads it.
+    /// Unsaved changes are lost.
+    fn restore_snapshot(&mut self, snapshot_id: Uuid, globals: &mut Globals) -> Command<Message> {
+        let canvas_id = self.id;
 
-        for pos in self.count_saved..self.tools.len() {
-            let val = self.tools.get(pos);
+        self.removed_layers = vec![];
+        self.edited_layers = false;
 
-            if let Some((tool, layer)) = val {
-                let mut document: Document = tool.serialize();
-                document.insert("order", pos.clone() as u32);
-                document.insert("canvas_id", self.id);
-                document.insert("name", tool.id());
-                document.insert("layer", layer);
+        if self.json_tools.is_some() {
+            self.written_to_disk = Some(Instant::now());
 
-                vec.push(document);
-            }
+            Command::perform(
+                async move {
+                    services::drawing::restore_snapshot_offline(canvas_id, snapshot_id).await?;
+                    services::drawing::get_drawing_offline(canvas_id).await
+                },
+                |result| match result {
+                    Ok((layers, tools, json_tools, size, guides)) => CanvasMessage::Loaded {
+                        layers,
+                        tools,
+                        json_tools: Some(json_tools),
+                        size,
+                        guides,
+                    }
+                    .into(),
+                    Err(err) => Message::Error(err),
+                },
+            )
+        } else if let Some(db) = globals.get_db() {
+            Command::perform(
+                async move {
+                    database::drawing::restore_snapshot(&db, canvas_id, snapshot_id).await?;
+                    database::drawing::get_drawing(&db, canvas_id).await
+                },
+                |result| match result {
+                    Ok((layers, tools, size, guides)) => CanvasMessage::Loaded {
+                        layers,
+                        tools,
+                        json_tools: None,
+                        size,
+                        guides,
+                    }
+                    .into(),
+                    Err(err) => Message::Error(err),
+                },
+            )
+        } else {
+            Command::none()
         }
+    }
 
-        vec
+    /// Resizes the drawing area. Tools that fall outside the new bounds are clipped.
+    fn resize(&mut self, width: u32, height: u32) {
+        self.width = Length::Fixed(width as f32);
+        self.height = Length::Fixed(height as f32);
+        self.svg.set_size(width, height);
+
+        for layer in self.layer_order.clone() {
+            self.clear_cache(layer);
+        }
+    }
+
+    /// Reverses the last change recorded in the global undo history. Returns false if there
+    /// was nothing to undo.
+    fn undo(&mut self, globals: &mut Globals) -> bool {
+        let Some(entry) = globals.pop_undo() else {
+            return false;
+        };
+
+        if let Some(reverse) = self.apply_change(entry) {
+            globals.push_redo(reverse);
+        }
+
+        true
+    }
+
+    /// Reapplies the last change reversed by [undo](Canvas::undo). Returns false if there was
+    /// nothing to redo.
+    fn redo(&mut self, globals: &mut Globals) -> bool {
+        let Some(entry) = globals.pop_redo() else {
+            return false;
+        };
+
+        if let Some(reverse) = self.apply_change(entry) {
+            globals.push_undo(reverse);
+        }
+
+        true
+    }
+
+    /// Applies a change from the undo history, returning the change that reverses it. Changes
+    /// that no longer fit the drawing, such as those referring to missing layers, are skipped.
+    fn apply_change(&mut self, entry: UndoEntry) -> Option<UndoEntry> {
+        match entry {
+            UndoEntry::RemoveTool(tool) => self
+                .remove_tool(&tool)
+                .map(|(layer, time)| UndoEntry::PlaceTool(tool, layer, time)),
+            UndoEntry::PlaceTool(tool, layer, time) => {
+                if !self.layers.contains_key(&layer) {
+                    return None;
+                }
+
+                self.place_tool(tool.clone(), layer, time);
+                Some(UndoEntry::RemoveTool(tool))
+            }
+            UndoEntry::ToggleLayer(id) => {
+                self.layers.get_mut(&id)?.toggle_visibility();
+                Some(UndoEntry::ToggleLayer(id))
+            }
+            UndoEntry::RenameLayer(id, name) => {
+                let layer = self.layers.get_mut(&id)?;
+                let old_name = layer.get_name().clone();
+
+                layer.set_name(name);
+                layer.set_new_name(None);
+                self.edited_layers = true;
+
+                Some(UndoEntry::RenameLayer(id, old_name))
+            }
+            UndoEntry::RemoveLayer(id) => self.take_layer(id),
+            UndoEntry::RestoreLayer {
+                id,
+                position,
+                name,
+                visible,
+                effects,
+                tools,
+                json_tools,
+            } => {
+                let mut layer = Layer::new(name);
+                if !visible {
+                    layer.toggle_visibility();
+                }
+                for effect in effects {
+                    layer.add_effect(effect);
+                }
+                for (_, tool, _) in &tools {
+                    layer.get_mut_tools().push(tool.clone());
+                }
+
+                self.layers.insert(id, layer);
+                self.layer_order
+                    .insert(position.min(self.layer_order.len()), id);
+
+                for (index, tool, time) in tools {
+                    let index = index.min(self.tools.len());
+                    self.tools.insert(index, (tool, id));
+                    self.tool_times.insert(index, time);
+                }
+                if let Some(ref mut all_json_tools) = self.json_tools {
+                    for (index, tool) in json_tools {
+                        all_json_tools.insert(index.min(all_json_tools.len()), tool);
+                    }
+                }
+
+                self.removed_layers.retain(|layer_id| *layer_id != id);
+                self.edited_layers = true;
+                self.clear_cache(id);
+
+                Some(UndoEntry::RemoveLayer(id))
+            }
+        }
+    }
+
+    /// Undoes or redoes changes until the given number of [tools](Tool) are placed.
+    fn restore_to_step(&mut self, step: usize, globals: &mut Globals) {
+        while self.tools.len() > step && self.undo(globals) {}
+        while self.tools.len() < step && self.redo(globals) {}
+
+        for layer in self.layer_order.clone() {
+            self.clear_cache(layer);
+        }
+    }
+
+    fn loaded(
+        &mut self,
+        layers: Vec<(Uuid, String)>,
+        tools: Vec<(Arc<dyn Tool>, Uuid)>,
+        json_tools: Option<Vec<JsonValue>>,
+        size: Option<(u32, u32)>,
+        guides: Vec<GuideAxis>,
+        globals: &mut Globals,
+    ) {
+        globals.log_message(LogEntry::new(
+            "Canvas",
+            format!("Loaded drawing with {} layers.", layers.len()),
+            None,
+        ));
+
+        self.tools = Box::new(vec![]);
+        self.layers = Box::new(HashMap::from_iter(
+            layers
+                .iter()
+                .map(|(id, name)| (*id, Layer::new(name.clone()))),
+        ));
+        self.layer_order = layers.iter().map(|(id, _)| *id).collect();
+        self.svg = SVG::new(&self.layer_order);
+        self.current_layer = self.layer_order[0];
+
+        for (tool, layer) in tools {
+            self.tools.push((tool.clone(), layer));
+            self.layers
+                .get_mut(&layer)
+                .unwrap()
+                .get_mut_tools()
+                .push(tool.clone());
+            self.svg.add_tool(
+                &layer,
+                Serialize::<Group>::serialize(tool.boxed_clone().deref()),
+            );
+        }
+
+        if let Some((width, height)) = size {
+            self.resize(width, height);
+        }
+
+        self.tool_times = vec![None; self.tools.len()];
+
+        // The loaded tools can be undone like the ones placed in this session.
+        globals.clear_undo_history();
+        for (tool, _) in self.tools.iter() {
+            globals.push_undo(UndoEntry::RemoveTool(tool.clone()));
+        }
+
+        self.count_saved = self.tools.len();
+        self.last_saved = self.count_saved;
+
+        self.json_tools = json_tools;
+        self.guides = guides;
+    }
+
+    /// Returns the [tools](Tool) starting from the given position as mongodb
+    /// [documents](Document).
+    fn get_tools_serialized(&self, from: usize) -> Vec<Document> {
+        let mut vec = vec![];
+
+        for pos in from..self.tools.len() {
+            let val = self.tools.get(pos);
+
+            if let Some((tool, layer)) = val {
+                let mut document: Document = tool.serialize();
+                document.insert("order", pos.clone() as u32);
+                document.insert("canvas_id", self.id);
+                document.insert("name", tool.id());
+                document.insert("layer", layer);
+                document.insert("session", self.session);
+
+                vec.push(document);
+            }
+        }
+
+        vec
     }
 
     /// Returns the new unsaved tools as svg [groups](Group).
@@ -421,9 +1144,9 @@ impl Canvas {
             .collect()
     }
 
-    /// Returns the new unsaved tools as json [objects](JsonValue).
-    fn get_tools_json(&self) -> Vec<JsonValue> {
-        self.tools[self.count_saved..]
+    /// Returns the [tools](Tool) starting from the given position as json [objects](JsonValue).
+    fn get_tools_json(&self, from: usize) -> Vec<JsonValue> {
+        self.tools[from..]
             .iter()
             .map(|(tool, layer)| {
                 let mut data: Object = Serialize::<Object>::serialize(tool.boxed_clone().deref());
@@ -435,14 +1158,46 @@ impl Canvas {
             .collect()
     }
 
-    /// Clears the cache of a layer.
-    fn clear_cache(&mut self, layer: Uuid) {
-        let layer = self.layers.get(&layer);
+    /// Clears the cache of a layer. If the layer has effects, they are rendered again.
+    fn clear_cache(&mut self, layer_id: Uuid) {
+        let layer = self.layers.get(&layer_id);
         if let Some(layer) = layer {
             layer.clear_cache();
+
+            if !layer.get_effects().is_empty() {
+                self.outdated_effects.insert(layer_id);
+            }
         }
     }
 
+    /// Renders the effects of the layers that have changed since they were last rendered.
+    fn render_effects(&mut self) -> Command<Message> {
+        let mut svg = SVG::new(&vec![]);
+        let (width, height) = self.svg.get_size();
+        svg.set_size(width, height);
+
+        Command::batch(self.outdated_effects.drain().filter_map(|layer_id| {
+            let layer = self.layers.get(&layer_id)?;
+            let mut svg = svg.clone();
+
+            svg.add_layer(layer_id);
+            for tool in layer.get_tools() {
+                svg.add_tool(&layer_id, Serialize::<Group>::serialize(tool.as_ref()));
+            }
+
+            let document = svg.layer_document(&layer_id);
+            let effects = layer.get_effects().to_vec();
+
+            Some(Command::perform(
+                effect::render_effects(document, effects),
+                move |result| match result {
+                    Ok(surfaces) => CanvasMessage::LayerEffectsRendered(layer_id, surfaces).into(),
+                    Err(err) => Message::Error(err),
+                },
+            ))
+        }))
+    }
+
     /// Sets the width of the canvas.
     pub fn width(mut self, width: Length) -> Self {
         self.width = width;
@@ -476,48 +1231,173 @@ impl Canvas {
                     self.new_name = Some(new_name);
                 }
             }
-            CanvasMessage::UseTool(tool) => self.use_tool(&tool),
+            CanvasMessage::UseTool(tool) => self.use_tool(&tool, globals),
+            CanvasMessage::Smudge(points, strength) => return self.smudge(points, strength),
+            CanvasMessage::ImportStamp => {
+                return Command::perform(stamp::import_stamp(), |result| match result {
+                    Ok(stamp_id) => {
+                        CanvasMessage::ChangeTool(Box::new(StampPending::new(stamp_id))).into()
+                    }
+                    Err(err) => Message::Error(err),
+                });
+            }
+            CanvasMessage::ImportImage => {
+                return Command::perform(raster::import_image(self.svg.get_size()), |result| {
+                    match result {
+                        Ok(tool) => CanvasMessage::UseTool(Arc::new(tool)).into(),
+                        Err(err) => Message::Error(err),
+                    }
+                });
+            }
+            CanvasMessage::PlaceStamp(stamp_id, position, scale, rotation) => {
+                return Command::perform(
+                    stamp::create_stamp(stamp_id, position, scale, rotation),
+                    |result| match result {
+                        Ok(tool) => CanvasMessage::UseTool(Arc::new(tool)).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                );
+            }
+            CanvasMessage::UseRemoteTool(tool, layer) => self.use_remote_tool(&tool, layer),
+            CanvasMessage::AddLayerEffect(layer, effect) => {
+                if let Some(layer_data) = self.layers.get_mut(&layer) {
+                    layer_data.add_effect(effect);
+                    self.clear_cache(layer);
+                }
+            }
+            CanvasMessage::RemoveLayerEffect(layer, index) => {
+                if let Some(layer_data) = self.layers.get_mut(&layer) {
+                    layer_data.remove_effect(index);
+                    self.clear_cache(layer);
+                }
+            }
+            CanvasMessage::LayerEffectsRendered(layer, surfaces) => {
+                if let Some(layer) = self.layers.get_mut(&layer) {
+                    layer.set_effect_surfaces(surfaces);
+                }
+            }
+            CanvasMessage::ToggleEffectsPanel => self.effects_expanded = !self.effects_expanded,
             CanvasMessage::UpdateStyle(update) => {
                 return self.style.update(update);
             }
-            CanvasMessage::AddLayer => self.add_layer(),
+            CanvasMessage::AddLayer => self.add_layer(globals),
             CanvasMessage::ActivateLayer(layer) => {
                 self.current_tool = self.current_tool.dyn_default();
                 self.current_layer = layer;
             }
             CanvasMessage::ToggleLayer(layer) => {
                 self.layers.get_mut(&layer).unwrap().toggle_visibility();
+                globals.push_undo(UndoEntry::ToggleLayer(layer));
+                globals.clear_redo();
             }
             CanvasMessage::ToggleEditLayerName(layer) => {
-                self.layers.get_mut(&layer).unwrap().toggle_name();
+                let layer_data = self.layers.get_mut(&layer).unwrap();
+                let old_name = layer_data.get_name().clone();
+
+                if let Some(new_name) = layer_data.toggle_name() {
+                    if new_name != old_name {
+                        globals.push_undo(UndoEntry::RenameLayer(layer, old_name));
+                        globals.clear_redo();
+                    }
+                }
                 self.edited_layers = true;
             }
             CanvasMessage::UpdateLayerName(id, name) => {
                 self.layers.get_mut(&id).unwrap().set_new_name(name);
             }
-            CanvasMessage::RemoveLayer(id) => {
-                return self.remove_layer(id, globals);
-            }
+            CanvasMessage::RemoveLayer(id) => self.remove_layer(id, globals),
+            CanvasMessage::MoveLayer(from, to) => self.move_layer(from, to),
             CanvasMessage::Save => {
-                return self.save(globals);
+                return self.save(false, globals);
+            }
+            CanvasMessage::Undo => {
+                self.undo(globals);
+            }
+            CanvasMessage::Redo => {
+                self.redo(globals);
+            }
+            CanvasMessage::RestoreToStep(step) => self.restore_to_step(step, globals),
+            CanvasMessage::ToggleSplitView => self.split_view = !self.split_view,
+            CanvasMessage::MoveSplit(position) => self.split_position = position,
+            CanvasMessage::Zoom(factor, center) => {
+                let zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+                let center = Vector::new(center.x, center.y);
+
+                self.pan = center + (self.pan - center) * (zoom / self.zoom);
+                self.zoom = zoom;
+            }
+            CanvasMessage::Pan(offset) => self.pan = self.pan + offset,
+            CanvasMessage::AddGuide(guide) => {
+                self.guides.push(guide);
+                self.edited_layers = true;
+            }
+            CanvasMessage::MoveGuide(index, guide) => {
+                if let Some(old_guide) = self.guides.get_mut(index) {
+                    *old_guide = guide;
+                    self.edited_layers = true;
+                }
+            }
+            CanvasMessage::RemoveGuide(index) => {
+                if index < self.guides.len() {
+                    self.guides.remove(index);
+                    self.edited_layers = true;
+                }
+            }
+            CanvasMessage::CursorMoved(_) => {}
+            CanvasMessage::CreateSnapshot(name) => {
+                return self.create_snapshot(name, globals);
+            }
+            CanvasMessage::SnapshotCreated(snapshot) => self.snapshots.push(snapshot),
+            CanvasMessage::LoadSnapshots => {
+                return self.load_snapshots(globals);
+            }
+            CanvasMessage::LoadedSnapshots(mut snapshots) => {
+                snapshots.sort_by_key(|snapshot| *snapshot.get_created());
+                self.snapshots = snapshots;
+            }
+            CanvasMessage::RestoreSnapshot(snapshot_id) => {
+                return self.restore_snapshot(snapshot_id, globals);
+            }
+            CanvasMessage::AutoSave => {
+                if self.has_unsaved_changes() {
+                    return self.save(true, globals);
+                }
             }
-            CanvasMessage::Undo => self.undo(),
-            CanvasMessage::Redo => self.redo(),
             CanvasMessage::ChangeTool(tool) => {
                 self.current_tool = (*tool).boxed_clone();
+                self.style.smudge = None;
+                self.style.texture = None;
+                self.style.star = None;
+                self.style.gradient = None;
                 self.current_tool.shape_style(&mut self.style);
             }
-            CanvasMessage::Saved => {
+            CanvasMessage::Saved { automatic } => {
                 self.count_saved = self.tools.len();
                 self.last_saved = self.count_saved;
+                self.edited_layers = false;
+                if automatic {
+                    self.auto_saved = Some(Instant::now());
+                }
+                if self.json_tools.is_some() {
+                    self.written_to_disk = Some(Instant::now());
+                }
+            }
+            CanvasMessage::ReloadFromDisk => {
+                return self.reload_from_disk(globals);
             }
             CanvasMessage::Loaded {
                 layers,
                 tools,
                 json_tools,
-            } => self.loaded(layers, tools, json_tools),
+                size,
+                guides,
+            } => self.loaded(layers, tools, json_tools, size, guides, globals),
+            CanvasMessage::Resize { width, height } => {
+                self.resize(width, height);
+                self.edited_layers = true;
+            }
         }
-        Command::none()
+        self.render_effects()
     }
 }
 
@@ -544,8 +1424,43 @@ struct CanvasVessel<'a> {
     /// The list of [canvas layers](Canvas).
     layers: HashMap<Uuid, canvas::Canvas<LayerVessel<'a>, CanvasMessage, Theme, Renderer>>,
 
+    /// The list of [canvas layers](Canvas) as they were last saved. Empty if the split view is off.
+    saved_layers: HashMap<Uuid, canvas::Canvas<LayerVessel<'a>, CanvasMessage, Theme, Renderer>>,
+
     /// The index of the currently active [Layer].
     current_layer: Uuid,
+
+    /// The position of the split view divider, if the split view is on.
+    split: Option<f32>,
+
+    /// The cursors of the collaborators.
+    collab_cursors: &'a HashMap<Uuid, CollabCursor>,
+
+    /// The active [SymmetryMode], whose axes are drawn over the layers.
+    symmetry: SymmetryMode,
+
+    /// The zoom level of the drawing.
+    zoom: f32,
+
+    /// The offset of the drawing from the top-left corner of the drawing area.
+    pan: Vector,
+
+    /// The guide lines placed on the drawing.
+    guides: &'a [GuideAxis],
+}
+
+/// The state of the [CanvasVessel].
+#[derive(Default)]
+struct VesselState {
+    /// Tells whether the split view divider is being dragged.
+    dragging: bool,
+
+    /// The positions of the fingers touching the drawing area.
+    active_fingers: HashMap<touch::Finger, Point>,
+
+    /// The guide being dragged, given by its index, or None if it is being pulled out of a
+    /// ruler, and its current position.
+    dragged_guide: Option<(Option<usize>, GuideAxis)>,
 }
 
 impl<'a> CanvasVessel<'a> {
@@ -557,7 +1472,14 @@ impl<'a> CanvasVessel<'a> {
             states: &canvas.layers,
             layer_order: &canvas.layer_order,
             layers: HashMap::new(),
+            saved_layers: HashMap::new(),
             current_layer: canvas.current_layer,
+            split: canvas.split_view.then_some(canvas.split_position),
+            collab_cursors: &canvas.collab_cursors,
+            symmetry: canvas.style.symmetry,
+            zoom: canvas.zoom,
+            pan: canvas.pan,
+            guides: &canvas.guides,
         };
 
         vessel.layers = HashMap::from_iter(vessel.states.iter().map(|(pos, state)| {
@@ -566,6 +1488,7 @@ impl<'a> CanvasVessel<'a> {
                 canvas::Canvas::new(LayerVessel::new(
                     state.get_cache(),
                     state.get_tools(),
+                    state.get_effect_surfaces(),
                     &canvas.current_tool,
                     &canvas.style,
                     *pos == vessel.current_layer,
@@ -573,8 +1496,600 @@ impl<'a> CanvasVessel<'a> {
             )
         }));
 
+        if canvas.split_view {
+            // Only the tools saved before any undo are still known to be part of the last save.
+            let mut saved_counts: HashMap<Uuid, usize> = HashMap::new();
+            for (_, layer) in &canvas.tools[..canvas.count_saved.min(canvas.tools.len())] {
+                *saved_counts.entry(*layer).or_default() += 1;
+            }
+
+            vessel.saved_layers = HashMap::from_iter(vessel.states.iter().map(|(pos, state)| {
+                let count = saved_counts.get(pos).copied().unwrap_or(0);
+
+                (
+                    *pos,
+                    canvas::Canvas::new(LayerVessel::new(
+                        state.get_saved_cache(),
+                        &state.get_tools()[..count],
+                        &[],
+                        &canvas.current_tool,
+                        &canvas.style,
+                        false,
+                    )),
+                )
+            }));
+        }
+
         vessel
     }
+
+    /// Draws the given [layers](Layer) in order.
+    fn draw_layers(
+        &self,
+        layers: &HashMap<Uuid, canvas::Canvas<LayerVessel<'a>, CanvasMessage, Theme, Renderer>>,
+        state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &iced::advanced::renderer::Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        let mut children = layout.children();
+        let cursor = self.get_drawing_cursor(layout.bounds(), cursor);
+        let transformation = self.get_transformation(layout.bounds());
+
+        iced::advanced::Renderer::with_transformation(renderer, transformation, |renderer| {
+            for (layer, index) in self.layer_order.iter().zip(0..layers.len()) {
+                if self.states.get(&layer).unwrap().is_visible() {
+                    layers[&layer].draw(
+                        &state.children[index],
+                        renderer,
+                        theme,
+                        style,
+                        children
+                            .next()
+                            .expect(&*format!("Canvas needs to have at least {} layers.", index)),
+                        cursor,
+                        viewport,
+                    );
+                }
+            }
+        });
+    }
+
+    /// Returns the transformation that zooms and pans the drawing inside the given bounds.
+    fn get_transformation(&self, bounds: Rectangle) -> Transformation {
+        Transformation::translate(bounds.x + self.pan.x, bounds.y + self.pan.y)
+            * Transformation::scale(self.zoom)
+            * Transformation::translate(-bounds.x, -bounds.y)
+    }
+
+    /// Maps the cursor from the drawing area onto the zoomed and panned drawing.
+    /// The cursor snaps to the guides close to it.
+    fn get_drawing_cursor(&self, bounds: Rectangle, cursor: Cursor) -> Cursor {
+        match cursor.position() {
+            Some(position) => {
+                let point = self
+                    .guides
+                    .iter()
+                    .fold(self.get_drawing_point(bounds, position), |point, guide| {
+                        guide.snap(point, GUIDE_DISTANCE / self.zoom)
+                    });
+
+                Cursor::Available(bounds.position() + (point - Point::ORIGIN))
+            }
+            None => cursor,
+        }
+    }
+
+    /// Returns the point of the drawing under the given position on the screen.
+    fn get_drawing_point(&self, bounds: Rectangle, position: Point) -> Point {
+        Point::ORIGIN + (position - bounds.position() - self.pan) * (1.0 / self.zoom)
+    }
+
+    /// Returns the position of the given guide on the screen, rounded to whole pixels.
+    fn get_guide_position(&self, bounds: Rectangle, guide: GuideAxis) -> f32 {
+        match guide {
+            GuideAxis::Horizontal(y) => (bounds.y + self.pan.y + y * self.zoom).round(),
+            GuideAxis::Vertical(x) => (bounds.x + self.pan.x + x * self.zoom).round(),
+        }
+    }
+
+    /// Returns the index of the guide under the given position on the screen, if any.
+    fn get_guide(&self, bounds: Rectangle, position: Point) -> Option<usize> {
+        self.guides.iter().position(|guide| {
+            let coordinate = match guide {
+                GuideAxis::Horizontal(_) => position.y,
+                GuideAxis::Vertical(_) => position.x,
+            };
+
+            (self.get_guide_position(bounds, *guide) - coordinate).abs() <= GUIDE_DISTANCE
+        })
+    }
+
+    /// Tells whether the given position on the screen is over one of the rulers.
+    fn is_over_ruler(&self, bounds: Rectangle, position: Point) -> bool {
+        position.y < bounds.y + RULER_SIZE || position.x < bounds.x + RULER_SIZE
+    }
+
+    /// Draws the rulers along the top and left edges of the drawing area.
+    fn draw_rulers(&self, renderer: &mut Renderer, bounds: Rectangle) {
+        const MARK_LENGTH: f32 = 6.0;
+
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+        let rulers = [
+            Rectangle {
+                height: RULER_SIZE,
+                ..bounds
+            },
+            Rectangle {
+                width: RULER_SIZE,
+                ..bounds
+            },
+        ];
+
+        for ruler in rulers {
+            iced::advanced::Renderer::fill_quad(
+                renderer,
+                Quad {
+                    bounds: ruler,
+                    border: Default::default(),
+                    shadow: Default::default(),
+                },
+                palette.background.weak.color,
+            );
+        }
+
+        let first = self.get_drawing_point(bounds, bounds.position());
+        let last = self.get_drawing_point(
+            bounds,
+            Point::new(bounds.x + bounds.width, bounds.y + bounds.height),
+        );
+
+        // Half steps are marked without labels.
+        let step = RULER_STEP / 2.0;
+        let mut mark = (first.x / step).ceil() * step;
+        while mark <= last.x {
+            let x = self.get_guide_position(bounds, GuideAxis::Vertical(mark));
+            let labeled = mark % RULER_STEP == 0.0;
+
+            self.draw_ruler_mark(
+                renderer,
+                Rectangle {
+                    x,
+                    y: bounds.y + RULER_SIZE - if labeled { RULER_SIZE } else { MARK_LENGTH },
+                    width: 1.0,
+                    height: if labeled { RULER_SIZE } else { MARK_LENGTH },
+                },
+                labeled.then_some((mark, Point::new(x + 2.0, bounds.y + RULER_SIZE / 2.0))),
+                bounds,
+            );
+
+            mark += step;
+        }
+
+        let mut mark = (first.y / step).ceil() * step;
+        while mark <= last.y {
+            let y = self.get_guide_position(bounds, GuideAxis::Horizontal(mark));
+            let labeled = mark % RULER_STEP == 0.0;
+
+            self.draw_ruler_mark(
+                renderer,
+                Rectangle {
+                    x: bounds.x + RULER_SIZE - if labeled { RULER_SIZE } else { MARK_LENGTH },
+                    y,
+                    width: if labeled { RULER_SIZE } else { MARK_LENGTH },
+                    height: 1.0,
+                },
+                labeled.then_some((mark, Point::new(bounds.x + 1.0, y + RULER_SIZE / 2.0))),
+                bounds,
+            );
+
+            mark += step;
+        }
+    }
+
+    /// Draws a single mark of a ruler, with the given label at the given position.
+    fn draw_ruler_mark(
+        &self,
+        renderer: &mut Renderer,
+        mark: Rectangle,
+        label: Option<(f32, Point)>,
+        bounds: Rectangle,
+    ) {
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+
+        iced::advanced::Renderer::fill_quad(
+            renderer,
+            Quad {
+                bounds: mark,
+                border: Default::default(),
+                shadow: Default::default(),
+            },
+            palette.background.weak.text,
+        );
+
+        if let Some((value, position)) = label {
+            iced::advanced::text::Renderer::fill_text(
+                renderer,
+                Text {
+                    content: format!("{}", value as i32),
+                    bounds: Size::new(f32::INFINITY, f32::INFINITY),
+                    size: 9.0.into(),
+                    line_height: LineHeight::default(),
+                    font: Font::default(),
+                    horizontal_alignment: Horizontal::Left,
+                    vertical_alignment: Vertical::Center,
+                    shaping: Shaping::Basic,
+                },
+                position,
+                palette.background.weak.text,
+                bounds,
+            );
+        }
+    }
+
+    /// Draws the guides as dashed lines, with the dragged guide at its current position.
+    fn draw_guides(
+        &self,
+        renderer: &mut Renderer,
+        bounds: Rectangle,
+        dragged_guide: Option<(Option<usize>, GuideAxis)>,
+    ) {
+        const DASH_LENGTH: f32 = 6.0;
+        const DASH_SPACING: f32 = 4.0;
+
+        let guides = self
+            .guides
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| dragged_guide.map_or(true, |(dragged, _)| dragged != Some(*index)))
+            .map(|(_, guide)| *guide)
+            .chain(dragged_guide.map(|(_, guide)| guide));
+
+        for guide in guides {
+            let position = self.get_guide_position(bounds, guide);
+            let (start, end) = match guide {
+                GuideAxis::Horizontal(_) => (bounds.x, bounds.x + bounds.width),
+                GuideAxis::Vertical(_) => (bounds.y, bounds.y + bounds.height),
+            };
+
+            let mut dash = start;
+            while dash < end {
+                let length = DASH_LENGTH.min(end - dash);
+
+                iced::advanced::Renderer::fill_quad(
+                    renderer,
+                    Quad {
+                        bounds: match guide {
+                            GuideAxis::Horizontal(_) => Rectangle {
+                                x: dash,
+                                y: position,
+                                width: length,
+                                height: 1.0,
+                            },
+                            GuideAxis::Vertical(_) => Rectangle {
+                                x: position,
+                                y: dash,
+                                width: 1.0,
+                                height: length,
+                            },
+                        },
+                        border: Default::default(),
+                        shadow: Default::default(),
+                    },
+                    Color::from_rgb8(0, 128, 255),
+                );
+
+                dash += DASH_LENGTH + DASH_SPACING;
+            }
+        }
+    }
+
+    /// Handles pulling guides out of the rulers, moving them, and dropping them back onto the
+    /// rulers to remove them.
+    fn on_guide_event(
+        &self,
+        state: &mut Tree,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+        shell: &mut Shell<'_, CanvasMessage>,
+    ) -> Status {
+        let dragged_guide = &mut state.state.downcast_mut::<VesselState>().dragged_guide;
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let Some(position) = cursor.position_over(bounds) else {
+                    return Status::Ignored;
+                };
+                let point = self.get_drawing_point(bounds, position);
+
+                *dragged_guide = if position.y < bounds.y + RULER_SIZE {
+                    Some((None, GuideAxis::Horizontal(point.y)))
+                } else if position.x < bounds.x + RULER_SIZE {
+                    Some((None, GuideAxis::Vertical(point.x)))
+                } else {
+                    self.get_guide(bounds, position)
+                        .map(|index| (Some(index), self.guides[index]))
+                };
+
+                if dragged_guide.is_some() {
+                    Status::Captured
+                } else {
+                    Status::Ignored
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if let Some((_, guide)) = dragged_guide {
+                    *guide = guide.through(self.get_drawing_point(bounds, *position));
+
+                    Status::Captured
+                } else {
+                    Status::Ignored
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                let Some((index, guide)) = dragged_guide.take() else {
+                    return Status::Ignored;
+                };
+                let over_ruler = cursor
+                    .position()
+                    .map_or(true, |position| self.is_over_ruler(bounds, position));
+
+                match (index, over_ruler) {
+                    (Some(index), true) => shell.publish(CanvasMessage::RemoveGuide(index)),
+                    (Some(index), false) => shell.publish(CanvasMessage::MoveGuide(index, guide)),
+                    (None, false) => shell.publish(CanvasMessage::AddGuide(guide)),
+                    (None, true) => {}
+                }
+
+                Status::Captured
+            }
+            _ => Status::Ignored,
+        }
+    }
+
+    /// Returns the [Layout] and the index of the current [Layer].
+    fn get_current_layer<'b>(&self, layout: Layout<'b>) -> (Layout<'b>, usize) {
+        let index = self
+            .layer_order
+            .iter()
+            .position(|id| *id == self.current_layer)
+            .expect(&*format!(
+                "Canvas needs to have the layer {}.",
+                self.current_layer
+            ));
+
+        (
+            layout.children().nth(index).expect(&*format!(
+                "Canvas needs to have at least {} children.",
+                index + 1
+            )),
+            index,
+        )
+    }
+
+    /// Sends the given event to the current [Layer], with the cursor mapped onto the drawing.
+    fn on_layer_event(
+        &mut self,
+        state: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, CanvasMessage>,
+        viewport: &Rectangle,
+    ) -> Status {
+        let cursor = self.get_drawing_cursor(layout.bounds(), cursor);
+        let (layout, index) = self.get_current_layer(layout);
+
+        self.layers.get_mut(&self.current_layer).unwrap().on_event(
+            &mut state.children[index],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    /// Handles touch events. A single finger draws like the left mouse button, while two fingers
+    /// pan the drawing and pinch to zoom it.
+    fn on_touch(
+        &mut self,
+        state: &mut Tree,
+        event: touch::Event,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, CanvasMessage>,
+        viewport: &Rectangle,
+    ) -> Status {
+        let bounds = layout.bounds();
+        let fingers = &mut state.state.downcast_mut::<VesselState>().active_fingers;
+
+        let (event, position) = match event {
+            touch::Event::FingerPressed { id, position } => {
+                if !bounds.contains(position) {
+                    return Status::Ignored;
+                }
+                fingers.insert(id, position);
+
+                match fingers.len() {
+                    1 => (mouse::Event::ButtonPressed(mouse::Button::Left), position),
+                    // A second finger ends the stroke of the first, which starts a gesture.
+                    2 => (mouse::Event::ButtonReleased(mouse::Button::Left), position),
+                    _ => return Status::Captured,
+                }
+            }
+            touch::Event::FingerMoved { id, position } => {
+                let Some(previous) = fingers.insert(id, position) else {
+                    return Status::Ignored;
+                };
+
+                match fingers.len() {
+                    1 => (mouse::Event::CursorMoved { position }, position),
+                    2 => {
+                        let other = *fingers
+                            .iter()
+                            .find(|(finger, _)| **finger != id)
+                            .map(|(_, position)| position)
+                            .unwrap();
+                        let old_center = previous + (other - previous) * 0.5;
+                        let new_center = position + (other - position) * 0.5;
+                        let old_distance = previous.distance(other);
+
+                        shell.publish(CanvasMessage::Pan(new_center - old_center));
+                        if old_distance > 0.0 {
+                            shell.publish(CanvasMessage::Zoom(
+                                position.distance(other) / old_distance,
+                                Point::ORIGIN + (new_center - bounds.position()),
+                            ));
+                        }
+
+                        return Status::Captured;
+                    }
+                    _ => return Status::Captured,
+                }
+            }
+            touch::Event::FingerLifted { id, position }
+            | touch::Event::FingerLost { id, position } => {
+                if fingers.remove(&id).is_none() {
+                    return Status::Ignored;
+                }
+
+                if fingers.is_empty() {
+                    (mouse::Event::ButtonReleased(mouse::Button::Left), position)
+                } else {
+                    return Status::Captured;
+                }
+            }
+        };
+
+        if let mouse::Event::CursorMoved { .. } = event {
+            if let Some(position) = Cursor::Available(position).position_in(bounds) {
+                shell.publish(CanvasMessage::CursorMoved(position));
+            }
+        }
+
+        self.on_layer_event(
+            state,
+            Event::Mouse(event),
+            layout,
+            Cursor::Available(position),
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    /// Draws the cursors of the collaborators as labeled dots.
+    fn draw_collab_cursors(&self, renderer: &mut Renderer, bounds: Rectangle) {
+        const RADIUS: f32 = 5.0;
+
+        for cursor in self.collab_cursors.values() {
+            let center = Point::new(bounds.x + cursor.position.x, bounds.y + cursor.position.y);
+            if !bounds.contains(center) {
+                continue;
+            }
+
+            iced::advanced::Renderer::fill_quad(
+                renderer,
+                Quad {
+                    bounds: Rectangle {
+                        x: center.x - RADIUS,
+                        y: center.y - RADIUS,
+                        width: 2.0 * RADIUS,
+                        height: 2.0 * RADIUS,
+                    },
+                    border: Border {
+                        radius: RADIUS.into(),
+                        ..Default::default()
+                    },
+                    shadow: Default::default(),
+                },
+                cursor.color,
+            );
+
+            iced::advanced::text::Renderer::fill_text(
+                renderer,
+                Text {
+                    content: cursor.name.clone(),
+                    bounds: Size::new(f32::INFINITY, f32::INFINITY),
+                    size: 12.0.into(),
+                    line_height: LineHeight::default(),
+                    font: Font::default(),
+                    horizontal_alignment: Horizontal::Left,
+                    vertical_alignment: Vertical::Center,
+                    shaping: Shaping::Basic,
+                },
+                Point::new(center.x + 2.0 * RADIUS, center.y),
+                cursor.color,
+                bounds,
+            );
+        }
+    }
+
+    /// Draws the axes of the active [SymmetryMode] as dotted lines starting from the center.
+    fn draw_symmetry_axes(&self, renderer: &mut Renderer, bounds: Rectangle) {
+        const DOT_SIZE: f32 = 2.0;
+        const DOT_SPACING: f32 = 8.0;
+
+        let angles: Vec<f32> = match self.symmetry {
+            SymmetryMode::None => return,
+            SymmetryMode::Horizontal => vec![-PI / 2.0, PI / 2.0],
+            SymmetryMode::Vertical => vec![0.0, PI],
+            SymmetryMode::Radial(folds) => (0..folds)
+                .map(|fold| -PI / 2.0 + 2.0 * PI * fold as f32 / folds as f32)
+                .collect(),
+        };
+
+        let center = bounds.center();
+        let length = bounds.width.hypot(bounds.height) / 2.0;
+
+        for angle in angles {
+            let (sin, cos) = angle.sin_cos();
+            let mut distance = 0.0;
+
+            while distance <= length {
+                let dot = Point::new(center.x + distance * cos, center.y + distance * sin);
+                if bounds.contains(dot) {
+                    iced::advanced::Renderer::fill_quad(
+                        renderer,
+                        Quad {
+                            bounds: Rectangle {
+                                x: dot.x - DOT_SIZE / 2.0,
+                                y: dot.y - DOT_SIZE / 2.0,
+                                width: DOT_SIZE,
+                                height: DOT_SIZE,
+                            },
+                            border: Default::default(),
+                            shadow: Default::default(),
+                        },
+                        Color::from_rgba8(0, 128, 255, 0.6),
+                    );
+                }
+
+                distance += DOT_SPACING;
+            }
+        }
+    }
+
+    /// Tells whether the cursor is over the split view divider.
+    fn is_over_divider(&self, bounds: Rectangle, cursor: Cursor) -> bool {
+        match (self.split, cursor.position_over(bounds)) {
+            (Some(split), Some(position)) => {
+                (position.x - (bounds.x + bounds.width * split)).abs() <= 5.0
+            }
+            _ => false,
+        }
+    }
 }
 
 impl<'a> Widget<CanvasMessage, Theme, Renderer> for CanvasVessel<'a> {
@@ -607,7 +2122,6 @@ impl<'a> Widget<CanvasMessage, Theme, Renderer> for CanvasVessel<'a> {
         cursor: Cursor,
         viewport: &Rectangle,
     ) {
-        let mut children = layout.children();
         let bounds = layout.bounds();
 
         iced::advanced::Renderer::fill_quad(
@@ -620,36 +2134,94 @@ impl<'a> Widget<CanvasMessage, Theme, Renderer> for CanvasVessel<'a> {
             Color::WHITE,
         );
 
-        for (layer, index) in self.layer_order.iter().zip(0..self.layers.len()) {
-            if self.states.get(&layer).unwrap().is_visible() {
-                self.layers[&layer].draw(
-                    &state.children[index],
+        if let Some(split) = self.split {
+            let divider = bounds.x + bounds.width * split;
+            let saved_bounds = Rectangle {
+                width: divider - bounds.x,
+                ..bounds
+            };
+            let current_bounds = Rectangle {
+                x: divider,
+                width: bounds.x + bounds.width - divider,
+                ..bounds
+            };
+
+            iced::advanced::Renderer::with_layer(renderer, saved_bounds, |renderer| {
+                self.draw_layers(
+                    &self.saved_layers,
+                    state,
                     renderer,
                     theme,
                     style,
-                    children
-                        .next()
-                        .expect(&*format!("Canvas needs to have at least {} layers.", index)),
+                    layout,
                     cursor,
                     viewport,
                 );
-            }
+            });
+            iced::advanced::Renderer::with_layer(renderer, current_bounds, |renderer| {
+                self.draw_layers(
+                    &self.layers,
+                    state,
+                    renderer,
+                    theme,
+                    style,
+                    layout,
+                    cursor,
+                    viewport,
+                );
+            });
+
+            iced::advanced::Renderer::fill_quad(
+                renderer,
+                Quad {
+                    bounds: Rectangle {
+                        x: divider - 1.0,
+                        width: 2.0,
+                        ..bounds
+                    },
+                    border: Default::default(),
+                    shadow: Default::default(),
+                },
+                Color::BLACK,
+            );
+        } else {
+            self.draw_layers(
+                &self.layers,
+                state,
+                renderer,
+                theme,
+                style,
+                layout,
+                cursor,
+                viewport,
+            );
+        }
+
+        if self.symmetry != SymmetryMode::None {
+            iced::advanced::Renderer::with_layer(renderer, bounds, |renderer| {
+                self.draw_symmetry_axes(renderer, bounds);
+            });
+        }
+
+        let dragged_guide = state.state.downcast_ref::<VesselState>().dragged_guide;
+        iced::advanced::Renderer::with_layer(renderer, bounds, |renderer| {
+            self.draw_guides(renderer, bounds, dragged_guide);
+            self.draw_rulers(renderer, bounds);
+        });
+
+        if !self.collab_cursors.is_empty() {
+            iced::advanced::Renderer::with_layer(renderer, bounds, |renderer| {
+                self.draw_collab_cursors(renderer, bounds);
+            });
         }
     }
 
     fn tag(&self) -> tree::Tag {
-        struct Tag<T>(T);
-        tree::Tag::of::<
-            Tag<<LayerVessel<'_> as canvas::Program<CanvasMessage, Theme, Renderer>>::State>,
-        >()
+        tree::Tag::of::<VesselState>()
     }
 
     fn state(&self) -> tree::State {
-        tree::State::new(<LayerVessel<'_> as canvas::Program<
-            CanvasMessage,
-            Theme,
-            Renderer,
-        >>::State::default())
+        tree::State::new(VesselState::default())
     }
 
     fn children(&self) -> Vec<Tree> {
@@ -686,32 +2258,49 @@ impl<'a> Widget<CanvasMessage, Theme, Renderer> for CanvasVessel<'a> {
             return Status::Ignored;
         }
 
-        let layer = self.layers.get_mut(&self.current_layer).unwrap();
-        let mut children = layout.children();
-        let binding = Node::default();
-        let mut layout = Layout::new(&binding);
-        let mut index = 0;
+        if let Event::Touch(event) = event {
+            return self.on_touch(state, event, layout, renderer, clipboard, shell, viewport);
+        }
 
-        for id in self.layer_order {
-            layout = children.next().expect(&*format!(
-                "Canvas needs to have at least {} children.",
-                self.current_layer
-            ));
-            if *id == self.current_layer {
-                break;
+        if let Event::Mouse(mouse::Event::CursorMoved { .. }) = event {
+            if let Some(position) = cursor.position_in(layout.bounds()) {
+                shell.publish(CanvasMessage::CursorMoved(position));
             }
-            index += 1;
         }
 
-        layer.on_event(
-            &mut state.children[index],
-            event,
-            layout,
-            cursor,
-            renderer,
-            clipboard,
-            shell,
-            viewport,
+        if self.on_guide_event(state, &event, layout.bounds(), cursor, shell) == Status::Captured {
+            return Status::Captured;
+        }
+
+        if self.split.is_some() {
+            let bounds = layout.bounds();
+            let split_state = state.state.downcast_mut::<VesselState>();
+
+            match event {
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                    if self.is_over_divider(bounds, cursor) =>
+                {
+                    split_state.dragging = true;
+                    return Status::Captured;
+                }
+                Event::Mouse(mouse::Event::CursorMoved { position }) if split_state.dragging => {
+                    shell.publish(CanvasMessage::MoveSplit(
+                        ((position.x - bounds.x) / bounds.width).clamp(0.0, 1.0),
+                    ));
+                    return Status::Captured;
+                }
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                    if split_state.dragging =>
+                {
+                    split_state.dragging = false;
+                    return Status::Captured;
+                }
+                _ => {}
+            }
+        }
+
+        self.on_layer_event(
+            state, event, layout, cursor, renderer, clipboard, shell, viewport,
         )
     }
 
@@ -727,22 +2316,37 @@ impl<'a> Widget<CanvasMessage, Theme, Renderer> for CanvasVessel<'a> {
             return Interaction::default();
         }
 
-        let mut children = layout.children();
-        let binding = Node::default();
-        let mut layout = Layout::new(&binding);
-        let mut index = 0;
+        if self.is_over_divider(layout.bounds(), cursor)
+            || state.state.downcast_ref::<VesselState>().dragging
+        {
+            return Interaction::ResizingHorizontally;
+        }
 
-        for id in self.layer_order {
-            layout = children.next().expect(&*format!(
-                "Canvas needs to have at least {} children.",
-                self.current_layer
-            ));
-            if *id == self.current_layer {
-                break;
-            }
-            index += 1;
+        let bounds = layout.bounds();
+        let dragged_guide = state
+            .state
+            .downcast_ref::<VesselState>()
+            .dragged_guide
+            .map(|(_, guide)| guide);
+        let hovered_guide = cursor.position_over(bounds).and_then(|position| {
+            self.get_guide(bounds, position)
+                .map(|index| self.guides[index])
+        });
+        match dragged_guide.or(hovered_guide) {
+            Some(GuideAxis::Horizontal(_)) => return Interaction::ResizingVertically,
+            Some(GuideAxis::Vertical(_)) => return Interaction::ResizingHorizontally,
+            None => {}
+        }
+        if cursor
+            .position_over(bounds)
+            .is_some_and(|position| self.is_over_ruler(bounds, position))
+        {
+            return Interaction::Pointer;
         }
 
+        let cursor = self.get_drawing_cursor(layout.bounds(), cursor);
+        let (layout, index) = self.get_current_layer(layout);
+
         self.layers[&self.current_layer].mouse_interaction(
             &state.children[index],
             layout,
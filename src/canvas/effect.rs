@@ -0,0 +1,195 @@
+use crate::canvas::tools::smudge;
+use crate::utils::encoder;
+use crate::utils::errors::Error;
+use iced::widget::canvas::Frame;
+use iced::{Color, Point, Size, Vector};
+use std::convert::identity;
+use std::fmt::{Display, Formatter};
+use tokio::task;
+
+/// The number of pixels of the drawing covered by a single pixel of an [EffectSurface].
+/// Blurred copies are smooth, so they are computed and drawn at a lower resolution.
+const SURFACE_SCALE: usize = 4;
+
+/// The kinds of effects that can be applied to a [Layer](crate::canvas::layer::Layer).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayerEffectKind {
+    /// A blurred silhouette of the layer, in the color of the effect.
+    Shadow,
+
+    /// A bright, blurred silhouette of the layer around its contents.
+    Glow,
+
+    /// A blurred copy of the layer, keeping its colors.
+    Blur,
+}
+
+impl LayerEffectKind {
+    /// The list of all effect kinds.
+    pub const ALL: [LayerEffectKind; 3] = [
+        LayerEffectKind::Shadow,
+        LayerEffectKind::Glow,
+        LayerEffectKind::Blur,
+    ];
+}
+
+impl Display for LayerEffectKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LayerEffectKind::Shadow => "Shadow",
+                LayerEffectKind::Glow => "Glow",
+                LayerEffectKind::Blur => "Blur",
+            }
+        )
+    }
+}
+
+/// An effect drawn beneath the contents of a [Layer](crate::canvas::layer::Layer).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerEffect {
+    /// The kind of the effect.
+    pub kind: LayerEffectKind,
+
+    /// The color of the effect. Ignored by [blurs](LayerEffectKind::Blur).
+    pub color: Color,
+
+    /// The radius of the blur.
+    pub radius: f32,
+
+    /// The offset of the blurred copy from the layer.
+    pub offset: Vector,
+}
+
+impl LayerEffect {
+    /// Returns the effect of the given kind, with its default settings.
+    pub fn new(kind: LayerEffectKind) -> Self {
+        match kind {
+            LayerEffectKind::Shadow => LayerEffect {
+                kind,
+                color: Color::from_rgba(0.0, 0.0, 0.0, 0.6),
+                radius: 6.0,
+                offset: Vector::new(6.0, 6.0),
+            },
+            LayerEffectKind::Glow => LayerEffect {
+                kind,
+                color: Color::from_rgb8(255, 220, 80),
+                radius: 10.0,
+                offset: Vector::new(0.0, 0.0),
+            },
+            LayerEffectKind::Blur => LayerEffect {
+                kind,
+                color: Color::TRANSPARENT,
+                radius: 4.0,
+                offset: Vector::new(0.0, 0.0),
+            },
+        }
+    }
+
+    /// Returns the color of a blurred pixel, given with premultiplied channels between 0 and 255.
+    fn get_color(&self, pixel: [f32; 4]) -> Color {
+        let alpha = pixel[3] / 255.0;
+
+        match self.kind {
+            LayerEffectKind::Shadow => Color {
+                a: self.color.a * alpha,
+                ..self.color
+            },
+            LayerEffectKind::Glow => Color {
+                a: (self.color.a * alpha * 2.0).min(1.0),
+                ..self.color
+            },
+            LayerEffectKind::Blur => Color::from_rgba(
+                (pixel[0] / pixel[3]).min(1.0),
+                (pixel[1] / pixel[3]).min(1.0),
+                (pixel[2] / pixel[3]).min(1.0),
+                alpha,
+            ),
+        }
+    }
+}
+
+/// The blurred copy of a layer for a single [LayerEffect], ready to be drawn.
+#[derive(Debug, Clone, Default)]
+pub struct EffectSurface {
+    /// The top-left corners and colors of the visible pixels.
+    pixels: Vec<(Point, Color)>,
+
+    /// The side of a pixel on the drawing.
+    pixel_size: f32,
+}
+
+impl EffectSurface {
+    /// Draws the surface on the given [Frame].
+    pub fn add_to_frame(&self, frame: &mut Frame) {
+        let size = Size::new(self.pixel_size, self.pixel_size);
+
+        for (position, color) in &self.pixels {
+            frame.fill_rectangle(*position, size, *color);
+        }
+    }
+}
+
+/// Renders the given layer and computes the [EffectSurface] of every effect, in order.
+pub async fn render_effects(
+    layer: svg::Document,
+    effects: Vec<LayerEffect>,
+) -> Result<Vec<EffectSurface>, Error> {
+    task::spawn_blocking(move || {
+        let pixmap = encoder::render_svg(&*layer.to_string())?;
+        let (image_width, image_height) = (pixmap.width() as usize, pixmap.height() as usize);
+        let (width, height) = (
+            image_width.div_ceil(SURFACE_SCALE),
+            image_height.div_ceil(SURFACE_SCALE),
+        );
+
+        // The layer is averaged down to the surface resolution, keeping premultiplied channels.
+        let mut pixels = vec![[0.0; 4]; width * height];
+        for (index, pixel) in pixmap.pixels().iter().enumerate() {
+            let (x, y) = (index % image_width, index / image_width);
+            let cell = &mut pixels[(y / SURFACE_SCALE) * width + x / SURFACE_SCALE];
+
+            cell[0] += pixel.red() as f32;
+            cell[1] += pixel.green() as f32;
+            cell[2] += pixel.blue() as f32;
+            cell[3] += pixel.alpha() as f32;
+        }
+        let cell_area = (SURFACE_SCALE * SURFACE_SCALE) as f32;
+        for cell in pixels.iter_mut() {
+            for channel in cell.iter_mut() {
+                *channel /= cell_area;
+            }
+        }
+
+        Ok(effects
+            .iter()
+            .map(|effect| {
+                let kernel =
+                    smudge::gaussian_kernel((effect.radius / SURFACE_SCALE as f32).max(0.5));
+                let blurred = smudge::blur_pass(&pixels, width, height, &kernel, true);
+                let blurred = smudge::blur_pass(&blurred, width, height, &kernel, false);
+
+                EffectSurface {
+                    pixels: blurred
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, pixel)| pixel[3] >= 1.0)
+                        .map(|(index, pixel)| {
+                            let position = Point::new(
+                                ((index % width) * SURFACE_SCALE) as f32,
+                                ((index / width) * SURFACE_SCALE) as f32,
+                            ) + effect.offset;
+
+                            (position, effect.get_color(*pixel))
+                        })
+                        .collect(),
+                    pixel_size: SURFACE_SCALE as f32,
+                }
+            })
+            .collect())
+    })
+    .await
+    .map_or_else(|err| Err(err.to_string().into()), identity)
+}
@@ -0,0 +1,105 @@
+use crate::utils::serde::{Deserialize, Serialize};
+use iced::Point;
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document};
+
+/// A guide line across the drawing, which the cursor snaps to while drawing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuideAxis {
+    /// A horizontal line, at the given distance from the top edge of the drawing.
+    Horizontal(f32),
+
+    /// A vertical line, at the given distance from the left edge of the drawing.
+    Vertical(f32),
+}
+
+impl GuideAxis {
+    /// Returns the distance of the guide from the top or left edge of the drawing.
+    pub fn get_position(&self) -> f32 {
+        match self {
+            GuideAxis::Horizontal(position) | GuideAxis::Vertical(position) => *position,
+        }
+    }
+
+    /// Returns a guide along the same axis, crossing the given point of the drawing.
+    pub fn through(&self, point: Point) -> Self {
+        match self {
+            GuideAxis::Horizontal(_) => GuideAxis::Horizontal(point.y),
+            GuideAxis::Vertical(_) => GuideAxis::Vertical(point.x),
+        }
+    }
+
+    /// Moves the point onto the guide, if it is closer than the given distance.
+    pub fn snap(&self, point: Point, distance: f32) -> Point {
+        match self {
+            GuideAxis::Horizontal(y) if (point.y - y).abs() <= distance => Point::new(point.x, *y),
+            GuideAxis::Vertical(x) if (point.x - x).abs() <= distance => Point::new(*x, point.y),
+            _ => point,
+        }
+    }
+
+    /// Returns the name of the axis, as it is stored.
+    fn get_axis_name(&self) -> &'static str {
+        match self {
+            GuideAxis::Horizontal(_) => "horizontal",
+            GuideAxis::Vertical(_) => "vertical",
+        }
+    }
+}
+
+impl Serialize<Document> for GuideAxis {
+    fn serialize(&self) -> Document {
+        doc! {
+            "axis": self.get_axis_name(),
+            "position": self.get_position(),
+        }
+    }
+}
+
+impl Deserialize<Document> for GuideAxis {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut position = 0.0;
+
+        if let Some(Bson::Double(value)) = document.get("position") {
+            position = *value as f32;
+        }
+
+        match document.get_str("axis") {
+            Ok("vertical") => GuideAxis::Vertical(position),
+            _ => GuideAxis::Horizontal(position),
+        }
+    }
+}
+
+impl Serialize<Object> for GuideAxis {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert("axis", JsonValue::String(self.get_axis_name().into()));
+        data.insert("position", JsonValue::Number(self.get_position().into()));
+
+        data
+    }
+}
+
+impl Deserialize<Object> for GuideAxis {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut position = 0.0;
+
+        if let Some(JsonValue::Number(value)) = document.get("position") {
+            position = f32::from(*value);
+        }
+
+        match document.get("axis").and_then(JsonValue::as_str) {
+            Some("vertical") => GuideAxis::Vertical(position),
+            _ => GuideAxis::Horizontal(position),
+        }
+    }
+}
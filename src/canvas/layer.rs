@@ -1,3 +1,6 @@
+use crate::canvas::canvas::Snapshot;
+use crate::canvas::effect::{EffectSurface, LayerEffect};
+use crate::canvas::guide::GuideAxis;
 use crate::canvas::style::{Style, StyleUpdate};
 use crate::canvas::tool::{Pending, Tool};
 use crate::scene::Message;
@@ -7,7 +10,7 @@ use iced::advanced::mouse;
 use iced::keyboard::Key;
 use iced::mouse::Cursor;
 use iced::widget::canvas::{self};
-use iced::{event, keyboard, Rectangle, Renderer};
+use iced::{event, keyboard, Point, Rectangle, Renderer, Vector};
 use json::JsonValue;
 use mongodb::bson::Uuid;
 use std::sync::Arc;
@@ -17,6 +20,9 @@ pub struct Layer {
     /// The cache memory of the [Layer].
     cache: canvas::Cache,
 
+    /// The cache memory of the [Layer] as it was last saved; used by the split view.
+    saved_cache: canvas::Cache,
+
     /// The tools drawn on the [Layer].
     tools: Vec<Arc<dyn Tool>>,
 
@@ -28,6 +34,12 @@ pub struct Layer {
 
     /// Tells whether the [Layer] is visible.
     visible: bool,
+
+    /// The [effects](LayerEffect) drawn beneath the [Layer].
+    effects: Vec<LayerEffect>,
+
+    /// The rendered [effects](LayerEffect), in the same order.
+    effect_surfaces: Vec<EffectSurface>,
 }
 
 impl Layer {
@@ -39,13 +51,18 @@ impl Layer {
     }
 
     pub fn clear_cache(&self) {
-        self.cache.clear()
+        self.cache.clear();
+        self.saved_cache.clear();
     }
 
     pub fn get_cache(&self) -> &canvas::Cache {
         &self.cache
     }
 
+    pub fn get_saved_cache(&self) -> &canvas::Cache {
+        &self.saved_cache
+    }
+
     pub fn get_tools(&self) -> &[Arc<dyn Tool>] {
         self.tools.as_slice()
     }
@@ -58,6 +75,10 @@ impl Layer {
         &self.name
     }
 
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
     pub fn get_new_name(&self) -> &Option<String> {
         &self.new_name
     }
@@ -89,6 +110,34 @@ impl Layer {
     pub fn set_new_name(&mut self, new_name: impl Into<Option<String>>) {
         self.new_name = new_name.into();
     }
+
+    pub fn get_effects(&self) -> &[LayerEffect] {
+        self.effects.as_slice()
+    }
+
+    pub fn add_effect(&mut self, effect: LayerEffect) {
+        self.effects.push(effect);
+    }
+
+    /// Removes the effect at the given position, along with its rendered surface.
+    pub fn remove_effect(&mut self, index: usize) {
+        if index < self.effects.len() {
+            self.effects.remove(index);
+        }
+        if index < self.effect_surfaces.len() {
+            self.effect_surfaces.remove(index);
+        }
+    }
+
+    pub fn get_effect_surfaces(&self) -> &[EffectSurface] {
+        self.effect_surfaces.as_slice()
+    }
+
+    /// Sets the rendered effects, and redraws the [Layer] with them.
+    pub fn set_effect_surfaces(&mut self, surfaces: Vec<EffectSurface>) {
+        self.effect_surfaces = surfaces;
+        self.clear_cache();
+    }
 }
 
 unsafe impl Send for Layer {}
@@ -98,10 +147,13 @@ impl Default for Layer {
     fn default() -> Self {
         Layer {
             cache: Default::default(),
+            saved_cache: Default::default(),
             tools: vec![],
             name: "New layer".to_string(),
             new_name: None,
             visible: true,
+            effects: vec![],
+            effect_surfaces: vec![],
         }
     }
 }
@@ -114,6 +166,9 @@ pub struct LayerVessel<'a> {
     /// The [tools](Tool) stored on the [LayerVessel].
     tools: &'a [Arc<dyn Tool>],
 
+    /// The rendered effects drawn beneath the [tools](Tool).
+    effects: &'a [EffectSurface],
+
     /// The currently selected [Tool].
     current_tool: &'a Box<dyn Pending>,
 
@@ -128,6 +183,7 @@ impl<'a> LayerVessel<'a> {
     pub fn new(
         state: &'a canvas::Cache,
         tools: &'a [Arc<dyn Tool>],
+        effects: &'a [EffectSurface],
         current_tool: &'a Box<dyn Pending>,
         style: &'a Style,
         active: bool,
@@ -135,6 +191,7 @@ impl<'a> LayerVessel<'a> {
         LayerVessel {
             state,
             tools,
+            effects,
             current_tool,
             style,
             active,
@@ -165,16 +222,8 @@ impl<'a> canvas::Program<CanvasMessage, Theme, Renderer> for LayerVessel<'a> {
                 } => {
                     let value: &str = key.as_str();
 
-                    if (value == "Z" || value == "z") && modifiers == keyboard::Modifiers::CTRL {
-                        return (event::Status::Captured, Some(CanvasMessage::Undo));
-                    } else if (value == "S" || value == "s")
-                        && modifiers == keyboard::Modifiers::CTRL
-                    {
+                    if (value == "S" || value == "s") && modifiers == keyboard::Modifiers::CTRL {
                         return (event::Status::Captured, Some(CanvasMessage::Save));
-                    } else if (value == "Y" || value == "y")
-                        && modifiers == keyboard::Modifiers::CTRL
-                    {
-                        return (event::Status::Captured, Some(CanvasMessage::Redo));
                     } else {
                         return (event::Status::Ignored, None);
                     }
@@ -215,6 +264,9 @@ impl<'a> canvas::Program<CanvasMessage, Theme, Renderer> for LayerVessel<'a> {
         cursor: Cursor,
     ) -> Vec<canvas::Geometry> {
         let content = self.state.draw(renderer, bounds.size(), |frame| {
+            for effect in self.effects {
+                effect.add_to_frame(frame);
+            }
             for tool in self.tools {
                 tool.add_to_frame(frame);
             }
@@ -260,6 +312,34 @@ pub enum CanvasMessage {
     /// Adds a [Tool] to the active [Layer].
     UseTool(Arc<dyn Tool>),
 
+    /// Smudges the drawing along the given points, with the given strength.
+    Smudge(Vec<Point>, f32),
+
+    /// Lets the user choose an image to store as a new stamp, and selects it.
+    ImportStamp,
+
+    /// Places the stamp with the given id at the given position, with the given scale and
+    /// rotation.
+    PlaceStamp(Uuid, Point, f32, f32),
+
+    /// Lets the user choose a raster image to place on the current [Layer].
+    ImportImage,
+
+    /// Adds a [Tool] placed and saved by a collaborator to the given [Layer].
+    UseRemoteTool(Arc<dyn Tool>, Uuid),
+
+    /// Adds a [LayerEffect] to the given [Layer].
+    AddLayerEffect(Uuid, LayerEffect),
+
+    /// Removes the [LayerEffect] at the given position from the given [Layer].
+    RemoveLayerEffect(Uuid, usize),
+
+    /// Sets the rendered effects of the given [Layer].
+    LayerEffectsRendered(Uuid, Vec<EffectSurface>),
+
+    /// Expands or collapses the effects panel.
+    ToggleEffectsPanel,
+
     /// Changed the [Tool] used for drawing.
     ChangeTool(Box<dyn Pending>),
 
@@ -284,24 +364,113 @@ pub enum CanvasMessage {
     /// Deletes a [Layer].
     RemoveLayer(Uuid),
 
+    /// Moves the [Layer] at the first position in the layer order to the second position.
+    MoveLayer(usize, usize),
+
     /// Saves the state of the drawing.
     Save,
 
-    /// Triggered when the drawing is successfully saved.
-    Saved,
+    /// Triggered when the drawing is successfully saved; tells whether it was saved
+    /// automatically.
+    Saved { automatic: bool },
+
+    /// Loads the drawing again from its file, after the file was changed by another program.
+    ReloadFromDisk,
 
     /// Triggered when the drawing data is successfully loaded.
     Loaded {
         layers: Vec<(Uuid, String)>,
         tools: Vec<(Arc<dyn Tool>, Uuid)>,
         json_tools: Option<Vec<JsonValue>>,
+        size: Option<(u32, u32)>,
+        guides: Vec<GuideAxis>,
     },
 
-    /// Removes the last added [Tool].
+    /// Resizes the drawing area to the given dimensions.
+    Resize { width: u32, height: u32 },
+
+    /// Reverses the last change recorded in the global undo history.
     Undo,
 
-    /// Adds the last removed [Tool].
+    /// Reapplies the last change reversed by [Undo](CanvasMessage::Undo).
     Redo,
+
+    /// Undoes or redoes [tools](Tool) until the given number of them are placed.
+    RestoreToStep(usize),
+
+    /// Toggles the view comparing the drawing to its last saved state.
+    ToggleSplitView,
+
+    /// Moves the divider of the split view to the given ratio of the width.
+    MoveSplit(f32),
+
+    /// Multiplies the zoom level of the drawing area by the given factor, keeping the given
+    /// point, relative to the drawing area, in place.
+    Zoom(f32, Point),
+
+    /// Moves the drawing by the given offset.
+    Pan(Vector),
+
+    /// Adds a guide line to the drawing.
+    AddGuide(GuideAxis),
+
+    /// Moves the guide line at the given index.
+    MoveGuide(usize, GuideAxis),
+
+    /// Removes the guide line at the given index.
+    RemoveGuide(usize),
+
+    /// Triggered when the cursor moves over the drawing area, at the given position relative
+    /// to it.
+    CursorMoved(Point),
+
+    /// Stores the current state of the drawing as a [Snapshot] with the given name.
+    CreateSnapshot(String),
+
+    /// Triggered when a [Snapshot] was successfully created.
+    SnapshotCreated(Snapshot),
+
+    /// Loads the list of [snapshots](Snapshot) of the drawing.
+    LoadSnapshots,
+
+    /// Triggered when the list of [snapshots](Snapshot) has been loaded.
+    LoadedSnapshots(Vec<Snapshot>),
+
+    /// Restores the drawing to the state stored in the [Snapshot] with the given id.
+    RestoreSnapshot(Uuid),
+
+    /// Triggered periodically when auto-saving is enabled; saves the drawing if it has unsaved
+    /// changes.
+    AutoSave,
+}
+
+impl CanvasMessage {
+    /// Tells whether the message modifies the drawing.
+    pub fn is_modifying(&self) -> bool {
+        match self {
+            CanvasMessage::ToggleEditName
+            | CanvasMessage::SetNewName(_)
+            | CanvasMessage::UseTool(_)
+            | CanvasMessage::AddLayerEffect(_, _)
+            | CanvasMessage::RemoveLayerEffect(_, _)
+            | CanvasMessage::AddLayer
+            | CanvasMessage::ToggleEditLayerName(_)
+            | CanvasMessage::UpdateLayerName(_, _)
+            | CanvasMessage::RemoveLayer(_)
+            | CanvasMessage::MoveLayer(_, _)
+            | CanvasMessage::AddGuide(_)
+            | CanvasMessage::MoveGuide(_, _)
+            | CanvasMessage::RemoveGuide(_)
+            | CanvasMessage::Save
+            | CanvasMessage::Resize { .. }
+            | CanvasMessage::Undo
+            | CanvasMessage::Redo
+            | CanvasMessage::RestoreToStep(_)
+            | CanvasMessage::CreateSnapshot(_)
+            | CanvasMessage::RestoreSnapshot(_) => true,
+            _ => false,
+        }
+    }
 }
 
 impl Into<Message> for CanvasMessage {
@@ -1,6 +1,9 @@
 pub mod canvas;
-pub(crate) mod layer;
+pub mod effect;
+pub mod guide;
+pub mod layer;
 pub mod style;
 pub mod svg;
 pub mod tool;
 pub mod tools;
+pub mod undo;
@@ -1,9 +1,13 @@
+use crate::canvas::tools::brushes::texture::{TexturePattern, TextureSettings};
+use crate::canvas::tools::gradient::GradientKind;
+use crate::canvas::tools::smudge::DEFAULT_SMUDGE_STRENGTH;
+use crate::canvas::tools::star::{DEFAULT_INNER_RADIUS_RATIO, DEFAULT_STAR_POINTS};
 use crate::scene::Message;
 use crate::utils::serde::{Deserialize, Serialize};
 use crate::utils::theme::Theme;
 use crate::widgets::ColorPicker;
 use iced::alignment::Horizontal;
-use iced::widget::{Button, Column, Slider, Text};
+use iced::widget::{Button, Column, Row, Slider, Text};
 use iced::{Color, Command, Element, Length, Renderer};
 use json::object::Object;
 use json::JsonValue;
@@ -17,6 +21,48 @@ use mongodb::bson::{doc, Bson, Document};
 pub struct Style {
     pub(crate) stroke: Option<(f32, Color, bool, bool)>,
     pub(crate) fill: Option<(Color, bool)>,
+    pub(crate) symmetry: SymmetryMode,
+    pub(crate) pressure_simulation: bool,
+    pub(crate) smudge: Option<(f32, bool)>,
+    pub(crate) texture: Option<(TextureSettings, bool)>,
+    pub(crate) star: Option<(usize, f32, bool)>,
+    pub(crate) gradient: Option<(GradientKind, Color, Color, bool)>,
+}
+
+/// The ways in which the used [tools](crate::canvas::tool::Tool) are copied around the center of
+/// the drawing area.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SymmetryMode {
+    /// The tools are not copied.
+    #[default]
+    None,
+
+    /// The tools are mirrored left to right, across the vertical axis.
+    Horizontal,
+
+    /// The tools are mirrored top to bottom, across the horizontal axis.
+    Vertical,
+
+    /// The tools are rotated the given amount of times, at equal angles.
+    Radial(u8),
+}
+
+impl SymmetryMode {
+    /// The default amount of copies for the radial symmetry.
+    const DEFAULT_FOLDS: u8 = 6;
+
+    /// The list of symmetry modes that can be picked, with their labels.
+    const ALL: [(&'static str, SymmetryMode); 4] = [
+        ("None", SymmetryMode::None),
+        ("Horizontal", SymmetryMode::Horizontal),
+        ("Vertical", SymmetryMode::Vertical),
+        ("Radial", SymmetryMode::Radial(SymmetryMode::DEFAULT_FOLDS)),
+    ];
+
+    /// Tells whether the two modes are of the same kind, ignoring the amount of radial copies.
+    fn same_kind(&self, other: &SymmetryMode) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
 }
 
 impl Style {
@@ -48,13 +94,34 @@ impl Style {
         })
     }
 
+    /// Returns the strength of the smudge, used as the radius of its blur.
+    pub fn get_smudge_strength(&self) -> f32 {
+        self.smudge
+            .map_or(DEFAULT_SMUDGE_STRENGTH, |(strength, _)| strength)
+    }
+
+    /// Returns the number of points and the inner radius ratio of a star.
+    pub fn get_star(&self) -> (usize, f32) {
+        self.star.map_or(
+            (DEFAULT_STAR_POINTS, DEFAULT_INNER_RADIUS_RATIO),
+            |(points, ratio, _)| (points, ratio),
+        )
+    }
+
+    /// Returns the kind and the start and end colors of a gradient.
+    pub fn get_gradient(&self) -> (GradientKind, Color, Color) {
+        self.gradient.map_or(
+            (GradientKind::Linear, Color::BLACK, Color::WHITE),
+            |(kind, start_color, end_color, _)| (kind, start_color, end_color),
+        )
+    }
+
     /// Returns the transparency of the fill.
     pub fn get_fill_alpha(&self) -> f32 {
         self.fill.map_or(0.0, |(color, _)| color.a)
     }
 
     /// Modifies the stroke width of the [pending tool](crate::canvas::tool::Pending).
-    #[allow(dead_code)]
     pub(crate) fn stroke_width(mut self, stroke_width: impl Into<f32>) -> Self {
         if let Some((_, color, v1, v2)) = self.stroke {
             self.stroke = Some((stroke_width.into(), color, v1, v2));
@@ -122,6 +189,94 @@ impl Style {
                     self.fill = Some((color, visible));
                 }
             }
+            StyleUpdate::SetSymmetry(symmetry) => {
+                self.symmetry = symmetry;
+            }
+            StyleUpdate::TogglePressureSimulation => {
+                self.pressure_simulation = !self.pressure_simulation;
+            }
+            StyleUpdate::ToggleSmudge => {
+                if let Some((strength, visible)) = self.smudge {
+                    self.smudge = Some((strength, !visible));
+                }
+            }
+            StyleUpdate::SmudgeStrength(strength) => {
+                if let Some((_, visible)) = self.smudge {
+                    self.smudge = Some((strength, visible));
+                }
+            }
+            StyleUpdate::ToggleGradient => {
+                if let Some((kind, start_color, end_color, visible)) = self.gradient {
+                    self.gradient = Some((kind, start_color, end_color, !visible));
+                }
+            }
+            StyleUpdate::GradientKind(kind) => {
+                if let Some((_, start_color, end_color, visible)) = self.gradient {
+                    self.gradient = Some((kind, start_color, end_color, visible));
+                }
+            }
+            StyleUpdate::GradientStartColor(start_color) => {
+                if let Some((kind, _, end_color, visible)) = self.gradient {
+                    self.gradient = Some((kind, start_color, end_color, visible));
+                }
+            }
+            StyleUpdate::GradientEndColor(end_color) => {
+                if let Some((kind, start_color, _, visible)) = self.gradient {
+                    self.gradient = Some((kind, start_color, end_color, visible));
+                }
+            }
+            StyleUpdate::ToggleStar => {
+                if let Some((points, ratio, visible)) = self.star {
+                    self.star = Some((points, ratio, !visible));
+                }
+            }
+            StyleUpdate::StarPoints(points) => {
+                if let Some((_, ratio, visible)) = self.star {
+                    self.star = Some((points, ratio, visible));
+                }
+            }
+            StyleUpdate::StarRatio(ratio) => {
+                if let Some((points, _, visible)) = self.star {
+                    self.star = Some((points, ratio, visible));
+                }
+            }
+            StyleUpdate::ToggleTexture => {
+                if let Some((settings, visible)) = self.texture {
+                    self.texture = Some((settings, !visible));
+                }
+            }
+            StyleUpdate::TexturePattern(pattern) => {
+                if let Some((settings, visible)) = self.texture {
+                    self.texture = Some((
+                        TextureSettings {
+                            pattern,
+                            ..settings
+                        },
+                        visible,
+                    ));
+                }
+            }
+            StyleUpdate::TextureDensity(density) => {
+                if let Some((settings, visible)) = self.texture {
+                    self.texture = Some((
+                        TextureSettings {
+                            density,
+                            ..settings
+                        },
+                        visible,
+                    ));
+                }
+            }
+            StyleUpdate::TextureAngle(angle) => {
+                if let Some((settings, visible)) = self.texture {
+                    self.texture = Some((TextureSettings { angle, ..settings }, visible));
+                }
+            }
+            StyleUpdate::TextureJitter(jitter) => {
+                if let Some((settings, visible)) = self.texture {
+                    self.texture = Some((TextureSettings { jitter, ..settings }, visible));
+                }
+            }
         }
 
         Command::none()
@@ -163,6 +318,16 @@ impl Style {
                     ColorPicker::new(color.r, color.g, color.b, color.a, StyleUpdate::StrokeColor);
                 column.push(picker.into());
             }
+
+            column.push(
+                Button::new(
+                    Text::new("Pressure simulation").horizontal_alignment(Horizontal::Center),
+                )
+                .on_press(StyleUpdate::TogglePressureSimulation)
+                .style(get_button_style(self.pressure_simulation))
+                .width(Length::Fill)
+                .into(),
+            );
         }
 
         if let Some((color, visibility)) = self.fill {
@@ -181,6 +346,145 @@ impl Style {
             }
         }
 
+        if let Some((strength, visibility)) = self.smudge {
+            column.push(
+                Button::new(Text::new("Smudge strength").horizontal_alignment(Horizontal::Center))
+                    .on_press(StyleUpdate::ToggleSmudge)
+                    .style(get_button_style(visibility))
+                    .width(Length::Fill)
+                    .into(),
+            );
+            if visibility {
+                column.push(Slider::new(1.0..=20.0, strength, StyleUpdate::SmudgeStrength).into());
+            }
+        }
+
+        if let Some((kind, start_color, end_color, visibility)) = self.gradient {
+            column.push(
+                Button::new(Text::new("Gradient").horizontal_alignment(Horizontal::Center))
+                    .on_press(StyleUpdate::ToggleGradient)
+                    .style(get_button_style(visibility))
+                    .width(Length::Fill)
+                    .into(),
+            );
+            if visibility {
+                column.push(
+                    Row::with_children(GradientKind::ALL.map(|gradient_kind| {
+                        Button::new(Text::new(gradient_kind.to_string()).size(14.0))
+                            .on_press(StyleUpdate::GradientKind(gradient_kind))
+                            .style(get_button_style(kind == gradient_kind))
+                            .into()
+                    }))
+                    .spacing(5.0)
+                    .into(),
+                );
+                column.push(Text::new("Start color").into());
+                column.push(
+                    ColorPicker::new(
+                        start_color.r,
+                        start_color.g,
+                        start_color.b,
+                        start_color.a,
+                        StyleUpdate::GradientStartColor,
+                    )
+                    .into(),
+                );
+                column.push(Text::new("End color").into());
+                column.push(
+                    ColorPicker::new(
+                        end_color.r,
+                        end_color.g,
+                        end_color.b,
+                        end_color.a,
+                        StyleUpdate::GradientEndColor,
+                    )
+                    .into(),
+                );
+            }
+        }
+
+        if let Some((points, ratio, visibility)) = self.star {
+            column.push(
+                Button::new(Text::new("Star shape").horizontal_alignment(Horizontal::Center))
+                    .on_press(StyleUpdate::ToggleStar)
+                    .style(get_button_style(visibility))
+                    .width(Length::Fill)
+                    .into(),
+            );
+            if visibility {
+                column.push(Text::new(format!("Points: {}", points)).into());
+                column.push(
+                    Slider::new(3..=20, points as u8, |points| {
+                        StyleUpdate::StarPoints(points as usize)
+                    })
+                    .into(),
+                );
+                column.push(Text::new(format!("Inner radius: {:.2}", ratio)).into());
+                column.push(
+                    Slider::new(0.1..=0.95, ratio, StyleUpdate::StarRatio)
+                        .step(0.05)
+                        .into(),
+                );
+            }
+        }
+
+        if let Some((settings, visibility)) = self.texture {
+            column.push(
+                Button::new(Text::new("Texture").horizontal_alignment(Horizontal::Center))
+                    .on_press(StyleUpdate::ToggleTexture)
+                    .style(get_button_style(visibility))
+                    .width(Length::Fill)
+                    .into(),
+            );
+            if visibility {
+                column.push(
+                    Row::with_children(TexturePattern::ALL.map(|pattern| {
+                        Button::new(Text::new(pattern.to_string()).size(14.0))
+                            .on_press(StyleUpdate::TexturePattern(pattern))
+                            .style(get_button_style(settings.pattern == pattern))
+                            .into()
+                    }))
+                    .spacing(5.0)
+                    .into(),
+                );
+                column.push(Text::new(format!("Density: {:.0}", settings.density)).into());
+                column.push(
+                    Slider::new(1.0..=20.0, settings.density, StyleUpdate::TextureDensity).into(),
+                );
+                column.push(Text::new(format!("Angle: {:.0}°", settings.angle)).into());
+                column.push(
+                    Slider::new(0.0..=180.0, settings.angle, StyleUpdate::TextureAngle).into(),
+                );
+                column.push(Text::new(format!("Jitter: {:.2}", settings.jitter)).into());
+                column.push(
+                    Slider::new(0.0..=1.0, settings.jitter, StyleUpdate::TextureJitter)
+                        .step(0.05)
+                        .into(),
+                );
+            }
+        }
+
+        column.push(Text::new("Symmetry").into());
+        column.push(
+            Row::with_children(SymmetryMode::ALL.map(|(label, mode)| {
+                Button::new(Text::new(label).size(14.0))
+                    .on_press(StyleUpdate::SetSymmetry(mode))
+                    .style(get_button_style(self.symmetry.same_kind(&mode)))
+                    .into()
+            }))
+            .spacing(5.0)
+            .into(),
+        );
+        if let SymmetryMode::Radial(folds) = self.symmetry {
+            column.push(Text::new(format!("Copies: {}", folds)).into());
+            column.push(
+                Slider::new(2..=12, folds, |folds| {
+                    StyleUpdate::SetSymmetry(SymmetryMode::Radial(folds))
+                })
+                .into(),
+            );
+        }
+
         Column::with_children(column)
             .padding(8.0)
             .spacing(10.0)
@@ -197,6 +501,22 @@ pub enum StyleUpdate {
     StrokeColor(Color),
     ToggleFill,
     Fill(Color),
+    SetSymmetry(SymmetryMode),
+    TogglePressureSimulation,
+    ToggleSmudge,
+    SmudgeStrength(f32),
+    ToggleGradient,
+    GradientKind(GradientKind),
+    GradientStartColor(Color),
+    GradientEndColor(Color),
+    ToggleStar,
+    StarPoints(usize),
+    StarRatio(f32),
+    ToggleTexture,
+    TexturePattern(TexturePattern),
+    TextureDensity(f32),
+    TextureAngle(f32),
+    TextureJitter(f32),
 }
 
 impl Serialize<Document> for Style {
@@ -1,3 +1,4 @@
+use crate::scenes::data::debug::{self, LogEntry};
 use mongodb::bson::Uuid;
 use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
@@ -18,6 +19,12 @@ pub struct SVG {
 
     /// The order of the layers.
     layer_order: Vec<Uuid>,
+
+    /// The width of the drawing area.
+    width: u32,
+
+    /// The height of the drawing area.
+    height: u32,
 }
 
 impl SVG {
@@ -28,9 +35,22 @@ impl SVG {
             group_order: BTreeMap::new(),
             tool_count: 0,
             layer_order: layers.clone(),
+            width: 800,
+            height: 600,
         }
     }
 
+    /// Returns the dimensions of the drawing area.
+    pub fn get_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Sets the dimensions of the drawing area. Anything drawn outside of them is clipped.
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
     pub fn add_layer(&mut self, layer_id: Uuid) {
         self.tools.insert(layer_id, vec![]);
         self.layer_order.push(layer_id);
@@ -52,6 +72,34 @@ impl SVG {
         self.tool_count += 1;
     }
 
+    /// Inserts a tool serialized as a [Group] at the given position among all the tools, on the
+    /// given layer. The groups of each layer stay in the order of their positions.
+    pub fn insert_tool(&mut self, layer: &Uuid, position: usize, data: Group) {
+        let position = position.min(self.tool_count);
+
+        for groups in self.tools.values_mut() {
+            for (_, order) in groups.iter_mut() {
+                if *order >= position {
+                    *order += 1;
+                }
+            }
+        }
+
+        let groups = self.tools.get_mut(layer).unwrap();
+        let index = groups
+            .iter()
+            .position(|(_, order)| *order > position)
+            .unwrap_or(groups.len());
+        groups.insert(index, (data, position));
+
+        self.group_order = BTreeMap::from_iter(
+            self.tools
+                .iter()
+                .filter_map(|(layer, groups)| groups.last().map(|(_, order)| (*order, *layer))),
+        );
+        self.tool_count += 1;
+    }
+
     /// Returns the number of layers.
     pub fn get_cnt_layers(&self) -> usize {
         self.tools.len()
@@ -88,6 +136,12 @@ impl SVG {
         self.layer_order.retain(|id| *id != *layer_id);
     }
 
+    /// Moves the layer at the given position in the layer order to the new position.
+    pub fn move_layer(&mut self, from: usize, to: usize) {
+        let layer_id = self.layer_order.remove(from);
+        self.layer_order.insert(to, layer_id);
+    }
+
     /// Save the svg locally at the given path;
     pub fn save<T>(self, path: T)
     where
@@ -95,33 +149,75 @@ impl SVG {
     {
         let document: Document = self.as_document();
         if let Err(e) = svg::save(path, &document) {
-            println!("Error saving svg document: {}", e);
+            debug::log(LogEntry::new(
+                "Canvas",
+                format!("Error saving svg document: {}", e),
+                None,
+            ));
         }
     }
 
-    /// Convert the [SVG] to a [svg document](Document).
-    pub fn as_document(&self) -> Document {
-        let background = Rectangle::new()
+    /// Returns the white background of the drawing area.
+    fn background(&self) -> Rectangle {
+        Rectangle::new()
             .set("x", 0.0)
             .set("y", 0.0)
-            .set("width", 800.0)
-            .set("height", 600.0)
-            .set("fill", "white");
+            .set("width", self.width)
+            .set("height", self.height)
+            .set("fill", "white")
+    }
 
+    /// Returns the tools of the given layer as a single [Group].
+    fn layer_group(&self, layer: &Uuid) -> Group {
+        let mut group = Group::new();
+
+        for tool in self.tools.get(layer).unwrap() {
+            group = group.add(tool.0.clone());
+        }
+
+        group
+    }
+
+    /// Returns an empty [svg document](Document) with the size of the drawing area.
+    fn empty_document(&self) -> Document {
+        Document::new()
+            .set("width", self.width)
+            .set("height", self.height)
+            .set("viewBox", (0, 0, self.width, self.height))
+    }
+
+    /// Convert the [SVG] to a [svg document](Document).
+    pub fn as_document(&self) -> Document {
         let mut tools = Group::new().set("style", "isolation:isolate");
 
         for layer in &self.layer_order {
-            let mut group = Group::new();
-
-            for tool in self.tools.get(layer).unwrap() {
-                group = group.add(tool.0.clone());
-            }
-            tools = tools.add(group);
+            tools = tools.add(self.layer_group(layer));
         }
 
-        Document::new()
-            .set("viewBox", (0.0, 0.0, 800.0, 600.0))
-            .add(background)
-            .add(tools)
+        self.empty_document().add(self.background()).add(tools)
+    }
+
+    /// Converts the given layer to its own [svg document](Document), without the background.
+    pub fn layer_document(&self, layer: &Uuid) -> Document {
+        self.empty_document().add(self.layer_group(layer))
+    }
+
+    /// Converts every layer of the [SVG] to its own [svg document](Document), in order. Only
+    /// the first layer is drawn over the background.
+    pub fn as_layer_documents(&self) -> Vec<Document> {
+        self.layer_order
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                let document = self.empty_document();
+                let document = if index == 0 {
+                    document.add(self.background())
+                } else {
+                    document
+                };
+
+                document.add(self.layer_group(layer))
+            })
+            .collect()
     }
 }
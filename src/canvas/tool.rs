@@ -1,8 +1,12 @@
 use crate::canvas::layer::CanvasMessage;
 use crate::canvas::style::Style;
-use crate::canvas::tools::brushes::{airbrush::Airbrush, eraser::Eraser, pen::Pen, pencil::Pencil};
+use crate::canvas::tools::brushes::{
+    airbrush::Airbrush, eraser::Eraser, pen::Pen, pencil::Pencil, texture::TextureTool,
+};
 use crate::canvas::tools::{
-    circle::Circle, ellipse::Ellipse, line::Line, polygon::Polygon, rect::Rect, triangle::Triangle,
+    arc::{CircleArc, Pie},
+    bezier::BezierTool, circle::Circle, ellipse::Ellipse, gradient::GradientFillTool, line::Line, polygon::Polygon, raster::RasterTool, rect::Rect,
+    smudge::SmudgeTool, stamp::StampTool, star::StarTool, symmetry::SymmetryCopy, triangle::Triangle,
 };
 use crate::utils::serde::{Deserialize, Serialize};
 use iced::widget::canvas::{event, Event, Frame, Geometry};
@@ -48,12 +52,22 @@ pub fn get_deserialized(document: &Document) -> Option<(Arc<dyn Tool>, Uuid)> {
             "Rectangle" => Some((Arc::new(Rect::deserialize(document)), layer)),
             "Triangle" => Some((Arc::new(Triangle::deserialize(document)), layer)),
             "Polygon" => Some((Arc::new(Polygon::deserialize(document)), layer)),
+            "Star" => Some((Arc::new(StarTool::deserialize(document)), layer)),
+            "Bezier" => Some((Arc::new(BezierTool::deserialize(document)), layer)),
             "Circle" => Some((Arc::new(Circle::deserialize(document)), layer)),
             "Ellipse" => Some((Arc::new(Ellipse::deserialize(document)), layer)),
+            "Arc" => Some((Arc::new(CircleArc::deserialize(document)), layer)),
+            "Pie" => Some((Arc::new(Pie::deserialize(document)), layer)),
             "FountainPen" => Some((Arc::new(Pen::deserialize(document)), layer)),
             "Pencil" => Some((Arc::new(Pencil::deserialize(document)), layer)),
             "Airbrush" => Some((Arc::new(Airbrush::deserialize(document)), layer)),
             "Eraser" => Some((Arc::new(Eraser::deserialize(document)), layer)),
+            "Gradient" => Some((Arc::new(GradientFillTool::deserialize(document)), layer)),
+            "Texture" => Some((Arc::new(TextureTool::deserialize(document)), layer)),
+            "Smudge" => Some((Arc::new(SmudgeTool::deserialize(document)), layer)),
+            "Symmetry" => Some((Arc::new(SymmetryCopy::deserialize(document)), layer)),
+            "Stamp" => Some((Arc::new(StampTool::deserialize(document)), layer)),
+            "Raster" => Some((Arc::new(RasterTool::deserialize(document)), layer)),
             _ => None,
         }
     } else {
@@ -74,12 +88,22 @@ pub fn get_json(value: &Object) -> Option<(Arc<dyn Tool>, Uuid)> {
             "Rectangle" => Some((Arc::new(Rect::deserialize(value)), layer)),
             "Triangle" => Some((Arc::new(Triangle::deserialize(value)), layer)),
             "Polygon" => Some((Arc::new(Polygon::deserialize(value)), layer)),
+            "Star" => Some((Arc::new(StarTool::deserialize(value)), layer)),
+            "Bezier" => Some((Arc::new(BezierTool::deserialize(value)), layer)),
             "Circle" => Some((Arc::new(Circle::deserialize(value)), layer)),
             "Ellipse" => Some((Arc::new(Ellipse::deserialize(value)), layer)),
+            "Arc" => Some((Arc::new(CircleArc::deserialize(value)), layer)),
+            "Pie" => Some((Arc::new(Pie::deserialize(value)), layer)),
             "FountainPen" => Some((Arc::new(Pen::deserialize(value)), layer)),
             "Pencil" => Some((Arc::new(Pencil::deserialize(value)), layer)),
             "Airbrush" => Some((Arc::new(Airbrush::deserialize(value)), layer)),
             "Eraser" => Some((Arc::new(Eraser::deserialize(value)), layer)),
+            "Gradient" => Some((Arc::new(GradientFillTool::deserialize(value)), layer)),
+            "Texture" => Some((Arc::new(TextureTool::deserialize(value)), layer)),
+            "Smudge" => Some((Arc::new(SmudgeTool::deserialize(value)), layer)),
+            "Symmetry" => Some((Arc::new(SymmetryCopy::deserialize(value)), layer)),
+            "Stamp" => Some((Arc::new(StampTool::deserialize(value)), layer)),
+            "Raster" => Some((Arc::new(RasterTool::deserialize(value)), layer)),
             _ => None,
         }
     } else {
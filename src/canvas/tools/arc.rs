@@ -0,0 +1,422 @@
+use crate::canvas::layer::CanvasMessage;
+use crate::canvas::style::Style;
+use crate::utils::serde::{Deserialize, Serialize};
+use iced::event::Status;
+use iced::keyboard::Key;
+use iced::mouse::Cursor;
+use iced::widget::canvas::path::{self, Builder};
+use iced::widget::canvas::{Event, Fill, Frame, Geometry, Path, Stroke};
+use iced::{keyboard, mouse, Color, Point, Radians, Rectangle, Renderer};
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document};
+use std::f32::consts::PI;
+use std::fmt::Debug;
+use std::sync::Arc;
+use svg::node::element::Group;
+
+use crate::canvas::tool::{Pending, Tool};
+
+/// Returns the angle of the point around the center, in radians.
+fn get_angle(center: Point, point: Point) -> f32 {
+    (point.y - center.y).atan2(point.x - center.x)
+}
+
+/// Returns the point on the circle with the given center and radius, at the given angle.
+fn get_point(center: Point, radius: f32, angle: f32) -> Point {
+    Point::new(
+        center.x + radius * angle.cos(),
+        center.y + radius * angle.sin(),
+    )
+}
+
+/// Adds the arc to the [Builder]. If it is a pie, the arc is joined to its center.
+fn build_sector(
+    builder: &mut Builder,
+    center: Point,
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    pie: bool,
+) {
+    if pie {
+        builder.move_to(center);
+        builder.line_to(get_point(center, radius, start_angle));
+    } else {
+        builder.move_to(get_point(center, radius, start_angle));
+    }
+
+    builder.arc(path::Arc {
+        center,
+        radius,
+        start_angle: Radians(start_angle),
+        end_angle: Radians(end_angle),
+    });
+
+    if pie {
+        builder.close();
+    }
+}
+
+/// Returns the angle the sector ends at, so that it is swept clockwise from the start angle.
+fn get_end_angle(start_angle: f32, cursor_angle: f32) -> f32 {
+    start_angle + (cursor_angle - start_angle).rem_euclid(2.0 * PI)
+}
+
+/// The [Pending] shared by the [CircleArc] and [Pie] tools. The center is placed on click, the
+/// radius is set by dragging, and the sweep is set by dragging again.
+#[derive(Clone)]
+pub enum SectorPending<const PIE: bool> {
+    None,
+    Center(Point),
+    Sweep(Point, f32, f32),
+}
+
+/// Draws an arc of a circle.
+pub type ArcPending = SectorPending<false>;
+
+/// Draws a filled slice of a circle.
+pub type PiePending = SectorPending<true>;
+
+impl<const PIE: bool> Pending for SectorPending<PIE> {
+    fn update(
+        &mut self,
+        event: Event,
+        cursor: Point,
+        style: Style,
+    ) -> (Status, Option<CanvasMessage>) {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let message = match mouse_event {
+                    mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                        if let SectorPending::None = self {
+                            *self = SectorPending::Center(cursor);
+                        }
+
+                        None
+                    }
+                    mouse::Event::ButtonReleased(mouse::Button::Left) => match self {
+                        SectorPending::None => None,
+                        SectorPending::Center(center) => {
+                            let radius = cursor.distance(*center);
+                            if radius > 0.0 {
+                                *self = SectorPending::Sweep(
+                                    *center,
+                                    radius,
+                                    get_angle(*center, cursor),
+                                );
+                            } else {
+                                *self = SectorPending::None;
+                            }
+
+                            None
+                        }
+                        SectorPending::Sweep(center, radius, start_angle) => {
+                            let sector = Sector::<PIE> {
+                                center: *center,
+                                radius: *radius,
+                                start_angle: *start_angle,
+                                end_angle: get_end_angle(*start_angle, get_angle(*center, cursor)),
+                                style: style.clone(),
+                            };
+
+                            *self = SectorPending::None;
+                            Some(CanvasMessage::UseTool(Arc::new(sector)))
+                        }
+                    },
+                    _ => None,
+                };
+
+                (Status::Captured, message)
+            }
+            Event::Keyboard(key_event) => match key_event {
+                keyboard::Event::KeyPressed {
+                    key: Key::Character(key),
+                    ..
+                } => {
+                    let value = key.as_str();
+                    if value == "S" || value == "s" {
+                        *self = SectorPending::None;
+
+                        (Status::Captured, None)
+                    } else {
+                        (Status::Ignored, None)
+                    }
+                }
+                _ => (Status::Ignored, None),
+            },
+            _ => (Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        renderer: &Renderer,
+        bounds: Rectangle,
+        cursor: Cursor,
+        style: Style,
+    ) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if let Some(cursor_position) = cursor.position_in(bounds) {
+            let path = match self {
+                SectorPending::None => None,
+                SectorPending::Center(center) => {
+                    Some(Path::circle(*center, cursor_position.distance(*center)))
+                }
+                SectorPending::Sweep(center, radius, start_angle) => {
+                    let end_angle =
+                        get_end_angle(*start_angle, get_angle(*center, cursor_position));
+
+                    Some(Path::new(|builder| {
+                        build_sector(builder, *center, *radius, *start_angle, end_angle, PIE)
+                    }))
+                }
+            };
+
+            if let Some(path) = path {
+                if let Some((width, color, _, _)) = style.stroke {
+                    frame.stroke(&path, Stroke::default().with_width(width).with_color(color));
+                }
+                if let (true, Some((color, _))) = (PIE, style.fill) {
+                    frame.fill(&path, Fill::from(color));
+                }
+            }
+        };
+
+        frame.into_geometry()
+    }
+
+    fn shape_style(&self, style: &mut Style) {
+        if style.stroke.is_none() {
+            style.stroke = Some((2.0, Color::BLACK, false, false));
+        }
+
+        if PIE {
+            if style.fill.is_none() {
+                style.fill = Some((Color::TRANSPARENT, false));
+            }
+        } else {
+            style.fill = None;
+        }
+    }
+
+    fn id(&self) -> String {
+        Sector::<PIE>::get_id()
+    }
+
+    fn default() -> Self
+    where
+        Self: Sized,
+    {
+        SectorPending::None
+    }
+
+    fn dyn_default(&self) -> Box<dyn Pending> {
+        Box::new(SectorPending::<PIE>::None)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pending> {
+        Box::new((*self).clone())
+    }
+}
+
+/// A part of a circle, swept clockwise from the start angle to the end angle. Pies are joined
+/// to their center and filled.
+#[derive(Debug, Clone)]
+pub struct Sector<const PIE: bool> {
+    center: Point,
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    style: Style,
+}
+
+/// An arc of a circle.
+pub type CircleArc = Sector<false>;
+
+/// A filled slice of a circle.
+pub type Pie = Sector<true>;
+
+impl<const PIE: bool> Sector<PIE> {
+    /// Returns the identifier of the tool.
+    fn get_id() -> String {
+        if PIE {
+            String::from("Pie")
+        } else {
+            String::from("Arc")
+        }
+    }
+
+    /// Returns the path data of the sector, in svg form.
+    fn get_path_data(&self) -> String {
+        let start = get_point(self.center, self.radius, self.start_angle);
+        let end = get_point(self.center, self.radius, self.end_angle);
+        let large_arc = (self.end_angle - self.start_angle > PI) as u8;
+
+        let arc = format!(
+            "A {} {} 0 {} 1 {},{}",
+            self.radius, self.radius, large_arc, end.x, end.y
+        );
+
+        if PIE {
+            format!(
+                "M {},{} L {},{} {} Z",
+                self.center.x, self.center.y, start.x, start.y, arc
+            )
+        } else {
+            format!("M {},{} {}", start.x, start.y, arc)
+        }
+    }
+}
+
+impl<const PIE: bool> Default for Sector<PIE> {
+    fn default() -> Self {
+        Sector {
+            center: Point::default(),
+            radius: 0.0,
+            start_angle: 0.0,
+            end_angle: 0.0,
+            style: Style::default(),
+        }
+    }
+}
+
+impl<const PIE: bool> Serialize<Document> for Sector<PIE> {
+    fn serialize(&self) -> Document {
+        doc! {
+            "center": Document::from(self.center.serialize()),
+            "radius": self.radius,
+            "start_angle": self.start_angle,
+            "end_angle": self.end_angle,
+            "style": Document::from(self.style.serialize()),
+        }
+    }
+}
+
+impl<const PIE: bool> Deserialize<Document> for Sector<PIE> {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut sector = Sector::default();
+
+        if let Some(Bson::Document(center)) = document.get("center") {
+            sector.center = Point::deserialize(center);
+        }
+        if let Some(Bson::Double(radius)) = document.get("radius") {
+            sector.radius = *radius as f32;
+        }
+        if let Some(Bson::Double(start_angle)) = document.get("start_angle") {
+            sector.start_angle = *start_angle as f32;
+        }
+        if let Some(Bson::Double(end_angle)) = document.get("end_angle") {
+            sector.end_angle = *end_angle as f32;
+        }
+        if let Some(Bson::Document(style)) = document.get("style") {
+            sector.style = Style::deserialize(style);
+        }
+
+        sector
+    }
+}
+
+impl<const PIE: bool> Serialize<Group> for Sector<PIE> {
+    fn serialize(&self) -> Group {
+        let (fill, fill_opacity) = if PIE {
+            (self.style.get_fill(), self.style.get_fill_alpha())
+        } else {
+            (String::from("none"), 0.0)
+        };
+
+        let path = svg::node::element::Path::new()
+            .set("stroke-width", self.style.get_stroke_width())
+            .set("stroke", self.style.get_stroke_color())
+            .set("stroke-opacity", self.style.get_stroke_alpha())
+            .set("fill", fill)
+            .set("fill-opacity", fill_opacity)
+            .set("d", self.get_path_data());
+
+        Group::new().set("class", self.id()).add(path)
+    }
+}
+
+impl<const PIE: bool> Serialize<Object> for Sector<PIE> {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert("center", JsonValue::Object(self.center.serialize()));
+        data.insert("radius", JsonValue::Number(self.radius.into()));
+        data.insert("start_angle", JsonValue::Number(self.start_angle.into()));
+        data.insert("end_angle", JsonValue::Number(self.end_angle.into()));
+        data.insert("style", JsonValue::Object(self.style.serialize()));
+
+        data
+    }
+}
+
+impl<const PIE: bool> Deserialize<Object> for Sector<PIE> {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut sector = Sector::default();
+
+        if let Some(JsonValue::Object(center)) = document.get("center") {
+            sector.center = Point::deserialize(center);
+        }
+        if let Some(JsonValue::Number(radius)) = document.get("radius") {
+            sector.radius = f32::from(*radius);
+        }
+        if let Some(JsonValue::Number(start_angle)) = document.get("start_angle") {
+            sector.start_angle = f32::from(*start_angle);
+        }
+        if let Some(JsonValue::Number(end_angle)) = document.get("end_angle") {
+            sector.end_angle = f32::from(*end_angle);
+        }
+        if let Some(JsonValue::Object(style)) = document.get("style") {
+            sector.style = Style::deserialize(style);
+        }
+
+        sector
+    }
+}
+
+impl<const PIE: bool> Tool for Sector<PIE> {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        let sector = Path::new(|builder| {
+            build_sector(
+                builder,
+                self.center,
+                self.radius,
+                self.start_angle,
+                self.end_angle,
+                PIE,
+            )
+        });
+
+        if let (true, Some((color, _))) = (PIE, self.style.fill) {
+            frame.fill(&sector, Fill::from(color));
+        }
+        if let Some((width, color, _, _)) = self.style.stroke {
+            frame.stroke(
+                &sector,
+                Stroke::default().with_width(width).with_color(color),
+            );
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        Self::get_id()
+    }
+}
+
+impl<const PIE: bool> Into<Box<dyn Tool>> for Box<Sector<PIE>> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
@@ -0,0 +1,369 @@
+use crate::canvas::layer::CanvasMessage;
+use crate::canvas::style::Style;
+use crate::utils::serde::{Deserialize, Serialize};
+use iced::event::Status;
+use iced::keyboard::Key;
+use iced::mouse::Cursor;
+use iced::widget::canvas::path::Builder;
+use iced::widget::canvas::{Event, Fill, Frame, Geometry, LineCap, Path, Stroke};
+use iced::{keyboard, mouse, Color, Point, Rectangle, Renderer};
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use svg::node::element::Group;
+
+use crate::canvas::tool::{Pending, Tool};
+
+/// The radius of the markers drawn over the control points.
+const RADIUS: f32 = 4.0;
+
+/// The longest time between two clicks for them to count as a double-click.
+const DOUBLE_CLICK_DELAY: Duration = Duration::from_millis(300);
+
+/// The largest distance between two clicks for them to count as a double-click.
+const DOUBLE_CLICK_DISTANCE: f32 = 5.0;
+
+#[derive(Clone)]
+pub enum BezierPending {
+    None,
+    Drawing(Vec<Point>, Instant),
+}
+
+/// Adds the curve going through the given control points to the [Builder]. The first point is
+/// the start of the curve, and every following group of three points forms a cubic segment.
+/// A trailing group of two points forms a quadratic segment, and a trailing single point a line.
+fn build_curve(builder: &mut Builder, points: &[Point]) {
+    let Some((first, rest)) = points.split_first() else {
+        return;
+    };
+
+    builder.move_to(*first);
+    for segment in rest.chunks(3) {
+        match segment {
+            [control_a, control_b, to] => builder.bezier_curve_to(*control_a, *control_b, *to),
+            [control, to] => builder.quadratic_curve_to(*control, *to),
+            [to] => builder.line_to(*to),
+            _ => {}
+        }
+    }
+}
+
+impl Pending for BezierPending {
+    fn update(
+        &mut self,
+        event: Event,
+        cursor: Point,
+        style: Style,
+    ) -> (Status, Option<CanvasMessage>) {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let message = match mouse_event {
+                    mouse::Event::ButtonPressed(mouse::Button::Left) => match self {
+                        BezierPending::None => {
+                            *self = BezierPending::Drawing(vec![cursor], Instant::now());
+                            None
+                        }
+                        BezierPending::Drawing(points, last_click) => {
+                            let is_double_click = last_click.elapsed() < DOUBLE_CLICK_DELAY
+                                && points.last().map_or(false, |last| {
+                                    cursor.distance(*last) < DOUBLE_CLICK_DISTANCE
+                                });
+
+                            if is_double_click {
+                                let control_points = points.clone();
+                                *self = BezierPending::None;
+
+                                if control_points.len() < 2 {
+                                    None
+                                } else {
+                                    let (width, color) = style
+                                        .stroke
+                                        .map_or((2.0, Color::BLACK), |(width, color, _, _)| {
+                                            (width, color)
+                                        });
+
+                                    Some(
+                                        CanvasMessage::UseTool(Arc::new(BezierTool {
+                                            control_points,
+                                            color,
+                                            width,
+                                        }))
+                                        .into(),
+                                    )
+                                }
+                            } else {
+                                points.push(cursor);
+                                *last_click = Instant::now();
+                                None
+                            }
+                        }
+                    },
+                    _ => None,
+                };
+
+                (Status::Captured, message)
+            }
+            Event::Keyboard(key_event) => match key_event {
+                keyboard::Event::KeyPressed {
+                    key: Key::Character(key),
+                    ..
+                } => {
+                    let value = key.as_str();
+                    if value == "S" || value == "s" {
+                        *self = BezierPending::None;
+
+                        (Status::Captured, None)
+                    } else {
+                        (Status::Ignored, None)
+                    }
+                }
+                _ => (Status::Ignored, None),
+            },
+            _ => (Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        renderer: &Renderer,
+        bounds: Rectangle,
+        cursor: Cursor,
+        style: Style,
+    ) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if let Some(cursor_position) = cursor.position_in(bounds) {
+            match self {
+                BezierPending::None => {}
+                BezierPending::Drawing(points, _) => {
+                    let markers = Path::new(|p| {
+                        for point in points {
+                            p.circle(*point, RADIUS);
+                        }
+                    });
+                    frame.fill(&markers, Fill::from(Color::from_rgba8(0, 255, 255, 0.3)));
+
+                    let mut points = points.clone();
+                    points.push(cursor_position);
+                    let curve = Path::new(|p| build_curve(p, &points));
+
+                    if let Some((width, color, _, _)) = style.stroke {
+                        frame.stroke(
+                            &curve,
+                            Stroke::default()
+                                .with_width(width)
+                                .with_color(color)
+                                .with_line_cap(LineCap::Round),
+                        );
+                    }
+                }
+            }
+        };
+
+        frame.into_geometry()
+    }
+
+    fn shape_style(&self, style: &mut Style) {
+        if style.stroke.is_none() {
+            style.stroke = Some((2.0, Color::BLACK, false, false));
+        }
+        style.fill = None;
+    }
+
+    fn id(&self) -> String {
+        String::from("Bezier")
+    }
+
+    fn default() -> Self
+    where
+        Self: Sized,
+    {
+        BezierPending::None
+    }
+
+    fn dyn_default(&self) -> Box<dyn Pending> {
+        Box::new(BezierPending::None)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pending> {
+        Box::new((*self).clone())
+    }
+}
+
+/// A curve made of cubic Bezier segments.
+#[derive(Debug, Clone)]
+pub struct BezierTool {
+    control_points: Vec<Point>,
+    color: Color,
+    width: f32,
+}
+
+impl BezierTool {
+    /// Returns the [Style] the curve is drawn with.
+    fn get_style(&self) -> Style {
+        Style {
+            stroke: Some((self.width, self.color, false, false)),
+            fill: None,
+            ..Style::default()
+        }
+    }
+
+    /// Returns the path data of the curve, in svg form.
+    fn get_path_data(&self) -> String {
+        let Some((first, rest)) = self.control_points.split_first() else {
+            return String::new();
+        };
+
+        rest.chunks(3).fold(
+            format!("M {},{}", first.x, first.y),
+            |data, segment| match segment {
+                [control_a, control_b, to] => format!(
+                    "{} C {},{} {},{} {},{}",
+                    data, control_a.x, control_a.y, control_b.x, control_b.y, to.x, to.y
+                ),
+                [control, to] => {
+                    format!("{} Q {},{} {},{}", data, control.x, control.y, to.x, to.y)
+                }
+                [to] => format!("{} L {},{}", data, to.x, to.y),
+                _ => data,
+            },
+        )
+    }
+}
+
+impl Default for BezierTool {
+    fn default() -> Self {
+        BezierTool {
+            control_points: vec![],
+            color: Color::BLACK,
+            width: 2.0,
+        }
+    }
+}
+
+impl Serialize<Document> for BezierTool {
+    fn serialize(&self) -> Document {
+        doc! {
+            "points": self.control_points.iter().map(|point| point.serialize()).collect::<Vec<Document>>().as_slice(),
+            "color": Document::from(self.color.serialize()),
+            "width": self.width,
+        }
+    }
+}
+
+impl Deserialize<Document> for BezierTool {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut bezier = BezierTool::default();
+
+        if let Some(Bson::Array(points)) = document.get("points") {
+            for point in points {
+                if let Bson::Document(point) = point {
+                    bezier.control_points.push(Point::deserialize(point));
+                }
+            }
+        }
+        if let Some(Bson::Document(color)) = document.get("color") {
+            bezier.color = Color::deserialize(color);
+        }
+        if let Some(Bson::Double(width)) = document.get("width") {
+            bezier.width = *width as f32;
+        }
+
+        bezier
+    }
+}
+
+impl Serialize<Group> for BezierTool {
+    fn serialize(&self) -> Group {
+        let style = self.get_style();
+
+        let path = svg::node::element::Path::new()
+            .set("stroke-width", style.get_stroke_width())
+            .set("stroke", style.get_stroke_color())
+            .set("stroke-linecap", "round")
+            .set("stroke-opacity", style.get_stroke_alpha())
+            .set("fill", "none")
+            .set("d", self.get_path_data());
+
+        Group::new().set("class", self.id()).add(path)
+    }
+}
+
+impl Serialize<Object> for BezierTool {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert(
+            "points",
+            JsonValue::Array(
+                self.control_points
+                    .iter()
+                    .map(|point| JsonValue::Object(point.serialize()))
+                    .collect(),
+            ),
+        );
+        data.insert("color", JsonValue::Object(self.color.serialize()));
+        data.insert("width", JsonValue::Number(self.width.into()));
+
+        data
+    }
+}
+
+impl Deserialize<Object> for BezierTool {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut bezier = BezierTool::default();
+
+        if let Some(JsonValue::Array(points)) = document.get("points") {
+            for point in points {
+                if let JsonValue::Object(point) = point {
+                    bezier.control_points.push(Point::deserialize(point));
+                }
+            }
+        }
+        if let Some(JsonValue::Object(color)) = document.get("color") {
+            bezier.color = Color::deserialize(color);
+        }
+        if let Some(JsonValue::Number(width)) = document.get("width") {
+            bezier.width = f32::from(*width);
+        }
+
+        bezier
+    }
+}
+
+impl Tool for BezierTool {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        let curve = Path::new(|builder| build_curve(builder, &self.control_points));
+
+        frame.stroke(
+            &curve,
+            Stroke::default()
+                .with_width(self.width)
+                .with_color(self.color)
+                .with_line_cap(LineCap::Round),
+        );
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        "Bezier".into()
+    }
+}
+
+impl Into<Box<dyn Tool>> for Box<BezierTool> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
@@ -12,18 +12,44 @@ use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::ops::{Add, Sub};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use iced::keyboard::Key;
 use svg::node::element::Group;
 
 use crate::canvas::tool::{Pending, Tool};
 
+/// Added to the speed of the cursor when simulating the pressure, so that it stays finite.
+const PRESSURE_EPSILON: f32 = 1.0;
+
+/// The lowest simulated pressure, so that fast strokes remain visible.
+const MIN_PRESSURE: f32 = 0.2;
+
+/// Returns the pressure simulated from the speed of the cursor, measured in pixels per
+/// millisecond. Slow movements give a pressure close to 1, and fast ones a lower pressure.
+fn simulate_pressure(distance: f32, elapsed: Duration) -> f32 {
+    let speed = distance / elapsed.as_millis().max(1) as f32;
+
+    (1.0 / (speed + PRESSURE_EPSILON)).clamp(MIN_PRESSURE, 1.0)
+}
+
+/// Returns the [Style] of the stroke piece with the given index, with the stroke width scaled
+/// by its pressure. Strokes without pressure data keep their [Style] unchanged.
+pub(crate) fn get_pressed_style(style: &Style, pressures: &[f32], index: usize) -> Style {
+    match pressures.get(index) {
+        Some(pressure) => style
+            .clone()
+            .stroke_width(style.get_stroke_width() * pressure),
+        None => style.clone(),
+    }
+}
+
 #[derive(Clone)]
 pub enum BrushPending<BrushType>
 where
     BrushType: Send + Sync + Clone + Brush,
 {
     None,
-    Stroking(Point, Point, Vec<Vector>),
+    Stroking(Point, Point, Vec<Vector>, Vec<f32>, Instant),
     _PhantomVariant(PhantomData<BrushType>),
 }
 
@@ -42,37 +68,67 @@ where
                 let message = match mouse_event {
                     mouse::Event::ButtonPressed(mouse::Button::Left) => match self {
                         BrushPending::None => {
-                            *self =
-                                BrushPending::Stroking(cursor, cursor, vec![Vector::new(0.0, 0.0)]);
+                            let pressures = if style.pressure_simulation {
+                                vec![1.0]
+                            } else {
+                                vec![]
+                            };
+
+                            *self = BrushPending::Stroking(
+                                cursor,
+                                cursor,
+                                vec![Vector::new(0.0, 0.0)],
+                                pressures,
+                                Instant::now(),
+                            );
 
                             None
                         }
                         _ => None,
                     },
                     mouse::Event::CursorMoved { .. } => match self {
-                        BrushPending::Stroking(start, last, offsets) => {
+                        BrushPending::Stroking(start, last, offsets, pressures, last_move) => {
                             let mut new_offsets = offsets.clone();
                             new_offsets.push(cursor.sub(*last));
 
-                            *self = BrushPending::Stroking(*start, cursor, new_offsets);
+                            let mut new_pressures = pressures.clone();
+                            if style.pressure_simulation {
+                                new_pressures.push(simulate_pressure(
+                                    cursor.distance(*last),
+                                    last_move.elapsed(),
+                                ));
+                            }
+
+                            *self = BrushPending::Stroking(
+                                *start,
+                                cursor,
+                                new_offsets,
+                                new_pressures,
+                                Instant::now(),
+                            );
 
                             None
                         }
                         _ => None,
                     },
                     mouse::Event::ButtonReleased(mouse::Button::Left) => match self {
-                        BrushPending::Stroking(start, _last, offsets) => {
+                        BrushPending::Stroking(start, _last, offsets, pressures, _) => {
                             let start_clone = start.clone();
                             let offsets_clone = offsets.clone();
+                            let pressures_clone = pressures.clone();
 
                             *self = BrushPending::None;
 
                             Some(
-                                CanvasMessage::UseTool(Arc::new(BrushType::new(
-                                    start_clone,
-                                    offsets_clone,
-                                    style,
-                                )))
+                                CanvasMessage::UseTool(
+                                    BrushType::new(
+                                        start_clone,
+                                        offsets_clone,
+                                        pressures_clone,
+                                        style,
+                                    )
+                                    .into_tool(),
+                                )
                                 .into(),
                             )
                         }
@@ -114,15 +170,15 @@ where
 
         if let Some(_cursor_position) = cursor.position_in(bounds) {
             match self {
-                BrushPending::Stroking(start, _last, offsets) => {
+                BrushPending::Stroking(start, _last, offsets, pressures, _) => {
                     let mut pos = *start;
 
-                    for offset in offsets.clone() {
+                    for (index, offset) in offsets.clone().into_iter().enumerate() {
                         BrushType::add_stroke_piece(
                             pos,
                             pos.add(offset),
                             &mut frame,
-                            style.clone(),
+                            get_pressed_style(&style, pressures, index),
                         );
                         pos = pos.add(offset.clone());
                     }
@@ -140,6 +196,7 @@ where
         }
 
         style.fill = None;
+        BrushType::shape_style(style);
     }
 
     fn id(&self) -> String {
@@ -163,7 +220,7 @@ where
 }
 
 pub trait Brush: Send + Sync + Debug {
-    fn new(start: Point, offsets: Vec<Vector>, style: Style) -> Self
+    fn new(start: Point, offsets: Vec<Vector>, pressures: Vec<f32>, style: Style) -> Self
     where
         Self: Sized;
     fn id() -> String
@@ -172,8 +229,24 @@ pub trait Brush: Send + Sync + Debug {
 
     fn get_start(&self) -> Point;
     fn get_offsets(&self) -> Vec<Vector>;
+    fn get_pressures(&self) -> Vec<f32>;
     fn get_style(&self) -> Style;
 
+    /// Locks or unlocks the [Style] options specific to the brush, after the common ones.
+    fn shape_style(_style: &mut Style)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// Converts the finished stroke into the [Tool] placed on the canvas.
+    fn into_tool(self) -> Arc<dyn Tool>
+    where
+        Self: Sized + Tool + 'static,
+    {
+        Arc::new(self)
+    }
+
     fn add_stroke_piece(point1: Point, point2: Point, frame: &mut Frame, style: Style)
     where
         Self: Sized;
@@ -194,11 +267,18 @@ where
     BrushType: Brush + Clone + 'static,
 {
     fn serialize(&self) -> Document {
-        doc! {
+        let mut document = doc! {
             "start": Document::from(self.get_start().serialize()),
             "offsets": self.get_offsets().iter().map(|offset| {offset.serialize()}).collect::<Vec<Document>>().as_slice(),
             "style": Document::from(self.get_style().serialize()),
+        };
+
+        let pressures = self.get_pressures();
+        if !pressures.is_empty() {
+            document.insert("pressures", pressures.as_slice());
         }
+
+        document
     }
 }
 
@@ -212,6 +292,7 @@ where
     {
         let mut brush_start: Point = Point::default();
         let mut brush_offsets: Vec<Vector> = vec![];
+        let mut brush_pressures: Vec<f32> = vec![];
         let mut brush_style: Style = Style::default();
 
         if let Some(Bson::Document(start)) = document.get("start") {
@@ -226,11 +307,19 @@ where
             }
         }
 
+        if let Some(Bson::Array(pressures)) = document.get("pressures") {
+            for pressure in pressures {
+                if let Bson::Double(pressure) = pressure {
+                    brush_pressures.push(*pressure as f32);
+                }
+            }
+        }
+
         if let Some(Bson::Document(style)) = document.get("style") {
             brush_style = Style::deserialize(style);
         }
 
-        BrushType::new(brush_start, brush_offsets, brush_style)
+        BrushType::new(brush_start, brush_offsets, brush_pressures, brush_style)
     }
 }
 
@@ -241,14 +330,26 @@ where
     fn serialize(&self) -> Group {
         let mut pos = self.get_start();
 
+        let style = self.get_style();
+        let pressures = self.get_pressures();
+
         let mut ret = Group::new().set("class", BrushType::id());
 
-        for offset in self.get_offsets() {
-            ret = BrushType::add_svg_stroke_piece(pos, pos.add(offset), ret, self.get_style());
+        for (index, offset) in self.get_offsets().into_iter().enumerate() {
+            ret = BrushType::add_svg_stroke_piece(
+                pos,
+                pos.add(offset),
+                ret,
+                get_pressed_style(&style, &pressures, index),
+            );
             pos = pos.add(offset.clone());
         }
 
-        BrushType::add_svg_end(pos, ret, self.get_style())
+        BrushType::add_svg_end(
+            pos,
+            ret,
+            get_pressed_style(&style, &pressures, pressures.len().saturating_sub(1)),
+        )
     }
 }
 
@@ -271,6 +372,19 @@ where
         );
         data.insert("style", JsonValue::Object(self.get_style().serialize()));
 
+        let pressures = self.get_pressures();
+        if !pressures.is_empty() {
+            data.insert(
+                "pressures",
+                JsonValue::Array(
+                    pressures
+                        .iter()
+                        .map(|pressure| JsonValue::Number((*pressure).into()))
+                        .collect(),
+                ),
+            );
+        }
+
         data
     }
 }
@@ -285,6 +399,7 @@ where
     {
         let mut brush_start = Point::default();
         let mut brush_offsets: Vec<Vector> = vec![];
+        let mut brush_pressures: Vec<f32> = vec![];
         let mut brush_style = Style::default();
 
         if let Some(JsonValue::Object(start)) = document.get("start") {
@@ -297,11 +412,18 @@ where
                 }
             }
         }
+        if let Some(JsonValue::Array(pressures)) = document.get("pressures") {
+            for pressure in pressures {
+                if let JsonValue::Number(pressure) = pressure {
+                    brush_pressures.push(f32::from(*pressure));
+                }
+            }
+        }
         if let Some(JsonValue::Object(style)) = document.get("style") {
             brush_style = Style::deserialize(style);
         }
 
-        BrushType::new(brush_start, brush_offsets, brush_style)
+        BrushType::new(brush_start, brush_offsets, brush_pressures, brush_style)
     }
 }
 
@@ -311,13 +433,24 @@ where
 {
     fn add_to_frame(&self, frame: &mut Frame) {
         let mut pos = self.get_start();
-
-        for offset in self.get_offsets() {
-            BrushType::add_stroke_piece(pos, pos.add(offset), frame, self.get_style());
+        let style = self.get_style();
+        let pressures = self.get_pressures();
+
+        for (index, offset) in self.get_offsets().into_iter().enumerate() {
+            BrushType::add_stroke_piece(
+                pos,
+                pos.add(offset),
+                frame,
+                get_pressed_style(&style, &pressures, index),
+            );
             pos = pos.add(offset.clone());
         }
 
-        BrushType::add_end(pos, frame, self.get_style());
+        BrushType::add_end(
+            pos,
+            frame,
+            get_pressed_style(&style, &pressures, pressures.len().saturating_sub(1)),
+        );
     }
 
     fn boxed_clone(&self) -> Box<dyn Tool> {
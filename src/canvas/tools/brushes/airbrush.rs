@@ -50,6 +50,7 @@ impl SeedableRng for RNG {
 pub struct Airbrush {
     start: Point,
     offsets: Vec<Vector>,
+    pressures: Vec<f32>,
     style: Style,
 }
 
@@ -102,13 +103,14 @@ impl Airbrush {
 }
 
 impl Brush for Airbrush {
-    fn new(start: Point, offsets: Vec<Vector>, style: Style) -> Self
+    fn new(start: Point, offsets: Vec<Vector>, pressures: Vec<f32>, style: Style) -> Self
     where
         Self: Sized,
     {
         Airbrush {
             start,
             offsets,
+            pressures,
             style,
         }
     }
@@ -127,6 +129,10 @@ impl Brush for Airbrush {
     fn get_offsets(&self) -> Vec<Vector> {
         self.offsets.clone()
     }
+
+    fn get_pressures(&self) -> Vec<f32> {
+        self.pressures.clone()
+    }
     fn get_style(&self) -> Style {
         self.style.clone()
     }
@@ -18,7 +18,7 @@ pub struct Eraser {
 }
 
 impl Brush for Eraser {
-    fn new(start: Point, offsets: Vec<Vector>, _style: Style) -> Self
+    fn new(start: Point, offsets: Vec<Vector>, _pressures: Vec<f32>, _style: Style) -> Self
     where
         Self: Sized,
     {
@@ -39,6 +39,10 @@ impl Brush for Eraser {
     fn get_offsets(&self) -> Vec<Vector> {
         self.offsets.clone()
     }
+
+    fn get_pressures(&self) -> Vec<f32> {
+        vec![]
+    }
     fn get_style(&self) -> Style {
         Style::default()
     }
@@ -2,3 +2,4 @@ pub mod airbrush;
 pub mod eraser;
 pub mod pen;
 pub mod pencil;
+pub mod texture;
@@ -13,17 +13,19 @@ use crate::canvas::tools::brush::Brush;
 pub struct Pen {
     start: Point,
     offsets: Vec<Vector>,
+    pressures: Vec<f32>,
     style: Style,
 }
 
 impl Brush for Pen {
-    fn new(start: Point, offsets: Vec<Vector>, style: Style) -> Self
+    fn new(start: Point, offsets: Vec<Vector>, pressures: Vec<f32>, style: Style) -> Self
     where
         Self: Sized,
     {
         Pen {
             start,
             offsets,
+            pressures,
             style,
         }
     }
@@ -39,6 +41,10 @@ impl Brush for Pen {
     fn get_offsets(&self) -> Vec<Vector> {
         self.offsets.clone()
     }
+
+    fn get_pressures(&self) -> Vec<f32> {
+        self.pressures.clone()
+    }
     fn get_style(&self) -> Style {
         self.style.clone()
     }
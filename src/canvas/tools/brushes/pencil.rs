@@ -12,17 +12,19 @@ use crate::canvas::tools::brush::Brush;
 pub struct Pencil {
     start: Point,
     offsets: Vec<Vector>,
+    pressures: Vec<f32>,
     style: Style,
 }
 
 impl Brush for Pencil {
-    fn new(start: Point, offsets: Vec<Vector>, style: Style) -> Self
+    fn new(start: Point, offsets: Vec<Vector>, pressures: Vec<f32>, style: Style) -> Self
     where
         Self: Sized,
     {
         Pencil {
             start,
             offsets,
+            pressures,
             style,
         }
     }
@@ -42,6 +44,10 @@ impl Brush for Pencil {
         self.offsets.clone()
     }
 
+    fn get_pressures(&self) -> Vec<f32> {
+        self.pressures.clone()
+    }
+
     fn get_style(&self) -> Style {
         self.style.clone()
     }
@@ -0,0 +1,504 @@
+use crate::canvas::style::Style;
+use crate::canvas::tool::Tool;
+use crate::utils::serde::{Deserialize, Serialize};
+use iced::widget::canvas::{Fill, Frame, LineCap, Path, Stroke};
+use iced::{Color, Point, Vector};
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f32::consts::PI;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Add;
+use std::sync::Arc;
+use svg::node::element::Group;
+
+use crate::canvas::tools::brush::{get_pressed_style, Brush};
+
+/// The patterns a [TextureBrush] can draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TexturePattern {
+    Airbrush,
+    Crosshatch,
+    Dots,
+    Fur,
+}
+
+impl TexturePattern {
+    /// The list of all patterns.
+    pub const ALL: [TexturePattern; 4] = [
+        TexturePattern::Airbrush,
+        TexturePattern::Crosshatch,
+        TexturePattern::Dots,
+        TexturePattern::Fur,
+    ];
+}
+
+impl Display for TexturePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                TexturePattern::Airbrush => "Airbrush",
+                TexturePattern::Crosshatch => "Crosshatch",
+                TexturePattern::Dots => "Dots",
+                TexturePattern::Fur => "Fur",
+            }
+        )
+    }
+}
+
+/// The parameters of the pattern drawn by a [TextureBrush].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextureSettings {
+    /// The pattern that is drawn.
+    pub(crate) pattern: TexturePattern,
+
+    /// The amount of micro-strokes drawn for every 10 pixels of the stroke.
+    pub(crate) density: f32,
+
+    /// The angle of the micro-strokes, in degrees.
+    pub(crate) angle: f32,
+
+    /// How much the micro-strokes are randomly moved and rotated, between 0 and 1.
+    pub(crate) jitter: f32,
+}
+
+impl Default for TextureSettings {
+    fn default() -> Self {
+        TextureSettings {
+            pattern: TexturePattern::Crosshatch,
+            density: 5.0,
+            angle: 45.0,
+            jitter: 0.3,
+        }
+    }
+}
+
+/// A primitive drawn as part of a texture.
+#[derive(Debug, Clone)]
+pub enum TextureStroke {
+    /// A line between two points, with the given width.
+    Line(Point, Point, f32),
+
+    /// A dot with the given center and radius.
+    Dot(Point, f32),
+}
+
+impl TextureStroke {
+    /// Adds the primitive to the [Frame], with the given [Color].
+    fn add_to_frame(&self, frame: &mut Frame, color: Color) {
+        match self {
+            TextureStroke::Line(from, to, width) => {
+                frame.stroke(
+                    &Path::line(*from, *to),
+                    Stroke::default()
+                        .with_width(*width)
+                        .with_color(color)
+                        .with_line_cap(LineCap::Round),
+                );
+            }
+            TextureStroke::Dot(center, radius) => {
+                frame.fill(&Path::circle(*center, *radius), Fill::from(color));
+            }
+        }
+    }
+
+    /// Adds the primitive to the svg [Group], with the given [Style].
+    fn add_to_svg(&self, svg: Group, style: &Style) -> Group {
+        match self {
+            TextureStroke::Line(from, to, width) => svg.add(
+                svg::node::element::Line::new()
+                    .set("x1", from.x)
+                    .set("y1", from.y)
+                    .set("x2", to.x)
+                    .set("y2", to.y)
+                    .set("stroke-width", *width)
+                    .set("stroke", style.get_stroke_color())
+                    .set("stroke-opacity", style.get_stroke_alpha())
+                    .set("stroke-linecap", "round"),
+            ),
+            TextureStroke::Dot(center, radius) => svg.add(
+                svg::node::element::Circle::new()
+                    .set("cx", center.x)
+                    .set("cy", center.y)
+                    .set("r", *radius)
+                    .set("fill", style.get_stroke_color())
+                    .set("fill-opacity", style.get_stroke_alpha()),
+            ),
+        }
+    }
+}
+
+impl Serialize<Document> for TextureStroke {
+    fn serialize(&self) -> Document {
+        match self {
+            TextureStroke::Line(from, to, width) => doc! {
+                "kind": "line",
+                "from": Document::from(from.serialize()),
+                "to": Document::from(to.serialize()),
+                "width": *width,
+            },
+            TextureStroke::Dot(center, radius) => doc! {
+                "kind": "dot",
+                "center": Document::from(center.serialize()),
+                "radius": *radius,
+            },
+        }
+    }
+}
+
+impl Deserialize<Document> for TextureStroke {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let get_point = |key: &str| match document.get(key) {
+            Some(Bson::Document(point)) => Point::deserialize(point),
+            _ => Point::ORIGIN,
+        };
+        let get_size = |key: &str| match document.get(key) {
+            Some(Bson::Double(size)) => *size as f32,
+            _ => 1.0,
+        };
+
+        match document.get("kind") {
+            Some(Bson::String(kind)) if kind == "line" => {
+                TextureStroke::Line(get_point("from"), get_point("to"), get_size("width"))
+            }
+            _ => TextureStroke::Dot(get_point("center"), get_size("radius")),
+        }
+    }
+}
+
+impl Serialize<Object> for TextureStroke {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        match self {
+            TextureStroke::Line(from, to, width) => {
+                data.insert("kind", JsonValue::String(String::from("line")));
+                data.insert("from", JsonValue::Object(from.serialize()));
+                data.insert("to", JsonValue::Object(to.serialize()));
+                data.insert("width", JsonValue::Number((*width).into()));
+            }
+            TextureStroke::Dot(center, radius) => {
+                data.insert("kind", JsonValue::String(String::from("dot")));
+                data.insert("center", JsonValue::Object(center.serialize()));
+                data.insert("radius", JsonValue::Number((*radius).into()));
+            }
+        }
+
+        data
+    }
+}
+
+impl Deserialize<Object> for TextureStroke {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let get_point = |key: &str| match document.get(key) {
+            Some(JsonValue::Object(point)) => Point::deserialize(point),
+            _ => Point::ORIGIN,
+        };
+        let get_size = |key: &str| match document.get(key) {
+            Some(JsonValue::Number(size)) => f32::from(*size),
+            _ => 1.0,
+        };
+
+        match document.get("kind").and_then(|kind| kind.as_str()) {
+            Some("line") => {
+                TextureStroke::Line(get_point("from"), get_point("to"), get_size("width"))
+            }
+            _ => TextureStroke::Dot(get_point("center"), get_size("radius")),
+        }
+    }
+}
+
+/// Generates the micro-strokes of the texture between the two points. The randomness is seeded
+/// by the points, so that the same segment always gets the same pattern.
+fn generate_strokes(point1: Point, point2: Point, style: &Style) -> Vec<TextureStroke> {
+    let settings = style
+        .texture
+        .map(|(settings, _)| settings)
+        .unwrap_or_default();
+    let size = style.get_stroke_width().max(1.0);
+
+    let seed = ((point1.x.to_bits() as u64) << 32 | point1.y.to_bits() as u64)
+        ^ ((point2.x.to_bits() as u64) << 16 | (point2.y.to_bits() as u64) << 48);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let segment = Vector::new(point2.x - point1.x, point2.y - point1.y);
+    let length = segment.x.hypot(segment.y);
+    let count = (settings.density * length.max(1.0) / 10.0).ceil() as usize;
+    let angle = settings.angle.to_radians();
+
+    let mut jitter = |scale: f32| scale * settings.jitter * rng.gen_range(-1.0..=1.0);
+    let direction = |angle: f32| Vector::new(angle.cos(), angle.sin());
+
+    (0..count)
+        .flat_map(|index| {
+            let ratio = (index as f32 + 0.5) / count as f32;
+            let center = Point::new(point1.x + segment.x * ratio, point1.y + segment.y * ratio);
+
+            match settings.pattern {
+                TexturePattern::Airbrush => {
+                    let spread = 2.0 * size * (0.5 + jitter(0.5).abs());
+                    let dot_angle = jitter(PI) + PI;
+                    let offset = direction(dot_angle) * spread;
+
+                    vec![TextureStroke::Dot(center.add(offset), 1.0)]
+                }
+                TexturePattern::Crosshatch => {
+                    let center = center.add(Vector::new(jitter(size), jitter(size)));
+
+                    [angle, angle + PI / 2.0]
+                        .into_iter()
+                        .map(|hatch_angle| {
+                            let half = direction(hatch_angle + jitter(PI / 8.0)) * size;
+                            TextureStroke::Line(
+                                Point::new(center.x - half.x, center.y - half.y),
+                                center.add(half),
+                                1.0,
+                            )
+                        })
+                        .collect()
+                }
+                TexturePattern::Dots => {
+                    let center = center.add(Vector::new(jitter(size), jitter(size)));
+
+                    vec![TextureStroke::Dot(center, size / 3.0)]
+                }
+                TexturePattern::Fur => {
+                    let hair = direction(angle + jitter(PI / 4.0)) * (2.0 * size + jitter(size));
+
+                    vec![TextureStroke::Line(center, center.add(hair), 1.0)]
+                }
+            }
+        })
+        .collect()
+}
+
+/// A brush that draws a procedural pattern of micro-strokes along its path. It is only used
+/// while stroking; once placed, it becomes a [TextureTool].
+#[derive(Debug, Clone)]
+pub struct TextureBrush {
+    start: Point,
+    offsets: Vec<Vector>,
+    pressures: Vec<f32>,
+    style: Style,
+}
+
+impl Brush for TextureBrush {
+    fn new(start: Point, offsets: Vec<Vector>, pressures: Vec<f32>, style: Style) -> Self
+    where
+        Self: Sized,
+    {
+        TextureBrush {
+            start,
+            offsets,
+            pressures,
+            style,
+        }
+    }
+
+    fn id() -> String
+    where
+        Self: Sized,
+    {
+        String::from("Texture")
+    }
+
+    fn get_start(&self) -> Point {
+        self.start
+    }
+
+    fn get_offsets(&self) -> Vec<Vector> {
+        self.offsets.clone()
+    }
+
+    fn get_pressures(&self) -> Vec<f32> {
+        self.pressures.clone()
+    }
+
+    fn get_style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn shape_style(style: &mut Style)
+    where
+        Self: Sized,
+    {
+        if style.texture.is_none() {
+            style.texture = Some((TextureSettings::default(), false));
+        }
+    }
+
+    fn into_tool(self) -> Arc<dyn Tool>
+    where
+        Self: Sized,
+    {
+        let mut strokes = vec![];
+        let mut pos = self.start;
+
+        for (index, offset) in self.offsets.iter().enumerate() {
+            let style = get_pressed_style(&self.style, &self.pressures, index);
+            strokes.extend(generate_strokes(pos, pos.add(*offset), &style));
+            pos = pos.add(*offset);
+        }
+
+        Arc::new(TextureTool {
+            strokes,
+            style: self.style,
+        })
+    }
+
+    fn add_stroke_piece(point1: Point, point2: Point, frame: &mut Frame, style: Style)
+    where
+        Self: Sized,
+    {
+        if let Some((_, color, _, _)) = style.stroke {
+            for stroke in generate_strokes(point1, point2, &style) {
+                stroke.add_to_frame(frame, color);
+            }
+        }
+    }
+
+    fn add_end(_point: Point, _frame: &mut Frame, _style: Style)
+    where
+        Self: Sized,
+    {
+    }
+
+    fn add_svg_stroke_piece(point1: Point, point2: Point, svg: Group, style: Style) -> Group
+    where
+        Self: Sized,
+    {
+        generate_strokes(point1, point2, &style)
+            .iter()
+            .fold(svg, |svg, stroke| stroke.add_to_svg(svg, &style))
+    }
+
+    fn add_svg_end(_point: Point, svg: Group, _style: Style) -> Group
+    where
+        Self: Sized,
+    {
+        svg
+    }
+}
+
+/// A placed texture, stored as the list of primitives it is made of.
+#[derive(Debug, Clone, Default)]
+pub struct TextureTool {
+    strokes: Vec<TextureStroke>,
+    style: Style,
+}
+
+impl Serialize<Document> for TextureTool {
+    fn serialize(&self) -> Document {
+        doc! {
+            "strokes": self.strokes.iter().map(|stroke| stroke.serialize()).collect::<Vec<Document>>().as_slice(),
+            "style": Document::from(self.style.serialize()),
+        }
+    }
+}
+
+impl Deserialize<Document> for TextureTool {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut texture = TextureTool::default();
+
+        if let Some(Bson::Array(strokes)) = document.get("strokes") {
+            for stroke in strokes {
+                if let Bson::Document(stroke) = stroke {
+                    texture.strokes.push(TextureStroke::deserialize(stroke));
+                }
+            }
+        }
+        if let Some(Bson::Document(style)) = document.get("style") {
+            texture.style = Style::deserialize(style);
+        }
+
+        texture
+    }
+}
+
+impl Serialize<Group> for TextureTool {
+    fn serialize(&self) -> Group {
+        self.strokes
+            .iter()
+            .fold(Group::new().set("class", self.id()), |svg, stroke| {
+                stroke.add_to_svg(svg, &self.style)
+            })
+    }
+}
+
+impl Serialize<Object> for TextureTool {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert(
+            "strokes",
+            JsonValue::Array(
+                self.strokes
+                    .iter()
+                    .map(|stroke| JsonValue::Object(stroke.serialize()))
+                    .collect(),
+            ),
+        );
+        data.insert("style", JsonValue::Object(self.style.serialize()));
+
+        data
+    }
+}
+
+impl Deserialize<Object> for TextureTool {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut texture = TextureTool::default();
+
+        if let Some(JsonValue::Array(strokes)) = document.get("strokes") {
+            for stroke in strokes {
+                if let JsonValue::Object(stroke) = stroke {
+                    texture.strokes.push(TextureStroke::deserialize(stroke));
+                }
+            }
+        }
+        if let Some(JsonValue::Object(style)) = document.get("style") {
+            texture.style = Style::deserialize(style);
+        }
+
+        texture
+    }
+}
+
+impl Tool for TextureTool {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        if let Some((_, color, _, _)) = self.style.stroke {
+            for stroke in &self.strokes {
+                stroke.add_to_frame(frame, color);
+            }
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        "Texture".into()
+    }
+}
+
+impl Into<Box<dyn Tool>> for Box<TextureBrush> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
@@ -0,0 +1,454 @@
+use crate::canvas::layer::CanvasMessage;
+use crate::canvas::style::Style;
+use crate::utils::serde::{Deserialize, Serialize};
+use iced::event::Status;
+use iced::keyboard::Key;
+use iced::mouse::Cursor;
+use iced::widget::canvas::gradient::Linear;
+use iced::widget::canvas::{self, Event, Fill, Frame, Geometry, Gradient, Path, Stroke};
+use iced::{keyboard, mouse, Color, Point, Rectangle, Renderer, Size};
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document, Uuid};
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+use svg::node::element::{Definitions, Group, LinearGradient, RadialGradient, Stop};
+
+use crate::canvas::tool::{Pending, Tool};
+
+/// The number of rings used to approximate a radial gradient on the canvas.
+const RADIAL_STEPS: usize = 64;
+
+/// The ways the colors of a [GradientFillTool] are spread.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    /// The colors change along the line from the start to the end point.
+    #[default]
+    Linear,
+
+    /// The colors change with the distance from the start point.
+    Radial,
+}
+
+impl GradientKind {
+    /// The list of all gradient kinds.
+    pub const ALL: [GradientKind; 2] = [GradientKind::Linear, GradientKind::Radial];
+}
+
+impl Display for GradientKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                GradientKind::Linear => "Linear",
+                GradientKind::Radial => "Radial",
+            }
+        )
+    }
+}
+
+/// Returns the color in #rrggbb format.
+fn to_hex(color: Color) -> String {
+    let data = color.into_rgba8();
+    format!("#{:02x?}{:02x?}{:02x?}", data[0], data[1], data[2])
+}
+
+/// Returns the color at the given ratio between the two colors.
+fn interpolate(start: Color, end: Color, ratio: f32) -> Color {
+    Color::from_rgba(
+        start.r + (end.r - start.r) * ratio,
+        start.g + (end.g - start.g) * ratio,
+        start.b + (end.b - start.b) * ratio,
+        start.a + (end.a - start.a) * ratio,
+    )
+}
+
+#[derive(Clone)]
+pub enum GradientPending {
+    None,
+    Start(Point),
+}
+
+impl Pending for GradientPending {
+    fn update(
+        &mut self,
+        event: Event,
+        cursor: Point,
+        style: Style,
+    ) -> (Status, Option<CanvasMessage>) {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let message = match mouse_event {
+                    mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                        *self = GradientPending::Start(cursor);
+                        None
+                    }
+                    mouse::Event::ButtonReleased(mouse::Button::Left) => match self {
+                        GradientPending::None => None,
+                        GradientPending::Start(start) => {
+                            let tool = GradientFillTool::new(*start, cursor, &style);
+                            *self = GradientPending::None;
+
+                            if tool.get_bounds().area() > 0.0 {
+                                Some(CanvasMessage::UseTool(Arc::new(tool)))
+                            } else {
+                                None
+                            }
+                        }
+                    },
+                    _ => None,
+                };
+
+                (Status::Captured, message)
+            }
+            Event::Keyboard(key_event) => match key_event {
+                keyboard::Event::KeyPressed {
+                    key: Key::Character(key),
+                    ..
+                } => {
+                    let value = key.as_str();
+                    if value == "S" || value == "s" {
+                        *self = GradientPending::None;
+
+                        (Status::Captured, None)
+                    } else {
+                        (Status::Ignored, None)
+                    }
+                }
+                _ => (Status::Ignored, None),
+            },
+            _ => (Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        renderer: &Renderer,
+        bounds: Rectangle,
+        cursor: Cursor,
+        style: Style,
+    ) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if let Some(cursor_position) = cursor.position_in(bounds) {
+            match self {
+                GradientPending::None => {}
+                GradientPending::Start(start) => {
+                    let tool = GradientFillTool::new(*start, cursor_position, &style);
+                    tool.add_to_frame(&mut frame);
+
+                    frame.stroke(
+                        &Path::line(*start, cursor_position),
+                        Stroke::default()
+                            .with_width(1.0)
+                            .with_color(Color::from_rgba8(0, 255, 255, 0.8)),
+                    );
+                }
+            }
+        };
+
+        frame.into_geometry()
+    }
+
+    fn shape_style(&self, style: &mut Style) {
+        style.stroke = None;
+        style.fill = None;
+        if style.gradient.is_none() {
+            style.gradient = Some((GradientKind::Linear, Color::BLACK, Color::WHITE, false));
+        }
+    }
+
+    fn id(&self) -> String {
+        String::from("Gradient")
+    }
+
+    fn default() -> Self
+    where
+        Self: Sized,
+    {
+        GradientPending::None
+    }
+
+    fn dyn_default(&self) -> Box<dyn Pending> {
+        Box::new(GradientPending::None)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pending> {
+        Box::new((*self).clone())
+    }
+}
+
+/// A rectangle filled with a gradient going from the start point to the end point. Linear
+/// gradients fill the rectangle between the two points, and radial gradients fill the square
+/// around the circle centered in the start point.
+#[derive(Debug, Clone)]
+pub struct GradientFillTool {
+    start: Point,
+    end: Point,
+    start_color: Color,
+    end_color: Color,
+    kind: GradientKind,
+
+    /// Identifies the gradient definition in the svg.
+    gradient_id: Uuid,
+}
+
+impl GradientFillTool {
+    /// Creates a gradient between the two points, with the colors picked in the [Style].
+    fn new(start: Point, end: Point, style: &Style) -> Self {
+        let (kind, start_color, end_color) = style.get_gradient();
+
+        GradientFillTool {
+            start,
+            end,
+            start_color,
+            end_color,
+            kind,
+            gradient_id: Uuid::new(),
+        }
+    }
+
+    /// Returns the area covered by the gradient.
+    fn get_bounds(&self) -> Rectangle {
+        match self.kind {
+            GradientKind::Linear => Rectangle::new(
+                Point::new(self.start.x.min(self.end.x), self.start.y.min(self.end.y)),
+                Size::new(
+                    (self.end.x - self.start.x).abs(),
+                    (self.end.y - self.start.y).abs(),
+                ),
+            ),
+            GradientKind::Radial => {
+                let radius = self.start.distance(self.end);
+
+                Rectangle::new(
+                    Point::new(self.start.x - radius, self.start.y - radius),
+                    Size::new(2.0 * radius, 2.0 * radius),
+                )
+            }
+        }
+    }
+
+    /// Returns the color stops of the gradient, in svg form.
+    fn get_stops(&self) -> [Stop; 2] {
+        [(0.0, self.start_color), (1.0, self.end_color)].map(|(offset, color)| {
+            Stop::new()
+                .set("offset", offset)
+                .set("stop-color", to_hex(color))
+                .set("stop-opacity", color.a)
+        })
+    }
+}
+
+impl Default for GradientFillTool {
+    fn default() -> Self {
+        GradientFillTool {
+            start: Point::default(),
+            end: Point::default(),
+            start_color: Color::BLACK,
+            end_color: Color::WHITE,
+            kind: GradientKind::Linear,
+            gradient_id: Uuid::new(),
+        }
+    }
+}
+
+impl Serialize<Document> for GradientFillTool {
+    fn serialize(&self) -> Document {
+        doc! {
+            "start": Document::from(self.start.serialize()),
+            "end": Document::from(self.end.serialize()),
+            "start_color": Document::from(self.start_color.serialize()),
+            "end_color": Document::from(self.end_color.serialize()),
+            "kind": self.kind.to_string(),
+            "gradient_id": self.gradient_id,
+        }
+    }
+}
+
+impl Deserialize<Document> for GradientFillTool {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut gradient = GradientFillTool::default();
+
+        if let Some(Bson::Document(start)) = document.get("start") {
+            gradient.start = Point::deserialize(start);
+        }
+        if let Some(Bson::Document(end)) = document.get("end") {
+            gradient.end = Point::deserialize(end);
+        }
+        if let Some(Bson::Document(start_color)) = document.get("start_color") {
+            gradient.start_color = Color::deserialize(start_color);
+        }
+        if let Some(Bson::Document(end_color)) = document.get("end_color") {
+            gradient.end_color = Color::deserialize(end_color);
+        }
+        if let Some(Bson::String(kind)) = document.get("kind") {
+            if kind == "Radial" {
+                gradient.kind = GradientKind::Radial;
+            }
+        }
+        if let Some(Bson::Binary(gradient_id)) = document.get("gradient_id") {
+            if let Ok(gradient_id) = gradient_id.to_uuid() {
+                gradient.gradient_id = gradient_id;
+            }
+        }
+
+        gradient
+    }
+}
+
+impl Serialize<Group> for GradientFillTool {
+    fn serialize(&self) -> Group {
+        let id = format!("gradient-{}", self.gradient_id);
+        let [start_stop, end_stop] = self.get_stops();
+
+        let definitions = match self.kind {
+            GradientKind::Linear => Definitions::new().add(
+                LinearGradient::new()
+                    .set("id", id.clone())
+                    .set("gradientUnits", "userSpaceOnUse")
+                    .set("x1", self.start.x)
+                    .set("y1", self.start.y)
+                    .set("x2", self.end.x)
+                    .set("y2", self.end.y)
+                    .add(start_stop)
+                    .add(end_stop),
+            ),
+            GradientKind::Radial => Definitions::new().add(
+                RadialGradient::new()
+                    .set("id", id.clone())
+                    .set("gradientUnits", "userSpaceOnUse")
+                    .set("cx", self.start.x)
+                    .set("cy", self.start.y)
+                    .set("r", self.start.distance(self.end))
+                    .add(start_stop)
+                    .add(end_stop),
+            ),
+        };
+
+        let bounds = self.get_bounds();
+        let rect = svg::node::element::Rectangle::new()
+            .set("x", bounds.x)
+            .set("y", bounds.y)
+            .set("width", bounds.width)
+            .set("height", bounds.height)
+            .set("fill", format!("url(#{})", id));
+
+        Group::new()
+            .set("class", self.id())
+            .add(definitions)
+            .add(rect)
+    }
+}
+
+impl Serialize<Object> for GradientFillTool {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert("start", JsonValue::Object(self.start.serialize()));
+        data.insert("end", JsonValue::Object(self.end.serialize()));
+        data.insert(
+            "start_color",
+            JsonValue::Object(self.start_color.serialize()),
+        );
+        data.insert("end_color", JsonValue::Object(self.end_color.serialize()));
+        data.insert("kind", JsonValue::String(self.kind.to_string()));
+        data.insert(
+            "gradient_id",
+            JsonValue::String(self.gradient_id.to_string()),
+        );
+
+        data
+    }
+}
+
+impl Deserialize<Object> for GradientFillTool {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut gradient = GradientFillTool::default();
+
+        if let Some(JsonValue::Object(start)) = document.get("start") {
+            gradient.start = Point::deserialize(start);
+        }
+        if let Some(JsonValue::Object(end)) = document.get("end") {
+            gradient.end = Point::deserialize(end);
+        }
+        if let Some(JsonValue::Object(start_color)) = document.get("start_color") {
+            gradient.start_color = Color::deserialize(start_color);
+        }
+        if let Some(JsonValue::Object(end_color)) = document.get("end_color") {
+            gradient.end_color = Color::deserialize(end_color);
+        }
+        if let Some("Radial") = document.get("kind").and_then(|kind| kind.as_str()) {
+            gradient.kind = GradientKind::Radial;
+        }
+        if let Some(gradient_id) = document
+            .get("gradient_id")
+            .and_then(|gradient_id| gradient_id.as_str())
+            .and_then(|gradient_id| Uuid::parse_str(gradient_id).ok())
+        {
+            gradient.gradient_id = gradient_id;
+        }
+
+        gradient
+    }
+}
+
+impl Tool for GradientFillTool {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        let bounds = self.get_bounds();
+
+        match self.kind {
+            GradientKind::Linear => {
+                let linear = Linear::new(self.start, self.end)
+                    .add_stop(0.0, self.start_color)
+                    .add_stop(1.0, self.end_color);
+
+                frame.fill(
+                    &Path::rectangle(bounds.position(), bounds.size()),
+                    Fill {
+                        style: canvas::Style::Gradient(Gradient::Linear(linear)),
+                        ..Fill::default()
+                    },
+                );
+            }
+            GradientKind::Radial => {
+                let radius = self.start.distance(self.end);
+
+                // The canvas only draws linear gradients, so the radial one is approximated by
+                // rings, with the end color filling the corners of the square.
+                frame.fill(
+                    &Path::rectangle(bounds.position(), bounds.size()),
+                    Fill::from(self.end_color),
+                );
+                for step in (0..RADIAL_STEPS).rev() {
+                    let ratio = step as f32 / RADIAL_STEPS as f32;
+                    frame.fill(
+                        &Path::circle(self.start, radius * (step + 1) as f32 / RADIAL_STEPS as f32),
+                        Fill::from(interpolate(self.start_color, self.end_color, ratio)),
+                    );
+                }
+            }
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        "Gradient".into()
+    }
+}
+
+impl Into<Box<dyn Tool>> for Box<GradientFillTool> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
@@ -1,8 +1,16 @@
+pub mod arc;
+pub mod bezier;
 pub mod brush;
 pub mod brushes;
 pub mod circle;
 pub mod ellipse;
+pub mod gradient;
 pub mod line;
 pub mod polygon;
+pub mod raster;
 pub mod rect;
+pub mod smudge;
+pub mod stamp;
+pub mod star;
+pub mod symmetry;
 pub mod triangle;
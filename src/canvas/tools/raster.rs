@@ -0,0 +1,260 @@
+use crate::debug_message;
+use crate::utils::errors::Error;
+use crate::utils::serde::{Deserialize, Serialize};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use iced::widget::canvas::Frame;
+use iced::{Color, Point, Rectangle, Size, Vector};
+use image::imageops::FilterType;
+use image::RgbaImage;
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use svg::node::element::{Group, Image};
+
+use crate::canvas::tool::Tool;
+
+/// Lets the user choose an image and creates the [RasterTool] placing it in the top-left corner
+/// of a drawing with the given size, shrunk to fit if it is larger than the drawing.
+pub async fn import_image(size: (u32, u32)) -> Result<RasterTool, Error> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("image", &["png", "jpg", "jpeg", "webp"])
+        .set_directory("~")
+        .pick_file()
+        .await
+        .ok_or(debug_message!("Error getting file path.").into())?;
+    let data = file.read().await;
+
+    tokio::task::spawn_blocking(move || {
+        let (width, height) = image::load_from_memory(&data)
+            .map(|image| (image.width() as f32, image.height() as f32))
+            .map_err(|err| debug_message!("{}", err).into())?;
+        let scale = (size.0 as f32 / width).min(size.1 as f32 / height).min(1.0);
+
+        Ok::<RasterTool, Error>(RasterTool::new(
+            data,
+            Rectangle::new(Point::ORIGIN, Size::new(width * scale, height * scale)),
+        ))
+    })
+    .await
+    .map_err(|err| debug_message!("{}", err).into())?
+}
+
+/// A raster image placed on the drawing.
+#[derive(Clone)]
+pub struct RasterTool {
+    /// The encoded image, in the format it was imported in.
+    data: Vec<u8>,
+
+    /// The area of the drawing covered by the image.
+    bounds: Rectangle,
+
+    /// The decoded image, resized to one pixel per unit of the drawing.
+    pixels: Arc<RgbaImage>,
+}
+
+impl RasterTool {
+    /// Creates a [RasterTool] covering the given area with the given encoded image.
+    pub fn new(data: Vec<u8>, bounds: Rectangle) -> Self {
+        let pixels = image::load_from_memory(&data)
+            .map(|image| {
+                image
+                    .resize_exact(
+                        bounds.width.round().max(1.0) as u32,
+                        bounds.height.round().max(1.0) as u32,
+                        FilterType::Triangle,
+                    )
+                    .to_rgba8()
+            })
+            .unwrap_or_default();
+
+        RasterTool {
+            data,
+            bounds,
+            pixels: Arc::new(pixels),
+        }
+    }
+
+    /// Returns the mime type of the encoded image.
+    fn get_mime_type(&self) -> &'static str {
+        image::guess_format(&self.data).map_or("image/png", |format| format.to_mime_type())
+    }
+}
+
+impl Debug for RasterTool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RasterTool")
+            .field("data", &format!("{} bytes", self.data.len()))
+            .field("bounds", &self.bounds)
+            .finish()
+    }
+}
+
+impl Default for RasterTool {
+    fn default() -> Self {
+        RasterTool {
+            data: vec![],
+            bounds: Rectangle::default(),
+            pixels: Arc::new(RgbaImage::new(0, 0)),
+        }
+    }
+}
+
+impl Serialize<Document> for RasterTool {
+    fn serialize(&self) -> Document {
+        doc! {
+            "data": STANDARD.encode(&self.data),
+            "x": self.bounds.x,
+            "y": self.bounds.y,
+            "width": self.bounds.width,
+            "height": self.bounds.height,
+        }
+    }
+}
+
+impl Deserialize<Document> for RasterTool {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut bounds = Rectangle::default();
+
+        if let Some(Bson::Double(x)) = document.get("x") {
+            bounds.x = *x as f32;
+        }
+        if let Some(Bson::Double(y)) = document.get("y") {
+            bounds.y = *y as f32;
+        }
+        if let Some(Bson::Double(width)) = document.get("width") {
+            bounds.width = *width as f32;
+        }
+        if let Some(Bson::Double(height)) = document.get("height") {
+            bounds.height = *height as f32;
+        }
+
+        match document
+            .get_str("data")
+            .ok()
+            .and_then(|data| STANDARD.decode(data).ok())
+        {
+            Some(data) => RasterTool::new(data, bounds),
+            None => RasterTool {
+                bounds,
+                ..RasterTool::default()
+            },
+        }
+    }
+}
+
+impl Serialize<Group> for RasterTool {
+    fn serialize(&self) -> Group {
+        let image = Image::new()
+            .set("x", self.bounds.x)
+            .set("y", self.bounds.y)
+            .set("width", self.bounds.width)
+            .set("height", self.bounds.height)
+            .set("preserveAspectRatio", "none")
+            .set(
+                "href",
+                format!(
+                    "data:{};base64,{}",
+                    self.get_mime_type(),
+                    STANDARD.encode(&self.data)
+                ),
+            );
+
+        Group::new().set("class", self.id()).add(image)
+    }
+}
+
+impl Serialize<Object> for RasterTool {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert("data", JsonValue::String(STANDARD.encode(&self.data)));
+        data.insert("x", JsonValue::Number(self.bounds.x.into()));
+        data.insert("y", JsonValue::Number(self.bounds.y.into()));
+        data.insert("width", JsonValue::Number(self.bounds.width.into()));
+        data.insert("height", JsonValue::Number(self.bounds.height.into()));
+
+        data
+    }
+}
+
+impl Deserialize<Object> for RasterTool {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut bounds = Rectangle::default();
+
+        if let Some(JsonValue::Number(x)) = document.get("x") {
+            bounds.x = f32::from(*x);
+        }
+        if let Some(JsonValue::Number(y)) = document.get("y") {
+            bounds.y = f32::from(*y);
+        }
+        if let Some(JsonValue::Number(width)) = document.get("width") {
+            bounds.width = f32::from(*width);
+        }
+        if let Some(JsonValue::Number(height)) = document.get("height") {
+            bounds.height = f32::from(*height);
+        }
+
+        match document
+            .get("data")
+            .and_then(|data| data.as_str())
+            .and_then(|data| STANDARD.decode(data).ok())
+        {
+            Some(data) => RasterTool::new(data, bounds),
+            None => RasterTool {
+                bounds,
+                ..RasterTool::default()
+            },
+        }
+    }
+}
+
+impl Tool for RasterTool {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        if self.pixels.width() == 0 || self.pixels.height() == 0 {
+            return;
+        }
+
+        // The pixels were resized to the bounds, up to rounding.
+        let pixel_size = Size::new(
+            self.bounds.width / self.pixels.width() as f32,
+            self.bounds.height / self.pixels.height() as f32,
+        );
+
+        for (x, y, pixel) in self.pixels.enumerate_pixels() {
+            let [red, green, blue, alpha] = pixel.0;
+            if alpha == 0 {
+                continue;
+            }
+
+            frame.fill_rectangle(
+                self.bounds.position()
+                    + Vector::new(x as f32 * pixel_size.width, y as f32 * pixel_size.height),
+                pixel_size,
+                Color::from_rgba8(red, green, blue, alpha as f32 / 255.0),
+            );
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        "Raster".into()
+    }
+}
+
+impl Into<Box<dyn Tool>> for Box<RasterTool> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
@@ -0,0 +1,455 @@
+use crate::canvas::layer::CanvasMessage;
+use crate::canvas::style::Style;
+use crate::debug_message;
+use crate::utils::encoder;
+use crate::utils::errors::Error;
+use crate::utils::serde::{Deserialize, Serialize};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use iced::event::Status;
+use iced::keyboard::Key;
+use iced::mouse::Cursor;
+use iced::widget::canvas::{Event, Frame, Geometry, LineCap, LineJoin, Path, Stroke};
+use iced::{keyboard, mouse, Color, Point, Rectangle, Renderer, Size};
+use image::{ImageFormat, RgbaImage};
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document};
+use std::convert::identity;
+use std::fmt::Debug;
+use std::io::Cursor as IoCursor;
+use svg::node::element::{Group, Image};
+use tokio::task;
+
+use crate::canvas::tool::{Pending, Tool};
+
+/// The smudge strength used when none has been chosen yet.
+pub const DEFAULT_SMUDGE_STRENGTH: f32 = 4.0;
+
+/// Returns the radius of the area smudged around the cursor for the given strength.
+fn get_brush_radius(strength: f32) -> f32 {
+    2.0 * strength + 5.0
+}
+
+#[derive(Clone)]
+pub enum SmudgePending {
+    None,
+    Smudging(Vec<Point>),
+}
+
+impl Pending for SmudgePending {
+    fn update(
+        &mut self,
+        event: Event,
+        cursor: Point,
+        style: Style,
+    ) -> (Status, Option<CanvasMessage>) {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let message = match mouse_event {
+                    mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                        *self = SmudgePending::Smudging(vec![cursor]);
+                        None
+                    }
+                    mouse::Event::CursorMoved { .. } => {
+                        if let SmudgePending::Smudging(points) = self {
+                            points.push(cursor);
+                        }
+                        None
+                    }
+                    mouse::Event::ButtonReleased(mouse::Button::Left) => match self {
+                        SmudgePending::Smudging(points) => {
+                            let points = points.clone();
+                            *self = SmudgePending::None;
+
+                            Some(CanvasMessage::Smudge(points, style.get_smudge_strength()))
+                        }
+                        SmudgePending::None => None,
+                    },
+                    _ => None,
+                };
+
+                (Status::Captured, message)
+            }
+            Event::Keyboard(key_event) => match key_event {
+                keyboard::Event::KeyPressed {
+                    key: Key::Character(key),
+                    ..
+                } => {
+                    let value = key.as_str();
+                    if value == "S" || value == "s" {
+                        *self = SmudgePending::None;
+
+                        (Status::Captured, None)
+                    } else {
+                        (Status::Ignored, None)
+                    }
+                }
+                _ => (Status::Ignored, None),
+            },
+            _ => (Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        renderer: &Renderer,
+        bounds: Rectangle,
+        cursor: Cursor,
+        style: Style,
+    ) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let radius = get_brush_radius(style.get_smudge_strength());
+        let color = Color::from_rgba8(0, 255, 255, 0.3);
+
+        if let Some(cursor_position) = cursor.position_in(bounds) {
+            match self {
+                SmudgePending::None => {
+                    frame.stroke(
+                        &Path::circle(cursor_position, radius),
+                        Stroke::default().with_width(1.0).with_color(color),
+                    );
+                }
+                SmudgePending::Smudging(points) => {
+                    let path = Path::new(|builder| {
+                        if let Some((first, rest)) = points.split_first() {
+                            builder.move_to(*first);
+                            for point in rest {
+                                builder.line_to(*point);
+                            }
+                        }
+                    });
+
+                    frame.stroke(
+                        &path,
+                        Stroke::default()
+                            .with_width(2.0 * radius)
+                            .with_color(color)
+                            .with_line_cap(LineCap::Round)
+                            .with_line_join(LineJoin::Round),
+                    );
+                }
+            }
+        };
+
+        frame.into_geometry()
+    }
+
+    fn shape_style(&self, style: &mut Style) {
+        style.stroke = None;
+        style.fill = None;
+        if style.smudge.is_none() {
+            style.smudge = Some((DEFAULT_SMUDGE_STRENGTH, false));
+        }
+    }
+
+    fn id(&self) -> String {
+        String::from("Smudge")
+    }
+
+    fn default() -> Self
+    where
+        Self: Sized,
+    {
+        SmudgePending::None
+    }
+
+    fn dyn_default(&self) -> Box<dyn Pending> {
+        Box::new(SmudgePending::None)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pending> {
+        Box::new((*self).clone())
+    }
+}
+
+/// A blurred patch of the drawing, placed over the area that was smudged.
+#[derive(Debug, Clone)]
+pub struct SmudgeTool {
+    /// The position of the top-left corner of the patch.
+    position: Point,
+
+    /// The pixels of the patch, with straight alpha. Pixels outside the smudged area are
+    /// fully transparent.
+    patch: RgbaImage,
+}
+
+/// Returns the normalized one-dimensional Gaussian kernel for the given blur radius.
+pub(crate) fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+    let radius = (3.0 * sigma).ceil() as i32;
+    let kernel: Vec<f32> = (-radius..=radius)
+        .map(|x| (-(x * x) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let sum: f32 = kernel.iter().sum();
+
+    kernel.into_iter().map(|weight| weight / sum).collect()
+}
+
+/// Blurs the given premultiplied pixels along one axis, clamping at the edges.
+pub(crate) fn blur_pass(
+    pixels: &[[f32; 4]],
+    width: usize,
+    height: usize,
+    kernel: &[f32],
+    horizontal: bool,
+) -> Vec<[f32; 4]> {
+    let radius = (kernel.len() / 2) as i64;
+    let mut result = vec![[0.0; 4]; pixels.len()];
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = [0.0; 4];
+
+            for (index, weight) in kernel.iter().enumerate() {
+                let offset = index as i64 - radius;
+                let (sample_x, sample_y) = if horizontal {
+                    ((x as i64 + offset).clamp(0, width as i64 - 1) as usize, y)
+                } else {
+                    (x, (y as i64 + offset).clamp(0, height as i64 - 1) as usize)
+                };
+
+                let sample = pixels[sample_y * width + sample_x];
+                for channel in 0..4 {
+                    sum[channel] += sample[channel] * weight;
+                }
+            }
+
+            result[y * width + x] = sum;
+        }
+    }
+
+    result
+}
+
+/// Creates the [SmudgeTool] for the given stroke, by rendering the drawing, applying a
+/// Gaussian blur with the given strength around the stroke and cutting out the blurred area.
+pub async fn create_smudge(
+    drawing: svg::Document,
+    points: Vec<Point>,
+    strength: f32,
+) -> Result<SmudgeTool, Error> {
+    task::spawn_blocking(move || {
+        let pixmap = encoder::render_svg(&*drawing.to_string())?;
+        let (image_width, image_height) = (pixmap.width() as i64, pixmap.height() as i64);
+
+        let brush_radius = get_brush_radius(strength);
+        let kernel = gaussian_kernel(strength.max(0.5));
+        let margin = brush_radius.ceil() as i64 + (kernel.len() / 2) as i64;
+
+        let (min, max) = points.iter().fold(
+            (
+                Point::new(f32::INFINITY, f32::INFINITY),
+                Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+            ),
+            |(min, max), point| {
+                (
+                    Point::new(min.x.min(point.x), min.y.min(point.y)),
+                    Point::new(max.x.max(point.x), max.y.max(point.y)),
+                )
+            },
+        );
+
+        let left = (min.x.floor() as i64 - margin).clamp(0, image_width);
+        let top = (min.y.floor() as i64 - margin).clamp(0, image_height);
+        let right = (max.x.ceil() as i64 + margin).clamp(0, image_width);
+        let bottom = (max.y.ceil() as i64 + margin).clamp(0, image_height);
+        if left >= right || top >= bottom {
+            return Err(debug_message!("The smudged area is outside of the drawing.").into());
+        }
+
+        let (width, height) = ((right - left) as usize, (bottom - top) as usize);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in top..bottom {
+            for x in left..right {
+                let pixel = pixmap.pixels()[(y * image_width + x) as usize];
+                pixels.push([
+                    pixel.red() as f32,
+                    pixel.green() as f32,
+                    pixel.blue() as f32,
+                    pixel.alpha() as f32,
+                ]);
+            }
+        }
+
+        let pixels = blur_pass(&pixels, width, height, &kernel, true);
+        let pixels = blur_pass(&pixels, width, height, &kernel, false);
+
+        // Only the pixels close to the stroke are kept, by stamping the brush on every point.
+        let mut mask = vec![false; width * height];
+        let radius = brush_radius.ceil() as i64;
+        for point in &points {
+            let (center_x, center_y) = (point.x as i64 - left, point.y as i64 - top);
+
+            for y in (center_y - radius).max(0)..(center_y + radius + 1).min(height as i64) {
+                for x in (center_x - radius).max(0)..(center_x + radius + 1).min(width as i64) {
+                    let (dx, dy) = ((x - center_x) as f32, (y - center_y) as f32);
+                    if dx.hypot(dy) <= brush_radius {
+                        mask[y as usize * width + x as usize] = true;
+                    }
+                }
+            }
+        }
+
+        let mut patch = RgbaImage::new(width as u32, height as u32);
+        for (index, pixel) in pixels.iter().enumerate() {
+            if !mask[index] || pixel[3] < 0.5 {
+                continue;
+            }
+
+            // The rendered pixels are premultiplied, while the patch holds straight colors.
+            let alpha = pixel[3] / 255.0;
+            patch.put_pixel(
+                (index % width) as u32,
+                (index / width) as u32,
+                image::Rgba([
+                    (pixel[0] / alpha).round().min(255.0) as u8,
+                    (pixel[1] / alpha).round().min(255.0) as u8,
+                    (pixel[2] / alpha).round().min(255.0) as u8,
+                    pixel[3].round() as u8,
+                ]),
+            );
+        }
+
+        Ok(SmudgeTool {
+            position: Point::new(left as f32, top as f32),
+            patch,
+        })
+    })
+    .await
+    .map_or_else(|err| Err(err.to_string().into()), identity)
+}
+
+impl SmudgeTool {
+    /// Returns the patch encoded as a png, in base64 form.
+    fn get_encoded_patch(&self) -> String {
+        let mut buffer = IoCursor::new(vec![]);
+        match self.patch.write_to(&mut buffer, ImageFormat::Png) {
+            Ok(()) => STANDARD.encode(buffer.into_inner()),
+            Err(_) => String::new(),
+        }
+    }
+
+    /// Decodes a patch stored in base64 png form.
+    fn decode_patch(data: &str) -> Option<RgbaImage> {
+        let bytes = STANDARD.decode(data).ok()?;
+        let image = image::load_from_memory_with_format(&bytes, ImageFormat::Png).ok()?;
+
+        Some(image.to_rgba8())
+    }
+}
+
+impl Default for SmudgeTool {
+    fn default() -> Self {
+        SmudgeTool {
+            position: Point::ORIGIN,
+            patch: RgbaImage::new(0, 0),
+        }
+    }
+}
+
+impl Serialize<Document> for SmudgeTool {
+    fn serialize(&self) -> Document {
+        doc! {
+            "position": Document::from(self.position.serialize()),
+            "patch": self.get_encoded_patch(),
+        }
+    }
+}
+
+impl Deserialize<Document> for SmudgeTool {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut smudge = SmudgeTool::default();
+
+        if let Some(Bson::Document(position)) = document.get("position") {
+            smudge.position = Point::deserialize(position);
+        }
+        if let Some(Bson::String(patch)) = document.get("patch") {
+            if let Some(patch) = SmudgeTool::decode_patch(patch) {
+                smudge.patch = patch;
+            }
+        }
+
+        smudge
+    }
+}
+
+impl Serialize<Group> for SmudgeTool {
+    fn serialize(&self) -> Group {
+        let image = Image::new()
+            .set("x", self.position.x)
+            .set("y", self.position.y)
+            .set("width", self.patch.width())
+            .set("height", self.patch.height())
+            .set(
+                "href",
+                format!("data:image/png;base64,{}", self.get_encoded_patch()),
+            );
+
+        Group::new().set("class", self.id()).add(image)
+    }
+}
+
+impl Serialize<Object> for SmudgeTool {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert("position", JsonValue::Object(self.position.serialize()));
+        data.insert("patch", JsonValue::String(self.get_encoded_patch()));
+
+        data
+    }
+}
+
+impl Deserialize<Object> for SmudgeTool {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut smudge = SmudgeTool::default();
+
+        if let Some(JsonValue::Object(position)) = document.get("position") {
+            smudge.position = Point::deserialize(position);
+        }
+        if let Some(patch) = document.get("patch").and_then(|patch| patch.as_str()) {
+            if let Some(patch) = SmudgeTool::decode_patch(patch) {
+                smudge.patch = patch;
+            }
+        }
+
+        smudge
+    }
+}
+
+impl Tool for SmudgeTool {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        for (x, y, pixel) in self.patch.enumerate_pixels() {
+            let [red, green, blue, alpha] = pixel.0;
+            if alpha == 0 {
+                continue;
+            }
+
+            frame.fill_rectangle(
+                Point::new(self.position.x + x as f32, self.position.y + y as f32),
+                Size::new(1.0, 1.0),
+                Color::from_rgba8(red, green, blue, alpha as f32 / 255.0),
+            );
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        "Smudge".into()
+    }
+}
+
+impl Into<Box<dyn Tool>> for Box<SmudgeTool> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
@@ -0,0 +1,469 @@
+use crate::canvas::layer::CanvasMessage;
+use crate::canvas::style::Style;
+use crate::debug_message;
+use crate::utils::errors::Error;
+use crate::utils::serde::{Deserialize, Serialize};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use directories::ProjectDirs;
+use iced::event::Status;
+use iced::keyboard::Key;
+use iced::mouse::{Cursor, ScrollDelta};
+use iced::widget::canvas::{Event, Frame, Geometry, Path, Stroke};
+use iced::{keyboard, mouse, Color, Point, Rectangle, Renderer, Size, Vector};
+use image::{ImageFormat, RgbaImage};
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document, Uuid, UuidRepresentation};
+use std::f32::consts::PI;
+use std::fmt::{Debug, Formatter};
+use std::io::Cursor as IoCursor;
+use std::path::PathBuf;
+use std::sync::Arc;
+use svg::node::element::{Group, Image};
+
+use crate::canvas::tool::{Pending, Tool};
+
+/// The factor the scale of the stamp changes by for every step of the mouse wheel.
+const SCALE_STEP: f32 = 1.1;
+
+/// The angle the stamp is rotated by for every press of the rotation key.
+const ROTATION_STEP: f32 = PI / 12.0;
+
+/// Returns the path of the directory holding the stamps.
+fn get_stamps_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "CharMe", "Chartsy").map(|dirs| dirs.data_local_dir().join("stamps"))
+}
+
+/// Loads the image of the stamp with the given id from the stamps directory.
+pub fn load_stamp(stamp_id: Uuid) -> Option<RgbaImage> {
+    let path = get_stamps_dir()?.join(format!("{}.webp", stamp_id));
+
+    image::open(path).ok().map(|image| image.to_rgba8())
+}
+
+/// Lets the user choose an image and stores it as a new stamp, returning its id.
+pub async fn import_stamp() -> Result<Uuid, Error> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("image", &["png", "jpg", "jpeg", "webp", "tiff", "bmp"])
+        .set_directory("~")
+        .pick_file()
+        .await
+        .ok_or(debug_message!("Error getting file path.").into())?;
+    let dir_path =
+        get_stamps_dir().ok_or(debug_message!("Unable to find project directory.").into())?;
+    let data = file.read().await;
+
+    let webp = tokio::task::spawn_blocking(move || {
+        let image =
+            image::load_from_memory(&data).map_err(|err| debug_message!("{}", err).into())?;
+        let mut buffer = IoCursor::new(vec![]);
+        image
+            .to_rgba8()
+            .write_to(&mut buffer, ImageFormat::WebP)
+            .map_err(|err| debug_message!("{}", err).into())?;
+
+        Ok::<Vec<u8>, Error>(buffer.into_inner())
+    })
+    .await
+    .map_err(|err| debug_message!("{}", err).into())??;
+
+    let stamp_id = Uuid::new();
+    tokio::fs::create_dir_all(dir_path.clone())
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?;
+    tokio::fs::write(dir_path.join(format!("{}.webp", stamp_id)), webp)
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    Ok(stamp_id)
+}
+
+/// Creates the [StampTool] placing the given stamp, once its image is loaded.
+pub async fn create_stamp(
+    stamp_id: Uuid,
+    position: Point,
+    scale: f32,
+    rotation: f32,
+) -> Result<StampTool, Error> {
+    let image = tokio::task::spawn_blocking(move || load_stamp(stamp_id))
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?
+        .ok_or(debug_message!("The stamp {} could not be loaded.", stamp_id).into())?;
+
+    Ok(StampTool {
+        stamp_id,
+        position,
+        scale,
+        rotation,
+        image: Some(Arc::new(image)),
+    })
+}
+
+/// The pending state of the stamp tool, placing the stamp with the given id on click.
+#[derive(Clone)]
+pub struct StampPending {
+    /// The id of the stamp being placed.
+    stamp_id: Uuid,
+
+    /// The size of the image of the stamp.
+    size: Size,
+
+    /// The scale the stamp is placed at.
+    scale: f32,
+
+    /// The rotation the stamp is placed at, in radians.
+    rotation: f32,
+}
+
+impl StampPending {
+    /// Creates the pending state for placing the stamp with the given id.
+    pub fn new(stamp_id: Uuid) -> Self {
+        let size = get_stamps_dir()
+            .and_then(|dir| image::image_dimensions(dir.join(format!("{}.webp", stamp_id))).ok())
+            .map_or(Size::ZERO, |(width, height)| {
+                Size::new(width as f32, height as f32)
+            });
+
+        StampPending {
+            stamp_id,
+            size,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl Pending for StampPending {
+    fn update(
+        &mut self,
+        event: Event,
+        cursor: Point,
+        _style: Style,
+    ) -> (Status, Option<CanvasMessage>) {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if self.size == Size::ZERO {
+                    return (Status::Ignored, None);
+                }
+
+                (
+                    Status::Captured,
+                    Some(CanvasMessage::PlaceStamp(
+                        self.stamp_id,
+                        cursor,
+                        self.scale,
+                        self.rotation,
+                    )),
+                )
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                let steps = match delta {
+                    ScrollDelta::Lines { y, .. } => y,
+                    ScrollDelta::Pixels { y, .. } => y / 20.0,
+                };
+                self.scale *= SCALE_STEP.powf(steps);
+
+                (Status::Captured, None)
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Character(key),
+                modifiers,
+                ..
+            }) => {
+                let value = key.as_str();
+                if value == "R" || value == "r" {
+                    self.rotation += if modifiers.shift() {
+                        -ROTATION_STEP
+                    } else {
+                        ROTATION_STEP
+                    };
+
+                    (Status::Captured, None)
+                } else {
+                    (Status::Ignored, None)
+                }
+            }
+            _ => (Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        renderer: &Renderer,
+        bounds: Rectangle,
+        cursor: Cursor,
+        _style: Style,
+    ) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if let Some(cursor_position) = cursor.position_in(bounds) {
+            let size = Size::new(self.size.width * self.scale, self.size.height * self.scale);
+
+            frame.with_save(|frame| {
+                frame.translate(Vector::new(cursor_position.x, cursor_position.y));
+                frame.rotate(self.rotation);
+                frame.stroke(
+                    &Path::rectangle(Point::new(-size.width / 2.0, -size.height / 2.0), size),
+                    Stroke::default()
+                        .with_width(1.0)
+                        .with_color(Color::from_rgba8(0, 255, 255, 0.6)),
+                );
+            });
+        }
+
+        frame.into_geometry()
+    }
+
+    fn shape_style(&self, style: &mut Style) {
+        style.stroke = None;
+        style.fill = None;
+    }
+
+    fn id(&self) -> String {
+        String::from("Stamp")
+    }
+
+    fn default() -> Self
+    where
+        Self: Sized,
+    {
+        StampPending {
+            stamp_id: Uuid::from_bytes([0; 16]),
+            size: Size::ZERO,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    fn dyn_default(&self) -> Box<dyn Pending> {
+        Box::new(StampPending {
+            scale: 1.0,
+            rotation: 0.0,
+            ..self.clone()
+        })
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pending> {
+        Box::new((*self).clone())
+    }
+}
+
+/// A stamp placed on the drawing.
+#[derive(Clone)]
+pub struct StampTool {
+    /// The id of the stamp, which names its image in the stamps directory.
+    stamp_id: Uuid,
+
+    /// The position of the center of the stamp.
+    position: Point,
+
+    /// The scale of the stamp.
+    scale: f32,
+
+    /// The rotation of the stamp around its center, in radians.
+    rotation: f32,
+
+    /// The image of the stamp. Is None if it is missing from the stamps directory.
+    image: Option<Arc<RgbaImage>>,
+}
+
+impl StampTool {
+    /// Returns the image encoded as a png, in base64 form.
+    fn get_encoded_image(&self) -> String {
+        let Some(image) = &self.image else {
+            return String::new();
+        };
+
+        let mut buffer = IoCursor::new(vec![]);
+        match image.write_to(&mut buffer, ImageFormat::Png) {
+            Ok(()) => STANDARD.encode(buffer.into_inner()),
+            Err(_) => String::new(),
+        }
+    }
+}
+
+impl Debug for StampTool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StampTool")
+            .field("stamp_id", &self.stamp_id)
+            .field("position", &self.position)
+            .field("scale", &self.scale)
+            .field("rotation", &self.rotation)
+            .finish()
+    }
+}
+
+impl Default for StampTool {
+    fn default() -> Self {
+        StampTool {
+            stamp_id: Uuid::from_bytes([0; 16]),
+            position: Point::ORIGIN,
+            scale: 1.0,
+            rotation: 0.0,
+            image: None,
+        }
+    }
+}
+
+impl Serialize<Document> for StampTool {
+    fn serialize(&self) -> Document {
+        doc! {
+            "id": self.stamp_id,
+            "x": self.position.x,
+            "y": self.position.y,
+            "scale": self.scale,
+            "rotation": self.rotation,
+        }
+    }
+}
+
+impl Deserialize<Document> for StampTool {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut stamp = StampTool::default();
+
+        if let Some(Bson::Binary(bin)) = document.get("id") {
+            if let Ok(stamp_id) = bin.to_uuid_with_representation(UuidRepresentation::Standard) {
+                stamp.stamp_id = stamp_id;
+            }
+        }
+        if let Some(Bson::Double(x)) = document.get("x") {
+            stamp.position.x = *x as f32;
+        }
+        if let Some(Bson::Double(y)) = document.get("y") {
+            stamp.position.y = *y as f32;
+        }
+        if let Some(Bson::Double(scale)) = document.get("scale") {
+            stamp.scale = *scale as f32;
+        }
+        if let Some(Bson::Double(rotation)) = document.get("rotation") {
+            stamp.rotation = *rotation as f32;
+        }
+        stamp.image = load_stamp(stamp.stamp_id).map(Arc::new);
+
+        stamp
+    }
+}
+
+impl Serialize<Group> for StampTool {
+    fn serialize(&self) -> Group {
+        let Some(image) = &self.image else {
+            return Group::new().set("class", self.id());
+        };
+
+        let image = Image::new()
+            .set("x", -(image.width() as f32) / 2.0)
+            .set("y", -(image.height() as f32) / 2.0)
+            .set("width", image.width())
+            .set("height", image.height())
+            .set(
+                "href",
+                format!("data:image/png;base64,{}", self.get_encoded_image()),
+            );
+
+        Group::new()
+            .set("class", self.id())
+            .set(
+                "transform",
+                format!(
+                    "translate({} {}) rotate({}) scale({})",
+                    self.position.x,
+                    self.position.y,
+                    self.rotation.to_degrees(),
+                    self.scale
+                ),
+            )
+            .add(image)
+    }
+}
+
+impl Serialize<Object> for StampTool {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert("id", JsonValue::String(self.stamp_id.to_string()));
+        data.insert("x", JsonValue::Number(self.position.x.into()));
+        data.insert("y", JsonValue::Number(self.position.y.into()));
+        data.insert("scale", JsonValue::Number(self.scale.into()));
+        data.insert("rotation", JsonValue::Number(self.rotation.into()));
+
+        data
+    }
+}
+
+impl Deserialize<Object> for StampTool {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut stamp = StampTool::default();
+
+        if let Some(stamp_id) = document
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| Uuid::parse_str(id).ok())
+        {
+            stamp.stamp_id = stamp_id;
+        }
+        if let Some(JsonValue::Number(x)) = document.get("x") {
+            stamp.position.x = f32::from(*x);
+        }
+        if let Some(JsonValue::Number(y)) = document.get("y") {
+            stamp.position.y = f32::from(*y);
+        }
+        if let Some(JsonValue::Number(scale)) = document.get("scale") {
+            stamp.scale = f32::from(*scale);
+        }
+        if let Some(JsonValue::Number(rotation)) = document.get("rotation") {
+            stamp.rotation = f32::from(*rotation);
+        }
+        stamp.image = load_stamp(stamp.stamp_id).map(Arc::new);
+
+        stamp
+    }
+}
+
+impl Tool for StampTool {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        let Some(image) = &self.image else {
+            return;
+        };
+        let offset = Vector::new(image.width() as f32 / 2.0, image.height() as f32 / 2.0);
+
+        frame.with_save(|frame| {
+            frame.translate(Vector::new(self.position.x, self.position.y));
+            frame.rotate(self.rotation);
+            frame.scale(self.scale);
+
+            for (x, y, pixel) in image.enumerate_pixels() {
+                let [red, green, blue, alpha] = pixel.0;
+                if alpha == 0 {
+                    continue;
+                }
+
+                frame.fill_rectangle(
+                    Point::new(x as f32, y as f32) - offset,
+                    Size::new(1.0, 1.0),
+                    Color::from_rgba8(red, green, blue, alpha as f32 / 255.0),
+                );
+            }
+        });
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        "Stamp".into()
+    }
+}
+
+impl Into<Box<dyn Tool>> for Box<StampTool> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
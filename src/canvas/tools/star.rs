@@ -0,0 +1,370 @@
+use crate::canvas::layer::CanvasMessage;
+use crate::canvas::style::Style;
+use crate::utils::serde::{Deserialize, Serialize};
+use iced::event::Status;
+use iced::keyboard::Key;
+use iced::mouse::Cursor;
+use iced::widget::canvas::{Event, Fill, Frame, Geometry, LineJoin, Path, Stroke};
+use iced::{keyboard, mouse, Color, Point, Rectangle, Renderer};
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document};
+use std::f32::consts::PI;
+use std::fmt::Debug;
+use std::sync::Arc;
+use svg::node::element::Group;
+
+use crate::canvas::tool::{Pending, Tool};
+
+/// The number of points a star has by default.
+pub const DEFAULT_STAR_POINTS: usize = 5;
+
+/// The default ratio between the inner and the outer radius of a star.
+pub const DEFAULT_INNER_RADIUS_RATIO: f32 = 0.5;
+
+/// Returns the vertices of a star, alternating between the outer and the inner radius. The
+/// first point of the star faces upwards.
+fn get_vertices(center: Point, outer_radius: f32, inner_radius: f32, points: usize) -> Vec<Point> {
+    let points = points.max(3);
+    let step = PI / points as f32;
+
+    (0..2 * points)
+        .map(|index| {
+            let radius = if index % 2 == 0 {
+                outer_radius
+            } else {
+                inner_radius
+            };
+            let angle = -PI / 2.0 + index as f32 * step;
+
+            Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Returns the closed [Path] going through the given vertices.
+fn build_star(vertices: &[Point]) -> Path {
+    Path::new(|builder| {
+        if let Some((first, rest)) = vertices.split_first() {
+            builder.move_to(*first);
+            for vertex in rest {
+                builder.line_to(*vertex);
+            }
+            builder.close();
+        }
+    })
+}
+
+#[derive(Clone)]
+pub enum StarPending {
+    None,
+    Center(Point),
+}
+
+impl Pending for StarPending {
+    fn update(
+        &mut self,
+        event: Event,
+        cursor: Point,
+        style: Style,
+    ) -> (Status, Option<CanvasMessage>) {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let message = match mouse_event {
+                    mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                        *self = StarPending::Center(cursor);
+                        None
+                    }
+                    mouse::Event::ButtonReleased(mouse::Button::Left) => match self {
+                        StarPending::None => None,
+                        StarPending::Center(center) => {
+                            let center = *center;
+                            let outer_radius = cursor.distance(center);
+                            let (points, ratio) = style.get_star();
+
+                            *self = StarPending::None;
+
+                            if outer_radius > 0.0 {
+                                Some(CanvasMessage::UseTool(Arc::new(StarTool {
+                                    center,
+                                    outer_radius,
+                                    inner_radius: outer_radius * ratio,
+                                    points,
+                                    style: style.clone(),
+                                })))
+                            } else {
+                                None
+                            }
+                        }
+                    },
+                    _ => None,
+                };
+
+                (Status::Captured, message)
+            }
+            Event::Keyboard(key_event) => match key_event {
+                keyboard::Event::KeyPressed {
+                    key: Key::Character(key),
+                    ..
+                } => {
+                    let value = key.as_str();
+                    if value == "S" || value == "s" {
+                        *self = StarPending::None;
+
+                        (Status::Captured, None)
+                    } else {
+                        (Status::Ignored, None)
+                    }
+                }
+                _ => (Status::Ignored, None),
+            },
+            _ => (Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        renderer: &Renderer,
+        bounds: Rectangle,
+        cursor: Cursor,
+        style: Style,
+    ) -> Geometry {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if let Some(cursor_position) = cursor.position_in(bounds) {
+            match self {
+                StarPending::None => {}
+                StarPending::Center(center) => {
+                    let outer_radius = cursor_position.distance(*center);
+                    let (points, ratio) = style.get_star();
+                    let star = build_star(&get_vertices(
+                        *center,
+                        outer_radius,
+                        outer_radius * ratio,
+                        points,
+                    ));
+
+                    if let Some((color, _)) = style.fill {
+                        frame.fill(&star, Fill::from(color));
+                    }
+                    if let Some((width, color, _, _)) = style.stroke {
+                        frame.stroke(
+                            &star,
+                            Stroke::default()
+                                .with_width(width)
+                                .with_color(color)
+                                .with_line_join(LineJoin::Miter),
+                        );
+                    }
+                }
+            }
+        };
+
+        frame.into_geometry()
+    }
+
+    fn shape_style(&self, style: &mut Style) {
+        if style.stroke.is_none() {
+            style.stroke = Some((2.0, Color::BLACK, false, false));
+        }
+        if style.fill.is_none() {
+            style.fill = Some((Color::TRANSPARENT, false));
+        }
+        if style.star.is_none() {
+            style.star = Some((DEFAULT_STAR_POINTS, DEFAULT_INNER_RADIUS_RATIO, false));
+        }
+    }
+
+    fn id(&self) -> String {
+        String::from("Star")
+    }
+
+    fn default() -> Self
+    where
+        Self: Sized,
+    {
+        StarPending::None
+    }
+
+    fn dyn_default(&self) -> Box<dyn Pending> {
+        Box::new(StarPending::None)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Pending> {
+        Box::new((*self).clone())
+    }
+}
+
+/// A star with the given amount of points, alternating between the outer and inner radius.
+#[derive(Debug, Clone)]
+pub struct StarTool {
+    center: Point,
+    outer_radius: f32,
+    inner_radius: f32,
+    points: usize,
+    style: Style,
+}
+
+impl StarTool {
+    /// Returns the vertices of the star.
+    fn get_vertices(&self) -> Vec<Point> {
+        get_vertices(
+            self.center,
+            self.outer_radius,
+            self.inner_radius,
+            self.points,
+        )
+    }
+}
+
+impl Default for StarTool {
+    fn default() -> Self {
+        StarTool {
+            center: Point::default(),
+            outer_radius: 0.0,
+            inner_radius: 0.0,
+            points: DEFAULT_STAR_POINTS,
+            style: Style::default(),
+        }
+    }
+}
+
+impl Serialize<Document> for StarTool {
+    fn serialize(&self) -> Document {
+        doc! {
+            "center": Document::from(self.center.serialize()),
+            "outer_radius": self.outer_radius,
+            "inner_radius": self.inner_radius,
+            "points": self.points as u32,
+            "style": Document::from(self.style.serialize()),
+        }
+    }
+}
+
+impl Deserialize<Document> for StarTool {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut star = StarTool::default();
+
+        if let Some(Bson::Document(center)) = document.get("center") {
+            star.center = Point::deserialize(center);
+        }
+        if let Some(Bson::Double(outer_radius)) = document.get("outer_radius") {
+            star.outer_radius = *outer_radius as f32;
+        }
+        if let Some(Bson::Double(inner_radius)) = document.get("inner_radius") {
+            star.inner_radius = *inner_radius as f32;
+        }
+        match document.get("points") {
+            Some(Bson::Int32(points)) => star.points = *points as usize,
+            Some(Bson::Int64(points)) => star.points = *points as usize,
+            _ => {}
+        }
+        if let Some(Bson::Document(style)) = document.get("style") {
+            star.style = Style::deserialize(style);
+        }
+
+        star
+    }
+}
+
+impl Serialize<Group> for StarTool {
+    fn serialize(&self) -> Group {
+        let polygon = svg::node::element::Polygon::new()
+            .set("stroke-width", self.style.get_stroke_width())
+            .set("stroke", self.style.get_stroke_color())
+            .set("stroke-linejoin", "miter")
+            .set("stroke-opacity", self.style.get_stroke_alpha())
+            .set("fill", self.style.get_fill())
+            .set("fill-opacity", self.style.get_fill_alpha())
+            .set(
+                "points",
+                self.get_vertices()
+                    .iter()
+                    .map(|vertex| format!("{},{}", vertex.x, vertex.y))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            );
+
+        Group::new().set("class", self.id()).add(polygon)
+    }
+}
+
+impl Serialize<Object> for StarTool {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert("center", JsonValue::Object(self.center.serialize()));
+        data.insert("outer_radius", JsonValue::Number(self.outer_radius.into()));
+        data.insert("inner_radius", JsonValue::Number(self.inner_radius.into()));
+        data.insert("points", JsonValue::Number(self.points.into()));
+        data.insert("style", JsonValue::Object(self.style.serialize()));
+
+        data
+    }
+}
+
+impl Deserialize<Object> for StarTool {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut star = StarTool::default();
+
+        if let Some(JsonValue::Object(center)) = document.get("center") {
+            star.center = Point::deserialize(center);
+        }
+        if let Some(JsonValue::Number(outer_radius)) = document.get("outer_radius") {
+            star.outer_radius = f32::from(*outer_radius);
+        }
+        if let Some(JsonValue::Number(inner_radius)) = document.get("inner_radius") {
+            star.inner_radius = f32::from(*inner_radius);
+        }
+        if let Some(points) = document.get("points").and_then(|points| points.as_usize()) {
+            star.points = points;
+        }
+        if let Some(JsonValue::Object(style)) = document.get("style") {
+            star.style = Style::deserialize(style);
+        }
+
+        star
+    }
+}
+
+impl Tool for StarTool {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        let star = build_star(&self.get_vertices());
+
+        if let Some((color, _)) = self.style.fill {
+            frame.fill(&star, Fill::from(color));
+        }
+        if let Some((width, color, _, _)) = self.style.stroke {
+            frame.stroke(
+                &star,
+                Stroke::default()
+                    .with_width(width)
+                    .with_color(color)
+                    .with_line_join(LineJoin::Miter),
+            );
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        "Star".into()
+    }
+}
+
+impl Into<Box<dyn Tool>> for Box<StarTool> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
@@ -0,0 +1,224 @@
+use crate::canvas::tool;
+use crate::utils::serde::{Deserialize, Serialize};
+use iced::widget::canvas::Frame;
+use iced::{Point, Radians, Vector};
+use json::object::Object;
+use json::JsonValue;
+use mongodb::bson::{doc, Bson, Document};
+use std::fmt::Debug;
+use std::sync::Arc;
+use svg::node::element::Group;
+
+use crate::canvas::tool::Tool;
+
+/// A copy of another [Tool], rotated and/or mirrored around a center point. Used to draw
+/// the copies produced by the [symmetry mode](crate::canvas::style::SymmetryMode).
+#[derive(Debug, Clone)]
+pub struct SymmetryCopy {
+    /// The copied [Tool]. Empty if it could not be deserialized.
+    tool: Option<Arc<dyn Tool>>,
+
+    /// The point the copy is rotated and mirrored around.
+    center: Point,
+
+    /// The rotation of the copy, in radians.
+    angle: f32,
+
+    /// Tells whether the copy is mirrored left to right.
+    mirror_x: bool,
+
+    /// Tells whether the copy is mirrored top to bottom.
+    mirror_y: bool,
+}
+
+impl SymmetryCopy {
+    /// Creates a copy of the given [Tool] rotated by the given angle around the center.
+    pub fn rotated(tool: Arc<dyn Tool>, center: Point, angle: f32) -> Self {
+        SymmetryCopy {
+            tool: Some(tool),
+            center,
+            angle,
+            mirror_x: false,
+            mirror_y: false,
+        }
+    }
+
+    /// Creates a copy of the given [Tool] mirrored around the center.
+    pub fn mirrored(tool: Arc<dyn Tool>, center: Point, mirror_x: bool, mirror_y: bool) -> Self {
+        SymmetryCopy {
+            tool: Some(tool),
+            center,
+            angle: 0.0,
+            mirror_x,
+            mirror_y,
+        }
+    }
+
+    /// Returns the scaling factors of the copy on both axes.
+    fn get_scale(&self) -> Vector {
+        Vector::new(
+            if self.mirror_x { -1.0 } else { 1.0 },
+            if self.mirror_y { -1.0 } else { 1.0 },
+        )
+    }
+}
+
+impl Default for SymmetryCopy {
+    fn default() -> Self {
+        SymmetryCopy {
+            tool: None,
+            center: Point::ORIGIN,
+            angle: 0.0,
+            mirror_x: false,
+            mirror_y: false,
+        }
+    }
+}
+
+impl Serialize<Document> for SymmetryCopy {
+    fn serialize(&self) -> Document {
+        let mut document = doc! {
+            "center": Document::from(self.center.serialize()),
+            "angle": self.angle,
+            "mirror_x": self.mirror_x,
+            "mirror_y": self.mirror_y,
+        };
+
+        if let Some(tool) = &self.tool {
+            let mut data: Document = tool.serialize();
+            data.insert("name", tool.id());
+            document.insert("tool", data);
+        }
+
+        document
+    }
+}
+
+impl Deserialize<Document> for SymmetryCopy {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut copy = SymmetryCopy::default();
+
+        if let Some(Bson::Document(data)) = document.get("tool") {
+            copy.tool = tool::get_deserialized(data).map(|(tool, _)| tool);
+        }
+        if let Some(Bson::Document(center)) = document.get("center") {
+            copy.center = Point::deserialize(center);
+        }
+        if let Some(Bson::Double(angle)) = document.get("angle") {
+            copy.angle = *angle as f32;
+        }
+        if let Some(Bson::Boolean(mirror_x)) = document.get("mirror_x") {
+            copy.mirror_x = *mirror_x;
+        }
+        if let Some(Bson::Boolean(mirror_y)) = document.get("mirror_y") {
+            copy.mirror_y = *mirror_y;
+        }
+
+        copy
+    }
+}
+
+impl Serialize<Group> for SymmetryCopy {
+    fn serialize(&self) -> Group {
+        let scale = self.get_scale();
+        let transform = format!(
+            "translate({} {}) rotate({}) scale({} {}) translate({} {})",
+            self.center.x,
+            self.center.y,
+            self.angle.to_degrees(),
+            scale.x,
+            scale.y,
+            -self.center.x,
+            -self.center.y
+        );
+
+        let mut group = Group::new()
+            .set("class", self.id())
+            .set("transform", transform);
+        if let Some(tool) = &self.tool {
+            group = group.add(Serialize::<Group>::serialize(tool.as_ref()));
+        }
+
+        group
+    }
+}
+
+impl Serialize<Object> for SymmetryCopy {
+    fn serialize(&self) -> Object {
+        let mut data = Object::new();
+
+        data.insert("center", JsonValue::Object(self.center.serialize()));
+        data.insert("angle", JsonValue::Number(self.angle.into()));
+        data.insert("mirror_x", JsonValue::Boolean(self.mirror_x));
+        data.insert("mirror_y", JsonValue::Boolean(self.mirror_y));
+
+        if let Some(tool) = &self.tool {
+            let mut tool_data: Object = tool.serialize();
+            tool_data.insert("name", JsonValue::String(tool.id()));
+            data.insert("tool", JsonValue::Object(tool_data));
+        }
+
+        data
+    }
+}
+
+impl Deserialize<Object> for SymmetryCopy {
+    fn deserialize(document: &Object) -> Self
+    where
+        Self: Sized,
+    {
+        let mut copy = SymmetryCopy::default();
+
+        if let Some(JsonValue::Object(data)) = document.get("tool") {
+            copy.tool = tool::get_json(data).map(|(tool, _)| tool);
+        }
+        if let Some(JsonValue::Object(center)) = document.get("center") {
+            copy.center = Point::deserialize(center);
+        }
+        if let Some(JsonValue::Number(angle)) = document.get("angle") {
+            copy.angle = f32::from(*angle);
+        }
+        if let Some(JsonValue::Boolean(mirror_x)) = document.get("mirror_x") {
+            copy.mirror_x = *mirror_x;
+        }
+        if let Some(JsonValue::Boolean(mirror_y)) = document.get("mirror_y") {
+            copy.mirror_y = *mirror_y;
+        }
+
+        copy
+    }
+}
+
+impl Tool for SymmetryCopy {
+    fn add_to_frame(&self, frame: &mut Frame) {
+        let Some(tool) = &self.tool else {
+            return;
+        };
+
+        frame.with_save(|frame| {
+            frame.translate(Vector::new(self.center.x, self.center.y));
+            frame.rotate(Radians(self.angle));
+            frame.scale_nonuniform(self.get_scale());
+            frame.translate(Vector::new(-self.center.x, -self.center.y));
+
+            tool.add_to_frame(frame);
+        });
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Tool> {
+        Box::new((*self).clone())
+    }
+
+    fn id(&self) -> String {
+        "Symmetry".into()
+    }
+}
+
+impl Into<Box<dyn Tool>> for Box<SymmetryCopy> {
+    fn into(self) -> Box<dyn Tool> {
+        self.boxed_clone()
+    }
+}
@@ -0,0 +1,55 @@
+use crate::canvas::effect::LayerEffect;
+use crate::canvas::tool::Tool;
+use chrono::{DateTime, Local};
+use json::JsonValue;
+use mongodb::bson::Uuid;
+use std::sync::Arc;
+
+/// A change to the [canvas](crate::canvas::canvas::Canvas) that reverses one made before it.
+/// Applying an [UndoEntry] yields the entry that reverses it in turn, so the same entries are
+/// used for both undoing and redoing.
+#[derive(Debug, Clone)]
+pub enum UndoEntry {
+    /// Removes the given placed [Tool]. Tools placed by collaborators may have been inserted
+    /// after it, so it is not necessarily the last one.
+    RemoveTool(Arc<dyn Tool>),
+
+    /// Places the [Tool] on the [Layer](crate::canvas::layer::Layer) with the given id, keeping
+    /// the moment it was first placed.
+    PlaceTool(Arc<dyn Tool>, Uuid, Option<DateTime<Local>>),
+
+    /// Toggles the visibility of the [Layer](crate::canvas::layer::Layer) with the given id.
+    ToggleLayer(Uuid),
+
+    /// Sets the name of the [Layer](crate::canvas::layer::Layer) with the given id.
+    RenameLayer(Uuid, String),
+
+    /// Removes the [Layer](crate::canvas::layer::Layer) with the given id, along with its tools.
+    RemoveLayer(Uuid),
+
+    /// Inserts a removed [Layer](crate::canvas::layer::Layer) back into the drawing.
+    RestoreLayer {
+        /// The id of the layer.
+        id: Uuid,
+
+        /// The position of the layer in the layer order.
+        position: usize,
+
+        /// The name of the layer.
+        name: String,
+
+        /// Tells whether the layer was visible.
+        visible: bool,
+
+        /// The effects of the layer.
+        effects: Vec<LayerEffect>,
+
+        /// The tools of the layer, with their positions in the tool list and the moments they
+        /// were placed.
+        tools: Vec<(usize, Arc<dyn Tool>, Option<DateTime<Local>>)>,
+
+        /// The json forms of the tools of the layer, with their positions in the json tool list.
+        /// Only used when the drawing is stored locally.
+        json_tools: Vec<(usize, JsonValue)>,
+    },
+}
@@ -0,0 +1,417 @@
+use crate::database::base::resolve_cursor;
+use crate::database::base::{driver_error, retry_with_backoff, CONNECTION_SETTINGS};
+use crate::debug_message;
+use crate::scenes::data::admin::{AuditEntry, Report, Statistics};
+use crate::scenes::data::auth::{Role, User};
+use crate::scenes::data::challenge::Challenge;
+use crate::utils::errors::{AuthError, Error};
+use crate::utils::serde::Deserialize;
+use mongodb::bson::{doc, DateTime, Document, Uuid};
+use mongodb::options::{AggregateOptions, FindOptions};
+use mongodb::Database;
+
+/// The maximum number of audit log entries that are returned.
+const AUDIT_LOG_LIMIT: i64 = 100;
+
+/// The amount of time after which a drawing session that hasn't moved its cursor is no longer
+/// considered active, in milliseconds.
+const ACTIVE_SESSION_TIMEOUT: i64 = 5 * 60 * 1000;
+
+/// Returns all registered users, ordered by their username.
+pub async fn get_users(db: &Database) -> Result<Vec<User>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("users")
+                .find(
+                    doc! {
+                        "expiration_date": { "$exists": false }
+                    },
+                    FindOptions::builder().sort(doc! { "username": 1 }).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<User>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Bans or unbans the user with the given id.
+pub async fn set_ban_status(db: &Database, user_id: Uuid, banned: bool) -> Result<(), Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("users")
+                .update_one(
+                    doc! {
+                        "id": user_id
+                    },
+                    doc! {
+                        "$set": {
+                            "is_banned": banned
+                        }
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Tells whether the user with the given id has been banned.
+pub async fn is_banned(db: &Database, user_id: Uuid) -> Result<bool, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("users")
+                .find_one(
+                    doc! {
+                        "id": user_id,
+                        "is_banned": true
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(user) => Ok(user.is_some()),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Tells whether the user with the given id is an admin.
+pub async fn is_admin(db: &Database, user_id: Uuid) -> Result<bool, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("users")
+                .find_one(
+                    doc! {
+                        "id": user_id,
+                        "role": Into::<i32>::into(Role::Admin)
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(user) => Ok(user.is_some()),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns [UserBanned](AuthError::UserBanned) if the user with the given id has been banned,
+/// since banned users can't write to the database.
+pub async fn ensure_not_banned(db: &Database, user_id: Uuid) -> Result<(), Error> {
+    if is_banned(db, user_id).await? {
+        Err(Error::AuthError(AuthError::UserBanned))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the reports that haven't been handled yet, oldest first, along with the reported
+/// posts and their authors.
+pub async fn get_reports(db: &Database) -> Result<Vec<Report>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("reports")
+                .aggregate(
+                    vec![
+                        doc! {
+                            "$sort": {
+                                "created_at": 1
+                            }
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "posts",
+                                "localField": "post_id",
+                                "foreignField": "id",
+                                "as": "post"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$post"
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "users",
+                                "localField": "post.user_id",
+                                "foreignField": "id",
+                                "as": "user"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$user"
+                        },
+                    ],
+                    AggregateOptions::builder().allow_disk_use(true).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Report>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Removes the given report from the queue, leaving the reported post untouched.
+pub async fn dismiss_report(db: &Database, report_id: Uuid) -> Result<(), Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("reports")
+                .delete_one(
+                    doc! {
+                        "id": report_id
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Deletes the given reported post, along with all the reports regarding it.
+pub async fn delete_reported_post(db: &Database, post_id: Uuid) -> Result<(), Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("posts")
+                .delete_one(
+                    doc! {
+                        "id": post_id
+                    },
+                    None,
+                )
+                .await
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+            db.collection::<Document>("reports")
+                .delete_many(
+                    doc! {
+                        "post_id": post_id
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the most recent challenges, including the ones that have ended.
+pub async fn get_challenges(db: &Database) -> Result<Vec<Challenge>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("challenges")
+                .find(
+                    doc! {},
+                    FindOptions::builder()
+                        .sort(doc! { "start_time": -1 })
+                        .limit(50)
+                        .build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Challenge>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Creates a challenge with the given prompt, which accepts drawings in the given interval.
+pub async fn create_challenge(
+    db: &Database,
+    prompt: String,
+    start_time: DateTime,
+    end_time: DateTime,
+) -> Result<(), Error> {
+    db.collection::<Document>("challenges")
+        .insert_one(
+            doc! {
+                "id": Uuid::new(),
+                "prompt": prompt,
+                "start_time": start_time,
+                "end_time": end_time
+            },
+            None,
+        )
+        .await
+        .map(|_| ())
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+}
+
+/// Stops the given challenge from accepting drawings.
+pub async fn close_challenge(db: &Database, challenge_id: Uuid) -> Result<(), Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("challenges")
+                .update_one(
+                    doc! {
+                        "id": challenge_id
+                    },
+                    doc! {
+                        "$set": {
+                            "end_time": DateTime::now()
+                        }
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the number of posts, users and currently active drawing sessions.
+pub async fn get_statistics(db: &Database) -> Result<Statistics, Error> {
+    retry_with_backoff(
+        || async move {
+            let posts = db
+                .collection::<Document>("posts")
+                .count_documents(doc! {}, None)
+                .await
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+            let users = db
+                .collection::<Document>("users")
+                .count_documents(
+                    doc! {
+                        "expiration_date": { "$exists": false }
+                    },
+                    None,
+                )
+                .await
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+            let active_since =
+                DateTime::from_millis(DateTime::now().timestamp_millis() - ACTIVE_SESSION_TIMEOUT);
+            let active_sessions = db
+                .collection::<Document>("cursors")
+                .count_documents(
+                    doc! {
+                        "updated_at": { "$gte": active_since }
+                    },
+                    None,
+                )
+                .await
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+            Ok(Statistics {
+                posts,
+                users,
+                active_sessions,
+            })
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the latest entries of the audit log, optionally only those of the user with the given
+/// tag and those of the given action.
+pub async fn get_audit_log(
+    db: &Database,
+    user_tag: Option<String>,
+    action: Option<&'static str>,
+) -> Result<Vec<AuditEntry>, Error> {
+    retry_with_backoff(
+        || {
+            let user_tag = user_tag.clone();
+
+            async move {
+                let mut filter = doc! {};
+
+                if let Some(user_tag) = user_tag {
+                    match db
+                        .collection::<Document>("users")
+                        .find_one(doc! { "user_tag": user_tag.clone() }, None)
+                        .await
+                    {
+                        Ok(Some(ref user)) => {
+                            filter.insert("user_id", User::deserialize(user).get_id());
+                        }
+                        Ok(None) => {
+                            return Err(Error::AuthError(AuthError::UserTagDoesNotExist(user_tag)))
+                        }
+                        Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+                    }
+                }
+                if let Some(action) = action {
+                    filter.insert("action", action);
+                }
+
+                match db
+                    .collection::<Document>("audit_log")
+                    .aggregate(
+                        vec![
+                            doc! {
+                                "$match": filter
+                            },
+                            doc! {
+                                "$sort": {
+                                    "created_at": -1
+                                }
+                            },
+                            doc! {
+                                "$limit": AUDIT_LOG_LIMIT
+                            },
+                            doc! {
+                                "$lookup": {
+                                    "from": "users",
+                                    "localField": "user_id",
+                                    "foreignField": "id",
+                                    "as": "user"
+                                }
+                            },
+                            doc! {
+                                "$unwind": {
+                                    "path": "$user",
+                                    "preserveNullAndEmptyArrays": true
+                                }
+                            },
+                        ],
+                        AggregateOptions::builder().allow_disk_use(true).build(),
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => Ok(resolve_cursor::<AuditEntry>(cursor).await),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
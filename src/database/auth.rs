@@ -1,111 +1,137 @@
+use crate::database::base::{driver_error, retry_with_backoff, CONNECTION_SETTINGS};
 use crate::debug_message;
-use crate::scenes::data::auth::User;
+use crate::scenes::data::auth::{OAuthProfile, User};
 use crate::utils::errors::{AuthError, DebugError, Error};
 use crate::utils::serde::Deserialize;
 use directories::ProjectDirs;
 use mongodb::bson::spec::BinarySubtype;
 use mongodb::bson::{doc, Binary, Bson, DateTime, Document, Uuid};
-use mongodb::options::UpdateOptions;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument, UpdateOptions};
 use mongodb::Database;
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
 
+/// Returns the given [User], or [UserBanned](AuthError::UserBanned) if they have been banned.
+fn unless_banned(user: User) -> Result<User, Error> {
+    if user.is_banned() {
+        Err(Error::AuthError(AuthError::UserBanned))
+    } else {
+        Ok(user)
+    }
+}
+
 /// Checks if an authentication token is saved on the user's computer.
 ///
-/// If there is one, the user will be automatically logged in.
+/// If there is one, the user will be automatically logged in. Fails with
+/// [TokenExpired](AuthError::TokenExpired) if the session of the token has expired, and with
+/// [UserBanned](AuthError::UserBanned) if the user has been banned.
 pub async fn get_user_from_token(database: &Database) -> Result<User, Error> {
-    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
-        .ok_or(debug_message!("Unable to find project directory.").into())?;
-    let file_path = proj_dirs.data_local_dir().join("./token");
-    let token = fs::read(file_path);
-
-    if let Ok(token) = token {
-        if token.len() != 32 {
-            return Err(Error::DebugError(DebugError::new(
-                "Auth token should have 32 bytes!",
-            )));
-        }
+    retry_with_backoff(
+        || async move {
+            let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+                .ok_or(debug_message!("Unable to find project directory.").into())?;
+            let file_path = proj_dirs.data_local_dir().join("./token");
+            let token = fs::read(file_path);
 
-        let mut sha = Sha256::new();
-        Digest::update(&mut sha, token);
-        let hash = sha.finalize();
-        let bin = Bson::Binary(Binary {
-            bytes: Vec::from(hash.iter().as_slice()),
-            subtype: BinarySubtype::Generic,
-        });
-
-        match database
-            .collection::<Document>("users")
-            .find_one(
-                doc! {
-                    "auth_token": bin,
-                    "token_expiration": {
-                        "$gt": Bson::DateTime(DateTime::now())
+            if let Ok(token) = token {
+                if token.len() != 32 {
+                    return Err(Error::DebugError(DebugError::new(
+                        "Auth token should have 32 bytes!",
+                    )));
+                }
+
+                let mut sha = Sha256::new();
+                Digest::update(&mut sha, token);
+                let hash = sha.finalize();
+                let bin = Bson::Binary(Binary {
+                    bytes: Vec::from(hash.iter().as_slice()),
+                    subtype: BinarySubtype::Generic,
+                });
+
+                match database
+                    .collection::<Document>("users")
+                    .find_one(
+                        doc! {
+                            "auth_token": bin,
+                            "expiration_date": {
+                                "$eq": null
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(Some(ref document)) => match document.get_datetime("token_expiration") {
+                        Ok(expiration) if *expiration > DateTime::now() => {
+                            unless_banned(User::deserialize(document))
+                        }
+                        _ => Err(Error::AuthError(AuthError::TokenExpired)),
                     },
-                    "expiration_date": {
-                        "$eq": null
-                    }
-                },
-                None,
-            )
-            .await
-        {
-            Ok(Some(ref document)) => Ok(User::deserialize(document)),
-            Ok(None) => Err(Error::DebugError(DebugError::new(debug_message!(
-                "No user previously logged in!"
-            )))),
-            Err(err) => Err(debug_message!("{}", err).into()),
-        }
-    } else {
-        Err(debug_message!("No user previously logged in!").into())
-    }
+                    Ok(None) => Err(Error::DebugError(DebugError::new(debug_message!(
+                        "No user previously logged in!"
+                    )))),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            } else {
+                Err(debug_message!("No user previously logged in!").into())
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// When a user is logged in, the authentication token is updated in the database in order
 /// to increase security.
 pub async fn update_user_token(database: &Database, user_id: Uuid) -> Result<(), Error> {
-    let (code, token) = User::gen_auth_token();
+    retry_with_backoff(
+        || async move {
+            let (code, token) = User::gen_auth_token();
 
-    let result = database.collection::<Document>("users").update_one(
-        doc! {
-            "id": user_id
-        },
-        doc! {
-            "$set": {
-                "auth_token": token.clone(),
-                "token_expiration": Bson::DateTime(
-                    DateTime::from_millis(DateTime::now().timestamp_millis() + 30 * 24 * 60 * 60 * 1000)
-                ),
-            },
-            "$unset": {
-                "expiration_date": null
-            }
-        },
-        None
-    ).await;
+            let result = database.collection::<Document>("users").update_one(
+                doc! {
+                    "id": user_id
+                },
+                doc! {
+                    "$set": {
+                        "auth_token": token.clone(),
+                        "token_expiration": Bson::DateTime(
+                            DateTime::from_millis(DateTime::now().timestamp_millis() + 30 * 24 * 60 * 60 * 1000)
+                        ),
+                    },
+                    "$unset": {
+                        "expiration_date": null
+                    }
+                },
+                None
+            ).await;
 
-    match result {
-        Ok(result) if result.modified_count > 0 => {
-            let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
-                .ok_or(debug_message!("Unable to find project directory.").into())?;
-            let file_path = proj_dirs.data_local_dir().join("./token");
+            match result {
+                Ok(result) if result.modified_count > 0 => {
+                    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+                        .ok_or(debug_message!("Unable to find project directory.").into())?;
+                    let file_path = proj_dirs.data_local_dir().join("./token");
 
-            let mut file =
-                File::create(file_path).map_err(|err| debug_message!("{}", err).into())?;
-            file.write(code.as_slice())
-                .map_err(|err| debug_message!("{}", err).into())?;
-        }
-        Ok(_) => {
-            return Err(debug_message!("Did not find user with id {}.", user_id).into());
-        }
-        Err(err) => {
-            return Err(debug_message!("{}", err).into());
-        }
-    }
+                    let mut file =
+                        File::create(file_path).map_err(|err| debug_message!("{}", err).into())?;
+                    file.write(code.as_slice())
+                        .map_err(|err| debug_message!("{}", err).into())?;
+                }
+                Ok(_) => {
+                    return Err(debug_message!("Did not find user with id {}.", user_id).into());
+                }
+                Err(err) => {
+                    return Err(driver_error(&err, debug_message!("{}", err)));
+                }
+            }
 
-    Ok(())
+            Ok(())
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Creates a [User] by adding the data to the database if a user with the given email doesn't
@@ -115,109 +141,358 @@ pub async fn create_user(
     user_email: String,
     user_data: Document,
 ) -> Result<(), Error> {
-    match db
-        .collection::<Document>("users")
-        .update_one(
-            doc! {
-                "email": user_email.clone()
-            },
-            doc! {
-                "$setOnInsert": user_data
-            },
-            UpdateOptions::builder().upsert(true).build(),
-        )
-        .await
-    {
-        Ok(result) => {
-            if result.matched_count > 0 {
-                Err(Error::AuthError(AuthError::RegisterUserAlreadyExists))
-            } else {
-                Ok(())
+    retry_with_backoff(
+        || {
+            let user_email = user_email.clone();
+            let user_data = user_data.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .update_one(
+                        doc! {
+                            "email": user_email.clone()
+                        },
+                        doc! {
+                            "$setOnInsert": user_data
+                        },
+                        UpdateOptions::builder().upsert(true).build(),
+                    )
+                    .await
+                {
+                    Ok(result) => {
+                        if result.matched_count > 0 {
+                            Err(Error::AuthError(AuthError::RegisterUserAlreadyExists))
+                        } else {
+                            Ok(())
+                        }
+                    }
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
             }
-        }
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Checks if there is a user in the database with the given email that expects the given
 /// validation code.
 pub async fn validate_email(db: &Database, email: String, code: String) -> Result<(), Error> {
-    match db
-        .collection::<Document>("users")
-        .update_one(
-            doc! {
-                "email": email.clone(),
-                "register_code": code.clone(),
-                "code_expiration": {
-                    "$gt": Bson::DateTime(DateTime::now())
+    retry_with_backoff(
+        || {
+            let email = email.clone();
+            let code = code.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .update_one(
+                        doc! {
+                            "email": email.clone(),
+                            "register_code": code.clone(),
+                            "code_expiration": {
+                                "$gt": Bson::DateTime(DateTime::now())
+                            }
+                        },
+                        doc! {
+                            "$set": {
+                                "validated": true
+                            },
+                            "$unset": {
+                                "expiration_date": null
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(result) => {
+                        if result.matched_count > 0 {
+                            Ok(())
+                        } else {
+                            Err(Error::AuthError(AuthError::RegisterBadCode))
+                        }
+                    }
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
                 }
-            },
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Generates a new e-mail verification code.
+pub async fn reset_register_code(db: &Database, email: String, code: String) -> Result<(), Error> {
+    retry_with_backoff(
+        || {
+            let email = email.clone();
+            let code = code.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .update_one(
+                        doc! {
+                            "email": email.clone()
+                        },
+                        doc! {
+                            "$set": {
+                                "register_code": code,
+                                "code_expiration": Bson::DateTime(
+                                    DateTime::from_millis(DateTime::now().timestamp_millis() + 5 * 60 * 1000)
+                                ),
+                                "expiration_date": Bson::DateTime(
+                                    DateTime::from_millis(DateTime::now().timestamp_millis() + 30 * 24 * 60 * 60 * 1000)
+                                )
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(result) => {
+                        if result.modified_count > 0 {
+                            Ok(())
+                        } else {
+                            Err(debug_message!("Database could not find user with email {}!", email).into())
+                        }
+                    }
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Checks if there exists a [User] with the given login credentials.
+pub async fn login(db: &Database, user_data: Document) -> Result<User, Error> {
+    retry_with_backoff(
+        || {
+            let user_data = user_data.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .find_one(user_data, None)
+                    .await
+                {
+                    Ok(Some(ref user)) => unless_banned(User::deserialize(user)),
+                    Ok(None) => Err(Error::AuthError(AuthError::LogInUserDoesntExist)),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the [User] that logged in through an OAuth provider with the given profile. The user
+/// is found by their id at the provider; otherwise, if the provider has verified the email, the
+/// validated account with the same email is linked to the provider, unless it is already linked
+/// to one. If an account with the email exists but can't be linked, the user needs to log in
+/// with their password; otherwise, a new account is created.
+pub async fn oauth_login(db: &Database, profile: OAuthProfile) -> Result<User, Error> {
+    let users = db.collection::<Document>("users");
+    let provider = profile.provider.get_name();
+
+    match users
+        .find_one(
             doc! {
-                "$set": {
-                    "validated": true
-                },
-                "$unset": {
-                    "expiration_date": null
+                "oauth_provider": provider,
+                "oauth_subject": profile.subject.clone(),
+                "expiration_date": {
+                    "$eq": null
                 }
             },
             None,
         )
         .await
     {
-        Ok(result) => {
-            if result.matched_count > 0 {
-                Ok(())
-            } else {
-                Err(Error::AuthError(AuthError::RegisterBadCode))
-            }
+        Ok(Some(ref user)) => return unless_banned(User::deserialize(user)),
+        Ok(None) => {}
+        Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+    }
+
+    if profile.email_verified {
+        match users
+            .find_one_and_update(
+                doc! {
+                    "email": profile.email.clone(),
+                    "validated": true,
+                    "oauth_provider": {
+                        "$exists": false
+                    },
+                    "expiration_date": {
+                        "$eq": null
+                    }
+                },
+                doc! {
+                    "$set": {
+                        "oauth_provider": provider,
+                        "oauth_subject": profile.subject.clone()
+                    }
+                },
+                FindOneAndUpdateOptions::builder()
+                    .return_document(ReturnDocument::After)
+                    .build(),
+            )
+            .await
+        {
+            Ok(Some(ref user)) => return unless_banned(User::deserialize(user)),
+            Ok(None) => {}
+            Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
         }
-        Err(err) => Err(debug_message!("{}", err).into()),
     }
-}
 
-/// Generates a new e-mail verification code.
-pub async fn reset_register_code(db: &Database, email: String, code: String) -> Result<(), Error> {
-    match db
-        .collection::<Document>("users")
-        .update_one(
-            doc! {
-                "email": email.clone()
-            },
+    match users
+        .find_one(
             doc! {
-                "$set": {
-                    "register_code": code,
-                    "code_expiration": Bson::DateTime(
-                        DateTime::from_millis(DateTime::now().timestamp_millis() + 5 * 60 * 1000)
-                    ),
-                    "expiration_date": Bson::DateTime(
-                        DateTime::from_millis(DateTime::now().timestamp_millis() + 30 * 24 * 60 * 60 * 1000)
-                    )
-                }
+                "email": profile.email.clone()
             },
             None,
         )
         .await
     {
-        Ok(result) => {
-            if result.modified_count > 0 {
-                Ok(())
-            } else {
-                Err(debug_message!("Database could not find user with email {}!", email).into())
-            }
-        }
-        Err(err) => Err(debug_message!("{}", err).into()),
+        Ok(Some(_)) => return Err(Error::AuthError(AuthError::OAuthPasswordRequired)),
+        Ok(None) => {}
+        Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
     }
-}
 
-/// Checks if there exists a [User] with the given login credentials.
-pub async fn login(db: &Database, user_data: Document) -> Result<User, Error> {
-    match db
-        .collection::<Document>("users")
-        .find_one(user_data, None)
-        .await
-    {
-        Ok(Some(ref user)) => Ok(User::deserialize(user)),
-        Ok(None) => Err(Error::AuthError(AuthError::LogInUserDoesntExist)),
-        Err(err) => Err(debug_message!("{}", err).into()),
+    if !profile.email_verified {
+        return Err(debug_message!(
+            "The email of the {} account is not verified.",
+            profile.provider
+        )
+        .into());
     }
+
+    let user = profile.serialize_new_user();
+    match users.insert_one(user.clone(), None).await {
+        Ok(_) => Ok(User::deserialize(&user)),
+        Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+    }
+}
+
+/// Stores the hash of a password reset token for the validated user with the given email,
+/// replacing their previous token. The token expires in thirty minutes. Returns false if there is
+/// no such user, in which case nothing is stored.
+pub async fn create_password_reset(
+    db: &Database,
+    email: String,
+    token_hash: Binary,
+) -> Result<bool, Error> {
+    retry_with_backoff(
+        || {
+            let email = email.clone();
+            let token_hash = token_hash.clone();
+
+            async move {
+                let user_id = match db
+                    .collection::<Document>("users")
+                    .find_one(
+                        doc! {
+                            "email": email,
+                            "validated": true,
+                            "expiration_date": {
+                                "$eq": null
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(Some(ref user)) => User::deserialize(user).get_id(),
+                    Ok(None) => return Ok(false),
+                    Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+                };
+
+                db.collection::<Document>("password_resets")
+                    .update_one(
+                        doc! {
+                            "user_id": user_id
+                        },
+                        doc! {
+                            "$set": {
+                                "token_hash": token_hash,
+                                "expiration": Bson::DateTime(
+                                    DateTime::from_millis(DateTime::now().timestamp_millis() + 30 * 60 * 1000)
+                                )
+                            }
+                        },
+                        UpdateOptions::builder().upsert(true).build(),
+                    )
+                    .await
+                    .map(|_| true)
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Sets the password of the user that requested the password reset token with the given hash,
+/// if the token has not expired. The sessions of the user are ended, and the token is removed.
+pub async fn reset_password(
+    db: &Database,
+    token_hash: Binary,
+    password_hash: String,
+) -> Result<(), Error> {
+    retry_with_backoff(
+        || {
+            let token_hash = token_hash.clone();
+            let password_hash = password_hash.clone();
+
+            async move {
+                let user_id = match db
+                    .collection::<Document>("password_resets")
+                    .find_one(
+                        doc! {
+                            "token_hash": token_hash.clone(),
+                            "expiration": {
+                                "$gt": Bson::DateTime(DateTime::now())
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(Some(reset)) => reset.get("user_id").cloned().unwrap_or(Bson::Null),
+                    Ok(None) => return Err(Error::AuthError(AuthError::PasswordResetBadToken)),
+                    Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+                };
+
+                db.collection::<Document>("users")
+                    .update_one(
+                        doc! {
+                            "id": user_id
+                        },
+                        doc! {
+                            "$set": {
+                                "password": password_hash,
+                                "token_expiration": Bson::DateTime(DateTime::now())
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+                // The token is only removed once the password has been changed, so that retrying
+                // after a failed update can still find it.
+                db.collection::<Document>("password_resets")
+                    .delete_one(doc! { "token_hash": token_hash }, None)
+                    .await
+                    .map(|_| ())
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
@@ -1,23 +1,106 @@
 use crate::config;
 use crate::debug_message;
-use crate::utils::errors::Error;
+use crate::scenes::data::debug::{self, LogEntry};
+use crate::utils::errors::{DebugError, Error};
 use crate::utils::serde::Deserialize;
 use dropbox_sdk::default_client::{NoauthDefaultClient, UserAuthDefaultClient};
 use dropbox_sdk::files::{self, DeleteArg};
 use dropbox_sdk::files::{DownloadArg, UploadArg, WriteMode};
-use mongodb::bson::Document;
-use mongodb::options::ClientOptions;
-use mongodb::{Client, Cursor};
+use mongodb::bson::{doc, DateTime, Document, Uuid};
+use mongodb::error::ErrorKind;
+use mongodb::options::{ClientOptions, IndexOptions};
+use mongodb::{Client, Cursor, Database, IndexModel};
+use std::future::Future;
 use std::io;
+use std::time::Duration;
 
-/// Attempts to connect to the database [Database].
+/// The settings used when connecting to and querying the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionSettings {
+    /// The number of times an operation is attempted before its error is returned.
+    pub max_attempts: u32,
+
+    /// The delay before the first retry of an operation, in milliseconds. It doubles with every
+    /// retry.
+    pub initial_delay_ms: u64,
+
+    /// How long the driver waits for a server to be reachable before an operation fails, in
+    /// milliseconds.
+    pub server_selection_timeout_ms: u64,
+}
+
+/// The [ConnectionSettings] of the application.
+pub const CONNECTION_SETTINGS: ConnectionSettings = ConnectionSettings {
+    max_attempts: 5,
+    initial_delay_ms: 100,
+    server_selection_timeout_ms: 5000,
+};
+
+/// Returns how long to wait before retrying an operation that has failed the given number of
+/// times.
+pub fn backoff_delay(failed_attempts: u32) -> Duration {
+    Duration::from_millis(CONNECTION_SETTINGS.initial_delay_ms)
+        * 2u32.saturating_pow(failed_attempts.saturating_sub(1))
+}
+
+/// Converts an error of the database driver into a [debug error](Error::DebugError) with the
+/// given message. Network errors and failures to select a server are marked as
+/// [transient](Error::is_transient), since they may not happen again.
+pub fn driver_error(err: &mongodb::error::Error, message: String) -> Error {
+    let error = DebugError::new(message);
+
+    match *err.kind {
+        ErrorKind::Io(_)
+        | ErrorKind::ServerSelection { .. }
+        | ErrorKind::ConnectionPoolCleared { .. } => Error::DebugError(error.transient()),
+        _ => Error::DebugError(error),
+    }
+}
+
+/// Runs the operation until it succeeds or has been attempted the given number of times, waiting
+/// twice as long after every failure (100ms, 200ms, 400ms, ...). Only
+/// [transient errors](Error::is_transient) are retried, since the others would only repeat.
+/// Operations that aren't idempotent, such as inserts, must not be retried: the failure may have
+/// happened after the write was committed.
+pub async fn retry_with_backoff<T, F, Fut>(mut f: F, max_attempts: u32) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        match f().await {
+            Err(err) if err.is_transient() && attempt < max_attempts => {
+                let delay = backoff_delay(attempt);
+                debug::log(LogEntry::new(
+                    "Database",
+                    format!(
+                        "Attempt {} of {} failed, retrying in {}ms: {}",
+                        attempt,
+                        max_attempts,
+                        delay.as_millis(),
+                        err
+                    ),
+                    None,
+                ));
+
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Attempts to connect to the database [Database], making sure that it responds.
 ///
 /// Returns an error upon failure.
 pub async fn connect_to_mongodb() -> Result<Client, Error>
 where
     Client: Send + 'static,
 {
-    let client_options = match ClientOptions::parse(
+    let mut client_options = match ClientOptions::parse(
         format!(
             "mongodb+srv://{}:{}@chartsy.1fzpgot.mongodb.net/?retryWrites=true&w=majority&appName=Chartsy",
             config::mongo_name(),
@@ -30,7 +113,103 @@ where
         }
     };
 
-    Client::with_options(client_options).map_err(|err| debug_message!("{}", err).into())
+    // The driver would otherwise wait 30 seconds for an unreachable cluster on every attempt.
+    let timeout = Duration::from_millis(CONNECTION_SETTINGS.server_selection_timeout_ms);
+    client_options.server_selection_timeout = Some(timeout);
+    client_options.connect_timeout = Some(timeout);
+
+    let client = Client::with_options(client_options)
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+    ping(&client.database("chartsy")).await?;
+
+    Ok(client)
+}
+
+/// Checks that the database responds.
+pub async fn ping(db: &Database) -> Result<(), Error> {
+    db.run_command(doc! { "ping": 1 }, None)
+        .await
+        .map(|_| ())
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+}
+
+/// The fields the collections are commonly queried or joined on, by collection.
+const INDEXES: [(&str, &str); 12] = [
+    ("users", "id"),
+    ("canvases", "user_id"),
+    ("posts", "user_id"),
+    ("posts", "tags"),
+    ("comments", "post_id"),
+    ("comments", "reply_to"),
+    ("ratings", "post_id"),
+    ("ratings", "user_id"),
+    ("email_changes", "user_id"),
+    ("password_resets", "token_hash"),
+    ("reports", "post_id"),
+    ("audit_log", "user_id"),
+];
+
+/// How long the cursor position of an editing session is kept after its last update, so that
+/// the sessions which were left stop being stored.
+const CURSOR_EXPIRY: Duration = Duration::from_secs(10 * 60);
+
+/// Records that the given user has performed the given write operation, optionally on the object
+/// with the given id.
+pub async fn audit_log(
+    db: &Database,
+    user_id: Uuid,
+    action: &str,
+    target_id: Option<Uuid>,
+) -> Result<(), Error> {
+    db.collection::<Document>("audit_log")
+        .insert_one(
+            doc! {
+                "user_id": user_id,
+                "action": action,
+                "target_id": target_id,
+                "created_at": DateTime::now()
+            },
+            None,
+        )
+        .await
+        .map(|_| ())
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+}
+
+/// Creates the indexes of the commonly queried fields, if they do not exist yet, and the index
+/// that expires the cursor positions of the left editing sessions.
+pub async fn ensure_indexes(db: &Database) -> Result<(), Error> {
+    for (collection, field) in INDEXES {
+        retry_with_backoff(
+            || async move {
+                db.collection::<Document>(collection)
+                    .create_index(IndexModel::builder().keys(doc! { field: 1 }).build(), None)
+                    .await
+                    .map(|_| ())
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+            },
+            CONNECTION_SETTINGS.max_attempts,
+        )
+        .await?;
+    }
+
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("cursors")
+                .create_index(
+                    IndexModel::builder()
+                        .keys(doc! { "updated_at": 1 })
+                        .options(IndexOptions::builder().expire_after(CURSOR_EXPIRY).build())
+                        .build(),
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Collects all entries of the cursor, attempting to deserialize them in the functions Type.
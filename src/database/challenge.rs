@@ -0,0 +1,128 @@
+use crate::database::base::resolve_cursor;
+use crate::database::base::{driver_error, retry_with_backoff, CONNECTION_SETTINGS};
+use crate::debug_message;
+use crate::scenes::data::challenge::Challenge;
+use crate::scenes::data::posts::Post;
+use crate::utils::errors::Error;
+use crate::utils::serde::Deserialize;
+use mongodb::bson::{doc, Bson, DateTime, Document, Uuid};
+use mongodb::options::{AggregateOptions, FindOneOptions};
+use mongodb::Database;
+
+/// Returns the challenge that currently accepts drawings, if there is one.
+pub async fn get_active_challenge(db: &Database) -> Result<Option<Challenge>, Error> {
+    retry_with_backoff(
+        || async move {
+            let now = DateTime::now();
+
+            match db
+                .collection::<Document>("challenges")
+                .find_one(
+                    doc! {
+                        "start_time": { "$lte": now },
+                        "end_time": { "$gte": now }
+                    },
+                    FindOneOptions::builder()
+                        .sort(doc! { "start_time": -1 })
+                        .build(),
+                )
+                .await
+            {
+                Ok(challenge) => Ok(challenge.as_ref().map(Challenge::deserialize)),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the posts submitted to the given challenge, ordered by their average rating.
+pub async fn get_leaderboard(db: &Database, challenge_id: Uuid) -> Result<Vec<Post>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("posts")
+                .aggregate(
+                    vec![
+                        doc! {
+                            "$match": {
+                                "challenge_id": challenge_id
+                            }
+                        },
+                        doc! {
+                            "$project": {
+                                "post": "$$ROOT"
+                            }
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "users",
+                                "localField": "post.user_id",
+                                "foreignField": "id",
+                                "pipeline": vec![
+                                    doc! {
+                                        "$match": {
+                                            "$expr": {
+                                                "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                            }
+                                        }
+                                    }
+                                ],
+                                "as": "user"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$user"
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "ratings",
+                                "localField": "post.id",
+                                "foreignField": "post_id",
+                                "pipeline": vec![
+                                    doc! {
+                                        "$group": {
+                                            "_id": Bson::Null,
+                                            "average": {
+                                                "$avg": {
+                                                    "$toDouble": "$rating"
+                                                }
+                                            },
+                                            "count": {
+                                                "$sum": 1
+                                            }
+                                        }
+                                    }
+                                ],
+                                "as": "average_rating"
+                            }
+                        },
+                        doc! {
+                            "$unwind": {
+                                "path": "$average_rating",
+                                "preserveNullAndEmptyArrays": true
+                            }
+                        },
+                        doc! {
+                            "$sort": {
+                                "average_rating.average": -1,
+                                "average_rating.count": -1
+                            }
+                        },
+                        doc! {
+                            "$limit": 50
+                        },
+                    ],
+                    AggregateOptions::builder().allow_disk_use(true).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
@@ -1,86 +1,648 @@
+use crate::canvas::canvas::Snapshot;
+use crate::canvas::guide::GuideAxis;
 use crate::canvas::tool;
 use crate::canvas::tool::Tool;
 use crate::database;
+use crate::database::base::{audit_log, driver_error, retry_with_backoff, CONNECTION_SETTINGS};
 use crate::debug_message;
 use crate::scene::Globals;
-use crate::scenes::data::drawing::Tag;
+use crate::scenes::data::auth::User;
+use crate::scenes::data::drawing::{DrawingPermission, Tag};
 use crate::utils::errors::{DebugError, Error};
-use mongodb::bson::{doc, Bson, Document, Uuid, UuidRepresentation};
+use crate::utils::rate_limiter::{self, RateLimiter};
+use crate::utils::serde::{Deserialize, Serialize};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use mongodb::bson::{doc, Bson, DateTime, Document, Uuid, UuidRepresentation};
+use mongodb::change_stream::event::ChangeStreamEvent;
+use mongodb::change_stream::ChangeStream;
+use mongodb::options::{FindOptions, ReplaceOptions};
 use mongodb::Database;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 /// Gets the data for the drawing stored online with the given id.
 pub async fn get_drawing(
     db: &Database,
     id: Uuid,
-) -> Result<(Vec<(Uuid, String)>, Vec<(Arc<dyn Tool>, Uuid)>), Error> {
-    let layers = match db
-        .collection::<Document>("canvases")
-        .find_one(
+) -> Result<
+    (
+        Vec<(Uuid, String)>,
+        Vec<(Arc<dyn Tool>, Uuid)>,
+        Option<(u32, u32)>,
+        Vec<GuideAxis>,
+    ),
+    Error,
+> {
+    retry_with_backoff(
+        || async move {
+            let (layers, size, guides) = match db
+                .collection::<Document>("canvases")
+                .find_one(
+                    doc! {
+                        "id": id
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(Some(document)) => {
+                    let size = document
+                        .get_i32("width")
+                        .ok()
+                        .zip(document.get_i32("height").ok())
+                        .map(|(width, height)| (width as u32, height as u32));
+                    let guides = document.get_array("guides").map_or(vec![], |guides| {
+                        guides
+                            .iter()
+                            .filter_map(|guide| guide.as_document().map(GuideAxis::deserialize))
+                            .collect()
+                    });
+
+                    if let Ok(layers) = document.get_array("layers") {
+                        let layers = layers
+                            .iter()
+                            .filter_map(|document| {
+                                document.as_document().map(|document| {
+                                    (
+                                        if let Some(Bson::Binary(bin)) = document.get("id") {
+                                            bin.to_uuid_with_representation(
+                                                UuidRepresentation::Standard,
+                                            )
+                                            .unwrap()
+                                        } else {
+                                            Uuid::from_bytes([0; 16])
+                                        },
+                                        document.get_str("name").unwrap().to_string(),
+                                    )
+                                })
+                            })
+                            .collect();
+
+                        (layers, size, guides)
+                    } else {
+                        return Err(Error::DebugError(DebugError::new(debug_message!(
+                            "Error retrieving layers from database!"
+                        ))));
+                    }
+                }
+                Ok(None) => {
+                    return Err(debug_message!(
+                        "The canvas with id {} could not be found in the database!",
+                        id
+                    )
+                    .into());
+                }
+                Err(err) => {
+                    return Err(driver_error(&err, debug_message!("{}", err)));
+                }
+            };
+
+            let tools = match db
+                .collection::<Document>("tools")
+                .find(
+                    doc! {
+                        "canvas_id": id
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(mut documents) => download_rasters(
+                    database::base::resolve_cursor::<Document>(&mut documents).await,
+                )
+                .await
+                .iter()
+                .filter_map(|document| tool::get_deserialized(document))
+                .collect(),
+                Err(err) => {
+                    return Err(driver_error(&err, debug_message!("{}", err)));
+                }
+            };
+
+            Ok((layers, tools, size, guides))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Moves the images of the raster tools among the given documents into file storage, storing
+/// the paths of the files instead, to keep the documents small.
+async fn upload_rasters(canvas_id: Uuid, tools: Vec<Document>) -> Result<Vec<Document>, Error> {
+    let mut documents = Vec::with_capacity(tools.len());
+
+    for mut tool in tools {
+        if tool.get_str("name") == Ok("Raster") {
+            if let Some(Bson::String(data)) = tool.remove("data") {
+                let data = STANDARD
+                    .decode(data)
+                    .map_err(|err| debug_message!("{}", err).into())?;
+                let path = format!("/rasters/{}/{}", canvas_id, Uuid::new());
+
+                database::base::upload_file(path.clone(), data).await?;
+                tool.insert("file", path);
+            }
+        }
+
+        documents.push(tool);
+    }
+
+    Ok(documents)
+}
+
+/// Downloads the images of the raster tools among the given documents from file storage.
+/// Rasters whose images cannot be downloaded are left empty.
+async fn download_rasters(tools: Vec<Document>) -> Vec<Document> {
+    let mut documents = Vec::with_capacity(tools.len());
+
+    for mut tool in tools {
+        if let Some(Bson::String(path)) = tool.remove("file") {
+            if let Ok(data) = database::base::download_file(path).await {
+                tool.insert("data", STANDARD.encode(data));
+            }
+        }
+
+        documents.push(tool);
+    }
+
+    documents
+}
+
+/// Opens a change stream over the tools inserted in the given drawing by other sessions than
+/// the given one.
+pub async fn watch_tools(
+    db: &Database,
+    canvas_id: Uuid,
+    session: Uuid,
+) -> Result<ChangeStream<ChangeStreamEvent<Document>>, Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("tools")
+                .watch(
+                    vec![doc! {
+                        "$match": {
+                            "operationType": "insert",
+                            "fullDocument.canvas_id": canvas_id,
+                            "fullDocument.session": { "$ne": session }
+                        }
+                    }],
+                    None,
+                )
+                .await
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Stores the position of the cursor of the given editing session over the given drawing.
+pub async fn update_cursor(
+    db: &Database,
+    canvas_id: Uuid,
+    session: Uuid,
+    user_id: Uuid,
+    name: String,
+    position: (f32, f32),
+) -> Result<(), Error> {
+    retry_with_backoff(
+        || {
+            let name = name.clone();
+
+            async move {
+                db.collection::<Document>("cursors")
+                    .replace_one(
+                        doc! {
+                            "canvas_id": canvas_id,
+                            "session": session
+                        },
+                        doc! {
+                            "canvas_id": canvas_id,
+                            "session": session,
+                            "user_id": user_id,
+                            "name": name,
+                            "x": position.0 as f64,
+                            "y": position.1 as f64,
+                            "updated_at": DateTime::now()
+                        },
+                        ReplaceOptions::builder().upsert(true).build(),
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Removes the cursor of the given editing session over the given drawing.
+pub async fn delete_cursor(db: &Database, canvas_id: Uuid, session: Uuid) -> Result<(), Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("cursors")
+                .delete_one(
+                    doc! {
+                        "canvas_id": canvas_id,
+                        "session": session
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the number of editing sessions on the given drawing, other than the given one, that
+/// have a stored cursor position.
+pub async fn count_collaborators(
+    db: &Database,
+    canvas_id: Uuid,
+    session: Uuid,
+) -> Result<usize, Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("cursors")
+                .count_documents(
+                    doc! {
+                        "canvas_id": canvas_id,
+                        "session": { "$ne": session }
+                    },
+                    None,
+                )
+                .await
+                .map(|count| count as usize)
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Opens a change stream over the cursor positions of the editing sessions on the given drawing,
+/// other than the given one.
+pub async fn watch_cursors(
+    db: &Database,
+    canvas_id: Uuid,
+    session: Uuid,
+) -> Result<ChangeStream<ChangeStreamEvent<Document>>, Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("cursors")
+                .watch(
+                    vec![doc! {
+                        "$match": {
+                            "operationType": { "$in": ["insert", "replace"] },
+                            "fullDocument.canvas_id": canvas_id,
+                            "fullDocument.session": { "$ne": session }
+                        }
+                    }],
+                    None,
+                )
+                .await
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Stores the given state of the drawing as a snapshot.
+pub async fn create_snapshot(
+    db: &Database,
+    canvas_id: Uuid,
+    snapshot: &Snapshot,
+    layers: Vec<(Uuid, String)>,
+    tools: Vec<Document>,
+    size: (u32, u32),
+) -> Result<(), Error> {
+    db.collection::<Document>("snapshots")
+        .insert_one(
             doc! {
-                "id": id
+                "id": snapshot.get_id(),
+                "canvas_id": canvas_id,
+                "name": snapshot.get_name().clone(),
+                "created": DateTime::from_system_time((*snapshot.get_created()).into()),
+                "width": size.0 as i32,
+                "height": size.1 as i32,
+                "layers": layers.into_iter().map(
+                    |(id, name)| doc! {
+                        "id": id,
+                        "name": name
+                    }
+                ).collect::<Vec<Document>>(),
+                "tools": tools
             },
             None,
         )
         .await
-    {
-        Ok(Some(document)) => {
-            if let Ok(layers) = document.get_array("layers") {
-                layers
+        .map(|_| ())
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+}
+
+/// Returns the snapshots of the given drawing, without their contents.
+pub async fn get_snapshots(db: &Database, canvas_id: Uuid) -> Result<Vec<Snapshot>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("snapshots")
+                .find(
+                    doc! {
+                        "canvas_id": canvas_id
+                    },
+                    FindOptions::builder()
+                        .projection(doc! {
+                            "id": 1,
+                            "name": 1,
+                            "created": 1
+                        })
+                        .build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(database::base::resolve_cursor::<Document>(cursor)
+                    .await
                     .iter()
                     .filter_map(|document| {
-                        document.as_document().map(|document| {
-                            (
-                                if let Some(Bson::Binary(bin)) = document.get("id") {
-                                    bin.to_uuid_with_representation(UuidRepresentation::Standard)
-                                        .unwrap()
-                                } else {
-                                    Uuid::from_bytes([0; 16])
-                                },
-                                document.get_str("name").unwrap().to_string(),
-                            )
-                        })
+                        let id = match document.get("id") {
+                            Some(Bson::Binary(bin)) => bin
+                                .to_uuid_with_representation(UuidRepresentation::Standard)
+                                .ok()?,
+                            _ => return None,
+                        };
+                        let name = document.get_str("name").ok()?;
+                        let created = document.get_datetime("created").ok()?;
+
+                        Some(Snapshot::new(id, name, SystemTime::from(*created).into()))
                     })
-                    .collect()
-            } else {
-                return Err(Error::DebugError(DebugError::new(debug_message!(
-                    "Error retrieving layers from database!"
-                ))));
+                    .collect()),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
             }
-        }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Replaces the tools and layers of the drawing with the ones stored in the given snapshot.
+pub async fn restore_snapshot(
+    db: &Database,
+    canvas_id: Uuid,
+    snapshot_id: Uuid,
+) -> Result<(), Error> {
+    let snapshot = match db
+        .collection::<Document>("snapshots")
+        .find_one(
+            doc! {
+                "id": snapshot_id,
+                "canvas_id": canvas_id
+            },
+            None,
+        )
+        .await
+    {
+        Ok(Some(document)) => document,
         Ok(None) => {
             return Err(debug_message!(
-                "The canvas with id {} could not be found in the database!",
-                id
+                "The snapshot with id {} could not be found in the database!",
+                snapshot_id
             )
             .into());
         }
         Err(err) => {
-            return Err(debug_message!("{}", err).into());
+            return Err(driver_error(&err, debug_message!("{}", err)));
         }
     };
 
-    let tools = match db
-        .collection::<Document>("tools")
-        .find(
+    let tools: Vec<Document> = snapshot
+        .get_array("tools")
+        .map(|tools| {
+            tools
+                .iter()
+                .filter_map(|tool| tool.as_document().cloned())
+                .collect()
+        })
+        .unwrap_or_default();
+    let layers = snapshot
+        .get_array("layers")
+        .map_err(|err| debug_message!("{}", err).into())?
+        .clone();
+
+    db.collection::<Document>("tools")
+        .delete_many(
             doc! {
-                "canvas_id": id
+                "canvas_id": canvas_id
             },
             None,
         )
         .await
-    {
-        Ok(mut documents) => database::base::resolve_cursor::<Document>(&mut documents)
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+    if tools.len() > 0 {
+        db.collection::<Document>("tools")
+            .insert_many(tools, None)
             .await
-            .iter()
-            .filter_map(|document| tool::get_deserialized(document))
-            .collect(),
-        Err(err) => {
-            return Err(debug_message!("{}", err).into());
-        }
-    };
+            .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+    }
+
+    db.collection::<Document>("canvases")
+        .update_one(
+            doc! {
+                "id": canvas_id
+            },
+            doc! {
+                "$set": {
+                    "width": snapshot.get_i32("width").unwrap_or(800),
+                    "height": snapshot.get_i32("height").unwrap_or(600),
+                    "layers": layers
+                }
+            },
+            None,
+        )
+        .await
+        .map(|_| ())
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+}
+
+/// Returns whether the drawing with the given id is locked, along with the id of its owner and
+/// the permissions of the users it is shared with.
+pub async fn get_access(
+    db: &Database,
+    id: Uuid,
+) -> Result<(bool, Uuid, HashMap<Uuid, DrawingPermission>), Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("canvases")
+                .find_one(
+                    doc! {
+                        "id": id
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(Some(document)) => {
+                    let owner = match document.get("user_id") {
+                        Some(Bson::Binary(bin)) => bin
+                            .to_uuid_with_representation(UuidRepresentation::Standard)
+                            .map_err(|err| debug_message!("{}", err).into())?,
+                        _ => {
+                            return Err(
+                                debug_message!("The canvas with id {} has no owner!", id).into()
+                            );
+                        }
+                    };
+
+                    let permissions = match document.get_document("permissions") {
+                        Ok(permissions) => permissions
+                            .iter()
+                            .filter_map(|(user_id, permission)| {
+                                Uuid::parse_str(user_id)
+                                    .ok()
+                                    .zip(permission.as_str().and_then(|permission| {
+                                        DrawingPermission::from_name(permission)
+                                    }))
+                            })
+                            .collect(),
+                        Err(_) => HashMap::new(),
+                    };
+
+                    Ok((
+                        document.get_bool("is_locked").unwrap_or(false),
+                        owner,
+                        permissions,
+                    ))
+                }
+                Ok(None) => Err(debug_message!(
+                    "The canvas with id {} could not be found in the database!",
+                    id
+                )
+                .into()),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Locks or unlocks the drawing with the given id, if the given user can manage it.
+pub async fn set_lock(
+    db: &Database,
+    user_id: Uuid,
+    canvas_id: Uuid,
+    locked: bool,
+) -> Result<(), Error> {
+    let matched = retry_with_backoff(
+        || async move {
+            db.collection::<Document>("canvases")
+                .update_one(
+                    managed_canvas_filter(canvas_id, user_id),
+                    doc! {
+                        "$set": {
+                            "is_locked": locked
+                        }
+                    },
+                    None,
+                )
+                .await
+                .map(|result| result.matched_count)
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await?;
+
+    if matched == 0 {
+        return Err(debug_message!("The user can't manage the canvas {}!", canvas_id).into());
+    }
+
+    Ok(())
+}
 
-    Ok((layers, tools))
+/// Returns the filter of the drawing with the given id, if the given user is allowed to manage
+/// it, either as its owner or as an administrator of it.
+fn managed_canvas_filter(canvas_id: Uuid, user_id: Uuid) -> Document {
+    doc! {
+        "id": canvas_id,
+        "$or": [
+            { "user_id": user_id },
+            { format!("permissions.{}", user_id): DrawingPermission::Admin.to_string() }
+        ]
+    }
+}
+
+/// Gives the user with the given id the given permission on the drawing, if the sharing user can
+/// manage it.
+pub async fn set_permission(
+    db: &Database,
+    sharer_id: Uuid,
+    canvas_id: Uuid,
+    user_id: Uuid,
+    permission: DrawingPermission,
+) -> Result<(), Error> {
+    let matched = retry_with_backoff(
+        || async move {
+            db.collection::<Document>("canvases")
+                .update_one(
+                    managed_canvas_filter(canvas_id, sharer_id),
+                    doc! {
+                        "$set": {
+                            format!("permissions.{}", user_id): permission.to_string()
+                        }
+                    },
+                    None,
+                )
+                .await
+                .map(|result| result.matched_count)
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await?;
+
+    if matched == 0 {
+        return Err(debug_message!("The user can't manage the canvas {}!", canvas_id).into());
+    }
+
+    Ok(())
+}
+
+/// Returns the users with the given ids.
+pub async fn get_users(db: &Database, ids: Vec<Uuid>) -> Result<Vec<User>, Error> {
+    retry_with_backoff(
+        || {
+            let ids = ids.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .find(
+                        doc! {
+                            "id": {
+                                "$in": ids
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => Ok(database::base::resolve_cursor::<User>(cursor).await),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Creates a new drawing with the given id, owned by the given user.
@@ -91,7 +653,7 @@ pub async fn create_drawing(
 ) -> Result<(Uuid, String), Error> {
     let layer_id = Uuid::new();
 
-    match db
+    let layer = match db
         .collection::<Document>("canvases")
         .insert_one(
             doc! {
@@ -107,57 +669,180 @@ pub async fn create_drawing(
         )
         .await
     {
-        Ok(_) => Ok((layer_id, "New layer".into())),
-        Err(err) => Err(debug_message!("{}", err).into()),
+        Ok(_) => (layer_id, String::from("New layer")),
+        Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+    };
+
+    audit_log(db, user_id, "create_drawing", Some(id)).await?;
+
+    Ok(layer)
+}
+
+/// Creates a copy of the drawing with the given id, along with its tools and preview. The copy
+/// is not shared with anyone, is not locked, and has its own copies of the raster images. If
+/// copying fails, whatever was already copied is removed.
+pub async fn duplicate_drawing(
+    db: &Database,
+    id: Uuid,
+    new_id: Uuid,
+    user_id: Uuid,
+    name: String,
+) -> Result<(), Error> {
+    let mut canvas = match db
+        .collection::<Document>("canvases")
+        .find_one(
+            doc! {
+                "id": id
+            },
+            None,
+        )
+        .await
+    {
+        Ok(Some(document)) => document,
+        Ok(None) => {
+            return Err(debug_message!(
+                "The canvas with id {} could not be found in the database!",
+                id
+            )
+            .into());
+        }
+        Err(err) => {
+            return Err(driver_error(&err, debug_message!("{}", err)));
+        }
+    };
+
+    canvas.remove("_id");
+    canvas.remove("permissions");
+    canvas.remove("is_locked");
+    canvas.insert("id", new_id);
+    canvas.insert("name", name);
+
+    let tools: Vec<Document> = match db
+        .collection::<Document>("tools")
+        .find(
+            doc! {
+                "canvas_id": id
+            },
+            None,
+        )
+        .await
+    {
+        Ok(ref mut cursor) => database::base::resolve_cursor::<Document>(cursor)
+            .await
+            .into_iter()
+            .map(|mut tool| {
+                tool.remove("_id");
+                tool.insert("canvas_id", new_id);
+
+                tool
+            })
+            .collect(),
+        Err(err) => {
+            return Err(driver_error(&err, debug_message!("{}", err)));
+        }
+    };
+
+    let copy = async {
+        // The raster images are copied as well, so that the drawings don't share their files.
+        let tools = upload_rasters(new_id, download_rasters(tools).await).await?;
+
+        db.collection::<Document>("canvases")
+            .insert_one(canvas, None)
+            .await
+            .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+        if tools.len() > 0 {
+            db.collection::<Document>("tools")
+                .insert_many(tools, None)
+                .await
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+        }
+
+        let preview = database::base::download_file(format!("/{}/{}.webp", user_id, id)).await?;
+        database::base::upload_file(format!("/{}/{}.webp", user_id, new_id), preview).await
+    };
+
+    if let Err(err) = copy.await {
+        // The copy would otherwise be left behind half made. Cleaning up is best effort, since
+        // the original error is the one reported.
+        let _ = db
+            .collection::<Document>("canvases")
+            .delete_one(doc! { "id": new_id }, None)
+            .await;
+        let _ = db
+            .collection::<Document>("tools")
+            .delete_many(doc! { "canvas_id": new_id }, None)
+            .await;
+        let _ = database::base::delete_data(format!("/rasters/{}", new_id)).await;
+
+        return Err(err);
     }
+
+    Ok(())
 }
 
 /// Creates a new post with the given id and credentials. The drawing itself will be stored
-/// in dropbox, and will be identified using the post id.
+/// in dropbox, and will be identified using the post id. Posts submitted to a challenge are
+/// tagged with its id.
 pub async fn create_post(
     db: &Database,
+    rate_limiter: &Mutex<RateLimiter>,
     id: Uuid,
     user_id: Uuid,
     description: String,
     tags: Vec<String>,
+    challenge: Option<Uuid>,
 ) -> Result<(), Error> {
+    rate_limiter::check(rate_limiter, "create_post")?;
+
+    let mut post = doc! {
+        "id": id,
+        "user_id": user_id,
+        "description": description,
+        "tags": tags.clone()
+    };
+    if let Some(challenge) = challenge {
+        post.insert("challenge_id", challenge);
+    }
+
     match db
         .collection::<Document>("posts")
-        .insert_one(
-            doc! {
-                "id": id,
-                "user_id": user_id,
-                "description": description,
-                "tags": tags.clone()
-            },
-            None,
-        )
+        .insert_one(post, None)
         .await
     {
         Ok(_) => Ok(()),
-        Err(err) => Err(debug_message!("{}", err).into()),
+        Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
     }
 }
 
 /// Attempt to get a list of all tags.
 pub async fn get_tags(db: &Database) -> Result<Vec<Tag>, Error> {
-    match db.collection::<Document>("tags").find(doc! {}, None).await {
-        Ok(ref mut cursor) => Ok(database::base::resolve_cursor::<Tag>(cursor).await),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+    retry_with_backoff(
+        || async move {
+            match db.collection::<Document>("tags").find(doc! {}, None).await {
+                Ok(ref mut cursor) => Ok(database::base::resolve_cursor::<Tag>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Updates the tool data of the drawing, by deleting everything that was undone and inserting
-/// everything in the given "tools" parameter.
+/// everything in the given "tools" parameter. The update is recorded as made by the given user.
 pub async fn update_drawing(
     db: &Database,
     canvas_id: Uuid,
+    user_id: Uuid,
     canvas_name: String,
     delete_lower_bound: u32,
     delete_upper_bound: u32,
     tools: Vec<Document>,
     removed_layers: Vec<Uuid>,
     layer_data: Vec<(Uuid, String)>,
+    size: (u32, u32),
+    guides: Vec<GuideAxis>,
 ) -> Result<(), Error> {
     match db
         .collection::<Document>("tools")
@@ -184,11 +869,13 @@ pub async fn update_drawing(
     {
         Ok(_) => {}
         Err(err) => {
-            return Err(debug_message!("{}", err).into());
+            return Err(driver_error(&err, debug_message!("{}", err)));
         }
     }
 
     if tools.len() > 0 {
+        let tools = upload_rasters(canvas_id, tools).await?;
+
         match db
             .collection::<Document>("tools")
             .insert_many(tools, None)
@@ -196,7 +883,7 @@ pub async fn update_drawing(
         {
             Ok(_) => {}
             Err(err) => {
-                return Err(debug_message!("{}", err).into());
+                return Err(driver_error(&err, debug_message!("{}", err)));
             }
         }
     }
@@ -210,11 +897,16 @@ pub async fn update_drawing(
             doc! {
                 "$set": {
                     "name": canvas_name,
+                    "width": size.0 as i32,
+                    "height": size.1 as i32,
                     "layers": layer_data.into_iter().map(
                         |(id, name)| doc! {
                             "id": id,
                             "name": name
                         }
+                    ).collect::<Vec<Document>>(),
+                    "guides": guides.iter().map(
+                        |guide| Serialize::<Document>::serialize(guide)
                     ).collect::<Vec<Document>>()
                 }
             },
@@ -222,29 +914,61 @@ pub async fn update_drawing(
         )
         .await
     {
-        Ok(_) => Ok(()),
-        Err(err) => Err(debug_message!("{}", err).into()),
+        Ok(_) => {}
+        Err(err) => {
+            return Err(driver_error(&err, debug_message!("{}", err)));
+        }
     }
+
+    audit_log(db, user_id, "update_drawing", Some(canvas_id)).await
 }
 
 pub async fn delete_drawing(id: Uuid, globals: &Globals) -> Result<(), Error> {
+    let mut attempted = false;
+
+    retry_with_backoff(
+        || {
+            let retry = std::mem::replace(&mut attempted, true);
+
+            async move {
+                let db = globals
+                    .get_db()
+                    .ok_or(debug_message!("No database connection.").into())?;
+
+                let canvases = db.collection::<Document>("canvases");
+
+                match canvases
+                    .delete_one(
+                        doc! {
+                            "id": id
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(result) if result.deleted_count == 1 => Ok(()),
+                    // A previous attempt may have deleted the drawing before failing.
+                    Ok(_) if retry => Ok(()),
+                    Ok(_) => Err(debug_message!(
+                        "Could not find drawing with id {} to delete.",
+                        id
+                    )
+                    .into()),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await?;
+
     let db = globals
         .get_db()
         .ok_or(debug_message!("No database connection.").into())?;
+    let user_id = globals
+        .get_user()
+        .ok_or(debug_message!("No user logged in.").into())?
+        .get_id();
 
-    let canvases = db.collection::<Document>("canvases");
-
-    match canvases
-        .delete_one(
-            doc! {
-                "id": id
-            },
-            None,
-        )
-        .await
-    {
-        Ok(result) if result.deleted_count == 1 => Ok(()),
-        Ok(_) => Err(debug_message!("Could not find drawing with id {} to delete.", id).into()),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+    audit_log(&db, user_id, "delete_drawing", Some(id)).await
 }
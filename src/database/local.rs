@@ -0,0 +1,170 @@
+use crate::debug_message;
+use crate::utils::errors::Error;
+use chrono::Local;
+use directories::ProjectDirs;
+use mongodb::bson::Uuid;
+use rusqlite::{params, Connection};
+
+/// The name of the list of drawings kept before the metadata store was introduced.
+const LEGACY_DRAWINGS_FILE: &str = "drawings.json";
+
+/// Opens the local metadata store, creating it if it does not exist yet. The drawings listed in
+/// the legacy `drawings.json` file are moved into the store the first time it is opened.
+fn open() -> Result<Connection, Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+    let dir_path = proj_dirs.data_local_dir();
+    std::fs::create_dir_all(dir_path).map_err(|err| debug_message!("{}", err).into())?;
+
+    let connection = Connection::open(dir_path.join("chartsy.db"))
+        .map_err(|err| debug_message!("{}", err).into())?;
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS drawings (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            (),
+        )
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    let legacy_path = dir_path.join(LEGACY_DRAWINGS_FILE);
+    if let Ok(input) = std::fs::read_to_string(&legacy_path) {
+        let drawings = json::parse(&*input).map_err(|err| debug_message!("{}", err).into())?;
+        let now = Local::now().timestamp();
+
+        for drawing in drawings.members() {
+            let (Some(id), Some(name)) = (drawing["id"].as_str(), drawing["name"].as_str()) else {
+                continue;
+            };
+
+            connection
+                .execute(
+                    "INSERT OR IGNORE INTO drawings (id, name, created_at, updated_at)
+                    VALUES (?1, ?2, ?3, ?3)",
+                    params![id, name, now],
+                )
+                .map_err(|err| debug_message!("{}", err).into())?;
+        }
+
+        std::fs::rename(&legacy_path, legacy_path.with_extension("json.bak"))
+            .map_err(|err| debug_message!("{}", err).into())?;
+    }
+
+    Ok(connection)
+}
+
+/// Runs the given queries on the local metadata store, on a thread where blocking is allowed.
+async fn run<T>(
+    queries: impl FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+) -> Result<T, Error>
+where
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let connection = open()?;
+
+        queries(&connection).map_err(|err| debug_message!("{}", err).into())
+    })
+    .await
+    .map_err(|err| debug_message!("{}", err).into())?
+}
+
+/// Collects the ids and names returned by the given query, skipping the rows with invalid ids.
+fn collect_drawings(
+    connection: &Connection,
+    query: &str,
+    params: impl rusqlite::Params,
+) -> rusqlite::Result<Vec<(Uuid, String)>> {
+    let mut statement = connection.prepare(query)?;
+    let rows = statement.query_map(params, |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut drawings = vec![];
+    for row in rows {
+        let (id, name) = row?;
+        if let Ok(id) = Uuid::parse_str(id) {
+            drawings.push((id, name));
+        }
+    }
+
+    Ok(drawings)
+}
+
+/// Returns the ids and names of the drawings stored locally, in the order they were created.
+pub async fn get_drawings() -> Result<Vec<(Uuid, String)>, Error> {
+    run(|connection| {
+        collect_drawings(
+            connection,
+            "SELECT id, name FROM drawings ORDER BY created_at, rowid",
+            (),
+        )
+    })
+    .await
+}
+
+/// Returns the ids and names of the drawings stored locally whose names contain the given query,
+/// ignoring case, in the order they were created.
+pub async fn search_drawings(query: &str) -> Result<Vec<(Uuid, String)>, Error> {
+    let pattern = format!(
+        "%{}%",
+        query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+
+    run(move |connection| {
+        collect_drawings(
+            connection,
+            "SELECT id, name FROM drawings WHERE name LIKE ?1 ESCAPE '\\'
+            ORDER BY created_at, rowid",
+            params![pattern],
+        )
+    })
+    .await
+}
+
+/// Adds a drawing with the given id and name to the local metadata store.
+pub async fn insert_drawing(id: Uuid, name: String) -> Result<(), Error> {
+    let now = Local::now().timestamp();
+
+    run(move |connection| {
+        connection.execute(
+            "INSERT OR REPLACE INTO drawings (id, name, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?3)",
+            params![id.to_string(), name, now],
+        )
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Sets the name of the drawing with the given id, and marks it as modified now.
+pub async fn update_drawing(id: Uuid, name: String) -> Result<(), Error> {
+    let now = Local::now().timestamp();
+
+    run(move |connection| {
+        connection.execute(
+            "UPDATE drawings SET name = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id.to_string(), name, now],
+        )
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Removes the drawing with the given id from the local metadata store.
+pub async fn delete_drawing(id: Uuid) -> Result<(), Error> {
+    run(move |connection| {
+        connection.execute(
+            "DELETE FROM drawings WHERE id = ?1",
+            params![id.to_string()],
+        )
+    })
+    .await
+    .map(|_| ())
+}
@@ -1,4 +1,5 @@
 use crate::database;
+use crate::database::base::{driver_error, retry_with_backoff, CONNECTION_SETTINGS};
 use crate::debug_message;
 use crate::utils::errors::Error;
 use mongodb::bson::{doc, Document, Uuid};
@@ -6,17 +7,90 @@ use mongodb::Database;
 
 /// Gets a list of drawings owned by the user with the given id.
 pub async fn get_drawings(db: &Database, user_id: Uuid) -> Result<Vec<Document>, Error> {
-    match db
-        .collection::<Document>("canvases")
-        .find(
-            doc! {
-                "user_id": user_id
-            },
-            None,
-        )
-        .await
-    {
-        Ok(ref mut cursor) => Ok(database::base::resolve_cursor::<Document>(cursor).await),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("canvases")
+                .find(
+                    doc! {
+                        "user_id": user_id
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(database::base::resolve_cursor::<Document>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Gets a list of drawings owned by the user with the given id, whose names contain the given
+/// query, ignoring case.
+pub async fn search_drawings(
+    db: &Database,
+    user_id: Uuid,
+    query: String,
+) -> Result<Vec<Document>, Error> {
+    retry_with_backoff(
+        || {
+            let query = query.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("canvases")
+                    .find(
+                        doc! {
+                            "user_id": user_id,
+                            "name": {
+                                "$regex": regex::escape(&*query),
+                                "$options": "i"
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => {
+                        Ok(database::base::resolve_cursor::<Document>(cursor).await)
+                    }
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Changes the name of the drawing with the given id.
+pub async fn rename_drawing(db: &Database, id: Uuid, name: String) -> Result<(), Error> {
+    retry_with_backoff(
+        || {
+            let name = name.clone();
+
+            async move {
+                db.collection::<Document>("canvases")
+                    .update_one(
+                        doc! {
+                            "id": id
+                        },
+                        doc! {
+                            "$set": {
+                                "name": name
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
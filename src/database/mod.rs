@@ -1,6 +1,9 @@
 pub mod base;
 pub mod main;
+pub mod admin;
 pub mod auth;
+pub mod challenge;
 pub mod drawing;
+pub mod local;
 pub mod posts;
 pub mod settings;
\ No newline at end of file
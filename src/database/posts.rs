@@ -1,185 +1,550 @@
+use crate::database::admin;
 use crate::database::base::resolve_cursor;
+use crate::database::base::{audit_log, driver_error, retry_with_backoff, CONNECTION_SETTINGS};
 use crate::debug_message;
 use crate::scene::Globals;
 use crate::scenes::data::auth::User;
-use crate::scenes::data::posts::{Comment, Post};
-use crate::utils::errors::{AuthError, DebugError, Error};
+use crate::scenes::data::posts::{
+    Comment, Post, SortOrder, COMMENT_PAGE_SIZE, USER_SEARCH_PAGE_SIZE,
+};
+use crate::utils::errors::{AuthError, Error};
+use crate::utils::rate_limiter::{self, RateLimiter};
 use crate::utils::serde::Deserialize;
-use mongodb::bson::{doc, Document, Uuid};
-use mongodb::options::{AggregateOptions, UpdateOptions};
+use mongodb::bson::{doc, Bson, DateTime, Document, Uuid, UuidRepresentation};
+use mongodb::options::{AggregateOptions, FindOptions, UpdateOptions};
 use mongodb::Database;
+use std::sync::Mutex;
 
-/// Gets a list of comments with the given filter, which will decide the parent of the comments.
-pub async fn get_comments(db: &Database, filter: Document) -> Result<Vec<Comment>, Error> {
-    match db
-        .collection::<Result<Document, mongodb::error::Error>>("comments")
-        .aggregate(
+/// Gets a page of comments with the given filter, which will decide the parent of the comments.
+/// The comments are sorted from the newest, and the page starts after the given comment, so that
+/// comments sent in the meantime do not shift the pages.
+pub async fn get_comments(
+    db: &Database,
+    filter: Document,
+    after: Option<(DateTime, Uuid)>,
+) -> Result<Vec<Comment>, Error> {
+    let mut filter = filter;
+    if let Some((created, id)) = after {
+        filter.insert(
+            "$or",
             vec![
-                doc! {
-                    "$match": filter,
-                },
-                doc! {
-                    "$lookup": {
-                        "from": "users",
-                        "localField": "user_id",
-                        "foreignField": "id",
-                        "pipeline": vec![
+                doc! { "created": { "$lt": created } },
+                doc! { "created": created, "id": { "$gt": id } },
+            ],
+        );
+    }
+
+    retry_with_backoff(
+        || {
+            let filter = filter.clone();
+
+            async move {
+                match db
+                    .collection::<Result<Document, mongodb::error::Error>>("comments")
+                    .aggregate(
+                        vec![
                             doc! {
-                                "$match": {
-                                    "$expr": {
-                                        "$eq": [ { "$type": "$expiration_date" }, "missing" ]
-                                    }
+                                "$match": filter,
+                            },
+                            doc! {
+                                "$sort": {
+                                    "created": -1,
+                                    "id": 1
                                 }
-                            }
+                            },
+                            doc! {
+                                "$limit": COMMENT_PAGE_SIZE as i64
+                            },
+                            doc! {
+                                "$lookup": {
+                                    "from": "users",
+                                    "localField": "user_id",
+                                    "foreignField": "id",
+                                    "pipeline": vec![
+                                        doc! {
+                                            "$match": {
+                                                "$expr": {
+                                                    "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                                }
+                                            }
+                                        }
+                                    ],
+                                    "as": "user"
+                                }
+                            },
+                            doc! {
+                                "$unwind": "$user"
+                            },
+                            doc! {
+                                "$lookup": {
+                                    "from": "users",
+                                    "localField": "mentions",
+                                    "foreignField": "id",
+                                    "as": "mentioned_users"
+                                }
+                            },
                         ],
-                        "as": "user"
-                    }
-                },
-                doc! {
-                    "$unwind": "$user"
-                },
-            ],
-            AggregateOptions::builder().allow_disk_use(true).build(),
-        )
-        .await
-    {
-        Ok(ref mut cursor) => Ok(resolve_cursor::<Comment>(cursor).await),
-        Err(err) => Err(Error::DebugError(DebugError::new(err.to_string()))),
-    }
+                        AggregateOptions::builder().allow_disk_use(true).build(),
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => Ok(resolve_cursor::<Comment>(cursor).await),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
-/// Inserts a comment from the given document.
-pub async fn create_comment(db: &Database, comment: &Document) -> Result<(), Error> {
+/// Inserts a comment of the given user from the given document.
+pub async fn create_comment(
+    db: &Database,
+    rate_limiter: &Mutex<RateLimiter>,
+    user_id: Uuid,
+    comment: &Document,
+) -> Result<(), Error> {
+    rate_limiter::check(rate_limiter, "create_comment")?;
+    admin::ensure_not_banned(db, user_id).await?;
+
     db.collection::<Document>("comments")
         .insert_one(comment, None)
         .await
-        .map(|_| ())
-        .map_err(|err| debug_message!("{}", err).into())
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+    let comment_id = match comment.get("id") {
+        Some(Bson::Binary(bin)) => bin
+            .to_uuid_with_representation(UuidRepresentation::Standard)
+            .ok(),
+        _ => None,
+    };
+
+    audit_log(db, user_id, "create_comment", comment_id).await
 }
 
-/// Generates recommendations for the user with the given id.
-pub async fn get_recommendations(db: &Database, user_id: Uuid) -> Result<Vec<Post>, Error> {
-    match db
-        .collection::<Document>("similarities")
-        .aggregate(
-            Vec::from([
-                // Get only the similarities with the authenticated user.
-                doc! {
-                    "$match": {
-                        "user_id": user_id
-                    }
-                },
-                // Leave only the id of the other user.
-                doc! {
-                    "$project": {
-                        "_id": {
-                            "$arrayElemAt": [
-                                {
-                                    "$filter": {
-                                        "input": "$user_id",
-                                        "as": "ids",
-                                        "cond": {
-                                            "$ne": [
-                                                "$$ids",
-                                                user_id
-                                            ]
-                                        }
-                                    }
-                                },
-                                0
-                            ]
+/// Returns the filter of the comment with the given id that the given user is allowed to
+/// modify: admins can modify any comment, while other users can only modify their own.
+async fn owned_comment_filter(db: &Database, user_id: Uuid, id: Uuid) -> Result<Document, Error> {
+    admin::ensure_not_banned(db, user_id).await?;
+
+    let mut filter = doc! {
+        "id": id
+    };
+    if !admin::is_admin(db, user_id).await? {
+        filter.insert("user_id", user_id);
+    }
+
+    Ok(filter)
+}
+
+/// Replaces the content of the comment with the given id, if it belongs to the given user.
+pub async fn update_comment(
+    db: &Database,
+    user_id: Uuid,
+    id: Uuid,
+    content: String,
+) -> Result<(), Error> {
+    let filter = owned_comment_filter(db, user_id, id).await?;
+
+    let matched = retry_with_backoff(
+        || {
+            let filter = filter.clone();
+            let content = content.clone();
+
+            async move {
+                db.collection::<Document>("comments")
+                    .update_one(
+                        filter,
+                        doc! {
+                            "$set": {
+                                "content": content
+                            }
                         },
-                        "score": 1
-                    }
-                },
-                // Join with users to get full data
-                doc! {
-                    "$lookup": {
-                        "from": "users",
-                        "localField": "_id",
-                        "foreignField": "id",
-                        "pipeline": vec![
+                        None,
+                    )
+                    .await
+                    .map(|result| result.matched_count)
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await?;
+
+    if matched == 0 {
+        return Err(debug_message!("Could not find comment {} of the user.", id).into());
+    }
+
+    Ok(())
+}
+
+/// Deletes the comment with the given id, if it belongs to the given user, along with all the
+/// replies in its thread.
+pub async fn delete_comment(db: &Database, user_id: Uuid, id: Uuid) -> Result<(), Error> {
+    let filter = owned_comment_filter(db, user_id, id).await?;
+
+    retry_with_backoff(
+        || {
+            let filter = filter.clone();
+
+            async move {
+                let comments = db.collection::<Document>("comments");
+
+                let thread = match comments
+                    .aggregate(
+                        vec![
                             doc! {
-                                "$match": {
-                                    "$expr": {
-                                        "$eq": [ { "$type": "$expiration_date" }, "missing" ]
-                                    }
+                                "$match": filter
+                            },
+                            doc! {
+                                "$graphLookup": {
+                                    "from": "comments",
+                                    "startWith": "$id",
+                                    "connectFromField": "id",
+                                    "connectToField": "reply_to",
+                                    "as": "replies"
                                 }
-                            }
+                            },
+                            doc! {
+                                "$project": {
+                                    "_id": 0,
+                                    "ids": { "$concatArrays": [["$id"], "$replies.id"] }
+                                }
+                            },
                         ],
-                        "as": "user"
+                        None,
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => resolve_cursor::<Document>(cursor).await,
+                    Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+                };
+
+                let ids = match thread.first().map(|document| document.get_array("ids")) {
+                    Some(Ok(ids)) => ids.clone(),
+                    _ => {
+                        return Err(
+                            debug_message!("Could not find comment {} of the user.", id).into()
+                        )
                     }
-                },
-                // Unwind user data
-                doc! {
-                    "$unwind": "$user"
-                },
-                // Join with posts
-                doc! {
-                    "$lookup": {
-                        "from": "posts",
-                        "localField": "_id",
-                        "foreignField": "user_id",
-                        "as": "post",
+                };
+
+                comments
+                    .delete_many(
+                        doc! {
+                            "id": { "$in": ids }
+                        },
+                        None,
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Gets the comments written by other users on the posts of the given user since the given
+/// time. Returns the ids of the posts and the text of the notifications.
+pub async fn get_new_comments(
+    db: &Database,
+    user_id: Uuid,
+    since: DateTime,
+) -> Result<Vec<(Uuid, String)>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("comments")
+                .aggregate(
+                    vec![
+                        doc! {
+                            "$match": {
+                                "created": { "$gt": since },
+                                "user_id": { "$ne": user_id }
+                            }
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "posts",
+                                "localField": "post_id",
+                                "foreignField": "id",
+                                "as": "post"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$post"
+                        },
+                        doc! {
+                            "$match": {
+                                "post.user_id": user_id
+                            }
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "users",
+                                "localField": "user_id",
+                                "foreignField": "id",
+                                "as": "user"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$user"
+                        },
+                    ],
+                    AggregateOptions::builder().allow_disk_use(true).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Document>(cursor)
+                    .await
+                    .iter()
+                    .filter_map(|document| {
+                        let post_id = match document.get_document("post").ok()?.get("id") {
+                            Some(Bson::Binary(bin)) => bin
+                                .to_uuid_with_representation(UuidRepresentation::Standard)
+                                .ok()?,
+                            _ => return None,
+                        };
+                        let username = document
+                            .get_document("user")
+                            .ok()?
+                            .get_str("username")
+                            .ok()?;
+
+                        Some((post_id, format!("{} commented on your post.", username)))
+                    })
+                    .collect()),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the stages that join the posts, stored in the `post` field, with the details shown
+/// to the user with the given id: their rating, the average rating given by the other users,
+/// and whether they follow the author and like the post.
+fn post_details_stages(user_id: Uuid) -> Vec<Document> {
+    vec![
+        // Join with ratings
+        doc! {
+            "$lookup": {
+                "from": "ratings",
+                "localField": "post.id",
+                "foreignField": "post_id",
+                "pipeline": vec![
+                    doc! {
+                        "$match": {
+                            "$expr": {
+                                "$eq": ["$user_id", user_id]
+                            }
+                        }
+                    },
+                    // Older ratings were stored as integers
+                    doc! {
+                        "$set": {
+                            "rating": {
+                                "$toDouble": "$rating"
+                            }
+                        }
                     }
-                },
-                // Unwind the posts
-                doc! {
-                    "$unwind": "$post"
-                },
-                // Add a field of random value
-                doc! {
-                    "$addFields": {
-                        "randomValue": { "$rand": {} }
+                ],
+                "as": "rating"
+            }
+        },
+        // Unwind the rating
+        doc! {
+            "$unwind": {
+                "path": "$rating",
+                "preserveNullAndEmptyArrays": true
+            }
+        },
+        // Join with the average rating given by the other users
+        doc! {
+            "$lookup": {
+                "from": "ratings",
+                "localField": "post.id",
+                "foreignField": "post_id",
+                "pipeline": vec![
+                    doc! {
+                        "$match": {
+                            "$expr": {
+                                "$ne": ["$user_id", user_id]
+                            }
+                        }
+                    },
+                    doc! {
+                        "$group": {
+                            "_id": Bson::Null,
+                            "average": {
+                                "$avg": {
+                                    "$toDouble": "$rating"
+                                }
+                            },
+                            "count": {
+                                "$sum": 1
+                            }
+                        }
                     }
-                },
-                // Remove those with score less than the random value
-                doc! {
-                    "$match": {
-                        "$expr": {
-                            "$lte": ["$randomValue", "$score"]
+                ],
+                "as": "average_rating"
+            }
+        },
+        doc! {
+            "$unwind": {
+                "path": "$average_rating",
+                "preserveNullAndEmptyArrays": true
+            }
+        },
+        // Join with the follows of the user
+        doc! {
+            "$lookup": {
+                "from": "follows",
+                "localField": "post.user_id",
+                "foreignField": "followed_id",
+                "pipeline": vec![
+                    doc! {
+                        "$match": {
+                            "$expr": {
+                                "$eq": ["$follower_id", user_id]
+                            }
                         }
                     }
-                },
-                // Sample 100 of those remaining
-                doc! {
-                    "$sample": {
-                        "size": 100
+                ],
+                "as": "follow"
+            }
+        },
+        // Join with the likes of the user
+        doc! {
+            "$lookup": {
+                "from": "likes",
+                "localField": "post.id",
+                "foreignField": "post_id",
+                "pipeline": vec![
+                    doc! {
+                        "$match": {
+                            "$expr": {
+                                "$eq": ["$user_id", user_id]
+                            }
+                        }
                     }
-                },
-                // Join with ratings
-                doc! {
-                    "$lookup": {
-                        "from": "ratings",
-                        "localField": "post.id",
-                        "foreignField": "post_id",
-                        "pipeline": vec![
-                            doc! {
-                                "$match": {
-                                    "$expr": {
-                                        "$eq": ["$user_id", user_id]
+                ],
+                "as": "like"
+            }
+        },
+    ]
+}
+
+/// Generates recommendations for the user with the given id.
+pub async fn get_recommendations(db: &Database, user_id: Uuid) -> Result<Vec<Post>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("similarities")
+                .aggregate(
+                    vec![
+                        // Get only the similarities with the authenticated user.
+                        doc! {
+                            "$match": {
+                                "user_id": user_id
+                            }
+                        },
+                        // Leave only the id of the other user.
+                        doc! {
+                            "$project": {
+                                "_id": {
+                                    "$arrayElemAt": [
+                                        {
+                                            "$filter": {
+                                                "input": "$user_id",
+                                                "as": "ids",
+                                                "cond": {
+                                                    "$ne": [
+                                                        "$$ids",
+                                                        user_id
+                                                    ]
+                                                }
+                                            }
+                                        },
+                                        0
+                                    ]
+                                },
+                                "score": 1
+                            }
+                        },
+                        // Join with users to get full data
+                        doc! {
+                            "$lookup": {
+                                "from": "users",
+                                "localField": "_id",
+                                "foreignField": "id",
+                                "pipeline": vec![
+                                    doc! {
+                                        "$match": {
+                                            "$expr": {
+                                                "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                            }
+                                        }
                                     }
+                                ],
+                                "as": "user"
+                            }
+                        },
+                        // Unwind user data
+                        doc! {
+                            "$unwind": "$user"
+                        },
+                        // Join with posts
+                        doc! {
+                            "$lookup": {
+                                "from": "posts",
+                                "localField": "_id",
+                                "foreignField": "user_id",
+                                "as": "post",
+                            }
+                        },
+                        // Unwind the posts
+                        doc! {
+                            "$unwind": "$post"
+                        },
+                        // Add a field of random value
+                        doc! {
+                            "$addFields": {
+                                "randomValue": { "$rand": {} }
+                            }
+                        },
+                        // Remove those with score less than the random value
+                        doc! {
+                            "$match": {
+                                "$expr": {
+                                    "$lte": ["$randomValue", "$score"]
                                 }
                             }
-                        ],
-                        "as": "rating"
-                    }
-                },
-                // Unwind the rating
-                doc! {
-                    "$unwind": {
-                        "path": "$rating",
-                        "preserveNullAndEmptyArrays": true
-                    }
-                },
-            ]),
-            AggregateOptions::builder().allow_disk_use(true).build(),
-        )
-        .await
-    {
-        Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+                        },
+                        // Sample 100 of those remaining
+                        doc! {
+                            "$sample": {
+                                "size": 100
+                            }
+                        },
+                    ]
+                    .into_iter()
+                    .chain(post_details_stages(user_id)),
+                    AggregateOptions::builder().allow_disk_use(true).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Gets the posts that contain all the given tags.
@@ -187,144 +552,384 @@ pub async fn get_filtered(
     db: &Database,
     user_id: Uuid,
     tags: Vec<String>,
+    sort_order: SortOrder,
 ) -> Result<Vec<Post>, Error> {
-    match db
-        .collection::<Document>("posts")
-        .aggregate(
-            vec![
-                doc! {
-                    "$match": {
-                        "tags": { "$all": tags }
-                    }
-                },
-                doc! {
-                    "$project": {
-                        "post": "$$ROOT"
-                    }
-                },
-                doc! {
-                    "$lookup": {
-                        "from": "users",
-                        "localField": "post.user_id",
-                        "foreignField": "id",
-                        "pipeline": vec![
+    retry_with_backoff(
+        || {
+            let tags = tags.clone();
+
+            async move {
+                let sort_stage = match sort_order {
+                    SortOrder::Date => doc! {
+                        "$sort": {
+                            "post._id": -1
+                        }
+                    },
+                    SortOrder::Rating => doc! {
+                        "$sort": {
+                            "average_rating.average": -1,
+                            "average_rating.count": -1
+                        }
+                    },
+                    // The fewer tags a post has besides the filters, the more relevant it is
+                    SortOrder::Relevance => doc! {
+                        "$sort": {
+                            "post.extra_tags": 1,
+                            "average_rating.average": -1
+                        }
+                    },
+                };
+
+                match db
+                    .collection::<Document>("posts")
+                    .aggregate(
+                        vec![
                             doc! {
                                 "$match": {
-                                    "$expr": {
-                                        "$eq": [ { "$type": "$expiration_date" }, "missing" ]
-                                    }
+                                    "tags": { "$all": tags.clone() }
                                 }
-                            }
-                        ],
-                        "as": "user"
-                    }
-                },
-                doc! {
-                    "$unwind": "$user"
-                },
-                doc! {
-                    "$lookup": {
-                        "from": "ratings",
-                        "localField": "post.id",
-                        "foreignField": "post_id",
-                        "pipeline": vec![
+                            },
                             doc! {
-                                "$match": {
-                                    "$expr": {
-                                        "$eq": ["$user_id", user_id]
+                                "$addFields": {
+                                    "extra_tags": {
+                                        "$subtract": [{ "$size": "$tags" }, tags.len() as i32]
                                     }
                                 }
-                            }
-                        ],
-                        "as": "rating"
-                    }
-                },
-                doc! {
-                    "$unwind": {
-                        "path": "$rating",
-                        "preserveNullAndEmptyArrays": true
-                    }
-                },
-                doc! {
-                    "$limit": 100
-                },
-            ],
-            AggregateOptions::builder().allow_disk_use(true).build(),
-        )
-        .await
-    {
-        Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+                            },
+                            doc! {
+                                "$project": {
+                                    "post": "$$ROOT"
+                                }
+                            },
+                            doc! {
+                                "$lookup": {
+                                    "from": "users",
+                                    "localField": "post.user_id",
+                                    "foreignField": "id",
+                                    "pipeline": vec![
+                                        doc! {
+                                            "$match": {
+                                                "$expr": {
+                                                    "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                                }
+                                            }
+                                        }
+                                    ],
+                                    "as": "user"
+                                }
+                            },
+                            doc! {
+                                "$unwind": "$user"
+                            },
+                        ]
+                        .into_iter()
+                        .chain(post_details_stages(user_id))
+                        .chain([
+                            sort_stage,
+                            doc! {
+                                "$limit": 100
+                            },
+                        ]),
+                        AggregateOptions::builder().allow_disk_use(true).build(),
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Gets the posts of the user with the given id.
 pub async fn get_user_posts(db: &Database, user_id: Uuid) -> Result<Vec<Post>, Error> {
-    match db
-        .collection::<Document>("posts")
-        .aggregate(
-            vec![
-                doc! {
-                    "$match": {
-                        "user_id": user_id
-                    }
-                },
-                doc! {
-                    "$project": {
-                        "post": "$$ROOT"
-                    }
-                },
-                doc! {
-                    "$lookup": {
-                        "from": "users",
-                        "localField": "post.user_id",
-                        "foreignField": "id",
-                        "pipeline": vec![
-                            doc! {
-                                "$match": {
-                                    "$expr": {
-                                        "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("posts")
+                .aggregate(
+                    vec![
+                        doc! {
+                            "$match": {
+                                "user_id": user_id
+                            }
+                        },
+                        doc! {
+                            "$project": {
+                                "post": "$$ROOT"
+                            }
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "users",
+                                "localField": "post.user_id",
+                                "foreignField": "id",
+                                "pipeline": vec![
+                                    doc! {
+                                        "$match": {
+                                            "$expr": {
+                                                "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                            }
+                                        }
                                     }
-                                }
+                                ],
+                                "as": "user"
                             }
-                        ],
-                        "as": "user"
-                    }
-                },
-                doc! {
-                    "$unwind": "$user"
-                },
-                doc! {
-                    "$lookup": {
-                        "from": "ratings",
-                        "localField": "post.id",
-                        "foreignField": "post_id",
-                        "pipeline": vec![
-                            doc! {
-                                "$match": {
-                                    "$expr": {
-                                        "$eq": ["$user_id", user_id]
+                        },
+                        doc! {
+                            "$unwind": "$user"
+                        },
+                    ]
+                    .into_iter()
+                    .chain(post_details_stages(user_id)),
+                    AggregateOptions::builder().allow_disk_use(true).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Gets the posts that received the most ratings in the last day relative to their age.
+pub async fn get_trending(db: &Database, user_id: Uuid) -> Result<Vec<Post>, Error> {
+    retry_with_backoff(
+        || async move {
+            let since = DateTime::from_millis(DateTime::now().timestamp_millis() - 24 * 60 * 60 * 1000);
+
+            match db
+                .collection::<Document>("posts")
+                .aggregate(
+                    vec![
+                        doc! {
+                            "$project": {
+                                "post": "$$ROOT"
+                            }
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "ratings",
+                                "localField": "post.id",
+                                "foreignField": "post_id",
+                                "pipeline": vec![
+                                    doc! {
+                                        "$match": {
+                                            "$expr": {
+                                                "$gte": ["$date", since]
+                                            }
+                                        }
                                     }
+                                ],
+                                "as": "recent_ratings"
+                            }
+                        },
+                        doc! {
+                            "$set": {
+                                "velocity": {
+                                    "$divide": [
+                                        { "$size": "$recent_ratings" },
+                                        {
+                                            "$max": [
+                                                1,
+                                                {
+                                                    "$dateDiff": {
+                                                        "startDate": { "$toDate": "$post._id" },
+                                                        "endDate": "$$NOW",
+                                                        "unit": "hour"
+                                                    }
+                                                }
+                                            ]
+                                        }
+                                    ]
                                 }
                             }
-                        ],
-                        "as": "rating"
-                    }
-                },
-                doc! {
-                    "$unwind": {
-                        "path": "$rating",
-                        "preserveNullAndEmptyArrays": true
-                    }
-                },
-            ],
-            AggregateOptions::builder().allow_disk_use(true).build(),
-        )
-        .await
-    {
-        Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+                        },
+                        doc! {
+                            "$match": {
+                                "velocity": { "$gt": 0 }
+                            }
+                        },
+                        doc! {
+                            "$sort": {
+                                "velocity": -1
+                            }
+                        },
+                        doc! {
+                            "$limit": 50
+                        },
+                        doc! {
+                            "$unset": "recent_ratings"
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "users",
+                                "localField": "post.user_id",
+                                "foreignField": "id",
+                                "pipeline": vec![
+                                    doc! {
+                                        "$match": {
+                                            "$expr": {
+                                                "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                            }
+                                        }
+                                    }
+                                ],
+                                "as": "user"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$user"
+                        },
+                    ]
+                    .into_iter()
+                    .chain(post_details_stages(user_id)),
+                    AggregateOptions::builder().allow_disk_use(true).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Gets the posts liked by the user with the given id.
+pub async fn get_liked_posts(db: &Database, user_id: Uuid) -> Result<Vec<Post>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("likes")
+                .aggregate(
+                    vec![
+                        doc! {
+                            "$match": {
+                                "user_id": user_id
+                            }
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "posts",
+                                "localField": "post_id",
+                                "foreignField": "id",
+                                "as": "post"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$post"
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "users",
+                                "localField": "post.user_id",
+                                "foreignField": "id",
+                                "pipeline": vec![
+                                    doc! {
+                                        "$match": {
+                                            "$expr": {
+                                                "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                            }
+                                        }
+                                    }
+                                ],
+                                "as": "user"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$user"
+                        },
+                    ]
+                    .into_iter()
+                    .chain(post_details_stages(user_id)),
+                    AggregateOptions::builder().allow_disk_use(true).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Gets the posts of the users followed by the user with the given id, starting with the
+/// newest ones.
+pub async fn get_followed_posts(db: &Database, user_id: Uuid) -> Result<Vec<Post>, Error> {
+    retry_with_backoff(
+        || async move {
+            match db
+                .collection::<Document>("follows")
+                .aggregate(
+                    vec![
+                        doc! {
+                            "$match": {
+                                "follower_id": user_id
+                            }
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "posts",
+                                "localField": "followed_id",
+                                "foreignField": "user_id",
+                                "as": "post"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$post"
+                        },
+                        doc! {
+                            "$sort": {
+                                "post._id": -1
+                            }
+                        },
+                        doc! {
+                            "$limit": 100
+                        },
+                        doc! {
+                            "$lookup": {
+                                "from": "users",
+                                "localField": "post.user_id",
+                                "foreignField": "id",
+                                "pipeline": vec![
+                                    doc! {
+                                        "$match": {
+                                            "$expr": {
+                                                "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                            }
+                                        }
+                                    }
+                                ],
+                                "as": "user"
+                            }
+                        },
+                        doc! {
+                            "$unwind": "$user"
+                        },
+                    ]
+                    .into_iter()
+                    .chain(post_details_stages(user_id)),
+                    AggregateOptions::builder().allow_disk_use(true).build(),
+                )
+                .await
+            {
+                Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Gets a list of "count" posts sampled randomly that are not in the "denied" list.
@@ -334,165 +939,494 @@ pub async fn get_random_posts(
     user_id: Uuid,
     denied: Vec<Uuid>,
 ) -> Result<Vec<Post>, Error> {
-    match db
-        .collection::<Document>("posts")
-        .aggregate(
-            vec![
-                doc! {
-                    "$match": {
-                        "id": {
-                            "$nin": denied
-                        }
-                    }
-                },
-                doc! {
-                    "$sample": {
-                        "size": count as i32
-                    }
-                },
-                doc! {
-                    "$lookup": {
-                        "from": "users",
-                        "localField": "user_id",
-                        "foreignField": "id",
-                        "pipeline": vec![
+    retry_with_backoff(
+        || {
+            let denied = denied.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("posts")
+                    .aggregate(
+                        vec![
                             doc! {
                                 "$match": {
-                                    "$expr": {
-                                        "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                    "id": {
+                                        "$nin": denied
                                     }
                                 }
-                            }
-                        ],
-                        "as": "user"
-                    }
-                },
-                doc! {
-                    "$unwind": "$user"
-                },
-                doc! {
-                    "$lookup": {
-                        "from": "posts",
-                        "localField": "id",
-                        "foreignField": "id",
-                        "as": "post"
-                    }
-                },
-                doc! {
-                    "$unwind": "$post"
-                },
-                doc! {
-                    "$lookup": {
-                        "from": "ratings",
-                        "localField": "id",
-                        "foreignField": "post_id",
-                        "pipeline": vec![
+                            },
                             doc! {
-                                "$match": {
-                                    "$expr": {
-                                        "$eq": [ "$user_id", user_id ]
-                                    }
+                                "$sample": {
+                                    "size": count as i32
                                 }
-                            }
-                        ],
-                        "as": "rating"
-                    }
-                },
-                doc! {
-                    "$unwind": {
-                        "path": "$rating",
-                        "preserveNullAndEmptyArrays": true
-                    }
-                },
-            ],
-            AggregateOptions::builder().allow_disk_use(true).build(),
-        )
-        .await
-    {
-        Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+                            },
+                            doc! {
+                                "$lookup": {
+                                    "from": "users",
+                                    "localField": "user_id",
+                                    "foreignField": "id",
+                                    "pipeline": vec![
+                                        doc! {
+                                            "$match": {
+                                                "$expr": {
+                                                    "$eq": [ { "$type": "$expiration_date" }, "missing" ]
+                                                }
+                                            }
+                                        }
+                                    ],
+                                    "as": "user"
+                                }
+                            },
+                            doc! {
+                                "$unwind": "$user"
+                            },
+                            doc! {
+                                "$lookup": {
+                                    "from": "posts",
+                                    "localField": "id",
+                                    "foreignField": "id",
+                                    "as": "post"
+                                }
+                            },
+                            doc! {
+                                "$unwind": "$post"
+                            },
+                        ]
+                        .into_iter()
+                        .chain(post_details_stages(user_id)),
+                        AggregateOptions::builder().allow_disk_use(true).build(),
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => Ok(resolve_cursor::<Post>(cursor).await),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Updates the rating that the user has given to the post.
 /// If there was no previous rating, it will be inserted.
 pub async fn update_rating(
     db: &Database,
+    rate_limiter: &Mutex<RateLimiter>,
     post_id: Uuid,
     user_id: Uuid,
-    rating: i32,
+    rating: f64,
 ) -> Result<(), Error> {
-    db.collection::<Document>("ratings")
-        .update_one(
-            doc! {
-                "post_id": post_id,
-                "user_id": user_id,
-            },
-            doc! {
-                "$set": {
-                    "rating": rating
-                }
-            },
-            UpdateOptions::builder().upsert(true).build(),
-        )
-        .await
-        .map(|_| ())
-        .map_err(|err| debug_message!("{}", err).into())
+    rate_limiter::check(rate_limiter, "update_rating")?;
+    admin::ensure_not_banned(db, user_id).await?;
+
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("ratings")
+                .update_one(
+                    doc! {
+                        "post_id": post_id,
+                        "user_id": user_id,
+                    },
+                    doc! {
+                        "$set": {
+                            "rating": rating,
+                            "date": DateTime::now()
+                        }
+                    },
+                    UpdateOptions::builder().upsert(true).build(),
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Deletes the rating that the user has given the post.
 pub async fn delete_rating(db: &Database, post_id: Uuid, user_id: Uuid) -> Result<(), Error> {
-    db.collection::<Document>("ratings")
-        .delete_one(
-            doc! {
-                "post_id": post_id,
-                "user_id": user_id
-            },
-            None,
-        )
-        .await
-        .map(|_| ())
-        .map_err(|err| debug_message!("{}", err).into())
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("ratings")
+                .delete_one(
+                    doc! {
+                        "post_id": post_id,
+                        "user_id": user_id
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Marks the post as liked by the user.
+pub async fn like_post(
+    db: &Database,
+    rate_limiter: &Mutex<RateLimiter>,
+    post_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), Error> {
+    rate_limiter::check(rate_limiter, "like_post")?;
+    admin::ensure_not_banned(db, user_id).await?;
+
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("likes")
+                .update_one(
+                    doc! {
+                        "post_id": post_id,
+                        "user_id": user_id
+                    },
+                    doc! {
+                        "$set": {
+                            "post_id": post_id,
+                            "user_id": user_id
+                        }
+                    },
+                    UpdateOptions::builder().upsert(true).build(),
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Removes the like the user has given the post.
+pub async fn unlike_post(db: &Database, post_id: Uuid, user_id: Uuid) -> Result<(), Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("likes")
+                .delete_one(
+                    doc! {
+                        "post_id": post_id,
+                        "user_id": user_id
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Marks the user with the given id as followed by the follower.
+pub async fn follow_user(
+    db: &Database,
+    rate_limiter: &Mutex<RateLimiter>,
+    follower_id: Uuid,
+    followed_id: Uuid,
+) -> Result<(), Error> {
+    rate_limiter::check(rate_limiter, "follow_user")?;
+    admin::ensure_not_banned(db, follower_id).await?;
+
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("follows")
+                .update_one(
+                    doc! {
+                        "follower_id": follower_id,
+                        "followed_id": followed_id
+                    },
+                    doc! {
+                        "$set": {
+                            "follower_id": follower_id,
+                            "followed_id": followed_id
+                        }
+                    },
+                    UpdateOptions::builder().upsert(true).build(),
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Removes the follow the follower has given the user with the given id.
+pub async fn unfollow_user(
+    db: &Database,
+    follower_id: Uuid,
+    followed_id: Uuid,
+) -> Result<(), Error> {
+    retry_with_backoff(
+        || async move {
+            db.collection::<Document>("follows")
+                .delete_one(
+                    doc! {
+                        "follower_id": follower_id,
+                        "followed_id": followed_id
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the users whose tags start with the given prefix.
+pub async fn get_users_by_prefix(db: &Database, prefix: String) -> Result<Vec<User>, Error> {
+    retry_with_backoff(
+        || {
+            let prefix = prefix.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .find(
+                        doc! {
+                            "user_tag": {
+                                "$regex": format!("^{}", regex::escape(&*prefix))
+                            },
+                            "expiration_date": { "$exists": false }
+                        },
+                        FindOptions::builder().limit(5).build(),
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => Ok(resolve_cursor::<User>(cursor).await),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns a page of the users whose tags start with the given prefix, ignoring case.
+pub async fn search_users_by_prefix(
+    db: &Database,
+    prefix: String,
+    page: usize,
+) -> Result<Vec<User>, Error> {
+    retry_with_backoff(
+        || {
+            let prefix = prefix.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .find(
+                        doc! {
+                            "user_tag": {
+                                "$regex": format!("^{}", regex::escape(&*prefix)),
+                                "$options": "i"
+                            },
+                            "expiration_date": { "$exists": false }
+                        },
+                        FindOptions::builder()
+                            .sort(doc! { "user_tag": 1 })
+                            .skip((page * USER_SEARCH_PAGE_SIZE) as u64)
+                            .limit(USER_SEARCH_PAGE_SIZE as i64)
+                            .build(),
+                    )
+                    .await
+                {
+                    Ok(ref mut cursor) => Ok(resolve_cursor::<User>(cursor).await),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Returns the user that has the given tag.
 pub async fn get_user_by_tag(db: &Database, user_tag: String) -> Result<User, Error> {
-    match db
-        .collection::<Document>("users")
-        .find_one(
+    retry_with_backoff(
+        || {
+            let user_tag = user_tag.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .find_one(
+                        doc! {
+                            "user_tag": user_tag.clone(),
+                            "expiration_date": { "$exists": false }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(Some(ref user)) => Ok(Deserialize::deserialize(user)),
+                    Ok(None) => Err(Error::AuthError(AuthError::UserTagDoesNotExist(user_tag))),
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Returns the author of the post with the given id.
+pub async fn get_post_author(db: &Database, post_id: Uuid) -> Result<User, Error> {
+    retry_with_backoff(
+        || async move {
+            let post = match db
+                .collection::<Document>("posts")
+                .find_one(doc! { "id": post_id }, None)
+                .await
+            {
+                Ok(Some(post)) => post,
+                Ok(None) => return Err(debug_message!("Post {} does not exist.", post_id).into()),
+                Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+            };
+
+            let user_id = post
+                .get("user_id")
+                .and_then(|user_id| match user_id {
+                    Bson::Binary(bin) => bin
+                        .to_uuid_with_representation(UuidRepresentation::Standard)
+                        .ok(),
+                    _ => None,
+                })
+                .ok_or(debug_message!("Post {} has no author.", post_id).into())?;
+
+            match db
+                .collection::<Document>("users")
+                .find_one(doc! { "id": user_id }, None)
+                .await
+            {
+                Ok(Some(ref user)) => Ok(Deserialize::deserialize(user)),
+                Ok(None) => Err(debug_message!("User {} does not exist.", user_id).into()),
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Updates the description and tags of the given post, if it belongs to the given user.
+pub async fn update_post(
+    db: &Database,
+    user_id: Uuid,
+    post_id: Uuid,
+    description: String,
+    tags: Vec<String>,
+) -> Result<(), Error> {
+    let matched = retry_with_backoff(
+        || {
+            let description = description.clone();
+            let tags = tags.clone();
+
+            async move {
+                db.collection::<Document>("posts")
+                    .update_one(
+                        doc! {
+                            "id": post_id,
+                            "user_id": user_id
+                        },
+                        doc! {
+                            "$set": {
+                                "description": description,
+                                "tags": tags
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                    .map(|result| result.matched_count)
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await?;
+
+    if matched == 0 {
+        return Err(debug_message!("Could not find post {} of the user.", post_id).into());
+    }
+
+    Ok(())
+}
+
+/// Stores a report of the given user regarding the given post, so that it shows up in the queue
+/// of the admins. The reports are anonymous, so the user is not stored.
+pub async fn create_report(
+    db: &Database,
+    rate_limiter: &Mutex<RateLimiter>,
+    user_id: Uuid,
+    post_id: Uuid,
+    description: String,
+) -> Result<(), Error> {
+    rate_limiter::check(rate_limiter, "create_report")?;
+    admin::ensure_not_banned(db, user_id).await?;
+
+    db.collection::<Document>("reports")
+        .insert_one(
             doc! {
-                "user_tag": user_tag.clone(),
-                "expiration_date": { "$exists": false }
+                "id": Uuid::new(),
+                "post_id": post_id,
+                "description": description,
+                "created_at": DateTime::now()
             },
             None,
         )
         .await
-    {
-        Ok(Some(ref user)) => Ok(Deserialize::deserialize(user)),
-        Ok(None) => Err(Error::AuthError(AuthError::UserTagDoesNotExist(user_tag))),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+        .map(|_| ())
+        .map_err(|err| driver_error(&err, debug_message!("{}", err)))
 }
 
 /// Deletes the given post.
 pub async fn delete_post(id: Uuid, globals: &Globals) -> Result<(), Error> {
+    retry_with_backoff(
+        || async move {
+            let db = globals
+                .get_db()
+                .ok_or(debug_message!("Could not access database.").into())?;
+
+            let posts = db.collection::<Document>("posts");
+
+            match posts
+                .delete_one(
+                    doc! {
+                        "id": id
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(result) if result.deleted_count > 0 => Ok(()),
+                Ok(_) => {
+                    Err(debug_message!("Could not find post with id {} to delete.", id).into())
+                }
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+            }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await?;
+
     let db = globals
         .get_db()
         .ok_or(debug_message!("Could not access database.").into())?;
+    let user_id = globals
+        .get_user()
+        .ok_or(debug_message!("No user logged in.").into())?
+        .get_id();
 
-    let posts = db.collection::<Document>("posts");
-
-    match posts
-        .delete_one(
-            doc! {
-                "id": id
-            },
-            None,
-        )
-        .await
-    {
-        Ok(result) if result.deleted_count > 0 => Ok(()),
-        Ok(_) => Err(debug_message!("Could not find post with id {} to delete.", id).into()),
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+    audit_log(&db, user_id, "delete_post", Some(id)).await
 }
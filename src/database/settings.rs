@@ -1,136 +1,308 @@
+use crate::database::base::{audit_log, driver_error, retry_with_backoff, CONNECTION_SETTINGS};
 use crate::debug_message;
 use crate::scene::Globals;
-use crate::utils::errors::{AuthError, DebugError, Error};
+use crate::utils::errors::{AuthError, Error};
 use mongodb::bson::{doc, Bson, DateTime, Document, Uuid};
+use mongodb::options::UpdateOptions;
 use mongodb::Database;
 
 /// Updates the data of a user, given their [id](Uuid) and what needs to be updated.
 pub async fn update_user(db: &Database, user_id: Uuid, update: Document) -> Result<(), Error> {
-    match db
-        .collection::<Document>("users")
-        .update_one(
-            doc! {
-                "id": user_id
-            },
-            doc! {
-                "$set": update
-            },
-            None,
-        )
-        .await
-    {
-        Ok(result) => {
-            if result.modified_count > 0 {
-                Ok(())
-            } else {
-                Err(debug_message!("Database couldn't find a user with id {}.", user_id).into())
+    retry_with_backoff(
+        || {
+            let update = update.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .update_one(
+                        doc! {
+                            "id": user_id
+                        },
+                        doc! {
+                            "$set": update
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(result) => {
+                        if result.modified_count > 0 {
+                            Ok(())
+                        } else {
+                            Err(debug_message!(
+                                "Database couldn't find a user with id {}.",
+                                user_id
+                            )
+                            .into())
+                        }
+                    }
+                    Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                }
             }
-        }
-        Err(err) => Err(debug_message!("{}", err).into()),
-    }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await?;
+
+    audit_log(db, user_id, "update_user", Some(user_id)).await
 }
 
 /// Checks if there already exists a user with the requested tag.
 pub async fn find_user_by_tag(globals: &Globals, user_tag: String) -> Result<(), Error> {
-    let user_id = globals.get_user().unwrap().get_id();
-
-    let db = globals.get_db().unwrap();
-    let mut session = globals.start_session().await.unwrap()?;
-    match session.start_transaction(None).await {
-        Ok(_) => {}
-        Err(err) => return Err(debug_message!("{}", err).into()),
-    }
-
-    match db
-        .collection::<Document>("users")
-        .find_one_with_session(
-            doc! {
-                "user_tag": user_tag.clone()
-            },
-            None,
-            &mut session,
-        )
-        .await
-    {
-        Ok(Some(_)) => {
-            return match session.abort_transaction().await {
-                Ok(_) => Err(Error::AuthError(AuthError::UserTagAlreadyExists)),
-                Err(err) => Err(debug_message!("{}", err).into()),
-            }
-        }
-        Ok(None) => {}
-        Err(err) => {
-            return match session.abort_transaction().await {
-                Ok(_) => Err(Error::DebugError(DebugError::new(err.to_string()))),
-                Err(err) => Err(debug_message!("{}", err).into()),
-            }
-        }
-    }
-
-    match db
-        .collection::<Document>("users")
-        .update_one_with_session(
-            doc! {
-                "id": user_id
-            },
-            doc! {
-                "$set": {
-                    "user_tag": user_tag
+    retry_with_backoff(
+        || {
+            let user_tag = user_tag.clone();
+
+            async move {
+                let user_id = globals.get_user().unwrap().get_id();
+
+                let db = globals.get_db().unwrap();
+                let mut session = globals.start_session().await.unwrap()?;
+                match session.start_transaction(None).await {
+                    Ok(_) => {}
+                    Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
                 }
-            },
-            None,
-            &mut session,
-        )
-        .await
-    {
-        Ok(result) => {
-            if result.modified_count > 0 {
-                match session.commit_transaction().await {
-                    Ok(_) => Ok(()),
-                    Err(err) => Err(debug_message!("{}", err).into()),
+
+                match db
+                    .collection::<Document>("users")
+                    .find_one_with_session(
+                        doc! {
+                            "user_tag": user_tag.clone()
+                        },
+                        None,
+                        &mut session,
+                    )
+                    .await
+                {
+                    Ok(Some(_)) => {
+                        return match session.abort_transaction().await {
+                            Ok(_) => Err(Error::AuthError(AuthError::UserTagAlreadyExists)),
+                            Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        return match session.abort_transaction().await {
+                            Ok(_) => Err(driver_error(&err, debug_message!("{}", err))),
+                            Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                        }
+                    }
                 }
-            } else {
-                match session.abort_transaction().await {
-                    Ok(_) => Err(debug_message!(
-                        "Database could not find user with id {}!",
-                        user_id
+
+                match db
+                    .collection::<Document>("users")
+                    .update_one_with_session(
+                        doc! {
+                            "id": user_id
+                        },
+                        doc! {
+                            "$set": {
+                                "user_tag": user_tag
+                            }
+                        },
+                        None,
+                        &mut session,
                     )
-                    .into()),
-                    Err(err) => Err(debug_message!("{}", err).into()),
+                    .await
+                {
+                    Ok(result) => {
+                        if result.modified_count > 0 {
+                            match session.commit_transaction().await {
+                                Ok(_) => Ok(()),
+                                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                            }
+                        } else {
+                            match session.abort_transaction().await {
+                                Ok(_) => Err(debug_message!(
+                                    "Database could not find user with id {}!",
+                                    user_id
+                                )
+                                .into()),
+                                Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                            }
+                        }
+                    }
+                    Err(err) => match session.abort_transaction().await {
+                        Ok(_) => Err(driver_error(&err, debug_message!("{}", err))),
+                        Err(err) => Err(driver_error(&err, debug_message!("{}", err))),
+                    },
                 }
             }
-        }
-        Err(err) => match session.abort_transaction().await {
-            Ok(_) => Err(debug_message!("{}", err).into()),
-            Err(err) => Err(debug_message!("{}", err).into()),
         },
-    }
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
 
 /// Sets the currently logged users expiration date to a week from now.
 /// The user will be automatically logged out and won't be able to log in automatically anymore.
 /// The account will be automatically deleted in a month.
 pub async fn delete_account(db: &Database, id: Uuid) -> Result<(), Error> {
-    match db.collection::<Document>("users").update_one(
-        doc!{
-            "id": id
+    retry_with_backoff(
+        || async move {
+            match db.collection::<Document>("users").update_one(
+                doc!{
+                    "id": id
+                },
+                doc! {
+                    "$set": {
+                        "expiration_date": Bson::DateTime(
+                            DateTime::from_millis(DateTime::now().timestamp_millis() + 30 * 24 * 60 * 60 * 1000)
+                        )
+                    }
+                },
+                None
+            ).await {
+                Ok(result) => {
+                    if result.modified_count > 0 {
+                        Ok(())
+                    } else {
+                        Err(debug_message!("Database could not find user with id {}.", id).into())
+                    }
+                }
+                Err(err) => Err(driver_error(&err, debug_message!("{}", err)))
+            }
         },
-        doc! {
-            "$set": {
-                "expiration_date": Bson::DateTime(
-                    DateTime::from_millis(DateTime::now().timestamp_millis() + 30 * 24 * 60 * 60 * 1000)
-                )
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await?;
+
+    audit_log(db, id, "delete_account", Some(id)).await
+}
+
+/// Stores a request of the user with the given [id](Uuid) to change their email to the given
+/// address, which is confirmed by the given code for the next five minutes. Fails if the address
+/// is already used by another account.
+pub async fn request_email_change(
+    db: &Database,
+    user_id: Uuid,
+    email: String,
+    code: String,
+) -> Result<(), Error> {
+    retry_with_backoff(
+        || {
+            let email = email.clone();
+            let code = code.clone();
+
+            async move {
+                match db
+                    .collection::<Document>("users")
+                    .find_one(doc! { "email": email.clone() }, None)
+                    .await
+                {
+                    Ok(Some(_)) => {
+                        return Err(Error::AuthError(AuthError::RegisterUserAlreadyExists))
+                    }
+                    Ok(None) => {}
+                    Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+                }
+
+                db.collection::<Document>("email_changes")
+                    .update_one(
+                        doc! {
+                            "user_id": user_id
+                        },
+                        doc! {
+                            "$set": {
+                                "email": email,
+                                "code": code,
+                                "code_expiration": Bson::DateTime(
+                                    DateTime::from_millis(DateTime::now().timestamp_millis() + 5 * 60 * 1000)
+                                )
+                            }
+                        },
+                        UpdateOptions::builder().upsert(true).build(),
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
             }
         },
-        None
-    ).await {
-        Ok(result) => {
-            if result.modified_count > 0 {
-                Ok(())
-            } else {
-                Err(debug_message!("Database could not find user with id {}.", id).into())
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
+}
+
+/// Sets the email of the user with the given [id](Uuid) to the address of their pending change
+/// request, if the given code matches the request and has not expired, and the address is still
+/// not used by another account. Returns the new email.
+pub async fn verify_email_change(
+    db: &Database,
+    user_id: Uuid,
+    code: String,
+) -> Result<String, Error> {
+    retry_with_backoff(
+        || {
+            let code = code.clone();
+
+            async move {
+                let email = match db
+                    .collection::<Document>("email_changes")
+                    .find_one(
+                        doc! {
+                            "user_id": user_id,
+                            "code": code,
+                            "code_expiration": {
+                                "$gt": Bson::DateTime(DateTime::now())
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(Some(request)) => request
+                        .get_str("email")
+                        .map(String::from)
+                        .map_err(|err| debug_message!("{}", err).into())?,
+                    Ok(None) => return Err(Error::AuthError(AuthError::RegisterBadCode)),
+                    Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+                };
+
+                // The address may have been taken by another account since the request was made.
+                match db
+                    .collection::<Document>("users")
+                    .find_one(
+                        doc! {
+                            "email": email.clone(),
+                            "id": { "$ne": user_id }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    Ok(Some(_)) => {
+                        return Err(Error::AuthError(AuthError::RegisterUserAlreadyExists))
+                    }
+                    Ok(None) => {}
+                    Err(err) => return Err(driver_error(&err, debug_message!("{}", err))),
+                }
+
+                db.collection::<Document>("users")
+                    .update_one(
+                        doc! {
+                            "id": user_id
+                        },
+                        doc! {
+                            "$set": {
+                                "email": email.clone()
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))?;
+
+                // The request is only removed once the email has been changed, so that retrying
+                // after a failed update can still find it.
+                db.collection::<Document>("email_changes")
+                    .delete_one(doc! { "user_id": user_id }, None)
+                    .await
+                    .map(|_| email)
+                    .map_err(|err| driver_error(&err, debug_message!("{}", err)))
             }
-        }
-        Err(err) => Err(debug_message!("{}", err).into())
-    }
+        },
+        CONNECTION_SETTINGS.max_attempts,
+    )
+    .await
 }
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate litcrypt2;
+
+use_litcrypt!();
+
+pub mod canvas;
+pub mod config;
+pub mod database;
+pub mod scene;
+pub mod scenes;
+pub mod utils;
+pub mod widgets;
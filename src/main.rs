@@ -3,33 +3,35 @@
     windows_subsystem = "windows"
 )]
 
-#[macro_use]
-extern crate litcrypt2;
-
-use_litcrypt!();
-
-mod canvas;
-mod config;
-mod database;
-mod scene;
-mod scenes;
-mod utils;
-mod widgets;
+use chartsy::{canvas, config, database, debug_message, scene, scenes, utils, widgets};
 
+use canvas::layer::CanvasMessage;
 use iced::window::icon::from_rgba;
 use image::load_from_memory_with_format;
 use lettre::transport::smtp::response::Response;
-use scene::{Globals, Message};
-use scenes::scenes::SceneManager;
+use scene::{DbStatus, Globals, Message, StartupStep};
+use scenes::data::app_settings::{Preferences, ThemeMode};
+use scenes::data::auth::Role;
+use scenes::data::debug::{LogEntry, ModalType};
+use scenes::data::deep_link::DeepLink;
+use scenes::scenes::{SceneId, SceneManager, Scenes};
+use scenes::services::{command_palette, debug};
 use utils::errors::error::Error;
+use utils::errors::AuthError;
 
-use crate::widgets::WaitPanel;
+use crate::widgets::{DrawableElement, ModalStack, Toasts, WaitPanel};
 use iced::font::{Family, Stretch, Style, Weight};
+use iced::widget::image::Handle;
 use iced::{
-    advanced::Application, executor, window, Command, Element, Font, Renderer, Settings,
-    Subscription,
+    advanced::Application,
+    executor, keyboard,
+    widget::{self, text_input, Column},
+    window, Command, Element, Font, Renderer, Settings, Subscription,
 };
 use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::time::{Duration, Instant};
 
 pub const INCONSOLATA_BYTES: &[u8] = include_bytes!("images/Inconsolata-SemiBold.ttf");
 pub const INCONSOLATA: Font = Font {
@@ -41,14 +43,53 @@ pub const INCONSOLATA: Font = Font {
 
 pub const APP_ICON: &[u8] = include_bytes!("images/icon.png");
 
+/// The time between two frames of the splash screen animation.
+const SPLASH_FRAME_DURATION: Duration = Duration::from_millis(300);
+
+/// The time between two checks of the database connection.
+const DB_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The time between two checks for new notifications.
+const NOTIFICATIONS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 pub fn main() -> iced::Result {
+    let preferences = match scenes::services::app_settings::load_preferences() {
+        Ok(preferences) => preferences,
+        Err(err) => {
+            scenes::data::debug::log(LogEntry::new(
+                "Preferences",
+                format!("Error loading preferences: {}", err),
+                None,
+            ));
+            Preferences::default()
+        }
+    };
+
+    let deep_link = std::env::args()
+        .skip(1)
+        .find_map(|arg| DeepLink::parse(&arg));
+
     Chartsy::run(Settings {
         antialiasing: true,
         default_font: INCONSOLATA,
+        default_text_size: (preferences.get_font_size() as f32).into(),
+        flags: Flags {
+            preferences,
+            deep_link,
+        },
         ..Settings::default()
     })
 }
 
+/// The values the [Application] is started with.
+struct Flags {
+    /// The preferences saved from the previous run.
+    preferences: Preferences,
+
+    /// The resource from the link the application was opened with, if any.
+    deep_link: Option<DeepLink>,
+}
+
 /// The model for the [Application].
 struct Chartsy {
     /// Handles transitions between scenes.
@@ -56,27 +97,89 @@ struct Chartsy {
 
     /// Holds the global data.
     globals: Globals,
+
+    /// The logo displayed on the splash screen.
+    logo: Handle,
+
+    /// The moment the application was started; used to animate the splash screen.
+    started: Instant,
+
+    /// The resource from the link the application was opened with, if any.
+    deep_link: Option<DeepLink>,
+
+    /// Holds the command palette when it is open.
+    command_palette: ModalStack<()>,
+
+    /// The input of the command palette.
+    command_palette_input: String,
+
+    /// The actions listed in the command palette, by name.
+    command_palette_actions: HashMap<String, fn() -> Message>,
+
+    /// Holds the modals that can be opened over any scene, such as the message log.
+    modals: ModalStack<ModalType>,
+
+    /// Tells whether the application was opened offline before the database connected, in which
+    /// case the user is loaded once the connection is made.
+    started_offline: bool,
+}
+
+impl Chartsy {
+    /// Logs in the user from the token saved by their previous session.
+    fn auto_log_in(&self) -> Command<Message> {
+        let Some(db) = self.globals.get_db() else {
+            return Command::none();
+        };
+
+        Command::perform(
+            async move {
+                let user = database::auth::get_user_from_token(&db).await?;
+
+                let user_id = user.get_id();
+                database::auth::update_user_token(&db, user_id).await?;
+
+                Ok(user)
+            },
+            Message::AutoLoggedIn,
+        )
+    }
 }
 
 impl Application for Chartsy {
     type Executor = executor::Default;
     type Message = Message;
     type Theme = iced::Theme;
-    type Flags = ();
+    type Flags = Flags;
     type Renderer = iced::Renderer;
 
-    fn new(_flags: Self::Flags) -> (Chartsy, Command<Self::Message>) {
+    fn new(
+        Flags {
+            preferences,
+            deep_link,
+        }: Self::Flags,
+    ) -> (Chartsy, Command<Self::Message>) {
         let mut globals = Globals::default();
-        let scene_loader = SceneManager::new(&mut globals);
+        globals.reset_cache(preferences.get_cache_capacity());
+        globals.set_preferences(preferences);
+        let scene_loader = SceneManager::new();
 
         let icon = load_from_memory_with_format(APP_ICON, image::ImageFormat::Png).unwrap();
         let width = icon.width();
         let height = icon.height();
+        let logo = Handle::from_rgba(width, height, icon.to_rgba8().into_raw());
 
         (
             Chartsy {
                 scene_loader,
                 globals,
+                logo,
+                started: Instant::now(),
+                deep_link,
+                command_palette: ModalStack::new(),
+                command_palette_input: String::new(),
+                command_palette_actions: HashMap::new(),
+                modals: ModalStack::new(),
+                started_offline: false,
             },
             Command::batch(vec![
                 window::maximize(window::Id::MAIN, true),
@@ -90,6 +193,12 @@ impl Application for Chartsy {
                     database::base::connect_to_mongodb(),
                     Message::DoneDatabaseInit,
                 ),
+                Command::perform(scenes::services::main::load_recent_drawings(), |result| {
+                    match result {
+                        Ok(drawings) => Message::LoadedRecentDrawings(drawings),
+                        Err(err) => Message::Error(err),
+                    }
+                }),
             ]),
         )
     }
@@ -99,48 +208,230 @@ impl Application for Chartsy {
     }
 
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
-        match message {
+        let command = match message {
             Message::None => Command::none(),
-            Message::ChangeScene(scene) => self.scene_loader.load(scene, &mut self.globals),
+            Message::ChangeScene(Scenes::Admin(_))
+                if !self
+                    .globals
+                    .get_user()
+                    .is_some_and(|user| *user.get_role() == Role::Admin) =>
+            {
+                Command::none()
+            }
+            Message::ChangeScene(scene) => {
+                self.scene_loader.transition(scene);
+                Command::none()
+            }
+            Message::TransitionTick => self.scene_loader.tick(&mut self.globals),
+            Message::WindowFocus(focused) => self.scene_loader.focus(&mut self.globals, focused),
             Message::DoAction(action) => {
-                match self.scene_loader.update(&mut self.globals, action) {
-                    Ok(command) => command,
-                    Err(err) => self.update(Message::Error(err)),
+                let target = SceneManager::get_target(action.deref());
+
+                if target.is_some_and(|target| target != self.scene_loader.get_scene_id()) {
+                    // The message belongs to a scene that is no longer active, such as a
+                    // background task that finished after the user left its scene. Only the
+                    // messages the scene opts into are kept for it.
+                    if action.is_bus_event() {
+                        self.globals.publish_event(action);
+                    }
+                    Command::none()
+                } else {
+                    match self.scene_loader.update(&mut self.globals, action) {
+                        Ok(command) => command,
+                        Err(err) => self.update(Message::Error(err)),
+                    }
                 }
             }
             Message::DoneDatabaseInit(result) => match result {
                 Ok(client) => {
                     self.globals.set_client(client);
-                    let db = self.globals.get_db().unwrap();
 
-                    println!("Successfully connected to database.");
-                    Command::perform(
-                        async move {
-                            let user = database::auth::get_user_from_token(&db).await?;
+                    self.globals.log_message(LogEntry::new(
+                        "Database",
+                        "Successfully connected to database.",
+                        None,
+                    ));
+                    self.globals.set_db_status(DbStatus::Connected);
 
-                            let user_id = user.get_id();
-                            database::auth::update_user_token(&db, user_id).await?;
+                    let ensure_indexes = match self.globals.get_db() {
+                        Some(db) => Command::perform(
+                            async move { database::base::ensure_indexes(&db).await },
+                            |result| match result {
+                                Ok(_) => Message::None,
+                                Err(err) => Message::Error(err),
+                            },
+                        ),
+                        None => Command::none(),
+                    };
 
-                            Ok(user)
-                        },
-                        |result| match result {
-                            Ok(user) => Message::AutoLoggedIn(user),
-                            Err(err) => Message::Error(err),
-                        },
-                    )
+                    // A connection that was lost later on does not restart the startup.
+                    if self.globals.get_startup_step() == StartupStep::ConnectingToDatabase {
+                        Command::batch(vec![
+                            ensure_indexes,
+                            self.update(Message::StartupProgress(StartupStep::LoadingUser)),
+                        ])
+                    } else if std::mem::take(&mut self.started_offline) {
+                        Command::batch(vec![ensure_indexes, self.auto_log_in()])
+                    } else {
+                        ensure_indexes
+                    }
                 }
                 Err(err) => {
-                    println!("Error connecting to database: {}", err);
-                    Command::perform(
-                        async { database::base::connect_to_mongodb().await },
+                    self.globals.log_message(LogEntry::new(
+                        "Database",
+                        format!("Error connecting to database: {}", err),
+                        None,
+                    ));
+
+                    let failed_attempts = match self.globals.get_db_status() {
+                        DbStatus::Reconnecting(failed_attempts) => failed_attempts + 1,
+                        _ => 1,
+                    };
+
+                    // The application is opened offline while the connection is retried, so
+                    // that local drawings do not wait for the database.
+                    let open_offline =
+                        self.globals.get_startup_step() == StartupStep::ConnectingToDatabase;
+                    if open_offline {
+                        self.started_offline = true;
+                    }
+
+                    let status =
+                        if failed_attempts >= database::base::CONNECTION_SETTINGS.max_attempts {
+                            self.update(Message::DbStatusChanged(DbStatus::Failed))
+                        } else {
+                            self.update(Message::DbStatusChanged(DbStatus::Reconnecting(
+                                failed_attempts,
+                            )))
+                        };
+
+                    if open_offline {
+                        Command::batch(vec![
+                            status,
+                            self.update(Message::StartupProgress(StartupStep::Ready)),
+                        ])
+                    } else {
+                        status
+                    }
+                }
+            },
+            Message::DbStatusChanged(db_status) => {
+                self.globals.set_db_status(db_status);
+
+                match db_status {
+                    DbStatus::Connected => Command::none(),
+                    DbStatus::Reconnecting(failed_attempts) => Command::perform(
+                        async move {
+                            if failed_attempts > 0 {
+                                tokio::time::sleep(database::base::backoff_delay(failed_attempts))
+                                    .await;
+                            }
+
+                            database::base::connect_to_mongodb().await
+                        },
                         Message::DoneDatabaseInit,
-                    )
+                    ),
+                    DbStatus::Failed => {
+                        self.started_offline = false;
+                        Command::none()
+                    }
+                }
+            }
+            Message::CheckDbHealth => match self.globals.get_db() {
+                Some(db) if self.globals.get_db_status() == DbStatus::Connected => {
+                    Command::perform(async move { database::base::ping(&db).await }, |result| {
+                        match result {
+                            Ok(_) => Message::None,
+                            Err(err) => Message::DbConnectionLost(err),
+                        }
+                    })
                 }
+                _ => Command::none(),
             },
-            Message::AutoLoggedIn(user) => {
-                self.globals.set_user(Some(user));
+            Message::DbConnectionLost(err) => {
+                self.globals.log_message(LogEntry::new(
+                    "Database",
+                    format!("Lost connection to database: {}", err),
+                    None,
+                ));
+
+                self.update(Message::DbStatusChanged(DbStatus::Reconnecting(0)))
+            }
+            Message::AutoLoggedIn(result) => {
+                let command = match result {
+                    Ok(user) => {
+                        self.globals.set_user(Some(user));
+                        Command::none()
+                    }
+                    Err(Error::AuthError(
+                        error @ (AuthError::TokenExpired | AuthError::UserBanned),
+                    )) => {
+                        self.globals.set_user(None);
+                        self.globals.push_toast(error.to_string());
+
+                        Command::perform(
+                            async { scenes::services::main::delete_token_file().await },
+                            |result| match result {
+                                Ok(_) => Message::None,
+                                Err(err) => Message::Error(err),
+                            },
+                        )
+                    }
+                    Err(err) => self.update(Message::Error(err)),
+                };
+
+                // The user may also be loaded after the application was opened offline.
+                if self.globals.get_startup_step() == StartupStep::LoadingUser {
+                    Command::batch(vec![
+                        command,
+                        self.update(Message::StartupProgress(StartupStep::Ready)),
+                    ])
+                } else {
+                    command
+                }
+            }
+            Message::StartupProgress(step) => {
+                self.globals.set_startup_step(step);
+
+                match step {
+                    StartupStep::ConnectingToDatabase => Command::none(),
+                    StartupStep::LoadingUser => {
+                        // Without a connection, the application starts offline.
+                        if self.globals.get_db().is_none() {
+                            return self.update(Message::StartupProgress(StartupStep::Ready));
+                        }
+
+                        self.auto_log_in()
+                    }
+                    StartupStep::Ready => {
+                        let user_logged_in = self.globals.get_user().is_some();
+                        let scene = self
+                            .deep_link
+                            .take()
+                            .and_then(|deep_link| deep_link.get_scene(user_logged_in))
+                            .unwrap_or(Scenes::Main(None));
+
+                        self.scene_loader.load(scene, &mut self.globals)
+                    }
+                }
+            }
+            Message::LoadedRecentDrawings(drawings) => {
+                self.globals.set_recent_drawings(drawings);
                 Command::none()
             }
+            Message::SetTheme(theme) => {
+                self.globals.set_theme(theme);
+
+                Command::perform(
+                    scenes::services::app_settings::save_preferences(
+                        self.globals.get_preferences().clone(),
+                    ),
+                    |result| match result {
+                        Ok(_) => Message::None,
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
             Message::SendSmtpMail(mail) => Command::perform(
                 async {
                     let connection =
@@ -166,43 +457,237 @@ impl Application for Chartsy {
                     Err(err) => Message::Error(err),
                 },
             ),
+            Message::DismissToast(index) => {
+                self.globals.dismiss_toast(index);
+                Command::none()
+            }
+            Message::ExpireToasts(now) => {
+                self.globals.expire_toasts(now);
+                Command::none()
+            }
+            Message::Describe(description) => {
+                self.globals.push_description(description);
+                Command::none()
+            }
+            Message::MoveFocus(backwards) => {
+                if backwards {
+                    widget::focus_previous()
+                } else {
+                    widget::focus_next()
+                }
+            }
+            Message::CheckNotifications => {
+                let (Some(db), Some(user)) = (self.globals.get_db(), self.globals.get_user())
+                else {
+                    return Command::none();
+                };
+                let user_id = user.get_id();
+                let since = self.globals.get_notifications_checked();
+                self.globals
+                    .set_notifications_checked(mongodb::bson::DateTime::now());
+
+                Command::perform(
+                    async move { database::posts::get_new_comments(&db, user_id, since).await },
+                    |result| match result {
+                        Ok(notifications) => Message::NewNotifications(notifications),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            Message::NewNotifications(notifications) => {
+                self.globals.add_notifications(notifications);
+                Command::none()
+            }
             Message::Error(error) => {
                 if error.is_debug() {
-                    eprintln!("{}", error);
+                    self.globals
+                        .log_message(LogEntry::new("Error", error.to_string(), None));
                     Command::none()
                 } else {
+                    self.globals.push_toast(error.to_string());
+
                     match self.scene_loader.handle_error(&mut self.globals, &error) {
                         Ok(command) => command,
                         Err(err) => self.update(Message::Error(err)),
                     }
                 }
             }
+            Message::FlushEventBus => {
+                let events = self.globals.take_events();
+
+                Command::batch(
+                    events
+                        .into_iter()
+                        .map(|event| self.scene_loader.handle_bus_event(event, &mut self.globals))
+                        .collect::<Vec<_>>(),
+                )
+            }
+            Message::ToggleCommandPalette => {
+                self.command_palette.toggle_modal(());
+                self.command_palette_input.clear();
+                self.command_palette_actions = command_palette::get_actions(
+                    self.scene_loader.get_scene_id(),
+                    self.globals.get_user().is_some(),
+                );
+
+                text_input::focus(command_palette::input_id())
+            }
+            Message::UpdateCommandPaletteInput(input) => {
+                self.command_palette_input = input;
+                Command::none()
+            }
+            Message::RunCommand(name) => {
+                self.command_palette.clear();
+
+                match self.command_palette_actions.get(&name) {
+                    Some(action) => self.update(action()),
+                    None => Command::none(),
+                }
+            }
+            Message::ToggleDebugMode => {
+                self.globals.toggle_debug_mode();
+
+                if self.globals.is_debug_mode() {
+                    self.modals.toggle_modal(ModalType::DebugLog);
+                } else {
+                    self.modals.clear();
+                }
+
+                Command::none()
+            }
+            // Only the drawing scene records changes that can be undone.
+            Message::GlobalUndo | Message::GlobalRedo
+                if self.scene_loader.get_scene_id() == SceneId::Drawing =>
+            {
+                let canvas_message = match message {
+                    Message::GlobalUndo => CanvasMessage::Undo,
+                    _ => CanvasMessage::Redo,
+                };
+                self.update(canvas_message.into())
+            }
+            Message::GlobalUndo | Message::GlobalRedo => Command::none(),
             Message::Quit => window::close(window::Id::MAIN),
+        };
+
+        if self.globals.has_events() {
+            Command::batch(vec![
+                command,
+                Command::perform(async {}, |()| Message::FlushEventBus),
+            ])
+        } else {
+            command
         }
     }
 
     fn view(&self) -> Element<'_, Self::Message, Self::Theme, Renderer> {
-        match self.scene_loader.view(&self.globals) {
+        let step = self.globals.get_startup_step();
+        if step != StartupStep::Ready {
+            return scenes::services::main::splash_screen(
+                self.logo.clone(),
+                step,
+                (self.started.elapsed().as_millis() / SPLASH_FRAME_DURATION.as_millis()) as usize,
+            );
+        }
+
+        let content = match self.scene_loader.view(&self.globals) {
             Ok(element) => element,
             Err(err) => {
                 if err.is_debug() {
-                    eprintln!("{}", err);
+                    scenes::data::debug::log(LogEntry::new("Scene", err.to_string(), None));
                 }
 
                 WaitPanel::new("Trouble loading scene...").into()
             }
-        }
+        };
+
+        let content = if self.globals.get_db_status() == DbStatus::Failed {
+            Column::with_children(vec![scenes::services::main::db_failed_banner(), content]).into()
+        } else {
+            content
+        };
+
+        let content = self.modals.get_modal(
+            DrawableElement::new(content).opacity(self.scene_loader.get_opacity()),
+            |modal| match modal {
+                ModalType::DebugLog => debug::debug_log(
+                    self.globals.get_message_log(),
+                    self.globals.get_accessibility_log(),
+                ),
+            },
+        );
+
+        let content = self.command_palette.get_modal(content, |()| {
+            command_palette::command_palette(
+                &self.command_palette_input,
+                &self.command_palette_actions,
+            )
+        });
+
+        Toasts::new(content, self.globals.get_toasts(), Message::DismissToast).into()
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
-        Subscription::none()
+        if self.globals.get_startup_step() != StartupStep::Ready {
+            return iced::time::every(SPLASH_FRAME_DURATION).map(|_| Message::None);
+        }
+
+        let mut subscriptions = vec![
+            self.scene_loader.subscription(&self.globals),
+            iced::event::listen_with(|event, _status| match event {
+                iced::Event::Window(_, window::Event::Focused) => Some(Message::WindowFocus(true)),
+                iced::Event::Window(_, window::Event::Unfocused) => {
+                    Some(Message::WindowFocus(false))
+                }
+                _ => None,
+            }),
+            keyboard::on_key_press(|key, modifiers| match key.as_ref() {
+                keyboard::Key::Character("d" | "D") if modifiers.command() && modifiers.shift() => {
+                    Some(Message::ToggleDebugMode)
+                }
+                keyboard::Key::Character("p") if modifiers.command() => {
+                    Some(Message::ToggleCommandPalette)
+                }
+                keyboard::Key::Character("z") if modifiers.command() => Some(Message::GlobalUndo),
+                keyboard::Key::Character("y") if modifiers.command() => Some(Message::GlobalRedo),
+                keyboard::Key::Named(keyboard::key::Named::Tab) => {
+                    Some(Message::MoveFocus(modifiers.shift()))
+                }
+                _ => None,
+            }),
+        ];
+
+        if !self.globals.get_toasts().is_empty() {
+            subscriptions
+                .push(iced::time::every(Duration::from_secs(1)).map(Message::ExpireToasts));
+        }
+
+        if self.globals.get_db_status() == DbStatus::Connected {
+            subscriptions
+                .push(iced::time::every(DB_HEALTH_CHECK_INTERVAL).map(|_| Message::CheckDbHealth));
+        }
+
+        if self.globals.get_db().is_some() && self.globals.get_user().is_some() {
+            subscriptions.push(
+                iced::time::every(NOTIFICATIONS_CHECK_INTERVAL)
+                    .map(|_| Message::CheckNotifications),
+            );
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     fn theme(&self) -> Self::Theme {
-        utils::theme::Theme::custom_with_fn(
-            String::from("Chartsy"),
-            utils::theme::PALETTE,
-            utils::theme::extended_palette_generator,
-        )
+        match self.globals.get_theme() {
+            ThemeMode::Dark => utils::theme::Theme::custom_with_fn(
+                String::from("Chartsy"),
+                utils::theme::PALETTE,
+                utils::theme::extended_palette_generator,
+            ),
+            ThemeMode::Light => utils::theme::Theme::custom_with_fn(
+                String::from("Chartsy Light"),
+                utils::theme::LIGHT_PALETTE,
+                utils::theme::light_extended_palette_generator,
+            ),
+        }
     }
 }
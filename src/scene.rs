@@ -1,16 +1,35 @@
+use crate::canvas::undo::UndoEntry;
 use crate::debug_message;
+use crate::scenes::data::app_settings::{Preferences, ThemeMode};
 use crate::scenes::data::auth::User;
-use crate::scenes::scenes::Scenes;
+use crate::scenes::data::debug::{self, LogEntry};
+use crate::scenes::data::drawing::SaveMode;
+use crate::scenes::scenes::{SceneId, Scenes};
 use crate::utils::cache::Cache;
 use crate::utils::errors::Error;
 use crate::utils::icons::{Icon, ICON};
+use crate::utils::rate_limiter::RateLimiter;
+use crate::widgets::toast::{ToastQueue, TOAST_DURATION};
 use iced::advanced::widget::Text;
 use iced::widget::{Button, Row};
-use iced::{Command, Element, Renderer};
+use iced::{Command, Element, Renderer, Subscription};
 use iced::{Length, Theme};
+use mongodb::bson::{DateTime, Uuid};
 use mongodb::{Client, ClientSession, Database};
 use std::any::Any;
-use std::fmt::{Debug, Formatter};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The maximum number of drawings kept in the recently opened list.
+const MAX_RECENT_DRAWINGS: usize = 10;
+
+/// The maximum number of widget descriptions kept in the accessibility log.
+const MAX_ACCESSIBILITY_LOG: usize = 50;
+
+/// The maximum number of entries kept in the message log.
+const MAX_MESSAGE_LOG: usize = 200;
 
 /// An individual scene that handles its actions internally.
 pub trait Scene {
@@ -45,6 +64,12 @@ pub trait Scene {
     /// Applies the [options](Self::Options) to self.
     fn apply_options(&mut self, options: Self::Options);
 
+    /// Returns the state of the [Scene] as its [options](Self::Options), to be applied the next
+    /// time the [Scene] is opened without options.
+    fn snapshot(&self) -> Option<Box<dyn Any + Send + Sync>> {
+        None
+    }
+
     /// Gets a dynamic [message](SceneMessage) and returns it in the associated type.
     fn unwrap_message<'a>(&self, message: &'a dyn SceneMessage) -> Result<&'a Self::Message, Error>
     where
@@ -68,9 +93,37 @@ pub trait Scene {
     /// function of the [Application](crate::Chartsy).
     fn view(&self, globals: &Globals) -> Element<'_, Message, Theme, Renderer>;
 
+    /// Returns the [subscriptions](Subscription) of the [Scene]; to be called in the
+    /// [subscription](iced::Application::subscription) function of the
+    /// [Application](crate::Chartsy).
+    fn subscription(&self, _globals: &Globals) -> Subscription<Message> {
+        Subscription::none()
+    }
+
     /// Handles an [Error].
     fn handle_error(&mut self, globals: &mut Globals, error: &Error) -> Command<Message>;
 
+    /// Handles the window of the [Application](crate::Chartsy) gaining focus.
+    fn on_focus(&mut self, _globals: &mut Globals) -> Command<Message> {
+        Command::none()
+    }
+
+    /// Handles the window of the [Application](crate::Chartsy) losing focus.
+    fn on_blur(&mut self, _globals: &mut Globals) -> Command<Message> {
+        Command::none()
+    }
+
+    /// Handles an event published on the [event bus](Globals::publish_event): a message of the
+    /// [Scene] that arrived while it was closed, delivered once it is opened again. Only the
+    /// messages that are [bus events](SceneMessage::is_bus_event) are delivered.
+    fn handle_bus_event(
+        &mut self,
+        _event: &dyn SceneMessage,
+        _globals: &mut Globals,
+    ) -> Command<Message> {
+        Command::none()
+    }
+
     /// Handles closing the [Scene].
     fn clear(&self, globals: &mut Globals);
 }
@@ -95,6 +148,13 @@ pub trait SceneMessage: Send + Sync {
 
     /// Returns a reference to a clone of the [message](SceneMessage) enclosed in a [Box].
     fn boxed_clone(&self) -> Box<dyn SceneMessage + 'static>;
+
+    /// Tells whether the [message](SceneMessage) is kept on the
+    /// [event bus](Globals::publish_event) when it arrives after its [Scene] was closed. Other
+    /// messages of closed scenes are dropped.
+    fn is_bus_event(&self) -> bool {
+        false
+    }
 }
 
 impl Clone for Box<dyn SceneMessage + 'static> {
@@ -117,18 +177,115 @@ pub enum Message {
     Error(Error),
     /// Changes the scene to the given [Scene](Scenes).
     ChangeScene(Scenes),
+    /// Advances the transition between two [Scenes](Scene) by a frame.
+    TransitionTick,
+    /// Triggers when the window gains or loses focus.
+    WindowFocus(bool),
+    /// Delivers the events published on the [event bus](Globals::publish_event) to the
+    /// [Scenes](Scene).
+    FlushEventBus,
+    /// Opens or closes the command palette.
+    ToggleCommandPalette,
+    /// Updates the input of the command palette.
+    UpdateCommandPaletteInput(String),
+    /// Runs the action with the given name from the command palette.
+    RunCommand(String),
+    /// Reverses the last change recorded in the [undo history](Globals::push_undo).
+    GlobalUndo,
+    /// Reapplies the last change reversed by [GlobalUndo](Message::GlobalUndo).
+    GlobalRedo,
+    /// Enables or disables the [debug mode](Globals::is_debug_mode), along with the message log
+    /// overlay.
+    ToggleDebugMode,
     /// Performs an [Action], which should correspond to the current [scenes](Scene) enum of messages.
     DoAction(Box<dyn SceneMessage>),
     /// Triggers when a database connection has been established.
     DoneDatabaseInit(Result<Client, Error>),
-    /// Triggers when a user has been logged in using a token stored locally from a previous login.
-    AutoLoggedIn(User),
+    /// Updates the [status](DbStatus) of the database connection, attempting to connect again if
+    /// it is reconnecting.
+    DbStatusChanged(DbStatus),
+    /// Checks whether the database connection is still alive.
+    CheckDbHealth,
+    /// Triggers when the health check finds that the database connection has been lost.
+    DbConnectionLost(Error),
+    /// Triggers when the attempt to log in using a token stored locally from a previous login
+    /// has finished.
+    AutoLoggedIn(Result<User, Error>),
+    /// Advances the startup of the application to the given [step](StartupStep).
+    StartupProgress(StartupStep),
     /// Sends en e-mail.
     SendSmtpMail(lettre::Message),
+    /// Triggers when the list of recently opened drawings has been loaded.
+    LoadedRecentDrawings(Vec<(Uuid, String, SaveMode)>),
+    /// Changes the color scheme of the application.
+    SetTheme(ThemeMode),
+    /// Dismisses the toast with the given index.
+    DismissToast(usize),
+    /// Dismisses the toasts that have been displayed for too long.
+    ExpireToasts(Instant),
+    /// Announces the description of a widget that gained focus.
+    Describe(String),
+    /// Moves the keyboard focus to the next focusable widget, or to the previous one if the
+    /// value is true.
+    MoveFocus(bool),
+    /// Checks the database for new comments on the posts of the user.
+    CheckNotifications,
+    /// Triggers when new notifications have been received, given as the id of the post they
+    /// refer to and their text.
+    NewNotifications(Vec<(Uuid, String)>),
     /// Quits the application.
     Quit,
 }
 
+/// The steps the [Application](crate::Chartsy) goes through before showing the first scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupStep {
+    /// Waiting for the connection to the database.
+    ConnectingToDatabase,
+    /// Authenticating the user from a previous session.
+    LoadingUser,
+    /// The startup has finished.
+    Ready,
+}
+
+impl Display for StartupStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                StartupStep::ConnectingToDatabase => "Connecting to database",
+                StartupStep::LoadingUser => "Loading user",
+                StartupStep::Ready => "Ready",
+            }
+        )
+    }
+}
+
+/// The status of the connection to the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbStatus {
+    /// The database is connected.
+    Connected,
+    /// Attempting to connect to the database; holds the number of attempts that have failed.
+    Reconnecting(u32),
+    /// All attempts to connect to the database have failed.
+    Failed,
+}
+
+impl Display for DbStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DbStatus::Connected => write!(f, "Connected"),
+            DbStatus::Reconnecting(0) => write!(f, "Connecting..."),
+            DbStatus::Reconnecting(attempts) => {
+                write!(f, "Reconnecting... (attempt {})", attempts + 1)
+            }
+            DbStatus::Failed => write!(f, "Offline"),
+        }
+    }
+}
+
 /// The [Applications](crate::Chartsy) global values.
 #[derive(Debug, Clone)]
 pub struct Globals {
@@ -138,14 +295,63 @@ pub struct Globals {
     /// The database the program is connected to.
     mongo_client: Option<Client>,
 
+    /// The status of the connection to the database.
+    db_status: DbStatus,
+
     /// The caching system.
     cache: Cache,
+
+    /// The drawings that were opened most recently, starting with the latest.
+    recent_drawings: VecDeque<(Uuid, String, SaveMode)>,
+
+    /// The unread notifications, as the id of the post they refer to and their text.
+    notifications: Vec<(Uuid, String)>,
+
+    /// The last time the database was checked for new notifications.
+    notifications_checked: DateTime,
+
+    /// The global preferences of the application.
+    preferences: Preferences,
+
+    /// The current step of the startup of the application.
+    startup_step: StartupStep,
+
+    /// The notification toasts currently displayed.
+    toasts: ToastQueue,
+
+    /// The descriptions of the widgets that gained focus, starting with the latest, to be read by
+    /// screen readers.
+    accessibility_log: VecDeque<String>,
+
+    /// The snapshots of the [Scenes](Scene) that were closed, to be restored when they are opened
+    /// again.
+    scene_cache: HashMap<SceneId, Arc<dyn Any + Send + Sync>>,
+
+    /// The events published for other [Scenes](Scene), delivered after the current message.
+    event_bus: VecDeque<Box<dyn SceneMessage>>,
+
+    /// The changes that reverse the latest changes made to the drawing, starting with the oldest.
+    undo_stack: Vec<UndoEntry>,
+
+    /// The changes that reapply the latest undone changes, starting with the oldest.
+    redo_stack: Vec<UndoEntry>,
+
+    /// Limits how often the database write operations can be performed.
+    rate_limiter: Arc<Mutex<RateLimiter>>,
+
+    /// Tells whether the handled messages are recorded in the message log.
+    debug_mode: bool,
+
+    /// The messages handled while the debug mode was enabled, starting with the latest.
+    message_log: VecDeque<LogEntry>,
 }
 
 impl Globals {
-    /// Updates the value of the user.
+    /// Updates the value of the user, and forgets the [Scenes](Scene) cached for the previous
+    /// user.
     pub fn set_user(&mut self, user: Option<User>) {
         self.user = user;
+        self.scene_cache.clear();
     }
 
     /// Returns the user data.
@@ -188,6 +394,248 @@ impl Globals {
     pub fn get_cache(&self) -> Cache {
         self.cache.clone()
     }
+
+    /// Returns the [RateLimiter] of the database write operations.
+    pub fn get_rate_limiter(&self) -> Arc<Mutex<RateLimiter>> {
+        self.rate_limiter.clone()
+    }
+
+    /// Replaces the cache with an empty one that holds at most the given number of images.
+    pub fn reset_cache(&mut self, max_entries: usize) {
+        self.cache = Cache::new().with_capacity(max_entries);
+    }
+
+    /// Returns the list of recently opened drawings.
+    pub fn get_recent_drawings(&self) -> &VecDeque<(Uuid, String, SaveMode)> {
+        &self.recent_drawings
+    }
+
+    /// Updates the list of recently opened drawings.
+    pub fn set_recent_drawings(
+        &mut self,
+        recent_drawings: impl Into<VecDeque<(Uuid, String, SaveMode)>>,
+    ) {
+        self.recent_drawings = recent_drawings.into();
+    }
+
+    /// Moves the given drawing to the front of the recently opened drawings.
+    pub fn add_recent_drawing(&mut self, id: Uuid, name: String, save_mode: SaveMode) {
+        self.remove_recent_drawing(id);
+        self.recent_drawings.push_front((id, name, save_mode));
+        self.recent_drawings.truncate(MAX_RECENT_DRAWINGS);
+    }
+
+    /// Removes the given drawing from the recently opened drawings.
+    pub fn remove_recent_drawing(&mut self, id: Uuid) {
+        self.recent_drawings.retain(|(drawing_id, _, _)| *drawing_id != id);
+    }
+
+    /// Returns the unread notifications.
+    pub fn get_notifications(&self) -> &Vec<(Uuid, String)> {
+        &self.notifications
+    }
+
+    /// Adds unread notifications.
+    pub fn add_notifications(&mut self, notifications: impl IntoIterator<Item = (Uuid, String)>) {
+        self.notifications.extend(notifications);
+    }
+
+    /// Marks the notifications referring to the given post as read.
+    pub fn read_notifications(&mut self, post_id: Uuid) {
+        self.notifications.retain(|(id, _)| *id != post_id);
+    }
+
+    /// Returns the last time the database was checked for new notifications.
+    pub fn get_notifications_checked(&self) -> DateTime {
+        self.notifications_checked
+    }
+
+    /// Updates the last time the database was checked for new notifications.
+    pub fn set_notifications_checked(&mut self, notifications_checked: DateTime) {
+        self.notifications_checked = notifications_checked;
+    }
+
+    /// Returns the time between two automatic saves of a drawing.
+    pub fn get_auto_save_interval(&self) -> Option<Duration> {
+        self.preferences.get_auto_save_interval()
+    }
+
+    /// Updates the time between two automatic saves of a drawing.
+    pub fn set_auto_save_interval(&mut self, auto_save_interval: Option<Duration>) {
+        self.preferences.set_auto_save_interval(auto_save_interval);
+    }
+
+    /// Returns the color scheme of the application.
+    pub fn get_theme(&self) -> ThemeMode {
+        self.preferences.get_theme()
+    }
+
+    /// Updates the color scheme of the application.
+    pub fn set_theme(&mut self, theme: ThemeMode) {
+        self.preferences.set_theme(theme);
+    }
+
+    /// Returns the global preferences.
+    pub fn get_preferences(&self) -> &Preferences {
+        &self.preferences
+    }
+
+    /// Returns the global preferences as mutable.
+    pub fn get_preferences_mut(&mut self) -> &mut Preferences {
+        &mut self.preferences
+    }
+
+    /// Updates the global preferences.
+    pub fn set_preferences(&mut self, preferences: Preferences) {
+        self.preferences = preferences;
+    }
+
+    /// Returns the status of the connection to the database.
+    pub fn get_db_status(&self) -> DbStatus {
+        self.db_status
+    }
+
+    /// Updates the status of the connection to the database.
+    pub fn set_db_status(&mut self, db_status: DbStatus) {
+        self.db_status = db_status;
+    }
+
+    /// Returns the current step of the startup.
+    pub fn get_startup_step(&self) -> StartupStep {
+        self.startup_step
+    }
+
+    /// Updates the current step of the startup.
+    pub fn set_startup_step(&mut self, startup_step: StartupStep) {
+        self.startup_step = startup_step;
+    }
+
+    /// Returns the notification toasts currently displayed.
+    pub fn get_toasts(&self) -> &ToastQueue {
+        &self.toasts
+    }
+
+    /// Displays a new notification toast with the given text.
+    pub fn push_toast(&mut self, text: impl Into<String>) {
+        self.toasts.push_back((text.into(), Instant::now()));
+    }
+
+    /// Dismisses the notification toast with the given index.
+    pub fn dismiss_toast(&mut self, index: usize) {
+        self.toasts.remove(index);
+    }
+
+    /// Dismisses the notification toasts that have been displayed for longer than
+    /// [TOAST_DURATION].
+    pub fn expire_toasts(&mut self, now: Instant) {
+        self.toasts
+            .retain(|(_, created)| now.duration_since(*created) < TOAST_DURATION);
+    }
+
+    /// Returns the descriptions of the widgets that gained focus, starting with the latest.
+    pub fn get_accessibility_log(&self) -> &VecDeque<String> {
+        &self.accessibility_log
+    }
+
+    /// Adds the description of a widget that gained focus to the accessibility log.
+    pub fn push_description(&mut self, description: String) {
+        self.accessibility_log.push_front(description);
+        self.accessibility_log.truncate(MAX_ACCESSIBILITY_LOG);
+    }
+
+    /// Stores the snapshot of a [Scene] that is being closed.
+    pub fn cache_scene(&mut self, id: SceneId, snapshot: Box<dyn Any + Send + Sync>) {
+        self.scene_cache.insert(id, Arc::from(snapshot));
+    }
+
+    /// Publishes an event on the event bus, to be delivered to the [Scene] it belongs to after the
+    /// current message, or once the [Scene] is opened.
+    pub fn publish_event(&mut self, event: Box<dyn SceneMessage>) {
+        self.event_bus.push_back(event);
+    }
+
+    /// Tells whether there are events waiting on the event bus.
+    pub fn has_events(&self) -> bool {
+        !self.event_bus.is_empty()
+    }
+
+    /// Removes and returns the events waiting on the event bus.
+    pub fn take_events(&mut self) -> VecDeque<Box<dyn SceneMessage>> {
+        std::mem::take(&mut self.event_bus)
+    }
+
+    /// Removes and returns the snapshot of a [Scene], if it was closed before.
+    pub fn take_cached_scene(&mut self, id: SceneId) -> Option<Arc<dyn Any + Send + Sync>> {
+        self.scene_cache.remove(&id)
+    }
+
+    /// Records a change that reverses the latest change made to the drawing.
+    pub fn push_undo(&mut self, entry: UndoEntry) {
+        self.undo_stack.push(entry);
+    }
+
+    /// Removes and returns the change that reverses the latest change made to the drawing.
+    pub fn pop_undo(&mut self) -> Option<UndoEntry> {
+        self.undo_stack.pop()
+    }
+
+    /// Records a change that reapplies the latest undone change.
+    pub fn push_redo(&mut self, entry: UndoEntry) {
+        self.redo_stack.push(entry);
+    }
+
+    /// Removes and returns the change that reapplies the latest undone change.
+    pub fn pop_redo(&mut self) -> Option<UndoEntry> {
+        self.redo_stack.pop()
+    }
+
+    /// Returns the changes that reapply the undone changes, starting with the oldest.
+    pub fn get_redo_stack(&self) -> &Vec<UndoEntry> {
+        &self.redo_stack
+    }
+
+    /// Forgets the undone changes; used once a new change is made.
+    pub fn clear_redo(&mut self) {
+        self.redo_stack.clear();
+    }
+
+    /// Forgets the whole undo history.
+    pub fn clear_undo_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    /// Tells whether the handled messages are recorded in the message log.
+    pub fn is_debug_mode(&self) -> bool {
+        self.debug_mode
+    }
+
+    /// Enables or disables the debug mode. The message log is cleared when it is disabled.
+    pub fn toggle_debug_mode(&mut self) {
+        self.debug_mode = !self.debug_mode;
+
+        if !self.debug_mode {
+            self.message_log.clear();
+        }
+    }
+
+    /// Returns the entries of the message log, starting with the latest.
+    pub fn get_message_log(&self) -> &VecDeque<LogEntry> {
+        &self.message_log
+    }
+
+    /// Adds an entry to the message log, after the [pending entries](debug::log), if the debug
+    /// mode is enabled.
+    pub fn log_message(&mut self, entry: LogEntry) {
+        let pending = debug::take_pending_entries();
+
+        if self.debug_mode {
+            for entry in pending.into_iter().chain([entry]) {
+                self.message_log.push_front(entry);
+            }
+            self.message_log.truncate(MAX_MESSAGE_LOG);
+        }
+    }
 }
 
 impl Default for Globals {
@@ -195,7 +643,22 @@ impl Default for Globals {
         Globals {
             user: None,
             mongo_client: None,
+            db_status: DbStatus::Reconnecting(0),
             cache: Cache::new(),
+            recent_drawings: VecDeque::new(),
+            notifications: vec![],
+            notifications_checked: DateTime::now(),
+            preferences: Preferences::default(),
+            startup_step: StartupStep::ConnectingToDatabase,
+            toasts: VecDeque::new(),
+            accessibility_log: VecDeque::new(),
+            scene_cache: HashMap::new(),
+            event_bus: VecDeque::new(),
+            undo_stack: vec![],
+            redo_stack: vec![],
+            rate_limiter: Arc::new(Mutex::new(RateLimiter::default())),
+            debug_mode: false,
+            message_log: VecDeque::new(),
         }
     }
 }
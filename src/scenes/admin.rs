@@ -0,0 +1,439 @@
+use crate::database;
+use crate::scene::{Globals, Message, Scene, SceneMessage};
+use crate::scenes::data::admin::{ActionFilter, AdminTabs, AuditEntry, Report, Statistics};
+use crate::scenes::data::auth::User;
+use crate::scenes::data::challenge::Challenge;
+use crate::utils::errors::Error;
+use crate::utils::theme::{self, Theme};
+use iced::widget::{Column, Space, Text};
+use iced::{Alignment, Command, Element, Length, Renderer};
+use mongodb::bson::{DateTime, Uuid};
+use std::any::Any;
+
+use super::services;
+
+/// The [Scene] through which admins moderate the site.
+pub struct Admin {
+    /// The registered users. Is None if they haven't been loaded yet.
+    users: Option<Vec<User>>,
+
+    /// The reports that haven't been handled yet.
+    reports: Option<Vec<Report>>,
+
+    /// The latest challenges.
+    challenges: Option<Vec<Challenge>>,
+
+    /// The statistics of the site.
+    statistics: Option<Statistics>,
+
+    /// The latest entries of the audit log that match the filters.
+    audit_log: Option<Vec<AuditEntry>>,
+
+    /// The tag of the user the audit log is filtered by. Is empty if it isn't filtered by user.
+    audit_user_filter: String,
+
+    /// The action the audit log is filtered by.
+    audit_action_filter: ActionFilter,
+
+    /// The prompt of the challenge that is being created.
+    challenge_prompt: String,
+
+    /// The duration, in hours, of the challenge that is being created.
+    challenge_duration: String,
+
+    /// The currently selected tab.
+    active_tab: AdminTabs,
+
+    /// The last error that an action has created.
+    error: Option<Error>,
+}
+
+/// The options of the [Admin] scene.
+#[derive(Debug, Clone)]
+pub struct AdminOptions {
+    /// The tab that is opened.
+    active_tab: AdminTabs,
+}
+
+impl AdminOptions {
+    /// Creates options that open the given tab.
+    pub fn new(active_tab: AdminTabs) -> Self {
+        AdminOptions { active_tab }
+    }
+}
+
+/// The possible [messages](SceneMessage) this [Scene] can trigger.
+#[derive(Clone)]
+pub enum AdminMessage {
+    /// Changes the selected tab, reloading its data.
+    SelectTab(AdminTabs),
+
+    /// Triggered when the users have been loaded.
+    LoadedUsers(Vec<User>),
+
+    /// Triggered when the reports have been loaded.
+    LoadedReports(Vec<Report>),
+
+    /// Triggered when the challenges have been loaded.
+    LoadedChallenges(Vec<Challenge>),
+
+    /// Triggered when the statistics have been loaded.
+    LoadedStatistics(Statistics),
+
+    /// Triggered when the audit log has been loaded.
+    LoadedAuditLog(Vec<AuditEntry>),
+
+    /// Updates the tag of the user the audit log is filtered by.
+    AuditUserFilterUpdate(String),
+
+    /// Changes the action the audit log is filtered by, reloading it.
+    AuditActionFilterUpdate(ActionFilter),
+
+    /// Bans or unbans the given user.
+    SetBanStatus { user_id: Uuid, banned: bool },
+
+    /// Triggered when the given user has been banned or unbanned.
+    DoneSetBanStatus { user_id: Uuid, banned: bool },
+
+    /// Removes the given report from the queue.
+    DismissReport(Uuid),
+
+    /// Deletes the given reported post.
+    DeleteReportedPost(Uuid),
+
+    /// Updates the prompt of the challenge that is being created.
+    ChallengePromptUpdate(String),
+
+    /// Updates the duration of the challenge that is being created.
+    ChallengeDurationUpdate(String),
+
+    /// Creates a challenge that starts now.
+    CreateChallenge,
+
+    /// Stops the given challenge from accepting drawings.
+    CloseChallenge(Uuid),
+
+    /// Handles errors.
+    Error(Error),
+}
+
+impl SceneMessage for AdminMessage {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> String {
+        match self {
+            Self::SelectTab(_) => String::from("Select tab"),
+            Self::LoadedUsers(_) => String::from("Loaded users"),
+            Self::LoadedReports(_) => String::from("Loaded reports"),
+            Self::LoadedChallenges(_) => String::from("Loaded challenges"),
+            Self::LoadedStatistics(_) => String::from("Loaded statistics"),
+            Self::LoadedAuditLog(_) => String::from("Loaded audit log"),
+            Self::AuditUserFilterUpdate(_) => String::from("Audit user filter update"),
+            Self::AuditActionFilterUpdate(_) => String::from("Audit action filter update"),
+            Self::SetBanStatus { .. } => String::from("Set ban status"),
+            Self::DoneSetBanStatus { .. } => String::from("Done set ban status"),
+            Self::DismissReport(_) => String::from("Dismiss report"),
+            Self::DeleteReportedPost(_) => String::from("Delete reported post"),
+            Self::ChallengePromptUpdate(_) => String::from("Challenge prompt update"),
+            Self::ChallengeDurationUpdate(_) => String::from("Challenge duration update"),
+            Self::CreateChallenge => String::from("Create challenge"),
+            Self::CloseChallenge(_) => String::from("Close challenge"),
+            Self::Error(_) => String::from("Error"),
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn SceneMessage + 'static> {
+        Box::new((*self).clone())
+    }
+}
+
+impl Into<Message> for AdminMessage {
+    fn into(self) -> Message {
+        Message::DoAction(Box::new(self))
+    }
+}
+
+impl Admin {
+    /// Loads the data displayed by the selected tab.
+    fn load_tab(&self, globals: &Globals) -> Command<Message> {
+        let db = globals.get_db().unwrap();
+
+        match self.active_tab {
+            AdminTabs::Users => Command::perform(
+                async move { database::admin::get_users(&db).await },
+                |result| match result {
+                    Ok(users) => AdminMessage::LoadedUsers(users).into(),
+                    Err(err) => Message::Error(err),
+                },
+            ),
+            AdminTabs::Reports => Command::perform(
+                async move { database::admin::get_reports(&db).await },
+                |result| match result {
+                    Ok(reports) => AdminMessage::LoadedReports(reports).into(),
+                    Err(err) => Message::Error(err),
+                },
+            ),
+            AdminTabs::Challenges => Command::perform(
+                async move { database::admin::get_challenges(&db).await },
+                |result| match result {
+                    Ok(challenges) => AdminMessage::LoadedChallenges(challenges).into(),
+                    Err(err) => Message::Error(err),
+                },
+            ),
+            AdminTabs::Statistics => Command::perform(
+                async move { database::admin::get_statistics(&db).await },
+                |result| match result {
+                    Ok(statistics) => AdminMessage::LoadedStatistics(statistics).into(),
+                    Err(err) => Message::Error(err),
+                },
+            ),
+            AdminTabs::AuditLog => {
+                let user_tag = Some(self.audit_user_filter.trim().trim_start_matches('@'))
+                    .filter(|user_tag| !user_tag.is_empty())
+                    .map(String::from);
+                let action = self.audit_action_filter.0;
+
+                Command::perform(
+                    async move { database::admin::get_audit_log(&db, user_tag, action).await },
+                    |result| match result {
+                        Ok(entries) => AdminMessage::LoadedAuditLog(entries).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+        }
+    }
+}
+
+impl Scene for Admin {
+    type Message = AdminMessage;
+    type Options = AdminOptions;
+
+    fn new(options: Option<Self::Options>, globals: &mut Globals) -> (Self, Command<Message>)
+    where
+        Self: Sized,
+    {
+        let mut admin = Admin {
+            users: None,
+            reports: None,
+            challenges: None,
+            statistics: None,
+            audit_log: None,
+            audit_user_filter: String::new(),
+            audit_action_filter: ActionFilter::default(),
+            challenge_prompt: String::new(),
+            challenge_duration: String::from("24"),
+            active_tab: AdminTabs::default(),
+            error: None,
+        };
+
+        if let Some(options) = options {
+            admin.apply_options(options);
+        }
+
+        let command = admin.load_tab(globals);
+
+        (admin, command)
+    }
+
+    fn get_title(&self) -> String {
+        String::from("Admin panel")
+    }
+
+    fn apply_options(&mut self, options: Self::Options) {
+        self.active_tab = options.active_tab;
+    }
+
+    fn update(&mut self, globals: &mut Globals, message: &Self::Message) -> Command<Message> {
+        match message {
+            AdminMessage::SelectTab(tab) => {
+                self.active_tab = *tab;
+                self.error = None;
+
+                self.load_tab(globals)
+            }
+            AdminMessage::LoadedUsers(users) => {
+                self.users = Some(users.clone());
+
+                Command::none()
+            }
+            AdminMessage::LoadedReports(reports) => {
+                self.reports = Some(reports.clone());
+
+                Command::none()
+            }
+            AdminMessage::LoadedChallenges(challenges) => {
+                self.challenges = Some(challenges.clone());
+
+                Command::none()
+            }
+            AdminMessage::LoadedStatistics(statistics) => {
+                self.statistics = Some(*statistics);
+
+                Command::none()
+            }
+            AdminMessage::LoadedAuditLog(entries) => {
+                self.audit_log = Some(entries.clone());
+
+                Command::none()
+            }
+            AdminMessage::AuditUserFilterUpdate(user_tag) => {
+                self.audit_user_filter = user_tag.clone();
+
+                Command::none()
+            }
+            AdminMessage::AuditActionFilterUpdate(action) => {
+                self.audit_action_filter = *action;
+
+                self.update(globals, &AdminMessage::SelectTab(AdminTabs::AuditLog))
+            }
+            AdminMessage::SetBanStatus { user_id, banned } => {
+                let db = globals.get_db().unwrap();
+                let (user_id, banned) = (*user_id, *banned);
+
+                Command::perform(
+                    async move { database::admin::set_ban_status(&db, user_id, banned).await },
+                    move |result| match result {
+                        Ok(_) => AdminMessage::DoneSetBanStatus { user_id, banned }.into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            AdminMessage::DoneSetBanStatus { user_id, banned } => {
+                if let Some(user) = self
+                    .users
+                    .iter_mut()
+                    .flatten()
+                    .find(|user| user.get_id() == *user_id)
+                {
+                    user.set_banned(*banned);
+                }
+
+                Command::none()
+            }
+            AdminMessage::DismissReport(report_id) => {
+                let db = globals.get_db().unwrap();
+                let report_id = *report_id;
+
+                Command::perform(
+                    async move { database::admin::dismiss_report(&db, report_id).await },
+                    |result| match result {
+                        Ok(_) => AdminMessage::SelectTab(AdminTabs::Reports).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            AdminMessage::DeleteReportedPost(post_id) => {
+                let db = globals.get_db().unwrap();
+                let post_id = *post_id;
+
+                Command::perform(
+                    async move { database::admin::delete_reported_post(&db, post_id).await },
+                    |result| match result {
+                        Ok(_) => AdminMessage::SelectTab(AdminTabs::Reports).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            AdminMessage::ChallengePromptUpdate(prompt) => {
+                self.challenge_prompt = prompt.clone();
+
+                Command::none()
+            }
+            AdminMessage::ChallengeDurationUpdate(duration) => {
+                self.challenge_duration = duration.clone();
+
+                Command::none()
+            }
+            AdminMessage::CreateChallenge => {
+                let hours = match self.challenge_duration.parse::<i64>() {
+                    Ok(hours) if hours > 0 => hours,
+                    _ => return Command::none(),
+                };
+                let db = globals.get_db().unwrap();
+                let prompt = self.challenge_prompt.trim().to_string();
+                let start_time = DateTime::now();
+                let end_time =
+                    DateTime::from_millis(start_time.timestamp_millis() + hours * 60 * 60 * 1000);
+
+                self.challenge_prompt = String::new();
+
+                Command::perform(
+                    async move {
+                        database::admin::create_challenge(&db, prompt, start_time, end_time).await
+                    },
+                    |result| match result {
+                        Ok(_) => AdminMessage::SelectTab(AdminTabs::Challenges).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            AdminMessage::CloseChallenge(challenge_id) => {
+                let db = globals.get_db().unwrap();
+                let challenge_id = *challenge_id;
+
+                Command::perform(
+                    async move { database::admin::close_challenge(&db, challenge_id).await },
+                    |result| match result {
+                        Ok(_) => AdminMessage::SelectTab(AdminTabs::Challenges).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            AdminMessage::Error(err) => {
+                self.error = Some(err.clone());
+
+                Command::none()
+            }
+        }
+    }
+
+    fn view(&self, globals: &Globals) -> Element<'_, Message, Theme, Renderer> {
+        let error = match &self.error {
+            Some(error) => Text::new(error.to_string())
+                .style(theme::text::danger)
+                .size(15.0)
+                .into(),
+            None => Space::with_width(Length::Fill).into(),
+        };
+
+        let admin_id = globals
+            .get_user()
+            .map(|user| user.get_id())
+            .unwrap_or(Uuid::from_bytes([0; 16]));
+
+        Column::with_children(vec![
+            self.title_element(),
+            error,
+            services::admin::admin_tabs(
+                services::admin::users_tab(&self.users, admin_id),
+                services::admin::reports_tab(&self.reports),
+                services::admin::challenges_tab(
+                    &self.challenges,
+                    &self.challenge_prompt,
+                    &self.challenge_duration,
+                ),
+                services::admin::statistics_tab(&self.statistics),
+                services::admin::audit_log_tab(
+                    &self.audit_log,
+                    &self.audit_user_filter,
+                    self.audit_action_filter,
+                ),
+                self.active_tab,
+            ),
+        ])
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_items(Alignment::Center)
+        .spacing(20.0)
+        .into()
+    }
+
+    fn handle_error(&mut self, globals: &mut Globals, error: &Error) -> Command<Message> {
+        self.update(globals, &AdminMessage::Error(error.clone()))
+    }
+
+    fn clear(&self, _globals: &mut Globals) {}
+}
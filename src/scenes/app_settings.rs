@@ -0,0 +1,303 @@
+use crate::scene::{Globals, Message, Scene, SceneMessage};
+use crate::scenes::data::app_settings::ThemeMode;
+use crate::scenes::data::drawing::{AutoSaveInterval, SaveMode};
+use crate::utils::errors::Error;
+use crate::utils::theme::{self, Theme};
+use iced::widget::{Column, Row, Scrollable, Space, Text};
+use iced::{Alignment, Command, Element, Length, Renderer};
+use std::any::Any;
+
+use super::services;
+
+/// The struct for the application settings [Scene], which holds the global preferences.
+pub struct AppSettings {
+    /// The current user input in the default canvas width TextInput.
+    width_input: String,
+
+    /// The current user input in the default canvas height TextInput.
+    height_input: String,
+
+    /// The last error that saving the preferences has created.
+    error: Option<Error>,
+
+    /// Tells whether the previews of the offline drawings are being regenerated.
+    regenerating: bool,
+
+    /// Tells whether the debug information panel is displayed.
+    show_debug_info: bool,
+}
+
+/// This scene has no options.
+#[derive(Debug, Clone)]
+pub struct AppSettingsOptions {}
+
+/// The possible [messages](SceneMessage) this [Scene] can trigger.
+#[derive(Clone)]
+pub enum AppSettingsMessage {
+    /// Changes the color scheme of the application.
+    SetTheme(ThemeMode),
+
+    /// When the default canvas width TextInput field is modified.
+    UpdateCanvasWidth(String),
+
+    /// When the default canvas height TextInput field is modified.
+    UpdateCanvasHeight(String),
+
+    /// Changes the interval between automatic saves.
+    SetAutoSave(AutoSaveInterval),
+
+    /// Changes the save mode new drawings are created with.
+    SetSaveMode(SaveMode),
+
+    /// Changes the default size of the text.
+    SetFontSize(u16),
+
+    /// Changes the maximum number of images kept in the cache.
+    SetCacheCapacity(usize),
+
+    /// Renders the previews of all offline drawings again from their stored data.
+    RegenerateAllPreviews,
+
+    /// Triggered when the previews of the offline drawings have been regenerated.
+    RegeneratedPreviews,
+
+    /// Shows or hides the debug information panel.
+    ToggleDebugInfo,
+
+    /// Removes all images from the cache.
+    ClearCache,
+
+    /// Handles errors.
+    Error(Error),
+}
+
+impl SceneMessage for AppSettingsMessage {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> String {
+        match self {
+            Self::SetTheme(_) => String::from("Set theme"),
+            Self::UpdateCanvasWidth(_) => String::from("Update canvas width"),
+            Self::UpdateCanvasHeight(_) => String::from("Update canvas height"),
+            Self::SetAutoSave(_) => String::from("Set auto-save"),
+            Self::SetSaveMode(_) => String::from("Set save mode"),
+            Self::SetFontSize(_) => String::from("Set font size"),
+            Self::SetCacheCapacity(_) => String::from("Set cache capacity"),
+            Self::RegenerateAllPreviews => String::from("Regenerate all previews"),
+            Self::RegeneratedPreviews => String::from("Regenerated previews"),
+            Self::ToggleDebugInfo => String::from("Toggle debug info"),
+            Self::ClearCache => String::from("Clear cache"),
+            Self::Error(_) => String::from("Error"),
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn SceneMessage + 'static> {
+        Box::new((*self).clone())
+    }
+}
+
+impl Into<Message> for AppSettingsMessage {
+    fn into(self) -> Message {
+        Message::DoAction(Box::new(self))
+    }
+}
+
+impl AppSettings {
+    /// Stores the current preferences locally.
+    fn save(&mut self, globals: &Globals) -> Command<Message> {
+        self.error = None;
+
+        Command::perform(
+            services::app_settings::save_preferences(globals.get_preferences().clone()),
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    /// Updates the default canvas size if both inputs hold valid dimensions.
+    fn update_canvas_size(&mut self, globals: &mut Globals) -> Command<Message> {
+        match (
+            self.width_input.parse::<u32>(),
+            self.height_input.parse::<u32>(),
+        ) {
+            (Ok(width), Ok(height)) if width > 0 && height > 0 => {
+                globals
+                    .get_preferences_mut()
+                    .set_canvas_size((width, height));
+
+                self.save(globals)
+            }
+            _ => Command::none(),
+        }
+    }
+}
+
+impl Scene for AppSettings {
+    type Message = AppSettingsMessage;
+    type Options = AppSettingsOptions;
+
+    fn new(options: Option<Self::Options>, globals: &mut Globals) -> (Self, Command<Message>)
+    where
+        Self: Sized,
+    {
+        let (width, height) = globals.get_preferences().get_canvas_size();
+
+        let mut app_settings = AppSettings {
+            width_input: width.to_string(),
+            height_input: height.to_string(),
+            error: None,
+            regenerating: false,
+            show_debug_info: false,
+        };
+
+        if let Some(options) = options {
+            app_settings.apply_options(options);
+        }
+
+        (app_settings, Command::none())
+    }
+
+    fn get_title(&self) -> String {
+        "Preferences".into()
+    }
+
+    fn apply_options(&mut self, _options: Self::Options) {}
+
+    fn update(&mut self, globals: &mut Globals, message: &Self::Message) -> Command<Message> {
+        match message {
+            AppSettingsMessage::SetTheme(theme) => {
+                globals.set_theme(*theme);
+                self.save(globals)
+            }
+            AppSettingsMessage::UpdateCanvasWidth(width) => {
+                if !width.chars().all(|c| c.is_ascii_digit()) {
+                    return Command::none();
+                }
+
+                self.width_input = width.clone();
+                self.update_canvas_size(globals)
+            }
+            AppSettingsMessage::UpdateCanvasHeight(height) => {
+                if !height.chars().all(|c| c.is_ascii_digit()) {
+                    return Command::none();
+                }
+
+                self.height_input = height.clone();
+                self.update_canvas_size(globals)
+            }
+            AppSettingsMessage::SetAutoSave(AutoSaveInterval(interval)) => {
+                globals.set_auto_save_interval(*interval);
+                self.save(globals)
+            }
+            AppSettingsMessage::SetSaveMode(save_mode) => {
+                globals.get_preferences_mut().set_save_mode(*save_mode);
+                self.save(globals)
+            }
+            AppSettingsMessage::SetFontSize(font_size) => {
+                globals.get_preferences_mut().set_font_size(*font_size);
+                self.save(globals)
+            }
+            AppSettingsMessage::SetCacheCapacity(cache_capacity) => {
+                globals
+                    .get_preferences_mut()
+                    .set_cache_capacity(*cache_capacity);
+                self.save(globals)
+            }
+            AppSettingsMessage::RegenerateAllPreviews => {
+                self.regenerating = true;
+                let cache = globals.get_cache();
+
+                Command::perform(
+                    async move {
+                        for (id, _) in services::main::get_drawings_offline().await? {
+                            services::drawing::regenerate_preview_offline(id, cache.clone())
+                                .await?;
+                        }
+
+                        Ok(())
+                    },
+                    |result: Result<(), Error>| match result {
+                        Ok(_) => AppSettingsMessage::RegeneratedPreviews.into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            AppSettingsMessage::RegeneratedPreviews => {
+                self.regenerating = false;
+                Command::none()
+            }
+            AppSettingsMessage::ToggleDebugInfo => {
+                self.show_debug_info = !self.show_debug_info;
+                Command::none()
+            }
+            AppSettingsMessage::ClearCache => {
+                globals.get_cache().clear();
+                Command::none()
+            }
+            AppSettingsMessage::Error(err) => {
+                self.error = Some(err.clone());
+                self.regenerating = false;
+                Command::none()
+            }
+        }
+    }
+
+    fn view(&self, globals: &Globals) -> Element<'_, Message, Theme, Renderer> {
+        let preferences = globals.get_preferences();
+
+        let error = match &self.error {
+            Some(error) => Text::new(error.to_string())
+                .style(theme::text::danger)
+                .size(15.0)
+                .into(),
+            None => Space::with_width(Length::Fill).into(),
+        };
+
+        let debug_info = if self.show_debug_info {
+            services::app_settings::debug_info_panel(&globals.get_cache())
+        } else {
+            Space::with_height(Length::Shrink).into()
+        };
+
+        Column::with_children(vec![
+            self.title_element(),
+            Scrollable::new(Row::with_children(vec![
+                Space::with_width(Length::FillPortion(1)).into(),
+                Column::with_children(vec![
+                    services::app_settings::theme_input(preferences),
+                    services::app_settings::canvas_size_input(
+                        &self.width_input,
+                        &self.height_input,
+                    ),
+                    services::app_settings::auto_save_input(preferences),
+                    services::app_settings::save_mode_input(preferences),
+                    services::app_settings::font_size_input(preferences),
+                    services::app_settings::cache_capacity_input(preferences),
+                    services::app_settings::regenerate_previews_input(self.regenerating),
+                    services::app_settings::debug_info_input(self.show_debug_info),
+                    debug_info,
+                    error,
+                ])
+                .spacing(20.0)
+                .width(Length::FillPortion(2))
+                .into(),
+                Space::with_width(Length::FillPortion(1)).into(),
+            ]))
+            .into(),
+        ])
+        .width(Length::Fill)
+        .align_items(Alignment::Center)
+        .spacing(20.0)
+        .into()
+    }
+
+    fn handle_error(&mut self, globals: &mut Globals, error: &Error) -> Command<Message> {
+        self.update(globals, &AppSettingsMessage::Error(error.clone()))
+    }
+
+    fn clear(&self, _globals: &mut Globals) {}
+}
@@ -3,10 +3,13 @@ use crate::scene::{Globals, Message, Scene, SceneMessage};
 use crate::scenes::data::auth::*;
 use crate::scenes::scenes::Scenes;
 use crate::utils::errors::{AuthError, Error};
+use crate::utils::secret;
 use crate::utils::serde::Serialize;
 use crate::utils::theme::Theme;
+use crate::widgets::ModalStack;
 use iced::widget::Column;
 use iced::{Command, Element, Renderer};
+use mongodb::bson::doc;
 use std::any::Any;
 
 use super::services;
@@ -40,6 +43,50 @@ pub enum AuthMessage {
     /// Triggered when the user has been successfully logged in. Holds the user data.
     LoggedIn(User),
 
+    /// Opens the modal where the user asks for a password reset.
+    ForgotPassword,
+
+    /// Triggered when a field in the password reset modals has been updated.
+    PasswordResetFieldUpdate(PasswordResetField),
+
+    /// Sends a password reset token to the given email address, if it belongs to an account.
+    RequestPasswordReset(String),
+
+    /// Sets the password of the account that requested the given password reset token.
+    ResetPassword { token: String, new_password: String },
+
+    /// Triggered when the password has been reset.
+    DonePasswordReset,
+
+    /// Closes the password reset modals.
+    ClosePasswordReset,
+
+    /// Lets the user log in through the given OAuth provider, in the browser.
+    OAuthLogin(OAuthProvider),
+
+    /// Triggered when the user has been authenticated by an OAuth provider. Holds the user data.
+    OAuthLoggedIn(User),
+
+    /// Generates a two-factor authentication secret for the logged in user, and displays it in
+    /// a modal until the user confirms the first code generated from it.
+    EnableTotp,
+
+    /// Triggered when the two-factor authentication code field has been updated.
+    TotpCodeUpdate(String),
+
+    /// Checks the code generated from the new two-factor authentication secret, and enables
+    /// two-factor authentication if it is correct.
+    ConfirmTotp,
+
+    /// Triggered when two-factor authentication has been enabled. Holds the secret.
+    DoneEnableTotp(String),
+
+    /// Checks the two-factor authentication code of the user that is logging in.
+    SubmitTotp,
+
+    /// Closes the two-factor authentication modals.
+    CancelTotp,
+
     /// Used for switching between the registration/login tabs.
     TabSelection(AuthTabIds),
 
@@ -62,6 +109,20 @@ impl SceneMessage for AuthMessage {
             Self::DoneRegistration => String::from("Successful registration"),
             Self::SendLogIn => String::from("Log In attempt"),
             Self::LoggedIn(_) => String::from("Logged in successfully"),
+            Self::ForgotPassword => String::from("Forgot password"),
+            Self::PasswordResetFieldUpdate(_) => String::from("Modified password reset field"),
+            Self::RequestPasswordReset(_) => String::from("Request password reset"),
+            Self::ResetPassword { .. } => String::from("Reset password"),
+            Self::DonePasswordReset => String::from("Password reset successfully"),
+            Self::ClosePasswordReset => String::from("Close password reset"),
+            Self::OAuthLogin(provider) => format!("Log in with {}", provider),
+            Self::OAuthLoggedIn(_) => String::from("Logged in with OAuth"),
+            Self::EnableTotp => String::from("Enable two-factor authentication"),
+            Self::TotpCodeUpdate(_) => String::from("Modified two-factor authentication code"),
+            Self::ConfirmTotp => String::from("Confirm two-factor authentication"),
+            Self::DoneEnableTotp(_) => String::from("Enabled two-factor authentication"),
+            Self::SubmitTotp => String::from("Submit two-factor authentication code"),
+            Self::CancelTotp => String::from("Cancel two-factor authentication"),
             Self::TabSelection(_) => String::from("Select tab"),
             Self::HandleError(_) => String::from("Handle an error"),
         }
@@ -101,19 +162,73 @@ pub struct Auth {
 
     /// Holds possible errors with the user input.
     code_error: Option<AuthError>,
+
+    /// The two-factor authentication secret that is being set up.
+    totp_secret: Option<String>,
+
+    /// The value of the two-factor authentication code field.
+    totp_code: String,
+
+    /// Holds possible errors with the two-factor authentication code.
+    totp_error: Option<AuthError>,
+
+    /// The user that is logging in, while waiting for their two-factor authentication code.
+    pending_user: Option<User>,
+
+    /// The value of the email field of the password reset request.
+    reset_email: String,
+
+    /// The value of the password reset token field.
+    reset_token: String,
+
+    /// The value of the new password field of the password reset.
+    reset_password: String,
+
+    /// Holds possible errors with the password reset.
+    reset_error: Option<AuthError>,
+
+    /// The stack of modals.
+    modals: ModalStack<ModalType>,
 }
 
 /// The options for the authentication page. Holds the initial [tab id](TabIds).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct AuthOptions {
     /// Holds the tab that should be open when the scene activates.
     active_tab: Option<AuthTabIds>,
+
+    /// Tells whether the scene should start setting up two-factor authentication for the
+    /// logged in user.
+    enable_totp: bool,
+
+    /// The password reset token the scene should open the password reset with, if any.
+    reset_token: Option<String>,
 }
 
 impl AuthOptions {
     pub fn new(active_tab: AuthTabIds) -> Self {
         AuthOptions {
             active_tab: Some(active_tab),
+            enable_totp: false,
+            reset_token: None,
+        }
+    }
+
+    /// Options that start setting up two-factor authentication for the logged in user.
+    pub fn enable_totp() -> Self {
+        AuthOptions {
+            active_tab: None,
+            enable_totp: true,
+            reset_token: None,
+        }
+    }
+
+    /// Options that open the password reset with the given token.
+    pub fn reset_password(token: String) -> Self {
+        AuthOptions {
+            active_tab: Some(AuthTabIds::LogIn),
+            enable_totp: false,
+            reset_token: Some(token),
         }
     }
 }
@@ -180,6 +295,91 @@ impl Auth {
         }
     }
 
+    fn enable_totp(&mut self, globals: &mut Globals) -> Command<Message> {
+        if globals.get_user().is_none() {
+            return Command::none();
+        }
+
+        self.totp_secret = Some(User::gen_totp_secret());
+        self.totp_code = String::new();
+        self.totp_error = None;
+        self.modals.toggle_modal(ModalType::TotpSetup);
+
+        Command::none()
+    }
+
+    fn confirm_totp(&mut self, globals: &mut Globals) -> Command<Message> {
+        let (Some(secret), Some(user), Some(db)) = (
+            self.totp_secret.clone(),
+            globals.get_user(),
+            globals.get_db(),
+        ) else {
+            return Command::none();
+        };
+        let user_id = user.get_id();
+
+        let valid = User::gen_totp(&secret, user.get_email())
+            .is_ok_and(|totp| totp.check_current(&self.totp_code).unwrap_or(false));
+        if !valid {
+            return self.update(
+                globals,
+                &AuthMessage::HandleError(Error::AuthError(AuthError::TotpInvalid)),
+            );
+        }
+
+        // The secret is needed to generate the codes it is checked against, so it cannot be
+        // stored hashed like the password, and is encrypted instead.
+        Command::perform(
+            async move {
+                let encrypted = secret::encrypt(&secret)?;
+
+                database::settings::update_user(
+                    &db,
+                    user_id,
+                    doc! {
+                        "totp_secret": encrypted
+                    },
+                )
+                .await
+                .map(|()| secret)
+            },
+            |result| match result {
+                Ok(secret) => AuthMessage::DoneEnableTotp(secret).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    fn submit_totp(&mut self, globals: &mut Globals) -> Command<Message> {
+        let Some(user) = self.pending_user.clone() else {
+            return Command::none();
+        };
+
+        if !user.check_totp(&self.totp_code) {
+            return self.update(
+                globals,
+                &AuthMessage::HandleError(Error::AuthError(AuthError::TotpInvalid)),
+            );
+        }
+
+        self.pending_user = None;
+        self.modals.clear();
+
+        self.grant_session(user, globals)
+    }
+
+    /// Logs the user in and gives them a new authentication token.
+    fn grant_session(&mut self, user: User, globals: &mut Globals) -> Command<Message> {
+        let db = globals.get_db().unwrap();
+        let id = user.get_id();
+        globals.set_user(Some(user));
+
+        Command::perform(
+            async move { database::auth::update_user_token(&db, id).await },
+            |_| Message::ChangeScene(Scenes::Main(None)),
+        )
+    }
+
     pub fn logged_in(&mut self, user: &User, globals: &mut Globals) -> Command<Message> {
         if !user.test_password(self.log_in_form.get_password()) {
             return self.update(
@@ -201,14 +401,119 @@ impl Auth {
             return self.update(globals, &AuthMessage::ResetRegisterCode);
         }
 
-        globals.set_user(Some(user.clone()));
-        let db = globals.get_db().unwrap();
-        let id = user.get_id();
+        self.start_session(user, globals)
+    }
 
-        return Command::perform(
-            async move { database::auth::update_user_token(&db, id).await },
-            |_| Message::ChangeScene(Scenes::Main(None)),
-        );
+    /// Logs in a user whose identity has been checked, after asking for their two-factor
+    /// authentication code if they have it enabled.
+    fn start_session(&mut self, user: &User, globals: &mut Globals) -> Command<Message> {
+        if user.has_totp() {
+            self.pending_user = Some(user.clone());
+
+            return self.update(
+                globals,
+                &AuthMessage::HandleError(Error::AuthError(AuthError::TotpRequired)),
+            );
+        }
+
+        self.grant_session(user.clone(), globals)
+    }
+
+    fn request_password_reset(
+        &mut self,
+        email: &String,
+        globals: &mut Globals,
+    ) -> Command<Message> {
+        if !User::check_email(email) {
+            self.reset_error = Some(AuthError::RegisterBadCredentials {
+                email: true,
+                username: false,
+                password: false,
+            });
+
+            return Command::none();
+        }
+
+        let Some(db) = globals.get_db() else {
+            return Command::none();
+        };
+        let email = email.clone();
+        let (token, token_hash) = User::gen_password_reset_token();
+
+        // The token form is shown whether or not the email belongs to an account, so that the
+        // accounts cannot be discovered through it.
+        self.reset_token = String::new();
+        self.reset_password = String::new();
+        self.reset_error = None;
+        self.modals.clear();
+        self.modals.toggle_modal(ModalType::ResetPassword);
+
+        Command::perform(
+            async move {
+                database::auth::create_password_reset(&db, email.clone(), token_hash)
+                    .await
+                    .map(|exists| exists.then(|| User::gen_password_reset_email(&email, &token)))
+            },
+            |result| match result {
+                Ok(Some(mail)) => Message::SendSmtpMail(mail),
+                Ok(None) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    fn reset_password(
+        &mut self,
+        token: &String,
+        new_password: &String,
+        globals: &mut Globals,
+    ) -> Command<Message> {
+        if !User::check_password(new_password) {
+            self.reset_error = Some(AuthError::RegisterBadCredentials {
+                email: false,
+                username: false,
+                password: true,
+            });
+
+            return Command::none();
+        }
+
+        let Some(token_hash) = User::hash_password_reset_token(token) else {
+            self.reset_error = Some(AuthError::PasswordResetBadToken);
+
+            return Command::none();
+        };
+        let Some(db) = globals.get_db() else {
+            return Command::none();
+        };
+        let password_hash = pwhash::bcrypt::hash(new_password).unwrap();
+        self.reset_error = None;
+
+        Command::perform(
+            async move { database::auth::reset_password(&db, token_hash, password_hash).await },
+            |result| match result {
+                Ok(()) => AuthMessage::DonePasswordReset.into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    fn oauth_login(&mut self, provider: OAuthProvider, globals: &mut Globals) -> Command<Message> {
+        let Some(db) = globals.get_db() else {
+            return Command::none();
+        };
+
+        Command::perform(
+            async move {
+                let profile = services::auth::oauth_login(provider).await?;
+
+                database::auth::oauth_login(&db, profile).await
+            },
+            |result| match result {
+                Ok(user) => AuthMessage::OAuthLoggedIn(user).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
     }
 }
 
@@ -217,7 +522,7 @@ impl Scene for Auth {
 
     type Options = AuthOptions;
 
-    fn new(options: Option<Self::Options>, _: &mut Globals) -> (Self, Command<Message>)
+    fn new(options: Option<Self::Options>, globals: &mut Globals) -> (Self, Command<Message>)
     where
         Self: Sized,
     {
@@ -227,12 +532,28 @@ impl Scene for Auth {
             log_in_form: LogInForm::default(),
             register_code: None,
             code_error: None,
+            totp_secret: None,
+            totp_code: String::new(),
+            totp_error: None,
+            pending_user: None,
+            reset_email: String::new(),
+            reset_token: String::new(),
+            reset_password: String::new(),
+            reset_error: None,
+            modals: ModalStack::new(),
         };
+
+        let mut command = Command::none();
         if let Some(options) = options {
+            let enable_totp = options.enable_totp;
             auth.apply_options(options);
+
+            if enable_totp {
+                command = auth.update(globals, &AuthMessage::EnableTotp);
+            }
         }
 
-        (auth, Command::none())
+        (auth, command)
     }
 
     fn get_title(&self) -> String {
@@ -243,6 +564,14 @@ impl Scene for Auth {
         if let Some(active_tab) = options.active_tab {
             self.active_tab = active_tab;
         }
+
+        if let Some(token) = options.reset_token {
+            self.reset_token = token;
+            self.reset_password = String::new();
+            self.reset_error = None;
+            self.modals.clear();
+            self.modals.toggle_modal(ModalType::ResetPassword);
+        }
     }
 
     fn update(&mut self, globals: &mut Globals, message: &Self::Message) -> Command<Message> {
@@ -320,6 +649,84 @@ impl Scene for Auth {
             AuthMessage::LoggedIn(user) => {
                 return self.logged_in(user, globals);
             }
+            AuthMessage::ForgotPassword => {
+                self.reset_email = self.log_in_form.get_email().clone();
+                self.reset_error = None;
+                self.modals.toggle_modal(ModalType::ForgotPassword);
+            }
+            AuthMessage::PasswordResetFieldUpdate(field) => match field {
+                PasswordResetField::Email(email) => {
+                    self.reset_email = email.clone();
+                }
+                PasswordResetField::Token(token) => {
+                    self.reset_token = token.clone();
+                }
+                PasswordResetField::Password(password) => {
+                    self.reset_password = password.clone();
+                }
+            },
+            AuthMessage::RequestPasswordReset(email) => {
+                return self.request_password_reset(email, globals);
+            }
+            AuthMessage::ResetPassword {
+                token,
+                new_password,
+            } => {
+                return self.reset_password(token, new_password, globals);
+            }
+            AuthMessage::DonePasswordReset => {
+                self.modals.clear();
+                self.reset_token = String::new();
+                self.reset_password = String::new();
+                self.active_tab = AuthTabIds::LogIn;
+
+                globals.push_toast("Your password has been reset. You can now log in.");
+            }
+            AuthMessage::ClosePasswordReset => {
+                self.modals.clear();
+                self.reset_token = String::new();
+                self.reset_password = String::new();
+                self.reset_error = None;
+            }
+            AuthMessage::OAuthLogin(provider) => {
+                return self.oauth_login(*provider, globals);
+            }
+            AuthMessage::OAuthLoggedIn(user) => {
+                return self.start_session(user, globals);
+            }
+            AuthMessage::EnableTotp => {
+                return self.enable_totp(globals);
+            }
+            AuthMessage::TotpCodeUpdate(code) => {
+                self.totp_code = code.clone();
+            }
+            AuthMessage::ConfirmTotp => {
+                return self.confirm_totp(globals);
+            }
+            AuthMessage::DoneEnableTotp(secret) => {
+                if let Some(user) = globals.get_user_mut() {
+                    user.set_totp_secret(Some(secret.clone()));
+                }
+                self.totp_secret = None;
+                self.modals.clear();
+
+                return Command::perform(async {}, |_| {
+                    Message::ChangeScene(Scenes::Settings(None))
+                });
+            }
+            AuthMessage::SubmitTotp => {
+                return self.submit_totp(globals);
+            }
+            AuthMessage::CancelTotp => {
+                self.pending_user = None;
+                self.modals.clear();
+
+                if self.totp_secret.take().is_some() {
+                    return Command::perform(async {}, |_| {
+                        Message::ChangeScene(Scenes::Settings(None))
+                    });
+                }
+            }
             AuthMessage::TabSelection(tab_id) => {
                 self.active_tab = *tab_id;
             }
@@ -329,7 +736,11 @@ impl Scene for Auth {
                         AuthError::RegisterBadCode => {
                             self.code_error = Some(error.clone());
                         }
-                        AuthError::LogInUserDoesntExist => {
+                        AuthError::LogInUserDoesntExist | AuthError::UserBanned => {
+                            self.log_in_form.set_error(error.clone());
+                        }
+                        AuthError::OAuthPasswordRequired => {
+                            self.active_tab = AuthTabIds::LogIn;
                             self.log_in_form.set_error(error.clone());
                         }
                         AuthError::RegisterBadCredentials { .. } => {
@@ -338,6 +749,18 @@ impl Scene for Auth {
                         AuthError::RegisterUserAlreadyExists => {
                             self.register_form.set_error(error.clone());
                         }
+                        AuthError::TotpRequired => {
+                            self.totp_code = String::new();
+                            self.totp_error = None;
+                            self.modals.toggle_modal(ModalType::TotpChallenge);
+                        }
+                        AuthError::PasswordResetBadToken => {
+                            self.reset_error = Some(error.clone());
+                        }
+                        AuthError::TotpInvalid => {
+                            self.totp_code = String::new();
+                            self.totp_error = Some(error.clone());
+                        }
                         _ => {}
                     }
                 }
@@ -359,7 +782,32 @@ impl Scene for Auth {
 
         let tabs = services::auth::tabs(register_tab, log_in_tab, self.active_tab);
 
-        Column::with_children(vec![self.title_element(), tabs]).into()
+        let underlay = Column::with_children(vec![self.title_element(), tabs]);
+
+        let modal_generator = |modal: ModalType| match modal {
+            ModalType::TotpSetup => services::auth::totp_setup(
+                self.totp_secret.clone().unwrap_or_default(),
+                globals
+                    .get_user()
+                    .map(|user| user.get_email().clone())
+                    .unwrap_or_default(),
+                &self.totp_code,
+                &self.totp_error,
+            ),
+            ModalType::TotpChallenge => {
+                services::auth::totp_challenge(&self.totp_code, &self.totp_error)
+            }
+            ModalType::ForgotPassword => {
+                services::auth::forgot_password(&self.reset_email, &self.reset_error)
+            }
+            ModalType::ResetPassword => services::auth::reset_password(
+                &self.reset_token,
+                &self.reset_password,
+                &self.reset_error,
+            ),
+        };
+
+        self.modals.get_modal(underlay, modal_generator)
     }
 
     fn handle_error(&mut self, globals: &mut Globals, error: &Error) -> Command<Message> {
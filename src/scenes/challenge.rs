@@ -0,0 +1,198 @@
+use crate::database;
+use crate::scene::{Globals, Message, Scene, SceneMessage};
+use crate::scenes::data::challenge::{Challenge, ChallengeTabs};
+use crate::scenes::data::posts::Post;
+use crate::utils::errors::Error;
+use crate::utils::theme::{self, Theme};
+use crate::widgets::WaitPanel;
+use iced::alignment::Horizontal;
+use iced::widget::{Column, Space, Text};
+use iced::{Alignment, Command, Element, Length, Renderer};
+use std::any::Any;
+
+use super::services;
+
+/// The [Scene] of the drawing challenge that is currently active.
+pub struct Challenges {
+    /// The active challenge. Is None if it hasn't been loaded yet.
+    challenge: Option<Option<Challenge>>,
+
+    /// The entries of the challenge, ranked by their average rating.
+    leaderboard: Option<Vec<Post>>,
+
+    /// The currently selected tab.
+    active_tab: ChallengeTabs,
+
+    /// The last error that loading the challenge has created.
+    error: Option<Error>,
+}
+
+/// This scene has no options.
+#[derive(Debug, Clone)]
+pub struct ChallengeOptions {}
+
+/// The possible [messages](SceneMessage) this [Scene] can trigger.
+#[derive(Clone)]
+pub enum ChallengeMessage {
+    /// Triggered when the active challenge has been loaded.
+    LoadedChallenge(Option<Challenge>),
+
+    /// Triggered when the entries of the challenge have been loaded.
+    LoadedLeaderboard(Vec<Post>),
+
+    /// Changes the selected tab.
+    SelectTab(ChallengeTabs),
+
+    /// Handles errors.
+    Error(Error),
+}
+
+impl SceneMessage for ChallengeMessage {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> String {
+        match self {
+            Self::LoadedChallenge(_) => String::from("Loaded challenge"),
+            Self::LoadedLeaderboard(_) => String::from("Loaded leaderboard"),
+            Self::SelectTab(_) => String::from("Select tab"),
+            Self::Error(_) => String::from("Error"),
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn SceneMessage + 'static> {
+        Box::new((*self).clone())
+    }
+}
+
+impl Into<Message> for ChallengeMessage {
+    fn into(self) -> Message {
+        Message::DoAction(Box::new(self))
+    }
+}
+
+impl Challenges {
+    /// Loads the entries of the given challenge.
+    fn load_leaderboard(challenge: &Challenge, globals: &Globals) -> Command<Message> {
+        let db = globals.get_db().unwrap();
+        let challenge_id = challenge.get_id();
+
+        Command::perform(
+            async move { database::challenge::get_leaderboard(&db, challenge_id).await },
+            |result| match result {
+                Ok(entries) => ChallengeMessage::LoadedLeaderboard(entries).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+}
+
+impl Scene for Challenges {
+    type Message = ChallengeMessage;
+    type Options = ChallengeOptions;
+
+    fn new(options: Option<Self::Options>, globals: &mut Globals) -> (Self, Command<Message>)
+    where
+        Self: Sized,
+    {
+        let mut challenges = Challenges {
+            challenge: None,
+            leaderboard: None,
+            active_tab: ChallengeTabs::Prompt,
+            error: None,
+        };
+
+        if let Some(options) = options {
+            challenges.apply_options(options);
+        }
+
+        let db = globals.get_db().unwrap();
+
+        (
+            challenges,
+            Command::perform(
+                async move { database::challenge::get_active_challenge(&db).await },
+                |result| match result {
+                    Ok(challenge) => ChallengeMessage::LoadedChallenge(challenge).into(),
+                    Err(err) => Message::Error(err),
+                },
+            ),
+        )
+    }
+
+    fn get_title(&self) -> String {
+        String::from("Today's Challenge")
+    }
+
+    fn apply_options(&mut self, _options: Self::Options) {}
+
+    fn update(&mut self, globals: &mut Globals, message: &Self::Message) -> Command<Message> {
+        match message {
+            ChallengeMessage::LoadedChallenge(challenge) => {
+                self.challenge = Some(challenge.clone());
+
+                match challenge {
+                    Some(challenge) => Self::load_leaderboard(challenge, globals),
+                    None => Command::none(),
+                }
+            }
+            ChallengeMessage::LoadedLeaderboard(entries) => {
+                self.leaderboard = Some(entries.clone());
+
+                Command::none()
+            }
+            ChallengeMessage::SelectTab(tab) => {
+                self.active_tab = *tab;
+
+                match (tab, &self.challenge) {
+                    (ChallengeTabs::Leaderboard, Some(Some(challenge))) => {
+                        Self::load_leaderboard(challenge, globals)
+                    }
+                    _ => Command::none(),
+                }
+            }
+            ChallengeMessage::Error(err) => {
+                self.error = Some(err.clone());
+
+                Command::none()
+            }
+        }
+    }
+
+    fn view(&self, _globals: &Globals) -> Element<'_, Message, Theme, Renderer> {
+        let error = match &self.error {
+            Some(error) => Text::new(error.to_string())
+                .style(theme::text::danger)
+                .size(15.0)
+                .into(),
+            None => Space::with_width(Length::Fill).into(),
+        };
+
+        let content = match &self.challenge {
+            None => WaitPanel::new("Loading...").into(),
+            Some(None) => Text::new("There is no active challenge right now.")
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+                .into(),
+            Some(Some(challenge)) => services::challenge::challenge_tabs(
+                services::challenge::prompt_tab(challenge),
+                services::challenge::leaderboard_tab(&self.leaderboard),
+                self.active_tab,
+            ),
+        };
+
+        Column::with_children(vec![self.title_element(), error, content])
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_items(Alignment::Center)
+            .spacing(20.0)
+            .into()
+    }
+
+    fn handle_error(&mut self, globals: &mut Globals, error: &Error) -> Command<Message> {
+        self.update(globals, &ChallengeMessage::Error(error.clone()))
+    }
+
+    fn clear(&self, _globals: &mut Globals) {}
+}
@@ -0,0 +1,205 @@
+use crate::scenes::data::auth::User;
+use crate::scenes::data::posts::Post;
+use crate::utils::serde::Deserialize;
+use mongodb::bson::{Bson, DateTime, Document, Uuid, UuidRepresentation};
+use std::fmt::{Display, Formatter};
+
+/// A report a user has submitted regarding a post.
+#[derive(Clone)]
+pub struct Report {
+    /// The id of the report.
+    id: Uuid,
+
+    /// The reported post, along with its author.
+    post: Post,
+
+    /// The reason the post was reported for.
+    description: String,
+
+    /// The moment the report was submitted.
+    created_at: DateTime,
+}
+
+impl Report {
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_post(&self) -> &Post {
+        &self.post
+    }
+
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+
+    pub fn get_created_at(&self) -> DateTime {
+        self.created_at
+    }
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Report {
+            id: Uuid::from_bytes([0; 16]),
+            post: Post::default(),
+            description: String::new(),
+            created_at: DateTime::MIN,
+        }
+    }
+}
+
+impl Deserialize<Document> for Report {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut report = Report::default();
+
+        if let Some(Bson::Binary(bin)) = document.get("id") {
+            if let Ok(uuid) = bin.to_uuid_with_representation(UuidRepresentation::Standard) {
+                report.id = uuid;
+            }
+        }
+        if let Ok(description) = document.get_str("description") {
+            report.description = description.into();
+        }
+        if let Ok(created_at) = document.get_datetime("created_at") {
+            report.created_at = *created_at;
+        }
+        report.post = Post::deserialize(document);
+
+        report
+    }
+}
+
+/// A write operation a user has performed, as recorded in the audit log.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    /// The user that performed the operation.
+    user: User,
+
+    /// The name of the operation.
+    action: String,
+
+    /// The id of the object the operation was performed on, if there is one.
+    target_id: Option<Uuid>,
+
+    /// The moment the operation was performed.
+    created_at: DateTime,
+}
+
+impl AuditEntry {
+    pub fn get_user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn get_action(&self) -> &String {
+        &self.action
+    }
+
+    pub fn get_target_id(&self) -> Option<Uuid> {
+        self.target_id
+    }
+
+    pub fn get_created_at(&self) -> DateTime {
+        self.created_at
+    }
+}
+
+impl Default for AuditEntry {
+    fn default() -> Self {
+        AuditEntry {
+            user: User::default(),
+            action: String::new(),
+            target_id: None,
+            created_at: DateTime::MIN,
+        }
+    }
+}
+
+impl Deserialize<Document> for AuditEntry {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut entry = AuditEntry::default();
+
+        if let Some(Bson::Document(user)) = document.get("user") {
+            entry.user = User::deserialize(user);
+        }
+        if let Ok(action) = document.get_str("action") {
+            entry.action = action.into();
+        }
+        if let Some(Bson::Binary(bin)) = document.get("target_id") {
+            entry.target_id = bin
+                .to_uuid_with_representation(UuidRepresentation::Standard)
+                .ok();
+        }
+        if let Ok(created_at) = document.get_datetime("created_at") {
+            entry.created_at = *created_at;
+        }
+
+        entry
+    }
+}
+
+/// The action the entries of the audit log are filtered by. Is None if all actions are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ActionFilter(pub Option<&'static str>);
+
+impl ActionFilter {
+    /// The filters that can be selected.
+    pub const OPTIONS: [ActionFilter; 8] = [
+        ActionFilter(None),
+        ActionFilter(Some("create_drawing")),
+        ActionFilter(Some("update_drawing")),
+        ActionFilter(Some("delete_drawing")),
+        ActionFilter(Some("create_comment")),
+        ActionFilter(Some("delete_post")),
+        ActionFilter(Some("update_user")),
+        ActionFilter(Some("delete_account")),
+    ];
+}
+
+impl Display for ActionFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => write!(f, "All actions"),
+            Some(action) => write!(f, "{}", action),
+        }
+    }
+}
+
+/// The statistics of the whole site.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Statistics {
+    /// The number of posts.
+    pub posts: u64,
+
+    /// The number of registered users.
+    pub users: u64,
+
+    /// The number of drawing sessions that have been active in the last few minutes.
+    pub active_sessions: u64,
+}
+
+/// The tabs of the admin [Scene](crate::scene::Scene).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum AdminTabs {
+    /// Lists the users, allowing them to be banned.
+    #[default]
+    Users,
+
+    /// Lists the reported posts.
+    Reports,
+
+    /// Lists the challenges, allowing new ones to be created.
+    Challenges,
+
+    /// Shows the statistics of the site.
+    Statistics,
+
+    /// Lists the latest write operations of the users.
+    AuditLog,
+}
@@ -0,0 +1,218 @@
+use crate::scenes::data::drawing::SaveMode;
+use crate::utils::cache::DEFAULT_MAX_ENTRIES;
+use json::object::Object;
+use json::JsonValue;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// The font sizes the user can choose from.
+pub const FONT_SIZES: [u16; 5] = [12, 14, 16, 18, 20];
+
+/// The maximum numbers of cached images the user can choose from.
+pub const CACHE_CAPACITIES: [usize; 4] = [100, DEFAULT_MAX_ENTRIES, 500, 1000];
+
+/// The color scheme of the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeMode {
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    /// All the available color schemes.
+    pub const ALL: [ThemeMode; 2] = [ThemeMode::Dark, ThemeMode::Light];
+}
+
+impl Display for ThemeMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ThemeMode::Dark => "Dark",
+                ThemeMode::Light => "Light",
+            }
+        )
+    }
+}
+
+/// The global preferences of the application, stored locally.
+#[derive(Debug, Clone)]
+pub struct Preferences {
+    /// The color scheme of the application.
+    theme: ThemeMode,
+
+    /// The size of the drawing area of new drawings.
+    canvas_size: (u32, u32),
+
+    /// The time between two automatic saves of a drawing. Is None if auto-saving is disabled.
+    auto_save_interval: Option<Duration>,
+
+    /// The save mode new drawings are created with.
+    save_mode: SaveMode,
+
+    /// The default size of the text.
+    font_size: u16,
+
+    /// The velocity score above which a trending post is marked as hot.
+    hot_threshold: f64,
+
+    /// The maximum number of images kept in the cache.
+    cache_capacity: usize,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Preferences {
+            theme: ThemeMode::Dark,
+            canvas_size: (800, 600),
+            auto_save_interval: None,
+            save_mode: SaveMode::Online,
+            font_size: 16,
+            hot_threshold: 1.0,
+            cache_capacity: DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+impl Preferences {
+    /// Reads the preferences from their json form. Missing or invalid fields keep their
+    /// default values.
+    pub fn from_json(json: &JsonValue) -> Self {
+        let mut preferences = Preferences::default();
+
+        match json["theme"].as_str() {
+            Some("dark") => preferences.theme = ThemeMode::Dark,
+            Some("light") => preferences.theme = ThemeMode::Light,
+            _ => {}
+        }
+        if let (Some(width), Some(height)) = (
+            json["canvas_width"].as_u32(),
+            json["canvas_height"].as_u32(),
+        ) {
+            if width > 0 && height > 0 {
+                preferences.canvas_size = (width, height);
+            }
+        }
+        preferences.auto_save_interval =
+            json["auto_save_interval"].as_u64().map(Duration::from_secs);
+        match json["save_mode"].as_str() {
+            Some("offline") => preferences.save_mode = SaveMode::Offline,
+            Some("online") => preferences.save_mode = SaveMode::Online,
+            _ => {}
+        }
+        if let Some(font_size) = json["font_size"].as_u16() {
+            preferences.font_size = font_size;
+        }
+        if let Some(hot_threshold) = json["hot_threshold"].as_f64() {
+            if hot_threshold >= 0.0 {
+                preferences.hot_threshold = hot_threshold;
+            }
+        }
+        if let Some(cache_capacity) = json["cache_capacity"].as_usize() {
+            if cache_capacity > 0 {
+                preferences.cache_capacity = cache_capacity;
+            }
+        }
+
+        preferences
+    }
+
+    /// Returns the preferences in json form.
+    pub fn to_json(&self) -> JsonValue {
+        let mut json = Object::new();
+        json.insert(
+            "theme",
+            JsonValue::String(String::from(match self.theme {
+                ThemeMode::Dark => "dark",
+                ThemeMode::Light => "light",
+            })),
+        );
+        json.insert("canvas_width", JsonValue::Number(self.canvas_size.0.into()));
+        json.insert(
+            "canvas_height",
+            JsonValue::Number(self.canvas_size.1.into()),
+        );
+        json.insert(
+            "auto_save_interval",
+            match self.auto_save_interval {
+                Some(interval) => JsonValue::Number(interval.as_secs().into()),
+                None => JsonValue::Null,
+            },
+        );
+        json.insert(
+            "save_mode",
+            JsonValue::String(String::from(match self.save_mode {
+                SaveMode::Offline => "offline",
+                SaveMode::Online => "online",
+            })),
+        );
+        json.insert("font_size", JsonValue::Number(self.font_size.into()));
+        json.insert(
+            "hot_threshold",
+            JsonValue::Number(self.hot_threshold.into()),
+        );
+        json.insert(
+            "cache_capacity",
+            JsonValue::Number(self.cache_capacity.into()),
+        );
+
+        JsonValue::Object(json)
+    }
+
+    pub fn get_theme(&self) -> ThemeMode {
+        self.theme
+    }
+
+    pub fn get_canvas_size(&self) -> (u32, u32) {
+        self.canvas_size
+    }
+
+    pub fn get_auto_save_interval(&self) -> Option<Duration> {
+        self.auto_save_interval
+    }
+
+    pub fn get_save_mode(&self) -> SaveMode {
+        self.save_mode
+    }
+
+    pub fn get_font_size(&self) -> u16 {
+        self.font_size
+    }
+
+    pub fn get_hot_threshold(&self) -> f64 {
+        self.hot_threshold
+    }
+
+    pub fn get_cache_capacity(&self) -> usize {
+        self.cache_capacity
+    }
+
+    pub fn set_theme(&mut self, theme: ThemeMode) {
+        self.theme = theme;
+    }
+
+    pub fn set_canvas_size(&mut self, canvas_size: (u32, u32)) {
+        self.canvas_size = canvas_size;
+    }
+
+    pub fn set_auto_save_interval(&mut self, auto_save_interval: Option<Duration>) {
+        self.auto_save_interval = auto_save_interval;
+    }
+
+    pub fn set_save_mode(&mut self, save_mode: SaveMode) {
+        self.save_mode = save_mode;
+    }
+
+    pub fn set_font_size(&mut self, font_size: u16) {
+        self.font_size = font_size;
+    }
+
+    pub fn set_hot_threshold(&mut self, hot_threshold: f64) {
+        self.hot_threshold = hot_threshold;
+    }
+
+    pub fn set_cache_capacity(&mut self, cache_capacity: usize) {
+        self.cache_capacity = cache_capacity;
+    }
+}
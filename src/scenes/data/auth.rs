@@ -1,6 +1,8 @@
 use crate::config;
+use crate::debug_message;
 use crate::utils::errors::AuthError;
 use crate::utils::errors::Error;
+use crate::utils::secret;
 use crate::utils::serde::{Deserialize, Serialize};
 use lettre::message::MultiPart;
 use lettre::Message;
@@ -9,6 +11,26 @@ use mongodb::bson::{doc, Binary, Bson, DateTime, Document, Uuid, UuidRepresentat
 use rand::{random, Rng};
 use regex::Regex;
 use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+/// The modals that can be displayed on the [Auth](crate::scenes::auth::Auth)
+/// [scene](crate::scene::Scene).
+#[derive(Clone, Eq, PartialEq)]
+pub enum ModalType {
+    /// This modal displays the QR code of a new two-factor authentication secret, and asks for
+    /// the first code generated from it.
+    TotpSetup,
+
+    /// This modal asks for a two-factor authentication code before logging the user in.
+    TotpChallenge,
+
+    /// This modal asks for the email of the account whose password was forgotten.
+    ForgotPassword,
+
+    /// This modal asks for a password reset token and the new password.
+    ResetPassword,
+}
 
 /// User account registration fields.
 #[derive(Clone)]
@@ -19,6 +41,14 @@ pub enum RegisterField {
     Code(String),
 }
 
+/// Password reset fields.
+#[derive(Clone)]
+pub enum PasswordResetField {
+    Email(String),
+    Token(String),
+    Password(String),
+}
+
 /// User account authentication fields.
 #[derive(Clone)]
 pub enum LogInField {
@@ -78,6 +108,12 @@ pub struct User {
 
     /// Tells whether the user has a profile picture set.
     profile_picture: bool,
+
+    /// The base32-encoded two-factor authentication secret, if the [User] has it enabled.
+    totp_secret: Option<String>,
+
+    /// Tells whether the [User] has been banned by an admin.
+    is_banned: bool,
 }
 
 impl User {
@@ -105,6 +141,64 @@ impl User {
         &self.role
     }
 
+    /// Tells whether the [user](User) has been banned.
+    pub fn is_banned(&self) -> bool {
+        self.is_banned
+    }
+
+    /// Marks the [user](User) as banned or not.
+    pub fn set_banned(&mut self, is_banned: bool) {
+        self.is_banned = is_banned;
+    }
+
+    /// Tells whether the [user](User) has two-factor authentication enabled.
+    pub fn has_totp(&self) -> bool {
+        self.totp_secret.is_some()
+    }
+
+    /// Sets the two-factor authentication secret of the [user](User).
+    pub fn set_totp_secret(&mut self, totp_secret: Option<String>) {
+        self.totp_secret = totp_secret;
+    }
+
+    /// Checks whether the given code is the current two-factor authentication code of the
+    /// [user](User).
+    pub fn check_totp(&self, code: &String) -> bool {
+        self.totp_secret.as_ref().is_some_and(|secret| {
+            User::gen_totp(secret, &self.email)
+                .is_ok_and(|totp| totp.check_current(code).unwrap_or(false))
+        })
+    }
+
+    /// Generates a new base32-encoded two-factor authentication secret.
+    pub fn gen_totp_secret() -> String {
+        Secret::generate_secret().to_encoded().to_string()
+    }
+
+    /// Builds the two-factor authentication code generator of the account with the given email
+    /// from its base32-encoded secret.
+    pub fn gen_totp(secret: &String, email: &String) -> Result<TOTP, Error> {
+        let secret = Secret::Encoded(secret.clone())
+            .to_bytes()
+            .map_err(|err| debug_message!("{:?}", err).into())?;
+
+        TOTP::new(
+            Algorithm::SHA1,
+            6,
+            1,
+            30,
+            secret,
+            Some(String::from("Chartsy")),
+            email.clone(),
+        )
+        .map_err(|err| debug_message!("{}", err).into())
+    }
+
+    /// Sets the email of the [user](User).
+    pub fn set_email(&mut self, email: impl Into<String>) {
+        self.email = email.into();
+    }
+
     /// Sets the username of the [user](User).
     pub fn set_username(&mut self, username: impl Into<String>) {
         self.username = username.into();
@@ -115,6 +209,29 @@ impl User {
         self.user_tag = user_tag.into();
     }
 
+    /// Generates the email that verifies the new address of the [user](User) with the given code.
+    pub fn gen_email_change_email(&self, email: &String, code: &String) -> Message {
+        Message::builder()
+            .from(
+                format!("Chartsy <{}>", config::email_address())
+                    .parse()
+                    .unwrap(),
+            )
+            .to(format!("{} <{}>", self.username, email).parse().unwrap())
+            .subject("Email change for Chartsy account")
+            .multipart(MultiPart::alternative_plain_html(
+                String::from(format!(
+                    "Use the following code to confirm your new email address:\n{}",
+                    code
+                )),
+                String::from(format!(
+                    "<p>Use the following code to confirm your new email address:</p><h1>{}</h1>",
+                    code
+                )),
+            ))
+            .unwrap()
+    }
+
     /// Tests whether the given password is the same as the [users](User).
     pub fn test_password(&self, password: &String) -> bool {
         pwhash::bcrypt::verify(password, &*self.password_hash)
@@ -144,6 +261,64 @@ impl User {
         )
     }
 
+    /// Generates a random password reset token. Returns the token as a hexadecimal string,
+    /// along with its hash.
+    pub fn gen_password_reset_token() -> (String, Binary) {
+        let (code, hash) = User::gen_auth_token();
+
+        (
+            code.iter().map(|byte| format!("{:02x}", byte)).collect(),
+            hash,
+        )
+    }
+
+    /// Returns the hash of a password reset token given as a hexadecimal string, or None if the
+    /// string is not a valid token.
+    pub fn hash_password_reset_token(token: &str) -> Option<Binary> {
+        let token = token.trim();
+        if token.len() != 64 || !token.is_ascii() {
+            return None;
+        }
+
+        let code = (0..32)
+            .map(|index| u8::from_str_radix(&token[2 * index..2 * index + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+
+        let mut sha = Sha256::new();
+        Digest::update(&mut sha, code);
+        let hash = sha.finalize();
+
+        Some(Binary {
+            bytes: Vec::from(hash.iter().as_slice()),
+            subtype: BinarySubtype::Generic,
+        })
+    }
+
+    /// Generates the email that sends the given password reset token to the given address.
+    pub fn gen_password_reset_email(email: &String, token: &String) -> Message {
+        let link = format!("{}{}", PASSWORD_RESET_LINK_PREFIX, token);
+
+        Message::builder()
+            .from(
+                format!("Chartsy <{}>", config::email_address())
+                    .parse()
+                    .unwrap(),
+            )
+            .to(email.parse().unwrap())
+            .subject("Password reset for Chartsy account")
+            .multipart(MultiPart::alternative_plain_html(
+                String::from(format!(
+                    "Open the following link to reset your password:\n{}\nOr paste this token in the application:\n{}\nThe link expires in 30 minutes.",
+                    link, token
+                )),
+                String::from(format!(
+                    "<p>Open the following link to reset your password:</p><p><a href=\"{}\">{}</a></p><p>Or paste this token in the application:</p><h3>{}</h3><p>The link expires in 30 minutes.</p>",
+                    link, link, token
+                )),
+            ))
+            .unwrap()
+    }
+
     /// Tells whether this users email address has been validated.
     pub fn is_validated(&self) -> bool {
         self.validated
@@ -257,6 +432,12 @@ impl Deserialize<Document> for User {
         if let Ok(profile_picture) = document.get_bool("profile_picture") {
             user.profile_picture = profile_picture;
         }
+        if let Ok(totp_secret) = document.get_str("totp_secret") {
+            user.totp_secret = secret::decrypt(totp_secret);
+        }
+        if let Ok(is_banned) = document.get_bool("is_banned") {
+            user.is_banned = is_banned;
+        }
 
         user
     }
@@ -428,3 +609,125 @@ pub enum AuthTabIds {
     Register,
     LogIn,
 }
+
+/// The providers a user can log in with instead of their email and password.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OAuthProvider {
+    Google,
+    GitHub,
+}
+
+impl OAuthProvider {
+    /// Returns the name the provider is stored under in the database.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "google",
+            OAuthProvider::GitHub => "github",
+        }
+    }
+
+    /// Returns the page where the user authorizes the application.
+    pub fn get_authorization_url(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "https://accounts.google.com/o/oauth2/v2/auth",
+            OAuthProvider::GitHub => "https://github.com/login/oauth/authorize",
+        }
+    }
+
+    /// Returns the endpoint that exchanges an authorization code for an access token.
+    pub fn get_token_url(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "https://oauth2.googleapis.com/token",
+            OAuthProvider::GitHub => "https://github.com/login/oauth/access_token",
+        }
+    }
+
+    /// Returns the endpoint that returns the profile of the user.
+    pub fn get_profile_url(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "https://openidconnect.googleapis.com/v1/userinfo",
+            OAuthProvider::GitHub => "https://api.github.com/user",
+        }
+    }
+
+    /// Returns the permissions requested from the user.
+    pub fn get_scope(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "openid email profile",
+            OAuthProvider::GitHub => "read:user user:email",
+        }
+    }
+
+    /// Returns the id of the application at the provider.
+    pub fn get_client_id(&self) -> String {
+        match self {
+            OAuthProvider::Google => config::google_client_id(),
+            OAuthProvider::GitHub => config::github_client_id(),
+        }
+        .into()
+    }
+
+    /// Returns the secret of the application at the provider.
+    pub fn get_client_secret(&self) -> String {
+        match self {
+            OAuthProvider::Google => config::google_client_secret(),
+            OAuthProvider::GitHub => config::github_client_secret(),
+        }
+        .into()
+    }
+}
+
+impl Display for OAuthProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OAuthProvider::Google => "Google",
+            OAuthProvider::GitHub => "GitHub",
+        })
+    }
+}
+
+/// The profile of a user, as returned by an [OAuthProvider].
+#[derive(Debug, Clone)]
+pub struct OAuthProfile {
+    /// The provider that authenticated the user.
+    pub provider: OAuthProvider,
+
+    /// The id of the user at the provider.
+    pub subject: String,
+
+    /// The e-mail address of the user.
+    pub email: String,
+
+    /// Tells whether the provider has verified that the e-mail address belongs to the user.
+    pub email_verified: bool,
+
+    /// The name of the user.
+    pub username: String,
+}
+
+impl OAuthProfile {
+    /// Returns the data of a new [User] created from this profile. The e-mail address has been
+    /// verified by the provider, and the user has no password.
+    pub fn serialize_new_user(&self) -> Document {
+        let username = self
+            .username
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>();
+
+        doc! {
+            "id": Uuid::new(),
+            "email": self.email.clone(),
+            "username": if username.is_empty() { String::from("user") } else { username },
+            "user_tag": Uuid::new().to_string(),
+            "role": Into::<i32>::into(Role::User),
+            "password": "",
+            "auth_token": "",
+            "validated": true,
+            "token_expiration": Bson::DateTime(DateTime::now()),
+            "profile_picture": false,
+            "oauth_provider": self.provider.get_name(),
+            "oauth_subject": self.subject.clone()
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::utils::serde::Deserialize;
+use mongodb::bson::{Bson, DateTime, Document, Uuid, UuidRepresentation};
+
+/// A themed prompt users can post their drawings to while it is active.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    /// The id of the challenge.
+    id: Uuid,
+
+    /// The theme the drawings should follow.
+    prompt: String,
+
+    /// The moment the challenge starts accepting drawings.
+    start_time: DateTime,
+
+    /// The moment the challenge stops accepting drawings.
+    end_time: DateTime,
+}
+
+impl Challenge {
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_prompt(&self) -> &String {
+        &self.prompt
+    }
+
+    pub fn get_start_time(&self) -> DateTime {
+        self.start_time
+    }
+
+    pub fn get_end_time(&self) -> DateTime {
+        self.end_time
+    }
+
+    /// Tells whether the challenge accepts drawings at the given moment.
+    pub fn is_active(&self, now: DateTime) -> bool {
+        self.start_time <= now && now <= self.end_time
+    }
+}
+
+impl Default for Challenge {
+    fn default() -> Self {
+        Challenge {
+            id: Uuid::from_bytes([0; 16]),
+            prompt: String::new(),
+            start_time: DateTime::MIN,
+            end_time: DateTime::MIN,
+        }
+    }
+}
+
+impl Deserialize<Document> for Challenge {
+    fn deserialize(document: &Document) -> Self
+    where
+        Self: Sized,
+    {
+        let mut challenge = Challenge::default();
+
+        if let Some(Bson::Binary(bin)) = document.get("id") {
+            if let Ok(uuid) = bin.to_uuid_with_representation(UuidRepresentation::Standard) {
+                challenge.id = uuid;
+            }
+        }
+        if let Ok(prompt) = document.get_str("prompt") {
+            challenge.prompt = prompt.into();
+        }
+        if let Ok(start_time) = document.get_datetime("start_time") {
+            challenge.start_time = *start_time;
+        }
+        if let Ok(end_time) = document.get_datetime("end_time") {
+            challenge.end_time = *end_time;
+        }
+
+        challenge
+    }
+}
+
+/// The tabs of the challenge [Scene](crate::scene::Scene).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum ChallengeTabs {
+    /// Shows the prompt of the challenge.
+    #[default]
+    Prompt,
+
+    /// Ranks the entries of the challenge by their average rating.
+    Leaderboard,
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Local};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The maximum number of entries waiting to be added to the message log.
+const MAX_PENDING_ENTRIES: usize = 200;
+
+/// The entries logged outside of the updates of the [Application](crate::Chartsy), such as from
+/// futures, which are added to the message log the next time a message is logged.
+static PENDING_ENTRIES: Mutex<Vec<LogEntry>> = Mutex::new(Vec::new());
+
+/// A message handled while the [debug mode](crate::scene::Globals::is_debug_mode) was enabled.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// The moment the message was handled.
+    time: DateTime<Local>,
+
+    /// The title of the [Scene](crate::scene::Scene) that handled the message.
+    scene: String,
+
+    /// The name of the message.
+    message: String,
+
+    /// The time it took to handle the message. Is None for entries that don't come from a
+    /// [Scene](crate::scene::Scene) update.
+    elapsed: Option<Duration>,
+}
+
+impl LogEntry {
+    pub fn new(
+        scene: impl Into<String>,
+        message: impl Into<String>,
+        elapsed: Option<Duration>,
+    ) -> Self {
+        LogEntry {
+            time: Local::now(),
+            scene: scene.into(),
+            message: message.into(),
+            elapsed,
+        }
+    }
+
+    pub fn get_time(&self) -> DateTime<Local> {
+        self.time
+    }
+
+    pub fn get_scene(&self) -> &String {
+        &self.scene
+    }
+
+    pub fn get_message(&self) -> &String {
+        &self.message
+    }
+
+    pub fn get_elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+}
+
+/// Queues an entry to be added to the message log, for the code that can't access the
+/// [Globals](crate::scene::Globals).
+pub fn log(entry: LogEntry) {
+    if let Ok(mut entries) = PENDING_ENTRIES.lock() {
+        if entries.len() == MAX_PENDING_ENTRIES {
+            entries.remove(0);
+        }
+        entries.push(entry);
+    }
+}
+
+/// Removes and returns the entries waiting to be added to the message log, oldest first.
+pub fn take_pending_entries() -> Vec<LogEntry> {
+    PENDING_ENTRIES
+        .lock()
+        .map(|mut entries| std::mem::take(&mut *entries))
+        .unwrap_or_default()
+}
+
+/// The types of the modals that can be opened over any [Scene](crate::scene::Scene).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModalType {
+    /// Modal that lists the latest entries of the message log.
+    DebugLog,
+}
@@ -0,0 +1,77 @@
+use mongodb::bson::Uuid;
+
+use crate::scenes::auth::AuthOptions;
+use crate::scenes::data::drawing::SaveMode;
+use crate::scenes::data::posts::POST_LINK_PREFIX;
+use crate::scenes::drawing::DrawingOptions;
+use crate::scenes::posts::PostsOptions;
+use crate::scenes::scenes::Scenes;
+
+/// The prefix of the links that open a drawing in the application.
+pub const DRAWING_LINK_PREFIX: &str = "chartsy://drawing/";
+
+/// The prefix of the links that reset the password of an account.
+pub const PASSWORD_RESET_LINK_PREFIX: &str = "chartsy://reset-password/";
+
+/// A resource the application was asked to open through a `chartsy://` link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    /// A drawing, given by its id and the place it is saved in; linked as
+    /// `chartsy://drawing/<uuid>/<online|offline>`.
+    Drawing(Uuid, SaveMode),
+
+    /// A post, given by its id; linked as `chartsy://post/<uuid>`.
+    Post(Uuid),
+
+    /// A password reset, given by its token; linked as `chartsy://reset-password/<token>`.
+    PasswordReset(String),
+}
+
+impl DeepLink {
+    /// Parses a link, returning None if it is not a valid `chartsy://` link.
+    pub fn parse(link: &str) -> Option<Self> {
+        let link = link.trim().trim_end_matches('/');
+
+        if let Some(post) = link.strip_prefix(POST_LINK_PREFIX) {
+            return Uuid::parse_str(post).ok().map(DeepLink::Post);
+        }
+
+        if let Some(token) = link.strip_prefix(PASSWORD_RESET_LINK_PREFIX) {
+            return (!token.is_empty()).then(|| DeepLink::PasswordReset(token.to_string()));
+        }
+
+        let (id, save_mode) = link.strip_prefix(DRAWING_LINK_PREFIX)?.split_once('/')?;
+        let save_mode = match save_mode.to_lowercase().as_str() {
+            "online" => SaveMode::Online,
+            "offline" => SaveMode::Offline,
+            _ => return None,
+        };
+
+        Uuid::parse_str(id)
+            .ok()
+            .map(|id| DeepLink::Drawing(id, save_mode))
+    }
+
+    /// Returns the [Scene](crate::scene::Scene) that opens the linked resource. Online resources
+    /// can only be opened by an authenticated user.
+    pub fn get_scene(&self, user_logged_in: bool) -> Option<Scenes> {
+        match *self {
+            DeepLink::PasswordReset(ref token) => Some(Scenes::Auth(Some(
+                AuthOptions::reset_password(token.clone()),
+            ))),
+            DeepLink::Drawing(id, SaveMode::Offline) => Some(Scenes::Drawing(Some(
+                DrawingOptions::new(Some(id), None, Some(SaveMode::Offline)),
+            ))),
+            DeepLink::Drawing(id, SaveMode::Online) => user_logged_in.then(|| {
+                Scenes::Drawing(Some(DrawingOptions::new(
+                    Some(id),
+                    None,
+                    Some(SaveMode::Online),
+                )))
+            }),
+            DeepLink::Post(id) => {
+                user_logged_in.then(|| Scenes::Posts(Some(PostsOptions::new(id))))
+            }
+        }
+    }
+}
@@ -1,8 +1,14 @@
+use crate::scenes::data::auth::User;
+use crate::utils::encoder::{self, ImageFilter};
 use crate::utils::serde::{Deserialize, Serialize};
+use iced::widget::image::Handle;
 use iced::widget::text_editor::{Action, Content};
-use mongodb::bson::{doc, Document};
+use image::DynamicImage;
+use mongodb::bson::{doc, Document, Uuid};
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::time::Duration;
 
 /// The types of the modals that can be opened.
 #[derive(Clone, Eq)]
@@ -12,6 +18,24 @@ pub enum ModalTypes {
 
     /// A screen that blocks user interaction.
     WaitScreen(String),
+
+    /// A dialog where the user can change the dimensions of the drawing area.
+    ResizeDialog,
+
+    /// A panel listing the actions performed on the drawing.
+    HistoryPanel,
+
+    /// A panel where the user can share the drawing with other users.
+    SharePanel,
+
+    /// A panel listing the snapshots of the drawing.
+    SnapshotPanel,
+
+    /// A dialog where the user can choose how the timelapse of the drawing is exported.
+    TimelapseDialog,
+
+    /// A dialog where the user can adjust the colors of the drawing before exporting it.
+    AdjustmentsDialog,
 }
 
 impl ModalTypes {
@@ -28,6 +52,48 @@ impl ModalTypes {
             _ => false,
         }
     }
+
+    pub fn is_resize_dialog(&self) -> bool {
+        match self {
+            Self::ResizeDialog => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_history_panel(&self) -> bool {
+        match self {
+            Self::HistoryPanel => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_share_panel(&self) -> bool {
+        match self {
+            Self::SharePanel => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_snapshot_panel(&self) -> bool {
+        match self {
+            Self::SnapshotPanel => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_timelapse_dialog(&self) -> bool {
+        match self {
+            Self::TimelapseDialog => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_adjustments_dialog(&self) -> bool {
+        match self {
+            Self::AdjustmentsDialog => true,
+            _ => false,
+        }
+    }
 }
 
 impl PartialEq for ModalTypes {
@@ -35,6 +101,12 @@ impl PartialEq for ModalTypes {
         match self {
             Self::PostPrompt => other.is_post_prompt(),
             Self::WaitScreen(_) => other.is_wait_screen(),
+            Self::ResizeDialog => other.is_resize_dialog(),
+            Self::HistoryPanel => other.is_history_panel(),
+            Self::SharePanel => other.is_share_panel(),
+            Self::SnapshotPanel => other.is_snapshot_panel(),
+            Self::TimelapseDialog => other.is_timelapse_dialog(),
+            Self::AdjustmentsDialog => other.is_adjustments_dialog(),
         }
     }
 }
@@ -50,6 +122,13 @@ pub struct Tag {
 }
 
 impl Tag {
+    pub fn new(name: impl Into<String>) -> Self {
+        Tag {
+            name: name.into(),
+            uses: 0,
+        }
+    }
+
     /// Reduces the name of a new tag to a base tag form.
     pub fn reduced(mut self) -> Self {
         self.name = self
@@ -214,8 +293,408 @@ impl PostData {
     }
 }
 
+/// The data of the canvas resize dialog.
+#[derive(Default)]
+pub struct ResizeData {
+    /// The width input.
+    width: String,
+
+    /// The height input.
+    height: String,
+
+    /// Tells whether the aspect ratio of the drawing area should be kept.
+    keep_ratio: bool,
+
+    /// The aspect ratio (width / height) of the drawing area when the dialog was opened.
+    ratio: f32,
+}
+
+/// Possible updates to the resize dialog data.
+#[derive(Clone)]
+pub enum UpdateResizeData {
+    Width(String),
+    Height(String),
+    ToggleKeepRatio,
+    Reset(u32, u32),
+}
+
+impl ResizeData {
+    /// Updates the resize dialog data.
+    pub fn update(&mut self, update: UpdateResizeData) {
+        match update {
+            UpdateResizeData::Width(width) => {
+                if !width.chars().all(|c| c.is_ascii_digit()) {
+                    return;
+                }
+
+                if self.keep_ratio {
+                    if let Ok(value) = width.parse::<u32>() {
+                        self.height = ((value as f32 / self.ratio).round() as u32).to_string();
+                    }
+                }
+                self.width = width;
+            }
+            UpdateResizeData::Height(height) => {
+                if !height.chars().all(|c| c.is_ascii_digit()) {
+                    return;
+                }
+
+                if self.keep_ratio {
+                    if let Ok(value) = height.parse::<u32>() {
+                        self.width = ((value as f32 * self.ratio).round() as u32).to_string();
+                    }
+                }
+                self.height = height;
+            }
+            UpdateResizeData::ToggleKeepRatio => {
+                self.keep_ratio = !self.keep_ratio;
+
+                if let Some((width, height)) = self.get_size() {
+                    self.ratio = width as f32 / height as f32;
+                }
+            }
+            UpdateResizeData::Reset(width, height) => {
+                self.width = width.to_string();
+                self.height = height.to_string();
+                self.ratio = width as f32 / height.max(1) as f32;
+            }
+        }
+    }
+
+    pub fn get_width(&self) -> &String {
+        &self.width
+    }
+
+    pub fn get_height(&self) -> &String {
+        &self.height
+    }
+
+    pub fn get_keep_ratio(&self) -> bool {
+        self.keep_ratio
+    }
+
+    /// Returns the dimensions written by the user, if they are valid.
+    pub fn get_size(&self) -> Option<(u32, u32)> {
+        match (self.width.parse::<u32>(), self.height.parse::<u32>()) {
+            (Ok(width), Ok(height)) if width > 0 && height > 0 => Some((width, height)),
+            _ => None,
+        }
+    }
+}
+
+/// The data of the timelapse export dialog.
+pub struct TimelapseData {
+    /// The frame rate input.
+    frame_rate: String,
+
+    /// The input of the number of tools placed between two frames.
+    step: String,
+}
+
+impl Default for TimelapseData {
+    fn default() -> Self {
+        TimelapseData {
+            frame_rate: String::from("10"),
+            step: String::from("1"),
+        }
+    }
+}
+
+/// Possible updates to the timelapse dialog data.
+#[derive(Clone)]
+pub enum UpdateTimelapseData {
+    FrameRate(String),
+    Step(String),
+}
+
+impl TimelapseData {
+    /// Updates the timelapse dialog data.
+    pub fn update(&mut self, update: UpdateTimelapseData) {
+        match update {
+            UpdateTimelapseData::FrameRate(frame_rate) => {
+                if frame_rate.chars().all(|c| c.is_ascii_digit()) {
+                    self.frame_rate = frame_rate;
+                }
+            }
+            UpdateTimelapseData::Step(step) => {
+                if step.chars().all(|c| c.is_ascii_digit()) {
+                    self.step = step;
+                }
+            }
+        }
+    }
+
+    pub fn get_frame_rate(&self) -> &String {
+        &self.frame_rate
+    }
+
+    pub fn get_step(&self) -> &String {
+        &self.step
+    }
+
+    /// Returns the frame rate and the step written by the user, if they are valid.
+    pub fn get_options(&self) -> Option<(u32, usize)> {
+        match (self.frame_rate.parse::<u32>(), self.step.parse::<usize>()) {
+            (Ok(frame_rate), Ok(step)) if frame_rate > 0 && step > 0 => Some((frame_rate, step)),
+            _ => None,
+        }
+    }
+}
+
+/// The data of the export adjustments dialog.
+pub struct AdjustmentsData {
+    /// The brightness adjustment, between -1 and 1.
+    brightness: f32,
+
+    /// The contrast adjustment, as a percentage.
+    contrast: f32,
+
+    /// The saturation factor.
+    saturation: f32,
+
+    /// The hue rotation, in degrees.
+    hue: f32,
+
+    /// Tells whether the colors are inverted.
+    invert: bool,
+
+    /// The thumbnail of the drawing the adjustments are previewed on.
+    thumbnail: Option<DynamicImage>,
+
+    /// The thumbnail with the adjustments applied.
+    preview: Option<Handle>,
+}
+
+impl Default for AdjustmentsData {
+    fn default() -> Self {
+        AdjustmentsData {
+            brightness: 0.0,
+            contrast: 0.0,
+            saturation: 1.0,
+            hue: 0.0,
+            invert: false,
+            thumbnail: None,
+            preview: None,
+        }
+    }
+}
+
+/// Possible updates to the export adjustments dialog data.
+#[derive(Clone)]
+pub enum UpdateAdjustmentsData {
+    Brightness(f32),
+    Contrast(f32),
+    Saturation(f32),
+    Hue(f32),
+    Invert(bool),
+    Thumbnail(DynamicImage),
+    Reset,
+}
+
+impl AdjustmentsData {
+    /// Updates the export adjustments dialog data, and renders the preview again.
+    pub fn update(&mut self, update: UpdateAdjustmentsData) {
+        match update {
+            UpdateAdjustmentsData::Brightness(brightness) => self.brightness = brightness,
+            UpdateAdjustmentsData::Contrast(contrast) => self.contrast = contrast,
+            UpdateAdjustmentsData::Saturation(saturation) => self.saturation = saturation,
+            UpdateAdjustmentsData::Hue(hue) => self.hue = hue,
+            UpdateAdjustmentsData::Invert(invert) => self.invert = invert,
+            UpdateAdjustmentsData::Thumbnail(thumbnail) => self.thumbnail = Some(thumbnail),
+            UpdateAdjustmentsData::Reset => *self = AdjustmentsData::default(),
+        }
+
+        self.preview = self.thumbnail.clone().map(|thumbnail| {
+            let preview = encoder::apply_filters(thumbnail, &self.get_filters()).to_rgba8();
+
+            Handle::from_rgba(preview.width(), preview.height(), preview.into_raw())
+        });
+    }
+
+    pub fn get_brightness(&self) -> f32 {
+        self.brightness
+    }
+
+    pub fn get_contrast(&self) -> f32 {
+        self.contrast
+    }
+
+    pub fn get_saturation(&self) -> f32 {
+        self.saturation
+    }
+
+    pub fn get_hue(&self) -> f32 {
+        self.hue
+    }
+
+    pub fn get_invert(&self) -> bool {
+        self.invert
+    }
+
+    pub fn get_preview(&self) -> &Option<Handle> {
+        &self.preview
+    }
+
+    /// Returns the filters corresponding to the adjustments, leaving out the ones that would not
+    /// change the drawing.
+    pub fn get_filters(&self) -> Vec<ImageFilter> {
+        let mut filters = vec![];
+
+        if self.brightness != 0.0 {
+            filters.push(ImageFilter::Brightness(self.brightness));
+        }
+        if self.contrast != 0.0 {
+            filters.push(ImageFilter::Contrast(self.contrast));
+        }
+        if self.saturation != 1.0 {
+            filters.push(ImageFilter::Saturation(self.saturation));
+        }
+        if self.hue != 0.0 {
+            filters.push(ImageFilter::Hue(self.hue));
+        }
+        if self.invert {
+            filters.push(ImageFilter::Invert);
+        }
+
+        filters
+    }
+}
+
+/// The access level a user has on a shared drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DrawingPermission {
+    /// The user can only see the drawing.
+    View,
+
+    /// The user can see the drawing and comment on it.
+    Comment,
+
+    /// The user can modify the drawing.
+    Edit,
+
+    /// The user can modify the drawing, lock it, and share it with other users.
+    Admin,
+}
+
+impl DrawingPermission {
+    /// All the permissions, in increasing order of access.
+    pub const ALL: [DrawingPermission; 4] = [
+        DrawingPermission::View,
+        DrawingPermission::Comment,
+        DrawingPermission::Edit,
+        DrawingPermission::Admin,
+    ];
+
+    /// Returns the permission with the given name, if it exists.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|permission| permission.to_string() == name)
+    }
+}
+
+impl Display for DrawingPermission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DrawingPermission::View => "View",
+                DrawingPermission::Comment => "Comment",
+                DrawingPermission::Edit => "Edit",
+                DrawingPermission::Admin => "Admin",
+            }
+        )
+    }
+}
+
+/// The data of the share panel.
+pub struct ShareData {
+    /// The tag of the user the drawing will be shared with.
+    user_tag: String,
+
+    /// The permission that will be given to the user.
+    permission: DrawingPermission,
+
+    /// The users the drawing is shared with.
+    users: HashMap<Uuid, User>,
+}
+
+/// Possible updates to the share panel data.
+#[derive(Clone)]
+pub enum UpdateShareData {
+    UserTag(String),
+    Permission(DrawingPermission),
+    Users(Vec<User>),
+}
+
+impl Default for ShareData {
+    fn default() -> Self {
+        ShareData {
+            user_tag: String::new(),
+            permission: DrawingPermission::View,
+            users: HashMap::new(),
+        }
+    }
+}
+
+impl ShareData {
+    /// Updates the share panel data.
+    pub fn update(&mut self, update: UpdateShareData) {
+        match update {
+            UpdateShareData::UserTag(user_tag) => self.user_tag = user_tag,
+            UpdateShareData::Permission(permission) => self.permission = permission,
+            UpdateShareData::Users(users) => {
+                for user in users {
+                    self.users.insert(user.get_id(), user);
+                }
+            }
+        }
+    }
+
+    pub fn get_user_tag(&self) -> &String {
+        &self.user_tag
+    }
+
+    pub fn get_permission(&self) -> DrawingPermission {
+        self.permission
+    }
+
+    pub fn get_user(&self, id: &Uuid) -> Option<&User> {
+        self.users.get(id)
+    }
+}
+
+/// The interval between automatic saves of a drawing; None disables auto-saving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoSaveInterval(pub Option<Duration>);
+
+impl AutoSaveInterval {
+    /// The intervals the user can choose from.
+    pub const OPTIONS: [AutoSaveInterval; 5] = [
+        AutoSaveInterval(None),
+        AutoSaveInterval(Some(Duration::from_secs(30))),
+        AutoSaveInterval(Some(Duration::from_secs(60))),
+        AutoSaveInterval(Some(Duration::from_secs(300))),
+        AutoSaveInterval(Some(Duration::from_secs(600))),
+    ];
+}
+
+impl Display for AutoSaveInterval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => write!(f, "Off"),
+            Some(interval) if interval.as_secs() < 60 => {
+                write!(f, "Every {} seconds", interval.as_secs())
+            }
+            Some(interval) if interval.as_secs() == 60 => write!(f, "Every minute"),
+            Some(interval) => write!(f, "Every {} minutes", interval.as_secs() / 60),
+        }
+    }
+}
+
 /// The mode in which the progress will be saved.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SaveMode {
     /// Saves the canvas locally.
     Offline,
@@ -223,3 +702,21 @@ pub enum SaveMode {
     /// Saves the canvas in a database.
     Online,
 }
+
+impl SaveMode {
+    /// All the save modes.
+    pub const ALL: [SaveMode; 2] = [SaveMode::Offline, SaveMode::Online];
+}
+
+impl Display for SaveMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SaveMode::Offline => "Offline",
+                SaveMode::Online => "Online",
+            }
+        )
+    }
+}
@@ -1,3 +1,5 @@
+use mongodb::bson::Uuid;
+
 /// The modals that can be displayed on the [Main] [scene](Scene).
 #[derive(Clone, Eq, PartialEq)]
 pub enum ModalType {
@@ -15,3 +17,19 @@ pub enum MainTabIds {
     Offline,
     Online,
 }
+
+/// A drawing read from an archive, waiting to be imported.
+#[derive(Debug, Clone)]
+pub struct ArchivedDrawing {
+    /// The id of the drawing.
+    pub id: Uuid,
+
+    /// The name of the drawing.
+    pub name: String,
+
+    /// The contents of the data.json file.
+    pub data: Vec<u8>,
+
+    /// The contents of the data.webp file.
+    pub preview: Vec<u8>,
+}
@@ -1,4 +1,9 @@
+pub mod admin;
+pub mod app_settings;
 pub mod auth;
+pub mod challenge;
+pub mod debug;
+pub mod deep_link;
 pub mod drawing;
 pub mod main;
 pub mod posts;
\ No newline at end of file
@@ -2,10 +2,20 @@ use crate::scene::Message;
 use crate::scenes::data::auth::User;
 use crate::scenes::posts::PostsMessage;
 use crate::utils::serde::{Deserialize, Serialize};
-use mongodb::bson::{doc, Bson, Document, Uuid, UuidRepresentation};
+use mongodb::bson::{doc, Bson, DateTime, Document, Uuid, UuidRepresentation};
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 
+/// The number of comments loaded at once for a post or comment.
+pub const COMMENT_PAGE_SIZE: usize = 20;
+
+/// The number of users displayed on a page of the user search results.
+pub const USER_SEARCH_PAGE_SIZE: usize = 12;
+
+/// The prefix of the links that open a post in the application.
+pub const POST_LINK_PREFIX: &str = "chartsy://post/";
+
 /// A comment on a post.
 #[derive(Clone)]
 pub struct Comment {
@@ -33,6 +43,18 @@ pub struct Comment {
 
     /// The index of the reply that is currently opened(absolute).
     open_reply: Option<usize>,
+
+    /// The new content of the [Comment], if the user is editing it.
+    edit_input: Option<String>,
+
+    /// Tells whether the [Comment] has been deleted.
+    deleted: bool,
+
+    /// The users mentioned in the [Comment].
+    mentions: Vec<User>,
+
+    /// The moment the [Comment] was sent.
+    created: DateTime,
 }
 
 impl Comment {
@@ -90,6 +112,22 @@ impl Comment {
         &self.open_reply
     }
 
+    pub fn get_edit_input(&self) -> &Option<String> {
+        &self.edit_input
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    pub fn get_mentions(&self) -> &Vec<User> {
+        &self.mentions
+    }
+
+    pub fn get_created(&self) -> DateTime {
+        self.created
+    }
+
     pub fn replies_not_loaded(&self) -> bool {
         self.replies.is_none()
     }
@@ -122,24 +160,32 @@ impl Default for Comment {
             parent: None,
             replies: None,
             open_reply: None,
+            edit_input: None,
+            deleted: false,
+            mentions: vec![],
+            created: DateTime::from_millis(0),
         }
     }
 }
 
 impl Serialize<Document> for Comment {
     fn serialize(&self) -> Document {
+        let mentions: Vec<Uuid> = self.mentions.iter().map(User::get_id).collect();
+
         if let Some(id) = self.reply_to {
             doc! {
                 "id": self.id,
                 "user_id": self.user.get_id(),
                 "content": self.content.clone(),
-                "reply_to": id
+                "reply_to": id,
+                "mentions": mentions
             }
         } else {
             doc! {
                 "id": self.id,
                 "user_id": self.user.get_id(),
                 "content": self.content.clone(),
+                "mentions": mentions
             }
         }
     }
@@ -163,6 +209,16 @@ impl Deserialize<Document> for Comment {
         if let Some(Bson::String(content)) = document.get("content") {
             comment.content = content.clone();
         }
+        if let Ok(created) = document.get_datetime("created") {
+            comment.created = *created;
+        }
+        if let Some(Bson::Array(mentions)) = document.get("mentioned_users") {
+            for mention in mentions {
+                if let Bson::Document(user) = mention {
+                    comment.mentions.push(Deserialize::deserialize(user));
+                }
+            }
+        }
 
         comment
     }
@@ -195,10 +251,52 @@ pub enum CommentMessage {
         parent: Option<(usize, usize)>,
     },
 
-    /// Loads the replies for a [Comment].
+    /// Toggles the editing of a [Comment].
+    ToggleEdit {
+        post: usize,
+        position: (usize, usize),
+    },
+
+    /// Updates the new content of a [Comment] that is being edited.
+    UpdateEditInput {
+        post: usize,
+        position: (usize, usize),
+        input: String,
+    },
+
+    /// Replaces the content of a [Comment].
+    Edit {
+        post: usize,
+        position: (usize, usize),
+        new_content: String,
+    },
+
+    /// Deletes a [Comment].
+    Delete {
+        post: usize,
+        position: (usize, usize),
+    },
+
+    /// Loads a page of the replies for a [Comment], starting after the given comment.
     Load {
         post: usize,
         parent: Option<(usize, usize)>,
+        after: Option<(DateTime, Uuid)>,
+    },
+
+    /// Triggers when the users whose tag starts with the mention being written in a comment
+    /// input have been loaded.
+    LoadedMentions {
+        post: usize,
+        position: Option<(usize, usize)>,
+        users: Vec<User>,
+    },
+
+    /// Completes the mention being written in a comment input with the given user.
+    Mention {
+        post: usize,
+        position: Option<(usize, usize)>,
+        user: User,
     },
 
     /// Loads comments that are replies to another comment.
@@ -207,6 +305,7 @@ pub enum CommentMessage {
         parent: Option<(usize, usize)>,
         comments: Vec<Comment>,
         tab: PostTabs,
+        after: Option<(DateTime, Uuid)>,
     },
 }
 
@@ -216,6 +315,27 @@ impl Into<Message> for CommentMessage {
     }
 }
 
+/// A change that is displayed before the database accepts it, and is undone if it is rejected.
+#[derive(Clone)]
+pub enum OptimisticChange {
+    /// The like of the post with the given id has been toggled.
+    Like(Uuid),
+
+    /// The rating given to a post has been changed from the previous one.
+    Rating {
+        tab: PostTabs,
+        post: usize,
+        previous: f32,
+    },
+
+    /// The comment with the given id has been added to a post.
+    Comment {
+        tab: PostTabs,
+        post: usize,
+        id: Uuid,
+    },
+}
+
 /// The data for a loaded post.
 #[derive(Clone)]
 pub struct Post {
@@ -231,8 +351,23 @@ pub struct Post {
     /// The [User] this [Post] belongs to.
     user: User,
 
-    /// The rating of the post.
-    rating: usize,
+    /// The rating of the post, in steps of half a star.
+    rating: f32,
+
+    /// The average rating given to the post by the other users.
+    avg_rating: f64,
+
+    /// The number of ratings given to the post by the other users.
+    rating_count: usize,
+
+    /// The number of ratings received in the last day per hour since the post was created.
+    velocity: f64,
+
+    /// Tells whether the authenticated user has liked the post.
+    is_liked: bool,
+
+    /// Tells whether the authenticated user follows the author of the post.
+    is_followed: bool,
 
     /// The input of the comment the user is currently writing.
     comment_input: String,
@@ -243,6 +378,16 @@ pub struct Post {
 
     /// The index of the comment that is currently opened.
     open_comment: Option<usize>,
+
+    /// The input the mention suggestions refer to, as the position of the comment being replied
+    /// to, or None for the comment input of the post.
+    mention_position: Option<(usize, usize)>,
+
+    /// The users suggested for the mention currently being written.
+    mention_suggestions: Vec<User>,
+
+    /// The users that have been mentioned in the inputs of this post, by their tags.
+    mentioned_users: HashMap<String, User>,
 }
 
 impl Post {
@@ -258,6 +403,10 @@ impl Post {
         &self.description
     }
 
+    pub fn get_tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
     pub fn get_comments(&self) -> &Vec<Vec<Comment>> {
         &self.comments
     }
@@ -274,18 +423,75 @@ impl Post {
         &self.open_comment
     }
 
-    pub fn get_rating(&self) -> &usize {
+    /// Returns the users suggested for the mention being written in the given input.
+    pub fn get_mention_suggestions(&self, position: Option<(usize, usize)>) -> &[User] {
+        if self.mention_position == position {
+            &self.mention_suggestions
+        } else {
+            &[]
+        }
+    }
+
+    /// Returns the users whose tags are mentioned in the given text.
+    fn get_mentions(&self, text: &String) -> Vec<User> {
+        self.mentioned_users
+            .iter()
+            .filter(|(tag, _)| {
+                text.split_whitespace()
+                    .any(|word| word.strip_prefix('@') == Some(tag.as_str()))
+            })
+            .map(|(_, user)| user.clone())
+            .collect()
+    }
+
+    pub fn get_rating(&self) -> &f32 {
         &self.rating
     }
 
+    pub fn get_avg_rating(&self) -> f64 {
+        self.avg_rating
+    }
+
+    pub fn get_rating_count(&self) -> usize {
+        self.rating_count
+    }
+
+    pub fn get_velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    pub fn is_liked(&self) -> bool {
+        self.is_liked
+    }
+
     pub fn get_image(&self, images: &HashMap<Uuid, Arc<Vec<u8>>>) -> Option<Arc<Vec<u8>>> {
         images.get(&self.id).map(Clone::clone)
     }
 
-    pub fn set_rating(&mut self, rating: impl Into<usize>) {
+    pub fn set_description(&mut self, description: impl Into<String>) {
+        self.description = description.into();
+    }
+
+    pub fn set_tags(&mut self, tags: impl Into<Vec<String>>) {
+        self.tags = tags.into();
+    }
+
+    pub fn set_rating(&mut self, rating: impl Into<f32>) {
         self.rating = rating.into();
     }
 
+    pub fn set_liked(&mut self, is_liked: bool) {
+        self.is_liked = is_liked;
+    }
+
+    pub fn is_followed(&self) -> bool {
+        self.is_followed
+    }
+
+    pub fn set_followed(&mut self, is_followed: bool) {
+        self.is_followed = is_followed;
+    }
+
     pub fn set_comment_input(&mut self, comment_input: impl Into<String>) {
         self.comment_input = comment_input.into();
     }
@@ -302,10 +508,18 @@ impl Default for Post {
             description: "".into(),
             tags: vec![],
             user: User::default(),
-            rating: 0,
+            rating: 0.0,
+            avg_rating: 0.0,
+            rating_count: 0,
+            velocity: 0.0,
+            is_liked: false,
+            is_followed: false,
             comment_input: Default::default(),
             comments: vec![],
             open_comment: None,
+            mention_position: None,
+            mention_suggestions: vec![],
+            mentioned_users: HashMap::new(),
         }
     }
 }
@@ -339,10 +553,34 @@ impl Deserialize<Document> for Post {
             post.user = User::deserialize(user);
         }
         if let Some(Bson::Document(rating)) = document.get("rating") {
-            if let Some(Bson::Int32(rating)) = rating.get("rating") {
-                post.rating = *rating as usize;
+            match rating.get("rating") {
+                Some(Bson::Double(rating)) => post.rating = *rating as f32,
+                Some(Bson::Int32(rating)) => post.rating = *rating as f32,
+                _ => {}
+            }
+        }
+        if let Some(Bson::Document(average_rating)) = document.get("average_rating") {
+            if let Some(Bson::Double(average)) = average_rating.get("average") {
+                post.avg_rating = *average;
+            }
+            match average_rating.get("count") {
+                Some(Bson::Int32(count)) => post.rating_count = *count as usize,
+                Some(Bson::Int64(count)) => post.rating_count = *count as usize,
+                _ => {}
             }
         }
+        match document.get("velocity") {
+            Some(Bson::Double(velocity)) => post.velocity = *velocity,
+            Some(Bson::Int32(velocity)) => post.velocity = *velocity as f64,
+            Some(Bson::Int64(velocity)) => post.velocity = *velocity as f64,
+            _ => {}
+        }
+        if let Some(Bson::Array(likes)) = document.get("like") {
+            post.is_liked = !likes.is_empty();
+        }
+        if let Some(Bson::Array(follows)) = document.get("follow") {
+            post.is_followed = !follows.is_empty();
+        }
 
         post
     }
@@ -356,11 +594,19 @@ pub struct PostList {
 
     /// Number of loaded posts.
     loaded: usize,
+
+    /// The last comment loaded from the database for each post and parent comment, and whether
+    /// there might be more comments after it.
+    comment_cursor: HashMap<(usize, Option<(usize, usize)>), (Option<(DateTime, Uuid)>, bool)>,
 }
 
 impl PostList {
     pub fn new(posts: Vec<Post>) -> Self {
-        PostList { posts, loaded: 0 }
+        PostList {
+            posts,
+            loaded: 0,
+            comment_cursor: HashMap::new(),
+        }
     }
 
     /// Load the next batch of images.
@@ -372,13 +618,48 @@ impl PostList {
     }
 
     /// Change the rating given to a post by the authenticated user.
-    pub fn rate_post(&mut self, index: usize, rating: usize) -> (Uuid, Option<usize>) {
+    pub fn rate_post(&mut self, index: usize, rating: f32) -> (Uuid, Option<f32>) {
         let post = &mut self.posts[index];
 
-        let rating = rating.clone();
         post.set_rating(rating);
 
-        (post.get_id(), if rating == 0 { None } else { Some(rating) })
+        (
+            post.get_id(),
+            if rating == 0.0 { None } else { Some(rating) },
+        )
+    }
+
+    /// Toggles the like of all posts with the given id. Returns the new state of the like,
+    /// or None if the post is not in this list.
+    pub fn toggle_like(&mut self, id: Uuid) -> Option<bool> {
+        let mut is_liked = None;
+
+        for post in self.posts.iter_mut().filter(|post| post.get_id() == id) {
+            post.set_liked(!post.is_liked());
+            is_liked = Some(post.is_liked());
+        }
+
+        is_liked
+    }
+
+    /// Sets the follow state of all posts of the user with the given id.
+    pub fn set_followed(&mut self, user_id: Uuid, is_followed: bool) {
+        for post in self
+            .posts
+            .iter_mut()
+            .filter(|post| post.get_user().get_id() == user_id)
+        {
+            post.set_followed(is_followed);
+        }
+    }
+
+    /// Returns whether the user with the given id is followed, or None if none of their posts
+    /// are in this list.
+    pub fn is_followed(&self, user_id: Uuid) -> Option<bool> {
+        self.posts
+            .iter()
+            .find(|post| post.get_user().get_id() == user_id)
+            .map(Post::is_followed)
     }
 
     /// Opens the given comment. If the replies haven't been loaded yet, returns true.
@@ -429,48 +710,196 @@ impl PostList {
         }
     }
 
-    /// Adds a reply to the given comment. Returns the reply data serialized.
+    /// Returns the mention being written at the end of the given input, without the '@'.
+    pub fn get_mention_prefix(
+        &self,
+        post_index: usize,
+        position: Option<(usize, usize)>,
+    ) -> Option<String> {
+        let post = &self.posts[post_index];
+        let input = match position {
+            Some((line, index)) => &post.comments[line][index].reply_input,
+            None => &post.comment_input,
+        };
+
+        if input.ends_with(char::is_whitespace) {
+            return None;
+        }
+
+        input
+            .split_whitespace()
+            .last()
+            .and_then(|word| word.strip_prefix('@'))
+            .filter(|prefix| !prefix.is_empty())
+            .map(String::from)
+    }
+
+    /// Sets the users suggested for the mention being written in the given input.
+    pub fn set_mention_suggestions(
+        &mut self,
+        post_index: usize,
+        position: Option<(usize, usize)>,
+        users: Vec<User>,
+    ) {
+        let post = &mut self.posts[post_index];
+
+        post.mention_position = position;
+        post.mention_suggestions = users;
+    }
+
+    /// Replaces the mention being written in the given input with the tag of the given user.
+    pub fn complete_mention(
+        &mut self,
+        post_index: usize,
+        position: Option<(usize, usize)>,
+        user: User,
+    ) {
+        let post = &mut self.posts[post_index];
+        let input = match position {
+            Some((line, index)) => &mut post.comments[line][index].reply_input,
+            None => &mut post.comment_input,
+        };
+
+        if let Some(start) = input.rfind('@') {
+            input.truncate(start);
+        }
+        input.push_str(&*format!("@{} ", user.get_user_tag()));
+
+        post.mentioned_users
+            .insert(user.get_user_tag().clone(), user);
+        post.mention_suggestions.clear();
+    }
+
+    /// Adds a reply to the given comment. Returns the id of the reply and its data serialized.
     pub fn add_reply(
         &mut self,
         user: User,
         post_index: usize,
         line: usize,
         index: usize,
-    ) -> Document {
+    ) -> (Uuid, Document) {
         let post = &mut self.posts[post_index];
         let parent = &post.comments[line][index];
 
-        let comment = Comment::new_reply(
+        let mut comment = Comment::new_reply(
             Uuid::new(),
             user,
             parent.get_reply_input().clone(),
             parent.get_id().clone(),
             (line, index),
         );
+        comment.mentions = post.get_mentions(&comment.content);
+        comment.created = DateTime::now();
 
-        let document = comment.serialize();
+        let id = comment.id;
+        let created = comment.created;
+        let mut document = comment.serialize();
 
         post.comments[line][index].reply_input = String::from("");
 
         let line = post.comments[line][index].replies.unwrap();
         post.comments[line].push(comment);
 
-        document
+        document.insert("post_id", post.id);
+        document.insert("created", created);
+        (id, document)
     }
 
-    /// Adds a comment to the given post. Returns the comment data serialized.
-    pub fn add_comment(&mut self, user: User, post_index: usize) -> Document {
+    /// Adds a comment to the given post. Returns the id of the comment and its data serialized.
+    pub fn add_comment(&mut self, user: User, post_index: usize) -> (Uuid, Document) {
         let post = &mut self.posts[post_index];
 
-        let comment = Comment::new_comment(Uuid::new(), user, post.comment_input.clone());
+        let mut comment = Comment::new_comment(Uuid::new(), user, post.comment_input.clone());
+        comment.mentions = post.get_mentions(&comment.content);
+        comment.created = DateTime::now();
 
+        let id = comment.id;
+        let created = comment.created;
         let mut document = comment.serialize();
 
         post.comment_input = String::from("");
         post.comments[0].push(comment);
 
         document.insert("post_id", post.id);
-        document
+        document.insert("created", created);
+        (id, document)
+    }
+
+    /// Toggles the editing of the given comment.
+    pub fn toggle_edit(&mut self, post_index: usize, line: usize, index: usize) {
+        let comment = &mut self.posts[post_index].comments[line][index];
+
+        comment.edit_input = match comment.edit_input {
+            Some(_) => None,
+            None => Some(comment.content.clone()),
+        };
+    }
+
+    /// Updates the edit input of the given comment.
+    pub fn update_edit_input(
+        &mut self,
+        post_index: usize,
+        line: usize,
+        index: usize,
+        input: String,
+    ) {
+        self.posts[post_index].comments[line][index].edit_input = Some(input);
+    }
+
+    /// Replaces the content of the given comment. Returns the id of the comment.
+    pub fn edit_comment(
+        &mut self,
+        post_index: usize,
+        line: usize,
+        index: usize,
+        content: String,
+    ) -> Uuid {
+        let comment = &mut self.posts[post_index].comments[line][index];
+
+        comment.content = content;
+        comment.edit_input = None;
+
+        comment.id
+    }
+
+    /// Marks the given comment as deleted, closing it if it is opened. Returns the id of the
+    /// comment.
+    pub fn delete_comment(&mut self, post_index: usize, line: usize, index: usize) -> Uuid {
+        let post = &self.posts[post_index];
+        let is_open = match post.comments[line][index].parent {
+            Some((parent_line, parent_index)) => {
+                post.comments[parent_line][parent_index].open_reply == Some(index)
+            }
+            None => post.open_comment == Some(index),
+        };
+
+        if is_open {
+            self.close_comment(post_index, line, index);
+        }
+
+        let comment = &mut self.posts[post_index].comments[line][index];
+        comment.deleted = true;
+
+        comment.id
+    }
+
+    /// Marks the comment with the given id as deleted, wherever it is in the given post.
+    pub fn remove_comment(&mut self, post_index: usize, id: Uuid) {
+        let position =
+            self.posts[post_index]
+                .comments
+                .iter()
+                .enumerate()
+                .find_map(|(line, comments)| {
+                    comments
+                        .iter()
+                        .position(|comment| comment.id == id)
+                        .map(|index| (line, index))
+                });
+
+        if let Some((line, index)) = position {
+            self.delete_comment(post_index, line, index);
+        }
     }
 
     /// Returns the load comments request mongo document.
@@ -481,31 +910,72 @@ impl PostList {
             }
         } else {
             doc! {
-                "post_id": self.posts[post_index].id
+                "post_id": self.posts[post_index].id,
+                "reply_to": { "$exists": false }
             }
         }
     }
 
-    /// Adds a new set of comments that were loaded.
+    /// Adds a new set of comments that were loaded. The first page creates a new line of
+    /// comments, while the following ones are appended to it.
     pub fn loaded_comments(
         &mut self,
         post_index: usize,
         parent: Option<(usize, usize)>,
-        comments: Vec<Comment>,
+        mut comments: Vec<Comment>,
+        after: Option<(DateTime, Uuid)>,
     ) {
+        let cursor = comments
+            .last()
+            .map(|comment| (comment.created, comment.id))
+            .or(after);
+        self.comment_cursor.insert(
+            (post_index, parent),
+            (cursor, comments.len() == COMMENT_PAGE_SIZE),
+        );
         let post = &mut self.posts[post_index];
-        post.comments.push(comments);
-        let new_line = post.comments.len() - 1;
 
-        for comment in &mut post.comments[new_line] {
+        for comment in &mut comments {
             comment.parent = parent;
         }
 
+        if after.is_some() {
+            let line = match parent {
+                Some((line, index)) => post.comments[line][index].replies.unwrap(),
+                None => 0,
+            };
+
+            post.comments[line].append(&mut comments);
+            return;
+        }
+
+        post.comments.push(comments);
+        let new_line = post.comments.len() - 1;
+
         if let Some((line, index)) = parent {
             post.comments[line][index].replies = Some(new_line);
         }
     }
 
+    /// Returns the last comment loaded from the database for the given post and parent comment,
+    /// after which the next page of comments starts.
+    pub fn get_comment_cursor(
+        &self,
+        post_index: usize,
+        parent: Option<(usize, usize)>,
+    ) -> Option<(DateTime, Uuid)> {
+        self.comment_cursor
+            .get(&(post_index, parent))
+            .and_then(|(cursor, _)| *cursor)
+    }
+
+    /// Tells whether there might be more comments to load for the given post and parent comment.
+    pub fn has_more_comments(&self, post_index: usize, parent: Option<(usize, usize)>) -> bool {
+        self.comment_cursor
+            .get(&(post_index, parent))
+            .map_or(false, |(_, more)| *more)
+    }
+
     /// Returns true if the given post has already loaded the main comments.
     pub fn has_loaded_comments(&self, post_index: usize) -> bool {
         self.posts[post_index].comments.len() > 0
@@ -524,6 +994,19 @@ impl PostList {
             .map(|val| (val.1, val.0))
     }
 
+    /// Returns the post with the given id.
+    pub fn get_post_by_id(&self, id: Uuid) -> Option<&Post> {
+        self.posts.iter().find(|post| post.get_id() == id)
+    }
+
+    /// Updates the description and tags of all posts with the given id.
+    pub fn edit_post(&mut self, id: Uuid, description: String, tags: Vec<String>) {
+        for post in self.posts.iter_mut().filter(|post| post.get_id() == id) {
+            post.set_description(description.clone());
+            post.set_tags(tags.clone());
+        }
+    }
+
     /// Tells whether the images have all been loaded.
     pub fn done_loading(&self) -> bool {
         self.loaded == self.posts.len()
@@ -561,6 +1044,9 @@ pub enum ModalType {
 
     /// Modal for reporting a post.
     ShowingReport(usize),
+
+    /// Modal for editing the description and tags of a post.
+    EditingPost(Uuid),
 }
 
 impl ModalType {
@@ -587,6 +1073,14 @@ impl ModalType {
             _ => false,
         }
     }
+
+    /// Checks if its value is [EditingPost](ModalType::EditingPost).
+    fn is_editing_post(&self) -> bool {
+        match self {
+            ModalType::EditingPost(_) => true,
+            _ => false,
+        }
+    }
 }
 
 impl PartialEq for ModalType {
@@ -595,12 +1089,46 @@ impl PartialEq for ModalType {
             ModalType::ShowingImage(_) => other.is_showing_image(),
             ModalType::ShowingPost(_) => other.is_showing_post(),
             ModalType::ShowingReport(_) => other.is_showing_report(),
+            ModalType::EditingPost(_) => other.is_editing_post(),
         }
     }
 }
 
 impl Eq for ModalType {}
 
+/// The orders in which the filtered posts can be sorted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    /// The newest posts first.
+    #[default]
+    Date,
+
+    /// The posts with the best average rating first.
+    Rating,
+
+    /// The posts whose tags best match the filter first.
+    Relevance,
+}
+
+impl SortOrder {
+    /// The list of all sort orders.
+    pub const ALL: [SortOrder; 3] = [SortOrder::Date, SortOrder::Rating, SortOrder::Relevance];
+}
+
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SortOrder::Date => "Newest",
+                SortOrder::Rating => "Best rated",
+                SortOrder::Relevance => "Most relevant",
+            }
+        )
+    }
+}
+
 /// The tabs the posts page is split into.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
 pub enum PostTabs {
@@ -613,4 +1141,25 @@ pub enum PostTabs {
 
     /// Posts generated by profile lookup.
     Profile,
+
+    /// Posts the user has liked.
+    Liked,
+
+    /// Posts of the users the user follows.
+    Following,
+
+    /// Posts that received the most ratings recently.
+    Trending,
+}
+
+impl PostTabs {
+    /// The list of all tabs.
+    pub const ALL: [PostTabs; 6] = [
+        PostTabs::Recommended,
+        PostTabs::Filtered,
+        PostTabs::Profile,
+        PostTabs::Liked,
+        PostTabs::Following,
+        PostTabs::Trending,
+    ];
 }
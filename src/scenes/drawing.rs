@@ -1,20 +1,25 @@
 use std::any::Any;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::canvas::canvas::Canvas;
+use crate::canvas::canvas::{Canvas, CollabCursor};
 use crate::canvas::svg::SVG;
 use crate::widgets::{ModalStack, WaitPanel};
 use iced::widget::text_editor::Content;
 use iced::widget::Container;
-use iced::{Command, Element, Length, Renderer};
+use iced::{Command, Element, Length, Point, Renderer, Subscription};
 use json::object::Object;
 use json::JsonValue;
 use mongodb::bson::Uuid;
 
 use crate::canvas::layer::CanvasMessage;
+use crate::canvas::tool::Tool;
 use crate::canvas::tools::line::LinePending;
 use crate::scene::{Globals, Message, Scene, SceneMessage};
-use crate::utils::errors::Error;
-use crate::{database, scenes::services, utils};
+use crate::utils::errors::{AuthError, Error};
+use crate::{database, debug_message, scenes::services, utils};
 
 use crate::utils::theme::Theme;
 
@@ -22,6 +27,18 @@ use crate::scenes::data::drawing::*;
 
 use super::scenes::Scenes;
 
+/// The time after which the cursor of an inactive collaborator is hidden.
+const CURSOR_EXPIRATION: Duration = Duration::from_secs(3);
+
+/// The minimum time between two updates of the cursor position sent to the collaborators.
+const CURSOR_SEND_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The time between two counts of the collaborators editing the drawing.
+const COLLABORATORS_COUNT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The size of the thumbnail the export adjustments are previewed on.
+const ADJUSTMENTS_PREVIEW_SIZE: u32 = 200;
+
 /// The [Messages](SceneMessage) for the [Drawing] scene.
 #[derive(Clone)]
 pub enum DrawingMessage {
@@ -34,15 +51,88 @@ pub enum DrawingMessage {
     /// Saves the file with the format and location that the user provides.
     SaveAs,
 
+    /// Exports the drawing to a PDF file with one PDF layer per canvas layer.
+    ExportLayeredPdf,
+
+    /// Updates the [TimelapseData] given the modified field.
+    UpdateTimelapseData(UpdateTimelapseData),
+
+    /// Exports the creation of the drawing as an animated GIF, to a file the user chooses.
+    ExportTimelapse,
+
+    /// Encodes the timelapse of the drawing to the given file.
+    EncodeTimelapse(PathBuf),
+
+    /// Triggered when the timelapse has been exported.
+    DoneExportTimelapse(Result<(), Error>),
+
+    /// Updates the [AdjustmentsData] given the modified field.
+    UpdateAdjustmentsData(UpdateAdjustmentsData),
+
+    /// Saves the file with the format and location that the user provides, after applying the
+    /// color adjustments from the [AdjustmentsData].
+    ExportWithAdjustments,
+
     /// Updates the [PostData] given the modified field.
     UpdatePostData(UpdatePostData),
 
+    /// Updates the [ResizeData] given the modified field.
+    UpdateResizeData(UpdateResizeData),
+
     /// Deletes the currently opened drawing.
     DeleteDrawing,
 
     /// Toggles a [Modal](ModalTypes).
     ToggleModal(ModalTypes),
 
+    /// Toggles receiving the changes other users make to the drawing in real time.
+    EnableCollaboration,
+
+    /// Triggered when a collaborator has saved a [Tool] on the drawing.
+    ReceivedTool {
+        tool: Arc<dyn Tool>,
+        layer: Uuid,
+        session: Uuid,
+    },
+
+    /// Triggered when a collaborator has moved their cursor over the drawing.
+    ReceivedCursor { session: Uuid, cursor: CollabCursor },
+
+    /// Removes the cursors of the collaborators that have been inactive for too long.
+    ExpireCursors,
+
+    /// Counts the other editing sessions that are collaborating on the drawing.
+    CountCollaborators,
+
+    /// Triggered when the collaborators editing the drawing have been counted.
+    CountedCollaborators(usize),
+
+    /// Locks or unlocks the drawing. Only available to the administrators of the drawing.
+    ToggleLock,
+
+    /// Triggered when the lock state, the owner and the permissions of the drawing have been
+    /// loaded.
+    LoadedAccess {
+        locked: bool,
+        owner: Uuid,
+        permissions: HashMap<Uuid, DrawingPermission>,
+    },
+
+    /// Updates the [ShareData] given the modified field.
+    UpdateShareData(UpdateShareData),
+
+    /// Gives the user with the given id the given permission on the drawing.
+    ShareWith(Uuid, DrawingPermission),
+
+    /// Shares the drawing with the user whose tag was written in the share panel.
+    ShareWithTag,
+
+    /// Updates the name of the snapshot that will be created.
+    UpdateSnapshotName(String),
+
+    /// Sets the interval between automatic saves and stores it in the settings.
+    SetAutoSave(AutoSaveInterval),
+
     /// Handles errors.
     ErrorHandler(Error),
 }
@@ -63,9 +153,30 @@ impl SceneMessage for DrawingMessage {
             Self::CanvasMessage(_) => String::from("Canvas action"),
             Self::PostDrawing => String::from("Post drawing"),
             Self::SaveAs => String::from("Save as..."),
+            Self::ExportLayeredPdf => String::from("Export layered PDF"),
+            Self::UpdateTimelapseData(_) => String::from("Update timelapse data"),
+            Self::ExportTimelapse => String::from("Export timelapse"),
+            Self::EncodeTimelapse(_) => String::from("Encode timelapse"),
+            Self::DoneExportTimelapse(_) => String::from("Done export timelapse"),
+            Self::UpdateAdjustmentsData(_) => String::from("Update adjustments data"),
+            Self::ExportWithAdjustments => String::from("Export with adjustments"),
             Self::UpdatePostData(_) => String::from("Update post data"),
+            Self::UpdateResizeData(_) => String::from("Update resize data"),
             Self::DeleteDrawing => String::from("Delete drawing"),
             Self::ToggleModal(_) => String::from("Toggle modal"),
+            Self::EnableCollaboration => String::from("Enable collaboration"),
+            Self::ReceivedTool { .. } => String::from("Received tool"),
+            Self::ReceivedCursor { .. } => String::from("Received cursor"),
+            Self::ExpireCursors => String::from("Expire cursors"),
+            Self::CountCollaborators => String::from("Count collaborators"),
+            Self::CountedCollaborators(_) => String::from("Counted collaborators"),
+            Self::ToggleLock => String::from("Toggle lock"),
+            Self::LoadedAccess { .. } => String::from("Loaded access"),
+            Self::UpdateShareData(_) => String::from("Update share data"),
+            Self::ShareWith(_, _) => String::from("Share with user"),
+            Self::ShareWithTag => String::from("Share with user tag"),
+            Self::UpdateSnapshotName(_) => String::from("Update snapshot name"),
+            Self::SetAutoSave(_) => String::from("Set auto-save"),
             Self::ErrorHandler(_) => String::from("Handle error"),
         }
     }
@@ -89,11 +200,47 @@ pub struct Drawing {
     /// The new post data.
     post_data: PostData,
 
+    /// The data of the resize dialog.
+    resize_data: ResizeData,
+
+    /// The data of the timelapse export dialog.
+    timelapse_data: TimelapseData,
+
+    /// The data of the export adjustments dialog.
+    adjustments_data: AdjustmentsData,
+
     /// The save mode of the drawing.
     save_mode: SaveMode,
 
+    /// The challenge the drawing will be submitted to when posted, if any.
+    challenge: Option<Uuid>,
+
     /// The stack of modals displayed.
     modal_stack: ModalStack<ModalTypes>,
+
+    /// Tells whether the changes of other users are received in real time.
+    collaborating: bool,
+
+    /// The number of other editing sessions collaborating on the drawing.
+    collaborators: usize,
+
+    /// Tells whether the drawing is read-only.
+    is_locked: bool,
+
+    /// The id of the user that owns the drawing.
+    owner: Option<Uuid>,
+
+    /// The permissions of the users the drawing is shared with.
+    permissions: HashMap<Uuid, DrawingPermission>,
+
+    /// The data of the share panel.
+    share_data: ShareData,
+
+    /// The moment the position of the cursor was last sent to the collaborators.
+    cursor_sent: Option<Instant>,
+
+    /// The name of the snapshot that will be created.
+    snapshot_name: String,
 }
 
 impl Drawing {
@@ -103,18 +250,36 @@ impl Drawing {
         let mut uuid = *self.canvas.get_id();
         if uuid != Uuid::from_bytes([0; 16]) {
             if let Some(db) = globals.get_db() {
-                Command::perform(
-                    async move { database::drawing::get_drawing(&db, uuid).await },
-                    move |res| match res {
-                        Ok((layers, tools)) => CanvasMessage::Loaded {
-                            layers,
-                            tools,
-                            json_tools: None,
-                        }
-                        .into(),
-                        Err(err) => Message::Error(err),
-                    },
-                )
+                let access_db = db.clone();
+
+                Command::batch(vec![
+                    Command::perform(
+                        async move { database::drawing::get_drawing(&db, uuid).await },
+                        move |res| match res {
+                            Ok((layers, tools, size, guides)) => CanvasMessage::Loaded {
+                                layers,
+                                tools,
+                                json_tools: None,
+                                size,
+                                guides,
+                            }
+                            .into(),
+                            Err(err) => Message::Error(err),
+                        },
+                    ),
+                    Command::perform(
+                        async move { database::drawing::get_access(&access_db, uuid).await },
+                        |res| match res {
+                            Ok((locked, owner, permissions)) => DrawingMessage::LoadedAccess {
+                                locked,
+                                owner,
+                                permissions,
+                            }
+                            .into(),
+                            Err(err) => Message::Error(err),
+                        },
+                    ),
+                ])
             } else {
                 Command::none()
             }
@@ -124,6 +289,9 @@ impl Drawing {
 
             if let Some(db) = globals.get_db() {
                 let user_id = globals.get_user().unwrap().get_id();
+                self.owner = Some(user_id);
+
+                let size = globals.get_preferences().get_canvas_size();
 
                 Command::batch(vec![
                     Command::perform(
@@ -147,6 +315,8 @@ impl Drawing {
                                 layers: vec![layer],
                                 tools: vec![],
                                 json_tools: None,
+                                size: Some(size),
+                                guides: vec![],
                             }
                             .into(),
                             Err(err) => Message::Error(err),
@@ -175,15 +345,21 @@ impl Drawing {
         default_json.insert("tools", JsonValue::Array(vec![]));
         default_json.insert("name", JsonValue::String(String::from("New drawing")));
 
+        let size = globals.get_preferences().get_canvas_size();
+        default_json.insert("width", JsonValue::Number(size.0.into()));
+        default_json.insert("height", JsonValue::Number(size.1.into()));
+
         let mut uuid = *self.canvas.get_id();
         if uuid != Uuid::from_bytes([0; 16]) {
             Command::perform(
                 async move { services::drawing::get_drawing_offline(uuid).await },
                 |result| match result {
-                    Ok((layers, tools, json_tools)) => CanvasMessage::Loaded {
+                    Ok((layers, tools, json_tools, size, guides)) => CanvasMessage::Loaded {
                         layers,
                         tools,
                         json_tools: Some(json_tools),
+                        size,
+                        guides,
                     }
                     .into(),
                     Err(err) => Message::Error(err),
@@ -207,6 +383,8 @@ impl Drawing {
                         layers: vec![(default_id, "New layer".to_string())],
                         tools: vec![],
                         json_tools: Some(vec![]),
+                        size: Some(size),
+                        guides: vec![],
                     }
                     .into(),
                 ),
@@ -219,12 +397,37 @@ impl Drawing {
         message: &CanvasMessage,
         globals: &mut Globals,
     ) -> Command<Message> {
+        if (self.is_locked || self.get_permission(globals) < DrawingPermission::Edit)
+            && message.is_modifying()
+        {
+            return match message {
+                CanvasMessage::Resize { .. } => self.update(
+                    globals,
+                    &DrawingMessage::ToggleModal(ModalTypes::ResizeDialog),
+                ),
+                _ => Command::none(),
+            };
+        }
+
         let mut commands = vec![];
 
         match message {
-            CanvasMessage::Save | CanvasMessage::Saved => commands.push(self.update(
+            CanvasMessage::CursorMoved(position) => {
+                return self.send_cursor(*position, globals);
+            }
+            CanvasMessage::Save | CanvasMessage::Saved { automatic: false } => {
+                commands.push(self.update(
+                    globals,
+                    &DrawingMessage::ToggleModal(ModalTypes::WaitScreen(String::from("Saving..."))),
+                ))
+            }
+            CanvasMessage::Resize { .. } => commands.push(
+                self.update(globals, &DrawingMessage::ToggleModal(ModalTypes::ResizeDialog)),
+            ),
+            CanvasMessage::CreateSnapshot(_) => self.snapshot_name.clear(),
+            CanvasMessage::RestoreSnapshot(_) => commands.push(self.update(
                 globals,
-                &DrawingMessage::ToggleModal(ModalTypes::WaitScreen(String::from("Saving..."))),
+                &DrawingMessage::ToggleModal(ModalTypes::SnapshotPanel),
             )),
             _ => {}
         }
@@ -237,8 +440,10 @@ impl Drawing {
     fn post_drawing(&mut self, globals: &mut Globals) -> Command<Message> {
         let document = self.canvas.get_svg().as_document();
         let db = globals.get_db().unwrap();
+        let rate_limiter = globals.get_rate_limiter();
         let user_id = globals.get_user().unwrap().get_id();
         let description = self.post_data.get_description().text();
+        let challenge = self.challenge;
 
         let tags: Vec<String> = self
             .post_data
@@ -267,7 +472,16 @@ impl Drawing {
             wait_modal_command,
             Command::perform(
                 async move {
-                    services::drawing::create_post(user_id, &document, description, tags, &db).await
+                    services::drawing::create_post(
+                        user_id,
+                        &document,
+                        description,
+                        tags,
+                        challenge,
+                        &db,
+                        &rate_limiter,
+                    )
+                    .await
                 },
                 |res| match res {
                     Ok(_) => {
@@ -283,7 +497,7 @@ impl Drawing {
         let document = self.canvas.get_svg().as_document();
 
         let download = Command::perform(
-            async move { services::drawing::download_drawing(&document).await },
+            async move { services::drawing::download_drawing(&document, vec![]).await },
             |result| match result {
                 Ok(_) => Message::None,
                 Err(err) => Message::Error(err),
@@ -296,6 +510,72 @@ impl Drawing {
         ])
     }
 
+    fn export_with_adjustments(&mut self, globals: &mut Globals) -> Command<Message> {
+        let document = self.canvas.get_svg().as_document();
+        let filters = self.adjustments_data.get_filters();
+
+        let download = Command::perform(
+            async move { services::drawing::download_drawing(&document, filters).await },
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        );
+
+        Command::batch(vec![
+            self.update(
+                globals,
+                &DrawingMessage::ToggleModal(ModalTypes::AdjustmentsDialog),
+            ),
+            download,
+        ])
+    }
+
+    fn export_timelapse(&mut self, globals: &mut Globals) -> Command<Message> {
+        if self.timelapse_data.get_options().is_none() {
+            return Command::none();
+        }
+
+        let close_modal_command = self.update(
+            globals,
+            &DrawingMessage::ToggleModal(ModalTypes::TimelapseDialog),
+        );
+
+        Command::batch(vec![
+            close_modal_command,
+            Command::perform(
+                services::drawing::choose_timelapse_file(),
+                |path| match path {
+                    Some(path) => DrawingMessage::EncodeTimelapse(path).into(),
+                    None => Message::None,
+                },
+            ),
+        ])
+    }
+
+    fn encode_timelapse(&mut self, path: PathBuf, globals: &mut Globals) -> Command<Message> {
+        let Some((frame_rate, step)) = self.timelapse_data.get_options() else {
+            return Command::none();
+        };
+        let frames = self.canvas.get_timeline(step);
+
+        let wait_modal_command = self.update(
+            globals,
+            &DrawingMessage::ToggleModal(ModalTypes::WaitScreen(format!(
+                "Encoding {} frames...",
+                frames.len()
+            ))),
+        );
+
+        Command::batch(vec![
+            wait_modal_command,
+            Command::perform(
+                async move { services::drawing::export_timelapse(frames, frame_rate, path).await },
+                |result| DrawingMessage::DoneExportTimelapse(result).into(),
+            ),
+        ])
+    }
+
     fn delete_drawing(&mut self, globals: &mut Globals) -> Command<Message> {
         let modal_command = self.update(
             globals,
@@ -326,6 +606,166 @@ impl Drawing {
         ])
     }
 
+    /// Sends the position of the cursor to the collaborators, if enough time has passed since
+    /// it was last sent.
+    fn send_cursor(&mut self, position: Point, globals: &Globals) -> Command<Message> {
+        if !self.collaborating || self.canvas.is_offline() {
+            return Command::none();
+        }
+        if self
+            .cursor_sent
+            .is_some_and(|sent| sent.elapsed() < CURSOR_SEND_INTERVAL)
+        {
+            return Command::none();
+        }
+        let (Some(db), Some(user)) = (globals.get_db(), globals.get_user()) else {
+            return Command::none();
+        };
+
+        self.cursor_sent = Some(Instant::now());
+
+        let canvas_id = *self.canvas.get_id();
+        let session = self.canvas.get_session();
+        let user_id = user.get_id();
+        let name = user.get_username().clone();
+
+        Command::perform(
+            async move {
+                database::drawing::update_cursor(
+                    &db,
+                    canvas_id,
+                    session,
+                    user_id,
+                    name,
+                    (position.x, position.y),
+                )
+                .await
+            },
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    /// Returns the permission the logged in user has on the drawing. The owner is an
+    /// administrator, and offline drawings can always be edited.
+    fn get_permission(&self, globals: &Globals) -> DrawingPermission {
+        if self.canvas.is_offline() {
+            return DrawingPermission::Admin;
+        }
+
+        match globals.get_user() {
+            Some(user) if Some(user.get_id()) == self.owner => DrawingPermission::Admin,
+            Some(user) => self
+                .permissions
+                .get(&user.get_id())
+                .copied()
+                .unwrap_or(DrawingPermission::View),
+            None => DrawingPermission::View,
+        }
+    }
+
+    fn toggle_lock(&mut self, globals: &mut Globals) -> Command<Message> {
+        if self.get_permission(globals) < DrawingPermission::Admin {
+            return Command::perform(async {}, |()| {
+                Message::Error(debug_message!("Only administrators can lock the drawing!").into())
+            });
+        }
+        let Some(db) = globals.get_db() else {
+            return Command::none();
+        };
+
+        let user_id = globals.get_user().unwrap().get_id();
+
+        self.is_locked = !self.is_locked;
+        let locked = self.is_locked;
+        let canvas_id = *self.canvas.get_id();
+
+        Command::perform(
+            async move { database::drawing::set_lock(&db, user_id, canvas_id, locked).await },
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    fn share_with(
+        &mut self,
+        user_id: Uuid,
+        permission: DrawingPermission,
+        globals: &mut Globals,
+    ) -> Command<Message> {
+        if self.get_permission(globals) < DrawingPermission::Admin {
+            return Command::perform(async {}, |()| {
+                Message::Error(debug_message!("Only administrators can share the drawing!").into())
+            });
+        }
+        if Some(user_id) == self.owner {
+            return Command::none();
+        }
+        let Some(db) = globals.get_db() else {
+            return Command::none();
+        };
+
+        let sharer_id = globals.get_user().unwrap().get_id();
+
+        let is_new = self.permissions.insert(user_id, permission).is_none();
+        let canvas_id = *self.canvas.get_id();
+
+        let mut commands = vec![Command::perform(
+            async move {
+                database::drawing::set_permission(&db, sharer_id, canvas_id, user_id, permission)
+                    .await
+            },
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )];
+
+        if is_new && self.share_data.get_user(&user_id).is_none() {
+            commands.push(self.load_share_users(vec![user_id], globals));
+        }
+
+        Command::batch(commands)
+    }
+
+    fn share_with_tag(&mut self, globals: &mut Globals) -> Command<Message> {
+        let Some(db) = globals.get_db() else {
+            return Command::none();
+        };
+
+        let user_tag = self.share_data.get_user_tag().clone();
+        let permission = self.share_data.get_permission();
+        self.share_data
+            .update(UpdateShareData::UserTag(String::new()));
+
+        Command::perform(
+            async move { database::posts::get_user_by_tag(&db, user_tag).await },
+            move |result| match result {
+                Ok(user) => DrawingMessage::ShareWith(user.get_id(), permission).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    /// Loads the data of the given users, to be displayed in the share panel.
+    fn load_share_users(&self, ids: Vec<Uuid>, globals: &Globals) -> Command<Message> {
+        let Some(db) = globals.get_db() else {
+            return Command::none();
+        };
+
+        Command::perform(
+            async move { database::drawing::get_users(&db, ids).await },
+            |result| match result {
+                Ok(users) => DrawingMessage::UpdateShareData(UpdateShareData::Users(users)).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
     fn toggle_modal(&mut self, modal: &ModalTypes, globals: &mut Globals) -> Command<Message> {
         self.modal_stack.toggle_modal(modal.clone());
 
@@ -350,6 +790,42 @@ impl Drawing {
                     Command::none()
                 }
             }
+            ModalTypes::ResizeDialog => {
+                let (width, height) = self.canvas.get_size();
+                self.resize_data.update(UpdateResizeData::Reset(width, height));
+
+                Command::none()
+            }
+            ModalTypes::AdjustmentsDialog => {
+                self.adjustments_data.update(UpdateAdjustmentsData::Reset);
+                let document = self.canvas.get_svg().as_document();
+
+                Command::perform(
+                    utils::encoder::render_thumbnail(document, ADJUSTMENTS_PREVIEW_SIZE),
+                    |result| match result {
+                        Ok(thumbnail) => DrawingMessage::UpdateAdjustmentsData(
+                            UpdateAdjustmentsData::Thumbnail(thumbnail),
+                        )
+                        .into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            ModalTypes::SnapshotPanel => self.update(globals, &CanvasMessage::LoadSnapshots.into()),
+            ModalTypes::SharePanel => {
+                let ids = self
+                    .permissions
+                    .keys()
+                    .filter(|id| self.share_data.get_user(id).is_none())
+                    .cloned()
+                    .collect::<Vec<Uuid>>();
+
+                if ids.is_empty() {
+                    Command::none()
+                } else {
+                    self.load_share_users(ids, globals)
+                }
+            }
             _ => Command::none(),
         }
     }
@@ -366,6 +842,9 @@ pub struct DrawingOptions {
 
     /// The save mode of the drawing.
     save_mode: Option<SaveMode>,
+
+    /// The challenge the drawing will be submitted to when posted.
+    challenge: Option<Uuid>,
 }
 
 impl DrawingOptions {
@@ -375,8 +854,15 @@ impl DrawingOptions {
             uuid,
             name,
             save_mode,
+            challenge: None,
         }
     }
+
+    /// Sets the challenge the drawing will be submitted to when posted.
+    pub fn challenge(mut self, challenge: Uuid) -> Self {
+        self.challenge = Some(challenge);
+        self
+    }
 }
 
 impl Scene for Drawing {
@@ -392,16 +878,47 @@ impl Scene for Drawing {
                 .width(Length::Fixed(800.0))
                 .height(Length::Fixed(600.0)),
             post_data: Default::default(),
-            save_mode: SaveMode::Online,
+            resize_data: Default::default(),
+            timelapse_data: Default::default(),
+            adjustments_data: Default::default(),
+            save_mode: globals.get_preferences().get_save_mode(),
+            challenge: None,
             modal_stack: ModalStack::new(),
+            collaborating: false,
+            collaborators: 0,
+            is_locked: false,
+            owner: None,
+            permissions: HashMap::new(),
+            share_data: Default::default(),
+            cursor_sent: None,
+            snapshot_name: String::new(),
         };
 
         let set_tool = Command::perform(async {}, |_| {
             CanvasMessage::ChangeTool(Box::new(LinePending::None)).into()
         });
 
+        let mut save_recent = Command::none();
         if let Some(options) = options {
+            let is_stored = options.uuid.is_some();
             drawing.apply_options(options);
+
+            if is_stored {
+                globals.add_recent_drawing(
+                    *drawing.canvas.get_id(),
+                    drawing.canvas.get_name().clone(),
+                    drawing.save_mode,
+                );
+
+                let recent_drawings = globals.get_recent_drawings().iter().cloned().collect();
+                save_recent = Command::perform(
+                    services::main::save_recent_drawings(recent_drawings),
+                    |result| match result {
+                        Ok(_) => Message::None,
+                        Err(err) => Message::Error(err),
+                    },
+                );
+            }
         }
 
         let init_data: Command<Message> = match drawing.save_mode {
@@ -409,7 +926,7 @@ impl Scene for Drawing {
             SaveMode::Offline => drawing.init_offline(globals),
         };
 
-        return (drawing, Command::batch([set_tool, init_data]));
+        return (drawing, Command::batch([set_tool, init_data, save_recent]));
     }
 
     fn get_title(&self) -> String {
@@ -430,6 +947,8 @@ impl Scene for Drawing {
         if let Some(save_mode) = options.save_mode {
             self.save_mode = save_mode;
         }
+
+        self.challenge = options.challenge;
     }
 
     fn update(&mut self, globals: &mut Globals, message: &Self::Message) -> Command<Message> {
@@ -439,10 +958,151 @@ impl Scene for Drawing {
                 self.post_data.update(update.clone());
                 Command::none()
             }
+            DrawingMessage::UpdateResizeData(update) => {
+                self.resize_data.update(update.clone());
+                Command::none()
+            }
             DrawingMessage::PostDrawing => self.post_drawing(globals),
             DrawingMessage::SaveAs => self.save_as(globals),
+            DrawingMessage::UpdateTimelapseData(update) => {
+                self.timelapse_data.update(update.clone());
+                Command::none()
+            }
+            DrawingMessage::ExportTimelapse => self.export_timelapse(globals),
+            DrawingMessage::EncodeTimelapse(path) => self.encode_timelapse(path.clone(), globals),
+            DrawingMessage::DoneExportTimelapse(result) => {
+                let close_modal_command = self.update(
+                    globals,
+                    &DrawingMessage::ToggleModal(ModalTypes::WaitScreen(String::new())),
+                );
+
+                match result {
+                    Ok(()) => close_modal_command,
+                    Err(err) => {
+                        let err = err.clone();
+
+                        Command::batch(vec![
+                            close_modal_command,
+                            Command::perform(async {}, move |_| Message::Error(err)),
+                        ])
+                    }
+                }
+            }
+            DrawingMessage::UpdateAdjustmentsData(update) => {
+                self.adjustments_data.update(update.clone());
+                Command::none()
+            }
+            DrawingMessage::ExportWithAdjustments => self.export_with_adjustments(globals),
+            DrawingMessage::ExportLayeredPdf => {
+                let layers = self.canvas.get_svg().as_layer_documents();
+
+                Command::perform(
+                    async move { services::drawing::export_layered_pdf(layers).await },
+                    |result| match result {
+                        Ok(_) => Message::None,
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
             DrawingMessage::DeleteDrawing => self.delete_drawing(globals),
             DrawingMessage::ToggleModal(modal) => self.toggle_modal(modal, globals),
+            DrawingMessage::EnableCollaboration => {
+                self.collaborating = !self.collaborating;
+                self.collaborators = 0;
+                self.canvas.clear_collab_cursors();
+
+                if self.collaborating {
+                    return self.update(globals, &DrawingMessage::CountCollaborators);
+                }
+
+                // The cursor of the session would otherwise be counted until it expires.
+                let Some(db) = globals.get_db() else {
+                    return Command::none();
+                };
+                let canvas_id = *self.canvas.get_id();
+                let session = self.canvas.get_session();
+
+                Command::perform(
+                    async move { database::drawing::delete_cursor(&db, canvas_id, session).await },
+                    |result| match result {
+                        Ok(_) => Message::None,
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            DrawingMessage::ReceivedTool { tool, layer, .. } => self
+                .canvas
+                .update(globals, CanvasMessage::UseRemoteTool(tool.clone(), *layer)),
+            DrawingMessage::ReceivedCursor { session, cursor } => {
+                self.canvas.set_collab_cursor(*session, cursor.clone());
+                Command::none()
+            }
+            DrawingMessage::ExpireCursors => {
+                self.canvas.expire_collab_cursors(CURSOR_EXPIRATION);
+                Command::none()
+            }
+            DrawingMessage::CountCollaborators => {
+                let Some(db) = globals.get_db() else {
+                    return Command::none();
+                };
+                let canvas_id = *self.canvas.get_id();
+                let session = self.canvas.get_session();
+
+                Command::perform(
+                    async move {
+                        database::drawing::count_collaborators(&db, canvas_id, session).await
+                    },
+                    |result| match result {
+                        Ok(count) => DrawingMessage::CountedCollaborators(count).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            DrawingMessage::CountedCollaborators(count) => {
+                self.collaborators = *count;
+                Command::none()
+            }
+            DrawingMessage::ToggleLock => self.toggle_lock(globals),
+            DrawingMessage::LoadedAccess {
+                locked,
+                owner,
+                permissions,
+            } => {
+                self.is_locked = *locked;
+                self.owner = Some(*owner);
+                self.permissions = permissions.clone();
+
+                Command::none()
+            }
+            DrawingMessage::UpdateShareData(update) => {
+                self.share_data.update(update.clone());
+                Command::none()
+            }
+            DrawingMessage::ShareWith(user_id, permission) => {
+                self.share_with(*user_id, *permission, globals)
+            }
+            DrawingMessage::ShareWithTag => self.share_with_tag(globals),
+            DrawingMessage::UpdateSnapshotName(name) => {
+                self.snapshot_name = name.clone();
+                Command::none()
+            }
+            DrawingMessage::SetAutoSave(AutoSaveInterval(interval)) => {
+                globals.set_auto_save_interval(*interval);
+
+                Command::perform(
+                    services::app_settings::save_preferences(globals.get_preferences().clone()),
+                    |result| match result {
+                        Ok(_) => Message::None,
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            DrawingMessage::ErrorHandler(
+                Error::AuthError(AuthError::UserBanned) | Error::RateLimited { .. },
+            ) => self.update(
+                globals,
+                &DrawingMessage::ToggleModal(ModalTypes::WaitScreen(String::new())),
+            ),
             DrawingMessage::ErrorHandler(_) => Command::none(),
         }
     }
@@ -454,9 +1114,18 @@ impl Scene for Drawing {
         let style_section = services::drawing::style_section(&self.canvas);
         let layers_section = services::drawing::layers_section(&self.canvas);
         let menu_section = services::drawing::menu_section(globals);
+        let header_section = services::drawing::header_section(
+            globals,
+            self.canvas.is_offline(),
+            self.collaborating.then_some(self.collaborators),
+            self.is_locked,
+            self.get_permission(globals),
+            self.canvas.get_auto_saved(),
+        );
 
         let underlay = services::drawing::underlay(
             &self.canvas,
+            header_section,
             tools_section,
             style_section,
             layers_section,
@@ -466,6 +1135,20 @@ impl Scene for Drawing {
         let modal_transform = |modal_type: ModalTypes| -> Element<Message, Theme, Renderer> {
             match modal_type {
                 ModalTypes::PostPrompt => services::drawing::post_prompt(&self.post_data),
+                ModalTypes::ResizeDialog => services::drawing::resize_dialog(&self.resize_data),
+                ModalTypes::TimelapseDialog => {
+                    services::drawing::timelapse_dialog(&self.timelapse_data)
+                }
+                ModalTypes::AdjustmentsDialog => {
+                    services::drawing::adjustments_dialog(&self.adjustments_data)
+                }
+                ModalTypes::HistoryPanel => services::drawing::history_panel(&self.canvas, globals),
+                ModalTypes::SharePanel => {
+                    services::drawing::share_panel(&self.share_data, &self.permissions)
+                }
+                ModalTypes::SnapshotPanel => {
+                    services::drawing::snapshot_panel(&self.canvas, &self.snapshot_name)
+                }
                 ModalTypes::WaitScreen(message) => Container::new(WaitPanel::new(message))
                     .style(iced::widget::container::bordered_box)
                     .into(),
@@ -475,6 +1158,58 @@ impl Scene for Drawing {
         self.modal_stack.get_modal(underlay, modal_transform)
     }
 
+    fn subscription(&self, globals: &Globals) -> Subscription<Message> {
+        let mut subscriptions = vec![];
+
+        if let Some(interval) = globals.get_auto_save_interval() {
+            subscriptions.push(iced::time::every(interval).map(|_| CanvasMessage::AutoSave.into()));
+            // Keeps the time since the last automatic save up to date.
+            subscriptions.push(iced::time::every(Duration::from_secs(1)).map(|_| Message::None));
+        }
+
+        if self.canvas.is_offline() {
+            let canvas_id = *self.canvas.get_id();
+
+            subscriptions.push(iced::subscription::channel(
+                ("data file", canvas_id),
+                100,
+                move |output| services::drawing::watch_data_file(canvas_id, output),
+            ));
+        }
+
+        if let (Some(db), true) = (
+            globals.get_db(),
+            self.collaborating && !self.canvas.is_offline(),
+        ) {
+            let canvas_id = *self.canvas.get_id();
+            let session = self.canvas.get_session();
+            let cursors_db = db.clone();
+
+            subscriptions.push(iced::subscription::channel(
+                ("tools", canvas_id, session),
+                100,
+                move |output| services::drawing::watch_tools(db, canvas_id, session, output),
+            ));
+            subscriptions.push(iced::subscription::channel(
+                ("cursors", canvas_id, session),
+                100,
+                move |output| {
+                    services::drawing::watch_cursors(cursors_db, canvas_id, session, output)
+                },
+            ));
+            subscriptions.push(
+                iced::time::every(Duration::from_secs(1))
+                    .map(|_| DrawingMessage::ExpireCursors.into()),
+            );
+            subscriptions.push(
+                iced::time::every(COLLABORATORS_COUNT_INTERVAL)
+                    .map(|_| DrawingMessage::CountCollaborators.into()),
+            );
+        }
+
+        Subscription::batch(subscriptions)
+    }
+
     fn handle_error(&mut self, globals: &mut Globals, error: &Error) -> Command<Message> {
         self.update(globals, &DrawingMessage::ErrorHandler(error.clone()))
     }
@@ -1,40 +1,109 @@
 use std::any::Any;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
 
 use crate::database;
+use crate::debug_message;
 use crate::utils::errors::Error;
-use crate::widgets::ModalStack;
+use crate::widgets::{ModalStack, WaitPanel};
 use iced::alignment::{Horizontal, Vertical};
-use iced::widget::{Button, Column, Container, Text};
-use iced::{Alignment, Command, Element, Length, Renderer, Theme};
+use iced::widget::{text_input, Button, Column, Container, Row, Text};
+use iced::{mouse, Alignment, Command, Element, Event, Length, Renderer, Subscription, Theme};
 use mongodb::bson::Uuid;
 
-use crate::scene::{Globals, Message, Scene, SceneMessage};
+use crate::scene::{DbStatus, Globals, Message, Scene, SceneMessage};
 use crate::scenes::scenes::Scenes;
 use crate::scenes::services;
 
+use crate::scenes::data::auth::User;
+use crate::scenes::data::challenge::Challenge;
 use crate::scenes::data::drawing::SaveMode;
 use crate::scenes::drawing::DrawingOptions;
 
 use crate::scenes::data::main::*;
 
+/// How long the list of online drawings is kept before it is fetched again.
+const DRAWINGS_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
 /// The [Messages](SceneMessage) of the main [Scene].
 #[derive(Clone)]
 pub enum MainMessage {
     /// Opens or closes the given modal.
     ToggleModal(ModalType),
 
-    /// Triggered when the drawings(either online or offline) are loaded.
-    LoadedDrawings(Vec<(Uuid, String)>, MainTabIds),
+    /// Triggered when the drawings(either online or offline) are loaded. Holds the page of the
+    /// offline drawings that should be displayed.
+    LoadedDrawings(Vec<(Uuid, String)>, MainTabIds, usize),
+
+    /// Changes the displayed page of the offline drawings.
+    ChangePage(usize),
+
+    /// Updates the filter applied to the names of the displayed drawings.
+    UpdateDrawingFilter(String),
+
+    /// Starts editing the name of the given drawing.
+    StartRename(Uuid, String),
+
+    /// Updates the name input of the drawing being renamed.
+    UpdateNameInput(String),
+
+    /// Changes the name of the given drawing.
+    RenameDrawing(Uuid, String, SaveMode),
+
+    /// Checks whether the name input of the drawing being renamed is still focused.
+    CheckNameFocus,
+
+    /// Changes the name of the drawing being renamed, once its name input has lost focus.
+    NameInputBlurred,
 
     /// Deletes the given drawing.
     DeleteDrawing(Uuid, SaveMode),
 
+    /// Creates a copy of the given drawing.
+    DuplicateDrawing(Uuid, SaveMode),
+
+    /// Triggered when creating the copy with the given id has failed.
+    DuplicateFailed(Uuid, SaveMode, Error),
+
+    /// Renders the preview of the given offline drawing again from its stored data.
+    RegenerateThumbnail(Uuid),
+
     /// Logs out the user from their account.
     LogOut,
 
     /// Changes the tab for the drawings online/offline tab bar.
     SelectTab(MainTabIds),
 
+    /// Loads the drawings on the active tab again.
+    RefreshDrawings,
+
+    /// Opens a file dialog for importing drawings from a ZIP archive.
+    ImportArchive,
+
+    /// Triggered when the drawings from an archive have been read.
+    ArchiveRead(Vec<ArchivedDrawing>),
+
+    /// Triggered when a drawing from an archive has been imported; holds the number of
+    /// imported drawings and the total number of drawings.
+    ImportProgress(usize, usize),
+
+    /// Selects or deselects the offline drawing with the given position on the current page, for
+    /// exporting.
+    SetSelected(usize, bool),
+
+    /// Exports the given offline drawings to a ZIP archive.
+    ExportSelected(Vec<Uuid>),
+
+    /// Uploads the given offline drawings to the database.
+    SyncToOnline(Vec<Uuid>),
+
+    /// Triggered when an offline drawing has been uploaded; holds the number of uploaded
+    /// drawings and the total number of drawings.
+    SyncProgress(usize, usize),
+
+    /// Triggered when the challenge that is currently active has been loaded.
+    LoadedChallenge(Option<Challenge>),
+
     /// Handles errors.
     ErrorHandler(Error),
 }
@@ -53,10 +122,29 @@ impl SceneMessage for MainMessage {
     fn get_name(&self) -> String {
         match self {
             Self::ToggleModal { .. } => String::from("Toggle modal"),
-            Self::LoadedDrawings(_, _) => String::from("Loaded drawings"),
+            Self::LoadedDrawings(_, _, _) => String::from("Loaded drawings"),
+            Self::ChangePage(_) => String::from("Change page"),
+            Self::UpdateDrawingFilter(_) => String::from("Update drawing filter"),
+            Self::StartRename(_, _) => String::from("Start rename"),
+            Self::UpdateNameInput(_) => String::from("Update name input"),
+            Self::RenameDrawing(_, _, _) => String::from("Rename drawing"),
+            Self::CheckNameFocus => String::from("Check name focus"),
+            Self::NameInputBlurred => String::from("Name input blurred"),
             Self::DeleteDrawing(_, _) => String::from("Delete drawing"),
+            Self::DuplicateDrawing(_, _) => String::from("Duplicate drawing"),
+            Self::DuplicateFailed(_, _, _) => String::from("Duplicate failed"),
+            Self::RegenerateThumbnail(_) => String::from("Regenerate thumbnail"),
             Self::LogOut => String::from("Logged out"),
             Self::SelectTab(_) => String::from("Select tab"),
+            Self::RefreshDrawings => String::from("Refresh drawings"),
+            Self::ImportArchive => String::from("Import archive"),
+            Self::ArchiveRead(_) => String::from("Archive read"),
+            Self::ImportProgress(_, _) => String::from("Import progress"),
+            Self::SetSelected(_, _) => String::from("Set selected"),
+            Self::ExportSelected(_) => String::from("Export selected"),
+            Self::SyncToOnline(_) => String::from("Sync to online"),
+            Self::SyncProgress(_, _) => String::from("Sync progress"),
+            Self::LoadedChallenge(_) => String::from("Loaded challenge"),
             Self::ErrorHandler(_) => String::from("Handle error"),
         }
     }
@@ -64,6 +152,10 @@ impl SceneMessage for MainMessage {
     fn boxed_clone(&self) -> Box<dyn SceneMessage + 'static> {
         Box::new((*self).clone())
     }
+
+    fn is_bus_event(&self) -> bool {
+        matches!(self, Self::ImportProgress(_, _) | Self::SyncProgress(_, _))
+    }
 }
 
 impl Into<Box<dyn SceneMessage + 'static>> for Box<MainMessage> {
@@ -81,11 +173,47 @@ pub struct Main {
     /// The list of the users' drawings that are stored online.
     drawings_online: Option<Vec<(Uuid, String)>>,
 
+    /// The moment the list of online drawings has to be fetched again.
+    drawings_cache_expiry: Option<Instant>,
+
     /// The list of the users' drawings that are stored offline.
     drawings_offline: Option<Vec<(Uuid, String)>>,
 
     /// The id of the active tab on the drawing selection tab bar.
     active_tab: MainTabIds,
+
+    /// The drawings from an archive that are waiting to be imported.
+    import_queue: Vec<ArchivedDrawing>,
+
+    /// The progress of the current archive import, if there is one.
+    import_progress: Option<(usize, usize)>,
+
+    /// The offline drawings selected for exporting.
+    selected_drawings: HashSet<Uuid>,
+
+    /// The offline drawings waiting to be uploaded to the database.
+    sync_queue: Vec<(Uuid, String)>,
+
+    /// The progress of the current upload to the database, if there is one.
+    syncing: Option<(usize, usize)>,
+
+    /// The displayed page of the offline drawings.
+    page: usize,
+
+    /// The number of offline drawings displayed on a page.
+    page_size: usize,
+
+    /// The text the names of the displayed drawings are filtered by.
+    filter_input: String,
+
+    /// The id of the drawing whose name is being edited.
+    editing_name: Option<Uuid>,
+
+    /// The new name of the drawing being edited.
+    name_input: String,
+
+    /// The drawing challenge that is currently active, if there is one.
+    challenge: Option<Challenge>,
 }
 
 /// The [Main] scene has no optional data.
@@ -109,13 +237,16 @@ impl Main {
         &mut self,
         tab: &MainTabIds,
         drawings: &Vec<(Uuid, String)>,
+        page: usize,
     ) -> Command<Message> {
         match tab {
             MainTabIds::Offline => {
                 self.drawings_offline = Some(drawings.clone());
+                self.page = page.min(self.get_page_count().saturating_sub(1));
             }
             MainTabIds::Online => {
                 self.drawings_online = Some(drawings.clone());
+                self.drawings_cache_expiry = Some(Instant::now() + DRAWINGS_CACHE_TTL);
             }
         }
 
@@ -124,10 +255,39 @@ impl Main {
         Command::perform(async {}, move |_| MainMessage::SelectTab(tab).into())
     }
 
+    /// Returns the offline drawings whose names match the filter.
+    fn get_offline_filtered(&self) -> Option<Vec<&(Uuid, String)>> {
+        let filter = self.filter_input.to_lowercase();
+
+        self.drawings_offline.as_ref().map(|drawings| {
+            drawings
+                .iter()
+                .filter(|(_, name)| name.to_lowercase().contains(&filter))
+                .collect()
+        })
+    }
+
+    /// Returns the number of pages of offline drawings.
+    fn get_page_count(&self) -> usize {
+        self.get_offline_filtered()
+            .map_or(0, |drawings| drawings.len().div_ceil(self.page_size))
+    }
+
+    /// Returns the offline drawings on the current page.
+    fn get_offline_page(&self) -> Option<Vec<(Uuid, String)>> {
+        self.get_offline_filtered().map(|drawings| {
+            drawings
+                .into_iter()
+                .skip(self.page * self.page_size)
+                .take(self.page_size)
+                .cloned()
+                .collect()
+        })
+    }
+
     pub fn load_previews(&self, globals: &Globals) -> Command<Message> {
         let commands_offline = self
-            .drawings_offline
-            .clone()
+            .get_offline_page()
             .map_or(Command::none(), |drawings| {
                 globals.get_cache().insert_if_not(
                     drawings.iter().map(|(id, _)| *id),
@@ -149,7 +309,25 @@ impl Main {
                 )
             });
 
-        Command::batch(vec![commands_offline, commands_online])
+        let user_id = globals.get_user().map(User::get_id);
+        let commands_recent = globals.get_cache().insert_if_not(
+            globals
+                .get_recent_drawings()
+                .iter()
+                .filter_map(|(id, _, save_mode)| match save_mode {
+                    SaveMode::Offline => Some((*id, None)),
+                    SaveMode::Online => user_id.map(|user_id| (*id, Some(user_id))),
+                }),
+            |(id, _)| id,
+            |(id, user_id)| async move {
+                match user_id {
+                    Some(user_id) => services::main::load_preview_online((id, user_id)).await,
+                    None => services::main::load_preview_offline(id).await,
+                }
+            },
+        );
+
+        Command::batch(vec![commands_offline, commands_online, commands_recent])
     }
 
     /// Logs out the currently authenticated user.
@@ -169,10 +347,14 @@ impl Main {
     /// Switches to the tab of locally stored drawings.
     fn select_offline_tab(&mut self, _globals: &mut Globals) -> Command<Message> {
         if self.drawings_offline.is_none() {
+            let page = self.page;
+
             Command::perform(
                 async { services::main::get_drawings_offline().await },
-                |result| match result {
-                    Ok(list) => MainMessage::LoadedDrawings(list, MainTabIds::Offline).into(),
+                move |result| match result {
+                    Ok(list) => {
+                        MainMessage::LoadedDrawings(list, MainTabIds::Offline, page).into()
+                    }
                     Err(err) => Message::Error(err),
                 },
             )
@@ -181,9 +363,14 @@ impl Main {
         }
     }
 
-    /// Switches to the tab of remotely stored drawings.
+    /// Switches to the tab of remotely stored drawings. The drawings are fetched again if they
+    /// have not been loaded yet, or if they were loaded too long ago.
     fn select_online_tab(&mut self, globals: &mut Globals) -> Command<Message> {
-        if self.drawings_online.is_none() {
+        let expired = self
+            .drawings_cache_expiry
+            .map_or(true, |expiry| Instant::now() >= expiry);
+
+        if self.drawings_online.is_none() || expired {
             if let (Some(db), Some(user)) = (globals.get_db(), globals.get_user()) {
                 let user_id = user.get_id();
 
@@ -193,6 +380,7 @@ impl Main {
                         Ok(ref documents) => MainMessage::LoadedDrawings(
                             services::main::get_drawings_online(documents),
                             MainTabIds::Online,
+                            0,
                         )
                         .into(),
                         Err(err) => Message::Error(err),
@@ -206,6 +394,248 @@ impl Main {
         }
     }
 
+    /// Opens a file dialog and reads the drawings from the chosen archive.
+    fn import_archive(&mut self) -> Command<Message> {
+        if self.import_progress.is_some() {
+            return Command::none();
+        }
+
+        Command::perform(
+            async { services::main::read_archive().await },
+            |result| match result {
+                Ok(Some(drawings)) => MainMessage::ArchiveRead(drawings).into(),
+                Ok(None) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    /// Imports the next drawing in the queue.
+    fn import_next(&mut self, globals: &mut Globals) -> Command<Message> {
+        let Some((done, total)) = self.import_progress else {
+            return Command::none();
+        };
+
+        if let Some(drawing) = self.import_queue.pop() {
+            Command::perform(
+                async move { services::main::import_drawing_offline(drawing).await },
+                move |result| match result {
+                    Ok(_) => MainMessage::ImportProgress(done + 1, total).into(),
+                    Err(err) => Message::Error(err),
+                },
+            )
+        } else {
+            self.import_progress = None;
+            self.drawings_offline = None;
+
+            if self.active_tab == MainTabIds::Offline {
+                self.select_offline_tab(globals)
+            } else {
+                Command::none()
+            }
+        }
+    }
+
+    /// Updates the drawing filter. The drawings of the active tab are searched for in the
+    /// database, or in the local metadata store.
+    fn update_filter(&mut self, filter: &String, globals: &mut Globals) -> Command<Message> {
+        self.filter_input = filter.clone();
+        self.page = 0;
+
+        if self.active_tab == MainTabIds::Offline {
+            let query = filter.clone();
+
+            return Command::perform(
+                async move {
+                    if query.is_empty() {
+                        database::local::get_drawings().await
+                    } else {
+                        database::local::search_drawings(&query).await
+                    }
+                },
+                |result| match result {
+                    Ok(list) => MainMessage::LoadedDrawings(list, MainTabIds::Offline, 0).into(),
+                    Err(err) => Message::Error(err),
+                },
+            );
+        }
+
+        if let (Some(db), Some(user)) = (globals.get_db(), globals.get_user()) {
+            let user_id = user.get_id();
+            let query = filter.clone();
+
+            Command::perform(
+                async move {
+                    if query.is_empty() {
+                        database::main::get_drawings(&db, user_id).await
+                    } else {
+                        database::main::search_drawings(&db, user_id, query).await
+                    }
+                },
+                |result| match result {
+                    Ok(ref documents) => MainMessage::LoadedDrawings(
+                        services::main::get_drawings_online(documents),
+                        MainTabIds::Online,
+                        0,
+                    )
+                    .into(),
+                    Err(err) => Message::Error(err),
+                },
+            )
+        } else {
+            Command::none()
+        }
+    }
+
+    /// Starts uploading the given offline drawings to the database.
+    fn sync_to_online(&mut self, ids: &Vec<Uuid>, globals: &mut Globals) -> Command<Message> {
+        if self.syncing.is_some() {
+            return Command::none();
+        }
+
+        self.sync_queue = self
+            .drawings_offline
+            .iter()
+            .flatten()
+            .filter(|(id, _)| ids.contains(id))
+            .cloned()
+            .collect();
+        self.syncing = Some((0, self.sync_queue.len()));
+        self.selected_drawings.clear();
+
+        self.sync_next(globals)
+    }
+
+    /// Uploads the next drawing in the queue.
+    fn sync_next(&mut self, globals: &mut Globals) -> Command<Message> {
+        let Some((done, total)) = self.syncing else {
+            return Command::none();
+        };
+
+        match (self.sync_queue.pop(), globals.get_db(), globals.get_user()) {
+            (Some((id, name)), Some(db), Some(user)) => {
+                let user_id = user.get_id();
+
+                Command::perform(
+                    async move {
+                        services::drawing::sync_drawing_online(&db, id, name, user_id).await
+                    },
+                    move |result| match result {
+                        Ok(_) => MainMessage::SyncProgress(done + 1, total).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            _ => {
+                self.sync_queue.clear();
+                self.syncing = None;
+                self.drawings_online = None;
+
+                Command::none()
+            }
+        }
+    }
+
+    /// Creates a copy of the given drawing and shows it right after the original in the list.
+    fn duplicate_drawing(
+        &mut self,
+        id: Uuid,
+        save_mode: SaveMode,
+        globals: &mut Globals,
+    ) -> Command<Message> {
+        let drawings = match save_mode {
+            SaveMode::Offline => self.drawings_offline.as_mut(),
+            SaveMode::Online => self.drawings_online.as_mut(),
+        };
+        let Some(drawings) = drawings else {
+            return Command::none();
+        };
+        let Some(position) = drawings.iter().position(|(drawing_id, _)| *drawing_id == id) else {
+            return Command::none();
+        };
+
+        let new_id = Uuid::new();
+        let name = format!("{} (copy)", drawings[position].1);
+        drawings.insert(position + 1, (new_id, name.clone()));
+
+        let db = globals.get_db();
+        let user_id = globals.get_user().map(|user| user.get_id());
+        let cache = globals.get_cache();
+
+        Command::perform(
+            async move {
+                match save_mode {
+                    SaveMode::Offline => {
+                        services::main::duplicate_drawing_offline(id, new_id, name).await?;
+                        let preview = services::main::load_preview_offline(new_id).await?;
+
+                        cache.insert(new_id, preview).await
+                    }
+                    SaveMode::Online => match (db, user_id) {
+                        (Some(db), Some(user_id)) => {
+                            database::drawing::duplicate_drawing(&db, id, new_id, user_id, name)
+                                .await?;
+                            let preview =
+                                services::main::load_preview_online((new_id, user_id)).await?;
+
+                            cache.insert(new_id, preview).await
+                        }
+                        _ => Err(debug_message!("No database connection.").into()),
+                    },
+                }
+            },
+            move |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => MainMessage::DuplicateFailed(new_id, save_mode, err).into(),
+            },
+        )
+    }
+
+    /// Changes the name of the given drawing, both in the list and in storage.
+    fn rename_drawing(
+        &mut self,
+        id: Uuid,
+        name: String,
+        save_mode: SaveMode,
+        globals: &mut Globals,
+    ) -> Command<Message> {
+        self.editing_name = None;
+
+        if name.is_empty() || name.len() > 40 {
+            return Command::none();
+        }
+
+        let drawings = match save_mode {
+            SaveMode::Offline => self.drawings_offline.as_mut(),
+            SaveMode::Online => self.drawings_online.as_mut(),
+        };
+        if let Some(drawings) = drawings {
+            for (drawing_id, drawing_name) in drawings.iter_mut() {
+                if *drawing_id == id {
+                    *drawing_name = name.clone();
+                }
+            }
+        }
+
+        let db = globals.get_db();
+
+        Command::perform(
+            async move {
+                match save_mode {
+                    SaveMode::Offline => services::main::rename_drawing_offline(id, name).await,
+                    SaveMode::Online => match db {
+                        Some(db) => database::main::rename_drawing(&db, id, name).await,
+                        None => Err(debug_message!("No database connection.").into()),
+                    },
+                }
+            },
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
     /// Sets the tab to the given value.
     fn select_tab(&mut self, tab_id: &MainTabIds, globals: &mut Globals) -> Command<Message> {
         self.active_tab = tab_id.clone();
@@ -221,21 +651,44 @@ impl Scene for Main {
     type Message = MainMessage;
     type Options = MainOptions;
 
-    fn new(options: Option<Self::Options>, _: &mut Globals) -> (Self, Command<Message>)
+    fn new(options: Option<Self::Options>, globals: &mut Globals) -> (Self, Command<Message>)
     where
         Self: Sized,
     {
         let mut main = Main {
             modals: ModalStack::new(),
             drawings_online: None,
+            drawings_cache_expiry: None,
             drawings_offline: None,
             active_tab: MainTabIds::Offline,
+            import_queue: vec![],
+            import_progress: None,
+            selected_drawings: HashSet::new(),
+            sync_queue: vec![],
+            syncing: None,
+            page: 0,
+            page_size: 20,
+            filter_input: String::new(),
+            editing_name: None,
+            name_input: String::new(),
+            challenge: None,
         };
         if let Some(options) = options {
             main.apply_options(options);
         }
 
-        (main, Command::none())
+        let load_challenge = match (globals.get_db(), globals.get_user()) {
+            (Some(db), Some(_)) => Command::perform(
+                async move { database::challenge::get_active_challenge(&db).await },
+                |result| match result {
+                    Ok(challenge) => MainMessage::LoadedChallenge(challenge).into(),
+                    Err(err) => Message::Error(err),
+                },
+            ),
+            _ => Command::none(),
+        };
+
+        (main, load_challenge)
     }
 
     fn get_title(&self) -> String {
@@ -247,12 +700,67 @@ impl Scene for Main {
     fn update(&mut self, globals: &mut Globals, message: &Self::Message) -> Command<Message> {
         match message {
             MainMessage::ToggleModal(modal) => self.toggle_modal(&modal, globals),
-            MainMessage::LoadedDrawings(drawings, tab) => self.loaded_drawings(&tab, &drawings),
+            MainMessage::LoadedDrawings(drawings, tab, page) => {
+                self.loaded_drawings(&tab, &drawings, *page)
+            }
+            MainMessage::UpdateDrawingFilter(filter) => self.update_filter(filter, globals),
+            MainMessage::RefreshDrawings => {
+                match self.active_tab {
+                    MainTabIds::Offline => self.drawings_offline = None,
+                    MainTabIds::Online => self.drawings_cache_expiry = None,
+                }
+
+                self.select_tab(&self.active_tab.clone(), globals)
+            }
+            MainMessage::StartRename(id, name) => {
+                self.editing_name = Some(*id);
+                self.name_input = name.clone();
+
+                text_input::focus(services::main::name_input_id())
+            }
+            MainMessage::UpdateNameInput(name) => {
+                self.name_input = name.clone();
+
+                Command::none()
+            }
+            MainMessage::RenameDrawing(id, name, save_mode) => {
+                self.rename_drawing(*id, name.clone(), *save_mode, globals)
+            }
+            MainMessage::CheckNameFocus => {
+                if self.editing_name.is_none() {
+                    return Command::none();
+                }
+
+                Command::widget(services::main::NameInputFocus::default())
+            }
+            MainMessage::NameInputBlurred => {
+                let Some(id) = self.editing_name else {
+                    return Command::none();
+                };
+
+                let online = self.drawings_online.as_ref().is_some_and(|drawings| {
+                    drawings.iter().any(|(drawing_id, _)| *drawing_id == id)
+                });
+                let save_mode = if online {
+                    SaveMode::Online
+                } else {
+                    SaveMode::Offline
+                };
+
+                self.rename_drawing(id, self.name_input.clone(), save_mode, globals)
+            }
+            MainMessage::ChangePage(page) => {
+                self.page = (*page).min(self.get_page_count().saturating_sub(1));
+                self.load_previews(globals)
+            }
             MainMessage::DeleteDrawing(id, save_mode) => {
+                globals.remove_recent_drawing(*id);
+                let recent_drawings = globals.get_recent_drawings().iter().cloned().collect();
                 let globals = globals.clone();
 
                 match save_mode {
                     SaveMode::Offline => {
+                        self.selected_drawings.remove(id);
                         self.drawings_offline
                             .as_mut()
                             .unwrap()
@@ -271,6 +779,8 @@ impl Scene for Main {
 
                 Command::perform(
                     async move {
+                        services::main::save_recent_drawings(recent_drawings).await?;
+
                         match save_mode {
                             SaveMode::Offline => {
                                 services::drawing::delete_drawing_offline(id).await
@@ -286,17 +796,96 @@ impl Scene for Main {
                     },
                 )
             }
+            MainMessage::DuplicateDrawing(id, save_mode) => {
+                self.duplicate_drawing(*id, *save_mode, globals)
+            }
+            MainMessage::DuplicateFailed(id, save_mode, err) => {
+                let drawings = match save_mode {
+                    SaveMode::Offline => self.drawings_offline.as_mut(),
+                    SaveMode::Online => self.drawings_online.as_mut(),
+                };
+                if let Some(drawings) = drawings {
+                    drawings.retain(|(drawing_id, _)| *drawing_id != *id);
+                }
+
+                let err = err.clone();
+                Command::perform(async {}, move |()| Message::Error(err))
+            }
+            MainMessage::RegenerateThumbnail(id) => Command::perform(
+                services::drawing::regenerate_preview_offline(*id, globals.get_cache()),
+                |result| match result {
+                    Ok(_) => Message::None,
+                    Err(err) => Message::Error(err),
+                },
+            ),
             MainMessage::LogOut => self.log_out(globals),
             MainMessage::SelectTab(tab_id) => self.select_tab(&tab_id, globals),
-            MainMessage::ErrorHandler(_) => Command::none(),
+            MainMessage::ImportArchive => self.import_archive(),
+            MainMessage::ArchiveRead(drawings) => {
+                self.import_queue = drawings.clone();
+                self.import_progress = Some((0, drawings.len()));
+
+                self.import_next(globals)
+            }
+            MainMessage::ImportProgress(done, total) => {
+                self.import_progress = Some((*done, *total));
+
+                self.import_next(globals)
+            }
+            MainMessage::SetSelected(index, selected) => {
+                let drawing = self
+                    .get_offline_page()
+                    .and_then(|drawings| drawings.get(*index).cloned());
+
+                if let Some((id, _)) = drawing {
+                    if *selected {
+                        self.selected_drawings.insert(id);
+                    } else {
+                        self.selected_drawings.remove(&id);
+                    }
+                }
+
+                Command::none()
+            }
+            MainMessage::ExportSelected(ids) => {
+                let ids = ids.clone();
+                self.selected_drawings.clear();
+
+                Command::perform(
+                    async move { services::main::export_drawings_offline(ids).await },
+                    |result| match result {
+                        Ok(_) => Message::None,
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            MainMessage::SyncToOnline(ids) => self.sync_to_online(ids, globals),
+            MainMessage::SyncProgress(done, total) => {
+                self.syncing = Some((*done, *total));
+
+                self.sync_next(globals)
+            }
+            MainMessage::LoadedChallenge(challenge) => {
+                self.challenge = challenge.clone();
+
+                Command::none()
+            }
+            MainMessage::ErrorHandler(_) => {
+                self.import_queue.clear();
+                self.import_progress = None;
+                self.sync_queue.clear();
+                self.syncing = None;
+
+                Command::none()
+            }
         }
     }
 
     fn view(&self, globals: &Globals) -> Element<Message, Theme, Renderer> {
         let container_auth = if let Some(user) = globals.get_user() {
-            services::main::auth_logged_in(&user)
+            services::main::auth_logged_in(&user, globals.get_theme())
         } else {
-            services::main::auth_logged_out()
+            services::main::auth_logged_out(globals.get_theme())
         };
 
         let title = Container::new(Text::new("Chartsy").width(Length::Shrink).size(50))
@@ -305,8 +894,12 @@ impl Scene for Main {
             .align_x(Horizontal::Center)
             .align_y(Vertical::Center);
 
-        let column_buttons =
-            services::main::main_column(globals.get_db().is_some() && globals.get_user().is_some());
+        let column_buttons = services::main::main_column(
+            globals.get_db().is_some() && globals.get_user().is_some(),
+            globals.get_db_status(),
+            globals.get_notifications(),
+            self.challenge.is_some(),
+        );
 
         let container_entrance: Container<Message, Theme, Renderer> = Container::new(
             Column::with_children(vec![
@@ -323,23 +916,54 @@ impl Scene for Main {
 
         let modal_generator = |modal_type: ModalType| match modal_type {
             ModalType::ShowingDrawings => {
-                let online_tab =
-                    services::main::drawings_tab(&self.drawings_online, SaveMode::Online, globals);
+                if let Some((done, total)) = self.syncing {
+                    return Element::from(
+                        Container::new(
+                            WaitPanel::new(format!("Syncing drawings... {}/{}", done, total))
+                                .with_progress(done as f32 / total.max(1) as f32),
+                        )
+                        .style(iced::widget::container::bordered_box),
+                    );
+                }
+
+                let editing = self.editing_name.map(|id| (id, self.name_input.as_str()));
+                let online_tab = services::main::drawings_tab(
+                    &self.drawings_online,
+                    SaveMode::Online,
+                    None,
+                    None,
+                    &self.filter_input,
+                    editing,
+                    globals,
+                );
 
                 let offline_tab = services::main::drawings_tab(
-                    &self.drawings_offline,
+                    &self.get_offline_page(),
                     SaveMode::Offline,
+                    Some(&self.selected_drawings),
+                    Some((self.page, self.get_page_count())),
+                    &self.filter_input,
+                    editing,
                     globals,
                 );
 
-                let title = Text::new("Your drawings")
-                    .horizontal_alignment(Horizontal::Center)
-                    .width(Length::Fill)
-                    .size(25)
-                    .into();
+                let title = Row::with_children(vec![
+                    Text::new("Your drawings")
+                        .horizontal_alignment(Horizontal::Center)
+                        .width(Length::Fill)
+                        .size(25)
+                        .into(),
+                    Button::new("↻ Refresh")
+                        .on_press(MainMessage::RefreshDrawings.into())
+                        .into(),
+                ])
+                .align_items(Alignment::Center)
+                .into();
                 let tabs = services::main::drawings_tabs(offline_tab, online_tab, self.active_tab);
 
-                services::main::display_drawings(title, tabs)
+                let recent = services::main::recent_drawings(globals);
+
+                services::main::display_drawings(title, &self.filter_input, recent, tabs)
             }
             ModalType::SelectingSaveMode => {
                 let offline_button = Button::new("Offline")
@@ -350,7 +974,10 @@ impl Scene for Main {
                     ))))
                     .into();
 
-                let online_button = if globals.get_db().is_some() && globals.get_user().is_some() {
+                let online_button = if globals.get_db().is_some()
+                    && globals.get_user().is_some()
+                    && globals.get_db_status() == DbStatus::Connected
+                {
                     Button::new("Online").on_press(Message::ChangeScene(Scenes::Drawing(Some(
                         DrawingOptions::new(None, None, Some(SaveMode::Online)),
                     ))))
@@ -361,7 +988,7 @@ impl Scene for Main {
                 .width(Length::FillPortion(1))
                 .into();
 
-                services::main::create_drawing(offline_button, online_button)
+                services::main::create_drawing(offline_button, online_button, self.import_progress)
             }
         };
 
@@ -372,5 +999,52 @@ impl Scene for Main {
         self.update(globals, &MainMessage::ErrorHandler(error.clone()))
     }
 
+    fn handle_bus_event(
+        &mut self,
+        event: &dyn SceneMessage,
+        globals: &mut Globals,
+    ) -> Command<Message> {
+        // The imports and uploads are driven by the scene, so they stop at the first drawing
+        // that finishes after the user has left it.
+        match event.as_any().downcast_ref::<MainMessage>() {
+            Some(MainMessage::ImportProgress(done, total)) => {
+                globals.push_toast(if done < total {
+                    format!(
+                        "The import was interrupted after {} of {} drawings.",
+                        done, total
+                    )
+                } else {
+                    format!("Imported {} drawings.", total)
+                });
+            }
+            Some(MainMessage::SyncProgress(done, total)) => {
+                globals.push_toast(if done < total {
+                    format!(
+                        "The upload was interrupted after {} of {} drawings.",
+                        done, total
+                    )
+                } else {
+                    format!("Uploaded {} drawings.", total)
+                });
+            }
+            _ => {}
+        }
+
+        Command::none()
+    }
+
+    fn subscription(&self, _globals: &Globals) -> Subscription<Message> {
+        if self.editing_name.is_none() {
+            return Subscription::none();
+        }
+
+        iced::event::listen_with(|event, _status| match event {
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => {
+                Some(MainMessage::CheckNameFocus.into())
+            }
+            _ => None,
+        })
+    }
+
     fn clear(&self, _globals: &mut Globals) {}
 }
@@ -1,4 +1,7 @@
+pub mod admin;
+pub mod app_settings;
 pub mod auth;
+pub mod challenge;
 pub mod data;
 pub mod drawing;
 pub mod main;
@@ -1,22 +1,24 @@
 use crate::debug_message;
 use crate::scene::{Globals, Message, Scene, SceneMessage};
 use crate::scenes::data::auth::User;
-use crate::scenes::data::drawing::Tag;
+use crate::scenes::data::drawing::{PostData, Tag, UpdatePostData};
 use crate::scenes::services;
-use crate::utils::errors::Error;
+use crate::utils::errors::{AuthError, Error};
 use crate::utils::icons::{Icon, ICON};
 use crate::utils::theme::{self, Theme};
-use crate::widgets::{Close, ComboBox, Grid, ModalStack, Tabs};
+use crate::widgets::{skeleton, Close, ComboBox, Grid, ModalStack, Skeleton, Tabs};
 use crate::{config, database};
+use iced::widget::scrollable::{self, RelativeOffset};
 use iced::widget::text_editor::{Action, Content};
-use iced::widget::{Button, Column, Container, Row, Text, TextInput};
-use iced::{Alignment, Command, Element, Length, Renderer, Size};
+use iced::widget::{Button, Column, Container, PickList, Row, Text, TextInput};
+use iced::{Alignment, Command, Element, Length, Renderer, Size, Subscription};
 use image::{ExtendedColorType, ImageFormat};
 use lettre::message::{Attachment, MultiPart, SinglePart};
-use mongodb::bson::Uuid;
+use mongodb::bson::{DateTime, Uuid};
 use mongodb::Database;
 use std::any::Any;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Formatter};
 use std::io::Cursor;
 
 use crate::scenes::data::posts::*;
@@ -35,6 +37,9 @@ pub enum PostsMessage {
     /// Loads a batch of images.
     LoadBatch(PostTabs),
 
+    /// Triggers when the list of posts of a tab is scrolled to the given relative offset.
+    Scrolled(PostTabs, f32),
+
     /// Handles messages related to comments.
     CommentMessage(CommentMessage),
 
@@ -42,7 +47,16 @@ pub enum PostsMessage {
     ToggleModal(ModalType),
 
     /// Sets the rating of the given post.
-    RatePost { post_index: usize, rating: usize },
+    RatePost { post_index: usize, rating: f32 },
+
+    /// Toggles the like the user has given to the post with the given id.
+    ToggleLike(Uuid),
+
+    /// Copies a link to the post with the given id to the clipboard.
+    SharePost(Uuid),
+
+    /// Follows or unfollows the given user.
+    ToggleFollow(User),
 
     /// Triggered when all tags have been loaded.
     LoadedTags(Vec<Tag>),
@@ -56,14 +70,26 @@ pub enum PostsMessage {
     /// Removes a tag from the filters.
     RemoveTag(Tag),
 
+    /// Opens the filtered tab with the given tag added to the filters.
+    FilterByTag(String),
+
+    /// Changes the order of the filtered posts and reloads them.
+    SetSortOrder(SortOrder),
+
     /// Opens a users' profile.
     OpenProfile(User),
 
     /// Update user tag input.
     UpdateUserTagInput(String),
 
-    /// Get user by tag.
-    GetUserByTag,
+    /// Searches for the users whose tags start with the given prefix.
+    SearchUsers(String),
+
+    /// Loads the given page of the user search results.
+    LoadSearchPage(usize),
+
+    /// Triggers when a page of the user search results has been loaded.
+    LoadedSearchResults(Vec<User>),
 
     /// Deletes a post.
     DeletePost(Uuid),
@@ -74,9 +100,24 @@ pub enum PostsMessage {
     /// Submits a post report.
     SubmitReport(usize),
 
+    /// Opens the dialog for editing the post with the given id.
+    EditPost(Uuid),
+
+    /// Updates the data of the post being edited.
+    UpdateEditData(UpdatePostData),
+
+    /// Saves the changes made to the post with the given id.
+    SubmitEdit(Uuid),
+
     /// Selects a tab.
     SelectTab(PostTabs),
 
+    /// Advances the shimmer of the placeholders of images that are still loading.
+    Shimmer,
+
+    /// Undoes a change that was rejected by the database with the given error.
+    RevertChange(OptimisticChange, Error),
+
     /// Triggers when an error occurred.
     ErrorHandler(Error),
 }
@@ -97,20 +138,33 @@ impl SceneMessage for PostsMessage {
             Self::LoadPosts => String::from("Load posts"),
             Self::LoadedPosts(_, _) => String::from("Loaded posts"),
             Self::LoadBatch(_) => String::from("Load batch"),
+            Self::Scrolled(_, _) => String::from("Scrolled"),
             Self::CommentMessage(_) => String::from("Loaded comments"),
             Self::ToggleModal(_) => String::from("Toggle modal"),
             Self::RatePost { .. } => String::from("Rate post"),
+            Self::ToggleLike(_) => String::from("Toggle like"),
+            Self::SharePost(_) => String::from("Share post"),
+            Self::ToggleFollow(_) => String::from("Toggle follow"),
             Self::LoadedTags(_) => String::from("Loaded tags"),
             Self::UpdateFilterInput(_) => String::from("Update filter input"),
             Self::AddTag(_) => String::from("Add tag"),
             Self::RemoveTag(_) => String::from("Remove tag"),
+            Self::FilterByTag(_) => String::from("Filter by tag"),
+            Self::SetSortOrder(_) => String::from("Set sort order"),
             Self::OpenProfile(_) => String::from("Open profile"),
             Self::UpdateUserTagInput(_) => String::from("Update user tag input"),
-            Self::GetUserByTag => String::from("Get user by tag"),
+            Self::SearchUsers(_) => String::from("Search users"),
+            Self::LoadSearchPage(_) => String::from("Load search page"),
+            Self::LoadedSearchResults(_) => String::from("Loaded search results"),
             Self::DeletePost(_) => String::from("Delete a post"),
             Self::UpdateReportInput(_) => String::from("Update report input"),
             Self::SubmitReport(_) => String::from("Submit report"),
+            Self::EditPost(_) => String::from("Edit post"),
+            Self::UpdateEditData(_) => String::from("Update edit data"),
+            Self::SubmitEdit(_) => String::from("Submit edit"),
             Self::SelectTab(_) => String::from("Select tab"),
+            Self::Shimmer => String::from("Shimmer"),
+            Self::RevertChange(_, _) => String::from("Revert change"),
             Self::ErrorHandler(_) => String::from("Error handler"),
         }
     }
@@ -146,6 +200,9 @@ pub struct Posts {
     /// Value of filter tag input.
     filter_input: String,
 
+    /// The order in which the filtered posts are sorted.
+    sort_order: SortOrder,
+
     /// Tab of user profile.
     profile: PostList,
 
@@ -155,17 +212,61 @@ pub struct Posts {
     /// The user tag input.
     user_tag_input: String,
 
+    /// The prefix of the user tags that were last searched for.
+    search_query: String,
+
+    /// The current page of the user search results.
+    search_page: usize,
+
+    /// The users found by the last search. None if no search is displayed.
+    search_results: Option<Vec<User>>,
+
+    /// Tab of posts liked by the user.
+    liked: PostList,
+
+    /// Tab of posts of the users the user follows.
+    following: PostList,
+
+    /// Tab of posts that are currently trending.
+    trending: PostList,
+
     /// Currently active tab.
     active_tab: PostTabs,
 
     /// The user input of a report.
     report_input: Content,
 
+    /// The data of the post being edited.
+    edit_data: PostData,
+
+    /// The id of a post that should be opened once the profile of its author is loaded.
+    open_post: Option<Uuid>,
+
+    /// The relative vertical offset each list of posts is scrolled to.
+    scroll_offsets: HashMap<PostTabs, f32>,
+
+    /// The phase of the shimmer animation of the [skeletons](Skeleton) shown while images load.
+    shimmer_phase: f32,
+
+    /// Tells whether the window has focus; animations are paused while it does not.
+    window_focused: bool,
+
     /// User error.
     error: Option<Error>,
 }
 
 impl Posts {
+    /// Tells whether some of the posts displayed in the active tab are still waiting for their
+    /// image.
+    fn images_loading(&self, globals: &Globals) -> bool {
+        let cache = globals.get_cache();
+
+        self.get_tab(self.active_tab)
+            .get_loaded_posts()
+            .into_iter()
+            .any(|(post, _)| cache.get(post.get_id()).is_none())
+    }
+
     /// Loads all necessary images.
     pub fn load_images(&self, globals: &Globals) -> Command<Message> {
         let ids = self
@@ -174,6 +275,9 @@ impl Posts {
             .into_iter()
             .chain(self.filtered.get_loaded_posts())
             .chain(self.profile.get_loaded_posts())
+            .chain(self.liked.get_loaded_posts())
+            .chain(self.following.get_loaded_posts())
+            .chain(self.trending.get_loaded_posts())
             .map(|(post, _)| (post.get_id(), post.get_user().get_id()));
 
         let post_images =
@@ -186,6 +290,9 @@ impl Posts {
             .get_loaded_posts()
             .into_iter()
             .chain(self.filtered.get_loaded_posts())
+            .chain(self.liked.get_loaded_posts())
+            .chain(self.following.get_loaded_posts())
+            .chain(self.trending.get_loaded_posts())
             .map(|(post, _)| {
                 post.get_user()
                     .has_profile_picture()
@@ -194,7 +301,13 @@ impl Posts {
             .chain(vec![self
                 .user_profile
                 .has_profile_picture()
-                .then_some(self.user_profile.get_id())]);
+                .then_some(self.user_profile.get_id())])
+            .chain(
+                self.search_results
+                    .iter()
+                    .flatten()
+                    .map(|user| user.has_profile_picture().then_some(user.get_id())),
+            );
 
         let profile_pictures = globals.get_cache().insert_if_not(
             profile_picure_ids,
@@ -217,9 +330,14 @@ impl Posts {
     }
 
     /// Creates a command that returns the list of posts that has all tags from the filter.
-    fn gen_filtered(db: Database, user_id: Uuid, tags: Vec<String>) -> Command<Message> {
+    fn gen_filtered(
+        db: Database,
+        user_id: Uuid,
+        tags: Vec<String>,
+        sort_order: SortOrder,
+    ) -> Command<Message> {
         Command::perform(
-            async move { database::posts::get_filtered(&db, user_id, tags).await },
+            async move { database::posts::get_filtered(&db, user_id, tags, sort_order).await },
             |result| match result {
                 Ok(posts) => PostsMessage::LoadedPosts(posts, PostTabs::Filtered).into(),
                 Err(err) => Message::Error(err),
@@ -238,6 +356,39 @@ impl Posts {
         )
     }
 
+    /// Creates a command that returns the list of posts of the users the given user follows.
+    fn gen_following(db: Database, user_id: Uuid) -> Command<Message> {
+        Command::perform(
+            async move { database::posts::get_followed_posts(&db, user_id).await },
+            |result| match result {
+                Ok(posts) => PostsMessage::LoadedPosts(posts, PostTabs::Following).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    /// Creates a command that returns the list of trending posts.
+    fn gen_trending(db: Database, user_id: Uuid) -> Command<Message> {
+        Command::perform(
+            async move { database::posts::get_trending(&db, user_id).await },
+            |result| match result {
+                Ok(posts) => PostsMessage::LoadedPosts(posts, PostTabs::Trending).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    /// Creates a command that returns the list of posts liked by the given user.
+    fn gen_liked(db: Database, user_id: Uuid) -> Command<Message> {
+        Command::perform(
+            async move { database::posts::get_liked_posts(&db, user_id).await },
+            |result| match result {
+                Ok(posts) => PostsMessage::LoadedPosts(posts, PostTabs::Liked).into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
     fn open_comment(
         &mut self,
         post: &usize,
@@ -251,6 +402,7 @@ impl Posts {
                 &CommentMessage::Load {
                     post: *post,
                     parent: Some((*line, *index)),
+                    after: None,
                 },
                 globals,
             )
@@ -275,17 +427,104 @@ impl Posts {
         globals: &Globals,
     ) -> Command<Message> {
         let db = globals.get_db().unwrap();
+        let rate_limiter = globals.get_rate_limiter();
         let user = globals.get_user().unwrap().clone();
+        let user_id = user.get_id();
 
-        let document = if let Some((line, index)) = parent {
+        let (id, document) = if let Some((line, index)) = parent {
             self.get_active_tab_mut()
                 .add_reply(user, *post, *line, *index)
         } else {
             self.get_active_tab_mut().add_comment(user, *post)
         };
+        let change = OptimisticChange::Comment {
+            tab: self.active_tab,
+            post: *post,
+            id,
+        };
+
+        Command::perform(
+            async move {
+                database::posts::create_comment(&db, &rate_limiter, user_id, &document).await
+            },
+            move |result| Self::optimistic_result(result, change),
+        )
+    }
+
+    /// Looks up the users that can be mentioned if the user is writing a mention in the
+    /// given comment input.
+    fn suggest_mentions(
+        &mut self,
+        post: usize,
+        position: Option<(usize, usize)>,
+        globals: &Globals,
+    ) -> Command<Message> {
+        let prefix = match self.get_active_tab().get_mention_prefix(post, position) {
+            Some(prefix) => prefix,
+            None => {
+                self.get_active_tab_mut()
+                    .set_mention_suggestions(post, position, vec![]);
+                return Command::none();
+            }
+        };
+        let db = globals.get_db().unwrap();
+
+        Command::perform(
+            async move { database::posts::get_users_by_prefix(&db, prefix).await },
+            move |result| match result {
+                Ok(users) => CommentMessage::LoadedMentions {
+                    post,
+                    position,
+                    users,
+                }
+                .into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    fn edit_comment(
+        &mut self,
+        post: &usize,
+        position: &(usize, usize),
+        new_content: &String,
+        globals: &Globals,
+    ) -> Command<Message> {
+        let db = globals.get_db().unwrap();
+        let user_id = globals.get_user().unwrap().get_id();
+        let new_content = new_content.clone();
+
+        let id = self.get_active_tab_mut().edit_comment(
+            *post,
+            position.0,
+            position.1,
+            new_content.clone(),
+        );
 
         Command::perform(
-            async move { database::posts::create_comment(&db, &document).await },
+            async move { database::posts::update_comment(&db, user_id, id, new_content).await },
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    fn delete_comment(
+        &mut self,
+        post: &usize,
+        position: &(usize, usize),
+        globals: &Globals,
+    ) -> Command<Message> {
+        let db = globals.get_db().unwrap();
+        let user_id = globals.get_user().unwrap().get_id();
+
+        let id = self
+            .get_active_tab_mut()
+            .delete_comment(*post, position.0, position.1);
+
+        Command::perform(
+            async move { database::posts::delete_comment(&db, user_id, id).await },
             |result| match result {
                 Ok(_) => Message::None,
                 Err(err) => Message::Error(err),
@@ -297,23 +536,26 @@ impl Posts {
         &mut self,
         post: &usize,
         parent: &Option<(usize, usize)>,
+        after: &Option<(DateTime, Uuid)>,
         globals: &Globals,
     ) -> Command<Message> {
         let db = globals.get_db().unwrap();
         let parent = parent.clone();
         let post = post.clone();
+        let after = after.clone();
 
         let active_tab = self.active_tab;
         let filter = self.get_tab_mut(active_tab).load_comments(post, parent);
 
         Command::perform(
-            async move { database::posts::get_comments(&db, filter).await },
+            async move { database::posts::get_comments(&db, filter, after).await },
             move |result| match result {
                 Ok(comments) => CommentMessage::Loaded {
                     post,
                     parent,
                     comments,
                     tab: active_tab,
+                    after,
                 }
                 .into(),
                 Err(err) => Message::Error(err),
@@ -338,18 +580,71 @@ impl Posts {
                 self.get_active_tab_mut()
                     .update_input(*post, *position, input.clone());
 
+                self.suggest_mentions(*post, *position, globals)
+            }
+            CommentMessage::LoadedMentions {
+                post,
+                position,
+                users,
+            } => {
+                self.get_active_tab_mut()
+                    .set_mention_suggestions(*post, *position, users.clone());
+
                 Command::none()
             }
+            CommentMessage::Mention {
+                post,
+                position,
+                user,
+            } => {
+                self.get_active_tab_mut()
+                    .complete_mention(*post, *position, user.clone());
+
+                Command::none()
+            }
+            CommentMessage::ToggleEdit { post, position } => {
+                self.get_active_tab_mut()
+                    .toggle_edit(*post, position.0, position.1);
+
+                Command::none()
+            }
+            CommentMessage::UpdateEditInput {
+                post,
+                position,
+                input,
+            } => {
+                self.get_active_tab_mut().update_edit_input(
+                    *post,
+                    position.0,
+                    position.1,
+                    input.clone(),
+                );
+
+                Command::none()
+            }
+            CommentMessage::Edit {
+                post,
+                position,
+                new_content,
+            } => self.edit_comment(post, position, new_content, globals),
+            CommentMessage::Delete { post, position } => {
+                self.delete_comment(post, position, globals)
+            }
             CommentMessage::Add { post, parent } => self.add_comment(post, parent, globals),
-            CommentMessage::Load { post, parent } => self.load_comments(post, parent, globals),
+            CommentMessage::Load {
+                post,
+                parent,
+                after,
+            } => self.load_comments(post, parent, after, globals),
             CommentMessage::Loaded {
                 post,
                 parent,
                 comments,
                 tab,
+                after,
             } => {
                 self.get_tab_mut(*tab)
-                    .loaded_comments(*post, *parent, comments.clone());
+                    .loaded_comments(*post, *parent, comments.clone(), *after);
 
                 Command::none()
             }
@@ -368,6 +663,8 @@ impl Posts {
             self.get_tab(tab),
             globals.get_user().unwrap(),
             globals.get_cache(),
+            globals.get_preferences().get_hot_threshold(),
+            self.shimmer_phase,
         )
         .width(size.width)
         .height(size.height)
@@ -385,10 +682,18 @@ impl Posts {
     /// Generate the modal that shows the post.
     pub fn gen_show_post<'a>(
         post_index: usize,
-        post: &'a Post,
+        list: &'a PostList,
         globals: &Globals,
     ) -> Element<'a, Message, Theme, Renderer> {
-        services::posts::generate_show_post(post, post_index, &globals.get_cache())
+        let post = list.get_post(post_index).unwrap();
+
+        services::posts::generate_show_post(
+            post,
+            post_index,
+            list,
+            globals.get_user().unwrap().get_id(),
+            &globals.get_cache(),
+        )
     }
 
     /// Generates the modal for sending a report.
@@ -400,12 +705,24 @@ impl Posts {
         services::posts::generate_show_report(post_index, &self.report_input)
     }
 
+    /// Generates the modal for editing a post.
+    pub fn gen_edit_post(
+        &self,
+        post_id: Uuid,
+        _globals: &Globals,
+    ) -> Element<Message, Theme, Renderer> {
+        services::posts::generate_edit_post(post_id, &self.edit_data)
+    }
+
     /// Returns the required tab.
     fn get_tab(&self, tab: PostTabs) -> &PostList {
         match tab {
             PostTabs::Recommended => &self.recommended,
             PostTabs::Filtered => &self.filtered,
             PostTabs::Profile => &self.profile,
+            PostTabs::Liked => &self.liked,
+            PostTabs::Following => &self.following,
+            PostTabs::Trending => &self.trending,
         }
     }
 
@@ -415,6 +732,9 @@ impl Posts {
             PostTabs::Recommended => &mut self.recommended,
             PostTabs::Filtered => &mut self.filtered,
             PostTabs::Profile => &mut self.profile,
+            PostTabs::Liked => &mut self.liked,
+            PostTabs::Following => &mut self.following,
+            PostTabs::Trending => &mut self.trending,
         }
     }
 
@@ -439,8 +759,12 @@ impl Posts {
                 db,
                 user_id,
                 self.tags.iter().map(|tag| tag.get_name().clone()).collect(),
+                self.sort_order,
             ),
             PostTabs::Profile => Self::gen_profile(db, user_id),
+            PostTabs::Liked => Self::gen_liked(db, user_id),
+            PostTabs::Following => Self::gen_following(db, user_id),
+            PostTabs::Trending => Self::gen_trending(db, user_id),
         }
     }
 
@@ -455,10 +779,33 @@ impl Posts {
         *self.get_tab_mut(tab.clone()) = PostList::new(posts.clone());
         let length = posts.len();
 
-        if length > 0 {
-            self.update(globals, &PostsMessage::LoadBatch(tab))
-        } else {
-            Command::none()
+        if length == 0 {
+            return Command::none();
+        }
+
+        let load_batch = self.update(globals, &PostsMessage::LoadBatch(tab));
+
+        if tab != PostTabs::Profile {
+            return load_batch;
+        }
+
+        match self
+            .open_post
+            .take()
+            .and_then(|id| posts.iter().position(|post| post.get_id() == id))
+        {
+            Some(index) => {
+                while self.profile.get_loaded_posts().into_iter().count() <= index {
+                    self.profile.load_batch();
+                }
+                self.active_tab = PostTabs::Profile;
+
+                Command::batch(vec![
+                    load_batch,
+                    self.toggle_modal(&ModalType::ShowingPost(index), globals),
+                ])
+            }
+            None => load_batch,
         }
     }
 
@@ -468,11 +815,16 @@ impl Posts {
 
         match modal {
             ModalType::ShowingPost(post) => {
+                if let Some(post) = self.get_active_tab().get_post(*post) {
+                    globals.read_notifications(post.get_id());
+                }
+
                 if !self.get_active_tab().has_loaded_comments(*post) {
                     self.update_comment(
                         &CommentMessage::Load {
                             post: *post,
                             parent: None,
+                            after: None,
                         },
                         globals,
                     )
@@ -492,35 +844,213 @@ impl Posts {
     fn rate_post(
         &mut self,
         post_index: usize,
-        rating: usize,
+        rating: f32,
         globals: &mut Globals,
     ) -> Command<Message> {
         let user_id = globals.get_user().unwrap().get_id();
         let db = globals.get_db().unwrap();
-
+        let rate_limiter = globals.get_rate_limiter();
+
+        let change = OptimisticChange::Rating {
+            tab: self.active_tab,
+            post: post_index,
+            previous: *self
+                .get_active_tab()
+                .get_post(post_index)
+                .unwrap()
+                .get_rating(),
+        };
         let (post_id, rating) = self.get_active_tab_mut().rate_post(post_index, rating);
 
         if let Some(rating) = rating {
             Command::perform(
                 async move {
-                    database::posts::update_rating(&db, post_id, user_id, rating as i32).await
-                },
-                |result| match result {
-                    Ok(_) => Message::None,
-                    Err(err) => Message::Error(err),
+                    database::posts::update_rating(
+                        &db,
+                        &rate_limiter,
+                        post_id,
+                        user_id,
+                        rating as f64,
+                    )
+                    .await
                 },
+                move |result| Self::optimistic_result(result, change),
             )
         } else {
             Command::perform(
                 async move { database::posts::delete_rating(&db, post_id, user_id).await },
-                |result| match result {
-                    Ok(_) => Message::None,
-                    Err(err) => Message::Error(err),
-                },
+                move |result| Self::optimistic_result(result, change),
             )
         }
     }
 
+    /// Returns the message handling the result of a change that was displayed before the
+    /// database accepted it, which undoes the change if the user has been rate limited or banned.
+    fn optimistic_result(result: Result<(), Error>, change: OptimisticChange) -> Message {
+        match result {
+            Ok(_) => Message::None,
+            Err(err @ (Error::AuthError(AuthError::UserBanned) | Error::RateLimited { .. })) => {
+                PostsMessage::RevertChange(change, err).into()
+            }
+            Err(err) => Message::Error(err),
+        }
+    }
+
+    /// Undoes a change that was rejected by the database, then displays the error.
+    fn revert_change(&mut self, change: &OptimisticChange, error: &Error) -> Command<Message> {
+        match change {
+            OptimisticChange::Like(post_id) => {
+                self.toggle_like_locally(*post_id);
+            }
+            OptimisticChange::Rating {
+                tab,
+                post,
+                previous,
+            } => {
+                self.get_tab_mut(*tab).rate_post(*post, *previous);
+            }
+            OptimisticChange::Comment { tab, post, id } => {
+                self.get_tab_mut(*tab).remove_comment(*post, *id);
+            }
+        }
+
+        let error = error.clone();
+        Command::perform(async {}, move |()| Message::Error(error))
+    }
+
+    /// Opens the edit dialog, filled in with the current data of the post.
+    fn edit_post(&mut self, post_id: Uuid, globals: &mut Globals) -> Command<Message> {
+        let post = match self
+            .recommended
+            .get_post_by_id(post_id)
+            .or(self.filtered.get_post_by_id(post_id))
+            .or(self.profile.get_post_by_id(post_id))
+        {
+            Some(post) => post,
+            None => {
+                return Command::perform(async {}, move |()| {
+                    Message::Error(
+                        debug_message!("Could not find post with id {} to edit.", post_id).into(),
+                    )
+                });
+            }
+        };
+
+        self.edit_data
+            .set_description(Content::with_text(post.get_description()));
+        self.edit_data
+            .set_post_tags(post.get_tags().iter().map(Tag::new).collect::<Vec<Tag>>());
+        self.edit_data.set_tag_input("");
+        self.edit_data.update(UpdatePostData::AllTags(
+            self.all_tags.iter().map(|tag| tag.clone()).collect(),
+        ));
+
+        self.toggle_modal(&ModalType::EditingPost(post_id), globals)
+    }
+
+    /// Saves the changes made to a post.
+    fn submit_edit(&mut self, post_id: Uuid, globals: &mut Globals) -> Command<Message> {
+        let db = globals.get_db().unwrap();
+        let user_id = globals.get_user().unwrap().get_id();
+        let description = self
+            .edit_data
+            .get_description()
+            .text()
+            .trim_end()
+            .to_string();
+        let tags: Vec<String> = self
+            .edit_data
+            .get_post_tags()
+            .iter()
+            .map(|tag| tag.get_name().clone())
+            .collect();
+
+        self.recommended
+            .edit_post(post_id, description.clone(), tags.clone());
+        self.filtered
+            .edit_post(post_id, description.clone(), tags.clone());
+        self.profile
+            .edit_post(post_id, description.clone(), tags.clone());
+
+        self.modals.toggle_modal(ModalType::EditingPost(post_id));
+
+        Command::perform(
+            async move {
+                database::posts::update_post(&db, user_id, post_id, description, tags).await
+            },
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    /// Likes or unlikes a post.
+    fn toggle_like(&mut self, post_id: Uuid, globals: &mut Globals) -> Command<Message> {
+        let user_id = globals.get_user().unwrap().get_id();
+        let db = globals.get_db().unwrap();
+        let rate_limiter = globals.get_rate_limiter();
+
+        let is_liked = self.toggle_like_locally(post_id);
+
+        Command::perform(
+            async move {
+                if is_liked {
+                    database::posts::like_post(&db, &rate_limiter, post_id, user_id).await
+                } else {
+                    database::posts::unlike_post(&db, post_id, user_id).await
+                }
+            },
+            move |result| Self::optimistic_result(result, OptimisticChange::Like(post_id)),
+        )
+    }
+
+    /// Toggles the like of the post with the given id in all tabs. Returns the new state of the
+    /// like.
+    fn toggle_like_locally(&mut self, post_id: Uuid) -> bool {
+        [
+            self.recommended.toggle_like(post_id),
+            self.filtered.toggle_like(post_id),
+            self.profile.toggle_like(post_id),
+            self.liked.toggle_like(post_id),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .unwrap_or(false)
+    }
+
+    /// Follows or unfollows the given user.
+    fn toggle_follow(&mut self, user: &User, globals: &mut Globals) -> Command<Message> {
+        let follower_id = globals.get_user().unwrap().get_id();
+        let followed_id = user.get_id();
+        let db = globals.get_db().unwrap();
+        let rate_limiter = globals.get_rate_limiter();
+
+        let is_followed = !PostTabs::ALL
+            .into_iter()
+            .find_map(|tab| self.get_tab(tab).is_followed(followed_id))
+            .unwrap_or(false);
+
+        for tab in PostTabs::ALL {
+            self.get_tab_mut(tab).set_followed(followed_id, is_followed);
+        }
+
+        Command::perform(
+            async move {
+                if is_followed {
+                    database::posts::follow_user(&db, &rate_limiter, follower_id, followed_id).await
+                } else {
+                    database::posts::unfollow_user(&db, follower_id, followed_id).await
+                }
+            },
+            |result| match result {
+                Ok(_) => Message::None,
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
     /// Submits a report.
     fn submit_report(&mut self, post_index: usize, globals: &mut Globals) -> Command<Message> {
         let post_index = post_index.clone();
@@ -593,8 +1123,28 @@ impl Posts {
             )
             .unwrap();
 
+        let db = globals.get_db().unwrap();
+        let rate_limiter = globals.get_rate_limiter();
+        let user_id = globals.get_user().unwrap().get_id();
+        let post_id = post.get_id();
+
         Command::batch(vec![
-            Command::perform(async {}, move |()| Message::SendSmtpMail(message)),
+            Command::perform(
+                async move {
+                    database::posts::create_report(
+                        &db,
+                        &rate_limiter,
+                        user_id,
+                        post_id,
+                        report_description,
+                    )
+                    .await
+                },
+                move |result| match result {
+                    Ok(_) => Message::SendSmtpMail(message),
+                    Err(err) => Message::Error(err),
+                },
+            ),
             Command::perform(async {}, move |()| {
                 PostsMessage::ToggleModal(ModalType::ShowingReport(post_index)).into()
             }),
@@ -602,9 +1152,46 @@ impl Posts {
     }
 }
 
-/// The [Posts] scene does not have any optional initialization values.
-#[derive(Debug, Clone, Copy)]
-pub struct PostsOptions {}
+/// The state of a [Posts] scene that was closed, restored when it is opened again.
+#[derive(Clone)]
+pub struct PostsSnapshot {
+    recommended: PostList,
+    filtered: PostList,
+    tags: HashSet<Tag>,
+    sort_order: SortOrder,
+    profile: PostList,
+    user_profile: User,
+    liked: PostList,
+    following: PostList,
+    trending: PostList,
+    active_tab: PostTabs,
+    scroll_offsets: HashMap<PostTabs, f32>,
+}
+
+impl Debug for PostsSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PostsSnapshot")
+    }
+}
+
+/// The options of the [Posts] scene.
+#[derive(Debug, Clone)]
+pub struct PostsOptions {
+    /// The id of a post that should be opened.
+    post: Option<Uuid>,
+
+    /// The state of the scene the last time it was closed.
+    snapshot: Option<PostsSnapshot>,
+}
+
+impl PostsOptions {
+    pub fn new(post: Uuid) -> Self {
+        PostsOptions {
+            post: Some(post),
+            snapshot: None,
+        }
+    }
+}
 
 impl Scene for Posts {
     type Message = PostsMessage;
@@ -621,14 +1208,29 @@ impl Scene for Posts {
             tags: HashSet::new(),
             all_tags: HashSet::new(),
             filter_input: String::from(""),
+            sort_order: SortOrder::default(),
             profile: PostList::new(vec![]),
             user_profile: globals.get_user().unwrap().clone(),
             user_tag_input: String::from(""),
+            search_query: String::from(""),
+            search_page: 0,
+            search_results: None,
+            liked: PostList::new(vec![]),
+            following: PostList::new(vec![]),
+            trending: PostList::new(vec![]),
             active_tab: PostTabs::Recommended,
             report_input: Content::new(),
+            edit_data: PostData::default(),
+            open_post: None,
+            scroll_offsets: HashMap::new(),
+            shimmer_phase: 0.0,
+            window_focused: true,
             error: None,
         };
 
+        let restored = options
+            .as_ref()
+            .is_some_and(|options| options.snapshot.is_some());
         if let Some(options) = options {
             posts.apply_options(options);
         }
@@ -637,18 +1239,51 @@ impl Scene for Posts {
         let db_clone = db.clone();
         let user_id = globals.get_user().unwrap().get_id().clone();
 
+        let load_tags = Command::perform(
+            async move { database::drawing::get_tags(&db_clone).await },
+            |tags| match tags {
+                Ok(tags) => PostsMessage::LoadedTags(tags).into(),
+                Err(err) => Message::Error(err),
+            },
+        );
+
+        if restored {
+            let offset = posts
+                .scroll_offsets
+                .get(&posts.active_tab)
+                .copied()
+                .unwrap_or(0.0);
+            let scroll = scrollable::snap_to(
+                services::posts::post_list_id(posts.active_tab),
+                RelativeOffset { x: 0.0, y: offset },
+            );
+            let load_images = posts.load_images(globals);
+
+            return (posts, Command::batch(vec![load_tags, load_images, scroll]));
+        }
+
+        let load_profile = match posts.open_post {
+            Some(post_id) => {
+                let db = db.clone();
+
+                Command::perform(
+                    async move { database::posts::get_post_author(&db, post_id).await },
+                    |result| match result {
+                        Ok(user) => PostsMessage::OpenProfile(user).into(),
+                        Err(err) => Message::Error(err),
+                    },
+                )
+            }
+            None => Self::gen_profile(db.clone(), user_id),
+        };
+
         (
             posts,
             Command::batch(vec![
                 Self::gen_recommended(db.clone(), user_id),
-                Command::perform(
-                    async move { database::drawing::get_tags(&db_clone).await },
-                    |tags| match tags {
-                        Ok(tags) => PostsMessage::LoadedTags(tags).into(),
-                        Err(err) => Message::Error(err),
-                    },
-                ),
-                Self::gen_profile(db, user_id),
+                load_tags,
+                load_profile,
+                Self::gen_liked(db, user_id),
             ]),
         )
     }
@@ -657,7 +1292,42 @@ impl Scene for Posts {
         String::from("Posts")
     }
 
-    fn apply_options(&mut self, _options: Self::Options) {}
+    fn apply_options(&mut self, options: Self::Options) {
+        self.open_post = options.post;
+
+        if let Some(snapshot) = options.snapshot {
+            self.recommended = snapshot.recommended;
+            self.filtered = snapshot.filtered;
+            self.tags = snapshot.tags;
+            self.sort_order = snapshot.sort_order;
+            self.profile = snapshot.profile;
+            self.user_profile = snapshot.user_profile;
+            self.liked = snapshot.liked;
+            self.following = snapshot.following;
+            self.trending = snapshot.trending;
+            self.active_tab = snapshot.active_tab;
+            self.scroll_offsets = snapshot.scroll_offsets;
+        }
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(PostsOptions {
+            post: None,
+            snapshot: Some(PostsSnapshot {
+                recommended: self.recommended.clone(),
+                filtered: self.filtered.clone(),
+                tags: self.tags.clone(),
+                sort_order: self.sort_order,
+                profile: self.profile.clone(),
+                user_profile: self.user_profile.clone(),
+                liked: self.liked.clone(),
+                following: self.following.clone(),
+                trending: self.trending.clone(),
+                active_tab: self.active_tab,
+                scroll_offsets: self.scroll_offsets.clone(),
+            }),
+        }))
+    }
 
     fn update(&mut self, globals: &mut Globals, message: &Self::Message) -> Command<Message> {
         match message {
@@ -669,11 +1339,27 @@ impl Scene for Posts {
 
                 Command::none()
             }
+            PostsMessage::Scrolled(tab, offset) => {
+                self.scroll_offsets.insert(*tab, *offset);
+
+                if *offset == 1.0 && !self.get_tab(*tab).done_loading() {
+                    self.update(globals, &PostsMessage::LoadBatch(*tab))
+                } else {
+                    Command::none()
+                }
+            }
             PostsMessage::CommentMessage(message) => self.update_comment(&message, globals),
             PostsMessage::ToggleModal(modal) => self.toggle_modal(modal, globals),
             PostsMessage::RatePost { post_index, rating } => {
                 self.rate_post(*post_index, *rating, globals)
             }
+            PostsMessage::ToggleLike(post_id) => self.toggle_like(*post_id, globals),
+            PostsMessage::SharePost(post_id) => {
+                globals.push_toast("Copied!");
+
+                iced::clipboard::write(format!("{}{}", POST_LINK_PREFIX, post_id))
+            }
+            PostsMessage::ToggleFollow(user) => self.toggle_follow(user, globals),
             PostsMessage::LoadedTags(tags) => {
                 self.all_tags = HashSet::from_iter(tags.iter().map(|tag| tag.clone()));
 
@@ -695,8 +1381,23 @@ impl Scene for Posts {
 
                 Command::none()
             }
+            PostsMessage::FilterByTag(tag) => {
+                self.modals.clear();
+
+                let add_tag = self.update(globals, &PostsMessage::AddTag(Tag::new(tag)));
+                let select_tab = self.update(globals, &PostsMessage::SelectTab(PostTabs::Filtered));
+                let load_posts = self.update(globals, &PostsMessage::LoadPosts);
+
+                Command::batch(vec![add_tag, select_tab, load_posts])
+            }
+            PostsMessage::SetSortOrder(sort_order) => {
+                self.sort_order = *sort_order;
+
+                self.update(globals, &PostsMessage::LoadPosts)
+            }
             PostsMessage::OpenProfile(user) => {
                 self.error = None;
+                self.search_results = None;
                 self.user_profile = user.clone();
                 self.active_tab = PostTabs::Profile;
 
@@ -709,18 +1410,32 @@ impl Scene for Posts {
 
                 Command::none()
             }
-            PostsMessage::GetUserByTag => {
+            PostsMessage::SearchUsers(prefix) => {
+                self.search_query = prefix.clone();
+
+                self.update(globals, &PostsMessage::LoadSearchPage(0))
+            }
+            PostsMessage::LoadSearchPage(page) => {
+                self.error = None;
+                self.search_page = *page;
+
                 let db = globals.get_db().unwrap();
-                let user_tag = self.user_tag_input.clone();
+                let prefix = self.search_query.clone();
+                let page = *page;
 
                 Command::perform(
-                    async move { database::posts::get_user_by_tag(&db, user_tag).await },
+                    async move { database::posts::search_users_by_prefix(&db, prefix, page).await },
                     |result| match result {
-                        Ok(user) => PostsMessage::OpenProfile(user).into(),
+                        Ok(users) => PostsMessage::LoadedSearchResults(users).into(),
                         Err(err) => Message::Error(err),
                     },
                 )
             }
+            PostsMessage::LoadedSearchResults(users) => {
+                self.search_results = Some(users.clone());
+
+                self.load_images(globals)
+            }
             PostsMessage::DeletePost(id) => {
                 let id = *id;
                 self.recommended.remove_post(id);
@@ -742,11 +1457,31 @@ impl Scene for Posts {
                 Command::none()
             }
             PostsMessage::SubmitReport(post_index) => self.submit_report(*post_index, globals),
+            PostsMessage::EditPost(post_id) => self.edit_post(*post_id, globals),
+            PostsMessage::UpdateEditData(update) => {
+                self.edit_data.update(update.clone());
+
+                Command::none()
+            }
+            PostsMessage::SubmitEdit(post_id) => self.submit_edit(*post_id, globals),
             PostsMessage::SelectTab(tab_id) => {
                 self.active_tab = *tab_id;
 
+                if *tab_id == PostTabs::Liked
+                    || *tab_id == PostTabs::Following
+                    || *tab_id == PostTabs::Trending
+                {
+                    self.load_posts(*tab_id, globals)
+                } else {
+                    Command::none()
+                }
+            }
+            PostsMessage::Shimmer => {
+                self.shimmer_phase = Skeleton::next_phase(self.shimmer_phase);
+
                 Command::none()
             }
+            PostsMessage::RevertChange(change, error) => self.revert_change(change, error),
             PostsMessage::ErrorHandler(error) => {
                 self.error = Some(error.clone());
 
@@ -774,10 +1509,15 @@ impl Scene for Posts {
                         |tag| PostsMessage::AddTag(tag).into(),
                     )
                     .on_input(|input| PostsMessage::UpdateFilterInput(input).into())
+                    .describe("Filter the posts by tag")
                     .into(),
                     Button::new("Submit")
                         .on_press(PostsMessage::LoadPosts.into())
                         .into(),
+                    PickList::new(SortOrder::ALL, Some(self.sort_order), |sort_order| {
+                        PostsMessage::SetSortOrder(sort_order).into()
+                    })
+                    .into(),
                 ])
                 .spacing(10.0)
                 .into(),
@@ -817,9 +1557,10 @@ impl Scene for Posts {
             TextInput::new("Input user tag...", &*self.user_tag_input)
                 .on_input(|input| PostsMessage::UpdateUserTagInput(input).into())
                 .on_paste(|input| PostsMessage::UpdateUserTagInput(input).into())
+                .on_submit(PostsMessage::SearchUsers(self.user_tag_input.clone()).into())
                 .into(),
             Button::new("Search")
-                .on_press(PostsMessage::GetUserByTag.into())
+                .on_press(PostsMessage::SearchUsers(self.user_tag_input.clone()).into())
                 .into(),
         ])
         .spacing(10.0)
@@ -834,6 +1575,11 @@ impl Scene for Posts {
                     .style(theme::text::danger)
                     .into(),
             ])
+        } else if let Some(users) = &self.search_results {
+            Column::with_children(vec![
+                user_tag_input,
+                services::posts::user_search_results(users, self.search_page, &globals.get_cache()),
+            ])
         } else {
             Column::with_children(vec![
                 user_tag_input,
@@ -870,6 +1616,30 @@ impl Scene for Posts {
         .align_items(Alignment::Center)
         .into();
 
+        let liked_tab = self
+            .gen_post_list(
+                PostTabs::Liked,
+                globals,
+                Size::new(Length::Shrink, Length::Shrink),
+            )
+            .into();
+
+        let following_tab = self
+            .gen_post_list(
+                PostTabs::Following,
+                globals,
+                Size::new(Length::Shrink, Length::Shrink),
+            )
+            .into();
+
+        let trending_tab = self
+            .gen_post_list(
+                PostTabs::Trending,
+                globals,
+                Size::new(Length::Shrink, Length::Shrink),
+            )
+            .into();
+
         let underlay = Column::with_children(vec![
             Row::with_children(vec![
                 Button::new(Text::new(Icon::Leave.to_string()).size(30.0).font(ICON))
@@ -890,10 +1660,18 @@ impl Scene for Posts {
                     ),
                     (PostTabs::Filtered, String::from("Filtered"), filtered_tab),
                     (PostTabs::Profile, String::from("Profile"), profile_tab),
+                    (PostTabs::Liked, String::from("Liked"), liked_tab),
+                    (
+                        PostTabs::Following,
+                        String::from("Following"),
+                        following_tab,
+                    ),
+                    (PostTabs::Trending, String::from("Trending"), trending_tab),
                 ],
                 |tab_id| PostsMessage::SelectTab(tab_id).into(),
             )
             .selected(self.active_tab)
+            .describe("The lists of posts")
             .width(Length::Fill)
             .height(Length::Fill)
             .into(),
@@ -902,19 +1680,38 @@ impl Scene for Posts {
         let modal_generator = |modal_type: ModalType| match modal_type {
             ModalType::ShowingImage(data) => Self::gen_show_image(data.clone(), globals),
             ModalType::ShowingPost(post_index) => {
-                let post = self.get_active_tab().get_post(post_index).unwrap();
-
-                Self::gen_show_post(post_index, post, globals)
+                Self::gen_show_post(post_index, self.get_active_tab(), globals)
             }
             ModalType::ShowingReport(post_index) => self.gen_show_report(post_index, globals),
+            ModalType::EditingPost(post_id) => self.gen_edit_post(post_id, globals),
         };
 
         self.modals.get_modal(underlay, modal_generator)
     }
 
+    fn subscription(&self, globals: &Globals) -> Subscription<Message> {
+        if self.window_focused && self.images_loading(globals) {
+            iced::time::every(skeleton::FRAME_INTERVAL).map(|_| PostsMessage::Shimmer.into())
+        } else {
+            Subscription::none()
+        }
+    }
+
     fn handle_error(&mut self, globals: &mut Globals, error: &Error) -> Command<Message> {
         self.update(globals, &PostsMessage::ErrorHandler(error.clone()))
     }
 
+    fn on_focus(&mut self, _globals: &mut Globals) -> Command<Message> {
+        self.window_focused = true;
+
+        Command::none()
+    }
+
+    fn on_blur(&mut self, _globals: &mut Globals) -> Command<Message> {
+        self.window_focused = false;
+
+        Command::none()
+    }
+
     fn clear(&self, _globals: &mut Globals) {}
 }
@@ -1,14 +1,28 @@
 use crate::debug_message;
 use crate::scene::{Globals, Message, Scene, SceneMessage};
-use crate::scenes::auth::{Auth, AuthOptions};
-use crate::scenes::drawing::{Drawing, DrawingOptions};
-use crate::scenes::main::{Main, MainOptions};
-use crate::scenes::posts::{Posts, PostsOptions};
-use crate::scenes::settings::{Settings, SettingsOptions};
+use crate::scenes::admin::{Admin, AdminMessage, AdminOptions};
+use crate::scenes::app_settings::{AppSettings, AppSettingsMessage, AppSettingsOptions};
+use crate::scenes::auth::{Auth, AuthMessage, AuthOptions};
+use crate::scenes::challenge::{ChallengeMessage, ChallengeOptions, Challenges};
+use crate::scenes::data::debug::LogEntry;
+use crate::scenes::drawing::{Drawing, DrawingMessage, DrawingOptions};
+use crate::scenes::main::{Main, MainMessage, MainOptions};
+use crate::scenes::posts::{Posts, PostsMessage, PostsOptions};
+use crate::scenes::settings::{Settings, SettingsMessage, SettingsOptions};
 use crate::utils::errors::Error;
 use crate::utils::theme::Theme;
-use iced::{Command, Element, Renderer};
+use iced::{Command, Element, Renderer, Subscription};
 use std::ops::Deref;
+use std::time::{Duration, Instant};
+
+/// The time between two frames of a [transition](TransitionState).
+const TRANSITION_FRAME: Duration = Duration::from_millis(16);
+
+/// The amount the opacity of a [Scene] changes by in a frame of a [transition](TransitionState).
+const TRANSITION_STEP: f32 = 0.08;
+
+/// The most events kept for the [Scenes](Scene) that are closed.
+const MAX_PENDING_EVENTS: usize = 50;
 
 /// The list of [Scenes](Scene) in the [Application](crate::Chartsy).
 #[derive(Debug, Clone)]
@@ -18,6 +32,52 @@ pub enum Scenes {
     Auth(Option<AuthOptions>),
     Posts(Option<PostsOptions>),
     Settings(Option<SettingsOptions>),
+    AppSettings(Option<AppSettingsOptions>),
+    Challenge(Option<ChallengeOptions>),
+    Admin(Option<AdminOptions>),
+}
+
+/// The stage of an animated transition between two [Scenes](Scene), holding the opacity of the
+/// displayed [Scene].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionState {
+    /// The outgoing [Scene] is fading out.
+    FadeOut(f32),
+
+    /// The incoming [Scene] is fading in.
+    FadeIn(f32),
+
+    /// No transition is happening.
+    None,
+}
+
+/// The identifier of each [Scene], regardless of its options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SceneId {
+    Main,
+    Drawing,
+    Auth,
+    Posts,
+    Settings,
+    AppSettings,
+    Challenge,
+    Admin,
+}
+
+impl Scenes {
+    /// Returns the [identifier](SceneId) of the [Scene].
+    pub fn get_id(&self) -> SceneId {
+        match self {
+            Scenes::Main(_) => SceneId::Main,
+            Scenes::Drawing(_) => SceneId::Drawing,
+            Scenes::Auth(_) => SceneId::Auth,
+            Scenes::Posts(_) => SceneId::Posts,
+            Scenes::Settings(_) => SceneId::Settings,
+            Scenes::AppSettings(_) => SceneId::AppSettings,
+            Scenes::Challenge(_) => SceneId::Challenge,
+            Scenes::Admin(_) => SceneId::Admin,
+        }
+    }
 }
 
 /// The [Scene] transition manager.
@@ -25,89 +85,214 @@ pub enum Scenes {
 /// Holds the current [Scene](Scenes) and an instance of each [Scene].
 pub struct SceneManager {
     current_scene: Scenes,
+    transition: TransitionState,
+    next_scene: Option<Scenes>,
     main: Option<Main>,
     drawing: Option<Drawing>,
     auth: Option<Auth>,
     posts: Option<Posts>,
     settings: Option<Settings>,
+    app_settings: Option<AppSettings>,
+    challenge: Option<Challenges>,
+    admin: Option<Admin>,
+
+    /// The events from the [event bus](Globals::publish_event) whose [Scene] is closed; they are
+    /// delivered once it is opened. Only the oldest are dropped once there are too many.
+    pending_events: Vec<Box<dyn SceneMessage>>,
 }
 
 impl SceneManager {
-    pub fn new(globals: &mut Globals) -> Self {
+    /// Creates the [SceneManager]; the first [Scene] is built by [load](SceneManager::load) once
+    /// the startup has finished.
+    pub fn new() -> Self {
         SceneManager {
             current_scene: Scenes::Main(None),
-            main: Some(Main::new(None, globals).0),
+            transition: TransitionState::None,
+            next_scene: None,
+            main: None,
             drawing: None,
             auth: None,
             posts: None,
             settings: None,
+            app_settings: None,
+            challenge: None,
+            admin: None,
+            pending_events: vec![],
         }
     }
 
-    /// Closes the current [Scene] and opens the requested [Scene].
-    pub fn load(&mut self, scene: Scenes, globals: &mut Globals) -> Command<Message> {
-        match self.current_scene {
-            Scenes::Main(_) => {
-                if let Some(main) = &self.main {
-                    main.clear(globals);
-                }
-                self.main = None
+    /// Returns the [identifier](SceneId) of the [Scene] the given message belongs to.
+    pub fn get_target(message: &dyn SceneMessage) -> Option<SceneId> {
+        let message = message.as_any();
+
+        if message.is::<MainMessage>() {
+            Some(SceneId::Main)
+        } else if message.is::<DrawingMessage>() {
+            Some(SceneId::Drawing)
+        } else if message.is::<AuthMessage>() {
+            Some(SceneId::Auth)
+        } else if message.is::<PostsMessage>() {
+            Some(SceneId::Posts)
+        } else if message.is::<SettingsMessage>() {
+            Some(SceneId::Settings)
+        } else if message.is::<AppSettingsMessage>() {
+            Some(SceneId::AppSettings)
+        } else if message.is::<ChallengeMessage>() {
+            Some(SceneId::Challenge)
+        } else if message.is::<AdminMessage>() {
+            Some(SceneId::Admin)
+        } else {
+            None
+        }
+    }
+
+    /// Fades out the current [Scene], after which the requested [Scene] is opened and faded in.
+    pub fn transition(&mut self, scene: Scenes) {
+        self.transition = match self.transition {
+            TransitionState::FadeOut(opacity) | TransitionState::FadeIn(opacity) => {
+                TransitionState::FadeOut(opacity)
             }
-            Scenes::Drawing(_) => {
-                if let Some(drawing) = &self.drawing {
-                    drawing.clear(globals);
-                }
-                self.drawing = None
+            TransitionState::None => TransitionState::FadeOut(1.0),
+        };
+        self.next_scene = Some(scene);
+    }
+
+    /// Advances the current transition by a frame, loading the requested [Scene] once the
+    /// current one has faded out.
+    pub fn tick(&mut self, globals: &mut Globals) -> Command<Message> {
+        match self.transition {
+            TransitionState::FadeOut(opacity) if opacity > TRANSITION_STEP => {
+                self.transition = TransitionState::FadeOut(opacity - TRANSITION_STEP);
+                Command::none()
             }
-            Scenes::Auth(_) => {
-                if let Some(auth) = &self.auth {
-                    auth.clear(globals);
+            TransitionState::FadeOut(_) => {
+                self.transition = TransitionState::FadeIn(0.0);
+
+                match self.next_scene.take() {
+                    Some(scene) => self.load(scene, globals),
+                    None => Command::none(),
                 }
-                self.auth = None;
             }
-            Scenes::Posts(_) => {
-                if let Some(posts) = &self.posts {
-                    posts.clear(globals);
-                }
-                self.posts = None;
+            TransitionState::FadeIn(opacity) if opacity < 1.0 - TRANSITION_STEP => {
+                self.transition = TransitionState::FadeIn(opacity + TRANSITION_STEP);
+                Command::none()
             }
-            Scenes::Settings(_) => {
-                if let Some(settings) = &self.settings {
-                    settings.clear(globals);
-                }
-                self.settings = None;
+            TransitionState::FadeIn(_) | TransitionState::None => {
+                self.transition = TransitionState::None;
+                Command::none()
             }
         }
+    }
+
+    /// Returns the [identifier](SceneId) of the current [Scene].
+    pub fn get_scene_id(&self) -> SceneId {
+        self.current_scene.get_id()
+    }
+
+    /// Returns the opacity the current [Scene] should be displayed at.
+    pub fn get_opacity(&self) -> f32 {
+        match self.transition {
+            TransitionState::FadeOut(opacity) | TransitionState::FadeIn(opacity) => opacity,
+            TransitionState::None => 1.0,
+        }
+    }
+
+    /// Caches the snapshot of the given [Scene] and closes it.
+    fn close<S: Scene>(scene: &mut Option<S>, id: SceneId, globals: &mut Globals) {
+        if let Some(scene) = scene.take() {
+            if let Some(snapshot) = scene.snapshot() {
+                globals.cache_scene(id, snapshot);
+            }
+            scene.clear(globals);
+        }
+    }
+
+    /// Returns the given options, or the cached snapshot of the [Scene] if there are none.
+    fn restore<Options: Clone + 'static>(
+        options: &Option<Options>,
+        id: SceneId,
+        globals: &mut Globals,
+    ) -> Option<Options> {
+        options.clone().or_else(|| {
+            globals
+                .take_cached_scene(id)
+                .and_then(|snapshot| snapshot.downcast_ref::<Options>().cloned())
+        })
+    }
+
+    /// Closes the current [Scene] and opens the requested [Scene], restoring its cached snapshot
+    /// when no options are given.
+    pub fn load(&mut self, scene: Scenes, globals: &mut Globals) -> Command<Message> {
+        let id = self.current_scene.get_id();
+        match self.current_scene {
+            Scenes::Main(_) => Self::close(&mut self.main, id, globals),
+            Scenes::Drawing(_) => Self::close(&mut self.drawing, id, globals),
+            Scenes::Auth(_) => Self::close(&mut self.auth, id, globals),
+            Scenes::Posts(_) => Self::close(&mut self.posts, id, globals),
+            Scenes::Settings(_) => Self::close(&mut self.settings, id, globals),
+            Scenes::AppSettings(_) => Self::close(&mut self.app_settings, id, globals),
+            Scenes::Challenge(_) => Self::close(&mut self.challenge, id, globals),
+            Scenes::Admin(_) => Self::close(&mut self.admin, id, globals),
+        }
 
         self.current_scene = scene;
 
-        match &self.current_scene {
+        let id = self.current_scene.get_id();
+        let command = match &self.current_scene {
             Scenes::Main(options) => {
-                let (main, command) = Scene::new(options.clone(), globals);
+                let (main, command) = Scene::new(Self::restore(options, id, globals), globals);
                 self.main = Some(main);
                 Command::batch(vec![command])
             }
             Scenes::Drawing(options) => {
-                let (drawing, command) = Scene::new(options.clone(), globals);
+                let (drawing, command) = Scene::new(Self::restore(options, id, globals), globals);
                 self.drawing = Some(drawing);
                 Command::batch(vec![command])
             }
             Scenes::Auth(options) => {
-                let (auth, command) = Scene::new(options.clone(), globals);
+                let (auth, command) = Scene::new(Self::restore(options, id, globals), globals);
                 self.auth = Some(auth);
                 Command::batch(vec![command])
             }
             Scenes::Posts(options) => {
-                let (posts, command) = Scene::new(options.clone(), globals);
+                let (posts, command) = Scene::new(Self::restore(options, id, globals), globals);
                 self.posts = Some(posts);
                 Command::batch(vec![command])
             }
             Scenes::Settings(options) => {
-                let (settings, command) = Scene::new(options.clone(), globals);
+                let (settings, command) = Scene::new(Self::restore(options, id, globals), globals);
                 self.settings = Some(settings);
                 Command::batch(vec![command])
             }
+            Scenes::AppSettings(options) => {
+                let (app_settings, command) =
+                    Scene::new(Self::restore(options, id, globals), globals);
+                self.app_settings = Some(app_settings);
+                Command::batch(vec![command])
+            }
+            Scenes::Challenge(options) => {
+                let (challenge, command) = Scene::new(Self::restore(options, id, globals), globals);
+                self.challenge = Some(challenge);
+                Command::batch(vec![command])
+            }
+            Scenes::Admin(options) => {
+                let (admin, command) = Scene::new(Self::restore(options, id, globals), globals);
+                self.admin = Some(admin);
+                Command::batch(vec![command])
+            }
+        };
+
+        let (events, pending_events): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_events)
+            .into_iter()
+            .partition(|event| Self::get_target(event.deref()) == Some(id));
+        self.pending_events = pending_events;
+
+        let mut commands = vec![command];
+        for event in events {
+            commands.push(self.handle_bus_event(event, globals));
         }
+
+        Command::batch(commands)
     }
 
     /// Returns the current [Scene] as a mutable variable.
@@ -116,7 +301,10 @@ impl SceneManager {
         globals: &mut Globals,
         message: Box<dyn SceneMessage>,
     ) -> Result<Command<Message>, Error> {
-        match self.current_scene {
+        let name = globals.is_debug_mode().then(|| message.get_name());
+        let start = Instant::now();
+
+        let result = match self.current_scene {
             Scenes::Main(_) => match self.main {
                 None => Err(debug_message!("Main scene missing.").into()),
                 Some(ref mut main) => main.unwrap_message(message.deref()).map(|message| {
@@ -153,7 +341,52 @@ impl SceneManager {
                     .unwrap_message(message.deref())
                     .map(|message| settings.update(globals, message)),
             },
+            Scenes::AppSettings(_) => match self.app_settings {
+                None => Err(debug_message!("App settings scene missing.").into()),
+                Some(ref mut app_settings) => app_settings
+                    .unwrap_message(message.deref())
+                    .map(|message| app_settings.update(globals, message)),
+            },
+            Scenes::Challenge(_) => match self.challenge {
+                None => Err(debug_message!("Challenge scene missing.").into()),
+                Some(ref mut challenge) => challenge
+                    .unwrap_message(message.deref())
+                    .map(|message| challenge.update(globals, message)),
+            },
+            Scenes::Admin(_) => match self.admin {
+                None => Err(debug_message!("Admin scene missing.").into()),
+                Some(ref mut admin) => admin
+                    .unwrap_message(message.deref())
+                    .map(|message| admin.update(globals, message)),
+            },
+        };
+
+        if let Some(name) = name {
+            globals.log_message(LogEntry::new(self.get_title(), name, Some(start.elapsed())));
+        }
+
+        result
+    }
+
+    /// Returns the title of the current [Scene].
+    pub fn get_title(&self) -> String {
+        match self.current_scene {
+            Scenes::Main(_) => self.main.as_ref().map(|main| main.get_title()),
+            Scenes::Drawing(_) => self.drawing.as_ref().map(|drawing| drawing.get_title()),
+            Scenes::Auth(_) => self.auth.as_ref().map(|auth| auth.get_title()),
+            Scenes::Posts(_) => self.posts.as_ref().map(|posts| posts.get_title()),
+            Scenes::Settings(_) => self.settings.as_ref().map(|settings| settings.get_title()),
+            Scenes::AppSettings(_) => self
+                .app_settings
+                .as_ref()
+                .map(|app_settings| app_settings.get_title()),
+            Scenes::Challenge(_) => self
+                .challenge
+                .as_ref()
+                .map(|challenge| challenge.get_title()),
+            Scenes::Admin(_) => self.admin.as_ref().map(|admin| admin.get_title()),
         }
+        .unwrap_or_default()
     }
 
     /// Returns the current [Scene].
@@ -179,6 +412,55 @@ impl SceneManager {
                 None => Err(debug_message!("Settings scene missing.").into()),
                 Some(ref settings) => Ok(settings.view(globals)),
             },
+            Scenes::AppSettings(_) => match self.app_settings {
+                None => Err(debug_message!("App settings scene missing.").into()),
+                Some(ref app_settings) => Ok(app_settings.view(globals)),
+            },
+            Scenes::Challenge(_) => match self.challenge {
+                None => Err(debug_message!("Challenge scene missing.").into()),
+                Some(ref challenge) => Ok(challenge.view(globals)),
+            },
+            Scenes::Admin(_) => match self.admin {
+                None => Err(debug_message!("Admin scene missing.").into()),
+                Some(ref admin) => Ok(admin.view(globals)),
+            },
+        }
+    }
+
+    /// Returns the subscriptions of the current [Scene], and the frames of the current
+    /// transition.
+    pub fn subscription(&self, globals: &Globals) -> Subscription<Message> {
+        let scene = match self.current_scene {
+            Scenes::Main(_) => self.main.as_ref().map(|main| main.subscription(globals)),
+            Scenes::Drawing(_) => self
+                .drawing
+                .as_ref()
+                .map(|drawing| drawing.subscription(globals)),
+            Scenes::Auth(_) => self.auth.as_ref().map(|auth| auth.subscription(globals)),
+            Scenes::Posts(_) => self.posts.as_ref().map(|posts| posts.subscription(globals)),
+            Scenes::Settings(_) => self
+                .settings
+                .as_ref()
+                .map(|settings| settings.subscription(globals)),
+            Scenes::AppSettings(_) => self
+                .app_settings
+                .as_ref()
+                .map(|app_settings| app_settings.subscription(globals)),
+            Scenes::Challenge(_) => self
+                .challenge
+                .as_ref()
+                .map(|challenge| challenge.subscription(globals)),
+            Scenes::Admin(_) => self.admin.as_ref().map(|admin| admin.subscription(globals)),
+        }
+        .unwrap_or(Subscription::none());
+
+        if self.transition == TransitionState::None {
+            scene
+        } else {
+            Subscription::batch(vec![
+                scene,
+                iced::time::every(TRANSITION_FRAME).map(|_| Message::TransitionTick),
+            ])
         }
     }
 
@@ -209,6 +491,127 @@ impl SceneManager {
                 None => Err(debug_message!("Settings scene missing.").into()),
                 Some(ref mut settings) => Ok(settings.handle_error(globals, error)),
             },
+            Scenes::AppSettings(_) => match self.app_settings {
+                None => Err(debug_message!("App settings scene missing.").into()),
+                Some(ref mut app_settings) => Ok(app_settings.handle_error(globals, error)),
+            },
+            Scenes::Challenge(_) => match self.challenge {
+                None => Err(debug_message!("Challenge scene missing.").into()),
+                Some(ref mut challenge) => Ok(challenge.handle_error(globals, error)),
+            },
+            Scenes::Admin(_) => match self.admin {
+                None => Err(debug_message!("Admin scene missing.").into()),
+                Some(ref mut admin) => Ok(admin.handle_error(globals, error)),
+            },
+        }
+    }
+
+    /// Notifies the current [Scene] that the window gained or lost focus.
+    pub fn focus(&mut self, globals: &mut Globals, focused: bool) -> Command<Message> {
+        fn notify(
+            scene: &mut impl Scene,
+            globals: &mut Globals,
+            focused: bool,
+        ) -> Command<Message> {
+            if focused {
+                scene.on_focus(globals)
+            } else {
+                scene.on_blur(globals)
+            }
+        }
+
+        match self.current_scene {
+            Scenes::Main(_) => self
+                .main
+                .as_mut()
+                .map(|main| notify(main, globals, focused)),
+            Scenes::Drawing(_) => self
+                .drawing
+                .as_mut()
+                .map(|drawing| notify(drawing, globals, focused)),
+            Scenes::Auth(_) => self
+                .auth
+                .as_mut()
+                .map(|auth| notify(auth, globals, focused)),
+            Scenes::Posts(_) => self
+                .posts
+                .as_mut()
+                .map(|posts| notify(posts, globals, focused)),
+            Scenes::Settings(_) => self
+                .settings
+                .as_mut()
+                .map(|settings| notify(settings, globals, focused)),
+            Scenes::AppSettings(_) => self
+                .app_settings
+                .as_mut()
+                .map(|app_settings| notify(app_settings, globals, focused)),
+            Scenes::Challenge(_) => self
+                .challenge
+                .as_mut()
+                .map(|challenge| notify(challenge, globals, focused)),
+            Scenes::Admin(_) => self
+                .admin
+                .as_mut()
+                .map(|admin| notify(admin, globals, focused)),
+        }
+        .unwrap_or(Command::none())
+    }
+
+    /// Delivers an event from the [event bus](Globals::publish_event) to the [Scene] it belongs
+    /// to. If the [Scene] is closed, the event is kept until it is opened.
+    pub fn handle_bus_event(
+        &mut self,
+        event: Box<dyn SceneMessage>,
+        globals: &mut Globals,
+    ) -> Command<Message> {
+        let command = match Self::get_target(event.deref()) {
+            Some(SceneId::Main) => self
+                .main
+                .as_mut()
+                .map(|main| main.handle_bus_event(event.deref(), globals)),
+            Some(SceneId::Drawing) => self
+                .drawing
+                .as_mut()
+                .map(|drawing| drawing.handle_bus_event(event.deref(), globals)),
+            Some(SceneId::Auth) => self
+                .auth
+                .as_mut()
+                .map(|auth| auth.handle_bus_event(event.deref(), globals)),
+            Some(SceneId::Posts) => self
+                .posts
+                .as_mut()
+                .map(|posts| posts.handle_bus_event(event.deref(), globals)),
+            Some(SceneId::Settings) => self
+                .settings
+                .as_mut()
+                .map(|settings| settings.handle_bus_event(event.deref(), globals)),
+            Some(SceneId::AppSettings) => self
+                .app_settings
+                .as_mut()
+                .map(|app_settings| app_settings.handle_bus_event(event.deref(), globals)),
+            Some(SceneId::Challenge) => self
+                .challenge
+                .as_mut()
+                .map(|challenge| challenge.handle_bus_event(event.deref(), globals)),
+            Some(SceneId::Admin) => self
+                .admin
+                .as_mut()
+                .map(|admin| admin.handle_bus_event(event.deref(), globals)),
+            None => return Command::none(),
+        };
+
+        match command {
+            Some(command) => command,
+            None => {
+                if event.is_bus_event() {
+                    if self.pending_events.len() >= MAX_PENDING_EVENTS {
+                        self.pending_events.remove(0);
+                    }
+                    self.pending_events.push(event);
+                }
+
+                Command::none()
+            }
         }
     }
 }
@@ -0,0 +1,366 @@
+use crate::scene::Message;
+use crate::scenes::admin::AdminMessage;
+use crate::scenes::data::admin::{ActionFilter, AdminTabs, AuditEntry, Report, Statistics};
+use crate::scenes::data::auth::User;
+use crate::scenes::data::challenge::Challenge;
+use crate::utils::theme::{self, Theme};
+use crate::widgets::{Tabs, WaitPanel};
+use iced::alignment::Horizontal;
+use iced::widget::{Button, Column, PickList, Row, Scrollable, Space, Text, TextInput};
+use iced::{Alignment, Element, Length, Renderer};
+use mongodb::bson::{DateTime, Uuid};
+
+/// Returns a message centered in the tab, shown when a list has no entries.
+fn empty_list<'a>(text: &'static str) -> Element<'a, Message, Theme, Renderer> {
+    Text::new(text)
+        .width(Length::Fill)
+        .horizontal_alignment(Horizontal::Center)
+        .into()
+}
+
+/// Formats the given moment for displaying.
+fn format_time(time: DateTime) -> String {
+    time.try_to_rfc3339_string()
+        .map(|time| time.replace('T', " ").chars().take(16).collect())
+        .unwrap_or_default()
+}
+
+/// Returns the tab that lists the users, each with a button for banning or unbanning them.
+/// The admin that is logged in can't ban themselves.
+pub fn users_tab<'a>(
+    users: &'a Option<Vec<User>>,
+    admin_id: Uuid,
+) -> Element<'a, Message, Theme, Renderer> {
+    let users = match users {
+        Some(users) => users,
+        None => return WaitPanel::new("Loading...").into(),
+    };
+
+    if users.is_empty() {
+        return empty_list("There are no users.");
+    }
+
+    Scrollable::new(
+        Column::with_children(users.iter().map(|user| {
+            let banned = user.is_banned();
+
+            Row::with_children(vec![
+                Column::with_children(vec![
+                    Text::new(format!("{}#{}", user.get_username(), user.get_user_tag()))
+                        .size(20.0)
+                        .into(),
+                    Text::new(user.get_email().clone())
+                        .style(theme::text::gray)
+                        .into(),
+                ])
+                .into(),
+                Space::with_width(Length::Fill).into(),
+                if banned {
+                    Text::new("Banned").style(theme::text::danger).into()
+                } else {
+                    Space::with_width(Length::Shrink).into()
+                },
+                Button::new(if banned { "Unban" } else { "Ban" })
+                    .padding(8)
+                    .on_press_maybe(
+                        (user.get_id() != admin_id).then_some(
+                            AdminMessage::SetBanStatus {
+                                user_id: user.get_id(),
+                                banned: !banned,
+                            }
+                            .into(),
+                        ),
+                    )
+                    .into(),
+            ])
+            .spacing(20.0)
+            .align_items(Alignment::Center)
+            .into()
+        }))
+        .spacing(10.0)
+        .padding(20.0),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+/// Returns the tab that lists the reported posts, each of which can either be dismissed or
+/// deleted.
+pub fn reports_tab<'a>(reports: &'a Option<Vec<Report>>) -> Element<'a, Message, Theme, Renderer> {
+    let reports = match reports {
+        Some(reports) => reports,
+        None => return WaitPanel::new("Loading...").into(),
+    };
+
+    if reports.is_empty() {
+        return empty_list("There are no reports.");
+    }
+
+    Scrollable::new(
+        Column::with_children(reports.iter().map(|report| {
+            let post = report.get_post();
+
+            Row::with_children(vec![
+                Column::with_children(vec![
+                    Text::new(format!(
+                        "Post by {}: \"{}\"",
+                        post.get_user().get_username(),
+                        post.get_description()
+                    ))
+                    .size(20.0)
+                    .into(),
+                    Text::new(report.get_description().clone()).into(),
+                    Text::new(format!(
+                        "Reported on {}",
+                        format_time(report.get_created_at())
+                    ))
+                    .style(theme::text::gray)
+                    .into(),
+                ])
+                .spacing(5.0)
+                .into(),
+                Space::with_width(Length::Fill).into(),
+                Button::new("Dismiss")
+                    .padding(8)
+                    .on_press(AdminMessage::DismissReport(report.get_id()).into())
+                    .into(),
+                Button::new("Delete post")
+                    .padding(8)
+                    .on_press(AdminMessage::DeleteReportedPost(post.get_id()).into())
+                    .into(),
+            ])
+            .spacing(20.0)
+            .align_items(Alignment::Center)
+            .into()
+        }))
+        .spacing(10.0)
+        .padding(20.0),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+/// Returns the tab that lists the latest challenges, along with a form for creating a new one.
+pub fn challenges_tab<'a>(
+    challenges: &'a Option<Vec<Challenge>>,
+    prompt: &String,
+    duration: &String,
+) -> Element<'a, Message, Theme, Renderer> {
+    let can_create =
+        !prompt.trim().is_empty() && duration.parse::<u32>().is_ok_and(|hours| hours > 0);
+
+    let form = Row::with_children(vec![
+        TextInput::new("Input prompt...", prompt)
+            .on_input(|value| AdminMessage::ChallengePromptUpdate(value).into())
+            .width(Length::Fill)
+            .into(),
+        TextInput::new("Duration in hours...", duration)
+            .on_input(|value| AdminMessage::ChallengeDurationUpdate(value).into())
+            .width(Length::Fixed(150.0))
+            .into(),
+        Button::new("Create challenge")
+            .padding(8)
+            .on_press_maybe(can_create.then_some(AdminMessage::CreateChallenge.into()))
+            .into(),
+    ])
+    .spacing(10.0)
+    .padding(20.0)
+    .align_items(Alignment::Center);
+
+    let list = match challenges {
+        None => WaitPanel::new("Loading...").into(),
+        Some(challenges) if challenges.is_empty() => empty_list("No challenges were created yet."),
+        Some(challenges) => {
+            let now = DateTime::now();
+
+            Scrollable::new(
+                Column::with_children(challenges.iter().map(|challenge| {
+                    let active = challenge.is_active(now);
+
+                    Row::with_children(vec![
+                        Column::with_children(vec![
+                            Text::new(challenge.get_prompt().clone()).size(20.0).into(),
+                            Text::new(format!(
+                                "{} - {}",
+                                format_time(challenge.get_start_time()),
+                                format_time(challenge.get_end_time())
+                            ))
+                            .style(theme::text::gray)
+                            .into(),
+                        ])
+                        .into(),
+                        Space::with_width(Length::Fill).into(),
+                        if active {
+                            Button::new("Close")
+                                .padding(8)
+                                .on_press(AdminMessage::CloseChallenge(challenge.get_id()).into())
+                                .into()
+                        } else if challenge.get_end_time() < now {
+                            Text::new("Ended").style(theme::text::gray).into()
+                        } else {
+                            Text::new("Scheduled").style(theme::text::gray).into()
+                        },
+                    ])
+                    .spacing(20.0)
+                    .align_items(Alignment::Center)
+                    .into()
+                }))
+                .spacing(10.0)
+                .padding(20.0),
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+        }
+    };
+
+    Column::with_children(vec![form.into(), list])
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+/// Returns the tab that shows the statistics of the site.
+pub fn statistics_tab<'a>(
+    statistics: &Option<Statistics>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let statistics = match statistics {
+        Some(statistics) => statistics,
+        None => return WaitPanel::new("Loading...").into(),
+    };
+
+    let statistic = |value: u64, label: &'static str| -> Element<'a, Message, Theme, Renderer> {
+        Column::with_children(vec![
+            Text::new(value.to_string()).size(40.0).into(),
+            Text::new(label).style(theme::text::gray).into(),
+        ])
+        .align_items(Alignment::Center)
+        .width(Length::Fill)
+        .into()
+    };
+
+    Column::with_children(vec![
+        Row::with_children(vec![
+            statistic(statistics.posts, "Posts"),
+            statistic(statistics.users, "Users"),
+            statistic(statistics.active_sessions, "Active drawing sessions"),
+        ])
+        .width(Length::Fill)
+        .into(),
+        Button::new("Refresh")
+            .padding(8)
+            .on_press(AdminMessage::SelectTab(AdminTabs::Statistics).into())
+            .into(),
+    ])
+    .spacing(20.0)
+    .padding(20.0)
+    .width(Length::Fill)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+/// Returns the tab that lists the latest entries of the audit log, along with the inputs for
+/// filtering them by user and by action.
+pub fn audit_log_tab<'a>(
+    entries: &'a Option<Vec<AuditEntry>>,
+    user_filter: &String,
+    action_filter: ActionFilter,
+) -> Element<'a, Message, Theme, Renderer> {
+    let filters = Row::with_children(vec![
+        TextInput::new("Filter by user tag...", user_filter)
+            .on_input(|value| AdminMessage::AuditUserFilterUpdate(value).into())
+            .on_submit(AdminMessage::SelectTab(AdminTabs::AuditLog).into())
+            .width(Length::Fill)
+            .into(),
+        PickList::new(ActionFilter::OPTIONS, Some(action_filter), |action| {
+            AdminMessage::AuditActionFilterUpdate(action).into()
+        })
+        .width(Length::Fixed(200.0))
+        .into(),
+        Button::new("Refresh")
+            .padding(8)
+            .on_press(AdminMessage::SelectTab(AdminTabs::AuditLog).into())
+            .into(),
+    ])
+    .spacing(10.0)
+    .padding(20.0)
+    .align_items(Alignment::Center);
+
+    let list = match entries {
+        None => WaitPanel::new("Loading...").into(),
+        Some(entries) if entries.is_empty() => empty_list("No matching entries were found."),
+        Some(entries) => Scrollable::new(
+            Column::with_children(entries.iter().map(|entry| {
+                Row::with_children(vec![
+                    Text::new(format_time(entry.get_created_at()))
+                        .style(theme::text::gray)
+                        .into(),
+                    Text::new(format!("@{}", entry.get_user().get_user_tag())).into(),
+                    Text::new(entry.get_action().clone()).into(),
+                    Space::with_width(Length::Fill).into(),
+                    Text::new(
+                        entry
+                            .get_target_id()
+                            .map(|target_id| target_id.to_string())
+                            .unwrap_or_default(),
+                    )
+                    .style(theme::text::gray)
+                    .into(),
+                ])
+                .spacing(20.0)
+                .align_items(Alignment::Center)
+                .into()
+            }))
+            .spacing(5.0)
+            .padding(20.0),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into(),
+    };
+
+    Column::with_children(vec![filters.into(), list])
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+/// Returns the tab bar of the admin scene.
+pub fn admin_tabs<'a>(
+    users_tab: Element<'a, Message, Theme, Renderer>,
+    reports_tab: Element<'a, Message, Theme, Renderer>,
+    challenges_tab: Element<'a, Message, Theme, Renderer>,
+    statistics_tab: Element<'a, Message, Theme, Renderer>,
+    audit_log_tab: Element<'a, Message, Theme, Renderer>,
+    active_tab: AdminTabs,
+) -> Element<'a, Message, Theme, Renderer> {
+    Tabs::new_with_tabs(
+        vec![
+            (AdminTabs::Users, String::from("Users"), users_tab),
+            (AdminTabs::Reports, String::from("Reports"), reports_tab),
+            (
+                AdminTabs::Challenges,
+                String::from("Challenges"),
+                challenges_tab,
+            ),
+            (
+                AdminTabs::Statistics,
+                String::from("Statistics"),
+                statistics_tab,
+            ),
+            (
+                AdminTabs::AuditLog,
+                String::from("Audit log"),
+                audit_log_tab,
+            ),
+        ],
+        |tab| AdminMessage::SelectTab(tab).into(),
+    )
+    .selected(active_tab)
+    .describe("The sections of the admin panel")
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
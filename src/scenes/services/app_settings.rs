@@ -0,0 +1,223 @@
+use std::io;
+
+use directories::ProjectDirs;
+use iced::{
+    widget::{Button, Column, Container, PickList, Row, Text, TextInput},
+    Alignment, Element, Length, Renderer,
+};
+
+use crate::{
+    debug_message,
+    scene::Message,
+    scenes::{
+        app_settings::AppSettingsMessage,
+        data::{
+            app_settings::{Preferences, ThemeMode, CACHE_CAPACITIES, FONT_SIZES},
+            drawing::{AutoSaveInterval, SaveMode},
+        },
+    },
+    utils::{cache::Cache, errors::Error, theme::Theme},
+};
+
+/// Returns the preferences stored locally, or the default ones if none were stored yet.
+/// Runs synchronously, since the preferences are needed before the application starts.
+pub fn load_preferences() -> Result<Preferences, Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+
+    let file_path = proj_dirs.config_local_dir().join("preferences.json");
+    let input = match std::fs::read_to_string(file_path) {
+        Ok(input) => input,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Preferences::default()),
+        Err(err) => return Err(debug_message!("{}", err).into()),
+    };
+
+    let json = json::parse(&*input).map_err(|err| debug_message!("{}", err).into())?;
+
+    Ok(Preferences::from_json(&json))
+}
+
+/// Stores the given preferences locally.
+pub async fn save_preferences(preferences: Preferences) -> Result<(), Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+    let dir_path = proj_dirs.config_local_dir();
+
+    tokio::fs::create_dir_all(dir_path)
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    tokio::fs::write(
+        dir_path.join("preferences.json"),
+        json::stringify(preferences.to_json()),
+    )
+    .await
+    .map_err(|err| debug_message!("{}", err).into())
+}
+
+/// Returns a row with the name of a preference and the input that changes it.
+fn preference_row<'a>(
+    label: &'a str,
+    input: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> Element<'a, Message, Theme, Renderer> {
+    Row::with_children(vec![
+        Text::new(label).width(Length::FillPortion(1)).into(),
+        input.into(),
+    ])
+    .spacing(20.0)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+pub fn theme_input<'a>(preferences: &Preferences) -> Element<'a, Message, Theme, Renderer> {
+    preference_row(
+        "Theme",
+        PickList::new(ThemeMode::ALL, Some(preferences.get_theme()), |theme| {
+            AppSettingsMessage::SetTheme(theme).into()
+        })
+        .width(Length::FillPortion(1)),
+    )
+}
+
+pub fn canvas_size_input<'a>(
+    width: &'a String,
+    height: &'a String,
+) -> Element<'a, Message, Theme, Renderer> {
+    preference_row(
+        "Default canvas size",
+        Row::with_children(vec![
+            TextInput::new("Width", width)
+                .on_input(|value| AppSettingsMessage::UpdateCanvasWidth(value).into())
+                .into(),
+            Text::new("x").into(),
+            TextInput::new("Height", height)
+                .on_input(|value| AppSettingsMessage::UpdateCanvasHeight(value).into())
+                .into(),
+        ])
+        .spacing(10.0)
+        .align_items(Alignment::Center)
+        .width(Length::FillPortion(1)),
+    )
+}
+
+pub fn auto_save_input<'a>(preferences: &Preferences) -> Element<'a, Message, Theme, Renderer> {
+    preference_row(
+        "Auto-save",
+        PickList::new(
+            AutoSaveInterval::OPTIONS,
+            Some(AutoSaveInterval(preferences.get_auto_save_interval())),
+            |interval| AppSettingsMessage::SetAutoSave(interval).into(),
+        )
+        .width(Length::FillPortion(1)),
+    )
+}
+
+pub fn save_mode_input<'a>(preferences: &Preferences) -> Element<'a, Message, Theme, Renderer> {
+    preference_row(
+        "Default save mode",
+        PickList::new(
+            SaveMode::ALL,
+            Some(preferences.get_save_mode()),
+            |save_mode| AppSettingsMessage::SetSaveMode(save_mode).into(),
+        )
+        .width(Length::FillPortion(1)),
+    )
+}
+
+pub fn font_size_input<'a>(preferences: &Preferences) -> Element<'a, Message, Theme, Renderer> {
+    preference_row(
+        "Font size (applied after restart)",
+        PickList::new(FONT_SIZES, Some(preferences.get_font_size()), |font_size| {
+            AppSettingsMessage::SetFontSize(font_size).into()
+        })
+        .width(Length::FillPortion(1)),
+    )
+}
+
+pub fn cache_capacity_input<'a>(
+    preferences: &Preferences,
+) -> Element<'a, Message, Theme, Renderer> {
+    preference_row(
+        "Cached images (applied after restart)",
+        PickList::new(
+            CACHE_CAPACITIES,
+            Some(preferences.get_cache_capacity()),
+            |cache_capacity| AppSettingsMessage::SetCacheCapacity(cache_capacity).into(),
+        )
+        .width(Length::FillPortion(1)),
+    )
+}
+
+pub fn regenerate_previews_input<'a>(regenerating: bool) -> Element<'a, Message, Theme, Renderer> {
+    let button = Button::new(if regenerating {
+        "Regenerating..."
+    } else {
+        "Regenerate all"
+    })
+    .width(Length::FillPortion(1));
+
+    preference_row(
+        "Offline drawing previews",
+        if regenerating {
+            button
+        } else {
+            button.on_press(AppSettingsMessage::RegenerateAllPreviews.into())
+        },
+    )
+}
+
+pub fn debug_info_input<'a>(show_debug_info: bool) -> Element<'a, Message, Theme, Renderer> {
+    preference_row(
+        "Debug info",
+        Button::new(if show_debug_info { "Hide" } else { "Show" })
+            .width(Length::FillPortion(1))
+            .on_press(AppSettingsMessage::ToggleDebugInfo.into()),
+    )
+}
+
+/// Formats a number of bytes in the largest unit that keeps the value above 1.
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Displays the memory used by the cached images and the usage counters of the cache, along
+/// with a button that empties the cache.
+pub fn debug_info_panel<'a>(cache: &Cache) -> Element<'a, Message, Theme, Renderer> {
+    let stats = cache.stats();
+
+    Container::new(
+        Column::with_children(vec![
+            Text::new(format!(
+                "Cache memory usage: {}",
+                format_bytes(cache.memory_usage_bytes())
+            ))
+            .into(),
+            Text::new(format!(
+                "Hits: {}, misses: {}, evictions: {}",
+                stats.hits, stats.misses, stats.evictions
+            ))
+            .into(),
+            Button::new("Clear cache")
+                .on_press(AppSettingsMessage::ClearCache.into())
+                .into(),
+        ])
+        .spacing(10.0),
+    )
+    .padding(10.0)
+    .width(Length::Fill)
+    .style(iced::widget::container::bordered_box)
+    .into()
+}
@@ -1,22 +1,42 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use iced::{
     advanced::widget::Text,
-    widget::{Button, Column, TextInput},
-    Element, Length, Renderer,
+    widget::{svg::Handle, Button, Column, Row, Svg, TextInput},
+    Alignment, Element, Length, Renderer,
 };
+use json::JsonValue;
+use mongodb::bson::Uuid;
+use qrcode::QrCode;
+use reqwest::Url;
+use svg::node::element::{Rectangle, SVG};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 
 use crate::{
+    debug_message,
     scene::{Globals, Message},
     scenes::{
         auth::AuthMessage,
-        data::auth::{AuthTabIds, LogInField, LogInForm, RegisterField, RegisterForm},
+        data::auth::{
+            AuthTabIds, LogInField, LogInForm, OAuthProfile, OAuthProvider, PasswordResetField,
+            RegisterField, RegisterForm, User,
+        },
     },
     utils::{
-        errors::AuthError,
+        errors::{AuthError, Error},
         theme::{self, Theme},
     },
-    widgets::{Centered, Tabs},
+    widgets::{card::Card, closeable::Closeable, Centered, Tabs},
 };
 
+/// How long the user has to authorize the application at an OAuth provider.
+const OAUTH_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The number of empty modules around the QR code.
+const QR_CODE_MARGIN: usize = 4;
+
 pub fn register_tab<'a>(
     register_form: &RegisterForm,
     register_code: &Option<String>,
@@ -120,6 +140,28 @@ pub fn log_in_tab<'a>(
         } else {
             Button::new("Log In").into()
         },
+        Button::new(Text::new("Forgot password?").size(14.0))
+            .style(iced::widget::button::text)
+            .on_press(AuthMessage::ForgotPassword.into())
+            .into(),
+        Text::new("Or log in with:").into(),
+        Row::with_children(
+            [OAuthProvider::Google, OAuthProvider::GitHub]
+                .into_iter()
+                .map(|provider| {
+                    Button::new(Text::new(provider.to_string()))
+                        .style(iced::widget::button::secondary)
+                        .on_press_maybe(
+                            globals
+                                .get_db()
+                                .map(|_| AuthMessage::OAuthLogin(provider).into()),
+                        )
+                        .into()
+                })
+                .collect::<Vec<Element<'a, Message, Theme, Renderer>>>(),
+        )
+        .spacing(10.0)
+        .into(),
     ])
     .spacing(10.0)
     .into()
@@ -140,8 +182,408 @@ pub fn tabs<'a>(
         )
         .selected(active_tab)
         .width(Length::Fill)
-        .height(Length::Fill),
+        .height(Length::Fill)
+        .describe("Register or log in"),
     )
     .height(0.75)
     .into()
 }
+
+/// Renders the given data as a QR code, drawn as an SVG grid of pixels.
+fn qr_code<'a>(data: &str) -> Element<'a, Message, Theme, Renderer> {
+    let code = match QrCode::new(data.as_bytes()) {
+        Ok(code) => code,
+        Err(_) => return Text::new("Could not generate QR code.").into(),
+    };
+
+    let size = code.width() + 2 * QR_CODE_MARGIN;
+    let mut document = SVG::new().set("viewBox", (0, 0, size, size)).add(
+        Rectangle::new()
+            .set("width", size)
+            .set("height", size)
+            .set("fill", "white"),
+    );
+
+    for y in 0..code.width() {
+        for x in 0..code.width() {
+            if code[(x, y)] == qrcode::Color::Dark {
+                document = document.add(
+                    Rectangle::new()
+                        .set("x", x + QR_CODE_MARGIN)
+                        .set("y", y + QR_CODE_MARGIN)
+                        .set("width", 1)
+                        .set("height", 1)
+                        .set("fill", "black"),
+                );
+            }
+        }
+    }
+
+    Svg::new(Handle::from_memory(document.to_string().into_bytes()))
+        .width(200.0)
+        .height(200.0)
+        .into()
+}
+
+/// Displays the input of a two-factor authentication code, with the given error above it.
+fn totp_input<'a>(
+    code: &String,
+    error: &Option<AuthError>,
+    on_submit: AuthMessage,
+) -> Element<'a, Message, Theme, Renderer> {
+    Column::with_children([
+        Text::new(if let Some(error) = error {
+            error.to_string()
+        } else {
+            String::from("")
+        })
+        .size(14.0)
+        .style(theme::text::danger)
+        .into(),
+        TextInput::new("Input 6-digit code...", code)
+            .on_input(|value| {
+                AuthMessage::TotpCodeUpdate(
+                    value.chars().filter(char::is_ascii_digit).take(6).collect(),
+                )
+                .into()
+            })
+            .on_submit(on_submit.clone().into())
+            .into(),
+        Button::new("Confirm").on_press(on_submit.into()).into(),
+    ])
+    .spacing(10.0)
+    .into()
+}
+
+/// Displays the QR code of a new two-factor authentication secret, which the user scans with
+/// their authenticator app before confirming the first code it generates.
+pub fn totp_setup<'a>(
+    secret: String,
+    email: String,
+    code: &String,
+    error: &Option<AuthError>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let qr_code = match User::gen_totp(&secret, &email) {
+        Ok(totp) => qr_code(&totp.get_url()),
+        Err(_) => Text::new("Could not generate QR code.").into(),
+    };
+
+    Closeable::<Message, Theme, Renderer>::new(
+        Card::new(
+            Text::new("Enable two-factor authentication"),
+            Column::with_children([
+                Text::new("Scan this code with your authenticator app:").into(),
+                qr_code,
+                Text::new(format!("Or enter the key manually: {}", secret))
+                    .size(14.0)
+                    .into(),
+                totp_input(code, error, AuthMessage::ConfirmTotp),
+            ])
+            .align_items(Alignment::Center)
+            .spacing(10.0),
+        )
+        .width(Length::Fixed(400.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(Into::<Message>::into(AuthMessage::CancelTotp), 25.0)
+    .close_padding(7.0)
+    .into()
+}
+
+/// Asks the user that is logging in for their two-factor authentication code.
+pub fn totp_challenge<'a>(
+    code: &String,
+    error: &Option<AuthError>,
+) -> Element<'a, Message, Theme, Renderer> {
+    Closeable::<Message, Theme, Renderer>::new(
+        Card::new(
+            Text::new("Two-factor authentication"),
+            Column::with_children([
+                Text::new(AuthError::TotpRequired.to_string()).into(),
+                totp_input(code, error, AuthMessage::SubmitTotp),
+            ])
+            .spacing(10.0),
+        )
+        .width(Length::Fixed(300.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(Into::<Message>::into(AuthMessage::CancelTotp), 25.0)
+    .close_padding(7.0)
+    .into()
+}
+
+/// Lets the user log in through the given OAuth provider: the authorization page is opened in
+/// the browser, and the provider redirects to a server listening on a loopback port, which
+/// receives the authorization code. The code is exchanged for an access token, which is used to
+/// fetch the profile of the user.
+pub async fn oauth_login(provider: OAuthProvider) -> Result<OAuthProfile, Error> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?;
+    let port = listener
+        .local_addr()
+        .map_err(|err| debug_message!("{}", err).into())?
+        .port();
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+    let state = Uuid::new().to_string();
+
+    let authorization_url = Url::parse_with_params(
+        provider.get_authorization_url(),
+        &[
+            ("client_id", provider.get_client_id()),
+            ("redirect_uri", redirect_uri.clone()),
+            ("response_type", String::from("code")),
+            ("scope", String::from(provider.get_scope())),
+            ("state", state.clone()),
+        ],
+    )
+    .map_err(|err| debug_message!("{}", err).into())?;
+    open::that(authorization_url.as_str()).map_err(|err| debug_message!("{}", err).into())?;
+
+    let code = tokio::time::timeout(OAUTH_TIMEOUT, receive_oauth_code(&listener, &state))
+        .await
+        .map_err(|_| debug_message!("The OAuth authorization timed out.").into())??;
+
+    let client = reqwest::Client::new();
+    let token = client
+        .post(provider.get_token_url())
+        .header("Accept", "application/json")
+        .form(&[
+            ("client_id", provider.get_client_id()),
+            ("client_secret", provider.get_client_secret()),
+            ("code", code),
+            ("grant_type", String::from("authorization_code")),
+            ("redirect_uri", redirect_uri),
+        ])
+        .send()
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?
+        .text()
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?;
+    let token = json::parse(&*token).map_err(|err| debug_message!("{}", err).into())?;
+    let access_token = token["access_token"]
+        .as_str()
+        .ok_or(debug_message!("The OAuth provider did not return an access token.").into())?
+        .to_string();
+
+    let profile = get_oauth_json(&client, provider.get_profile_url(), &access_token).await?;
+    let (subject, username) = match provider {
+        OAuthProvider::Google => (
+            profile["sub"].as_str().unwrap_or_default().to_string(),
+            &profile["name"],
+        ),
+        OAuthProvider::GitHub => (profile["id"].to_string(), &profile["login"]),
+    };
+    let username = username.as_str().unwrap_or_default().to_string();
+
+    let (email, email_verified) = match provider {
+        OAuthProvider::Google => (
+            profile["email"]
+                .as_str()
+                .ok_or(debug_message!("The OAuth provider did not return an email.").into())?
+                .to_string(),
+            profile["email_verified"] == true,
+        ),
+        // GitHub leaves out the email of users that keep it private, and doesn't tell whether
+        // it has been verified.
+        OAuthProvider::GitHub => {
+            let emails =
+                get_oauth_json(&client, "https://api.github.com/user/emails", &access_token)
+                    .await?;
+            let email = emails
+                .members()
+                .find(|email| email["primary"] == true)
+                .ok_or(debug_message!("The GitHub account has no primary email.").into())?;
+
+            (
+                email["email"]
+                    .as_str()
+                    .ok_or(debug_message!("The OAuth provider did not return an email.").into())?
+                    .to_string(),
+                email["verified"] == true,
+            )
+        }
+    };
+
+    if subject.is_empty() || subject == "null" {
+        return Err(debug_message!("The OAuth provider did not return a user id.").into());
+    }
+
+    Ok(OAuthProfile {
+        provider,
+        subject,
+        email,
+        email_verified,
+        username,
+    })
+}
+
+/// Fetches the json data at the given url of an OAuth provider, authorized by the given access
+/// token.
+async fn get_oauth_json(
+    client: &reqwest::Client,
+    url: &str,
+    access_token: &String,
+) -> Result<JsonValue, Error> {
+    let response = client
+        .get(url)
+        .bearer_auth(access_token)
+        .header("Accept", "application/json")
+        .header("User-Agent", "Chartsy")
+        .send()
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?
+        .text()
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    json::parse(&*response).map_err(|err| debug_message!("{}", err).into())
+}
+
+/// Waits for the OAuth provider to redirect the browser to the loopback server, and returns the
+/// authorization code it sends. Requests that do not carry the given state are ignored.
+async fn receive_oauth_code(listener: &TcpListener, state: &String) -> Result<String, Error> {
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|err| debug_message!("{}", err).into())?;
+
+        let mut buffer = vec![0; 4096];
+        let length = stream
+            .read(&mut buffer)
+            .await
+            .map_err(|err| debug_message!("{}", err).into())?;
+        let request = String::from_utf8_lossy(&buffer[..length]);
+
+        // The request line looks like `GET /callback?code=...&state=... HTTP/1.1`.
+        let Some(url) = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|path| Url::parse(&*format!("http://127.0.0.1{}", path)).ok())
+        else {
+            continue;
+        };
+
+        let parameters = url.query_pairs().collect::<HashMap<_, _>>();
+        if parameters.get("state").map(|value| value.as_ref()) != Some(state.as_str()) {
+            continue;
+        }
+
+        let (message, result) = match parameters.get("code") {
+            Some(code) => (
+                "You are now logged in to Chartsy. You can close this window.",
+                Ok(code.to_string()),
+            ),
+            None => (
+                "The authorization was denied. You can close this window.",
+                Err(debug_message!("The OAuth authorization was denied.").into()),
+            ),
+        };
+
+        let _ = stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    message.len(),
+                    message
+                )
+                .as_bytes(),
+            )
+            .await;
+
+        return result;
+    }
+}
+
+/// Displays the given error of the password reset, if there is one.
+fn reset_error_text<'a>(error: &Option<AuthError>) -> Element<'a, Message, Theme, Renderer> {
+    Text::new(if let Some(error) = error {
+        error.to_string()
+    } else {
+        String::from("")
+    })
+    .size(14.0)
+    .style(theme::text::danger)
+    .into()
+}
+
+/// Asks for the email of the account whose password was forgotten.
+pub fn forgot_password<'a>(
+    email: &String,
+    error: &Option<AuthError>,
+) -> Element<'a, Message, Theme, Renderer> {
+    Closeable::<Message, Theme, Renderer>::new(
+        Card::new(
+            Text::new("Forgot password"),
+            Column::with_children([
+                Text::new("A password reset link will be sent to your email address.").into(),
+                reset_error_text(error),
+                TextInput::new("Input email...", email)
+                    .on_input(|value| {
+                        AuthMessage::PasswordResetFieldUpdate(PasswordResetField::Email(value))
+                            .into()
+                    })
+                    .on_submit(AuthMessage::RequestPasswordReset(email.clone()).into())
+                    .into(),
+                Button::new("Send reset link")
+                    .on_press(AuthMessage::RequestPasswordReset(email.clone()).into())
+                    .into(),
+            ])
+            .spacing(10.0),
+        )
+        .width(Length::Fixed(400.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(Into::<Message>::into(AuthMessage::ClosePasswordReset), 25.0)
+    .close_padding(7.0)
+    .into()
+}
+
+/// Asks for a password reset token and the new password.
+pub fn reset_password<'a>(
+    token: &String,
+    password: &String,
+    error: &Option<AuthError>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let reset = AuthMessage::ResetPassword {
+        token: token.clone(),
+        new_password: password.clone(),
+    };
+
+    Closeable::<Message, Theme, Renderer>::new(
+        Card::new(
+            Text::new("Reset password"),
+            Column::with_children([
+                Text::new("If the email belongs to an account, a reset link has been sent to it.")
+                    .into(),
+                reset_error_text(error),
+                Text::new("Reset token:").into(),
+                TextInput::new("Input reset token...", token)
+                    .on_input(|value| {
+                        AuthMessage::PasswordResetFieldUpdate(PasswordResetField::Token(value))
+                            .into()
+                    })
+                    .into(),
+                Text::new("New password:").into(),
+                TextInput::new("Input new password...", password)
+                    .on_input(|value| {
+                        AuthMessage::PasswordResetFieldUpdate(PasswordResetField::Password(value))
+                            .into()
+                    })
+                    .on_submit(reset.clone().into())
+                    .secure(true)
+                    .into(),
+                Button::new("Reset password").on_press(reset.into()).into(),
+            ])
+            .spacing(10.0),
+        )
+        .width(Length::Fixed(400.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(Into::<Message>::into(AuthMessage::ClosePasswordReset), 25.0)
+    .close_padding(7.0)
+    .into()
+}
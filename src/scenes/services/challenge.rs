@@ -0,0 +1,111 @@
+use crate::scene::Message;
+use crate::scenes::challenge::ChallengeMessage;
+use crate::scenes::data::challenge::{Challenge, ChallengeTabs};
+use crate::scenes::data::drawing::SaveMode;
+use crate::scenes::data::posts::Post;
+use crate::scenes::drawing::DrawingOptions;
+use crate::scenes::scenes::Scenes;
+use crate::scenes::services;
+use crate::utils::theme::{self, Theme};
+use crate::widgets::{Tabs, WaitPanel};
+use iced::alignment::Horizontal;
+use iced::widget::{Button, Column, Row, Scrollable, Space, Text};
+use iced::{Alignment, Element, Length, Renderer};
+use mongodb::bson::DateTime;
+
+/// Returns the tab that displays the prompt of the challenge.
+pub fn prompt_tab<'a>(challenge: &Challenge) -> Element<'a, Message, Theme, Renderer> {
+    let hours_left =
+        (challenge.get_end_time().timestamp_millis() - DateTime::now().timestamp_millis()).max(0)
+            / (60 * 60 * 1000);
+
+    Column::with_children(vec![
+        Text::new(challenge.get_prompt().clone())
+            .size(30.0)
+            .horizontal_alignment(Horizontal::Center)
+            .into(),
+        Text::new(format!(
+            "Ends in {} {}",
+            hours_left,
+            if hours_left == 1 { "hour" } else { "hours" }
+        ))
+        .style(theme::text::gray)
+        .into(),
+        Button::new("Submit drawing")
+            .padding(8)
+            .on_press(Message::ChangeScene(Scenes::Drawing(Some(
+                DrawingOptions::new(None, None, Some(SaveMode::Online))
+                    .challenge(challenge.get_id()),
+            ))))
+            .into(),
+    ])
+    .spacing(20.0)
+    .padding(20.0)
+    .width(Length::Fill)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+/// Returns the tab that ranks the entries of the challenge by their average rating.
+pub fn leaderboard_tab<'a>(
+    entries: &'a Option<Vec<Post>>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let entries = match entries {
+        Some(entries) => entries,
+        None => return WaitPanel::new("Loading...").into(),
+    };
+
+    if entries.is_empty() {
+        return Text::new("No drawings were submitted yet.")
+            .width(Length::Fill)
+            .horizontal_alignment(Horizontal::Center)
+            .into();
+    }
+
+    Scrollable::new(
+        Column::with_children(entries.iter().enumerate().map(|(index, post)| {
+            Row::with_children(vec![
+                Text::new(format!("#{}", index + 1)).size(25.0).into(),
+                Column::with_children(vec![
+                    Text::new(post.get_user().get_username()).size(20.0).into(),
+                    Text::new(post.get_description().clone()).into(),
+                ])
+                .into(),
+                Space::with_width(Length::Fill).into(),
+                services::posts::average_rating(post),
+            ])
+            .spacing(20.0)
+            .align_items(Alignment::Center)
+            .into()
+        }))
+        .spacing(10.0)
+        .padding(20.0),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+/// Returns the tab bar of the challenge scene.
+pub fn challenge_tabs<'a>(
+    prompt_tab: Element<'a, Message, Theme, Renderer>,
+    leaderboard_tab: Element<'a, Message, Theme, Renderer>,
+    active_tab: ChallengeTabs,
+) -> Element<'a, Message, Theme, Renderer> {
+    Tabs::new_with_tabs(
+        vec![
+            (ChallengeTabs::Prompt, String::from("Prompt"), prompt_tab),
+            (
+                ChallengeTabs::Leaderboard,
+                String::from("Leaderboard"),
+                leaderboard_tab,
+            ),
+        ],
+        |tab| ChallengeMessage::SelectTab(tab).into(),
+    )
+    .selected(active_tab)
+    .describe("The prompt and the leaderboard of the challenge")
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
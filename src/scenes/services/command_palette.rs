@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use iced::{
+    widget::{text_input, Button, Column, Scrollable, Text, TextInput},
+    Element, Length, Renderer,
+};
+
+use crate::{
+    scene::{Message, SceneMessage},
+    scenes::{
+        drawing::DrawingMessage,
+        main::MainMessage,
+        posts::PostsMessage,
+        scenes::{SceneId, Scenes},
+    },
+    utils::theme::{self, Theme},
+    widgets::{card::Card, closeable::Closeable, combo_box, Centered},
+};
+
+/// The maximum number of actions listed in the command palette.
+const MAX_RESULTS: usize = 20;
+
+/// Returns the id of the input of the command palette.
+pub fn input_id() -> text_input::Id {
+    text_input::Id::new("command_palette")
+}
+
+/// Returns the actions that can be run from the command palette, by name: the global actions,
+/// and the actions of the given [Scene](crate::scene::Scene) that take no parameters.
+pub fn get_actions(scene: SceneId, user_logged_in: bool) -> HashMap<String, fn() -> Message> {
+    let mut actions: HashMap<String, fn() -> Message> = HashMap::new();
+
+    actions.insert(String::from("Go to main menu"), || {
+        Message::ChangeScene(Scenes::Main(None))
+    });
+    actions.insert(String::from("Open app settings"), || {
+        Message::ChangeScene(Scenes::AppSettings(None))
+    });
+    actions.insert(String::from("Quit"), || Message::Quit);
+
+    if user_logged_in {
+        actions.insert(String::from("Browse posts"), || {
+            Message::ChangeScene(Scenes::Posts(None))
+        });
+        actions.insert(String::from("Open account settings"), || {
+            Message::ChangeScene(Scenes::Settings(None))
+        });
+        actions.insert(String::from("Open today's challenge"), || {
+            Message::ChangeScene(Scenes::Challenge(None))
+        });
+        actions.insert(String::from("Check notifications"), || {
+            Message::CheckNotifications
+        });
+    }
+
+    let scene_actions: &[fn() -> Message] = match scene {
+        SceneId::Main => &[|| MainMessage::ImportArchive.into()],
+        SceneId::Drawing => &[
+            || DrawingMessage::SaveAs.into(),
+            || DrawingMessage::ExportLayeredPdf.into(),
+            || DrawingMessage::ToggleLock.into(),
+        ],
+        SceneId::Posts => &[|| PostsMessage::LoadPosts.into()],
+        _ => &[],
+    };
+
+    // The actions of the scenes are named after their messages.
+    for action in scene_actions {
+        if let Message::DoAction(message) = action() {
+            actions.insert(message.get_name(), *action);
+        }
+    }
+
+    actions
+}
+
+/// Displays the command palette: an input that filters the actions by name, above the list of
+/// matching actions.
+pub fn command_palette<'a>(
+    input: &str,
+    actions: &HashMap<String, fn() -> Message>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let mut names = actions.keys().cloned().collect::<Vec<String>>();
+    names.sort();
+    if !input.is_empty() {
+        names = combo_box::filter_tags(names, input, MAX_RESULTS);
+    }
+
+    let mut input = TextInput::new("Search actions...", input)
+        .id(input_id())
+        .on_input(Message::UpdateCommandPaletteInput)
+        .padding(8);
+    if let Some(first) = names.first() {
+        input = input.on_submit(Message::RunCommand(first.clone()));
+    }
+
+    let results = Column::with_children(
+        names
+            .into_iter()
+            .map(|name| {
+                Button::new(Text::new(name.clone()))
+                    .width(Length::Fill)
+                    .style(iced::widget::button::text)
+                    .on_press(Message::RunCommand(name))
+                    .into()
+            })
+            .collect::<Vec<Element<'a, Message, Theme, Renderer>>>(),
+    )
+    .spacing(2.0);
+
+    Centered::new(
+        Closeable::<Message, Theme, Renderer>::new(Card::new(
+            Text::new("Command palette"),
+            Column::with_children(vec![
+                input.into(),
+                Scrollable::new(results).height(Length::Fill).into(),
+            ])
+            .spacing(10.0),
+        ))
+        .style(theme::closeable::Closeable::Transparent)
+        .on_close(Message::ToggleCommandPalette, 25.0)
+        .close_padding(7.0),
+    )
+    .height(0.6)
+    .into()
+}
@@ -0,0 +1,74 @@
+use std::collections::VecDeque;
+
+use iced::{
+    widget::{Column, Row, Scrollable, Space, Text},
+    Alignment, Element, Length, Renderer,
+};
+
+use crate::{
+    scene::Message,
+    scenes::data::debug::LogEntry,
+    utils::theme::{self, Theme},
+    widgets::{card::Card, closeable::Closeable, Centered},
+};
+
+/// Displays the message log: the messages handled while the debug mode was enabled, starting
+/// with the latest, along with the scene that handled them and how long it took. The latest
+/// entry of the accessibility log is shown above it.
+pub fn debug_log<'a>(
+    entries: &'a VecDeque<LogEntry>,
+    accessibility_log: &'a VecDeque<String>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let announced = Text::new(format!(
+        "Last announced description: {}",
+        accessibility_log
+            .front()
+            .map_or("none", |description| description.as_str())
+    ))
+    .style(theme::text::gray);
+
+    let content: Element<'a, Message, Theme, Renderer> = if entries.is_empty() {
+        Text::new("No messages were handled yet.").into()
+    } else {
+        Scrollable::new(
+            Column::with_children(entries.iter().map(|entry| {
+                Row::with_children(vec![
+                    Text::new(entry.get_time().format("%H:%M:%S%.3f").to_string())
+                        .style(theme::text::gray)
+                        .into(),
+                    Text::new(entry.get_scene().clone())
+                        .width(Length::Fixed(150.0))
+                        .into(),
+                    Text::new(entry.get_message().clone()).into(),
+                    Space::with_width(Length::Fill).into(),
+                    Text::new(
+                        entry
+                            .get_elapsed()
+                            .map(|elapsed| format!("{:.3} ms", elapsed.as_secs_f64() * 1000.0))
+                            .unwrap_or_default(),
+                    )
+                    .style(theme::text::gray)
+                    .into(),
+                ])
+                .spacing(15.0)
+                .align_items(Alignment::Center)
+                .into()
+            }))
+            .spacing(2.0),
+        )
+        .height(Length::Fill)
+        .into()
+    };
+
+    Centered::new(
+        Closeable::<Message, Theme, Renderer>::new(Card::new(
+            Text::new(format!("Message log ({} entries)", entries.len())),
+            Column::with_children(vec![announced.into(), content]).spacing(10.0),
+        ))
+        .style(theme::closeable::Closeable::Transparent)
+        .on_close(Message::ToggleDebugMode, 25.0)
+        .close_padding(7.0),
+    )
+    .height(0.8)
+    .into()
+}
@@ -1,54 +1,123 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use directories::ProjectDirs;
+use iced::futures::channel::mpsc::Sender;
+use iced::futures::{SinkExt, StreamExt};
 use iced::{
     advanced::widget::Text,
     alignment::Horizontal,
     widget::{
         scrollable::{Direction, Properties},
-        Button, Column, Container, Row, Scrollable, Space, TextEditor, TextInput,
+        tooltip::Position,
+        Button, Checkbox, Column, Container, Image, PickList, Row, Scrollable, Slider, Space,
+        TextEditor, TextInput, Tooltip,
     },
-    Alignment, Element, Length, Renderer,
+    Alignment, Element, Length, Point, Renderer,
 };
 use image::{load_from_memory_with_format, ImageFormat};
+use chrono::{DateTime, Local};
 use json::{object::Object, JsonValue};
-use mongodb::{bson::Uuid, Database};
+use mongodb::{
+    bson::{Bson, Document, Uuid, UuidRepresentation},
+    Database,
+};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use rfd::AsyncFileDialog;
-use svg::node::element::SVG;
+use svg::node::element::{Group, SVG};
 
 use crate::{
     canvas::{
-        canvas::Canvas,
+        canvas::{Canvas, CollabCursor, Snapshot},
+        effect::{LayerEffect, LayerEffectKind},
+        guide::GuideAxis,
         layer::CanvasMessage,
         tool::{self, Pending, Tool},
         tools::{
+            arc::{ArcPending, PiePending},
+            bezier::BezierPending,
             brush::BrushPending,
-            brushes::{airbrush::Airbrush, eraser::Eraser, pen::Pen, pencil::Pencil},
+            brushes::{
+                airbrush::Airbrush, eraser::Eraser, pen::Pen, pencil::Pencil, texture::TextureBrush,
+            },
             circle::CirclePending,
             ellipse::EllipsePending,
+            gradient::GradientPending,
             line::LinePending,
             polygon::PolygonPending,
             rect::RectPending,
+            smudge::SmudgePending,
+            star::StarPending,
             triangle::TrianglePending,
         },
     },
     database, debug_message,
     scene::{Globals, Message},
     scenes::{
-        data::drawing::{ModalTypes, PostData, UpdatePostData},
+        data::drawing::{
+            AdjustmentsData, AutoSaveInterval, DrawingPermission, ModalTypes, PostData, ResizeData,
+            ShareData, TimelapseData, UpdateAdjustmentsData, UpdatePostData, UpdateResizeData,
+            UpdateShareData, UpdateTimelapseData,
+        },
         drawing::DrawingMessage,
         scenes::Scenes,
     },
     utils::{
         self,
         cache::Cache,
+        encoder::ImageFilter,
         errors::Error,
         icons::{Icon, ToolIcon, ICON},
+        rate_limiter::RateLimiter,
+        serde::{Deserialize, Serialize},
         theme::{self, Theme},
     },
-    widgets::{Card, Close, Closeable, ComboBox, Grid},
+    widgets::{splitter, Card, Close, Closeable, ComboBox, DragList, Grid, Splitter},
 };
 
+/// The name of the file holding the data of a locally stored drawing.
+const DATA_FILE: &str = "data.json.gz";
+
+/// The name of the uncompressed data file written by older versions of the application.
+const LEGACY_DATA_FILE: &str = "data.json";
+
+/// Reads the data of the locally stored drawing in the given directory. Falls back to the
+/// uncompressed file of older versions if the drawing has not been saved since.
+pub async fn read_data_offline(dir_path: &Path) -> Result<String, Error> {
+    let contents = match tokio::fs::read(dir_path.join(DATA_FILE)).await {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            tokio::fs::read(dir_path.join(LEGACY_DATA_FILE))
+                .await
+                .map_err(|err| debug_message!("{}", err).into())?
+        }
+        Err(err) => return Err(debug_message!("{}", err).into()),
+    };
+
+    String::from_utf8(utils::compression::decompress(&contents)?)
+        .map_err(|err| debug_message!("{}", err).into())
+}
+
+/// Compresses and writes the data of the locally stored drawing in the given directory,
+/// removing the uncompressed file of older versions if there is one.
+pub async fn write_data_offline(dir_path: &Path, data: &[u8]) -> Result<(), Error> {
+    tokio::fs::write(
+        dir_path.join(DATA_FILE),
+        utils::compression::compress(data)?,
+    )
+    .await
+    .map_err(|err| debug_message!("{}", err).into())?;
+
+    match tokio::fs::remove_file(dir_path.join(LEGACY_DATA_FILE)).await {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(debug_message!("{}", err).into()),
+        _ => Ok(()),
+    }
+}
+
 pub async fn save_preview_offline(id: Uuid, document: SVG, cache: Cache) -> Result<(), Error> {
     let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
         .ok_or(debug_message!("Unable to find project directory").into())?;
@@ -68,6 +137,22 @@ pub async fn save_preview_offline(id: Uuid, document: SVG, cache: Cache) -> Resu
     cache.insert(id, Arc::new(pixel_image)).await
 }
 
+/// Renders the preview of a locally stored drawing again from its stored data, replacing the
+/// previous one.
+pub async fn regenerate_preview_offline(id: Uuid, cache: Cache) -> Result<(), Error> {
+    let (layers, tools, _, size, _) = get_drawing_offline(id).await?;
+
+    let mut svg = crate::canvas::svg::SVG::new(&layers.iter().map(|(id, _)| *id).collect());
+    if let Some((width, height)) = size {
+        svg.set_size(width, height);
+    }
+    for (tool, layer) in &tools {
+        svg.add_tool(layer, Serialize::<Group>::serialize(tool.as_ref()));
+    }
+
+    save_preview_offline(id, svg.as_document(), cache).await
+}
+
 pub async fn save_preview_online(
     id: Uuid,
     user_id: Uuid,
@@ -92,34 +177,15 @@ pub async fn save_offline(
     mut tools: Vec<JsonValue>,
     new_tools: Vec<JsonValue>,
     layers: Vec<(Uuid, String)>,
+    size: (u32, u32),
+    guides: Vec<GuideAxis>,
 ) -> Result<(), Error> {
     let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
         .ok_or(debug_message!("Unable to find project directory").into())?;
     let dir_path = proj_dirs.data_local_dir();
-    let file_path = dir_path.join(id.to_string()).join("data.json");
-    let drawings_path = dir_path.join("drawings.json");
-
-    let drawings = tokio::fs::read_to_string(drawings_path.clone())
-        .await
-        .map_err(|err| debug_message!("{}", err).into())?;
+    let drawing_path = dir_path.join(id.to_string());
 
     match tokio::task::spawn_blocking(move || {
-        let mut drawings =
-            json::parse(&*drawings).map_err(|err| debug_message!("{}", err).into())?;
-
-        if let JsonValue::Array(drawings) = &mut drawings {
-            for drawing in drawings {
-                if let JsonValue::Object(drawing) = drawing {
-                    if let Some(JsonValue::String(id)) = drawing.get("id") {
-                        if id.clone() == id.to_string() {
-                            drawing.insert("name", JsonValue::String(name));
-                            break;
-                        };
-                    }
-                }
-            }
-        }
-
         for _ in delete_bounds.0..delete_bounds.1 {
             tools.pop();
         }
@@ -143,53 +209,166 @@ pub async fn save_offline(
             ),
         );
         data.insert("tools", JsonValue::Array(tools));
+        data.insert("width", JsonValue::Number(size.0.into()));
+        data.insert("height", JsonValue::Number(size.1.into()));
+        data.insert(
+            "guides",
+            JsonValue::Array(
+                guides
+                    .iter()
+                    .map(|guide| JsonValue::Object(guide.serialize()))
+                    .collect(),
+            ),
+        );
 
-        Ok((drawings, data))
+        Ok(data)
     })
     .await
     {
         Ok(Err(err)) => Err(err),
-        Ok(Ok((drawings, data))) => {
-            tokio::fs::write(drawings_path, json::stringify(drawings))
-                .await
-                .map_err(|err| debug_message!("{}", err).into())?;
+        Ok(Ok(data)) => {
+            database::local::update_drawing(id, name).await?;
 
-            tokio::fs::write(file_path, json::stringify(JsonValue::Object(data)))
-                .await
-                .map_err(|err| debug_message!("{}", err).into())
+            write_data_offline(
+                &drawing_path,
+                json::stringify(JsonValue::Object(data)).as_bytes(),
+            )
+            .await
         }
         Err(err) => Err(debug_message!("{}", err).into()),
     }
 }
 
-pub async fn delete_drawing_offline(id: Uuid) -> Result<(), Error> {
+/// Stores the given state of the drawing as a snapshot in the `snapshots` subdirectory of the
+/// drawing.
+pub async fn create_snapshot_offline(
+    canvas_id: Uuid,
+    snapshot: &Snapshot,
+    layers: Vec<(Uuid, String)>,
+    tools: Vec<JsonValue>,
+    size: (u32, u32),
+) -> Result<(), Error> {
     let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
         .ok_or(debug_message!("Unable to find project directory.").into())?;
+    let dir_path = proj_dirs
+        .data_local_dir()
+        .join(canvas_id.to_string())
+        .join("snapshots");
 
-    let drawings_path = proj_dirs.data_local_dir().join("drawings.json");
-    let drawings = tokio::fs::read_to_string(drawings_path.clone())
+    tokio::fs::create_dir_all(dir_path.clone())
         .await
         .map_err(|err| debug_message!("{}", err).into())?;
 
-    let mut drawings = json::parse(&drawings).map_err(|err| debug_message!("{}", err).into())?;
-    if let JsonValue::Array(ref mut drawings) = drawings {
-        drawings.retain(|drawing| match drawing {
-            JsonValue::Object(drawing) => {
-                if let Some(JsonValue::String(drawing_id)) = drawing.get("id") {
-                    let drawing_id = Uuid::parse_str(drawing_id);
+    let mut data = Object::new();
+    data.insert("name", JsonValue::String(snapshot.get_name().clone()));
+    data.insert(
+        "created",
+        JsonValue::String(snapshot.get_created().to_rfc3339()),
+    );
+    data.insert(
+        "layers",
+        JsonValue::Array(
+            layers
+                .iter()
+                .map(|(id, name)| {
+                    let mut object = Object::new();
+                    object.insert("id", JsonValue::String(id.to_string()));
+                    object.insert("name", JsonValue::String(name.clone()));
 
-                    drawing_id.is_ok_and(|drawing_id| id != drawing_id)
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        });
-    }
+                    JsonValue::Object(object)
+                })
+                .collect(),
+        ),
+    );
+    data.insert("tools", JsonValue::Array(tools));
+    data.insert("width", JsonValue::Number(size.0.into()));
+    data.insert("height", JsonValue::Number(size.1.into()));
+
+    tokio::fs::write(
+        dir_path.join(format!("{}.json", snapshot.get_id())),
+        json::stringify(JsonValue::Object(data)),
+    )
+    .await
+    .map_err(|err| debug_message!("{}", err).into())
+}
+
+/// Returns the snapshots stored for the given drawing.
+pub async fn get_snapshots_offline(canvas_id: Uuid) -> Result<Vec<Snapshot>, Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+    let dir_path = proj_dirs
+        .data_local_dir()
+        .join(canvas_id.to_string())
+        .join("snapshots");
 
-    tokio::fs::write(drawings_path, json::stringify(drawings))
+    let mut entries = match tokio::fs::read_dir(dir_path).await {
+        Ok(entries) => entries,
+        Err(_) => {
+            return Ok(vec![]);
+        }
+    };
+
+    let mut snapshots = vec![];
+    while let Some(entry) = entries
+        .next_entry()
         .await
-        .map_err(|err| debug_message!("{}", err).into())?;
+        .map_err(|err| debug_message!("{}", err).into())?
+    {
+        let path = entry.path();
+        let Some(id) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| Uuid::parse_str(stem).ok())
+        else {
+            continue;
+        };
+
+        let data = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|err| debug_message!("{}", err).into())?;
+        let data = json::parse(&*data).map_err(|err| debug_message!("{}", err).into())?;
+
+        let (Some(name), Some(created)) = (
+            data["name"].as_str(),
+            data["created"]
+                .as_str()
+                .and_then(|created| DateTime::parse_from_rfc3339(created).ok()),
+        ) else {
+            continue;
+        };
+
+        snapshots.push(Snapshot::new(id, name, created.with_timezone(&Local)));
+    }
+
+    Ok(snapshots)
+}
+
+/// Replaces the data of the drawing with the one stored in the given snapshot.
+pub async fn restore_snapshot_offline(canvas_id: Uuid, snapshot_id: Uuid) -> Result<(), Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+    let dir_path = proj_dirs.data_local_dir().join(canvas_id.to_string());
+
+    let data = tokio::fs::read_to_string(
+        dir_path
+            .join("snapshots")
+            .join(format!("{}.json", snapshot_id)),
+    )
+    .await
+    .map_err(|err| debug_message!("{}", err).into())?;
+    let mut data = json::parse(&*data).map_err(|err| debug_message!("{}", err).into())?;
+
+    data.remove("name");
+    data.remove("created");
+
+    write_data_offline(&dir_path, json::stringify(data).as_bytes()).await
+}
+
+pub async fn delete_drawing_offline(id: Uuid) -> Result<(), Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+
+    database::local::delete_drawing(id).await?;
 
     let drawing_path = proj_dirs.data_local_dir().join(id.to_string());
     tokio::fs::remove_dir_all(drawing_path)
@@ -217,17 +396,15 @@ pub async fn get_drawing_offline(
         Vec<(Uuid, String)>,
         Vec<(Arc<dyn Tool>, Uuid)>,
         Vec<JsonValue>,
+        Option<(u32, u32)>,
+        Vec<GuideAxis>,
     ),
     Error,
 > {
     let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
         .ok_or(debug_message!("Unable to find project directory.").into())?;
-    let file_path = proj_dirs
-        .data_local_dir()
-        .join(String::from("./") + &*id.to_string() + "/data.json");
-    let data = tokio::fs::read_to_string(file_path)
-        .await
-        .map_err(|err| debug_message!("{}", err).into())?;
+    let dir_path = proj_dirs.data_local_dir().join(id.to_string());
+    let data = read_data_offline(&dir_path).await?;
 
     let data = json::parse(&*data).map_err(|err| debug_message!("{}", err).into())?;
 
@@ -235,6 +412,7 @@ pub async fn get_drawing_offline(
         let mut layers = vec![];
         let mut tools = vec![];
         let mut json_tools = vec![];
+        let mut guides = vec![];
 
         if let Some(JsonValue::Array(layer_array)) = data.get("layers") {
             layers = layer_array
@@ -263,9 +441,27 @@ pub async fn get_drawing_offline(
             }
         }
 
-        Ok((layers, tools, json_tools))
+        let size = data
+            .get("width")
+            .and_then(JsonValue::as_u32)
+            .zip(data.get("height").and_then(JsonValue::as_u32));
+
+        if let Some(JsonValue::Array(guide_list)) = data.get("guides") {
+            guides = guide_list
+                .iter()
+                .filter_map(|json| {
+                    if let JsonValue::Object(object) = json {
+                        Some(GuideAxis::deserialize(object))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+        }
+
+        Ok((layers, tools, json_tools, size, guides))
     } else {
-        Ok((vec![], vec![], vec![]))
+        Ok((vec![], vec![], vec![], None, vec![]))
     }
 }
 
@@ -273,25 +469,7 @@ pub async fn create_drawing_offline(id: Uuid, json_data: Object) -> Result<(), E
     let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
         .ok_or(debug_message!("Unable to find project directory.").into())?;
 
-    let drawings_path = proj_dirs.data_local_dir().join("drawings.json");
-    let drawings = json::parse(
-        &*tokio::fs::read_to_string(drawings_path.clone())
-            .await
-            .map_err(|err| debug_message!("{}", err).into())?,
-    )
-    .map_err(|err| debug_message!("{}", err).into())?;
-
-    if let JsonValue::Array(mut drawings) = drawings {
-        let mut drawing = Object::new();
-        drawing.insert("id", JsonValue::String(id.to_string()));
-        drawing.insert("name", JsonValue::String(String::from("New drawing")));
-
-        drawings.push(JsonValue::Object(drawing));
-
-        tokio::fs::write(drawings_path, json::stringify(JsonValue::Array(drawings)))
-            .await
-            .map_err(|err| debug_message!("{}", err).into())?;
-    }
+    database::local::insert_drawing(id, String::from("New drawing")).await?;
 
     let dir_path = proj_dirs
         .data_local_dir()
@@ -302,13 +480,11 @@ pub async fn create_drawing_offline(id: Uuid, json_data: Object) -> Result<(), E
 
     let drawing_path = dir_path.join("data.webp");
 
-    let file_path = dir_path.join("data.json");
-    tokio::fs::write(
-        file_path,
+    write_data_offline(
+        &dir_path,
         json::stringify(JsonValue::Object(json_data)).as_bytes(),
     )
-    .await
-    .map_err(|err| debug_message!("{}", err).into())?;
+    .await?;
 
     let svg = crate::canvas::svg::SVG::new(&vec![Uuid::new()]).as_document();
     let webp = utils::encoder::encode_svg(svg, "webp").await?;
@@ -323,8 +499,12 @@ pub async fn create_post(
     data: &SVG,
     description: String,
     tags: Vec<String>,
+    challenge: Option<Uuid>,
     db: &Database,
+    rate_limiter: &Mutex<RateLimiter>,
 ) -> Result<(), Error> {
+    database::admin::ensure_not_banned(db, user_id).await?;
+
     let img = utils::encoder::encode_svg(data.clone(), "webp").await?;
     let post_id = Uuid::new();
 
@@ -335,17 +515,31 @@ pub async fn create_post(
         }
     }
 
-    database::drawing::create_post(&db, post_id, user_id, description, tags).await
+    database::drawing::create_post(
+        &db,
+        rate_limiter,
+        post_id,
+        user_id,
+        description,
+        tags,
+        challenge,
+    )
+    .await
 }
 
-pub async fn download_drawing(document: &SVG) -> Result<(), Error> {
+/// Saves the drawing with the format and location the user provides. When filters are given,
+/// only raster formats can be chosen, and the filters are applied before encoding.
+pub async fn download_drawing(document: &SVG, filters: Vec<ImageFilter>) -> Result<(), Error> {
+    let formats: &[&str] = if filters.is_empty() {
+        &["png", "jpg", "jpeg", "webp", "svg", "tiff", "bmp", "pdf"]
+    } else {
+        &["png", "jpg", "jpeg", "webp", "tiff", "bmp"]
+    };
+
     let file = AsyncFileDialog::new()
         .set_title("Save As...")
         .set_directory("~")
-        .add_filter(
-            "image",
-            &["png", "jpg", "jpeg", "webp", "svg", "tiff", "bmp"],
-        )
+        .add_filter("image", formats)
         .save_file()
         .await;
 
@@ -356,7 +550,11 @@ pub async fn download_drawing(document: &SVG) -> Result<(), Error> {
                 .split(".")
                 .last()
                 .ok_or(debug_message!("File needs to have a readable format.").into())?;
-            let img = utils::encoder::encode_svg(document.clone(), &*format).await?;
+            let img = if format == "pdf" {
+                utils::encoder::encode_pdf(vec![document.clone()]).await?
+            } else {
+                utils::encoder::encode_svg_with_filters(document.clone(), &*format, filters).await?
+            };
 
             handle
                 .write(img.as_slice())
@@ -367,6 +565,54 @@ pub async fn download_drawing(document: &SVG) -> Result<(), Error> {
     }
 }
 
+/// Lets the user choose the file the timelapse is exported to. Returns None if they cancel.
+pub async fn choose_timelapse_file() -> Option<PathBuf> {
+    AsyncFileDialog::new()
+        .set_title("Export timelapse...")
+        .set_directory("~")
+        .add_filter("gif", &["gif"])
+        .save_file()
+        .await
+        .map(|handle| handle.path().to_path_buf())
+}
+
+/// Exports the given states of the drawing as the frames of an animated GIF to the given file.
+pub async fn export_timelapse(
+    frames: Vec<SVG>,
+    frame_rate: u32,
+    path: PathBuf,
+) -> Result<(), Error> {
+    if frames.is_empty() {
+        return Err(debug_message!("The drawing is empty.").into());
+    }
+
+    let file = std::fs::File::create(path).map_err(|err| debug_message!("{}", err).into())?;
+
+    utils::encoder::encode_gif(frames, frame_rate, io::BufWriter::new(file)).await
+}
+
+/// Exports the drawing to a PDF file, with every layer of the canvas on its own PDF layer.
+pub async fn export_layered_pdf(layers: Vec<SVG>) -> Result<(), Error> {
+    let file = AsyncFileDialog::new()
+        .set_title("Export layers as PDF...")
+        .set_directory("~")
+        .add_filter("pdf", &["pdf"])
+        .save_file()
+        .await;
+
+    match file {
+        Some(handle) => {
+            let pdf = utils::encoder::encode_pdf(layers).await?;
+
+            handle
+                .write(pdf.as_slice())
+                .await
+                .map_err(|err| err.to_string().into())
+        }
+        None => Err(debug_message!("Error getting file.").into()),
+    }
+}
+
 pub fn tools_section<'a>(current_tool_id: String) -> Element<'a, Message, Theme, Renderer> {
     let tool_button = |name, pending: Box<dyn Pending>| -> Element<'a, Message, Theme, Renderer> {
         let style = if current_tool_id == pending.id() {
@@ -395,11 +641,15 @@ pub fn tools_section<'a>(current_tool_id: String) -> Element<'a, Message, Theme,
             ToolIcon::Polygon.to_string(),
             Box::new(PolygonPending::None),
         ),
+        tool_button(ToolIcon::Star.to_string(), Box::new(StarPending::None)),
+        tool_button(ToolIcon::Bezier.to_string(), Box::new(BezierPending::None)),
         tool_button(ToolIcon::Circle.to_string(), Box::new(CirclePending::None)),
         tool_button(
             ToolIcon::Ellipse.to_string(),
             Box::new(EllipsePending::None),
         ),
+        tool_button(ToolIcon::Arc.to_string(), Box::new(ArcPending::None)),
+        tool_button(ToolIcon::Pie.to_string(), Box::new(PiePending::None)),
     ])
     .spacing(25.0)
     .padding(18.0)
@@ -418,6 +668,45 @@ pub fn tools_section<'a>(current_tool_id: String) -> Element<'a, Message, Theme,
             ToolIcon::Airbrush.to_string(),
             Box::new(BrushPending::<Airbrush>::None),
         ),
+        tool_button(
+            ToolIcon::Texture.to_string(),
+            Box::new(BrushPending::<TextureBrush>::None),
+        ),
+        tool_button(ToolIcon::Smudge.to_string(), Box::new(SmudgePending::None)),
+        tool_button(
+            ToolIcon::Gradient.to_string(),
+            Box::new(GradientPending::None),
+        ),
+    ])
+    .spacing(25.0)
+    .padding(18.0)
+    .into();
+
+    let images_section: Element<Message, Theme, Renderer> = Grid::new(vec![
+        Button::<Message, Theme, Renderer>::new(
+            Text::new(ToolIcon::Stamp.to_string())
+                .font(ICON)
+                .line_height(1.0)
+                .size(25.0),
+        )
+        .style(if current_tool_id == "Stamp" {
+            iced::widget::button::primary
+        } else {
+            iced::widget::button::secondary
+        })
+        .on_press(CanvasMessage::ImportStamp.into())
+        .padding(10.0)
+        .into(),
+        Button::<Message, Theme, Renderer>::new(
+            Text::new(ToolIcon::Image.to_string())
+                .font(ICON)
+                .line_height(1.0)
+                .size(25.0),
+        )
+        .style(iced::widget::button::secondary)
+        .on_press(CanvasMessage::ImportImage.into())
+        .padding(10.0)
+        .into(),
     ])
     .spacing(25.0)
     .padding(18.0)
@@ -443,6 +732,11 @@ pub fn tools_section<'a>(current_tool_id: String) -> Element<'a, Message, Theme,
                 .size(20.0)
                 .into(),
             brushes_section,
+            Text::new("Images")
+                .horizontal_alignment(Horizontal::Center)
+                .size(20.0)
+                .into(),
+            images_section,
             Text::new("Eraser")
                 .horizontal_alignment(Horizontal::Center)
                 .size(20.0)
@@ -559,16 +853,13 @@ pub fn layers_section<'a>(canvas: &'a Canvas) -> Element<'a, Message, Theme, Ren
 
     Container::new(Scrollable::new(Column::with_children(vec![
         title,
-        Column::with_children(
-            canvas
-                .get_layer_order()
-                .iter()
-                .map(|id| layer(id))
-                .collect::<Vec<Element<Message, Theme, Renderer>>>(),
-        )
+        DragList::new(canvas.get_layer_order().iter(), layer, |from, to| {
+            CanvasMessage::MoveLayer(from, to).into()
+        })
         .padding(8.0)
         .spacing(5.0)
         .into(),
+        effects_panel(canvas),
     ])))
     .padding(2.0)
     .width(Length::Fill)
@@ -577,6 +868,84 @@ pub fn layers_section<'a>(canvas: &'a Canvas) -> Element<'a, Message, Theme, Ren
     .into()
 }
 
+/// The expandable panel listing the effects of the active layer.
+fn effects_panel<'a>(canvas: &'a Canvas) -> Element<'a, Message, Theme, Renderer> {
+    let expanded = canvas.is_effects_expanded();
+    let title = Button::new(
+        Row::with_children(vec![
+            Text::new(if expanded { Icon::Down } else { Icon::Right }.to_string())
+                .font(ICON)
+                .into(),
+            Text::new("Effects").size(16.0).into(),
+        ])
+        .spacing(5.0)
+        .align_items(Alignment::Center),
+    )
+    .style(iced::widget::button::text)
+    .on_press(CanvasMessage::ToggleEffectsPanel.into())
+    .into();
+
+    if !expanded {
+        return Column::with_children(vec![title]).padding(8.0).into();
+    }
+
+    let layer_id = *canvas.get_current_layer();
+    let effects = canvas
+        .get_layers()
+        .get(&layer_id)
+        .map(|layer| layer.get_effects())
+        .unwrap_or_default();
+
+    let effect_list = effects
+        .iter()
+        .enumerate()
+        .map(|(index, effect)| {
+            Row::with_children(vec![
+                Text::new(format!("{} ({}px)", effect.kind, effect.radius))
+                    .width(Length::Fill)
+                    .into(),
+                Button::new(
+                    Text::new(Icon::X.to_string())
+                        .font(ICON)
+                        .style(theme::text::danger),
+                )
+                .style(iced::widget::button::text)
+                .on_press(CanvasMessage::RemoveLayerEffect(layer_id, index).into())
+                .padding(0.0)
+                .into(),
+            ])
+            .spacing(5.0)
+            .align_items(Alignment::Center)
+            .into()
+        })
+        .collect::<Vec<Element<Message, Theme, Renderer>>>();
+
+    let add_buttons = Row::with_children(
+        LayerEffectKind::ALL
+            .iter()
+            .map(|kind| {
+                Button::new(Text::new(format!("+ {}", kind)))
+                    .style(iced::widget::button::secondary)
+                    .on_press(
+                        CanvasMessage::AddLayerEffect(layer_id, LayerEffect::new(*kind)).into(),
+                    )
+                    .into()
+            })
+            .collect::<Vec<Element<Message, Theme, Renderer>>>(),
+    )
+    .spacing(5.0)
+    .into();
+
+    Column::with_children(vec![
+        title,
+        Column::with_children(effect_list).spacing(5.0).into(),
+        add_buttons,
+    ])
+    .padding(8.0)
+    .spacing(5.0)
+    .into()
+}
+
 pub fn menu_section<'a>(globals: &Globals) -> Element<'a, Message, Theme, Renderer> {
     Container::new(
         Column::with_children(vec![
@@ -591,6 +960,19 @@ pub fn menu_section<'a>(globals: &Globals) -> Element<'a, Message, Theme, Render
             .width(Length::Fill)
             .padding(5.0)
             .into(),
+            Row::with_children(vec![
+                Text::new("Auto-save:").into(),
+                PickList::new(
+                    AutoSaveInterval::OPTIONS,
+                    Some(AutoSaveInterval(globals.get_auto_save_interval())),
+                    |interval| DrawingMessage::SetAutoSave(interval).into(),
+                )
+                .width(Length::Fill)
+                .into(),
+            ])
+            .spacing(5.0)
+            .align_items(Alignment::Center)
+            .into(),
             Space::with_height(Length::Fill).into(),
             if globals.get_db().is_some() && globals.get_user().is_some() {
                 Button::new(
@@ -624,48 +1006,224 @@ pub fn menu_section<'a>(globals: &Globals) -> Element<'a, Message, Theme, Render
             .into(),
             Space::with_height(Length::Fill).into(),
             Button::new(
-                Text::new("Delete")
+                Text::new("Export with adjustments")
                     .horizontal_alignment(Horizontal::Center)
                     .width(Length::Fill)
                     .size(20.0),
             )
-            .style(iced::widget::button::danger)
-            .on_press(DrawingMessage::DeleteDrawing.into())
+            .on_press(DrawingMessage::ToggleModal(ModalTypes::AdjustmentsDialog).into())
             .padding(5.0)
             .width(Length::Fill)
             .into(),
             Space::with_height(Length::Fill).into(),
-        ])
-        .spacing(10.0)
-        .align_items(Alignment::Center),
-    )
-    .padding(10.0)
-    .style(iced::widget::container::bordered_box)
-    .center_x(Length::Fill)
-    .center_y(Length::FillPortion(1))
-    .into()
-}
-
-pub fn underlay<'a>(
-    canvas: &'a Canvas,
-    tools_section: Element<'a, Message, Theme, Renderer>,
-    style_section: Element<'a, Message, Theme, Renderer>,
-    layers_section: Element<'a, Message, Theme, Renderer>,
-    menu_section: Element<'a, Message, Theme, Renderer>,
-) -> Element<'a, Message, Theme, Renderer> {
-    Column::with_children(vec![
-        Row::with_children(vec![
-            Button::new(Text::new(Icon::Leave.to_string()).font(ICON).size(30.0))
-                .padding(0.0)
-                .style(iced::widget::button::text)
-                .on_press(Message::ChangeScene(Scenes::Main(None)))
-                .into(),
-            if let Some(new_name) = canvas.get_new_name() {
-                TextInput::new("Add name", new_name)
-                    .on_input(|value| CanvasMessage::SetNewName(value).into())
-                    .on_submit(CanvasMessage::ToggleEditName.into())
-                    .size(30.0)
-                    .into()
+            Button::new(
+                Text::new("Export layers as PDF")
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill)
+                    .size(20.0),
+            )
+            .on_press(DrawingMessage::ExportLayeredPdf.into())
+            .padding(5.0)
+            .width(Length::Fill)
+            .into(),
+            Space::with_height(Length::Fill).into(),
+            Button::new(
+                Text::new("Export timelapse")
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill)
+                    .size(20.0),
+            )
+            .on_press(DrawingMessage::ToggleModal(ModalTypes::TimelapseDialog).into())
+            .padding(5.0)
+            .width(Length::Fill)
+            .into(),
+            Space::with_height(Length::Fill).into(),
+            Button::new(
+                Text::new("Resize canvas")
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill)
+                    .size(20.0),
+            )
+            .on_press(DrawingMessage::ToggleModal(ModalTypes::ResizeDialog).into())
+            .padding(5.0)
+            .width(Length::Fill)
+            .into(),
+            Space::with_height(Length::Fill).into(),
+            Button::new(
+                Text::new("History")
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill)
+                    .size(20.0),
+            )
+            .on_press(DrawingMessage::ToggleModal(ModalTypes::HistoryPanel).into())
+            .padding(5.0)
+            .width(Length::Fill)
+            .into(),
+            Space::with_height(Length::Fill).into(),
+            Button::new(
+                Text::new("Snapshots")
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill)
+                    .size(20.0),
+            )
+            .on_press(DrawingMessage::ToggleModal(ModalTypes::SnapshotPanel).into())
+            .padding(5.0)
+            .width(Length::Fill)
+            .into(),
+            Space::with_height(Length::Fill).into(),
+            Button::new(
+                Text::new("Compare to saved")
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill)
+                    .size(20.0),
+            )
+            .on_press(CanvasMessage::ToggleSplitView.into())
+            .padding(5.0)
+            .width(Length::Fill)
+            .into(),
+            Space::with_height(Length::Fill).into(),
+            Button::new(
+                Text::new("Delete")
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Fill)
+                    .size(20.0),
+            )
+            .style(iced::widget::button::danger)
+            .on_press(DrawingMessage::DeleteDrawing.into())
+            .padding(5.0)
+            .width(Length::Fill)
+            .into(),
+            Space::with_height(Length::Fill).into(),
+        ])
+        .spacing(10.0)
+        .align_items(Alignment::Center),
+    )
+    .padding(10.0)
+    .style(iced::widget::container::bordered_box)
+    .center_x(Length::Fill)
+    .center_y(Length::FillPortion(1))
+    .into()
+}
+
+pub fn header_section<'a>(
+    globals: &Globals,
+    is_offline: bool,
+    collaborators: Option<usize>,
+    is_locked: bool,
+    permission: DrawingPermission,
+    auto_saved: Option<Instant>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let auto_saved: Element<'a, Message, Theme, Renderer> =
+        match (globals.get_auto_save_interval(), auto_saved) {
+            (Some(_), Some(auto_saved)) => Text::new(format!(
+                "Auto-saved {} seconds ago",
+                auto_saved.elapsed().as_secs()
+            ))
+            .size(14.0)
+            .style(theme::text::gray)
+            .into(),
+            _ => Space::with_width(Length::Shrink).into(),
+        };
+
+    if is_offline || globals.get_db().is_none() || globals.get_user().is_none() {
+        return auto_saved;
+    }
+
+    let lock_icon = Text::new(if is_locked {
+        Icon::Lock.to_string()
+    } else {
+        Icon::Unlock.to_string()
+    })
+    .font(ICON)
+    .size(30.0);
+
+    let lock: Element<'a, Message, Theme, Renderer> = if permission == DrawingPermission::Admin {
+        Tooltip::new(
+            Button::new(lock_icon)
+                .padding(0.0)
+                .style(iced::widget::button::text)
+                .on_press(DrawingMessage::ToggleLock.into()),
+            Text::new(if is_locked {
+                "Unlock drawing"
+            } else {
+                "Lock drawing"
+            }),
+            Position::FollowCursor,
+        )
+        .into()
+    } else if is_locked || permission < DrawingPermission::Edit {
+        Tooltip::new(
+            lock_icon,
+            Text::new("The drawing is read-only"),
+            Position::FollowCursor,
+        )
+        .into()
+    } else {
+        Space::with_width(Length::Shrink).into()
+    };
+
+    let share: Element<'a, Message, Theme, Renderer> = if permission == DrawingPermission::Admin {
+        Button::new(Text::new("Share").size(20.0))
+            .on_press(DrawingMessage::ToggleModal(ModalTypes::SharePanel).into())
+            .padding(5.0)
+            .into()
+    } else {
+        Space::with_width(Length::Shrink).into()
+    };
+
+    let mut header = Row::with_children(vec![
+        auto_saved,
+        lock,
+        share,
+        Button::new(
+            Text::new(if collaborators.is_some() {
+                "Stop collaborating"
+            } else {
+                "Collaborate"
+            })
+            .size(20.0),
+        )
+        .on_press(DrawingMessage::EnableCollaboration.into())
+        .padding(5.0)
+        .into(),
+    ])
+    .spacing(10.0)
+    .align_items(Alignment::Center);
+
+    if let Some(collaborators) = collaborators {
+        header = header.push(
+            Container::new(
+                Text::new(format!("{} collaborators", collaborators)).style(theme::text::dark),
+            )
+            .style(theme::container::badge)
+            .padding([2.0, 8.0]),
+        );
+    }
+
+    header.into()
+}
+
+pub fn underlay<'a>(
+    canvas: &'a Canvas,
+    header_section: Element<'a, Message, Theme, Renderer>,
+    tools_section: Element<'a, Message, Theme, Renderer>,
+    style_section: Element<'a, Message, Theme, Renderer>,
+    layers_section: Element<'a, Message, Theme, Renderer>,
+    menu_section: Element<'a, Message, Theme, Renderer>,
+) -> Element<'a, Message, Theme, Renderer> {
+    Column::with_children(vec![
+        Row::with_children(vec![
+            Button::new(Text::new(Icon::Leave.to_string()).font(ICON).size(30.0))
+                .padding(0.0)
+                .style(iced::widget::button::text)
+                .on_press(Message::ChangeScene(Scenes::Main(None)))
+                .into(),
+            if let Some(new_name) = canvas.get_new_name() {
+                TextInput::new("Add name", new_name)
+                    .on_input(|value| CanvasMessage::SetNewName(value).into())
+                    .on_submit(CanvasMessage::ToggleEditName.into())
+                    .size(30.0)
+                    .into()
             } else {
                 Text::new(canvas.get_name()).size(30.0).into()
             },
@@ -678,36 +1236,37 @@ pub fn underlay<'a>(
                     .on_press(CanvasMessage::ToggleEditName.into())
                     .into()
             },
+            Space::with_width(Length::Fill).into(),
+            header_section,
         ])
         .spacing(10.0)
         .padding(10.0)
+        .align_items(Alignment::Center)
         .into(),
-        Row::with_children(vec![
+        Splitter::new(
             Column::with_children(vec![tools_section.into(), style_section.into()])
-                .width(Length::Fixed(250.0))
-                .height(Length::Fill)
-                .into(),
-            Container::new(Scrollable::with_direction(
-                canvas,
-                Direction::Both {
-                    vertical: Properties::default(),
-                    horizontal: Properties::default(),
-                },
-            ))
-            .center_x(Length::Fill)
-            .center_y(Length::Fill)
-            .into(),
-            Column::with_children(vec![layers_section.into(), menu_section.into()])
-                .align_items(Alignment::Center)
-                .width(Length::Fixed(250.0))
-                .height(Length::Fill)
-                .into(),
-        ])
-        .padding(0)
-        .spacing(20)
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .align_items(Alignment::Center)
+                .width(Length::Fill)
+                .height(Length::Fill),
+            Splitter::new(
+                Container::new(Scrollable::with_direction(
+                    canvas,
+                    Direction::Both {
+                        vertical: Properties::default(),
+                        horizontal: Properties::default(),
+                    },
+                ))
+                .center_x(Length::Fill)
+                .center_y(Length::Fill),
+                Column::with_children(vec![layers_section.into(), menu_section.into()])
+                    .align_items(Alignment::Center)
+                    .width(Length::Fill)
+                    .height(Length::Fill),
+                splitter::Direction::Horizontal,
+            )
+            .ratio(0.75),
+            splitter::Direction::Horizontal,
+        )
+        .ratio(0.2)
         .into(),
     ])
     .into()
@@ -767,6 +1326,7 @@ pub fn post_prompt<'a>(post_data: &'a PostData) -> Element<'a, Message, Theme, R
                     .on_input(|new_value| {
                         DrawingMessage::UpdatePostData(UpdatePostData::TagInput(new_value)).into()
                     })
+                    .describe("Add a tag to the post")
                     .into(),
                     Button::new(Text::new(Icon::Add.to_string()).size(30).font(ICON))
                         .on_press(
@@ -799,3 +1359,638 @@ pub fn post_prompt<'a>(post_data: &'a PostData) -> Element<'a, Message, Theme, R
     .height(Length::Shrink)
     .into()
 }
+
+pub fn resize_dialog<'a>(resize_data: &'a ResizeData) -> Element<'a, Message, Theme, Renderer> {
+    let resize_button = Button::new("Resize");
+    let resize_button = if let Some((width, height)) = resize_data.get_size() {
+        resize_button.on_press(CanvasMessage::Resize { width, height }.into())
+    } else {
+        resize_button
+    };
+
+    Closeable::new(
+        Card::new(
+            Text::new("Resize canvas"),
+            Column::with_children(vec![
+                Text::new("Width:").into(),
+                TextInput::new("Width", &*resize_data.get_width())
+                    .on_input(|value| {
+                        DrawingMessage::UpdateResizeData(UpdateResizeData::Width(value)).into()
+                    })
+                    .into(),
+                Text::new("Height:").into(),
+                TextInput::new("Height", &*resize_data.get_height())
+                    .on_input(|value| {
+                        DrawingMessage::UpdateResizeData(UpdateResizeData::Height(value)).into()
+                    })
+                    .into(),
+                Checkbox::new("Maintain aspect ratio", resize_data.get_keep_ratio())
+                    .on_toggle(|_| {
+                        DrawingMessage::UpdateResizeData(UpdateResizeData::ToggleKeepRatio).into()
+                    })
+                    .into(),
+            ])
+            .spacing(10.0)
+            .height(Length::Shrink),
+        )
+        .footer(resize_button)
+        .width(Length::Fixed(300.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(
+        Into::<Message>::into(DrawingMessage::ToggleModal(ModalTypes::ResizeDialog)),
+        25.0,
+    )
+    .close_padding(7.0)
+    .width(Length::Shrink)
+    .height(Length::Shrink)
+    .into()
+}
+
+pub fn timelapse_dialog<'a>(
+    timelapse_data: &'a TimelapseData,
+) -> Element<'a, Message, Theme, Renderer> {
+    let export_button = Button::new("Export");
+    let export_button = if timelapse_data.get_options().is_some() {
+        export_button.on_press(DrawingMessage::ExportTimelapse.into())
+    } else {
+        export_button
+    };
+
+    Closeable::new(
+        Card::new(
+            Text::new("Export timelapse"),
+            Column::with_children(vec![
+                Text::new("Frames per second:").into(),
+                TextInput::new("Frames per second", &*timelapse_data.get_frame_rate())
+                    .on_input(|value| {
+                        DrawingMessage::UpdateTimelapseData(UpdateTimelapseData::FrameRate(value))
+                            .into()
+                    })
+                    .into(),
+                Text::new("Tools per frame:").into(),
+                TextInput::new("Tools per frame", &*timelapse_data.get_step())
+                    .on_input(|value| {
+                        DrawingMessage::UpdateTimelapseData(UpdateTimelapseData::Step(value)).into()
+                    })
+                    .into(),
+            ])
+            .spacing(10.0)
+            .height(Length::Shrink),
+        )
+        .footer(export_button)
+        .width(Length::Fixed(300.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(
+        Into::<Message>::into(DrawingMessage::ToggleModal(ModalTypes::TimelapseDialog)),
+        25.0,
+    )
+    .close_padding(7.0)
+    .width(Length::Shrink)
+    .height(Length::Shrink)
+    .into()
+}
+
+pub fn adjustments_dialog<'a>(
+    adjustments_data: &'a AdjustmentsData,
+) -> Element<'a, Message, Theme, Renderer> {
+    let preview: Element<'a, Message, Theme, Renderer> = match adjustments_data.get_preview() {
+        Some(handle) => Image::new(handle.clone()).into(),
+        None => Text::new("Loading preview...").into(),
+    };
+
+    Closeable::new(
+        Card::new(
+            Text::new("Export with adjustments"),
+            Column::with_children(vec![
+                Container::new(preview)
+                    .center_x(Length::Fill)
+                    .center_y(Length::Fixed(200.0))
+                    .into(),
+                Text::new("Brightness:").into(),
+                Slider::new(-1.0..=1.0, adjustments_data.get_brightness(), |value| {
+                    DrawingMessage::UpdateAdjustmentsData(UpdateAdjustmentsData::Brightness(value))
+                        .into()
+                })
+                .step(0.05)
+                .into(),
+                Text::new("Contrast:").into(),
+                Slider::new(-100.0..=100.0, adjustments_data.get_contrast(), |value| {
+                    DrawingMessage::UpdateAdjustmentsData(UpdateAdjustmentsData::Contrast(value))
+                        .into()
+                })
+                .into(),
+                Text::new("Saturation:").into(),
+                Slider::new(0.0..=2.0, adjustments_data.get_saturation(), |value| {
+                    DrawingMessage::UpdateAdjustmentsData(UpdateAdjustmentsData::Saturation(value))
+                        .into()
+                })
+                .step(0.05)
+                .into(),
+                Text::new("Hue:").into(),
+                Slider::new(-180.0..=180.0, adjustments_data.get_hue(), |value| {
+                    DrawingMessage::UpdateAdjustmentsData(UpdateAdjustmentsData::Hue(value)).into()
+                })
+                .into(),
+                Checkbox::new("Invert colors", adjustments_data.get_invert())
+                    .on_toggle(|value| {
+                        DrawingMessage::UpdateAdjustmentsData(UpdateAdjustmentsData::Invert(value))
+                            .into()
+                    })
+                    .into(),
+            ])
+            .spacing(10.0)
+            .height(Length::Shrink),
+        )
+        .footer(Button::new("Export").on_press(DrawingMessage::ExportWithAdjustments.into()))
+        .width(Length::Fixed(300.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(
+        Into::<Message>::into(DrawingMessage::ToggleModal(ModalTypes::AdjustmentsDialog)),
+        25.0,
+    )
+    .close_padding(7.0)
+    .width(Length::Shrink)
+    .height(Length::Shrink)
+    .into()
+}
+
+/// Formats the time that passed since the given moment.
+fn relative_time(time: &Option<DateTime<Local>>) -> String {
+    let Some(time) = time else {
+        return String::from("before opening");
+    };
+
+    let seconds = (Local::now() - *time).num_seconds();
+    if seconds < 60 {
+        String::from("just now")
+    } else if seconds < 3600 {
+        format!("{} min ago", seconds / 60)
+    } else {
+        format!("{} h ago", seconds / 3600)
+    }
+}
+
+/// Returns a panel listing the users the drawing is shared with, where their permissions can
+/// be changed, and where the drawing can be shared with new users.
+pub fn share_panel<'a>(
+    share_data: &'a ShareData,
+    permissions: &'a HashMap<Uuid, DrawingPermission>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let users = permissions
+        .iter()
+        .map(|(user_id, permission)| {
+            let user_id = *user_id;
+            let name = share_data
+                .get_user(&user_id)
+                .map(|user| format!("{} @{}", user.get_username(), user.get_user_tag()))
+                .unwrap_or(String::from("Loading..."));
+
+            Row::with_children(vec![
+                Text::new(name).width(Length::Fill).into(),
+                PickList::new(
+                    DrawingPermission::ALL,
+                    Some(*permission),
+                    move |permission| DrawingMessage::ShareWith(user_id, permission).into(),
+                )
+                .into(),
+            ])
+            .spacing(10.0)
+            .align_items(Alignment::Center)
+            .into()
+        })
+        .collect::<Vec<Element<Message, Theme, Renderer>>>();
+
+    let share_button = Button::new("Share");
+    let share_button = if share_data.get_user_tag().is_empty() {
+        share_button
+    } else {
+        share_button.on_press(DrawingMessage::ShareWithTag.into())
+    };
+
+    Closeable::new(
+        Card::new(
+            Text::new("Share drawing"),
+            Column::with_children(vec![
+                Scrollable::new(Column::with_children(users).spacing(5.0))
+                    .height(Length::Fixed(250.0))
+                    .into(),
+                Row::with_children(vec![
+                    TextInput::new("User tag", share_data.get_user_tag())
+                        .on_input(|value| {
+                            DrawingMessage::UpdateShareData(UpdateShareData::UserTag(value)).into()
+                        })
+                        .width(Length::Fill)
+                        .into(),
+                    PickList::new(
+                        DrawingPermission::ALL,
+                        Some(share_data.get_permission()),
+                        |permission| {
+                            DrawingMessage::UpdateShareData(UpdateShareData::Permission(permission))
+                                .into()
+                        },
+                    )
+                    .into(),
+                ])
+                .spacing(10.0)
+                .align_items(Alignment::Center)
+                .into(),
+            ])
+            .spacing(10.0)
+            .height(Length::Shrink),
+        )
+        .footer(share_button)
+        .width(Length::Fixed(400.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(
+        Into::<Message>::into(DrawingMessage::ToggleModal(ModalTypes::SharePanel)),
+        25.0,
+    )
+    .close_padding(7.0)
+    .width(Length::Shrink)
+    .height(Length::Shrink)
+    .into()
+}
+
+/// Returns a panel listing the snapshots of the drawing, where new ones can be created and
+/// older ones can be restored.
+pub fn snapshot_panel<'a>(
+    canvas: &'a Canvas,
+    snapshot_name: &'a String,
+) -> Element<'a, Message, Theme, Renderer> {
+    let snapshots = canvas
+        .get_snapshots()
+        .iter()
+        .rev()
+        .map(|snapshot| {
+            Row::with_children(vec![
+                Text::new(snapshot.get_name().clone())
+                    .width(Length::Fill)
+                    .into(),
+                Text::new(snapshot.get_created().format("%d %b %Y %H:%M").to_string())
+                    .size(12.0)
+                    .into(),
+                Button::new("Restore")
+                    .on_press(CanvasMessage::RestoreSnapshot(snapshot.get_id()).into())
+                    .into(),
+            ])
+            .spacing(10.0)
+            .align_items(Alignment::Center)
+            .into()
+        })
+        .collect::<Vec<Element<Message, Theme, Renderer>>>();
+
+    let create_button = Button::new("Create snapshot");
+    let create_button = if snapshot_name.is_empty() {
+        create_button
+    } else {
+        create_button.on_press(CanvasMessage::CreateSnapshot(snapshot_name.clone()).into())
+    };
+
+    Closeable::new(
+        Card::new(
+            Text::new("Snapshots"),
+            Column::with_children(vec![
+                Scrollable::new(Column::with_children(snapshots).spacing(5.0))
+                    .height(Length::Fixed(300.0))
+                    .into(),
+                TextInput::new("Snapshot name", snapshot_name)
+                    .on_input(|value| DrawingMessage::UpdateSnapshotName(value).into())
+                    .into(),
+            ])
+            .spacing(10.0)
+            .height(Length::Shrink),
+        )
+        .footer(create_button)
+        .width(Length::Fixed(400.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(
+        Into::<Message>::into(DrawingMessage::ToggleModal(ModalTypes::SnapshotPanel)),
+        25.0,
+    )
+    .close_padding(7.0)
+    .width(Length::Shrink)
+    .height(Length::Shrink)
+    .into()
+}
+
+pub fn history_panel<'a>(
+    canvas: &'a Canvas,
+    globals: &Globals,
+) -> Element<'a, Message, Theme, Renderer> {
+    let (history, position) = canvas.get_history(globals);
+
+    let entry = |label: String, details: String, step: usize| -> Element<Message, Theme, Renderer> {
+        Button::new(
+            Row::with_children(vec![
+                Text::new(label).width(Length::Fill).into(),
+                Text::new(details).size(12.0).into(),
+            ])
+            .spacing(10.0)
+            .align_items(Alignment::Center),
+        )
+        .style(if step == position {
+            iced::widget::button::primary
+        } else if step < position {
+            iced::widget::button::text
+        } else {
+            iced::widget::button::secondary
+        })
+        .on_press(CanvasMessage::RestoreToStep(step).into())
+        .width(Length::Fill)
+        .into()
+    };
+
+    let mut entries = vec![entry(String::from("Blank canvas"), String::new(), 0)];
+    entries.extend(
+        history
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, layer, time))| {
+                let layer_name = canvas
+                    .get_layers()
+                    .get(&layer)
+                    .map(|layer| layer.get_name().clone())
+                    .unwrap_or_default();
+
+                entry(
+                    name,
+                    format!("{} - {}", layer_name, relative_time(&time)),
+                    index + 1,
+                )
+            }),
+    );
+
+    Closeable::new(
+        Card::new(
+            Text::new("History"),
+            Scrollable::new(Column::with_children(entries).spacing(5.0))
+                .height(Length::Fixed(400.0)),
+        )
+        .width(Length::Fixed(350.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(
+        Into::<Message>::into(DrawingMessage::ToggleModal(ModalTypes::HistoryPanel)),
+        25.0,
+    )
+    .close_padding(7.0)
+    .width(Length::Shrink)
+    .height(Length::Shrink)
+    .into()
+}
+
+/// Uploads a locally stored drawing to the database, under a new id owned by the given user.
+pub async fn sync_drawing_online(
+    db: &Database,
+    id: Uuid,
+    name: String,
+    user_id: Uuid,
+) -> Result<(), Error> {
+    let (layers, tools, _, size, guides) = get_drawing_offline(id).await?;
+    let new_id = Uuid::new();
+
+    let mut svg = crate::canvas::svg::SVG::new(&layers.iter().map(|(id, _)| *id).collect());
+    if let Some((width, height)) = size {
+        svg.set_size(width, height);
+    }
+    for (tool, layer) in &tools {
+        svg.add_tool(layer, Serialize::<Group>::serialize(tool.as_ref()));
+    }
+
+    let webp = utils::encoder::encode_svg(svg.as_document(), "webp").await?;
+    database::base::upload_file(format!("/{}/{}.webp", user_id, new_id), webp).await?;
+
+    let tools = tools
+        .iter()
+        .enumerate()
+        .map(|(order, (tool, layer))| {
+            let mut document: Document = Serialize::<Document>::serialize(tool.as_ref());
+            document.insert("order", order as u32);
+            document.insert("canvas_id", new_id);
+            document.insert("name", tool.id());
+            document.insert("layer", layer);
+
+            document
+        })
+        .collect();
+
+    database::drawing::create_drawing(db, new_id, user_id).await?;
+    database::drawing::update_drawing(
+        db,
+        new_id,
+        user_id,
+        name,
+        0,
+        0,
+        tools,
+        vec![],
+        layers,
+        size.unwrap_or((800, 600)),
+        guides,
+    )
+    .await
+}
+
+/// Listens for the cursor movements of the collaborators on the given drawing, and sends them
+/// to the scene.
+pub async fn watch_cursors(
+    db: Database,
+    canvas_id: Uuid,
+    session: Uuid,
+    mut output: Sender<Message>,
+) -> Infallible {
+    loop {
+        match database::drawing::watch_cursors(&db, canvas_id, session).await {
+            Ok(mut stream) => {
+                while let Some(event) = stream.next().await {
+                    let document = match event {
+                        Ok(event) => event.full_document,
+                        Err(err) => {
+                            let _ = output
+                                .send(Message::Error(debug_message!("{}", err).into()))
+                                .await;
+                            break;
+                        }
+                    };
+
+                    let Some(document) = document else {
+                        continue;
+                    };
+                    let (
+                        Some(Bson::Binary(session)),
+                        Some(Bson::Binary(user_id)),
+                        Ok(name),
+                        Ok(x),
+                        Ok(y),
+                    ) = (
+                        document.get("session"),
+                        document.get("user_id"),
+                        document.get_str("name"),
+                        document.get_f64("x"),
+                        document.get_f64("y"),
+                    )
+                    else {
+                        continue;
+                    };
+                    let (Ok(session), Ok(user_id)) = (
+                        session.to_uuid_with_representation(UuidRepresentation::Standard),
+                        user_id.to_uuid_with_representation(UuidRepresentation::Standard),
+                    ) else {
+                        continue;
+                    };
+
+                    let _ = output
+                        .send(
+                            DrawingMessage::ReceivedCursor {
+                                session,
+                                cursor: CollabCursor::new(
+                                    user_id,
+                                    name,
+                                    Point::new(x as f32, y as f32),
+                                ),
+                            }
+                            .into(),
+                        )
+                        .await;
+                }
+            }
+            Err(err) => {
+                let _ = output.send(Message::Error(err)).await;
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
+/// Watches the file of the locally stored drawing with the given id, and reloads the drawing
+/// when it is changed by another program.
+pub async fn watch_data_file(id: Uuid, mut output: Sender<Message>) -> Infallible {
+    loop {
+        let (sender, mut events) = iced::futures::channel::mpsc::channel(100);
+
+        // The watcher stops when it is dropped, so it is kept for as long as the events are read.
+        let _watcher = match create_data_file_watcher(id, sender) {
+            Ok(Some(watcher)) => watcher,
+            Ok(None) => {
+                // The directory of a new drawing may not have been created yet.
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+            Err(err) => {
+                let _ = output.send(Message::Error(err)).await;
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        while let Some(event) = events.next().await {
+            if !is_data_file_change(&event) {
+                continue;
+            }
+
+            // Writing a file usually produces several events, which are gathered into one reload.
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            while let Ok(Some(_)) = events.try_next() {}
+
+            let _ = output.send(CanvasMessage::ReloadFromDisk.into()).await;
+        }
+    }
+}
+
+/// Starts watching the directory of the locally stored drawing with the given id, sending its
+/// events through the given channel. Returns None if the directory does not exist.
+fn create_data_file_watcher(
+    id: Uuid,
+    mut sender: Sender<notify::Event>,
+) -> Result<Option<RecommendedWatcher>, Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+    let dir_path = proj_dirs.data_local_dir().join(id.to_string());
+    if !dir_path.exists() {
+        return Ok(None);
+    }
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let _ = sender.try_send(event);
+        }
+    })
+    .map_err(|err| debug_message!("{}", err).into())?;
+
+    watcher
+        .watch(&dir_path, RecursiveMode::NonRecursive)
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    Ok(Some(watcher))
+}
+
+/// Tells whether the event is a change of the data file of a drawing.
+fn is_data_file_change(event: &notify::Event) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event.paths.iter().any(|path| {
+            path.file_name()
+                .is_some_and(|name| name == DATA_FILE || name == LEGACY_DATA_FILE)
+        })
+}
+
+/// Forwards the tools that collaborators save on the given drawing. If the change stream
+/// closes, it is opened again after a few seconds.
+pub async fn watch_tools(
+    db: Database,
+    canvas_id: Uuid,
+    session: Uuid,
+    mut output: Sender<Message>,
+) -> Infallible {
+    loop {
+        match database::drawing::watch_tools(&db, canvas_id, session).await {
+            Ok(mut stream) => {
+                while let Some(event) = stream.next().await {
+                    let document = match event {
+                        Ok(event) => event.full_document,
+                        Err(err) => {
+                            let _ = output
+                                .send(Message::Error(debug_message!("{}", err).into()))
+                                .await;
+                            break;
+                        }
+                    };
+
+                    let Some(document) = document else {
+                        continue;
+                    };
+                    let Some((tool, layer)) = tool::get_deserialized(&document) else {
+                        continue;
+                    };
+                    let session = match document.get("session") {
+                        Some(Bson::Binary(bin)) => bin
+                            .to_uuid_with_representation(UuidRepresentation::Standard)
+                            .unwrap_or(Uuid::from_bytes([0; 16])),
+                        _ => Uuid::from_bytes([0; 16]),
+                    };
+
+                    let _ = output
+                        .send(
+                            DrawingMessage::ReceivedTool {
+                                tool,
+                                layer,
+                                session,
+                            }
+                            .into(),
+                        )
+                        .await;
+                }
+            }
+            Err(err) => {
+                let _ = output.send(Message::Error(err)).await;
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
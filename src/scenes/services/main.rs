@@ -1,30 +1,48 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Local};
 use directories::ProjectDirs;
 use iced::{
-    advanced::widget::Text,
+    advanced::widget::{
+        self,
+        operation::{Focusable, Outcome},
+        Operation, Text,
+    },
     alignment::{Horizontal, Vertical},
-    widget::{Button, Column, Container, Row, Scrollable, Space},
-    Alignment, Element, Length, Renderer, Size,
+    widget::{
+        image::Handle,
+        scrollable::{Direction, Properties},
+        text_input,
+        tooltip::Position,
+        Button, Column, Container, Image, Row, Scrollable, Space, TextInput, Tooltip,
+    },
+    Alignment, Element, Length, Rectangle, Renderer, Size,
 };
 use image::{load_from_memory_with_format, ImageFormat};
-use json::JsonValue;
+use json::{object::Object, JsonValue};
 use mongodb::bson::{Bson, Document, Uuid, UuidRepresentation};
+use rfd::AsyncFileDialog;
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read, Write};
 use tokio::io;
+use zip::write::SimpleFileOptions;
 
 use crate::{
     database, debug_message,
-    scene::{Globals, Message},
+    scene::{DbStatus, Globals, Message, StartupStep},
     scenes::{
         auth::AuthOptions,
         data::{
-            auth::{AuthTabIds, User},
+            app_settings::ThemeMode,
+            auth::{AuthTabIds, Role, User},
             drawing::SaveMode,
-            main::{MainTabIds, ModalType},
+            main::{ArchivedDrawing, MainTabIds, ModalType},
         },
         drawing::DrawingOptions,
         main::MainMessage,
+        posts::PostsOptions,
         scenes::Scenes,
+        services,
     },
     utils::{
         cache::PixelImage,
@@ -32,56 +50,324 @@ use crate::{
         icons::{Icon, ICON},
         theme::{self, Theme},
     },
-    widgets::{card::Card, closeable::Closeable, Centered, Tabs},
+    widgets::{
+        card::Card, closeable::Closeable, Badge, Centered, CheckboxList, ContextMenu, Tabs,
+        WaitPanel,
+    },
 };
 
 /// Returns the ids of the drawings stored locally.
 pub async fn get_drawings_offline() -> Result<Vec<(Uuid, String)>, Error> {
+    database::local::get_drawings().await
+}
+
+/// Lets the user pick a ZIP archive and reads the drawings stored in it. Every drawing must be
+/// stored as a `<uuid>/data.json` and `<uuid>/data.webp` pair. Returns None if no file was chosen.
+pub async fn read_archive() -> Result<Option<Vec<ArchivedDrawing>>, Error> {
+    let file = AsyncFileDialog::new()
+        .set_title("Import drawings")
+        .set_directory("~")
+        .add_filter("archive", &["zip"])
+        .pick_file()
+        .await;
+
+    let Some(handle) = file else {
+        return Ok(None);
+    };
+
+    let bytes = handle.read().await;
+
+    tokio::task::spawn_blocking(move || {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+            .map_err(|err| debug_message!("{}", err).into())?;
+        let mut files: HashMap<Uuid, (Option<Vec<u8>>, Option<Vec<u8>>)> = HashMap::new();
+
+        for index in 0..archive.len() {
+            let mut entry = archive
+                .by_index(index)
+                .map_err(|err| debug_message!("{}", err).into())?;
+
+            let parts: Vec<String> = entry
+                .name()
+                .split('/')
+                .filter(|part| !part.is_empty())
+                .map(String::from)
+                .collect();
+            if parts.len() < 2 {
+                continue;
+            }
+
+            let Ok(id) = Uuid::parse_str(&parts[parts.len() - 2]) else {
+                continue;
+            };
+
+            let mut contents = vec![];
+            entry
+                .read_to_end(&mut contents)
+                .map_err(|err| debug_message!("{}", err).into())?;
+
+            let slot = files.entry(id).or_default();
+            match parts[parts.len() - 1].as_str() {
+                "data.json" => slot.0 = Some(contents),
+                "data.webp" => slot.1 = Some(contents),
+                _ => {}
+            }
+        }
+
+        Ok(files
+            .into_iter()
+            .filter_map(|(id, files)| match files {
+                (Some(data), Some(preview)) => {
+                    let name = std::str::from_utf8(&data)
+                        .ok()
+                        .and_then(|data| json::parse(data).ok())
+                        .and_then(|data| data["name"].as_str().map(String::from))
+                        .unwrap_or(String::from("New drawing"));
+
+                    Some(ArchivedDrawing {
+                        id,
+                        name,
+                        data,
+                        preview,
+                    })
+                }
+                _ => None,
+            })
+            .collect())
+    })
+    .await
+    .map_err(|err| debug_message!("{}", err).into())?
+    .map(Some)
+}
+
+/// Stores an [ArchivedDrawing] locally and adds it to the list of drawings.
+/// If a drawing with the same id already exists, the imported one receives a new id.
+pub async fn import_drawing_offline(drawing: ArchivedDrawing) -> Result<(), Error> {
     let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
         .ok_or(debug_message!("Unable to find project directory.").into())?;
 
-    let dir_path = proj_dirs.data_local_dir();
-    tokio::fs::create_dir_all(dir_path)
+    let mut id = drawing.id;
+    while tokio::fs::try_exists(proj_dirs.data_local_dir().join(id.to_string()))
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?
+    {
+        id = Uuid::new();
+    }
+
+    let dir_path = proj_dirs.data_local_dir().join(id.to_string());
+    tokio::fs::create_dir_all(dir_path.clone())
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?;
+    services::drawing::write_data_offline(&dir_path, &drawing.data).await?;
+    tokio::fs::write(dir_path.join("data.webp"), drawing.preview)
         .await
         .map_err(|err| debug_message!("{}", err).into())?;
 
-    let file_path = dir_path.join("drawings.json");
-    let input = match tokio::fs::read_to_string(file_path.clone()).await {
-        Ok(input) => input,
-        Err(err) => {
-            if err.kind() == io::ErrorKind::NotFound {
-                tokio::fs::write(file_path, json::stringify(JsonValue::Array(vec![])))
-                    .await
-                    .map_err(|err| debug_message!("{}", err).into())?;
-            }
+    database::local::insert_drawing(id, drawing.name).await
+}
+
+/// Bundles the given locally stored drawings into a ZIP archive, together with a `manifest.json`
+/// holding their names and creation times, and lets the user choose where to save it.
+pub async fn export_drawings_offline(ids: Vec<Uuid>) -> Result<(), Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+    let names: HashMap<Uuid, String> = HashMap::from_iter(get_drawings_offline().await?);
+
+    let mut files = vec![];
+    let mut manifest = vec![];
+    for id in ids {
+        let dir_path = proj_dirs.data_local_dir().join(id.to_string());
+
+        let metadata = tokio::fs::metadata(dir_path.clone())
+            .await
+            .map_err(|err| debug_message!("{}", err).into())?;
+        let created: DateTime<Local> = metadata
+            .created()
+            .or(metadata.modified())
+            .map_err(|err| debug_message!("{}", err).into())?
+            .into();
+
+        // The data is archived uncompressed, since the archive is compressed as a whole.
+        let data = services::drawing::read_data_offline(&dir_path).await?;
+        files.push((format!("{}/data.json", id), data.into_bytes()));
 
-            return Ok(vec![]);
+        let preview = tokio::fs::read(dir_path.join("data.webp"))
+            .await
+            .map_err(|err| debug_message!("{}", err).into())?;
+        files.push((format!("{}/data.webp", id), preview));
+
+        let mut entry = Object::new();
+        entry.insert("id", JsonValue::String(id.to_string()));
+        entry.insert(
+            "name",
+            JsonValue::String(
+                names
+                    .get(&id)
+                    .cloned()
+                    .unwrap_or(String::from("New drawing")),
+            ),
+        );
+        entry.insert("created", JsonValue::String(created.to_rfc3339()));
+
+        manifest.push(JsonValue::Object(entry));
+    }
+    files.push((
+        String::from("manifest.json"),
+        json::stringify(JsonValue::Array(manifest)).into_bytes(),
+    ));
+
+    let archive = tokio::task::spawn_blocking(move || {
+        let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+
+        for (name, contents) in files {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .map_err(|err| debug_message!("{}", err).into())?;
+            writer
+                .write_all(&contents)
+                .map_err(|err| debug_message!("{}", err).into())?;
         }
-    };
-    let mut list = vec![];
 
-    let json = json::parse(&*input).map_err(|err| debug_message!("{}", err).into())?;
-    if let JsonValue::Array(drawings) = json {
-        for drawing in drawings {
-            if let JsonValue::Object(drawing) = drawing {
-                let name = if let Some(JsonValue::Short(name)) = drawing.get("name") {
-                    name.to_string()
-                } else if let Some(JsonValue::String(name)) = drawing.get("name") {
-                    name.clone()
-                } else {
-                    String::from("New drawing")
-                };
+        writer
+            .finish()
+            .map(Cursor::into_inner)
+            .map_err(|err| debug_message!("{}", err).into())
+    })
+    .await
+    .map_err(|err| debug_message!("{}", err).into())??;
 
-                if let Some(JsonValue::String(id)) = drawing.get("id") {
-                    if let Ok(id) = Uuid::parse_str(id) {
-                        list.push((id, name));
-                    }
-                }
-            }
+    let file = AsyncFileDialog::new()
+        .set_title("Export drawings")
+        .set_directory("~")
+        .set_file_name("drawings.zip")
+        .add_filter("archive", &["zip"])
+        .save_file()
+        .await;
+
+    match file {
+        Some(handle) => handle
+            .write(&archive)
+            .await
+            .map_err(|err| debug_message!("{}", err).into()),
+        None => Ok(()),
+    }
+}
+
+/// Changes the name of a locally stored drawing.
+pub async fn rename_drawing_offline(id: Uuid, name: String) -> Result<(), Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+
+    database::local::update_drawing(id, name.clone()).await?;
+
+    let dir_path = proj_dirs.data_local_dir().join(id.to_string());
+    let mut data = json::parse(&*services::drawing::read_data_offline(&dir_path).await?)
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    if let JsonValue::Object(ref mut data) = data {
+        data.insert("name", JsonValue::String(name));
+    }
+
+    services::drawing::write_data_offline(&dir_path, json::stringify(data).as_bytes()).await
+}
+
+/// Creates a copy of a locally stored drawing with the given id and name.
+pub async fn duplicate_drawing_offline(id: Uuid, new_id: Uuid, name: String) -> Result<(), Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+
+    let dir_path = proj_dirs.data_local_dir().join(id.to_string());
+    let new_dir_path = proj_dirs.data_local_dir().join(new_id.to_string());
+    tokio::fs::create_dir_all(new_dir_path.clone())
+        .await
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    let copy = async {
+        let mut data = json::parse(&*services::drawing::read_data_offline(&dir_path).await?)
+            .map_err(|err| debug_message!("{}", err).into())?;
+        if let JsonValue::Object(ref mut data) = data {
+            data.insert("name", JsonValue::String(name.clone()));
         }
+
+        services::drawing::write_data_offline(&new_dir_path, json::stringify(data).as_bytes())
+            .await?;
+        tokio::fs::copy(dir_path.join("data.webp"), new_dir_path.join("data.webp"))
+            .await
+            .map_err(|err| debug_message!("{}", err).into())?;
+
+        database::local::insert_drawing(new_id, name).await
+    };
+
+    // A partial copy would otherwise be left behind without ever being listed.
+    if let Err(err) = copy.await {
+        let _ = tokio::fs::remove_dir_all(new_dir_path).await;
+        return Err(err);
     }
 
-    Ok(list)
+    Ok(())
+}
+
+/// Returns the list of recently opened drawings.
+pub async fn load_recent_drawings() -> Result<Vec<(Uuid, String, SaveMode)>, Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+
+    let file_path = proj_dirs.data_local_dir().join("recent.json");
+    let input = match tokio::fs::read_to_string(file_path).await {
+        Ok(input) => input,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(debug_message!("{}", err).into()),
+    };
+
+    let json = json::parse(&*input).map_err(|err| debug_message!("{}", err).into())?;
+
+    Ok(json
+        .members()
+        .filter_map(|drawing| {
+            let id = Uuid::parse_str(drawing["id"].as_str()?).ok()?;
+            let name = drawing["name"].as_str()?.to_string();
+            let save_mode = match drawing["save_mode"].as_str()? {
+                "offline" => SaveMode::Offline,
+                "online" => SaveMode::Online,
+                _ => return None,
+            };
+
+            Some((id, name, save_mode))
+        })
+        .collect())
+}
+
+/// Stores the list of recently opened drawings.
+pub async fn save_recent_drawings(drawings: Vec<(Uuid, String, SaveMode)>) -> Result<(), Error> {
+    let proj_dirs = ProjectDirs::from("", "CharMe", "Chartsy")
+        .ok_or(debug_message!("Unable to find project directory.").into())?;
+
+    let json = JsonValue::Array(
+        drawings
+            .into_iter()
+            .map(|(id, name, save_mode)| {
+                let mut entry = Object::new();
+                entry.insert("id", JsonValue::String(id.to_string()));
+                entry.insert("name", JsonValue::String(name));
+                entry.insert(
+                    "save_mode",
+                    JsonValue::String(String::from(match save_mode {
+                        SaveMode::Offline => "offline",
+                        SaveMode::Online => "online",
+                    })),
+                );
+
+                JsonValue::Object(entry)
+            })
+            .collect(),
+    );
+
+    tokio::fs::write(
+        proj_dirs.data_local_dir().join("recent.json"),
+        json::stringify(json),
+    )
+    .await
+    .map_err(|err| debug_message!("{}", err).into())
 }
 
 /// Returns the ids of the drawings stored in a database that belong to the currently
@@ -141,32 +427,108 @@ pub async fn delete_token_file() -> Result<(), Error> {
         .map_err(|err| debug_message!("{}", err).into())
 }
 
-pub fn auth_logged_in<'a>(user: &User) -> Element<'a, Message, Theme, Renderer> {
+/// Returns the screen displayed while the application is starting up, showing the logo, the
+/// version and the current [step](StartupStep) of the startup.
+pub fn splash_screen<'a>(
+    logo: Handle,
+    step: StartupStep,
+    frame: usize,
+) -> Element<'a, Message, Theme, Renderer> {
+    let spinner = ["", ".", "..", "..."][frame % 4];
+
+    Container::new(
+        Column::with_children(vec![
+            Image::new(logo)
+                .width(Length::Fixed(128.0))
+                .height(Length::Fixed(128.0))
+                .into(),
+            Text::new("Chartsy").size(50).into(),
+            Text::new(format!("v{}", env!("CARGO_PKG_VERSION")))
+                .size(15)
+                .style(theme::text::gray)
+                .into(),
+            Row::with_children(vec![
+                Text::new(Icon::Loading.to_string())
+                    .font(ICON)
+                    .size(20)
+                    .into(),
+                Text::new(format!("{}{:<3}", step, spinner)).size(20).into(),
+            ])
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into(),
+        ])
+        .spacing(15)
+        .align_items(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .align_x(Horizontal::Center)
+    .align_y(Vertical::Center)
+    .into()
+}
+
+/// Returns a button that switches between the dark and the light color schemes.
+fn theme_button<'a>(theme: ThemeMode) -> Element<'a, Message, Theme, Renderer> {
+    let (icon, next_theme) = match theme {
+        ThemeMode::Dark => (Icon::Sun, ThemeMode::Light),
+        ThemeMode::Light => (Icon::Moon, ThemeMode::Dark),
+    };
+
+    Tooltip::new(
+        Button::new(Text::new(icon.to_string()).font(ICON).size(20.0))
+            .padding(8)
+            .on_press(Message::SetTheme(next_theme)),
+        Text::new(format!(
+            "Switch to {} theme",
+            next_theme.to_string().to_lowercase()
+        )),
+        Position::FollowCursor,
+    )
+    .into()
+}
+
+pub fn auth_logged_in<'a>(user: &User, theme: ThemeMode) -> Element<'a, Message, Theme, Renderer> {
     let welcome_message = Text::new(format!("Welcome, {}!", user.get_username()))
         .vertical_alignment(Vertical::Bottom);
     let settings_button = Button::new("Settings")
         .padding(8)
         .on_press(Message::ChangeScene(Scenes::Settings(None)));
+    let preferences_button = Button::new("Preferences")
+        .padding(8)
+        .on_press(Message::ChangeScene(Scenes::AppSettings(None)));
     let logout_button = Button::new("Log Out")
         .padding(8)
         .on_press(MainMessage::LogOut.into());
 
+    let mut buttons: Vec<Element<'a, Message, Theme, Renderer>> = vec![welcome_message.into()];
+    if *user.get_role() == Role::Admin {
+        buttons.push(
+            Button::new("Admin panel")
+                .padding(8)
+                .on_press(Message::ChangeScene(Scenes::Admin(None)))
+                .into(),
+        );
+    }
+    buttons.extend([
+        settings_button.into(),
+        preferences_button.into(),
+        theme_button(theme),
+        logout_button.into(),
+    ]);
+
     Row::with_children(vec![
         Space::with_width(Length::Fill).into(),
-        Row::with_children(vec![
-            welcome_message.into(),
-            settings_button.into(),
-            logout_button.into(),
-        ])
-        .align_items(Alignment::Center)
-        .width(Length::Shrink)
-        .spacing(20)
-        .into(),
+        Row::with_children(buttons)
+            .align_items(Alignment::Center)
+            .width(Length::Shrink)
+            .spacing(20)
+            .into(),
     ])
     .into()
 }
 
-pub fn auth_logged_out<'a>() -> Element<'a, Message, Theme, Renderer> {
+pub fn auth_logged_out<'a>(theme: ThemeMode) -> Element<'a, Message, Theme, Renderer> {
     let register_button = Button::new("Register")
         .padding(8)
         .on_press(Message::ChangeScene(Scenes::Auth(Some(AuthOptions::new(
@@ -177,18 +539,35 @@ pub fn auth_logged_out<'a>() -> Element<'a, Message, Theme, Renderer> {
         .on_press(Message::ChangeScene(Scenes::Auth(Some(AuthOptions::new(
             AuthTabIds::LogIn,
         )))));
+    let preferences_button = Button::new("Preferences")
+        .padding(8)
+        .on_press(Message::ChangeScene(Scenes::AppSettings(None)));
 
     Row::with_children(vec![
         Space::with_width(Length::Fill).into(),
-        Row::with_children(vec![register_button.into(), login_button.into()])
-            .width(Length::Shrink)
-            .spacing(20)
-            .into(),
+        Row::with_children(vec![
+            register_button.into(),
+            login_button.into(),
+            preferences_button.into(),
+            theme_button(theme),
+        ])
+        .width(Length::Shrink)
+        .spacing(20)
+        .into(),
     ])
     .into()
 }
 
-pub fn main_column<'a>(user_logged_in: bool) -> Element<'a, Message, Theme, Renderer> {
+/// Displays the main buttons. The online features are disabled while the database is not
+/// connected, with the status of the connection shown below the buttons.
+pub fn main_column<'a>(
+    user_logged_in: bool,
+    db_status: DbStatus,
+    notifications: &Vec<(Uuid, String)>,
+    challenge_active: bool,
+) -> Element<'a, Message, Theme, Renderer> {
+    let connected = db_status == DbStatus::Connected;
+
     let start_drawing_button = Button::new(
         Text::new("Start new Drawing")
             .width(Length::Fill)
@@ -211,7 +590,32 @@ pub fn main_column<'a>(user_logged_in: bool) -> Element<'a, Message, Theme, Rend
             .horizontal_alignment(Horizontal::Center),
     )
     .padding(8)
-    .on_press(Message::ChangeScene(Scenes::Posts(None)));
+    .on_press_maybe(connected.then_some(Message::ChangeScene(Scenes::Posts(None))));
+
+    let browse_posts_button: Element<'a, Message, Theme, Renderer> = match notifications.first() {
+        Some((post_id, text)) if connected => Tooltip::new(
+            Badge::new(
+                browse_posts_button
+                    .width(Length::Fill)
+                    .on_press(Message::ChangeScene(Scenes::Posts(Some(
+                        PostsOptions::new(*post_id),
+                    )))),
+                notifications.len(),
+            ),
+            Text::new(text.clone()),
+            Position::FollowCursor,
+        )
+        .into(),
+        _ => browse_posts_button.width(Length::Fill).into(),
+    };
+
+    let challenge_button = Button::new(
+        Text::new("Today's Challenge")
+            .width(Length::Fill)
+            .horizontal_alignment(Horizontal::Center),
+    )
+    .padding(8)
+    .on_press_maybe(connected.then_some(Message::ChangeScene(Scenes::Challenge(None))));
 
     let quit_button = Button::new(
         Text::new("Quit")
@@ -221,13 +625,18 @@ pub fn main_column<'a>(user_logged_in: bool) -> Element<'a, Message, Theme, Rend
     .padding(8)
     .on_press(Message::Quit);
 
-    Column::with_children(if user_logged_in {
-        vec![
+    let mut column = Column::with_children(if user_logged_in {
+        let mut buttons = vec![
             start_drawing_button.width(Length::Fill).into(),
             continue_drawing_button.width(Length::Fill).into(),
-            browse_posts_button.width(Length::Fill).into(),
-            quit_button.width(Length::Fill).into(),
-        ]
+            browse_posts_button,
+        ];
+        if challenge_active {
+            buttons.push(challenge_button.width(Length::Fill).into());
+        }
+        buttons.push(quit_button.width(Length::Fill).into());
+
+        buttons
     } else {
         vec![
             start_drawing_button.width(Length::Fill).into(),
@@ -238,24 +647,120 @@ pub fn main_column<'a>(user_logged_in: bool) -> Element<'a, Message, Theme, Rend
     .spacing(20)
     .height(Length::FillPortion(3))
     .width(Length::Fixed(200.0))
-    .align_items(Alignment::Center)
+    .align_items(Alignment::Center);
+
+    if !connected {
+        column = column.push(
+            Text::new(format!("Database: {}", db_status))
+                .size(14)
+                .style(theme::text::gray),
+        );
+    }
+
+    column.into()
+}
+
+/// Displays a banner telling the user that the database could not be reached, with a button for
+/// trying to connect again.
+pub fn db_failed_banner<'a>() -> Element<'a, Message, Theme, Renderer> {
+    Container::new(
+        Row::with_children(vec![
+            Text::new("Could not connect to the database. Online features are unavailable.")
+                .style(theme::text::danger)
+                .width(Length::Fill)
+                .into(),
+            Button::new("Retry")
+                .on_press(Message::DbStatusChanged(DbStatus::Reconnecting(0)))
+                .into(),
+        ])
+        .spacing(10)
+        .align_items(Alignment::Center),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .style(iced::widget::container::bordered_box)
     .into()
 }
 
+/// Returns the id of the name input of the drawing being renamed.
+pub fn name_input_id() -> text_input::Id {
+    text_input::Id::new("drawing_name")
+}
+
+/// The [Operation] that checks whether the name input of the drawing being renamed is still
+/// focused, and produces a [MainMessage::NameInputBlurred] otherwise.
+#[derive(Default)]
+pub struct NameInputFocus {
+    /// Tells whether the name input was found focused.
+    focused: bool,
+}
+
+impl Operation<Message> for NameInputFocus {
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<Message>),
+    ) {
+        operate_on_children(self)
+    }
+
+    fn focusable(&mut self, state: &mut dyn Focusable, id: Option<&widget::Id>) {
+        if id == Some(&name_input_id().into()) {
+            self.focused = state.is_focused();
+        }
+    }
+
+    fn finish(&self) -> Outcome<Message> {
+        if self.focused {
+            Outcome::None
+        } else {
+            Outcome::Some(MainMessage::NameInputBlurred.into())
+        }
+    }
+}
+
 pub fn display_drawing<'a>(
     id: Uuid,
     image: Element<'a, Message, Theme, Renderer>,
     name: String,
     save_mode: SaveMode,
+    name_input: Option<&str>,
 ) -> Element<'a, Message, Theme, Renderer> {
-    Button::new(
+    let open = Message::ChangeScene(Scenes::Drawing(Some(DrawingOptions::new(
+        Some(id),
+        Some(name.clone()),
+        Some(save_mode),
+    ))));
+
+    let drawing = Button::new(
         Row::<Message, Theme, Renderer>::with_children(vec![
-            Text::new(name.clone())
+            if let Some(name_input) = name_input {
+                TextInput::new("Drawing name", name_input)
+                    .id(name_input_id())
+                    .on_input(|value| MainMessage::UpdateNameInput(value).into())
+                    .on_submit(
+                        MainMessage::RenameDrawing(id, name_input.to_string(), save_mode).into(),
+                    )
+                    .width(Length::FillPortion(1))
+                    .into()
+            } else {
+                Button::new(
+                    Text::new(name.clone())
+                        .width(Length::Fill)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .style(iced::widget::button::text)
+                .on_press(MainMessage::StartRename(id, name.clone()).into())
                 .width(Length::FillPortion(1))
-                .horizontal_alignment(Horizontal::Center)
-                .into(),
+                .into()
+            },
             Space::with_width(Length::FillPortion(1)).into(),
             image,
+            Button::new(Text::new(Icon::Copy.to_string()).font(ICON))
+                .style(iced::widget::button::text)
+                .on_press(MainMessage::DuplicateDrawing(id, save_mode).into())
+                .into(),
             Button::new(
                 Text::new(Icon::Trash.to_string())
                     .font(ICON)
@@ -268,43 +773,161 @@ pub fn display_drawing<'a>(
         .align_items(Alignment::Center),
     )
     .style(iced::widget::button::secondary)
-    .on_press(Message::ChangeScene(Scenes::Drawing(Some(
-        DrawingOptions::new(Some(id), Some(name), Some(save_mode)),
-    ))))
+    .on_press(open.clone())
     .width(Length::Fill)
-    .padding(10.0)
-    .into()
+    .padding(10.0);
+
+    let mut actions = vec![
+        (String::from("Open"), open),
+        (
+            String::from("Rename"),
+            MainMessage::StartRename(id, name).into(),
+        ),
+        (
+            String::from("Duplicate"),
+            MainMessage::DuplicateDrawing(id, save_mode).into(),
+        ),
+        (
+            String::from("Delete"),
+            MainMessage::DeleteDrawing(id, save_mode).into(),
+        ),
+    ];
+    if save_mode == SaveMode::Offline {
+        actions.push((
+            String::from("Regenerate preview"),
+            MainMessage::RegenerateThumbnail(id).into(),
+        ));
+    }
+
+    ContextMenu::new(drawing, actions).into()
 }
 
 pub fn drawings_tab<'a>(
     drawings: &Option<Vec<(Uuid, String)>>,
     save_mode: SaveMode,
+    selected: Option<&HashSet<Uuid>>,
+    pages: Option<(usize, usize)>,
+    filter: &str,
+    editing: Option<(Uuid, &str)>,
     globals: &Globals,
 ) -> Element<'a, Message, Theme, Renderer> {
-    Container::new(Scrollable::new(
-        Column::<Message, Theme, Renderer>::with_children(match drawings {
-            Some(drawings) => drawings
-                .clone()
-                .iter()
-                .map(|(uuid, name)| {
-                    display_drawing(
-                        *uuid,
-                        globals.get_cache().get_element(
-                            *uuid,
-                            Size::new(Length::FillPortion(1), Length::Fixed(150.0)),
-                            Size::new(Length::Fixed(200.0), Length::Fixed(150.0)),
-                            None,
-                        ),
-                        name.clone(),
-                        save_mode,
-                    )
-                })
-                .collect(),
-            None => vec![],
-        })
+    let filter = filter.to_lowercase();
+
+    let drawings: Vec<(Uuid, String)> = drawings
+        .iter()
+        .flatten()
+        .filter(|(_, name)| name.to_lowercase().contains(&filter))
+        .cloned()
+        .collect();
+    let view = |(uuid, name): (Uuid, String)| {
+        display_drawing(
+            uuid,
+            globals.get_cache().get_element(
+                uuid,
+                Size::new(Length::FillPortion(1), Length::Fixed(150.0)),
+                Size::new(Length::Fixed(200.0), Length::Fixed(150.0)),
+                None,
+            ),
+            name,
+            save_mode,
+            editing
+                .filter(|(id, _)| *id == uuid)
+                .map(|(_, name_input)| name_input),
+        )
+    };
+
+    let list = Scrollable::new(if let Some(selected) = selected {
+        Element::from(
+            CheckboxList::new(
+                drawings.into_iter().map(|drawing| {
+                    let checked = selected.contains(&drawing.0);
+                    (drawing, checked)
+                }),
+                view,
+                |index, checked| MainMessage::SetSelected(index, checked).into(),
+            )
+            .spacing(20.0)
+            .padding([15.0, 15.0, 0.0, 15.0]),
+        )
+    } else {
+        Column::<Message, Theme, Renderer>::with_children(
+            drawings.into_iter().map(view).collect::<Vec<_>>(),
+        )
         .spacing(20.0)
-        .padding([15.0, 15.0, 0.0, 15.0]),
-    ))
+        .padding([15.0, 15.0, 0.0, 15.0])
+        .into()
+    })
+    .height(Length::Fill);
+
+    let mut footer: Vec<Element<'a, Message, Theme, Renderer>> = vec![];
+
+    if let Some((page, page_count)) = pages {
+        footer.push(
+            Button::new("Previous")
+                .padding(8)
+                .on_press_maybe(if page > 0 {
+                    Some(MainMessage::ChangePage(page - 1).into())
+                } else {
+                    None
+                })
+                .into(),
+        );
+        footer.push(Text::new(format!("Page {}/{}", page + 1, page_count.max(1))).into());
+        footer.push(
+            Button::new("Next")
+                .padding(8)
+                .on_press_maybe(if page + 1 < page_count {
+                    Some(MainMessage::ChangePage(page + 1).into())
+                } else {
+                    None
+                })
+                .into(),
+        );
+    }
+
+    if let Some(selected) = selected {
+        let export_message: Option<Message> = if selected.is_empty() {
+            None
+        } else {
+            Some(MainMessage::ExportSelected(selected.iter().copied().collect()).into())
+        };
+
+        footer.push(Space::with_width(Length::Fill).into());
+        if globals.get_db().is_some() && globals.get_user().is_some() {
+            let sync_message: Option<Message> = if selected.is_empty() {
+                None
+            } else {
+                Some(MainMessage::SyncToOnline(selected.iter().copied().collect()).into())
+            };
+
+            footer.push(
+                Button::new("Sync selected")
+                    .padding(8)
+                    .on_press_maybe(sync_message)
+                    .into(),
+            );
+        }
+        footer.push(
+            Button::new("Export selected")
+                .padding(8)
+                .on_press_maybe(export_message)
+                .into(),
+        );
+    }
+
+    Container::new(if footer.is_empty() {
+        Element::from(list)
+    } else {
+        Column::with_children(vec![
+            list.into(),
+            Row::with_children(footer)
+                .spacing(10.0)
+                .padding(15.0)
+                .align_items(Alignment::Center)
+                .into(),
+        ])
+        .into()
+    })
     .width(Length::Fill)
     .height(Length::Fill)
     .into()
@@ -323,17 +946,79 @@ pub fn drawings_tabs<'a>(
         |tab| MainMessage::SelectTab(tab).into(),
     )
     .selected(active_tab)
+    .describe("The drawings stored on this device and online")
     .width(Length::Fill)
     .height(Length::Fill)
     .into()
 }
 
+/// Displays the recently opened drawings.
+pub fn recent_drawings<'a>(globals: &Globals) -> Element<'a, Message, Theme, Renderer> {
+    let drawings = globals.get_recent_drawings();
+
+    if drawings.is_empty() {
+        return Space::with_height(Length::Shrink).into();
+    }
+
+    Column::with_children(vec![
+        Text::new("Recent").size(20).into(),
+        Scrollable::with_direction(
+            Row::with_children(drawings.iter().map(|(id, name, save_mode)| {
+                Button::new(
+                    Column::with_children(vec![
+                        globals.get_cache().get_element(
+                            *id,
+                            Size::new(Length::Fixed(100.0), Length::Fixed(75.0)),
+                            Size::new(Length::Fixed(100.0), Length::Fixed(75.0)),
+                            10.0,
+                        ),
+                        Text::new(name.clone())
+                            .size(14)
+                            .width(Length::Fixed(100.0))
+                            .horizontal_alignment(Horizontal::Center)
+                            .into(),
+                    ])
+                    .spacing(5.0)
+                    .align_items(Alignment::Center),
+                )
+                .style(iced::widget::button::secondary)
+                .on_press(Message::ChangeScene(Scenes::Drawing(Some(
+                    DrawingOptions::new(Some(*id), Some(name.clone()), Some(*save_mode)),
+                ))))
+                .padding(5.0)
+                .into()
+            }))
+            .spacing(10.0)
+            .padding([0.0, 0.0, 10.0, 0.0]),
+            Direction::Horizontal(Properties::default()),
+        )
+        .into(),
+    ])
+    .spacing(5.0)
+    .padding([10.0, 15.0, 0.0, 15.0])
+    .into()
+}
+
 pub fn display_drawings<'a>(
     title: Element<'a, Message, Theme, Renderer>,
+    filter_input: &str,
+    recent: Element<'a, Message, Theme, Renderer>,
     tabs: Element<'a, Message, Theme, Renderer>,
 ) -> Element<'a, Message, Theme, Renderer> {
+    let content = Column::with_children(vec![
+        recent,
+        Container::new(
+            TextInput::new("Search drawings...", filter_input)
+                .on_input(|value| MainMessage::UpdateDrawingFilter(value).into())
+                .padding(8),
+        )
+        .padding([10.0, 15.0, 0.0, 15.0])
+        .into(),
+        tabs,
+    ]);
+
     Centered::new(
-        Closeable::<Message, Theme, Renderer>::new(Card::new(title, tabs).content_padding(0.0))
+        Closeable::<Message, Theme, Renderer>::new(Card::new(title, content).content_padding(0.0))
             .height(Length::FillPortion(5))
             .width(Length::Fill)
             .style(theme::closeable::Closeable::Transparent)
@@ -350,7 +1035,27 @@ pub fn display_drawings<'a>(
 pub fn create_drawing<'a>(
     offline_button: Element<'a, Message, Theme, Renderer>,
     online_button: Element<'a, Message, Theme, Renderer>,
+    import_progress: Option<(usize, usize)>,
 ) -> Element<'a, Message, Theme, Renderer> {
+    let import_element: Element<'a, Message, Theme, Renderer> =
+        if let Some((done, total)) = import_progress {
+            WaitPanel::new(format!("Importing drawings... {}/{}", done, total))
+                .with_progress_ring(done as f32 / total.max(1) as f32)
+                .height(Length::Shrink)
+                .into()
+        } else {
+            Button::new(
+                Text::new("Import from archive")
+                    .width(Length::Fill)
+                    .horizontal_alignment(Horizontal::Center),
+            )
+            .padding(8)
+            .width(Length::Fill)
+            .style(iced::widget::button::secondary)
+            .on_press(MainMessage::ImportArchive.into())
+            .into()
+        };
+
     Closeable::<Message, Theme, Renderer>::new(
         Card::new(
             Text::new("Create new drawing"),
@@ -362,8 +1067,10 @@ pub fn create_drawing<'a>(
                     online_button,
                 ])
                 .into(),
+                Space::with_height(Length::Fill).into(),
+                import_element,
             ])
-            .height(Length::Fixed(150.0)),
+            .height(Length::Fixed(200.0)),
         )
         .width(Length::Fixed(300.0)),
     )
@@ -1,3 +1,5 @@
+pub mod app_settings;
+
 pub mod drawing;
 
 pub mod posts;
@@ -7,3 +9,11 @@ pub mod settings;
 pub mod main;
 
 pub mod auth;
+
+pub mod challenge;
+
+pub mod command_palette;
+
+pub mod debug;
+
+pub mod admin;
@@ -3,10 +3,10 @@ use std::sync::Arc;
 use iced::{
     advanced::widget::Text,
     widget::{
-        text_editor::Content, tooltip::Position, Button, Column, Container, Row, Scrollable, Space,
-        TextEditor, TextInput, Tooltip,
+        scrollable, text_editor::Content, tooltip::Position, Button, Column, Container, Image, Row,
+        Scrollable, Space, TextEditor, TextInput, Tooltip,
     },
-    Alignment, Element, Length, Pixels, Renderer, Size,
+    Alignment, Element, Length, Renderer, Size,
 };
 use image::{load_from_memory_with_format, ImageFormat};
 use mongodb::{bson::Uuid, Database};
@@ -17,7 +17,10 @@ use crate::{
     scenes::{
         data::{
             auth::{Role, User},
-            posts::{CommentMessage, ModalType, Post, PostList, PostTabs},
+            drawing::{PostData, UpdatePostData},
+            posts::{
+                Comment, CommentMessage, ModalType, Post, PostList, PostTabs, USER_SEARCH_PAGE_SIZE,
+            },
         },
         posts::PostsMessage,
     },
@@ -27,7 +30,9 @@ use crate::{
         icons::{Icon, ICON},
         theme::{self, Theme},
     },
-    widgets::{Card, Closeable, PostSummary, Rating, WaitPanel},
+    widgets::{
+        Avatar, Card, Close, Closeable, ComboBox, Grid, PostSummary, Rating, Skeleton, WaitPanel,
+    },
 };
 
 pub async fn delete_post(id: Uuid, globals: &Globals) -> Result<(), Error> {
@@ -94,16 +99,16 @@ pub fn image_profile_link<'a>(
     cache: &Cache,
 ) -> Element<'a, Message, Theme, Renderer> {
     Tooltip::new(
-        Button::new(cache.get_element(
-            if post.get_user().has_profile_picture() {
-                post.get_user().get_id()
-            } else {
-                Uuid::from_bytes([0; 16])
-            },
-            Size::new(Length::Fixed(50.0), Length::Fixed(50.0)),
-            Size::new(Length::Fixed(50.0), Length::Fixed(50.0)),
-            Some(Pixels(5.0)),
-        ))
+        Button::new(
+            Avatar::new(
+                post.get_user()
+                    .has_profile_picture()
+                    .then(|| cache.get_handle(post.get_user().get_id()))
+                    .flatten(),
+                post.get_user().get_username(),
+            )
+            .size(50.0),
+        )
         .on_press(
             PostsMessage::ToggleModal(ModalType::ShowingImage(post.get_user().get_id())).into(),
         )
@@ -114,8 +119,71 @@ pub fn image_profile_link<'a>(
     .into()
 }
 
-pub fn tag_profile_link<'a>(post: &'a Post) -> Element<'a, Message, Theme, Renderer> {
-    Tooltip::new(
+/// Displays a page of the users found by a search, as a grid of their profile pictures and
+/// usernames, followed by the buttons that change the page.
+pub fn user_search_results<'a>(
+    users: &Vec<User>,
+    page: usize,
+    cache: &Cache,
+) -> Element<'a, Message, Theme, Renderer> {
+    let results: Element<'a, Message, Theme, Renderer> = if users.is_empty() {
+        Text::new("No users found.").size(20.0).into()
+    } else {
+        Grid::new(users.iter().map(|user| {
+            Button::new(
+                Column::with_children(vec![
+                    Avatar::new(
+                        user.has_profile_picture()
+                            .then(|| cache.get_handle(user.get_id()))
+                            .flatten(),
+                        user.get_username(),
+                    )
+                    .size(100.0)
+                    .into(),
+                    Text::new(user.get_username().clone()).into(),
+                    Text::new(format!("@{}", user.get_user_tag()))
+                        .size(13.0)
+                        .style(theme::text::gray)
+                        .into(),
+                ])
+                .spacing(5.0)
+                .align_items(Alignment::Center),
+            )
+            .style(iced::widget::button::text)
+            .on_press(PostsMessage::OpenProfile(user.clone()).into())
+        }))
+        .into()
+    };
+
+    let previous = Button::new("Previous")
+        .on_press_maybe((page > 0).then(|| PostsMessage::LoadSearchPage(page - 1).into()));
+    let next = Button::new("Next").on_press_maybe(
+        (users.len() == USER_SEARCH_PAGE_SIZE)
+            .then(|| PostsMessage::LoadSearchPage(page + 1).into()),
+    );
+
+    Column::with_children(vec![
+        Scrollable::new(results).height(Length::Fill).into(),
+        Row::with_children(vec![
+            previous.into(),
+            Text::new(format!("Page {}", page + 1)).into(),
+            next.into(),
+        ])
+        .spacing(20.0)
+        .align_items(Alignment::Center)
+        .into(),
+    ])
+    .spacing(10.0)
+    .align_items(Alignment::Center)
+    .padding([0.0, 200.0])
+    .into()
+}
+
+pub fn tag_profile_link<'a>(
+    post: &'a Post,
+    user_id: Uuid,
+) -> Element<'a, Message, Theme, Renderer> {
+    let link = Tooltip::new(
         Button::new(
             Text::new(format!("@{}", post.get_user().get_user_tag()))
                 .size(15.0)
@@ -126,7 +194,51 @@ pub fn tag_profile_link<'a>(post: &'a Post) -> Element<'a, Message, Theme, Rende
         .on_press(PostsMessage::OpenProfile(post.get_user().clone()).into()),
         Text::new(format!("{}'s profile", post.get_user().get_user_tag())),
         Position::FollowCursor,
+    );
+
+    if post.get_user().get_id() == user_id {
+        return link.into();
+    }
+
+    Row::with_children(vec![link.into(), follow_button(post)])
+        .spacing(10.0)
+        .align_items(Alignment::Center)
+        .into()
+}
+
+/// Returns a button that follows or unfollows the author of the post.
+pub fn follow_button<'a>(post: &Post) -> Element<'a, Message, Theme, Renderer> {
+    Button::new(
+        Text::new(if post.is_followed() {
+            "Unfollow"
+        } else {
+            "Follow"
+        })
+        .size(13.0),
     )
+    .padding([2.0, 8.0])
+    .style(if post.is_followed() {
+        iced::widget::button::secondary
+    } else {
+        iced::widget::button::primary
+    })
+    .on_press(PostsMessage::ToggleFollow(post.get_user().clone()).into())
+    .into()
+}
+
+pub fn post_tags<'a>(post: &'a Post) -> Element<'a, Message, Theme, Renderer> {
+    Grid::new(post.get_tags().iter().map(|tag| {
+        Button::new(
+            Container::new(Text::new(tag.clone()).style(theme::text::dark))
+                .style(theme::container::badge)
+                .padding(10.0),
+        )
+        .on_press(PostsMessage::FilterByTag(tag.clone()).into())
+        .style(iced::widget::button::text)
+        .padding(0.0)
+    }))
+    .padding(0.0)
+    .spacing(5.0)
     .into()
 }
 
@@ -172,11 +284,123 @@ pub fn delete_button<'a>(
     }
 }
 
+/// Displays the average rating given to the post by the other users.
+pub fn average_rating<'a>(post: &Post) -> Element<'a, Message, Theme, Renderer> {
+    let count = post.get_rating_count();
+
+    Row::with_children(vec![
+        Text::new(Icon::StarFull.to_string())
+            .font(ICON)
+            .style(theme::text::gray)
+            .into(),
+        Text::new(format!(
+            "{:.1} ({} {})",
+            post.get_avg_rating(),
+            count,
+            if count == 1 { "rating" } else { "ratings" }
+        ))
+        .style(theme::text::gray)
+        .into(),
+    ])
+    .spacing(5.0)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+pub fn like_button<'a>(post: &Post) -> Element<'a, Message, Theme, Renderer> {
+    Tooltip::new(
+        Button::new(
+            Text::new(if post.is_liked() {
+                Icon::Heart.to_string()
+            } else {
+                Icon::HeartEmpty.to_string()
+            })
+            .font(ICON)
+            .style(theme::text::danger)
+            .size(30),
+        )
+        .on_press(PostsMessage::ToggleLike(post.get_id()).into())
+        .padding(0.0)
+        .style(iced::widget::button::text),
+        Text::new(if post.is_liked() { "Unlike" } else { "Like" }),
+        Position::FollowCursor,
+    )
+    .into()
+}
+
+pub fn share_button<'a>(post: &Post) -> Element<'a, Message, Theme, Renderer> {
+    Tooltip::new(
+        Button::new(Text::new(Icon::Copy.to_string()).font(ICON).size(30))
+            .on_press(PostsMessage::SharePost(post.get_id()).into())
+            .padding(0.0)
+            .style(iced::widget::button::text),
+        Text::new("Copy link"),
+        Position::FollowCursor,
+    )
+    .into()
+}
+
+pub fn edit_button<'a>(post: &Post, user_id: Uuid) -> Element<'a, Message, Theme, Renderer> {
+    if user_id == post.get_user().get_id() {
+        Tooltip::new(
+            Button::new(Text::new(Icon::Edit.to_string()).font(ICON).size(30))
+                .on_press(PostsMessage::EditPost(post.get_id()).into())
+                .padding(0.0)
+                .style(iced::widget::button::text),
+            Text::new("Edit post"),
+            Position::FollowCursor,
+        )
+        .into()
+    } else {
+        Space::with_height(Length::Shrink).into()
+    }
+}
+
+/// Returns the image of a post in a list, or a [Skeleton] while it is loading. Trending posts with
+/// a velocity above the threshold are marked with a badge above their top-right corner.
+fn thumbnail<'a>(
+    post: &Post,
+    cache: &Cache,
+    tab: PostTabs,
+    hot_threshold: f64,
+    shimmer_phase: f32,
+) -> Element<'a, Message, Theme, Renderer> {
+    let image: Element<'a, Message, Theme, Renderer> = match cache.get_handle(post.get_id()) {
+        Some(handle) => Image::new(handle).into(),
+        None => Skeleton::new(shimmer_phase)
+            .width(Length::Fixed(800.0))
+            .height(Length::Fixed(600.0))
+            .into(),
+    };
+
+    if tab != PostTabs::Trending || post.get_velocity() <= hot_threshold {
+        return image;
+    }
+
+    Column::with_children(vec![
+        Container::new(Text::new("🔥 Hot").style(theme::text::dark))
+            .style(theme::container::badge)
+            .padding([2.0, 8.0])
+            .into(),
+        image,
+    ])
+    .spacing(5.0)
+    .align_items(Alignment::End)
+    .into()
+}
+
+/// Returns the id of the scrollable list of posts of the given tab.
+pub fn post_list_id(tab: PostTabs) -> scrollable::Id {
+    scrollable::Id::new(format!("posts_{}", tab as u8))
+}
+
 pub fn generate_post_list<'a>(
     tab: PostTabs,
     list: &'a PostList,
     user: &User,
     cache: Cache,
+    hot_threshold: f64,
+    shimmer_phase: f32,
 ) -> Container<'a, Message, Theme, Renderer> {
     let user_id = user.get_id();
     let user_role = user.get_role();
@@ -191,27 +415,28 @@ pub fn generate_post_list<'a>(
                             Row::with_children(vec![
                                 image_profile_link(post, &cache),
                                 Column::with_children(vec![
-                                    tag_profile_link(post),
+                                    tag_profile_link(post, user_id),
                                     Text::new(post.get_user().get_username()).size(20.0).into(),
                                     Text::new(post.get_description().clone()).into(),
+                                    post_tags(post),
+                                    average_rating(post),
                                 ])
                                 .into(),
                                 Space::with_width(Length::Fill).into(),
                                 Column::with_children(vec![
+                                    like_button(post),
+                                    share_button(post),
                                     report_button(index),
+                                    edit_button(post, user_id),
                                     delete_button(post, user_id, user_role),
                                 ])
                                 .into(),
                             ])
                             .spacing(10.0),
-                            cache.get_element(
-                                post.get_id(),
-                                Size::new(Length::Shrink, Length::Shrink),
-                                Size::new(Length::Fixed(800.0), Length::Fixed(600.0)),
-                                None,
-                            ),
+                            thumbnail(post, &cache, tab, hot_threshold, shimmer_phase),
                         )
                         .padding(40)
+                        .describe(format!("Post by {}", post.get_user().get_username()))
                         .on_click_image(Into::<Message>::into(PostsMessage::ToggleModal(
                             ModalType::ShowingImage(post.get_id()),
                         )))
@@ -226,12 +451,9 @@ pub fn generate_post_list<'a>(
             .align_items(Alignment::Center)
             .spacing(50),
         )
+        .id(post_list_id(tab))
         .on_scroll(move |viewport| {
-            if viewport.relative_offset().y == 1.0 && !list.done_loading() {
-                Some(PostsMessage::LoadBatch(tab).into())
-            } else {
-                None
-            }
+            Some(PostsMessage::Scrolled(tab, viewport.relative_offset().y).into())
         })
         .width(Length::Fill),
     )
@@ -255,31 +477,182 @@ pub fn generate_show_image<'a>(id: Uuid, cache: Cache) -> Element<'a, Message, T
     .into()
 }
 
-fn comment_input<'a>(post: &'a Post, post_index: usize) -> Column<'a, Message, Theme, Renderer> {
-    Column::with_children(vec![Row::with_children(vec![
-        TextInput::new("Write comment here...", &*post.get_comment_input())
-            .width(Length::Fill)
-            .on_input(move |value| {
-                CommentMessage::UpdateInput {
-                    post: post_index,
-                    position: None,
-                    input: value,
-                }
-                .into()
-            })
+/// Returns the list of users that can be mentioned in the given comment input.
+fn mention_suggestions<'a>(
+    post: &'a Post,
+    post_index: usize,
+    position: Option<(usize, usize)>,
+) -> Element<'a, Message, Theme, Renderer> {
+    Column::with_children(post.get_mention_suggestions(position).iter().map(|user| {
+        Button::new(
+            Text::new(format!(
+                "@{} ({})",
+                user.get_user_tag(),
+                user.get_username()
+            ))
+            .size(15.0),
+        )
+        .style(iced::widget::button::text)
+        .padding(2.0)
+        .on_press(
+            CommentMessage::Mention {
+                post: post_index,
+                position,
+                user: user.clone(),
+            }
             .into(),
-        Button::new(Text::new(Icon::Submit.to_string()).font(ICON))
-            .on_press(
-                CommentMessage::Add {
-                    post: post_index,
-                    parent: None,
+        )
+        .into()
+    }))
+    .into()
+}
+
+/// Returns the content of the comment, where the mentions of users link to their profiles.
+fn comment_content<'a>(comment: &'a Comment) -> Element<'a, Message, Theme, Renderer> {
+    if comment.get_mentions().is_empty() {
+        return Text::new(comment.get_content().clone()).into();
+    }
+
+    let mut content = Row::new().align_items(Alignment::Center);
+    let mut text = String::new();
+
+    for word in comment.get_content().split(' ') {
+        let mentioned = word.strip_prefix('@').and_then(|tag| {
+            comment
+                .get_mentions()
+                .iter()
+                .find(|user| user.get_user_tag() == tag)
+        });
+
+        match mentioned {
+            Some(user) => {
+                if !text.is_empty() {
+                    content = content.push(Text::new(text.clone()));
+                    text.clear();
                 }
+
+                content = content.push(
+                    Button::new(Text::new(word.to_string()).style(theme::text::gray))
+                        .style(iced::widget::button::text)
+                        .padding(0.0)
+                        .on_press(PostsMessage::OpenProfile(user.clone()).into()),
+                );
+                text.push(' ');
+            }
+            None => {
+                text.push_str(word);
+                text.push(' ');
+            }
+        }
+    }
+
+    if !text.trim().is_empty() {
+        content = content.push(Text::new(text));
+    }
+
+    content.into()
+}
+
+fn comment_input<'a>(post: &'a Post, post_index: usize) -> Column<'a, Message, Theme, Renderer> {
+    Column::with_children(vec![
+        Row::with_children(vec![
+            TextInput::new("Write comment here...", &*post.get_comment_input())
+                .width(Length::Fill)
+                .on_input(move |value| {
+                    CommentMessage::UpdateInput {
+                        post: post_index,
+                        position: None,
+                        input: value,
+                    }
+                    .into()
+                })
                 .into(),
-            )
-            .style(iced::widget::button::text)
-            .into(),
+            Button::new(Text::new(Icon::Submit.to_string()).font(ICON))
+                .on_press(
+                    CommentMessage::Add {
+                        post: post_index,
+                        parent: None,
+                    }
+                    .into(),
+                )
+                .style(iced::widget::button::text)
+                .into(),
+        ])
+        .into(),
+        mention_suggestions(post, post_index, None),
     ])
-    .into()])
+}
+
+fn comment_body<'a>(
+    comment: &'a Comment,
+    post_index: usize,
+    position: (usize, usize),
+    user_id: Uuid,
+) -> Element<'a, Message, Theme, Renderer> {
+    let content: Element<'a, Message, Theme, Renderer> =
+        if let Some(edit_input) = comment.get_edit_input() {
+            TextInput::new("Edit comment...", edit_input)
+                .on_input(move |value| {
+                    CommentMessage::UpdateEditInput {
+                        post: post_index,
+                        position,
+                        input: value,
+                    }
+                    .into()
+                })
+                .on_submit(
+                    CommentMessage::Edit {
+                        post: post_index,
+                        position,
+                        new_content: edit_input.clone(),
+                    }
+                    .into(),
+                )
+                .into()
+        } else {
+            comment_content(comment)
+        };
+
+    let mut header = Row::with_children(vec![Text::new(comment.get_user().get_username().clone())
+        .size(17.0)
+        .into()])
+    .spacing(5.0)
+    .align_items(Alignment::Center);
+
+    if comment.get_user().get_id() == user_id {
+        header = header
+            .push(
+                Button::new(Text::new(Icon::Edit.to_string()).font(ICON).size(15.0))
+                    .style(iced::widget::button::text)
+                    .padding(0.0)
+                    .on_press(
+                        CommentMessage::ToggleEdit {
+                            post: post_index,
+                            position,
+                        }
+                        .into(),
+                    ),
+            )
+            .push(
+                Button::new(
+                    Text::new(Icon::Trash.to_string())
+                        .font(ICON)
+                        .size(15.0)
+                        .style(theme::text::danger),
+                )
+                .style(iced::widget::button::text)
+                .padding(0.0)
+                .on_press(
+                    CommentMessage::Delete {
+                        post: post_index,
+                        position,
+                    }
+                    .into(),
+                ),
+            );
+    }
+
+    Column::with_children(vec![header.into(), content]).into()
 }
 
 fn comment_with_children<'a>(
@@ -287,6 +660,7 @@ fn comment_with_children<'a>(
     post_index: usize,
     line: usize,
     index: usize,
+    user_id: Uuid,
 ) -> Element<'a, Message, Theme, Renderer> {
     Into::<Element<Message, Theme, Renderer>>::into(
         Row::with_children(vec![
@@ -301,15 +675,12 @@ fn comment_with_children<'a>(
                 )
                 .into(),
             Column::with_children(vec![
-                Text::new(
-                    post.get_comments()[line][index]
-                        .get_user()
-                        .get_username()
-                        .clone(),
-                )
-                .size(17.0)
-                .into(),
-                Text::new(post.get_comments()[line][index].get_content().clone()).into(),
+                comment_body(
+                    &post.get_comments()[line][index],
+                    post_index,
+                    (line, index),
+                    user_id,
+                ),
                 Row::with_children(vec![
                     TextInput::new(
                         "Write reply here...",
@@ -336,6 +707,7 @@ fn comment_with_children<'a>(
                         .into(),
                 ])
                 .into(),
+                mention_suggestions(post, post_index, Some((line, index))),
             ])
             .into(),
         ])
@@ -348,11 +720,29 @@ fn comment_without_children<'a>(
     post: &'a Post,
     post_index: usize,
     line: usize,
+    parent: Option<(usize, usize)>,
+    list: &PostList,
+    user_id: Uuid,
 ) -> Element<'a, Message, Theme, Renderer> {
+    let load_more = list.has_more_comments(post_index, parent).then(|| {
+        Button::new("Load more")
+            .on_press(
+                CommentMessage::Load {
+                    post: post_index,
+                    parent,
+                    after: list.get_comment_cursor(post_index, parent),
+                }
+                .into(),
+            )
+            .style(iced::widget::button::text)
+            .into()
+    });
+
     Column::with_children(
         post.get_comments()[line]
             .iter()
             .zip(0..post.get_comments()[line].len())
+            .filter(|(comment, _)| !comment.is_deleted())
             .map(|(comment, index)| {
                 Row::with_children(vec![
                     Button::new(Text::new(Icon::Right.to_string()).font(ICON))
@@ -365,18 +755,13 @@ fn comment_without_children<'a>(
                             .into(),
                         )
                         .into(),
-                    Column::with_children(vec![
-                        Text::new(comment.get_user().get_username().clone())
-                            .size(17.0)
-                            .into(),
-                        Text::new(comment.get_content().clone()).into(),
-                    ])
-                    .into(),
+                    comment_body(comment, post_index, (line, index), user_id),
                 ])
                 .spacing(10.0)
                 .align_items(Alignment::Center)
                 .into()
-            }),
+            })
+            .chain(load_more),
     )
     .spacing(10.0)
     .into()
@@ -385,6 +770,8 @@ fn comment_without_children<'a>(
 pub fn generate_comment_chain<'a>(
     post: &'a Post,
     post_index: usize,
+    list: &PostList,
+    user_id: Uuid,
 ) -> Element<'a, Message, Theme, Renderer> {
     let mut comment_chain = comment_input(post, post_index);
 
@@ -394,10 +781,13 @@ pub fn generate_comment_chain<'a>(
         Err(0usize)
     };
 
+    let mut parent = None;
     let mut done = false;
     while !done {
         comment_chain = comment_chain.push(match position {
             Ok((line, index)) => {
+                parent = Some((line, index));
+
                 position =
                     if let Some(reply_index) = post.get_comments()[line][index].get_open_reply() {
                         Ok((
@@ -410,7 +800,7 @@ pub fn generate_comment_chain<'a>(
                             .unwrap_or(post.get_comments().len()))
                     };
 
-                comment_with_children(post, post_index, line, index)
+                comment_with_children(post, post_index, line, index, user_id)
             }
             Err(line) => {
                 done = true;
@@ -421,7 +811,7 @@ pub fn generate_comment_chain<'a>(
                         .height(Length::Fill)
                         .into()
                 } else {
-                    comment_without_children(post, post_index, line)
+                    comment_without_children(post, post_index, line, parent, list, user_id)
                 }
             }
         });
@@ -433,9 +823,11 @@ pub fn generate_comment_chain<'a>(
 pub fn generate_show_post<'a>(
     post: &'a Post,
     post_index: usize,
+    list: &PostList,
+    user_id: Uuid,
     cache: &Cache,
 ) -> Element<'a, Message, Theme, Renderer> {
-    let comment_chain = generate_comment_chain(post, post_index);
+    let comment_chain = generate_comment_chain(post, post_index, list, user_id);
 
     Row::with_children(vec![
         Closeable::new(cache.get_element(
@@ -456,28 +848,39 @@ pub fn generate_show_post<'a>(
                 Row::with_children(vec![
                     image_profile_link(post, cache),
                     Column::with_children(vec![
-                        tag_profile_link(post),
+                        tag_profile_link(post, user_id),
                         Text::new(post.get_user().get_username()).size(20.0).into(),
                         Text::new(post.get_description().clone()).into(),
+                        post_tags(post),
                     ])
                     .spacing(5.0)
                     .into(),
                 ])
                 .into(),
-                Rating::new()
-                    .on_rate(move |value| {
-                        PostsMessage::RatePost {
-                            post_index: post_index.clone(),
-                            rating: value,
-                        }
-                        .into()
-                    })
-                    .on_unrate(Into::<Message>::into(PostsMessage::RatePost {
-                        post_index,
-                        rating: 0,
-                    }))
-                    .value(*post.get_rating())
-                    .into(),
+                Row::with_children(vec![
+                    Rating::new()
+                        .half_stars(true)
+                        .on_rate(move |value| {
+                            PostsMessage::RatePost {
+                                post_index: post_index.clone(),
+                                rating: value,
+                            }
+                            .into()
+                        })
+                        .on_unrate(Into::<Message>::into(PostsMessage::RatePost {
+                            post_index,
+                            rating: 0.0,
+                        }))
+                        .value(*post.get_rating())
+                        .describe("Rate the post")
+                        .into(),
+                    average_rating(post),
+                    like_button(post),
+                    share_button(post),
+                ])
+                .spacing(10.0)
+                .align_items(Alignment::Center)
+                .into(),
                 comment_chain,
             ])
             .spacing(5.0),
@@ -530,3 +933,89 @@ pub fn generate_show_report<'a>(
     .style(theme::closeable::Closeable::Transparent)
     .into()
 }
+
+pub fn generate_edit_post<'a>(
+    post_id: Uuid,
+    edit_data: &'a PostData,
+) -> Element<'a, Message, Theme, Renderer> {
+    Closeable::new(
+        Card::new(
+            Text::new("Edit post").size(20.0),
+            Column::with_children(vec![
+                Text::new("Description:").into(),
+                TextEditor::new(edit_data.get_description())
+                    .on_action(|action| {
+                        PostsMessage::UpdateEditData(UpdatePostData::Description(action)).into()
+                    })
+                    .into(),
+                Text::new("Tags:").into(),
+                Grid::new(
+                    edit_data
+                        .get_post_tags()
+                        .iter()
+                        .enumerate()
+                        .map(|(index, tag)| {
+                            Container::new(
+                                Row::with_children(vec![
+                                    Text::new(tag.get_name().clone())
+                                        .style(theme::text::dark)
+                                        .into(),
+                                    Close::new(Into::<Message>::into(
+                                        PostsMessage::UpdateEditData(UpdatePostData::RemoveTag(
+                                            index,
+                                        )),
+                                    ))
+                                    .size(15.0)
+                                    .into(),
+                                ])
+                                .spacing(5.0)
+                                .align_items(Alignment::Center),
+                            )
+                            .style(theme::container::badge)
+                            .padding(10.0)
+                        }),
+                )
+                .padding(0.0)
+                .spacing(5.0)
+                .into(),
+                Row::with_children(vec![
+                    ComboBox::new(
+                        edit_data.get_all_tags().clone(),
+                        "Add a new tag...",
+                        &*edit_data.get_tag_input(),
+                        |tag| PostsMessage::UpdateEditData(UpdatePostData::SelectedTag(tag)).into(),
+                    )
+                    .width(Length::Fill)
+                    .on_input(|new_value| {
+                        PostsMessage::UpdateEditData(UpdatePostData::TagInput(new_value)).into()
+                    })
+                    .describe("Add a tag to the post")
+                    .into(),
+                    Button::new(Text::new(Icon::Add.to_string()).size(30).font(ICON))
+                        .on_press(
+                            PostsMessage::UpdateEditData(UpdatePostData::NewTag(
+                                edit_data.get_tag_input().clone(),
+                            ))
+                            .into(),
+                        )
+                        .style(iced::widget::button::text)
+                        .padding(0)
+                        .into(),
+                ])
+                .align_items(Alignment::Center)
+                .spacing(10)
+                .into(),
+            ])
+            .spacing(10.0)
+            .height(Length::Shrink),
+        )
+        .footer(Button::new("Save").on_press(PostsMessage::SubmitEdit(post_id).into()))
+        .width(Length::Fixed(300.0)),
+    )
+    .style(theme::closeable::Closeable::Transparent)
+    .on_close(
+        Into::<Message>::into(PostsMessage::ToggleModal(ModalType::EditingPost(post_id))),
+        25.0,
+    )
+    .into()
+}
@@ -99,6 +99,62 @@ pub async fn set_user_image(
     }
 }
 
+/// Displays the email input. Once a verification code has been sent to the new address, an
+/// input for the code is displayed as well.
+pub fn email_input<'a>(
+    email: String,
+    field_value: String,
+    code_value: Option<String>,
+) -> Element<'a, Message, Theme, Renderer> {
+    let mut column = Column::with_children(vec![
+        Text::new("Email").size(20.0).into(),
+        Row::with_children(vec![
+            TextInput::new("Input email...", &*field_value.clone())
+                .on_input(|value| SettingsMessage::UpdateEmailField(value).into())
+                .size(15.0)
+                .into(),
+            Space::with_width(Length::Fill).into(),
+            if field_value == email || code_value.is_some() {
+                Button::new(Text::new("Update").size(15.0))
+            } else {
+                Button::new(Text::new("Update").size(15.0))
+                    .on_press(SettingsMessage::RequestEmailChange.into())
+            }
+            .into(),
+        ])
+        .spacing(5.0)
+        .into(),
+    ])
+    .width(Length::Fill)
+    .spacing(5.0);
+
+    if let Some(code) = code_value {
+        column = column.push(
+            Row::with_children(vec![
+                TextInput::new("Input the code sent to the new email...", &*code)
+                    .on_input(|value| SettingsMessage::UpdateEmailCodeField(value).into())
+                    .on_submit(SettingsMessage::VerifyEmailChange(code.clone()).into())
+                    .size(15.0)
+                    .into(),
+                Space::with_width(Length::Fill).into(),
+                Button::new(Text::new("Verify").size(15.0))
+                    .on_press(SettingsMessage::VerifyEmailChange(code).into())
+                    .into(),
+            ])
+            .spacing(5.0),
+        );
+    }
+
+    column.into()
+}
+
+pub fn email_error<'a>(error: Error) -> Element<'a, Message, Theme, Renderer> {
+    Text::new(error.to_string())
+        .style(theme::text::danger)
+        .size(15.0)
+        .into()
+}
+
 pub fn username_input<'a>(
     username: String,
     field_value: String,
@@ -1,5 +1,6 @@
 use crate::database;
 use crate::scene::{Globals, Message, Scene, SceneMessage};
+use crate::scenes::auth::AuthOptions;
 use crate::scenes::data::auth::User;
 use crate::scenes::scenes::Scenes;
 use crate::utils::errors::{AuthError, Error};
@@ -16,6 +17,13 @@ use super::services;
 
 /// The struct for the settings [Scene].
 pub struct Settings {
+    /// The current user input in the email TextInput.
+    email_input: String,
+
+    /// The current user input in the email verification code TextInput. Is set once a code has
+    /// been sent to the new email address.
+    email_code_input: Option<String>,
+
     /// The current user input in the username TextInput.
     username_input: String,
 
@@ -48,6 +56,18 @@ pub struct SettingsOptions {}
 /// The possible [messages](SceneMessage) this [Scene] can trigger.
 #[derive(Clone)]
 pub enum SettingsMessage {
+    /// When the email TextInput field is modified.
+    UpdateEmailField(String),
+
+    /// Email update request. Sends a verification code to the new address.
+    RequestEmailChange,
+
+    /// When the email verification code TextInput field is modified.
+    UpdateEmailCodeField(String),
+
+    /// Confirms the email update with the given verification code.
+    VerifyEmailChange(String),
+
     /// When the username TextInput field is modified.
     UpdateUsernameField(String),
 
@@ -96,6 +116,10 @@ impl SceneMessage for SettingsMessage {
 
     fn get_name(&self) -> String {
         match self {
+            Self::UpdateEmailField(_) => String::from("Update email field"),
+            Self::RequestEmailChange => String::from("Request email change"),
+            Self::UpdateEmailCodeField(_) => String::from("Update email code field"),
+            Self::VerifyEmailChange(_) => String::from("Verify email change"),
             Self::UpdateUsernameField(_) => String::from("Update username field"),
             Self::UpdateUsername => String::from("Update username"),
             Self::UpdateUserTagField(_) => String::from("Update user tag field"),
@@ -124,6 +148,62 @@ impl Into<Message> for SettingsMessage {
 }
 
 impl Settings {
+    fn request_email_change(&mut self, globals: &mut Globals) -> Command<Message> {
+        if !User::check_email(&self.email_input) {
+            self.input_error = Some(Error::AuthError(AuthError::RegisterBadCredentials {
+                email: true,
+                username: false,
+                password: false,
+            }));
+
+            return Command::none();
+        }
+
+        let email = self.email_input.clone();
+        let code = User::gen_register_code();
+        let db = globals.get_db().unwrap();
+        let user = globals.get_user().unwrap().clone();
+        self.input_error = None;
+        self.email_code_input = Some(String::new());
+
+        Command::perform(
+            async move {
+                database::settings::request_email_change(
+                    &db,
+                    user.get_id(),
+                    email.clone(),
+                    code.clone(),
+                )
+                .await
+                .map(|()| user.gen_email_change_email(&email, &code))
+            },
+            |result| match result {
+                Ok(mail) => Message::SendSmtpMail(mail),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
+    fn verify_email_change(&mut self, code: &String, globals: &mut Globals) -> Command<Message> {
+        let code = code.clone();
+        let db = globals.get_db().unwrap();
+        let user_id = globals.get_user().unwrap().get_id();
+        self.input_error = None;
+
+        Command::perform(
+            async move { database::settings::verify_email_change(&db, user_id, code).await },
+            |result| match result {
+                Ok(email) => SettingsMessage::DoneUpdate(Arc::new(move |settings, globals| {
+                    settings.email_input = email.clone();
+                    settings.email_code_input = None;
+                    globals.get_user_mut().unwrap().set_email(email.clone());
+                }))
+                .into(),
+                Err(err) => Message::Error(err),
+            },
+        )
+    }
+
     fn update_username(&mut self, globals: &mut Globals) -> Command<Message> {
         if !User::check_username(&self.username_input) {
             self.input_error = Some(Error::AuthError(AuthError::RegisterBadCredentials {
@@ -272,6 +352,8 @@ impl Scene for Settings {
         let user = globals.get_user().unwrap().clone();
 
         let mut settings = Self {
+            email_input: user.get_email().clone(),
+            email_code_input: None,
             username_input: user.get_username().clone(),
             user_tag_input: user.get_user_tag().clone(),
             password_input: String::from(""),
@@ -306,6 +388,19 @@ impl Scene for Settings {
 
     fn update(&mut self, globals: &mut Globals, message: &Self::Message) -> Command<Message> {
         match message {
+            SettingsMessage::UpdateEmailField(email) => {
+                self.email_input = email.clone();
+                self.email_code_input = None;
+
+                Command::none()
+            }
+            SettingsMessage::RequestEmailChange => self.request_email_change(globals),
+            SettingsMessage::UpdateEmailCodeField(code) => {
+                self.email_code_input = Some(code.clone());
+
+                Command::none()
+            }
+            SettingsMessage::VerifyEmailChange(code) => self.verify_email_change(code, globals),
             SettingsMessage::UpdateUsernameField(username) => {
                 self.username_input = username.clone();
                 Command::none()
@@ -360,6 +455,9 @@ impl Scene for Settings {
                 Command::none()
             }
             SettingsMessage::Error(err) => {
+                if *err == Error::AuthError(AuthError::RegisterUserAlreadyExists) {
+                    self.email_code_input = None;
+                }
                 self.input_error = Some(err.clone());
 
                 Command::none()
@@ -368,22 +466,37 @@ impl Scene for Settings {
     }
 
     fn view(&self, globals: &Globals) -> Element<'_, Message, Theme, Renderer> {
-        let (username_error, password_error) =
+        let (email_error, username_error, password_error) =
             if let Some(Error::AuthError(AuthError::RegisterBadCredentials {
-                email: _,
+                email,
                 username,
                 password,
             })) = self.input_error
             {
-                (username, password)
+                (email, username, password)
             } else {
-                (false, false)
+                (false, false, false)
             };
 
         let title = self.title_element();
 
         let user = globals.get_user().unwrap();
 
+        let email = services::settings::email_input(
+            user.get_email().clone(),
+            self.email_input.clone(),
+            self.email_code_input.clone(),
+        );
+
+        let email_error = if email_error
+            || Some(Error::AuthError(AuthError::RegisterUserAlreadyExists)) == self.input_error
+            || Some(Error::AuthError(AuthError::RegisterBadCode)) == self.input_error
+        {
+            services::settings::email_error(self.input_error.clone().unwrap())
+        } else {
+            Space::with_width(Length::Fill).into()
+        };
+
         let username = services::settings::username_input(
             user.get_username().clone(),
             self.username_input.clone(),
@@ -436,6 +549,16 @@ impl Scene for Settings {
                 Space::with_width(Length::Fill).into()
             };
 
+        let two_factor = if user.has_totp() {
+            Text::new("Two-factor authentication is enabled.").into()
+        } else {
+            Button::new("Enable two-factor authentication")
+                .on_press(Message::ChangeScene(Scenes::Auth(Some(
+                    AuthOptions::enable_totp(),
+                ))))
+                .into()
+        };
+
         let delete_account = Button::new("Delete account")
             .style(iced::widget::button::danger)
             .on_press(SettingsMessage::DeleteAccount.into())
@@ -446,10 +569,12 @@ impl Scene for Settings {
             Scrollable::new(Row::with_children(vec![
                 Space::with_width(Length::FillPortion(1)).into(),
                 Column::with_children(vec![
+                    Column::with_children(vec![email, email_error]).into(),
                     Column::with_children(vec![username, username_error]).into(),
                     Column::with_children(vec![user_tag, user_tag_error]).into(),
                     Column::with_children(vec![password, password_error]).into(),
                     Column::with_children(vec![profile_picture, profile_picture_error]).into(),
+                    two_factor,
                     delete_account,
                 ])
                 .spacing(20.0)
@@ -1,21 +1,29 @@
-use std::{future::Future, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
 
 use iced::{
-    widget::{image::Handle, Container, Image},
+    futures::channel::oneshot,
+    widget::{image::Handle, Container, Image, Text},
     Command, Element, Length, Pixels, Renderer, Size,
 };
 use image::{DynamicImage, RgbaImage};
-use moka;
 use mongodb::bson::Uuid;
+use tokio::task::JoinHandle;
 
 use crate::{
-    debug_message,
     scene::Message,
     widgets::{wait_panel::Appearance, WaitPanel},
 };
 
 use super::{errors::Error, theme::Theme};
 
+/// The default maximum number of images kept in the [Cache].
+pub const DEFAULT_MAX_ENTRIES: usize = 200;
+
 /// An image represented by pixel data.
 #[derive(Debug, Clone)]
 pub struct PixelImage {
@@ -64,45 +72,182 @@ impl Into<DynamicImage> for PixelImage {
     }
 }
 
+/// Counters describing how the [Cache] has been used; useful for debugging.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of lookups that found their image.
+    pub hits: u64,
+
+    /// The number of lookups that did not find their image.
+    pub misses: u64,
+
+    /// The number of images removed to make room for new ones.
+    pub evictions: u64,
+}
+
+/// The usage data shared by all the clones of a [Cache].
+#[derive(Debug, Default)]
+struct CacheUsage {
+    /// The moment each cached image was last accessed.
+    accessed: HashMap<Uuid, Instant>,
+
+    /// The usage counters.
+    stats: CacheStats,
+}
+
+impl CacheUsage {
+    /// Marks the image with the given id as accessed now, and returns the ids of the least
+    /// recently accessed images that no longer fit in the given number of entries.
+    fn touch(&mut self, id: Uuid, max_entries: usize) -> Vec<Uuid> {
+        self.accessed.insert(id, Instant::now());
+
+        let mut evicted = vec![];
+        while self.accessed.len() > max_entries {
+            let Some(oldest) = self
+                .accessed
+                .iter()
+                .min_by_key(|(_, accessed)| **accessed)
+                .map(|(id, _)| *id)
+            else {
+                break;
+            };
+
+            self.accessed.remove(&oldest);
+            self.stats.evictions += 1;
+            evicted.push(oldest);
+        }
+
+        evicted
+    }
+}
+
+/// The loading state of an image in the [Cache].
+#[derive(Debug)]
+pub enum CacheEntry {
+    /// The image is being loaded by the given task.
+    Loading(JoinHandle<Result<Arc<PixelImage>, Error>>),
+
+    /// The image has been loaded.
+    Loaded(Arc<PixelImage>),
+
+    /// The image could not be loaded.
+    Failed(Error),
+}
+
 #[derive(Debug, Clone)]
 pub struct Cache {
-    cache_sync: moka::sync::Cache<Uuid, Arc<PixelImage>>,
-    cache_async: moka::future::Cache<Uuid, Arc<PixelImage>>,
+    /// The images in the [Cache], by id, in their current loading state.
+    entries: Arc<Mutex<HashMap<Uuid, CacheEntry>>>,
+
+    /// The maximum number of images kept in the [Cache]. When it is exceeded, the least
+    /// recently accessed images are evicted.
+    max_entries: usize,
+
+    /// The access times and the usage counters.
+    usage: Arc<Mutex<CacheUsage>>,
 }
 
 impl Cache {
     pub fn new() -> Self {
         Cache {
-            cache_async: moka::future::Cache::builder()
-                .time_to_idle(Duration::from_secs(60 * 60))
-                .max_capacity(500 * 1024 * 1024)
-                .build(),
-            cache_sync: moka::sync::Cache::builder()
-                .time_to_idle(Duration::from_secs(5 * 60))
-                .max_capacity(50 * 1024 * 1024)
-                .build(),
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            usage: Arc::new(Mutex::new(CacheUsage::default())),
         }
     }
 
-    /// Uploads image into cache.
-    pub async fn insert(&self, id: Uuid, image: Arc<PixelImage>) -> Result<(), Error> {
-        let cache_sync = self.cache_sync.clone();
-        let cache_async = self.cache_async.clone();
+    /// Sets the maximum number of images kept in the [Cache].
+    pub fn with_capacity(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
 
-        cache_async.insert(id, Arc::clone(&image)).await;
+        self
+    }
 
-        tokio::task::spawn_blocking(move || {
-            cache_sync.insert(id, Arc::clone(&image));
+    /// Returns the usage counters of the [Cache].
+    pub fn stats(&self) -> CacheStats {
+        self.usage
+            .lock()
+            .map(|usage| usage.stats)
+            .unwrap_or_default()
+    }
 
-            ()
-        })
-        .await
-        .map_err(|err| debug_message!("{}", err).into())?;
+    /// Returns the number of bytes taken by the pixel data of the loaded images.
+    pub fn memory_usage_bytes(&self) -> usize {
+        self.lock_entries()
+            .values()
+            .map(|entry| match entry {
+                CacheEntry::Loaded(pixels) => {
+                    pixels.get_width() as usize * pixels.get_height() as usize * 4
+                }
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Removes all images from the [Cache], aborting the ones that are still loading. The usage
+    /// counters are kept.
+    pub fn clear(&self) {
+        let mut entries = self.lock_entries();
+        for (_, entry) in entries.drain() {
+            if let CacheEntry::Loading(handle) = entry {
+                handle.abort();
+            }
+        }
+
+        if let Ok(mut usage) = self.usage.lock() {
+            usage.accessed.clear();
+        }
+    }
+
+    /// Locks the entries of the [Cache]. A poisoned lock is recovered, since the entries are
+    /// always left in a valid state.
+    fn lock_entries(&self) -> MutexGuard<HashMap<Uuid, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Counts a lookup of the image with the given id, marking the image as accessed if it was
+    /// found.
+    fn record_lookup(&self, id: Uuid, found: bool) {
+        let Ok(mut usage) = self.usage.lock() else {
+            return;
+        };
+
+        if found {
+            usage.stats.hits += 1;
+            usage.accessed.insert(id, Instant::now());
+        } else {
+            usage.stats.misses += 1;
+        }
+    }
+
+    /// Marks the image with the given id as accessed and sets its entry, evicting the least
+    /// recently accessed images if the [Cache] is full. Evicted images that are still loading
+    /// have their loading aborted.
+    fn set_entry(&self, entries: &mut HashMap<Uuid, CacheEntry>, id: Uuid, entry: CacheEntry) {
+        let evicted = self
+            .usage
+            .lock()
+            .map(|mut usage| usage.touch(id, self.max_entries))
+            .unwrap_or_default();
+
+        entries.insert(id, entry);
+        for evicted_id in evicted {
+            if let Some(CacheEntry::Loading(handle)) = entries.remove(&evicted_id) {
+                handle.abort();
+            }
+        }
+    }
+
+    /// Uploads image into cache.
+    pub async fn insert(&self, id: Uuid, image: Arc<PixelImage>) -> Result<(), Error> {
+        let mut entries = self.lock_entries();
+        self.set_entry(&mut entries, id, CacheEntry::Loaded(image));
 
         Ok(())
     }
 
-    /// Gets the handle of an image from its id.
+    /// Displays an image from its id: the image itself once it has been loaded, and a
+    /// placeholder while it is loading or if it could not be loaded.
     pub fn get_element<'a>(
         &self,
         id: Uuid,
@@ -110,34 +255,75 @@ impl Cache {
         backup_size: Size<Length>,
         text_size: impl Into<Option<Pixels>>,
     ) -> Element<'a, Message, Theme, Renderer> {
-        match self.cache_sync.get(&id) {
-            Some(pixels) => Image::new(Handle::from_rgba(
-                pixels.get_width(),
-                pixels.get_height(),
-                pixels.get_data().clone(),
-            ))
-            .width(size.width)
-            .height(size.height)
-            .into(),
-            None => {
+        let entries = self.lock_entries();
+        let entry = entries.get(&id);
+        self.record_lookup(id, matches!(entry, Some(CacheEntry::Loaded(_))));
+
+        let text_size = text_size.into();
+        let placeholder = match entry {
+            Some(CacheEntry::Loaded(pixels)) => {
+                return Image::new(Handle::from_rgba(
+                    pixels.get_width(),
+                    pixels.get_height(),
+                    pixels.get_data().clone(),
+                ))
+                .width(size.width)
+                .height(size.height)
+                .into();
+            }
+            Some(CacheEntry::Failed(_)) => {
+                let mut text = Text::new("Could not load image.");
+                if let Some(text_size) = text_size {
+                    text = text.size(text_size);
+                }
+
+                Element::from(
+                    Container::new(text)
+                        .center_x(Length::Fill)
+                        .center_y(Length::Fill),
+                )
+            }
+            Some(CacheEntry::Loading(_)) | None => {
                 let mut appearance = Appearance::default();
-                if let Some(text_size) = text_size.into() {
+                if let Some(text_size) = text_size {
                     appearance = appearance.text_size(text_size);
                 }
 
-                Container::new(WaitPanel::new("Loading...").style(appearance))
-                    .width(backup_size.width)
-                    .height(backup_size.height)
-                    .style(iced::widget::container::bordered_box)
-                    .into()
+                WaitPanel::new("Loading...").style(appearance).into()
             }
-        }
+        };
+
+        Container::new(placeholder)
+            .width(backup_size.width)
+            .height(backup_size.height)
+            .style(iced::widget::container::bordered_box)
+            .into()
     }
 
     pub fn get(&self, id: Uuid) -> Option<Arc<PixelImage>> {
-        self.cache_sync.get(&id)
+        let pixels = match self.lock_entries().get(&id) {
+            Some(CacheEntry::Loaded(pixels)) => Some(Arc::clone(pixels)),
+            _ => None,
+        };
+        self.record_lookup(id, pixels.is_some());
+
+        pixels
+    }
+
+    /// Gets the handle of an image from its id, if it has been loaded.
+    pub fn get_handle(&self, id: Uuid) -> Option<Handle> {
+        self.get(id).map(|pixels| {
+            Handle::from_rgba(
+                pixels.get_width(),
+                pixels.get_height(),
+                pixels.get_data().clone(),
+            )
+        })
     }
 
+    /// Starts loading the given items that are not in the [Cache] yet. Their entries are marked
+    /// as [loading](CacheEntry::Loading) right away, and are replaced with the loaded image or
+    /// the error once the loading task ends. Images that failed to load are not retried.
     pub fn insert_if_not<F, I>(
         &self,
         items: impl IntoIterator<Item = I>,
@@ -145,29 +331,40 @@ impl Cache {
         load: impl Fn(I) -> F + Send + Copy + 'static,
     ) -> Command<Message>
     where
-        F: Future<Output = Result<Arc<PixelImage>, Error>> + Send,
+        F: Future<Output = Result<Arc<PixelImage>, Error>> + Send + 'static,
         I: Send + Clone + 'static,
     {
         Command::batch(items.into_iter().filter_map(move |item| {
             let id = (get_id)(item.clone());
-            let cache_sync = self.cache_sync.clone();
-            let cache_async = self.cache_async.clone();
-
-            (!cache_sync.contains_key(&id)).then_some(Command::perform(
-                async move {
-                    let data = cache_async.try_get_with(id, (load)(item)).await?;
-
-                    tokio::task::spawn_blocking(move || {
-                        cache_sync.get_with(id, || Arc::clone(&data));
-                    })
-                    .await
-                    .map_err(|err| Arc::new(debug_message!("{}", err).into()))
-                },
-                |result: Result<(), Arc<Error>>| match result {
-                    Ok(_) => Message::None,
-                    Err(err) => Message::Error(err.as_ref().clone()),
-                },
-            ))
+
+            // The entries stay locked until the loading entry is set, so that a task that ends
+            // right away cannot have its result overwritten.
+            let mut entries = self.lock_entries();
+            if entries.contains_key(&id) {
+                return None;
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let cache = self.clone();
+            let handle = tokio::spawn(async move {
+                let result = (load)(item).await;
+
+                let entry = match &result {
+                    Ok(image) => CacheEntry::Loaded(Arc::clone(image)),
+                    Err(err) => CacheEntry::Failed(err.clone()),
+                };
+                cache.set_entry(&mut cache.lock_entries(), id, entry);
+
+                let _ = sender.send(result.as_ref().map(|_| ()).map_err(Clone::clone));
+
+                result
+            });
+            self.set_entry(&mut entries, id, CacheEntry::Loading(handle));
+
+            Some(Command::perform(receiver, |result| match result {
+                Ok(Err(err)) => Message::Error(err),
+                _ => Message::None,
+            }))
         }))
     }
 }
@@ -0,0 +1,41 @@
+use super::errors::Error;
+use crate::debug_message;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// The bytes every gzip stream starts with.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Tells whether the data is compressed with gzip.
+pub fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(&GZIP_MAGIC)
+}
+
+/// Compresses the data with gzip.
+pub fn compress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder
+        .write_all(data)
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    encoder
+        .finish()
+        .map_err(|err| debug_message!("{}", err).into())
+}
+
+/// Decompresses gzip data. Data that is not compressed is returned as it is, so that files
+/// written before compression was introduced can still be read.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    if !is_compressed(data) {
+        return Ok(data.to_vec());
+    }
+
+    let mut decompressed = vec![];
+    GzDecoder::new(data)
+        .read_to_end(&mut decompressed)
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    Ok(decompressed)
+}
@@ -1,14 +1,102 @@
 use super::errors::Error;
 use crate::debug_message;
-use image::{DynamicImage, ImageFormat, RgbaImage};
-use resvg::tiny_skia::Transform;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::imageops::FilterType;
+use image::{Delay, DynamicImage, Frame, ImageFormat, Rgba, RgbaImage};
+use printpdf::{Image, ImageTransform, Mm, PdfDocument};
+use resvg::tiny_skia::{Pixmap, Transform};
 use resvg::usvg::fontdb;
 use std::convert::identity;
-use std::io::Cursor;
+use std::io::{Cursor, Write};
 use svg::Document;
 use tokio::task;
 
+/// The resolution the drawings are placed on PDF pages with.
+const PDF_DPI: f32 = 96.0;
+
+/// A color adjustment applied to a rasterized drawing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFilter {
+    /// Adds the given value, relative to the full color range, to every channel; -1 turns the
+    /// image black and 1 turns it white.
+    Brightness(f32),
+
+    /// Changes the contrast by the given percentage; negative values reduce it.
+    Contrast(f32),
+
+    /// Multiplies the saturation by the given factor; 0 turns the image grayscale.
+    Saturation(f32),
+
+    /// Rotates the hue by the given number of degrees.
+    Hue(f32),
+
+    /// Inverts the colors.
+    Invert,
+}
+
+/// Applies the given filters to the image, in order. The transparency is left unchanged.
+pub fn apply_filters(image: DynamicImage, filters: &[ImageFilter]) -> DynamicImage {
+    filters
+        .iter()
+        .fold(image, |mut image, filter| match *filter {
+            ImageFilter::Brightness(value) => image.brighten((value * 255.0).round() as i32),
+            ImageFilter::Contrast(value) => image.adjust_contrast(value),
+            ImageFilter::Saturation(factor) => {
+                let mut rgba = image.to_rgba8();
+
+                for Rgba([red, green, blue, _]) in rgba.pixels_mut() {
+                    let luminance =
+                        0.299 * *red as f32 + 0.587 * *green as f32 + 0.114 * *blue as f32;
+                    let saturate = |channel: u8| {
+                        (luminance + (channel as f32 - luminance) * factor).clamp(0.0, 255.0) as u8
+                    };
+
+                    *red = saturate(*red);
+                    *green = saturate(*green);
+                    *blue = saturate(*blue);
+                }
+
+                DynamicImage::ImageRgba8(rgba)
+            }
+            ImageFilter::Hue(degrees) => image.huerotate(degrees.round() as i32),
+            ImageFilter::Invert => {
+                image.invert();
+                image
+            }
+        })
+}
+
+/// Rasterizes the given svg data.
+pub(crate) fn render_svg(svg_data: &str) -> Result<Pixmap, Error> {
+    let opt = resvg::usvg::Options::default();
+    let tree = match resvg::usvg::Tree::from_str(svg_data, &opt, &fontdb::Database::default()) {
+        Ok(tree) => tree,
+        Err(err) => {
+            return Err(debug_message!("{}", err.to_string()).into());
+        }
+    };
+
+    let mut pixmap = match Pixmap::new(tree.size().width() as u32, tree.size().height() as u32) {
+        Some(pixmap) => pixmap,
+        None => return Err(debug_message!("Error initializing pixmap.").into()),
+    };
+
+    resvg::render(&tree, Transform::default(), &mut pixmap.as_mut());
+
+    Ok(pixmap)
+}
+
 pub async fn encode_svg(svg: Document, format: impl Into<String>) -> Result<Vec<u8>, Error> {
+    encode_svg_with_filters(svg, format, vec![]).await
+}
+
+/// Encodes the given svg document in the given format, after applying the given filters to it.
+/// The filters are ignored when encoding as svg, since the drawing is not rasterized.
+pub async fn encode_svg_with_filters(
+    svg: Document,
+    format: impl Into<String>,
+    filters: Vec<ImageFilter>,
+) -> Result<Vec<u8>, Error> {
     let format = format.into();
 
     task::spawn_blocking(move || {
@@ -18,24 +106,7 @@ pub async fn encode_svg(svg: Document, format: impl Into<String>) -> Result<Vec<
             return Ok(svg_data.as_bytes().to_vec());
         }
 
-        let opt = resvg::usvg::Options::default();
-        let tree = match resvg::usvg::Tree::from_str(&*svg_data, &opt, &fontdb::Database::default())
-        {
-            Ok(tree) => tree,
-            Err(err) => {
-                return Err(debug_message!("{}", err.to_string()).into());
-            }
-        };
-
-        let mut pixmap = match resvg::tiny_skia::Pixmap::new(
-            tree.size().width() as u32,
-            tree.size().height() as u32,
-        ) {
-            Some(pixmap) => pixmap,
-            None => return Err(debug_message!("Error initializing pixmap.").into()),
-        };
-
-        resvg::render(&tree, Transform::default(), &mut pixmap.as_mut());
+        let pixmap = render_svg(&*svg_data)?;
 
         let rgba_image =
             match RgbaImage::from_raw(pixmap.width(), pixmap.height(), pixmap.data().to_vec()) {
@@ -45,7 +116,7 @@ pub async fn encode_svg(svg: Document, format: impl Into<String>) -> Result<Vec<
                 }
             };
 
-        let dyn_image = DynamicImage::ImageRgba8(rgba_image);
+        let dyn_image = apply_filters(DynamicImage::ImageRgba8(rgba_image), &filters);
 
         let dyn_image = match &*format {
             "png" | "webp" | "tiff" | "bmp" => dyn_image,
@@ -75,3 +146,119 @@ pub async fn encode_svg(svg: Document, format: impl Into<String>) -> Result<Vec<
     .await
     .map_or_else(|err| Err(err.to_string().into()), identity)
 }
+
+/// Rasterizes the given svg document, scaled down to fit in a square with the given side.
+pub async fn render_thumbnail(svg: Document, size: u32) -> Result<DynamicImage, Error> {
+    task::spawn_blocking(move || {
+        let pixmap = render_svg(&*svg.to_string())?;
+
+        match RgbaImage::from_raw(pixmap.width(), pixmap.height(), pixmap.data().to_vec()) {
+            Some(image) => {
+                Ok(DynamicImage::ImageRgba8(image).resize(size, size, FilterType::Triangle))
+            }
+            None => Err(debug_message!("Error reading rgba image.").into()),
+        }
+    })
+    .await
+    .map_or_else(|err| Err(err.to_string().into()), identity)
+}
+
+/// Encodes the given svg documents as a single PDF page, with every document placed on its own
+/// PDF layer. The first document is at the bottom, and all documents must have the same size.
+pub async fn encode_pdf(layers: Vec<Document>) -> Result<Vec<u8>, Error> {
+    task::spawn_blocking(move || {
+        let pixmaps = layers
+            .iter()
+            .map(|layer| render_svg(&*layer.to_string()))
+            .collect::<Result<Vec<Pixmap>, Error>>()?;
+
+        let (width, height) = match pixmaps.first() {
+            Some(pixmap) => (pixmap.width(), pixmap.height()),
+            None => return Err(debug_message!("There is nothing to export.").into()),
+        };
+        let to_mm = |pixels: u32| Mm(pixels as f32 * 25.4 / PDF_DPI);
+
+        let (document, page, first_layer) =
+            PdfDocument::new("Chartsy drawing", to_mm(width), to_mm(height), "Layer 1");
+
+        for (index, pixmap) in pixmaps.into_iter().enumerate() {
+            let layer = if index == 0 {
+                document.get_page(page).get_layer(first_layer)
+            } else {
+                document
+                    .get_page(page)
+                    .add_layer(format!("Layer {}", index + 1))
+            };
+
+            // The pixmap holds premultiplied colors, while the PDF image expects straight ones.
+            let data = pixmap
+                .pixels()
+                .iter()
+                .flat_map(|pixel| {
+                    let color = pixel.demultiply();
+                    [color.red(), color.green(), color.blue(), color.alpha()]
+                })
+                .collect();
+
+            let image = match printpdf::image_crate::RgbaImage::from_raw(
+                pixmap.width(),
+                pixmap.height(),
+                data,
+            ) {
+                Some(image) => image,
+                None => return Err(debug_message!("Error reading rgba image.").into()),
+            };
+
+            Image::from_dynamic_image(&printpdf::image_crate::DynamicImage::ImageRgba8(image))
+                .add_to_layer(
+                    layer,
+                    ImageTransform {
+                        dpi: Some(PDF_DPI),
+                        ..Default::default()
+                    },
+                );
+        }
+
+        document
+            .save_to_bytes()
+            .map_err(|err| debug_message!("{}", err).into())
+    })
+    .await
+    .map_or_else(|err| Err(err.to_string().into()), identity)
+}
+
+/// Encodes the given svg documents as the frames of an animated GIF that loops forever, and
+/// writes it to the given writer. The frames are rasterized one at a time, as they are encoded.
+pub async fn encode_gif<W>(frames: Vec<Document>, frame_rate: u32, writer: W) -> Result<(), Error>
+where
+    W: Write + Send + 'static,
+{
+    task::spawn_blocking(move || {
+        let delay = Delay::from_numer_denom_ms(1000, frame_rate.max(1));
+
+        let mut encoder = GifEncoder::new(writer);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|err| debug_message!("{}", err).into())?;
+
+        for frame in frames {
+            let pixmap = render_svg(&*frame.to_string())?;
+            let image = match RgbaImage::from_raw(
+                pixmap.width(),
+                pixmap.height(),
+                pixmap.data().to_vec(),
+            ) {
+                Some(image) => image,
+                None => return Err(debug_message!("Error reading rgba image.").into()),
+            };
+
+            encoder
+                .encode_frame(Frame::from_parts(image, 0, 0, delay))
+                .map_err(|err| debug_message!("{}", err).into())?;
+        }
+
+        Ok(())
+    })
+    .await
+    .map_or_else(|err| Err(err.to_string().into()), identity)
+}
@@ -30,7 +30,26 @@ pub enum AuthError {
     UserTagAlreadyExists,
 
     /// The user tag provided doesn't exist.
-    UserTagDoesNotExist(String)
+    UserTagDoesNotExist(String),
+
+    /// The session of the saved authentication token has expired.
+    TokenExpired,
+
+    /// The user has two-factor authentication enabled, and needs to input a code.
+    TotpRequired,
+
+    /// The provided two-factor authentication code is incorrect.
+    TotpInvalid,
+
+    /// The provided password reset token is incorrect or has expired.
+    PasswordResetBadToken,
+
+    /// The account of the user has been banned by an admin.
+    UserBanned,
+
+    /// An account with the email of the OAuth profile exists, but can't be linked to the
+    /// provider, so the user needs to log in with their password.
+    OAuthPasswordRequired,
 }
 
 impl Display for AuthError {
@@ -62,6 +81,12 @@ impl Display for AuthError {
                     
                     &*message
                 },
+                AuthError::TokenExpired => "Your session expired. Please log in again.",
+                AuthError::TotpRequired => "Please input the code from your authenticator app.",
+                AuthError::TotpInvalid => "The provided authentication code is incorrect!",
+                AuthError::PasswordResetBadToken => "The provided reset token is incorrect or has expired!",
+                AuthError::UserBanned => "Your account has been suspended.",
+                AuthError::OAuthPasswordRequired => "An account with this email already exists. Please log in with your password.",
             }
         )
     }
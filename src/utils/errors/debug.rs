@@ -4,14 +4,29 @@ use std::fmt::{Debug, Display, Formatter};
 #[derive(Clone, Eq, PartialEq)]
 pub struct DebugError {
     message: String,
+
+    /// Tells whether the error was caused by a temporary failure, such as a lost connection, in
+    /// which case the operation that caused it is worth retrying.
+    transient: bool,
 }
 
 impl DebugError {
     pub fn new(message: impl Into<String>) -> Self {
         DebugError {
             message: message.into(),
+            transient: false,
         }
     }
+
+    /// Marks the error as caused by a temporary failure.
+    pub fn transient(mut self) -> Self {
+        self.transient = true;
+        self
+    }
+
+    pub fn is_transient(&self) -> bool {
+        self.transient
+    }
 }
 
 impl Display for DebugError {
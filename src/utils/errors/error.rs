@@ -1,6 +1,7 @@
 use super::AuthError;
 use super::DebugError;
 use std::fmt::{Debug, Display, Formatter};
+use std::time::Duration;
 
 /// Error types.
 #[derive(Clone, Eq, PartialEq)]
@@ -10,6 +11,10 @@ pub enum Error {
 
     /// An error that the user has encountered while creating or updating their data.
     AuthError(AuthError),
+
+    /// The user has performed an action too many times in a short while, and can perform it again
+    /// after the given amount of time.
+    RateLimited { retry_after: Duration },
 }
 
 impl Error {
@@ -21,6 +26,15 @@ impl Error {
         }
     }
 
+    /// Tells whether the error was caused by a temporary failure, and the operation that caused it
+    /// is worth retrying.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::DebugError(error) => error.is_transient(),
+            _ => false,
+        }
+    }
+
     /// Tells whether the error is an [AuthError].
     pub fn is_auth(&self) -> bool {
         match self {
@@ -35,6 +49,11 @@ impl Display for Error {
         match self {
             Error::DebugError(error) => Display::fmt(error, f),
             Error::AuthError(error) => Display::fmt(error, f),
+            Error::RateLimited { retry_after } => write!(
+                f,
+                "You're doing this too often. Please try again in {} seconds.",
+                retry_after.as_secs().max(1)
+            ),
         }
     }
 }
@@ -44,6 +63,7 @@ impl Debug for Error {
         match self {
             Error::DebugError(error) => Debug::fmt(error, f),
             Error::AuthError(error) => Debug::fmt(error, f),
+            Error::RateLimited { .. } => Display::fmt(self, f),
         }
     }
 }
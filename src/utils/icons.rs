@@ -22,9 +22,17 @@ pub enum Icon {
     Loading,
     StarEmpty,
     StarFull,
+    StarHalf,
     Submit,
     Down,
     Right,
+    Copy,
+    Heart,
+    HeartEmpty,
+    Lock,
+    Unlock,
+    Sun,
+    Moon,
 }
 
 pub enum ToolIcon {
@@ -32,11 +40,20 @@ pub enum ToolIcon {
     Rectangle,
     Triangle,
     Polygon,
+    Star,
+    Bezier,
     Circle,
     Ellipse,
+    Arc,
+    Pie,
     Pencil,
     FountainPen,
     Airbrush,
+    Texture,
+    Smudge,
+    Stamp,
+    Image,
+    Gradient,
     Eraser,
 }
 
@@ -54,9 +71,17 @@ impl Display for Icon {
             Icon::Loading => '\u{F1978}',
             Icon::StarEmpty => '\u{F41E}',
             Icon::StarFull => '\u{F51F}',
+            Icon::StarHalf => '\u{F123}',
             Icon::Submit => '\u{F048A}',
             Icon::Down => '\u{F107}',
             Icon::Right => '\u{F105}',
+            Icon::Copy => '\u{F0C5}',
+            Icon::Heart => '\u{F004}',
+            Icon::HeartEmpty => '\u{F08A}',
+            Icon::Lock => '\u{F023}',
+            Icon::Unlock => '\u{F09C}',
+            Icon::Sun => '\u{F185}',
+            Icon::Moon => '\u{F186}',
         })
     }
 }
@@ -68,11 +93,20 @@ impl Display for ToolIcon {
             ToolIcon::Rectangle => '\u{F05C6}',
             ToolIcon::Triangle => '\u{F0563}',
             ToolIcon::Polygon => '\u{F0560}',
+            ToolIcon::Star => '\u{F04CE}',
+            ToolIcon::Bezier => '\u{F0559}',
             ToolIcon::Circle => '\u{F0556}',
             ToolIcon::Ellipse => '\u{F0893}',
+            ToolIcon::Arc => '\u{F0026}',
+            ToolIcon::Pie => '\u{F012A}',
             ToolIcon::Pencil => '\u{F03EB}',
             ToolIcon::FountainPen => '\u{F0D12}',
             ToolIcon::Airbrush => '\u{F0665}',
+            ToolIcon::Texture => '\u{F0512}',
+            ToolIcon::Smudge => '\u{F00B5}',
+            ToolIcon::Stamp => '\u{F0D37}',
+            ToolIcon::Image => '\u{F02E9}',
+            ToolIcon::Gradient => '\u{F06A0}',
             ToolIcon::Eraser => '\u{F01FE}',
         })
     }
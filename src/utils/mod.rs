@@ -9,3 +9,9 @@ pub mod theme;
 pub mod errors;
 
 pub mod cache;
+
+pub mod compression;
+
+pub mod rate_limiter;
+
+pub mod secret;
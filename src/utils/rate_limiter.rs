@@ -0,0 +1,72 @@
+use crate::utils::errors::Error;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// The number of times an action can be performed in a window, by default.
+const DEFAULT_MAX_CALLS: usize = 20;
+
+/// The length of a window, by default.
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Limits how often the database write operations can be performed, by counting the calls of
+/// each action in a fixed window of time.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    /// The number of calls of each action in the current window, and the moment the window
+    /// started.
+    bucket: HashMap<String, (usize, Instant)>,
+
+    /// The number of times an action can be performed in a window.
+    max_calls: usize,
+
+    /// The length of a window.
+    window: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(max_calls: usize, window: Duration) -> Self {
+        RateLimiter {
+            bucket: HashMap::new(),
+            max_calls: max_calls.max(1),
+            window,
+        }
+    }
+
+    /// Counts a call of the given action. Fails with [RateLimited](Error::RateLimited) if the
+    /// action has already been performed too many times in the current window, in which case
+    /// the call isn't counted.
+    pub fn check(&mut self, key: &str) -> Result<(), Error> {
+        let now = Instant::now();
+        let (calls, start) = self.bucket.entry(key.into()).or_insert((0, now));
+
+        let elapsed = now.duration_since(*start);
+        if elapsed >= self.window {
+            *calls = 0;
+            *start = now;
+        } else if *calls >= self.max_calls {
+            return Err(Error::RateLimited {
+                retry_after: self.window - elapsed,
+            });
+        }
+
+        *calls += 1;
+
+        Ok(())
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter::new(DEFAULT_MAX_CALLS, DEFAULT_WINDOW)
+    }
+}
+
+/// Counts a call of the given action on the shared [RateLimiter]. A poisoned lock is recovered,
+/// since the counters are always left in a valid state.
+pub fn check(rate_limiter: &Mutex<RateLimiter>, key: &str) -> Result<(), Error> {
+    rate_limiter
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .check(key)
+}
@@ -0,0 +1,63 @@
+use crate::config;
+use crate::debug_message;
+use crate::utils::errors::Error;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::random;
+use sha2::{Digest, Sha256};
+
+/// The prefix of the encrypted secrets, which tells them apart from the ones that were stored
+/// before they were encrypted.
+const ENCRYPTED_PREFIX: &str = "enc:";
+
+/// The length of the random nonce each secret is encrypted with.
+const NONCE_LENGTH: usize = 24;
+
+/// Returns the XChaCha20-Poly1305 cipher the secrets are encrypted with, keyed by the SHA-256
+/// hash of `config::totp_key()`. The config module has to provide that key next to the other
+/// keys of the application, and it must not change once secrets have been stored with it.
+fn cipher() -> XChaCha20Poly1305 {
+    let key: String = config::totp_key().into();
+
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&Sha256::digest(key.as_bytes()));
+
+    XChaCha20Poly1305::new(&Key::from(bytes))
+}
+
+/// Encrypts the given secret so that it can be stored in the database.
+pub fn encrypt(secret: &str) -> Result<String, Error> {
+    let nonce = random::<[u8; NONCE_LENGTH]>();
+
+    let encrypted = cipher()
+        .encrypt(&XNonce::from(nonce), secret.as_bytes())
+        .map_err(|err| debug_message!("{}", err).into())?;
+
+    let mut data = nonce.to_vec();
+    data.extend(encrypted);
+
+    Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(data)))
+}
+
+/// Decrypts a secret read from the database. Secrets stored before they were encrypted are
+/// returned as they are, while encrypted secrets that fail the authentication check are
+/// rejected.
+pub fn decrypt(secret: &str) -> Option<String> {
+    let Some(encoded) = secret.strip_prefix(ENCRYPTED_PREFIX) else {
+        return Some(secret.to_string());
+    };
+
+    let data = STANDARD.decode(encoded).ok()?;
+    if data.len() < NONCE_LENGTH {
+        return None;
+    }
+    let (nonce, encrypted) = data.split_at(NONCE_LENGTH);
+
+    let decrypted = cipher()
+        .decrypt(XNonce::from_slice(nonce), encrypted)
+        .ok()?;
+
+    String::from_utf8(decrypted).ok()
+}
@@ -19,6 +19,21 @@ pub const PALETTE: Palette = Palette {
     danger: DANGER,
 };
 
+pub const LIGHT_BACKGROUND: Color = color!(0xeff1f5);
+pub const LIGHT_TEXT: Color = color!(0x4c4f69);
+pub const LIGHT_DANGER: Color = color!(0xd20f39);
+pub const LIGHT_SUCCESS: Color = color!(0x40a02b);
+pub const LIGHT_PRIMARY: Color = color!(0x1e66f5);
+pub const LIGHT_SECONDARY: Color = color!(0xccd0da);
+
+pub const LIGHT_PALETTE: Palette = Palette {
+    background: LIGHT_BACKGROUND,
+    text: LIGHT_TEXT,
+    primary: LIGHT_PRIMARY,
+    success: LIGHT_SUCCESS,
+    danger: LIGHT_DANGER,
+};
+
 pub fn extended_palette_generator(palette: Palette) -> palette::Extended {
     palette::Extended {
         background: Background::new(palette.background, palette.text),
@@ -30,6 +45,17 @@ pub fn extended_palette_generator(palette: Palette) -> palette::Extended {
     }
 }
 
+pub fn light_extended_palette_generator(palette: Palette) -> palette::Extended {
+    palette::Extended {
+        background: Background::new(palette.background, palette.text),
+        primary: Primary::generate(palette.primary, palette.background, palette.background),
+        secondary: Secondary::generate(LIGHT_SECONDARY, palette.background),
+        success: Success::generate(palette.success, palette.background, palette.background),
+        danger: Danger::generate(palette.danger, palette.background, palette.background),
+        is_dark: false,
+    }
+}
+
 pub mod text {
     use iced::{widget::text::Style, Color};
 
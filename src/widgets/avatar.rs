@@ -0,0 +1,233 @@
+use iced::advanced::image::{self, FilterMethod, Handle};
+use iced::advanced::layout::{Limits, Node};
+use iced::advanced::renderer::{Quad, Style};
+use iced::advanced::text::{LineHeight, Shaping};
+use iced::advanced::widget::Tree;
+use iced::advanced::{Layout, Text, Widget};
+use iced::alignment::{Horizontal, Vertical};
+use iced::mouse::Cursor;
+use iced::{Border, Color, Element, Length, Point, Radians, Rectangle, Size, Vector};
+
+use crate::utils::theme;
+
+/// The default diameter of an [Avatar].
+const DEFAULT_SIZE: f32 = 50.0;
+
+/// The height of the horizontal strips the image is cropped into, to approximate a circle.
+const STRIP_HEIGHT: f32 = 1.0;
+
+/// The size of the initials, relative to the diameter of the [Avatar].
+const INITIALS_SCALE: f32 = 0.4;
+
+/// The luminance above which the initials are drawn in black instead of white.
+const LUMINANCE_THRESHOLD: f32 = 0.5;
+
+/// A circular profile picture. Users without a picture are shown as a colored circle holding
+/// their initials.
+pub struct Avatar {
+    /// The profile picture, if it exists and has been loaded.
+    handle: Option<Handle>,
+
+    /// The initials displayed when there is no profile picture.
+    initials: String,
+
+    /// The color of the circle displayed when there is no profile picture.
+    color: Color,
+
+    /// The diameter of the [Avatar].
+    size: f32,
+}
+
+impl Avatar {
+    /// Creates an [Avatar] with the given profile picture, falling back to the initials of the
+    /// given username.
+    pub fn new(handle: Option<Handle>, username: &str) -> Self {
+        Avatar {
+            handle,
+            initials: Self::get_initials(username),
+            color: Self::get_color(username),
+            size: DEFAULT_SIZE,
+        }
+    }
+
+    /// Sets the diameter of the [Avatar].
+    pub fn size(mut self, size: impl Into<f32>) -> Self {
+        self.size = size.into();
+
+        self
+    }
+
+    /// Returns the first letters of the first two words of the username, or the first two
+    /// letters of the username if it is a single word.
+    fn get_initials(username: &str) -> String {
+        let words = username.split_whitespace().collect::<Vec<&str>>();
+
+        let initials: String = if words.len() > 1 {
+            words
+                .iter()
+                .take(2)
+                .filter_map(|word| word.chars().next())
+                .collect()
+        } else {
+            username.trim().chars().take(2).collect()
+        };
+
+        initials.to_uppercase()
+    }
+
+    /// Picks the color of the fallback circle from the palette, so that every username always has
+    /// the same color.
+    fn get_color(username: &str) -> Color {
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+        let colors = [
+            palette.primary.base.color,
+            palette.secondary.base.color,
+            palette.success.base.color,
+            palette.danger.base.color,
+        ];
+
+        let hash = username.bytes().fold(0usize, |hash, byte| {
+            hash.wrapping_mul(31).wrapping_add(byte as usize)
+        });
+
+        colors[hash % colors.len()]
+    }
+
+    /// Returns black or white, whichever is more readable on the given color.
+    fn get_contrasting(color: Color) -> Color {
+        let luminance = 0.299 * color.r + 0.587 * color.g + 0.114 * color.b;
+
+        if luminance > LUMINANCE_THRESHOLD {
+            Color::BLACK
+        } else {
+            Color::WHITE
+        }
+    }
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer> for Avatar
+where
+    Renderer: iced::advanced::Renderer
+        + iced::advanced::text::Renderer<Font = iced::Font>
+        + image::Renderer<Handle = Handle>,
+{
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fixed(self.size), Length::Fixed(self.size))
+    }
+
+    fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.resolve(
+            Length::Fixed(self.size),
+            Length::Fixed(self.size),
+            Size::new(self.size, self.size),
+        ))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let diameter = bounds.width.min(bounds.height);
+        let radius = diameter / 2.0;
+        let circle = Rectangle::new(
+            bounds.center() - Vector::new(radius, radius),
+            Size::new(diameter, diameter),
+        );
+
+        let Some(handle) = &self.handle else {
+            renderer.fill_quad(
+                Quad {
+                    bounds: circle,
+                    border: Border {
+                        radius: radius.into(),
+                        ..Border::default()
+                    },
+                    shadow: Default::default(),
+                },
+                self.color,
+            );
+
+            renderer.fill_text(
+                Text {
+                    content: self.initials.clone(),
+                    bounds: circle.size(),
+                    size: (diameter * INITIALS_SCALE).into(),
+                    line_height: LineHeight::default(),
+                    font: renderer.default_font(),
+                    horizontal_alignment: Horizontal::Center,
+                    vertical_alignment: Vertical::Center,
+                    shaping: Shaping::Basic,
+                },
+                circle.center(),
+                Self::get_contrasting(self.color),
+                circle,
+            );
+
+            return;
+        };
+
+        // The image is scaled to cover the circle, and centered on it.
+        let image_size = renderer.measure_image(handle);
+        if image_size.width == 0 || image_size.height == 0 {
+            return;
+        }
+        let scale = diameter / (image_size.width.min(image_size.height) as f32);
+        let image_bounds = Rectangle::new(
+            circle.center()
+                - Vector::new(
+                    image_size.width as f32 * scale / 2.0,
+                    image_size.height as f32 * scale / 2.0,
+                ),
+            Size::new(
+                image_size.width as f32 * scale,
+                image_size.height as f32 * scale,
+            ),
+        );
+
+        // Layers can only clip to rectangles, so the image is drawn in horizontal strips, each as
+        // wide as the circle at its height.
+        let mut y = 0.0;
+        while y < diameter {
+            let height = STRIP_HEIGHT.min(diameter - y);
+            let distance = (y + height / 2.0 - radius).abs();
+            let half_width = (radius * radius - distance * distance).max(0.0).sqrt();
+
+            renderer.with_layer(
+                Rectangle::new(
+                    Point::new(circle.center_x() - half_width, circle.y + y),
+                    Size::new(2.0 * half_width, height),
+                ),
+                |renderer| {
+                    renderer.draw_image(
+                        handle.clone(),
+                        FilterMethod::Linear,
+                        image_bounds,
+                        Radians(0.0),
+                        1.0,
+                    );
+                },
+            );
+
+            y += height;
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Avatar> for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a
+        + iced::advanced::Renderer
+        + iced::advanced::text::Renderer<Font = iced::Font>
+        + image::Renderer<Handle = Handle>,
+{
+    fn from(value: Avatar) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,291 @@
+use iced::{
+    advanced::{
+        layout::{Limits, Node},
+        renderer::{Quad, Style},
+        text::{LineHeight, Paragraph, Shaping},
+        widget::{Operation, Tree},
+        Clipboard, Layout, Shell, Text, Widget,
+    },
+    alignment::{Horizontal, Vertical},
+    event::Status,
+    mouse::{Cursor, Interaction},
+    Border, Color, Element, Event, Length, Point, Rectangle, Size, Vector,
+};
+
+use crate::utils::theme;
+
+/// The text size of the count.
+const TEXT_SIZE: f32 = 11.0;
+
+/// The padding between the count and the edge of the badge.
+const BADGE_PADDING: f32 = 3.0;
+
+/// The largest count that is displayed; larger counts are shown as "99+".
+const MAX_COUNT: usize = 99;
+
+/// A widget that displays a count in a colored circle over the top-right corner of its content.
+pub struct Badge<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// The content the badge is displayed over.
+    content: Element<'a, Message, Theme, Renderer>,
+
+    /// The displayed count.
+    count: usize,
+
+    /// Tells whether the badge is displayed.
+    visible: bool,
+
+    /// The color of the badge.
+    color: Color,
+}
+
+impl<'a, Message, Theme, Renderer> Badge<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// Creates a [Badge] showing the given count over the given content.
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>, count: usize) -> Self {
+        Badge {
+            content: content.into(),
+            count,
+            visible: true,
+            color: theme::extended_palette_generator(theme::PALETTE)
+                .danger
+                .base
+                .color,
+        }
+    }
+
+    /// Sets whether the badge is displayed.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+
+        self
+    }
+
+    /// Sets the color of the badge.
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+
+        self
+    }
+
+    /// Returns the text of the count.
+    fn get_count_text(&self) -> String {
+        if self.count > MAX_COUNT {
+            format!("{}+", MAX_COUNT)
+        } else {
+            self.count.to_string()
+        }
+    }
+
+    /// Returns the given content with the text settings of the count.
+    fn get_text<Content, Font>(content: Content, font: Font) -> Text<Content, Font> {
+        Text {
+            content,
+            bounds: Size::new(f32::INFINITY, f32::INFINITY),
+            size: TEXT_SIZE.into(),
+            line_height: LineHeight::default(),
+            font,
+            horizontal_alignment: Horizontal::Center,
+            vertical_alignment: Vertical::Center,
+            shaping: Shaping::Basic,
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Badge<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content])
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
+        let content_node = self
+            .content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits);
+
+        Node::with_children(content_node.size(), vec![content_node])
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("Badge needs to have content node.");
+
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            content_layout,
+            cursor,
+            viewport,
+        );
+
+        if !self.visible || self.count == 0 {
+            return;
+        }
+
+        let bounds = layout.bounds();
+        let text = self.get_count_text();
+        let text_size =
+            Renderer::Paragraph::with_text(Self::get_text(&*text, renderer.default_font()))
+                .min_bounds();
+        let height = text_size.height + 2.0 * BADGE_PADDING;
+        let width = (text_size.width + 2.0 * BADGE_PADDING).max(height);
+        let badge_bounds = Rectangle::new(
+            Point::new(bounds.x + bounds.width - width, bounds.y),
+            Size::new(width, height),
+        );
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+
+        renderer.with_layer(bounds, |renderer| {
+            renderer.fill_quad(
+                Quad {
+                    bounds: badge_bounds,
+                    border: Border {
+                        radius: (height / 2.0).into(),
+                        ..Border::default()
+                    },
+                    shadow: Default::default(),
+                },
+                self.color,
+            );
+
+            renderer.fill_text(
+                Self::get_text(text, renderer.default_font()),
+                badge_bounds.center(),
+                palette.danger.base.text,
+                badge_bounds,
+            );
+        });
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("Badge needs to have content node.");
+
+        self.content.as_widget().mouse_interaction(
+            &state.children[0],
+            content_layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn on_event(
+        &mut self,
+        state: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> Status {
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("Badge needs to have content node.");
+
+        self.content.as_widget_mut().on_event(
+            &mut state.children[0],
+            event,
+            content_layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn operate(
+        &self,
+        state: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("Badge needs to have content node.");
+
+        self.content.as_widget().operate(
+            &mut state.children[0],
+            content_layout,
+            renderer,
+            operation,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("Badge needs to have content node.");
+
+        self.content.as_widget_mut().overlay(
+            &mut state.children[0],
+            content_layout,
+            renderer,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Badge<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    fn from(value: Badge<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(value)
+    }
+}
@@ -1,6 +1,8 @@
+use crate::scene;
+use crate::widgets::description::{self, Description};
 use iced::advanced::layout::{Limits, Node};
 use iced::advanced::renderer::{Quad, Style};
-use iced::advanced::widget::{Operation, Tree};
+use iced::advanced::widget::{tree, Operation, Tree};
 use iced::advanced::{Clipboard, Layout, Shell, Widget};
 use iced::border::Radius;
 use iced::event::Status;
@@ -16,7 +18,7 @@ const DEFAULT_PADDING: f32 = 10.0;
 pub struct Card<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
     /// The width of the [Card].
@@ -45,12 +47,15 @@ where
 
     /// The style of the [Card].
     style: <Theme as StyleSheet>::Style,
+
+    /// The optional [Description] of the [Card].
+    description: Option<Description<Message>>,
 }
 
 impl<'a, Message, Theme, Renderer> Card<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
     /// Creates a new [Card].
@@ -68,6 +73,7 @@ where
             content_padding: DEFAULT_PADDING.into(),
             footer_padding: DEFAULT_PADDING.into(),
             style: <Theme as StyleSheet>::Style::default(),
+            description: None,
         }
     }
 
@@ -119,15 +125,33 @@ where
 
         self
     }
+
+    /// Sets the description of the [Card], announced to screen readers when it gains focus.
+    pub fn describe(mut self, text: impl Into<String>) -> Self
+    where
+        Message: From<scene::Message>,
+    {
+        self.description = Some(Description::new(text));
+
+        self
+    }
 }
 
 impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
     for Card<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<description::State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(description::State::default())
+    }
+
     fn size(&self) -> Size<Length> {
         Size::new(self.width, self.height)
     }
@@ -317,6 +341,10 @@ where
         renderer: &Renderer,
         operation: &mut dyn Operation<Message>,
     ) {
+        if let Some(description) = &self.description {
+            description.operate(state.state.downcast_mut::<description::State>(), operation);
+        }
+
         let mut children = layout.children();
 
         let header_layout = children.next().expect("Card needs to have header.");
@@ -351,6 +379,16 @@ where
         shell: &mut Shell<'_, Message>,
         viewport: &Rectangle,
     ) -> Status {
+        if let Some(description) = &self.description {
+            description.on_event(
+                state.state.downcast_mut::<description::State>(),
+                &event,
+                layout.bounds(),
+                cursor,
+                shell,
+            );
+        }
+
         let mut children = layout.children();
 
         let header_layout = children.next().expect("Card needs to have header.");
@@ -449,21 +487,31 @@ where
         renderer: &Renderer,
         translation: Vector,
     ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        let description_overlay = self.description.as_ref().and_then(|description| {
+            description.overlay(
+                state.state.downcast_ref::<description::State>(),
+                layout.bounds(),
+                translation,
+            )
+        });
+
         let mut children = vec![&mut self.header, &mut self.content];
         if let Some(footer) = self.footer.as_mut() {
             children.push(footer);
         }
 
-        let overlays: Vec<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> = layout
-            .children()
-            .zip(state.children.iter_mut())
-            .zip(children)
-            .filter_map(|((layout, state), element)| {
-                element
-                    .as_widget_mut()
-                    .overlay(state, layout, renderer, translation)
-            })
-            .collect();
+        let mut overlays: Vec<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> =
+            layout
+                .children()
+                .zip(state.children.iter_mut())
+                .zip(children)
+                .filter_map(|((layout, state), element)| {
+                    element
+                        .as_widget_mut()
+                        .overlay(state, layout, renderer, translation)
+                })
+                .collect();
+        overlays.extend(description_overlay);
 
         (!overlays.is_empty())
             .then_some(iced::advanced::overlay::Group::with_children(overlays).overlay())
@@ -474,7 +522,7 @@ impl<'a, Message, Theme, Renderer> From<Card<'a, Message, Theme, Renderer>>
     for Element<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
     fn from(value: Card<'a, Message, Theme, Renderer>) -> Self {
@@ -0,0 +1,113 @@
+use iced::widget::{Checkbox, Column, Row};
+use iced::{Alignment, Element, Length, Padding};
+use std::marker::PhantomData;
+
+/// The space between the checkbox and the item it selects.
+const CHECKBOX_SPACING: f32 = 10.0;
+
+/// A widget that displays a list of items vertically, each with a checkbox, so that several of
+/// them can be selected at once.
+pub struct CheckboxList<'a, T, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// The elements displaying the items, and whether each item is checked.
+    children: Vec<(Element<'a, Message, Theme, Renderer>, bool)>,
+
+    /// The message triggered with the position of a toggled item and its new value.
+    on_toggle: fn(usize, bool) -> Message,
+
+    /// The space between the items.
+    spacing: f32,
+
+    /// The padding around the items.
+    padding: Padding,
+
+    /// The width of the [CheckboxList].
+    width: Length,
+
+    /// The type of the displayed items.
+    items: PhantomData<T>,
+}
+
+impl<'a, T, Message, Theme, Renderer> CheckboxList<'a, T, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// Creates a new [CheckboxList] that displays every item with the given function, next to a
+    /// checkbox holding whether the item is checked.
+    pub fn new(
+        items: impl IntoIterator<Item = (T, bool)>,
+        view: impl Fn(T) -> Element<'a, Message, Theme, Renderer>,
+        on_toggle: fn(usize, bool) -> Message,
+    ) -> Self {
+        CheckboxList {
+            children: items
+                .into_iter()
+                .map(|(item, checked)| (view(item), checked))
+                .collect(),
+            on_toggle,
+            spacing: 0.0,
+            padding: Padding::ZERO,
+            width: Length::Fill,
+            items: PhantomData,
+        }
+    }
+
+    /// Sets the space between the items.
+    pub fn spacing(mut self, spacing: impl Into<f32>) -> Self {
+        self.spacing = spacing.into();
+
+        self
+    }
+
+    /// Sets the padding around the items.
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+
+        self
+    }
+
+    /// Sets the width of the [CheckboxList].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+
+        self
+    }
+}
+
+impl<'a, T, Message, Theme, Renderer> From<CheckboxList<'a, T, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    T: 'a,
+    Message: 'a + Clone,
+    Theme: 'a + iced::widget::checkbox::Catalog,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer,
+{
+    fn from(value: CheckboxList<'a, T, Message, Theme, Renderer>) -> Self {
+        let on_toggle = value.on_toggle;
+
+        Column::with_children(
+            value
+                .children
+                .into_iter()
+                .enumerate()
+                .map(|(index, (child, checked))| {
+                    Row::with_children(vec![
+                        Checkbox::new("", checked)
+                            .on_toggle(move |checked| on_toggle(index, checked))
+                            .into(),
+                        child,
+                    ])
+                    .spacing(CHECKBOX_SPACING)
+                    .align_items(Alignment::Center)
+                    .into()
+                })
+                .collect::<Vec<Element<'a, Message, Theme, Renderer>>>(),
+        )
+        .spacing(value.spacing)
+        .padding(value.padding)
+        .width(value.width)
+        .into()
+    }
+}
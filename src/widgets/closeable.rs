@@ -1,19 +1,35 @@
+use crate::scene;
 use crate::widgets::close::Close;
+use crate::widgets::description::{self, Description, TooltipOverlay};
 use iced::advanced::layout::{Limits, Node};
 use iced::advanced::renderer::{Quad, Style};
-use iced::advanced::widget::{Operation, Tree};
-use iced::advanced::{Clipboard, Layout, Shell, Widget};
+use iced::advanced::widget::{tree, Operation, Tree};
+use iced::advanced::{overlay, Clipboard, Layout, Shell, Widget};
 use iced::event::Status;
 use iced::mouse::{Cursor, Interaction};
 use iced::theme::Palette;
 use iced::{
-    mouse, Alignment, Background, Element, Event, Length, Padding, Point, Rectangle, Size, Vector,
+    mouse, window, Alignment, Background, Element, Event, Length, Padding, Point, Rectangle, Size,
+    Vector,
 };
+use std::time::{Duration, Instant};
 
 /// The default padding for the content.
 const DEFAULT_PADDING: f32 = 0.0;
 /// The default padding for the [close button](Close).
 const DEFAULT_CLOSE_PADDING: f32 = 10.0;
+/// The time it takes the tooltip to fade in, once its delay has passed.
+const TOOLTIP_FADE: Duration = Duration::from_millis(150);
+
+/// The state of a [Closeable].
+#[derive(Default)]
+struct State {
+    /// The moment the cursor started hovering over the [close button](Close).
+    hovered_since: Option<Instant>,
+
+    /// The state of the [Description] of the [Closeable].
+    description: description::State,
+}
 
 /// A [Widget] for a container which can be closed.
 pub struct Closeable<'a, Message, Theme, Renderer>
@@ -51,6 +67,13 @@ where
 
     /// The [style](StyleSheet::Style) of the [Closeable].
     style: <Theme as StyleSheet>::Style,
+
+    /// The text of the tooltip of the [close button](Close), and the time the cursor has to
+    /// hover over the button before it is shown.
+    tooltip: Option<(String, Duration)>,
+
+    /// The optional [Description] of the [Closeable].
+    description: Option<Description<Message>>,
 }
 
 impl<'a, Message, Theme, Renderer> Closeable<'a, Message, Theme, Renderer>
@@ -72,6 +95,8 @@ where
             close_padding: DEFAULT_CLOSE_PADDING.into(),
             close_button: None,
             style: <Theme as StyleSheet>::Style::default(),
+            tooltip: None,
+            description: None,
         }
     }
 
@@ -137,6 +162,24 @@ where
 
         self
     }
+
+    /// Sets the tooltip of the [close button](Close), shown above it once the cursor has hovered
+    /// over it for the given delay.
+    pub fn with_tooltip(mut self, text: &str, delay: Duration) -> Self {
+        self.tooltip = Some((String::from(text), delay));
+
+        self
+    }
+
+    /// Sets the description of the [Closeable], announced to screen readers when it gains focus.
+    pub fn describe(mut self, text: impl Into<String>) -> Self
+    where
+        Message: From<scene::Message>,
+    {
+        self.description = Some(Description::new(text));
+
+        self
+    }
 }
 
 impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -146,6 +189,14 @@ where
     Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
     fn size(&self) -> Size<Length> {
         Size::new(self.width, self.height)
     }
@@ -266,6 +317,13 @@ where
         renderer: &Renderer,
         operation: &mut dyn Operation<Message>,
     ) {
+        if let Some(description) = &self.description {
+            description.operate(
+                &mut state.state.downcast_mut::<State>().description,
+                operation,
+            );
+        }
+
         let mut children = layout.children();
 
         let content_node = children.next().expect("Closeable needs to have content.");
@@ -286,6 +344,16 @@ where
         shell: &mut Shell<'_, Message>,
         viewport: &Rectangle,
     ) -> Status {
+        if let Some(description) = &self.description {
+            description.on_event(
+                &mut state.state.downcast_mut::<State>().description,
+                &event,
+                layout.bounds(),
+                cursor,
+                shell,
+            );
+        }
+
         let mut children = layout.children();
 
         let content_node = children.next().expect("Closeable needs to have content.");
@@ -317,6 +385,38 @@ where
 
         let close_status = if let Some(close_button) = self.close_button.as_mut() {
             let close_node = children.next().expect("Image should have close button");
+
+            if let Some((_, delay)) = &self.tooltip {
+                let state = state.state.downcast_mut::<State>();
+
+                match event {
+                    Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                        if !cursor.is_over(close_node.bounds()) {
+                            state.hovered_since = None;
+                        } else if state.hovered_since.is_none() {
+                            let now = Instant::now();
+
+                            state.hovered_since = Some(now);
+                            shell.request_redraw(window::RedrawRequest::At(now + *delay));
+                        }
+                    }
+                    Event::Window(window::Event::RedrawRequested(now)) => {
+                        if let Some(hovered_since) = state.hovered_since {
+                            let elapsed = now.saturating_duration_since(hovered_since);
+
+                            if elapsed < *delay {
+                                shell.request_redraw(window::RedrawRequest::At(
+                                    hovered_since + *delay,
+                                ));
+                            } else if elapsed < *delay + TOOLTIP_FADE {
+                                shell.request_redraw(window::RedrawRequest::NextFrame);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
             close_button.as_widget_mut().on_event(
                 &mut state.children[1],
                 event,
@@ -386,14 +486,48 @@ where
         renderer: &Renderer,
         translation: Vector,
     ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
-        self.content.as_widget_mut().overlay(
+        let bounds = layout.bounds();
+        let mut children = layout.children();
+        let content_node = children.next().expect("Closeable needs to have content.");
+        let hovered_since = state.state.downcast_ref::<State>().hovered_since;
+        let description_overlay = self.description.as_ref().and_then(|description| {
+            description.overlay(
+                &state.state.downcast_ref::<State>().description,
+                bounds,
+                translation,
+            )
+        });
+
+        let content_overlay = self.content.as_widget_mut().overlay(
             &mut state.children[0],
-            layout
-                .children()
-                .next()
-                .expect("Closeable needs to have content."),
+            content_node,
             renderer,
             translation,
+        );
+
+        let tooltip_overlay = match (&self.tooltip, hovered_since, children.next()) {
+            (Some((text, delay)), Some(hovered_since), Some(close_node)) => {
+                let elapsed = hovered_since.elapsed();
+
+                if elapsed >= *delay {
+                    let alpha =
+                        ((elapsed - *delay).as_secs_f32() / TOOLTIP_FADE.as_secs_f32()).min(1.0);
+
+                    Some(overlay::Element::new(Box::new(TooltipOverlay::new(
+                        text.clone(),
+                        close_node.bounds() + translation,
+                        alpha,
+                    ))))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        description::group(
+            description::group(content_overlay, tooltip_overlay),
+            description_overlay,
         )
     }
 }
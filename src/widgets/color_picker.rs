@@ -1,15 +1,18 @@
+use crate::scene;
+use crate::widgets::description::{self, Description};
+use crate::widgets::number_input::NumberInput;
 use iced::advanced::layout::{Limits, Node};
 use iced::advanced::renderer::{Quad, Style};
-use iced::advanced::widget::Tree;
-use iced::advanced::{Clipboard, Layout, Shell, Widget};
+use iced::advanced::widget::{tree, Operation, Tree};
+use iced::advanced::{overlay, Clipboard, Layout, Shell, Widget};
 use iced::alignment::Horizontal;
 use iced::event::Status;
 use iced::gradient::Linear;
 use iced::mouse::{Button, Cursor, Interaction};
-use iced::widget::{Column, Row, Slider, Text, TextInput};
+use iced::widget::{Column, Row, Slider, Text};
 use iced::{
     mouse, Alignment, Background, Color, Element, Event, Gradient, Length, Padding, Point,
-    Rectangle, Size,
+    Rectangle, Size, Vector,
 };
 use std::f32::consts::PI;
 
@@ -59,6 +62,9 @@ where
 
     /// The update function of the [ColorPicker].
     on_update: fn(Color) -> Message,
+
+    /// The optional [Description] of the [ColorPicker].
+    description: Option<Description<Message>>,
 }
 
 impl<Message> ColorPicker<Message>
@@ -85,6 +91,7 @@ where
             padding: DEFAULT_PADDING.into(),
             spacing: DEFAULT_PADDING,
             on_update,
+            description: None,
         }
     }
 
@@ -132,13 +139,31 @@ where
 
         self
     }
+
+    /// Sets the description of the [ColorPicker], announced to screen readers when it gains focus.
+    pub fn describe(mut self, text: impl Into<String>) -> Self
+    where
+        Message: From<scene::Message>,
+    {
+        self.description = Some(Description::new(text));
+
+        self
+    }
 }
 
 impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer> for ColorPicker<Message>
 where
     Message: Clone,
-    Renderer: iced::advanced::Renderer,
+    Renderer: iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<description::State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(description::State::default())
+    }
+
     fn size(&self) -> Size<Length> {
         Size::new(self.width, self.height)
     }
@@ -293,9 +318,21 @@ where
         );
     }
 
+    fn operate(
+        &self,
+        state: &mut Tree,
+        _layout: Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        if let Some(description) = &self.description {
+            description.operate(state.state.downcast_mut::<description::State>(), operation);
+        }
+    }
+
     fn on_event(
         &mut self,
-        _state: &mut Tree,
+        state: &mut Tree,
         event: Event,
         layout: Layout<'_>,
         cursor: Cursor,
@@ -304,6 +341,16 @@ where
         shell: &mut Shell<'_, Message>,
         _viewport: &Rectangle,
     ) -> Status {
+        if let Some(description) = &self.description {
+            description.on_event(
+                state.state.downcast_mut::<description::State>(),
+                &event,
+                layout.bounds(),
+                cursor,
+                shell,
+            );
+        }
+
         let mut children = layout.children();
 
         let layout_2d = children
@@ -419,13 +466,30 @@ where
             Interaction::default()
         }
     }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut Tree,
+        layout: Layout<'_>,
+        _renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.description.as_ref().and_then(|description| {
+            description.overlay(
+                state.state.downcast_ref::<description::State>(),
+                layout.bounds(),
+                translation,
+            )
+        })
+    }
 }
 
 impl<'a, Message, Theme, Renderer> From<ColorPicker<Message>>
     for Element<'a, Message, Theme, Renderer>
 where
-    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a
+        + iced::widget::button::Catalog
         + iced::widget::text::Catalog
         + iced::widget::text_input::Catalog
         + iced::widget::slider::Catalog,
@@ -436,28 +500,7 @@ where
         let green_default = (value.green * 255.0) as u8;
         let blue_default = (value.blue * 255.0) as u8;
         let alpha = (value.alpha * 255.0) as u8;
-
-        let parse_input = move |mut input: String, index: usize| {
-            if input == "" {
-                input = String::from("0");
-            }
-            let parsed = input.parse::<u8>();
-
-            if let Ok(number) = parsed {
-                match index {
-                    0 => (value.on_update)(Color::from_rgb8(number, green_default, blue_default)),
-                    1 => (value.on_update)(Color::from_rgb8(red_default, number, blue_default)),
-                    2 => (value.on_update)(Color::from_rgb8(red_default, green_default, number)),
-                    _ => (value.on_update)(Color::from_rgb8(
-                        red_default,
-                        green_default,
-                        blue_default,
-                    )),
-                }
-            } else {
-                (value.on_update)(Color::from_rgb8(red_default, green_default, blue_default))
-            }
-        };
+        let on_update = value.on_update;
 
         let send_alpha = move |alpha| {
             (value.on_update)(Color::from_rgba8(
@@ -468,48 +511,35 @@ where
             ))
         };
 
-        let red_text = if red_default == 0 {
-            String::from("")
-        } else {
-            red_default.to_string()
-        };
-        let green_text = if green_default == 0 {
-            String::from("")
-        } else {
-            green_default.to_string()
-        };
-        let blue_text = if blue_default == 0 {
-            String::from("")
-        } else {
-            blue_default.to_string()
-        };
-
         Column::with_children(vec![
             Element::new(value),
             Row::with_children(vec![
                 Row::with_children(vec![
                     Text::new("R:").into(),
-                    TextInput::new("0", &red_text)
-                        .on_input(move |input| parse_input(input, 0))
-                        .into(),
+                    NumberInput::new(red_default, move |red| {
+                        on_update(Color::from_rgb8(red, green_default, blue_default))
+                    })
+                    .into(),
                 ])
                 .align_items(Alignment::Center)
                 .spacing(2.0)
                 .into(),
                 Row::with_children(vec![
                     Text::new("G:").into(),
-                    TextInput::new("0", &green_text)
-                        .on_input(move |input| parse_input(input, 1))
-                        .into(),
+                    NumberInput::new(green_default, move |green| {
+                        on_update(Color::from_rgb8(red_default, green, blue_default))
+                    })
+                    .into(),
                 ])
                 .align_items(Alignment::Center)
                 .spacing(2.0)
                 .into(),
                 Row::with_children(vec![
                     Text::new("B:").into(),
-                    TextInput::new("0", &blue_text)
-                        .on_input(move |input| parse_input(input, 2))
-                        .into(),
+                    NumberInput::new(blue_default, move |blue| {
+                        on_update(Color::from_rgb8(red_default, green_default, blue))
+                    })
+                    .into(),
                 ])
                 .align_items(Alignment::Center)
                 .spacing(2.0)
@@ -2,16 +2,20 @@ use difflib::sequencematcher::SequenceMatcher;
 use iced::advanced::layout::{Limits, Node};
 use iced::advanced::overlay::Element;
 use iced::advanced::renderer::{Quad, Style};
-use iced::advanced::widget::{Operation, Tree};
+use iced::advanced::widget::{operation::Focusable, tree, Operation, Tree};
 use iced::advanced::{Clipboard, Layout, Overlay, Shell, Widget};
 use iced::event::Status;
+use iced::keyboard::key::Named;
+use iced::keyboard::Key;
 use iced::mouse::{Cursor, Interaction};
 use iced::widget::text_input::State;
 use iced::widget::{Column, Text, TextInput};
-use iced::{mouse, Border, Color, Event, Length, Point, Rectangle, Size, Vector};
+use iced::{keyboard, mouse, Border, Color, Event, Length, Point, Rectangle, Size, Vector};
 use std::fmt::Display;
 
+use crate::scene;
 use crate::utils::theme;
+use crate::widgets::description::{self, Description};
 
 /// A widget where the user can input text and is offered choices from a given list of options
 ///that are similar to that input.
@@ -19,7 +23,7 @@ pub struct ComboBox<'a, Tag, Message, Theme, Renderer>
 where
     Tag: 'a + Clone + Display,
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + iced::widget::text_input::Catalog + iced::widget::text::Catalog,
 {
     /// The list of options the user can choose from.
@@ -36,13 +40,16 @@ where
 
     /// The index of the tab that is hovered on.
     tag_hovered: Option<usize>,
+
+    /// The optional [Description] of the [ComboBox].
+    description: Option<Description<Message>>,
 }
 
 impl<'a, Tag, Message, Theme, Renderer> ComboBox<'a, Tag, Message, Theme, Renderer>
 where
     Tag: 'a + Clone + Display,
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + iced::widget::text_input::Catalog + iced::widget::text::Catalog,
 {
     /// Creates a new combo box.
@@ -52,7 +59,11 @@ where
         value: &'a str,
         on_selected: fn(Tag) -> Message,
     ) -> Self {
-        let filtered_tags: Vec<Tag> = filter_tags(tags.into_iter().collect(), value, 10);
+        let filtered_tags: Vec<Tag> = if value.trim().is_empty() {
+            vec![]
+        } else {
+            filter_tags(tags.into_iter().collect(), value, 10)
+        };
 
         ComboBox {
             tags: filtered_tags.clone(),
@@ -66,6 +77,7 @@ where
             .padding(1.0),
             on_selected,
             tag_hovered: None,
+            description: None,
         }
     }
 
@@ -82,6 +94,16 @@ where
 
         self
     }
+
+    /// Sets the description of the [ComboBox], announced to screen readers when it gains focus.
+    pub fn describe(mut self, text: impl Into<String>) -> Self
+    where
+        Message: From<scene::Message>,
+    {
+        self.description = Some(Description::new(text));
+
+        self
+    }
 }
 
 impl<'a, Tag, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -89,9 +111,17 @@ impl<'a, Tag, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
 where
     Tag: 'a + Clone + Display,
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + iced::widget::text_input::Catalog + iced::widget::text::Catalog,
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<description::State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(description::State::default())
+    }
+
     fn size(&self) -> Size<Length> {
         (&self.text_input as &dyn Widget<Message, Theme, Renderer>).size()
     }
@@ -158,6 +188,22 @@ where
         shell: &mut Shell<'_, Message>,
         viewport: &Rectangle,
     ) -> Status {
+        if let Some(description) = &self.description {
+            // The keyboard focus of the combo box is the focus of its text input.
+            let focused = state.children[0]
+                .state
+                .downcast_ref::<State<Renderer::Paragraph>>()
+                .is_focused();
+            let description_state = state.state.downcast_mut::<description::State>();
+            if focused {
+                description_state.focus();
+            } else {
+                description_state.unfocus();
+            }
+
+            description.on_event(description_state, &event, layout.bounds(), cursor, shell);
+        }
+
         self.text_input.on_event(
             &mut state.children[0],
             event,
@@ -189,6 +235,14 @@ where
         renderer: &Renderer,
         translation: Vector,
     ) -> Option<Element<'b, Message, Theme, Renderer>> {
+        let description_overlay = self.description.as_ref().and_then(|description| {
+            description.overlay(
+                state.state.downcast_ref::<description::State>(),
+                layout.bounds(),
+                translation,
+            )
+        });
+
         let mut children = state.children.iter_mut();
         let state_input = children.next().expect("Need to have text input child.");
         let is_focused = state_input
@@ -199,7 +253,7 @@ where
             self.text_input
                 .overlay(state_input, layout, renderer, translation);
 
-        if self.tags.len() > 0 && is_focused {
+        let combo_box_overlay = if self.tags.len() > 0 && is_focused {
             let bounds = layout.bounds();
             let column = Column::<Message, Theme, Renderer>::with_children(
                 self.tags
@@ -235,7 +289,9 @@ where
             }
         } else {
             text_input_overlay
-        }
+        };
+
+        description::group(combo_box_overlay, description_overlay)
     }
 }
 
@@ -244,7 +300,7 @@ impl<'a, Tag, Message, Theme, Renderer> From<ComboBox<'a, Tag, Message, Theme, R
 where
     Tag: 'a + Clone + Display,
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + iced::widget::text_input::Catalog + iced::widget::text::Catalog,
 {
     fn from(value: ComboBox<'a, Tag, Message, Theme, Renderer>) -> Self {
@@ -302,6 +358,45 @@ where
             tag_hovered,
         }
     }
+
+    /// Moves the hovered tag with the arrow keys, selects it on enter and closes the [Menu] on
+    /// escape.
+    fn on_key_pressed(&mut self, key: Named, shell: &mut Shell<'_, Message>) -> Status {
+        match key {
+            Named::ArrowDown => {
+                *self.tag_hovered = Some(match *self.tag_hovered {
+                    Some(index) if index + 1 < self.tags.len() => index + 1,
+                    Some(index) => index,
+                    None => 0,
+                });
+
+                Status::Captured
+            }
+            Named::ArrowUp => {
+                *self.tag_hovered = match *self.tag_hovered {
+                    Some(index) if index > 0 => Some(index - 1),
+                    _ => None,
+                };
+
+                Status::Captured
+            }
+            Named::Enter => match self.tag_hovered.take() {
+                Some(index) if index < self.tags.len() => {
+                    shell.publish((self.on_select)(self.tags[index].clone()));
+
+                    Status::Captured
+                }
+                _ => Status::Ignored,
+            },
+            Named::Escape => {
+                *self.tag_hovered = None;
+                self.tags.clear();
+
+                Status::Captured
+            }
+            _ => Status::Ignored,
+        }
+    }
 }
 
 impl<'a, 'b, Tag, Message, Theme, Renderer> Overlay<Message, Theme, Renderer>
@@ -413,8 +508,8 @@ where
                     let mut bounds = node.bounds();
                     bounds.width = width;
 
-                    if cursor.is_over(bounds) {
-                        shell.publish((self.on_select)(self.tags[index].clone()));
+                    if let (true, Some(tag)) = (cursor.is_over(bounds), self.tags.get(index)) {
+                        shell.publish((self.on_select)(tag.clone()));
                         return Status::Captured;
                     }
 
@@ -451,6 +546,10 @@ where
                 *self.tag_hovered = None;
                 Status::Ignored
             }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Named(key),
+                ..
+            }) => self.on_key_pressed(key, shell),
             _ => Status::Ignored,
         }
     }
@@ -472,27 +571,289 @@ where
     }
 }
 
-/// Filters the given list of tags and returns only the ones similar to the user input.
-fn filter_tags<Tag>(tags: Vec<Tag>, user_input: &str, count: usize) -> Vec<Tag>
+/// The similarity a tag needs to have with the user input to be offered.
+const MIN_SCORE: f64 = 0.35;
+
+/// Returns how similar the two strings are, between 0 and 1.
+fn similarity(first: &str, second: &str) -> f64 {
+    let (first, second) = (first.to_string(), second.to_string());
+    let mut matcher = SequenceMatcher::new(&first, &second);
+
+    matcher.ratio() as f64
+}
+
+/// Returns how similar the tag name is to the user input. If the input has several
+/// space-separated terms, every one of them has to be similar to a word of the tag name, and
+/// the score is their average; None is returned if one of them matches no word.
+fn get_score(user_input: &str, tag_name: &str) -> Option<f64> {
+    if user_input == tag_name {
+        return Some(1.0);
+    }
+
+    let terms: Vec<&str> = user_input.split_whitespace().collect();
+
+    if terms.len() < 2 {
+        return Some(similarity(user_input, tag_name));
+    }
+
+    let words: Vec<&str> = tag_name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut total = 0.0;
+    for term in &terms {
+        let best = words
+            .iter()
+            .map(|word| similarity(term, word))
+            .fold(0.0, f64::max);
+
+        if best <= MIN_SCORE {
+            return None;
+        }
+        total += best;
+    }
+
+    Some(total / terms.len() as f64)
+}
+
+/// Filters the given list of tags and returns at most count of the ones similar to the user
+/// input, the most similar first. Tags that match the input exactly come before all others.
+/// If the input is blank, the first count tags are returned.
+pub(crate) fn filter_tags<Tag>(tags: Vec<Tag>, user_input: &str, count: usize) -> Vec<Tag>
 where
     Tag: Clone + Display,
 {
-    let mut filtered: Vec<(usize, f64)> = vec![];
     let user_input = user_input.to_lowercase();
 
+    if user_input.trim().is_empty() {
+        return tags.into_iter().take(count).collect();
+    }
+
+    let mut filtered: Vec<(usize, bool, f64)> = vec![];
     for (tag, i) in tags.iter().zip(0..tags.len()) {
         let tag_name = tag.to_string().to_lowercase();
 
-        let mut matcher = SequenceMatcher::new(&user_input, &tag_name);
-        let score = matcher.ratio() as f64;
+        let Some(score) = get_score(&user_input, &tag_name) else {
+            continue;
+        };
 
-        if score > 0.35 {
-            if filtered.len() < count {
-                filtered.push((i, score));
-            }
+        if score > MIN_SCORE {
+            filtered.push((i, tag_name == user_input, score));
         }
     }
 
-    filtered.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-    filtered.iter().map(|(pos, _)| tags[*pos].clone()).collect()
+    // Tags whose words are a permutation of the terms also score 1, so exact matches are
+    // ordered separately.
+    filtered.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.total_cmp(&a.2)));
+    filtered.truncate(count);
+    filtered
+        .iter()
+        .map(|(pos, _, _)| tags[*pos].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_tags, get_score, MIN_SCORE};
+    use proptest::prelude::*;
+
+    const TAGS: [&str; 10] = [
+        "digital art",
+        "pixel art",
+        "landscape",
+        "portrait",
+        "abstract",
+        "sketch",
+        "watercolor painting",
+        "oil painting",
+        "digital painting",
+        "concept art",
+    ];
+
+    fn filter(user_input: &str) -> Vec<&'static str> {
+        filter_tags(TAGS.to_vec(), user_input, 10)
+    }
+
+    #[test]
+    fn exact_match_comes_first() {
+        assert_eq!(filter("landscape").first(), Some(&"landscape"));
+        assert_eq!(filter("sketch").first(), Some(&"sketch"));
+    }
+
+    #[test]
+    fn matching_ignores_case() {
+        assert_eq!(filter("LandScape").first(), Some(&"landscape"));
+    }
+
+    #[test]
+    fn partial_match() {
+        assert_eq!(filter("land").first(), Some(&"landscape"));
+        assert_eq!(filter("pixel"), vec!["pixel art"]);
+    }
+
+    #[test]
+    fn empty_input_returns_the_first_tags() {
+        assert_eq!(filter(""), TAGS.to_vec());
+        assert_eq!(filter_tags(TAGS.to_vec(), "", 3), TAGS[..3].to_vec());
+    }
+
+    #[test]
+    fn blank_input_returns_the_first_tags() {
+        assert_eq!(filter_tags(TAGS.to_vec(), "   ", 3), TAGS[..3].to_vec());
+    }
+
+    #[test]
+    fn unrelated_input_matches_nothing() {
+        assert!(filter("zzzz").is_empty());
+    }
+
+    #[test]
+    fn multiple_terms_match_in_any_order() {
+        assert_eq!(filter("art digital").first(), Some(&"digital art"));
+        assert_eq!(filter("paint oil").first(), Some(&"oil painting"));
+    }
+
+    #[test]
+    fn multiple_partial_terms() {
+        let filtered = filter("digi paint");
+
+        assert_eq!(filtered.first(), Some(&"digital painting"));
+        assert!(!filtered.contains(&"landscape"));
+    }
+
+    #[test]
+    fn every_term_has_to_match() {
+        assert!(filter("digital zzzz").is_empty());
+        assert_eq!(get_score("oil zzzz", "oil painting"), None);
+    }
+
+    #[test]
+    fn multiple_terms_match_words_of_the_tag() {
+        let filtered = filter("water paint");
+
+        assert_eq!(filtered.first(), Some(&"watercolor painting"));
+        assert!(!filtered.contains(&"sketch"));
+    }
+
+    #[test]
+    fn exact_multiple_terms_score_highest() {
+        assert_eq!(get_score("oil painting", "oil painting"), Some(1.0));
+    }
+
+    #[test]
+    fn count_limits_the_results() {
+        assert_eq!(filter_tags(TAGS.to_vec(), "art", 2).len(), 2);
+    }
+
+    #[test]
+    fn count_is_applied_after_ranking() {
+        let tags = vec!["digital art", "pixel art", "concept art", "art"];
+
+        assert_eq!(filter_tags(tags, "art", 1), vec!["art"]);
+    }
+
+    #[test]
+    fn exact_match_comes_before_permutations() {
+        let tags = vec!["painting oil", "oil painting"];
+
+        assert_eq!(get_score("oil painting", "painting oil"), Some(1.0));
+        assert_eq!(
+            filter_tags(tags, "oil painting", 2),
+            vec!["oil painting", "painting oil"]
+        );
+    }
+
+    #[test]
+    fn exact_match_is_kept_when_its_words_differ_from_the_terms() {
+        assert_eq!(get_score("x-yyyyyyyy z", "x-yyyyyyyy z"), Some(1.0));
+        assert_eq!(
+            filter_tags(vec!["x-yyyyyyyy z"], "x-yyyyyyyy z", 1),
+            vec!["x-yyyyyyyy z"]
+        );
+    }
+
+    #[test]
+    fn punctuation_separates_words() {
+        assert_eq!(get_score("sci fi", "sci-fi"), Some(1.0));
+    }
+
+    #[test]
+    fn multiple_terms_score_is_the_average_of_the_terms() {
+        let score = get_score("oil paint", "oil painting").unwrap();
+
+        assert!(score > MIN_SCORE && score < 1.0);
+        assert!(score > get_score("oil paint", "digital painting").unwrap());
+    }
+
+    fn tags_strategy() -> impl Strategy<Value = Vec<String>> {
+        prop::collection::vec("[a-z]{1,8}([ -][a-z]{1,8}){0,2}", 0..20)
+    }
+
+    proptest! {
+        #[test]
+        fn output_never_exceeds_count(
+            tags in tags_strategy(),
+            user_input in "\\PC{0,12}",
+            count in 0usize..15,
+        ) {
+            prop_assert!(filter_tags(tags, &user_input, count).len() <= count);
+        }
+
+        #[test]
+        fn output_only_contains_given_tags(
+            tags in tags_strategy(),
+            user_input in "[a-zA-Z ]{0,12}",
+            count in 0usize..15,
+        ) {
+            for tag in filter_tags(tags.clone(), &user_input, count) {
+                prop_assert!(tags.contains(&tag));
+            }
+        }
+
+        #[test]
+        fn blank_input_returns_up_to_count_tags(
+            tags in tags_strategy(),
+            user_input in " {0,5}",
+            count in 0usize..15,
+        ) {
+            let expected = tags.iter().take(count).cloned().collect::<Vec<String>>();
+
+            prop_assert_eq!(filter_tags(tags, &user_input, count), expected);
+        }
+
+        #[test]
+        fn exact_match_ranks_first(
+            tags in tags_strategy().prop_filter("needs a tag", |tags| !tags.is_empty()),
+            index in any::<prop::sample::Index>(),
+            uppercase in any::<bool>(),
+            count in 1usize..15,
+        ) {
+            let tag = index.get(&tags).clone();
+            let user_input = if uppercase { tag.to_uppercase() } else { tag.clone() };
+
+            let filtered = filter_tags(tags, &user_input, count);
+
+            prop_assert_eq!(filtered.first(), Some(&tag));
+        }
+
+        #[test]
+        fn arbitrary_exact_match_ranks_first(
+            tag in "\\PC{1,16}",
+            other_tags in prop::collection::vec("\\PC{0,16}", 0..10),
+            count in 1usize..15,
+        ) {
+            prop_assume!(!tag.trim().is_empty());
+
+            let mut tags = other_tags;
+            tags.push(tag.clone());
+
+            let filtered = filter_tags(tags, &tag, count);
+
+            prop_assert_eq!(
+                filtered.first().map(|first| first.to_lowercase()),
+                Some(tag.to_lowercase())
+            );
+        }
+    }
 }
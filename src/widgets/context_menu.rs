@@ -0,0 +1,392 @@
+use iced::advanced::layout::{Limits, Node};
+use iced::advanced::overlay::Element as OverlayElement;
+use iced::advanced::renderer::{Quad, Style};
+use iced::advanced::text::{LineHeight, Shaping};
+use iced::advanced::widget::{tree, Operation, Tree};
+use iced::advanced::{Clipboard, Layout, Overlay, Shell, Text, Widget};
+use iced::alignment::{Horizontal, Vertical};
+use iced::event::Status;
+use iced::keyboard::key::Named;
+use iced::keyboard::Key;
+use iced::mouse::{Cursor, Interaction};
+use iced::{
+    keyboard, mouse, Border, Color, Element, Event, Length, Point, Rectangle, Size, Vector,
+};
+
+use crate::utils::theme;
+
+/// The width of the menu.
+const MENU_WIDTH: f32 = 160.0;
+
+/// The height of an item of the menu.
+const ITEM_HEIGHT: f32 = 28.0;
+
+/// The text size of the items of the menu.
+const TEXT_SIZE: f32 = 16.0;
+
+/// The padding between the text of an item and the left edge of the menu.
+const ITEM_PADDING: f32 = 10.0;
+
+/// The state of a [ContextMenu].
+#[derive(Default)]
+struct State {
+    /// The position the menu was opened at. Is None if the menu is closed.
+    position: Option<Point>,
+
+    /// The index of the item the user is hovering on.
+    item_hovered: Option<usize>,
+}
+
+/// A widget that opens a menu of actions when its content is right-clicked.
+pub struct ContextMenu<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    /// The content the menu is opened on.
+    content: Element<'a, Message, Theme, Renderer>,
+
+    /// The labels of the items of the menu and the messages triggered when they are clicked.
+    items: Vec<(String, Message)>,
+}
+
+impl<'a, Message, Theme, Renderer> ContextMenu<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    /// Creates a new [ContextMenu] over the given content, with the given items.
+    pub fn new(
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+        items: Vec<(String, Message)>,
+    ) -> Self {
+        ContextMenu {
+            content: content.into(),
+            items,
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for ContextMenu<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content])
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> Status {
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) = event {
+            if let Some(position) = cursor.position_over(layout.bounds()) {
+                if !self.items.is_empty() {
+                    let state = tree.state.downcast_mut::<State>();
+                    state.position = Some(position);
+                    state.item_hovered = None;
+
+                    return Status::Captured;
+                }
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<OverlayElement<'b, Message, Theme, Renderer>> {
+        if let Some(position) = tree.state.downcast_ref::<State>().position {
+            Some(OverlayElement::new(Box::new(Menu {
+                state: tree.state.downcast_mut::<State>(),
+                items: &self.items,
+                position: position + translation,
+            })))
+        } else {
+            self.content.as_widget_mut().overlay(
+                &mut tree.children[0],
+                layout,
+                renderer,
+                translation,
+            )
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<ContextMenu<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    fn from(value: ContextMenu<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(value)
+    }
+}
+
+/// The overlay of a [ContextMenu] that displays its items.
+struct Menu<'b, Message>
+where
+    Message: Clone,
+{
+    /// The state of the [ContextMenu].
+    state: &'b mut State,
+
+    /// The items of the menu.
+    items: &'b [(String, Message)],
+
+    /// The position the menu was opened at.
+    position: Point,
+}
+
+impl<'b, Message> Menu<'b, Message>
+where
+    Message: Clone,
+{
+    /// Returns the index of the item under the cursor, if any.
+    fn get_item(&self, layout: Layout<'_>, cursor: Cursor) -> Option<usize> {
+        let bounds = layout.bounds();
+
+        cursor
+            .position_over(bounds)
+            .map(|position| ((position.y - bounds.y) / ITEM_HEIGHT) as usize)
+            .filter(|index| *index < self.items.len())
+    }
+
+    /// Closes the menu.
+    fn close(&mut self) {
+        self.state.position = None;
+        self.state.item_hovered = None;
+    }
+}
+
+impl<'b, Message, Theme, Renderer> Overlay<Message, Theme, Renderer> for Menu<'b, Message>
+where
+    Message: Clone,
+    Renderer: iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    fn layout(&mut self, _renderer: &Renderer, bounds: Size) -> Node {
+        let size = Size::new(MENU_WIDTH, ITEM_HEIGHT * self.items.len() as f32);
+
+        // The menu is kept on the screen, opening to the left or upwards if there is no room.
+        let x = if self.position.x + size.width > bounds.width {
+            (self.position.x - size.width).max(0.0)
+        } else {
+            self.position.x
+        };
+        let y = if self.position.y + size.height > bounds.height {
+            (self.position.y - size.height).max(0.0)
+        } else {
+            self.position.y
+        };
+
+        Node::new(size).move_to(Point::new(x, y))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+    ) {
+        let bounds = layout.bounds();
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+
+        for (index, (label, _)) in self.items.iter().enumerate() {
+            let item_bounds = Rectangle {
+                y: bounds.y + index as f32 * ITEM_HEIGHT,
+                height: ITEM_HEIGHT,
+                ..bounds
+            };
+
+            renderer.fill_quad(
+                Quad {
+                    bounds: item_bounds,
+                    border: Default::default(),
+                    shadow: Default::default(),
+                },
+                if self.state.item_hovered == Some(index) {
+                    palette.background.strong.color
+                } else {
+                    palette.background.base.color
+                },
+            );
+
+            renderer.fill_text(
+                Text {
+                    content: label.clone(),
+                    bounds: Size::new(item_bounds.width - ITEM_PADDING, ITEM_HEIGHT),
+                    size: TEXT_SIZE.into(),
+                    line_height: LineHeight::default(),
+                    font: renderer.default_font(),
+                    horizontal_alignment: Horizontal::Left,
+                    vertical_alignment: Vertical::Center,
+                    shaping: Shaping::Basic,
+                },
+                Point::new(item_bounds.x + ITEM_PADDING, item_bounds.center_y()),
+                palette.background.base.text,
+                item_bounds,
+            );
+        }
+
+        renderer.fill_quad(
+            Quad {
+                bounds,
+                border: Border {
+                    color: palette.background.base.text,
+                    width: 1.0,
+                    radius: Default::default(),
+                },
+                shadow: Default::default(),
+            },
+            Color::TRANSPARENT,
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> Status {
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                self.state.item_hovered = self.get_item(layout, cursor);
+
+                Status::Ignored
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => {
+                if let Some(index) = self.get_item(layout, cursor) {
+                    shell.publish(self.items[index].1.clone());
+                }
+                self.close();
+
+                Status::Captured
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Named(Named::Escape),
+                ..
+            }) => {
+                self.close();
+
+                Status::Captured
+            }
+            _ => Status::Ignored,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> Interaction {
+        if cursor.is_over(layout.bounds()) {
+            Interaction::Pointer
+        } else {
+            Interaction::default()
+        }
+    }
+}
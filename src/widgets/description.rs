@@ -0,0 +1,247 @@
+use crate::scene;
+use crate::utils::theme;
+use iced::advanced::layout::Node;
+use iced::advanced::renderer::{Quad, Style};
+use iced::advanced::text::{LineHeight, Paragraph, Shaping};
+use iced::advanced::widget::{operation::Focusable, Operation};
+use iced::advanced::{overlay, Layout, Overlay, Shell, Text};
+use iced::alignment::{Horizontal, Vertical};
+use iced::keyboard::{self, key::Named, Key};
+use iced::mouse::Cursor;
+use iced::{mouse, touch, Border, Color, Event, Padding, Point, Rectangle, Size, Vector};
+
+/// The text size of a tooltip.
+const TOOLTIP_TEXT_SIZE: f32 = 14.0;
+/// The padding between the text of a tooltip and its border.
+const TOOLTIP_PADDING: f32 = 4.0;
+/// The distance between a tooltip and the bounds it describes.
+const TOOLTIP_GAP: f32 = 4.0;
+
+/// The state of a described widget.
+#[derive(Default)]
+pub struct State {
+    /// Tells whether the widget has focus.
+    focused: bool,
+
+    /// Tells whether the description has been announced since the widget gained focus.
+    announced: bool,
+
+    /// Tells whether the Tab key is being held, which shows the description as a tooltip.
+    tab_held: bool,
+}
+
+impl Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// The semantic description of a widget, announced to screen readers through the
+/// [accessibility log](crate::scene::Globals::get_accessibility_log) when the widget gains focus,
+/// either by being pressed or by moving the keyboard focus to it with Tab.
+pub struct Description<Message> {
+    /// The text of the description.
+    text: String,
+
+    /// The message announcing the description.
+    on_focus: Message,
+}
+
+impl<Message> Description<Message>
+where
+    Message: Clone,
+{
+    /// Creates a [Description] with the given text.
+    pub fn new(text: impl Into<String>) -> Self
+    where
+        Message: From<scene::Message>,
+    {
+        let text = text.into();
+
+        Description {
+            on_focus: scene::Message::Describe(text.clone()).into(),
+            text,
+        }
+    }
+
+    /// Makes the widget reachable by the keyboard focus traversal.
+    pub fn operate(&self, state: &mut State, operation: &mut dyn Operation<Message>) {
+        operation.focusable(state, None);
+    }
+
+    /// Updates the [State] of the widget with the given bounds. The widget gains focus when it is
+    /// pressed, and loses it when anything else is pressed. The description is announced once
+    /// the widget has gained focus, including through the keyboard focus traversal.
+    pub fn on_event(
+        &self,
+        state: &mut State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => state.focused = cursor.is_over(bounds),
+            Event::Touch(touch::Event::FingerPressed { position, .. }) => {
+                state.focused = bounds.contains(*position)
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Named(Named::Tab),
+                ..
+            }) => state.tab_held = true,
+            Event::Keyboard(keyboard::Event::KeyReleased {
+                key: Key::Named(Named::Tab),
+                ..
+            }) => state.tab_held = false,
+            _ => {}
+        }
+
+        if state.focused && !state.announced {
+            shell.publish(self.on_focus.clone());
+        }
+        state.announced = state.focused;
+    }
+
+    /// Returns the tooltip showing the description above the given bounds, while the Tab key is
+    /// held.
+    pub fn overlay<'b, Theme, Renderer>(
+        &self,
+        state: &State,
+        bounds: Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>>
+    where
+        Renderer: iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+    {
+        state.tab_held.then(|| {
+            overlay::Element::new(Box::new(TooltipOverlay::new(
+                self.text.clone(),
+                bounds + translation,
+                1.0,
+            )))
+        })
+    }
+}
+
+/// Groups the overlay of a widget with the tooltip of its [Description].
+pub fn group<'b, Message, Theme, Renderer>(
+    overlay: Option<overlay::Element<'b, Message, Theme, Renderer>>,
+    tooltip: Option<overlay::Element<'b, Message, Theme, Renderer>>,
+) -> Option<overlay::Element<'b, Message, Theme, Renderer>>
+where
+    Message: 'b,
+    Theme: 'b,
+    Renderer: 'b + iced::advanced::Renderer,
+{
+    match (overlay, tooltip) {
+        (Some(overlay), Some(tooltip)) => {
+            Some(overlay::Group::with_children(vec![overlay, tooltip]).overlay())
+        }
+        (overlay, None) => overlay,
+        (None, tooltip) => tooltip,
+    }
+}
+
+/// A tooltip centered above the bounds it describes.
+pub(crate) struct TooltipOverlay {
+    /// The text of the tooltip.
+    text: String,
+
+    /// The bounds the tooltip is centered above.
+    anchor: Rectangle,
+
+    /// The opacity of the tooltip, as it fades in.
+    alpha: f32,
+}
+
+impl TooltipOverlay {
+    /// Creates a tooltip with the given text and opacity, centered above the given bounds.
+    pub(crate) fn new(text: String, anchor: Rectangle, alpha: f32) -> Self {
+        TooltipOverlay {
+            text,
+            anchor,
+            alpha,
+        }
+    }
+
+    /// Returns the given content with the text settings of the tooltip.
+    fn get_text<Content, Font>(content: Content, font: Font) -> Text<Content, Font> {
+        Text {
+            content,
+            bounds: Size::new(f32::INFINITY, f32::INFINITY),
+            size: TOOLTIP_TEXT_SIZE.into(),
+            line_height: LineHeight::default(),
+            font,
+            horizontal_alignment: Horizontal::Left,
+            vertical_alignment: Vertical::Top,
+            shaping: Shaping::Basic,
+        }
+    }
+}
+
+impl<Message, Theme, Renderer> Overlay<Message, Theme, Renderer> for TooltipOverlay
+where
+    Renderer: iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> Node {
+        let text_size =
+            Renderer::Paragraph::with_text(Self::get_text(&*self.text, renderer.default_font()))
+                .min_bounds();
+        let size = text_size.expand(Padding::new(TOOLTIP_PADDING));
+
+        let x = (self.anchor.center_x() - size.width / 2.0)
+            .clamp(0.0, (bounds.width - size.width).max(0.0));
+        let y = (self.anchor.y - size.height - TOOLTIP_GAP).max(0.0);
+
+        Node::new(size).move_to(Point::new(x, y))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+    ) {
+        let bounds = layout.bounds();
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+
+        renderer.fill_quad(
+            Quad {
+                bounds,
+                border: Border {
+                    color: Color {
+                        a: self.alpha,
+                        ..palette.background.strong.text
+                    },
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                shadow: Default::default(),
+            },
+            Color {
+                a: self.alpha,
+                ..palette.background.strong.color
+            },
+        );
+
+        renderer.fill_text(
+            Self::get_text(self.text.clone(), renderer.default_font()),
+            Point::new(bounds.x + TOOLTIP_PADDING, bounds.y + TOOLTIP_PADDING),
+            Color {
+                a: self.alpha,
+                ..palette.background.strong.text
+            },
+            bounds,
+        );
+    }
+}
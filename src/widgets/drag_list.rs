@@ -0,0 +1,393 @@
+use iced::{
+    advanced::{
+        layout::{Limits, Node},
+        renderer::{Quad, Style},
+        widget::{tree, Operation, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    event::Status,
+    mouse::{self, Cursor, Interaction},
+    Border, Color, Element, Event, Length, Padding, Point, Rectangle, Size, Vector,
+};
+use std::marker::PhantomData;
+
+use crate::utils::theme;
+
+/// The distance the cursor has to move while pressed before an item starts being dragged.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// The thickness of the line showing where the dragged item will be dropped.
+const DROP_LINE_WIDTH: f32 = 2.0;
+
+/// The state of a [DragList].
+#[derive(Default)]
+struct State {
+    /// The item that was pressed and the position it was pressed at; it becomes dragged once
+    /// the cursor moves far enough.
+    pressed: Option<(usize, Point)>,
+
+    /// The item being dragged.
+    dragging: Option<usize>,
+
+    /// The position, between 0 and the number of items, the dragged item would be inserted at.
+    drop_target: Option<usize>,
+}
+
+/// A widget that displays a list of items vertically, which can be reordered by dragging them.
+pub struct DragList<'a, T, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// The elements displaying the items.
+    children: Vec<Element<'a, Message, Theme, Renderer>>,
+
+    /// The message triggered with the old and the new position of a dragged item.
+    on_reorder: fn(usize, usize) -> Message,
+
+    /// The space between the items.
+    spacing: f32,
+
+    /// The padding around the items.
+    padding: Padding,
+
+    /// The width of the [DragList].
+    width: Length,
+
+    /// The type of the displayed items.
+    items: PhantomData<T>,
+}
+
+impl<'a, T, Message, Theme, Renderer> DragList<'a, T, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// Creates a new [DragList] that displays every item with the given function.
+    pub fn new(
+        items: impl IntoIterator<Item = T>,
+        view: impl Fn(T) -> Element<'a, Message, Theme, Renderer>,
+        on_reorder: fn(usize, usize) -> Message,
+    ) -> Self {
+        DragList {
+            children: items.into_iter().map(view).collect(),
+            on_reorder,
+            spacing: 0.0,
+            padding: Padding::ZERO,
+            width: Length::Fill,
+            items: PhantomData,
+        }
+    }
+
+    /// Sets the space between the items.
+    pub fn spacing(mut self, spacing: impl Into<f32>) -> Self {
+        self.spacing = spacing.into();
+
+        self
+    }
+
+    /// Sets the padding around the items.
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+
+        self
+    }
+
+    /// Sets the width of the [DragList].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+
+        self
+    }
+
+    /// Returns the position the dragged item would be inserted at, given the cursor height.
+    fn get_drop_target(layout: Layout<'_>, y: f32) -> usize {
+        layout
+            .children()
+            .position(|child| y < child.bounds().center_y())
+            .unwrap_or(layout.children().count())
+    }
+}
+
+impl<'a, T, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for DragList<'a, T, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, Length::Shrink)
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.children.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&self.children)
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
+        let limits = limits.width(self.width).shrink(self.padding);
+        let child_limits = Limits::new(
+            Size::new(limits.max().width, 0.0),
+            Size::new(limits.max().width, f32::INFINITY),
+        );
+
+        let mut y = self.padding.top;
+        let nodes = self
+            .children
+            .iter()
+            .zip(tree.children.iter_mut())
+            .map(|(child, tree)| {
+                let node = child
+                    .as_widget()
+                    .layout(tree, renderer, &child_limits)
+                    .move_to(Point::new(self.padding.left, y));
+                y += node.size().height + self.spacing;
+
+                node
+            })
+            .collect::<Vec<Node>>();
+
+        let height = if nodes.is_empty() {
+            self.padding.top
+        } else {
+            y - self.spacing
+        };
+
+        Node::with_children(
+            Size::new(
+                limits.max().width + self.padding.horizontal(),
+                height + self.padding.bottom,
+            ),
+            nodes,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        for ((child, state), child_layout) in self
+            .children
+            .iter()
+            .zip(tree.children.iter())
+            .zip(layout.children())
+        {
+            child.as_widget().draw(
+                state,
+                renderer,
+                theme,
+                style,
+                child_layout,
+                cursor,
+                viewport,
+            );
+        }
+
+        let state = tree.state.downcast_ref::<State>();
+        let (Some(dragging), Some(drop_target)) = (state.dragging, state.drop_target) else {
+            return;
+        };
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+
+        if let Some(dragged) = layout.children().nth(dragging) {
+            renderer.fill_quad(
+                Quad {
+                    bounds: dragged.bounds(),
+                    border: Border {
+                        color: palette.primary.strong.color,
+                        width: 1.0,
+                        radius: Default::default(),
+                    },
+                    shadow: Default::default(),
+                },
+                Color::TRANSPARENT,
+            );
+        }
+
+        let bounds = layout.bounds();
+        let y = match layout.children().nth(drop_target) {
+            Some(child) => child.bounds().y - self.spacing / 2.0,
+            None => {
+                layout
+                    .children()
+                    .last()
+                    .map_or(bounds.y, |child| child.bounds().y + child.bounds().height)
+                    + self.spacing / 2.0
+            }
+        };
+
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle {
+                    x: bounds.x + self.padding.left,
+                    y: y - DROP_LINE_WIDTH / 2.0,
+                    width: bounds.width - self.padding.horizontal(),
+                    height: DROP_LINE_WIDTH,
+                },
+                border: Default::default(),
+                shadow: Default::default(),
+            },
+            palette.primary.strong.color,
+        );
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        for ((child, state), child_layout) in self
+            .children
+            .iter()
+            .zip(tree.children.iter_mut())
+            .zip(layout.children())
+        {
+            child
+                .as_widget()
+                .operate(state, child_layout, renderer, operation);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> Status {
+        let state = tree.state.downcast_mut::<State>();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                state.pressed = cursor.position().and_then(|position| {
+                    layout
+                        .children()
+                        .position(|child| child.bounds().contains(position))
+                        .map(|index| (index, position))
+                });
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if let Some((index, start)) = state.pressed {
+                    if state.dragging.is_none() && position.distance(start) > DRAG_THRESHOLD {
+                        state.dragging = Some(index);
+                    }
+                }
+                if state.dragging.is_some() {
+                    state.drop_target = Some(Self::get_drop_target(layout, position.y));
+
+                    return Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                state.pressed = None;
+
+                if let Some(from) = state.dragging.take() {
+                    if let Some(target) = state.drop_target.take() {
+                        // The item is removed before being inserted, so later positions shift.
+                        let to = if target > from { target - 1 } else { target };
+
+                        if to != from {
+                            shell.publish((self.on_reorder)(from, to));
+                        }
+                    }
+
+                    return Status::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        self.children
+            .iter_mut()
+            .zip(tree.children.iter_mut())
+            .zip(layout.children())
+            .map(|((child, state), child_layout)| {
+                child.as_widget_mut().on_event(
+                    state,
+                    event.clone(),
+                    child_layout,
+                    cursor,
+                    renderer,
+                    clipboard,
+                    shell,
+                    viewport,
+                )
+            })
+            .fold(Status::Ignored, Status::merge)
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        if tree.state.downcast_ref::<State>().dragging.is_some() {
+            return Interaction::Grabbing;
+        }
+
+        self.children
+            .iter()
+            .zip(tree.children.iter())
+            .zip(layout.children())
+            .map(|((child, state), child_layout)| {
+                child
+                    .as_widget()
+                    .mouse_interaction(state, child_layout, cursor, viewport, renderer)
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        iced::advanced::overlay::from_children(
+            &mut self.children,
+            tree,
+            layout,
+            renderer,
+            translation,
+        )
+    }
+}
+
+impl<'a, T, Message, Theme, Renderer> From<DragList<'a, T, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    T: 'a,
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn from(value: DragList<'a, T, Message, Theme, Renderer>) -> Self {
+        Element::new(value)
+    }
+}
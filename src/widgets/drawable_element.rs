@@ -0,0 +1,233 @@
+use iced::{
+    advanced::{
+        layout::{Limits, Node},
+        renderer::{Quad, Style},
+        widget::{Operation, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    event::Status,
+    mouse::{Cursor, Interaction},
+    Border, Color, Element, Event, Length, Rectangle, Size, Vector,
+};
+
+use crate::utils::theme;
+
+/// A widget that draws its content at a given opacity, by covering it with the background color.
+/// The content does not receive any events while it is not fully opaque.
+pub struct DrawableElement<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// The content being drawn.
+    content: Element<'a, Message, Theme, Renderer>,
+
+    /// The opacity of the content. Takes value in [0, 1].
+    opacity: f32,
+}
+
+impl<'a, Message, Theme, Renderer> DrawableElement<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// Creates a fully opaque [DrawableElement] with the given content.
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        DrawableElement {
+            content: content.into(),
+            opacity: 1.0,
+        }
+    }
+
+    /// Sets the opacity of the content.
+    pub fn opacity(mut self, opacity: impl Into<f32>) -> Self {
+        self.opacity = opacity.into().clamp(0.0, 1.0);
+
+        self
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for DrawableElement<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content])
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
+        let content_node = self
+            .content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits);
+
+        Node::with_children(content_node.size(), vec![content_node])
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("DrawableElement needs to have content node.");
+
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            content_layout,
+            cursor,
+            viewport,
+        );
+
+        if self.opacity == 1.0 {
+            return;
+        }
+
+        let background = theme::extended_palette_generator(theme::PALETTE)
+            .background
+            .base
+            .color;
+
+        renderer.with_layer(layout.bounds(), |renderer| {
+            renderer.fill_quad(
+                Quad {
+                    bounds: layout.bounds(),
+                    border: Border::default(),
+                    shadow: Default::default(),
+                },
+                Color {
+                    a: 1.0 - self.opacity,
+                    ..background
+                },
+            );
+        });
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        if self.opacity < 1.0 {
+            return Interaction::Idle;
+        }
+
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("DrawableElement needs to have content node.");
+
+        self.content.as_widget().mouse_interaction(
+            &state.children[0],
+            content_layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn on_event(
+        &mut self,
+        state: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> Status {
+        if self.opacity < 1.0 {
+            return Status::Ignored;
+        }
+
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("DrawableElement needs to have content node.");
+
+        self.content.as_widget_mut().on_event(
+            &mut state.children[0],
+            event,
+            content_layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn operate(
+        &self,
+        state: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("DrawableElement needs to have content node.");
+
+        self.content.as_widget().operate(
+            &mut state.children[0],
+            content_layout,
+            renderer,
+            operation,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        let content_layout = layout
+            .children()
+            .next()
+            .expect("DrawableElement needs to have content node.");
+
+        self.content.as_widget_mut().overlay(
+            &mut state.children[0],
+            content_layout,
+            renderer,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<DrawableElement<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn from(value: DrawableElement<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(value)
+    }
+}
@@ -1,20 +1,40 @@
+pub mod avatar;
+pub mod badge;
 pub mod card;
 pub mod centered;
+pub mod checkbox_list;
 pub mod close;
 pub mod closeable;
 pub mod color_picker;
 pub mod combo_box;
+pub mod context_menu;
+pub mod description;
+pub mod drag_list;
+pub mod drawable_element;
 pub mod grid;
 pub mod modal_stack;
+pub mod number_input;
 pub mod post_summary;
+pub mod progress_ring;
 pub mod rating;
+pub mod resizable;
+pub mod skeleton;
+pub mod splitter;
 pub mod tabs;
+pub mod toast;
 pub mod wait_panel;
 
+pub type Avatar = avatar::Avatar;
+
+pub type Badge<'a, Message, Theme, Renderer> = badge::Badge<'a, Message, Theme, Renderer>;
+
 pub type Card<'a, Message, Theme, Renderer> = card::Card<'a, Message, Theme, Renderer>;
 
 pub type Centered<'a, Message, Theme, Renderer> = centered::Centered<'a, Message, Theme, Renderer>;
 
+pub type CheckboxList<'a, T, Message, Theme, Renderer> =
+    checkbox_list::CheckboxList<'a, T, Message, Theme, Renderer>;
+
 pub type Close<Message> = close::Close<Message>;
 
 pub type Closeable<'a, Message, Theme, Renderer> =
@@ -25,15 +45,37 @@ pub type ColorPicker<Message> = color_picker::ColorPicker<Message>;
 pub type ComboBox<'a, Tag, Message, Theme, Renderer> =
     combo_box::ComboBox<'a, Tag, Message, Theme, Renderer>;
 
+pub type ContextMenu<'a, Message, Theme, Renderer> =
+    context_menu::ContextMenu<'a, Message, Theme, Renderer>;
+
+pub type DragList<'a, T, Message, Theme, Renderer> =
+    drag_list::DragList<'a, T, Message, Theme, Renderer>;
+
+pub type DrawableElement<'a, Message, Theme, Renderer> =
+    drawable_element::DrawableElement<'a, Message, Theme, Renderer>;
+
 pub type Grid<'a, Message, Theme, Renderer> = grid::Grid<'a, Message, Theme, Renderer>;
 
 pub type ModalStack<ModalTypes> = modal_stack::ModalStack<ModalTypes>;
 
+pub type NumberInput<'a, T, Message> = number_input::NumberInput<'a, T, Message>;
+
 pub type PostSummary<'a, Message, Theme, Renderer> =
     post_summary::PostSummary<'a, Message, Theme, Renderer>;
 
+pub type ProgressRing = progress_ring::ProgressRing;
+
 pub type Rating<F, Message> = rating::Rating<F, Message>;
 
+pub type Resizable<'a, Message, Theme, Renderer> =
+    resizable::Resizable<'a, Message, Theme, Renderer>;
+
+pub type Skeleton = skeleton::Skeleton;
+
+pub type Splitter<'a, Message, Theme, Renderer> = splitter::Splitter<'a, Message, Theme, Renderer>;
+
 pub type Tabs<'a, Type, Message, Theme, Renderer> = tabs::Tabs<'a, Type, Message, Theme, Renderer>;
 
+pub type Toasts<'a, Message> = toast::Toasts<'a, Message>;
+
 pub type WaitPanel = wait_panel::WaitPanel;
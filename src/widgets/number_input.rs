@@ -0,0 +1,164 @@
+use iced::alignment::Horizontal;
+use iced::widget::{Button, Row, Text, TextInput};
+use iced::{Alignment, Element, Length};
+use num_traits::{Bounded, Num};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The space between the text field and the buttons.
+const DEFAULT_SPACING: f32 = 2.0;
+
+/// The width of the increment and decrement buttons.
+const BUTTON_WIDTH: f32 = 25.0;
+
+/// A text field that only accepts numbers, flanked by buttons that decrement and increment its
+/// value.
+pub struct NumberInput<'a, T, Message>
+where
+    T: Num + Bounded + PartialOrd + Copy + Display + FromStr,
+{
+    /// The current value.
+    value: T,
+
+    /// The smallest value that can be set.
+    min: T,
+
+    /// The largest value that can be set.
+    max: T,
+
+    /// The amount the buttons change the value by.
+    step: T,
+
+    /// The function triggered with every validated value.
+    on_change: Box<dyn Fn(T) -> Message + 'a>,
+
+    /// The width of the text field.
+    width: Length,
+}
+
+impl<'a, T, Message> NumberInput<'a, T, Message>
+where
+    T: Num + Bounded + PartialOrd + Copy + Display + FromStr,
+{
+    /// Creates a [NumberInput] with the given value and update function.
+    pub fn new(value: T, on_change: impl Fn(T) -> Message + 'a) -> Self {
+        NumberInput {
+            value,
+            min: T::min_value(),
+            max: T::max_value(),
+            step: T::one(),
+            on_change: Box::new(on_change),
+            width: Length::Fill,
+        }
+    }
+
+    /// Sets the smallest value that can be set.
+    pub fn min(mut self, min: T) -> Self {
+        self.min = min;
+
+        self
+    }
+
+    /// Sets the largest value that can be set.
+    pub fn max(mut self, max: T) -> Self {
+        self.max = max;
+
+        self
+    }
+
+    /// Sets the amount the buttons change the value by.
+    pub fn step(mut self, step: T) -> Self {
+        self.step = step;
+
+        self
+    }
+
+    /// Sets the width of the text field.
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+
+        self
+    }
+
+    /// Returns the given value, limited to the bounds of the [NumberInput].
+    fn clamp(value: T, min: T, max: T) -> T {
+        if value < min {
+            min
+        } else if value > max {
+            max
+        } else {
+            value
+        }
+    }
+
+    /// Returns the value after pressing the increment button.
+    fn get_incremented(&self) -> T {
+        if self.value > self.max - self.step {
+            self.max
+        } else {
+            self.value + self.step
+        }
+    }
+
+    /// Returns the value after pressing the decrement button.
+    fn get_decremented(&self) -> T {
+        if self.value < self.min + self.step {
+            self.min
+        } else {
+            self.value - self.step
+        }
+    }
+}
+
+impl<'a, T, Message, Theme, Renderer> From<NumberInput<'a, T, Message>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    T: 'a + Num + Bounded + PartialOrd + Copy + Display + FromStr,
+    Message: 'a + Clone,
+    Theme: 'a
+        + iced::widget::button::Catalog
+        + iced::widget::text::Catalog
+        + iced::widget::text_input::Catalog,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer,
+{
+    fn from(value: NumberInput<'a, T, Message>) -> Self {
+        let decrement =
+            (value.value > value.min).then(|| (value.on_change)(value.get_decremented()));
+        let increment =
+            (value.value < value.max).then(|| (value.on_change)(value.get_incremented()));
+
+        let (current, min, max) = (value.value, value.min, value.max);
+        let on_change = value.on_change;
+        let parse_input = move |input: String| {
+            if input.is_empty() {
+                on_change(NumberInput::<T, Message>::clamp(T::zero(), min, max))
+            } else if let Ok(number) = input.parse::<T>() {
+                on_change(NumberInput::<T, Message>::clamp(number, min, max))
+            } else {
+                on_change(current)
+            }
+        };
+
+        let button = |text: &'static str, message: Option<Message>| {
+            Button::new(
+                Text::new(text)
+                    .width(Length::Fill)
+                    .horizontal_alignment(Horizontal::Center),
+            )
+            .width(Length::Fixed(BUTTON_WIDTH))
+            .on_press_maybe(message)
+        };
+
+        Row::with_children(vec![
+            button("-", decrement).into(),
+            TextInput::new("0", &current.to_string())
+                .on_input(parse_input)
+                .width(value.width)
+                .into(),
+            button("+", increment).into(),
+        ])
+        .align_items(Alignment::Center)
+        .spacing(DEFAULT_SPACING)
+        .into()
+    }
+}
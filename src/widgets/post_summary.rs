@@ -1,6 +1,8 @@
+use crate::scene;
+use crate::widgets::description::{self, Description};
 use iced::advanced::layout::{Limits, Node};
 use iced::advanced::renderer::{Quad, Style};
-use iced::advanced::widget::{Operation, Tree};
+use iced::advanced::widget::{tree, Operation, Tree};
 use iced::advanced::{Clipboard, Layout, Shell, Widget};
 use iced::event::Status;
 use iced::mouse::{Cursor, Interaction};
@@ -12,11 +14,30 @@ use iced::{
 /// The default padding of the image in the [post summary](PostSummary).
 const DEFAULT_PADDING: f32 = 8.0;
 
+/// The tint drawn over the [post summary](PostSummary) while it is hovered over.
+const HOVERED_TINT: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.05);
+
+/// The tint drawn over the [post summary](PostSummary) while it is pressed.
+const PRESSED_TINT: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.1);
+
+/// The state of a [post summary](PostSummary).
+#[derive(Default)]
+struct State {
+    /// Tells whether the cursor is over the [post summary](PostSummary).
+    hovered: bool,
+
+    /// Tells whether the [post summary](PostSummary) is being pressed.
+    pressed: bool,
+
+    /// The state of the [Description] of the [post summary](PostSummary).
+    description: description::State,
+}
+
 /// A widget which represents the summary of the post. Will present the image and basic data.
 pub struct PostSummary<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
     /// The padding of the image associated to the post.
@@ -36,12 +57,15 @@ where
 
     /// The style of the [post summary](PostSummary).
     style: <Theme as StyleSheet>::Style,
+
+    /// The optional [Description] of the [post summary](PostSummary).
+    description: Option<Description<Message>>,
 }
 
 impl<'a, Message, Theme, Renderer> PostSummary<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
     /// Creates a new [post summary](PostSummary), given the posts image.
@@ -56,6 +80,7 @@ where
             on_click_data: None,
             on_click_image: None,
             style: <Theme as StyleSheet>::Style::default(),
+            description: None,
         }
     }
 
@@ -86,15 +111,34 @@ where
 
         self
     }
+
+    /// Sets the description of the [post summary](PostSummary), announced to screen readers when
+    /// it gains focus.
+    pub fn describe(mut self, text: impl Into<String>) -> Self
+    where
+        Message: From<scene::Message>,
+    {
+        self.description = Some(Description::new(text));
+
+        self
+    }
 }
 
 impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
     for PostSummary<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
     fn size(&self) -> Size<Length> {
         Size::new(Length::Shrink, Length::Shrink)
     }
@@ -156,8 +200,9 @@ where
         viewport: &Rectangle,
     ) {
         let bounds = layout.bounds();
+        let summary_state = state.state.downcast_ref::<State>();
 
-        let appearance = if cursor.is_over(bounds) {
+        let appearance = if summary_state.hovered {
             theme.hovered(&self.style)
         } else {
             theme.active(&self.style)
@@ -176,6 +221,28 @@ where
             appearance.background_color,
         );
 
+        let tint = if summary_state.pressed {
+            Some(PRESSED_TINT)
+        } else if summary_state.hovered {
+            Some(HOVERED_TINT)
+        } else {
+            None
+        };
+
+        if let Some(tint) = tint {
+            renderer.fill_quad(
+                Quad {
+                    bounds,
+                    border: Border {
+                        radius: 10.0.into(),
+                        ..Border::default()
+                    },
+                    shadow: Default::default(),
+                },
+                tint,
+            );
+        }
+
         let mut children = layout.children();
         let summary_layout = children.next().expect("Post needs to have summary.");
         self.summary.as_widget().draw(
@@ -215,6 +282,13 @@ where
         renderer: &Renderer,
         operation: &mut dyn Operation<Message>,
     ) {
+        if let Some(description) = &self.description {
+            description.operate(
+                &mut state.state.downcast_mut::<State>().description,
+                operation,
+            );
+        }
+
         let mut children = layout.children();
 
         let summary_layout = children.next().expect("Post needs to have summary.");
@@ -249,6 +323,23 @@ where
         let image_layout = children.next().expect("Post needs to have image.");
         let image_bounds = image_layout.bounds();
 
+        let summary_state = state.state.downcast_mut::<State>();
+        summary_state.hovered = cursor.is_over(bounds);
+
+        if let Some(description) = &self.description {
+            description.on_event(
+                &mut summary_state.description,
+                &event,
+                bounds,
+                cursor,
+                shell,
+            );
+        }
+
+        if let Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) = event {
+            summary_state.pressed = false;
+        }
+
         let result = self.summary.as_widget_mut().on_event(
             &mut state.children[0],
             event.clone(),
@@ -266,6 +357,10 @@ where
 
         match event {
             Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if cursor.is_over(bounds) {
+                    state.state.downcast_mut::<State>().pressed = true;
+                }
+
                 if cursor.is_over(image_bounds) {
                     if let Some(message) = &self.on_click_image {
                         shell.publish(message.clone());
@@ -310,17 +405,27 @@ where
         renderer: &Renderer,
         translation: Vector,
     ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        let description_overlay = self.description.as_ref().and_then(|description| {
+            description.overlay(
+                &state.state.downcast_ref::<State>().description,
+                layout.bounds(),
+                translation,
+            )
+        });
+
         let summary_layout = layout
             .children()
             .next()
             .expect("Post needs to have summary");
 
-        self.summary.as_widget_mut().overlay(
+        let summary_overlay = self.summary.as_widget_mut().overlay(
             &mut state.children[0],
             summary_layout,
             renderer,
             translation,
-        )
+        );
+
+        description::group(summary_overlay, description_overlay)
     }
 }
 
@@ -328,7 +433,7 @@ impl<'a, Message, Theme, Renderer> From<PostSummary<'a, Message, Theme, Renderer
     for Element<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
     Theme: 'a + StyleSheet,
 {
     fn from(value: PostSummary<'a, Message, Theme, Renderer>) -> Self {
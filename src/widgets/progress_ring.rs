@@ -0,0 +1,158 @@
+use iced::advanced::layout::{Limits, Node};
+use iced::advanced::renderer::{Quad, Style};
+use iced::advanced::widget::Tree;
+use iced::advanced::{Layout, Widget};
+use iced::mouse::Cursor;
+use iced::{Border, Color, Element, Length, Point, Rectangle, Size, Vector};
+use std::f32::consts::PI;
+
+use crate::utils::theme;
+
+/// The default diameter of a [ProgressRing].
+const DEFAULT_SIZE: f32 = 40.0;
+
+/// The default thickness of a [ProgressRing].
+const DEFAULT_THICKNESS: f32 = 4.0;
+
+/// The opacity of the part of the ring that has not been filled yet.
+const TRACK_ALPHA: f32 = 0.2;
+
+/// A circular progress indicator, filled clockwise starting from the top.
+pub struct ProgressRing {
+    /// The completed fraction, between 0 and 1.
+    value: f32,
+
+    /// The diameter of the [ProgressRing].
+    size: f32,
+
+    /// The thickness of the ring.
+    thickness: f32,
+
+    /// The color of the filled part of the ring.
+    color: Color,
+}
+
+impl ProgressRing {
+    /// Creates a [ProgressRing] filled up to the given value, between 0 and 1.
+    pub fn new(value: f32) -> Self {
+        ProgressRing {
+            value: value.clamp(0.0, 1.0),
+            size: DEFAULT_SIZE,
+            thickness: DEFAULT_THICKNESS,
+            color: theme::extended_palette_generator(theme::PALETTE)
+                .primary
+                .strong
+                .color,
+        }
+    }
+
+    /// Sets the diameter of the [ProgressRing].
+    pub fn size(mut self, size: impl Into<f32>) -> Self {
+        self.size = size.into();
+
+        self
+    }
+
+    /// Sets the thickness of the ring.
+    pub fn thickness(mut self, thickness: impl Into<f32>) -> Self {
+        self.thickness = thickness.into();
+
+        self
+    }
+
+    /// Sets the color of the filled part of the ring.
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+
+        self
+    }
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer> for ProgressRing
+where
+    Renderer: iced::advanced::Renderer,
+{
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fixed(self.size), Length::Fixed(self.size))
+    }
+
+    fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.resolve(
+            Length::Fixed(self.size),
+            Length::Fixed(self.size),
+            Size::new(self.size, self.size),
+        ))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let diameter = bounds.width.min(bounds.height);
+        let thickness = self.thickness.min(diameter / 2.0);
+
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle::new(
+                    bounds.center() - Vector::new(diameter / 2.0, diameter / 2.0),
+                    Size::new(diameter, diameter),
+                ),
+                border: Border {
+                    color: Color {
+                        a: TRACK_ALPHA,
+                        ..self.color
+                    },
+                    width: thickness,
+                    radius: (diameter / 2.0).into(),
+                },
+                shadow: Default::default(),
+            },
+            Color::TRANSPARENT,
+        );
+
+        if self.value == 0.0 {
+            return;
+        }
+
+        // The arc is drawn as overlapping dots, spaced at most half of the thickness apart.
+        let radius = (diameter - thickness) / 2.0;
+        let length = 2.0 * PI * radius * self.value;
+        let steps = (length / (thickness / 2.0)).ceil().max(1.0) as usize;
+
+        for step in 0..=steps {
+            let angle = -PI / 2.0 + 2.0 * PI * self.value * step as f32 / steps as f32;
+            let center = bounds.center() + Vector::new(angle.cos(), angle.sin()) * radius;
+
+            renderer.fill_quad(
+                Quad {
+                    bounds: Rectangle::new(
+                        Point::new(center.x - thickness / 2.0, center.y - thickness / 2.0),
+                        Size::new(thickness, thickness),
+                    ),
+                    border: Border {
+                        radius: (thickness / 2.0).into(),
+                        ..Border::default()
+                    },
+                    shadow: Default::default(),
+                },
+                self.color,
+            );
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<ProgressRing> for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn from(value: ProgressRing) -> Self {
+        Element::new(value)
+    }
+}
@@ -1,14 +1,16 @@
 use iced::advanced::layout::{Limits, Node};
 use iced::advanced::renderer::Style;
-use iced::advanced::widget::Tree;
-use iced::advanced::{Clipboard, Layout, Shell, Text, Widget};
+use iced::advanced::widget::{tree, Operation, Tree};
+use iced::advanced::{overlay, Clipboard, Layout, Shell, Text, Widget};
 use iced::alignment::{Horizontal, Vertical};
 use iced::event::Status;
 use iced::mouse::{Cursor, Interaction};
 use iced::widget::text::{LineHeight, Shaping};
-use iced::{mouse, Color, Element, Event, Length, Padding, Point, Rectangle, Size};
+use iced::{mouse, Color, Element, Event, Length, Padding, Point, Rectangle, Size, Vector};
 
+use crate::scene;
 use crate::utils::icons::{Icon, ICON};
+use crate::widgets::description::{self, Description};
 use iced::advanced::text::Renderer;
 
 /// The default size of a star.
@@ -22,7 +24,7 @@ const DEFAULT_PADDING: f32 = 5.0;
 
 pub struct Rating<F, Message>
 where
-    F: Fn(usize) -> Message,
+    F: Fn(f32) -> Message,
     Message: Clone,
 {
     /// The size of a star.
@@ -41,16 +43,22 @@ where
     on_unrate: Option<Message>,
 
     /// The current rating(0 if no rating).
-    value: usize,
+    value: f32,
 
     /// The rating on which the user is hovering.
-    hovered_value: Option<usize>,
+    hovered_value: Option<f32>,
+
+    /// Tells whether the user can give ratings in steps of half a star.
+    half_stars: bool,
+
+    /// The optional [Description] of the [Rating].
+    description: Option<Description<Message>>,
 }
 
 impl<F, Message> Rating<F, Message>
 where
     Message: Clone,
-    F: Fn(usize) -> Message,
+    F: Fn(f32) -> Message,
 {
     /// Initializes the empty and full star images and returns a default [Rating].
     pub fn new() -> Self {
@@ -60,8 +68,10 @@ where
             padding: DEFAULT_PADDING.into(),
             on_rate: None,
             on_unrate: None,
-            value: 0,
+            value: 0.0,
             hovered_value: None,
+            half_stars: false,
+            description: None,
         }
     }
 
@@ -101,18 +111,43 @@ where
     }
 
     /// Sets the current rating.
-    pub fn value(mut self, value: impl Into<usize>) -> Self {
+    pub fn value(mut self, value: impl Into<f32>) -> Self {
         self.value = value.into();
 
         self
     }
+
+    /// Sets whether the user can give ratings in steps of half a star.
+    pub fn half_stars(mut self, half_stars: bool) -> Self {
+        self.half_stars = half_stars;
+
+        self
+    }
+
+    /// Sets the description of the [Rating], announced to screen readers when it gains focus.
+    pub fn describe(mut self, text: impl Into<String>) -> Self
+    where
+        Message: From<scene::Message>,
+    {
+        self.description = Some(Description::new(text));
+
+        self
+    }
 }
 
 impl<F, Message, Theme> Widget<Message, Theme, iced::Renderer> for Rating<F, Message>
 where
     Message: Clone,
-    F: Fn(usize) -> Message,
+    F: Fn(f32) -> Message,
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<description::State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(description::State::default())
+    }
+
     fn size(&self) -> Size<Length> {
         Size::new(
             Length::Fixed(
@@ -161,19 +196,19 @@ where
                 .next()
                 .expect(&*format!("Rating needs to have more than {} children.", i));
 
-            let mut content: String = Icon::StarEmpty.to_string();
+            let value = match self.hovered_value {
+                Some(hovered_value) if hovered_value != self.value => hovered_value,
+                Some(_) => 0.0,
+                None => self.value,
+            };
 
-            if let Some(hovered_value) = self.hovered_value {
-                if hovered_value != self.value {
-                    if i < hovered_value {
-                        content = Icon::StarFull.to_string();
-                    }
-                }
+            let content = if value >= (i + 1) as f32 {
+                Icon::StarFull.to_string()
+            } else if value >= i as f32 + 0.5 {
+                Icon::StarHalf.to_string()
             } else {
-                if i < self.value {
-                    content = Icon::StarFull.to_string();
-                }
-            }
+                Icon::StarEmpty.to_string()
+            };
 
             renderer.fill_text(
                 Text {
@@ -193,9 +228,21 @@ where
         }
     }
 
+    fn operate(
+        &self,
+        state: &mut Tree,
+        _layout: Layout<'_>,
+        _renderer: &iced::Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        if let Some(description) = &self.description {
+            description.operate(state.state.downcast_mut::<description::State>(), operation);
+        }
+    }
+
     fn on_event(
         &mut self,
-        _state: &mut Tree,
+        state: &mut Tree,
         event: Event,
         layout: Layout<'_>,
         cursor: Cursor,
@@ -204,6 +251,16 @@ where
         shell: &mut Shell<'_, Message>,
         _viewport: &Rectangle,
     ) -> Status {
+        if let Some(description) = &self.description {
+            description.on_event(
+                state.state.downcast_mut::<description::State>(),
+                &event,
+                layout.bounds(),
+                cursor,
+                shell,
+            );
+        }
+
         match event {
             Event::Mouse(mouse::Event::CursorMoved { .. }) => {
                 let bounds = layout.bounds();
@@ -215,16 +272,18 @@ where
                         let bounds = layout.bounds();
 
                         if cursor.is_over(bounds) {
-                            match self.hovered_value {
-                                None => {
-                                    self.hovered_value = Some(i);
-                                }
-                                Some(value) => {
-                                    if value != i {
-                                        self.hovered_value = Some(i);
+                            let mut value = i as f32;
+                            if self.half_stars {
+                                if let Some(position) = cursor.position() {
+                                    if position.x < bounds.center_x() {
+                                        value -= 0.5;
                                     }
                                 }
                             }
+
+                            if self.hovered_value != Some(value) {
+                                self.hovered_value = Some(value);
+                            }
                         }
                     }
                 } else {
@@ -239,7 +298,7 @@ where
                 if let Some(hovered_value) = self.hovered_value {
                     if self.value == hovered_value {
                         if let Some(on_unrate) = &self.on_unrate {
-                            self.value = 0;
+                            self.value = 0.0;
 
                             shell.publish(on_unrate.clone());
                             return Status::Captured;
@@ -274,6 +333,22 @@ where
             Interaction::default()
         }
     }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut Tree,
+        layout: Layout<'_>,
+        _renderer: &iced::Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, iced::Renderer>> {
+        self.description.as_ref().and_then(|description| {
+            description.overlay(
+                state.state.downcast_ref::<description::State>(),
+                layout.bounds(),
+                translation,
+            )
+        })
+    }
 }
 
 impl<'a, F, Message, Theme> From<Rating<F, Message>> for Element<'a, Message, Theme, iced::Renderer>
@@ -0,0 +1,394 @@
+use iced::{
+    advanced::{
+        layout::{Limits, Node},
+        renderer::{Quad, Style},
+        widget::{tree, Operation, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    event::Status,
+    mouse::{self, Cursor, Interaction},
+    Element, Event, Length, Point, Rectangle, Size, Vector,
+};
+
+use crate::utils::theme;
+
+/// The default thickness of the drag handles.
+const DEFAULT_HANDLE_SIZE: f32 = 6.0;
+
+/// The default smallest size the content can be resized to.
+const DEFAULT_MIN_SIZE: Size = Size::new(50.0, 50.0);
+
+/// A drag handle of a [Resizable], given by the directions it resizes the content in. Each
+/// direction is -1 for the left or top edge, 1 for the right or bottom edge and 0 if the handle
+/// does not resize along that axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Handle {
+    horizontal: i8,
+    vertical: i8,
+}
+
+impl Handle {
+    /// The handles on the four edges and the four corners.
+    const ALL: [Handle; 8] = [
+        Handle::new(-1, -1),
+        Handle::new(0, -1),
+        Handle::new(1, -1),
+        Handle::new(-1, 0),
+        Handle::new(1, 0),
+        Handle::new(-1, 1),
+        Handle::new(0, 1),
+        Handle::new(1, 1),
+    ];
+
+    const fn new(horizontal: i8, vertical: i8) -> Self {
+        Handle {
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// Returns the area of the handle on the border of the given bounds.
+    fn get_bounds(&self, bounds: Rectangle, size: f32) -> Rectangle {
+        let (x, width) = match self.horizontal {
+            -1 => (bounds.x, size),
+            1 => (bounds.x + bounds.width - size, size),
+            _ => (bounds.x + size, bounds.width - 2.0 * size),
+        };
+        let (y, height) = match self.vertical {
+            -1 => (bounds.y, size),
+            1 => (bounds.y + bounds.height - size, size),
+            _ => (bounds.y + size, bounds.height - 2.0 * size),
+        };
+
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Tells whether the handle is on a corner.
+    fn is_corner(&self) -> bool {
+        self.horizontal != 0 && self.vertical != 0
+    }
+
+    /// Returns the cursor displayed over the handle.
+    fn get_interaction(&self, dragging: bool) -> Interaction {
+        if self.is_corner() {
+            if dragging {
+                Interaction::Grabbing
+            } else {
+                Interaction::Grab
+            }
+        } else if self.horizontal != 0 {
+            Interaction::ResizingHorizontally
+        } else {
+            Interaction::ResizingVertically
+        }
+    }
+}
+
+/// The state of a [Resizable].
+#[derive(Default)]
+struct State {
+    /// The handle being dragged, the position the drag started at and the size of the content
+    /// at that moment.
+    drag: Option<(Handle, Point, Size)>,
+}
+
+/// A widget that wraps its content and lets the user resize it by dragging its edges and corners.
+pub struct Resizable<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// The content that is resized.
+    content: Element<'a, Message, Theme, Renderer>,
+
+    /// The current size of the content.
+    size: Size,
+
+    /// The smallest size the content can be resized to.
+    min_size: Size,
+
+    /// The thickness of the drag handles.
+    handle_size: f32,
+
+    /// The message triggered with the new size when a handle is dragged.
+    on_resize: fn(Size) -> Message,
+}
+
+impl<'a, Message, Theme, Renderer> Resizable<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// Wraps the given content, with the given size.
+    pub fn new(
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+        size: Size,
+        on_resize: fn(Size) -> Message,
+    ) -> Self {
+        Resizable {
+            content: content.into(),
+            size,
+            min_size: DEFAULT_MIN_SIZE,
+            handle_size: DEFAULT_HANDLE_SIZE,
+            on_resize,
+        }
+    }
+
+    /// Sets the smallest size the content can be resized to.
+    pub fn min_size(mut self, min_size: Size) -> Self {
+        self.min_size = min_size;
+
+        self
+    }
+
+    /// Sets the thickness of the drag handles.
+    pub fn handle_size(mut self, handle_size: impl Into<f32>) -> Self {
+        self.handle_size = handle_size.into();
+
+        self
+    }
+
+    /// Returns the handle under the cursor, if any.
+    fn get_handle(&self, bounds: Rectangle, cursor: Cursor) -> Option<Handle> {
+        Handle::ALL
+            .into_iter()
+            .find(|handle| cursor.is_over(handle.get_bounds(bounds, self.handle_size)))
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Resizable<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(
+            Length::Fixed(self.size.width),
+            Length::Fixed(self.size.height),
+        )
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content])
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
+        let size = limits.resolve(
+            Length::Fixed(self.size.width),
+            Length::Fixed(self.size.height),
+            self.size,
+        );
+
+        let content_node = self.content.as_widget().layout(
+            &mut tree.children[0],
+            renderer,
+            &Limits::new(Size::ZERO, size),
+        );
+
+        Node::with_children(size, vec![content_node])
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout
+                .children()
+                .next()
+                .expect("Resizable needs to have content."),
+            cursor,
+            viewport,
+        );
+
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+        let dragged = tree
+            .state
+            .downcast_ref::<State>()
+            .drag
+            .map(|(handle, _, _)| handle);
+        let hovered = dragged.or_else(|| self.get_handle(bounds, cursor));
+
+        for handle in Handle::ALL {
+            let color = if Some(handle) == hovered {
+                palette.primary.strong.color
+            } else {
+                palette.background.strong.color
+            };
+
+            renderer.fill_quad(
+                Quad {
+                    bounds: handle.get_bounds(bounds, self.handle_size),
+                    border: Default::default(),
+                    shadow: Default::default(),
+                },
+                color,
+            );
+        }
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content.as_widget().operate(
+            &mut tree.children[0],
+            layout
+                .children()
+                .next()
+                .expect("Resizable needs to have content."),
+            renderer,
+            operation,
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> Status {
+        let bounds = layout.bounds();
+        let state = tree.state.downcast_mut::<State>();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let (Some(handle), Some(position)) =
+                    (self.get_handle(bounds, cursor), cursor.position())
+                {
+                    state.drag = Some((handle, position, self.size));
+                    return Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if let Some((handle, origin, start)) = state.drag {
+                    let offset = position - origin;
+                    let size = Size::new(
+                        (start.width + offset.x * handle.horizontal as f32)
+                            .max(self.min_size.width),
+                        (start.height + offset.y * handle.vertical as f32)
+                            .max(self.min_size.height),
+                    );
+
+                    if size != self.size {
+                        shell.publish((self.on_resize)(size));
+                    }
+                    return Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if state.drag.take().is_some() {
+                    return Status::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout
+                .children()
+                .next()
+                .expect("Resizable needs to have content."),
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        if let Some((handle, _, _)) = tree.state.downcast_ref::<State>().drag {
+            return handle.get_interaction(true);
+        }
+        if let Some(handle) = self.get_handle(layout.bounds(), cursor) {
+            return handle.get_interaction(false);
+        }
+
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout
+                .children()
+                .next()
+                .expect("Resizable needs to have content."),
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout
+                .children()
+                .next()
+                .expect("Resizable needs to have content."),
+            renderer,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Resizable<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn from(value: Resizable<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(value)
+    }
+}
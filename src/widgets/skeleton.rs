@@ -0,0 +1,142 @@
+use iced::advanced::layout::{Limits, Node};
+use iced::advanced::renderer::{Quad, Style};
+use iced::advanced::widget::Tree;
+use iced::advanced::{Layout, Widget};
+use iced::gradient::Linear;
+use iced::mouse::Cursor;
+use iced::{Border, Color, Element, Gradient, Length, Radians, Rectangle, Size};
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use crate::utils::theme;
+
+/// The time between two frames of the shimmer animation.
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(30);
+
+/// The number of frames it takes the shimmer to cross the [Skeleton].
+const FRAMES_PER_SWEEP: f32 = 50.0;
+
+/// The width of the shimmer, relative to the width of the [Skeleton].
+const SHIMMER_WIDTH: f32 = 0.3;
+
+/// The opacity of the brightest part of the shimmer.
+const SHIMMER_ALPHA: f32 = 0.4;
+
+/// A placeholder displayed while content is loading, with a shimmer moving over it from left to
+/// right.
+pub struct Skeleton {
+    /// The width of the [Skeleton].
+    width: Length,
+
+    /// The height of the [Skeleton].
+    height: Length,
+
+    /// How far the shimmer has moved across the [Skeleton], between 0 and 1.
+    phase: f32,
+}
+
+impl Skeleton {
+    /// Creates a [Skeleton] with the shimmer at the given phase of its animation.
+    pub fn new(phase: f32) -> Self {
+        Skeleton {
+            width: Length::Fill,
+            height: Length::Fill,
+            phase: phase.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Sets the width of the [Skeleton].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+
+        self
+    }
+
+    /// Sets the height of the [Skeleton].
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+
+        self
+    }
+
+    /// Returns the phase of the shimmer one frame after the given phase.
+    pub fn next_phase(phase: f32) -> f32 {
+        (phase + 1.0 / FRAMES_PER_SWEEP) % 1.0
+    }
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer> for Skeleton
+where
+    Renderer: iced::advanced::Renderer,
+{
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.resolve(self.width, self.height, Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+
+        renderer.fill_quad(
+            Quad {
+                bounds,
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Border::default()
+                },
+                shadow: Default::default(),
+            },
+            palette.background.weak.color,
+        );
+
+        // The shimmer starts fully to the left of the bounds and ends fully to their right.
+        let shimmer_width = bounds.width * SHIMMER_WIDTH;
+        let shimmer_bounds = Rectangle {
+            x: bounds.x - shimmer_width + (bounds.width + shimmer_width) * self.phase,
+            width: shimmer_width,
+            ..bounds
+        };
+        let highlight = Color {
+            a: SHIMMER_ALPHA,
+            ..palette.background.base.color
+        };
+
+        renderer.with_layer(bounds, |renderer| {
+            renderer.fill_quad(
+                Quad {
+                    bounds: shimmer_bounds,
+                    border: Border::default(),
+                    shadow: Default::default(),
+                },
+                Gradient::Linear(
+                    Linear::new(Radians(PI / 2.0))
+                        .add_stop(0.0, Color::TRANSPARENT)
+                        .add_stop(0.5, highlight)
+                        .add_stop(1.0, Color::TRANSPARENT),
+                ),
+            );
+        });
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Skeleton> for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn from(value: Skeleton) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,475 @@
+use iced::{
+    advanced::{
+        layout::{Limits, Node},
+        renderer::{Quad, Style},
+        widget::{tree, Operation, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
+    event::Status,
+    mouse::{self, Cursor, Interaction},
+    Element, Event, Length, Point, Rectangle, Size, Vector,
+};
+
+use crate::utils::theme;
+
+/// The default thickness of the divider.
+const DEFAULT_DIVIDER_SIZE: f32 = 6.0;
+
+/// The smallest part of the space either child can take.
+const MIN_RATIO: f32 = 0.05;
+
+/// The way the children of a [Splitter] are placed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// The children are placed side by side, split by a vertical divider.
+    Horizontal,
+
+    /// The children are placed one above the other, split by a horizontal divider.
+    Vertical,
+}
+
+/// The state of a [Splitter].
+struct State {
+    /// The part of the space taken by the first child.
+    ratio: f32,
+
+    /// Tells whether the divider is being dragged.
+    dragging: bool,
+}
+
+/// A widget that places two children next to each other, with a divider between them that can
+/// be dragged to change how much space each of them takes.
+pub struct Splitter<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// The first child; placed on the left or on top.
+    first: Element<'a, Message, Theme, Renderer>,
+
+    /// The second child; placed on the right or on the bottom.
+    second: Element<'a, Message, Theme, Renderer>,
+
+    /// The way the children are placed.
+    direction: Direction,
+
+    /// The part of the space taken by the first child before the divider is ever dragged.
+    ratio: f32,
+
+    /// The thickness of the divider.
+    divider_size: f32,
+
+    /// The width of the [Splitter].
+    width: Length,
+
+    /// The height of the [Splitter].
+    height: Length,
+}
+
+impl<'a, Message, Theme, Renderer> Splitter<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    /// Creates a new [Splitter] with the given children, splitting the space in half.
+    pub fn new(
+        first: impl Into<Element<'a, Message, Theme, Renderer>>,
+        second: impl Into<Element<'a, Message, Theme, Renderer>>,
+        direction: Direction,
+    ) -> Self {
+        Splitter {
+            first: first.into(),
+            second: second.into(),
+            direction,
+            ratio: 0.5,
+            divider_size: DEFAULT_DIVIDER_SIZE,
+            width: Length::Fill,
+            height: Length::Fill,
+        }
+    }
+
+    /// Sets the part of the space initially taken by the first child. Takes value in (0, 1).
+    pub fn ratio(mut self, ratio: impl Into<f32>) -> Self {
+        self.ratio = ratio.into().clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+
+        self
+    }
+
+    /// Sets the thickness of the divider.
+    pub fn divider_size(mut self, divider_size: impl Into<f32>) -> Self {
+        self.divider_size = divider_size.into();
+
+        self
+    }
+
+    /// Sets the width of the [Splitter].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+
+        self
+    }
+
+    /// Sets the height of the [Splitter].
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+
+        self
+    }
+
+    /// Returns the [FillPortion](Length::FillPortion) of each child for the given ratio.
+    fn get_portions(ratio: f32) -> (Length, Length) {
+        let first = (ratio * 1000.0).round() as u16;
+
+        (
+            Length::FillPortion(first.max(1)),
+            Length::FillPortion((1000 - first).max(1)),
+        )
+    }
+
+    /// Returns the bounds of the divider, given the layout of the second child.
+    fn get_divider_bounds(&self, bounds: Rectangle, second: Rectangle) -> Rectangle {
+        match self.direction {
+            Direction::Horizontal => Rectangle {
+                x: second.x - self.divider_size,
+                width: self.divider_size,
+                ..bounds
+            },
+            Direction::Vertical => Rectangle {
+                y: second.y - self.divider_size,
+                height: self.divider_size,
+                ..bounds
+            },
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Splitter<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State {
+            ratio: self.ratio,
+            dragging: false,
+        })
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.first), Tree::new(&self.second)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.first, &self.second])
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
+        let ratio = tree.state.downcast_ref::<State>().ratio;
+        let size = limits.resolve(self.width, self.height, Size::ZERO);
+        let (first_portion, second_portion) = Self::get_portions(ratio);
+
+        let (first_size, second_size) = match self.direction {
+            Direction::Horizontal => {
+                let space = (size.width - self.divider_size).max(0.0);
+                let first = space * first_portion.fill_factor() as f32
+                    / (first_portion.fill_factor() + second_portion.fill_factor()) as f32;
+
+                (
+                    Size::new(first, size.height),
+                    Size::new(space - first, size.height),
+                )
+            }
+            Direction::Vertical => {
+                let space = (size.height - self.divider_size).max(0.0);
+                let first = space * first_portion.fill_factor() as f32
+                    / (first_portion.fill_factor() + second_portion.fill_factor()) as f32;
+
+                (
+                    Size::new(size.width, first),
+                    Size::new(size.width, space - first),
+                )
+            }
+        };
+
+        let first_node = self.first.as_widget().layout(
+            &mut tree.children[0],
+            renderer,
+            &Limits::new(Size::ZERO, first_size),
+        );
+        let second_node = self
+            .second
+            .as_widget()
+            .layout(
+                &mut tree.children[1],
+                renderer,
+                &Limits::new(Size::ZERO, second_size),
+            )
+            .move_to(match self.direction {
+                Direction::Horizontal => Point::new(first_size.width + self.divider_size, 0.0),
+                Direction::Vertical => Point::new(0.0, first_size.height + self.divider_size),
+            });
+
+        Node::with_children(size, vec![first_node, second_node])
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let mut children = layout.children();
+        let first_node = children
+            .next()
+            .expect("Splitter needs to have a first child.");
+        let second_node = children
+            .next()
+            .expect("Splitter needs to have a second child.");
+
+        self.first.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            first_node,
+            cursor,
+            viewport,
+        );
+        self.second.as_widget().draw(
+            &tree.children[1],
+            renderer,
+            theme,
+            style,
+            second_node,
+            cursor,
+            viewport,
+        );
+
+        let divider = self.get_divider_bounds(bounds, second_node.bounds());
+        let palette = theme::extended_palette_generator(theme::PALETTE);
+        let color = if tree.state.downcast_ref::<State>().dragging || cursor.is_over(divider) {
+            palette.primary.strong.color
+        } else {
+            palette.background.strong.color
+        };
+
+        renderer.fill_quad(
+            Quad {
+                bounds: divider,
+                border: Default::default(),
+                shadow: Default::default(),
+            },
+            color,
+        );
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        let mut children = layout.children();
+
+        self.first.as_widget().operate(
+            &mut tree.children[0],
+            children
+                .next()
+                .expect("Splitter needs to have a first child."),
+            renderer,
+            operation,
+        );
+        self.second.as_widget().operate(
+            &mut tree.children[1],
+            children
+                .next()
+                .expect("Splitter needs to have a second child."),
+            renderer,
+            operation,
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> Status {
+        let bounds = layout.bounds();
+        let mut children = layout.children();
+        let first_node = children
+            .next()
+            .expect("Splitter needs to have a first child.");
+        let second_node = children
+            .next()
+            .expect("Splitter needs to have a second child.");
+        let divider = self.get_divider_bounds(bounds, second_node.bounds());
+        let state = tree.state.downcast_mut::<State>();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if cursor.is_over(divider) {
+                    state.dragging = true;
+                    return Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if state.dragging {
+                    let ratio = match self.direction {
+                        Direction::Horizontal => {
+                            (position.x - bounds.x - self.divider_size / 2.0)
+                                / (bounds.width - self.divider_size)
+                        }
+                        Direction::Vertical => {
+                            (position.y - bounds.y - self.divider_size / 2.0)
+                                / (bounds.height - self.divider_size)
+                        }
+                    };
+
+                    if ratio.is_finite() {
+                        state.ratio = ratio.clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+                        shell.invalidate_layout();
+                    }
+                    return Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if state.dragging {
+                    state.dragging = false;
+                    return Status::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        let first_status = self.first.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event.clone(),
+            first_node,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+        let second_status = self.second.as_widget_mut().on_event(
+            &mut tree.children[1],
+            event,
+            second_node,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+
+        first_status.merge(second_status)
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        let mut children = layout.children();
+        let first_node = children
+            .next()
+            .expect("Splitter needs to have a first child.");
+        let second_node = children
+            .next()
+            .expect("Splitter needs to have a second child.");
+        let divider = self.get_divider_bounds(layout.bounds(), second_node.bounds());
+
+        if tree.state.downcast_ref::<State>().dragging || cursor.is_over(divider) {
+            return match self.direction {
+                Direction::Horizontal => Interaction::ResizingHorizontally,
+                Direction::Vertical => Interaction::ResizingVertically,
+            };
+        }
+
+        self.first
+            .as_widget()
+            .mouse_interaction(&tree.children[0], first_node, cursor, viewport, renderer)
+            .max(self.second.as_widget().mouse_interaction(
+                &tree.children[1],
+                second_node,
+                cursor,
+                viewport,
+                renderer,
+            ))
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        let mut children = layout.children();
+        let first_node = children
+            .next()
+            .expect("Splitter needs to have a first child.");
+        let second_node = children
+            .next()
+            .expect("Splitter needs to have a second child.");
+        let (first_tree, second_tree) = tree.children.split_at_mut(1);
+
+        let overlays: Vec<_> = [
+            self.first.as_widget_mut().overlay(
+                &mut first_tree[0],
+                first_node,
+                renderer,
+                translation,
+            ),
+            self.second.as_widget_mut().overlay(
+                &mut second_tree[0],
+                second_node,
+                renderer,
+                translation,
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if overlays.is_empty() {
+            None
+        } else {
+            Some(iced::advanced::overlay::Group::with_children(overlays).overlay())
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Splitter<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    fn from(value: Splitter<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(value)
+    }
+}
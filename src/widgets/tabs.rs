@@ -1,13 +1,28 @@
 use iced::{
+    advanced::{
+        layout::{Limits, Node},
+        overlay,
+        renderer::{self, Quad},
+        widget::{tree, Operation, Tree},
+        Clipboard, Layout, Shell, Widget,
+    },
     alignment::Horizontal,
+    event,
+    keyboard::{self, key::Named, Key},
+    mouse::{self, Cursor, Interaction},
     widget::{
         button::{Status, Style},
         Button, Column, Row, Text,
     },
-    Element, Length,
+    Border, Color, Element, Event, Length, Rectangle, Size, Vector,
 };
 
+use crate::scene;
 use crate::utils::theme;
+use crate::widgets::description::{self, Description};
+
+/// The width of the ring drawn around the selected tab when the [Tabs] are focused.
+const FOCUS_RING_WIDTH: f32 = 2.0;
 
 pub struct Tabs<'a, Type, Message, Theme, Renderer>
 where
@@ -20,6 +35,7 @@ where
     width: Length,
     height: Length,
     on_select: fn(Type) -> Message,
+    description: Option<Description<Message>>,
 }
 
 impl<'a, Type, Message, Theme, Renderer> Tabs<'a, Type, Message, Theme, Renderer>
@@ -38,6 +54,7 @@ where
             width: Length::Shrink,
             height: Length::Shrink,
             on_select,
+            description: None,
         }
     }
 
@@ -58,6 +75,16 @@ where
 
         self
     }
+
+    /// Sets the description of the [Tabs], announced to screen readers when they gain focus.
+    pub fn describe(mut self, text: impl Into<String>) -> Self
+    where
+        Message: From<scene::Message>,
+    {
+        self.description = Some(Description::new(text));
+
+        self
+    }
 }
 
 impl<'a, Type, Message, Renderer> From<Tabs<'a, Type, Message, theme::Theme, Renderer>>
@@ -65,7 +92,7 @@ impl<'a, Type, Message, Renderer> From<Tabs<'a, Type, Message, theme::Theme, Ren
 where
     Type: 'a + Eq + Default + Copy,
     Message: 'a + Clone,
-    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
 {
     fn from(value: Tabs<'a, Type, Message, theme::Theme, Renderer>) -> Self {
         let (titles, contents) = value.tabs.into_iter().fold(
@@ -77,7 +104,9 @@ where
             },
         );
 
-        Column::with_children(vec![
+        let types = titles.iter().map(|(tab, _)| *tab).collect();
+
+        let content: Element<'a, Message, theme::Theme, Renderer> = Column::with_children(vec![
             Row::with_children(
                 titles
                     .into_iter()
@@ -115,6 +144,264 @@ where
         ])
         .width(value.width)
         .height(value.height)
-        .into()
+        .into();
+
+        Element::new(TabsWidget {
+            types,
+            selected: value.selected,
+            on_select: value.on_select,
+            content,
+            description: value.description,
+        })
+    }
+}
+
+/// The state of the [Tabs].
+#[derive(Default)]
+struct State {
+    /// Tells whether the tab titles were clicked last, so that the arrow keys switch tabs.
+    focused: bool,
+
+    /// The state of the [Description] of the [Tabs].
+    description: description::State,
+}
+
+/// The [Widget] the [Tabs] turn into. Besides clicking the titles, the selected tab can be
+/// changed with the left and right arrow keys once the titles are focused.
+struct TabsWidget<'a, Type, Message, Theme, Renderer>
+where
+    Type: 'a + Eq + Copy,
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    /// The tabs, in the order of their titles.
+    types: Vec<Type>,
+
+    /// The selected tab.
+    selected: Type,
+
+    /// The message triggered when a tab is selected.
+    on_select: fn(Type) -> Message,
+
+    /// The titles and the content of the selected tab.
+    content: Element<'a, Message, Theme, Renderer>,
+
+    /// The optional [Description] of the [Tabs].
+    description: Option<Description<Message>>,
+}
+
+impl<'a, Type, Message, Theme, Renderer> TabsWidget<'a, Type, Message, Theme, Renderer>
+where
+    Type: 'a + Eq + Copy,
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer,
+{
+    /// Returns the position of the selected tab.
+    fn selected_index(&self) -> Option<usize> {
+        self.types.iter().position(|tab| *tab == self.selected)
+    }
+}
+
+impl<'a, Type, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for TabsWidget<'a, Type, Message, Theme, Renderer>
+where
+    Type: 'a + Eq + Copy,
+    Message: 'a + Clone,
+    Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+
+        if !tree.state.downcast_ref::<State>().focused {
+            return;
+        }
+
+        let title = self.selected_index().and_then(|index| {
+            layout
+                .children()
+                .next()
+                .and_then(|titles| titles.children().nth(index))
+        });
+
+        if let Some(title) = title {
+            let palette = theme::extended_palette_generator(theme::PALETTE);
+
+            renderer.fill_quad(
+                Quad {
+                    bounds: title.bounds(),
+                    border: Border {
+                        color: palette.primary.strong.color,
+                        width: FOCUS_RING_WIDTH,
+                        radius: Default::default(),
+                    },
+                    shadow: Default::default(),
+                },
+                Color::TRANSPARENT,
+            );
+        }
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content]);
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        if let Some(description) = &self.description {
+            description.operate(
+                &mut tree.state.downcast_mut::<State>().description,
+                operation,
+            );
+        }
+
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        if let Some(description) = &self.description {
+            description.on_event(
+                &mut tree.state.downcast_mut::<State>().description,
+                &event,
+                layout.bounds(),
+                cursor,
+                shell,
+            );
+        }
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let titles = layout.children().next().map(|titles| titles.bounds());
+
+                tree.state.downcast_mut::<State>().focused =
+                    titles.is_some_and(|titles| cursor.is_over(titles));
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: Key::Named(key @ (Named::ArrowLeft | Named::ArrowRight)),
+                ..
+            }) if tree.state.downcast_ref::<State>().focused => {
+                if let Some(index) = self.selected_index() {
+                    let count = self.types.len();
+                    let index = if key == Named::ArrowLeft {
+                        (index + count - 1) % count
+                    } else {
+                        (index + 1) % count
+                    };
+
+                    shell.publish((self.on_select)(self.types[index]));
+                }
+
+                return event::Status::Captured;
+            }
+            _ => {}
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let description_overlay = self.description.as_ref().and_then(|description| {
+            description.overlay(
+                &tree.state.downcast_ref::<State>().description,
+                layout.bounds(),
+                translation,
+            )
+        });
+
+        let content_overlay = self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            translation,
+        );
+
+        description::group(content_overlay, description_overlay)
     }
 }
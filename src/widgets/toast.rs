@@ -0,0 +1,308 @@
+use crate::utils::theme::Theme;
+use crate::widgets::Close;
+use iced::advanced::layout::{Limits, Node};
+use iced::advanced::renderer::Style;
+use iced::advanced::widget::{Operation, Tree};
+use iced::advanced::{overlay, Clipboard, Layout, Overlay, Shell, Widget};
+use iced::event::Status;
+use iced::mouse::{Cursor, Interaction};
+use iced::widget::{Column, Container, Row, Text};
+use iced::{Alignment, Element, Event, Length, Padding, Point, Rectangle, Renderer, Size, Vector};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The time a toast is displayed before being dismissed automatically.
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// The distance between the toasts and the edges of the screen.
+const TOAST_MARGIN: f32 = 20.0;
+
+/// The queue of toasts currently displayed, as their text and the moment they were created.
+pub type ToastQueue = VecDeque<(String, Instant)>;
+
+/// A widget that displays a stack of dismissible notification cards in the bottom-right corner
+/// of the screen, on top of its content.
+pub struct Toasts<'a, Message>
+where
+    Message: 'a + Clone,
+{
+    /// The content the toasts are displayed over.
+    content: Element<'a, Message, Theme, Renderer>,
+
+    /// The stack of toasts, as a single element.
+    toasts: Option<Element<'a, Message, Theme, Renderer>>,
+}
+
+impl<'a, Message> Toasts<'a, Message>
+where
+    Message: 'a + Clone,
+{
+    /// Creates a new [Toasts] widget over the given content. The closure returns the [Message]
+    /// triggered when the toast with the given index is dismissed.
+    pub fn new(
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+        queue: &ToastQueue,
+        on_close: impl Fn(usize) -> Message,
+    ) -> Self {
+        let toasts = if queue.is_empty() {
+            None
+        } else {
+            Some(
+                Column::with_children(queue.iter().enumerate().map(|(index, (text, _))| {
+                    Container::new(
+                        Row::with_children(vec![
+                            Text::new(text.clone()).width(Length::Fill).into(),
+                            Close::new(on_close(index)).size(20.0).into(),
+                        ])
+                        .spacing(10.0)
+                        .align_items(Alignment::Center),
+                    )
+                    .padding(10.0)
+                    .width(Length::Fixed(300.0))
+                    .style(iced::widget::container::bordered_box)
+                    .into()
+                }))
+                .spacing(10.0)
+                .into(),
+            )
+        };
+
+        Toasts {
+            content: content.into(),
+            toasts,
+        }
+    }
+}
+
+impl<'a, Message> Widget<Message, Theme, Renderer> for Toasts<'a, Message>
+where
+    Message: 'a + Clone,
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &state.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        let mut children = vec![Tree::new(&self.content)];
+        if let Some(toasts) = &self.toasts {
+            children.push(Tree::new(toasts));
+        }
+
+        children
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        let mut children = vec![&self.content];
+        if let Some(toasts) = &self.toasts {
+            children.push(toasts);
+        }
+
+        tree.diff_children(children.as_slice());
+    }
+
+    fn operate<'b>(
+        &'b self,
+        state: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut state.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        state: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> Status {
+        self.content.as_widget_mut().on_event(
+            &mut state.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        self.content.as_widget().mouse_interaction(
+            &state.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let (content_state, toasts_state) = state.children.split_at_mut(1);
+
+        let content = self.content.as_widget_mut().overlay(
+            &mut content_state[0],
+            layout,
+            renderer,
+            translation,
+        );
+
+        let toasts = self.toasts.as_mut().map(|toasts| {
+            overlay::Element::new(Box::new(ToastOverlay {
+                state: &mut toasts_state[0],
+                toasts,
+                translation,
+            }))
+        });
+
+        let children: Vec<_> = content.into_iter().chain(toasts).collect();
+        if children.is_empty() {
+            None
+        } else {
+            Some(overlay::Group::with_children(children).overlay())
+        }
+    }
+}
+
+impl<'a, Message> From<Toasts<'a, Message>> for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+{
+    fn from(value: Toasts<'a, Message>) -> Self {
+        Element::new(value)
+    }
+}
+
+/// The [Overlay] that positions the toasts in the bottom-right corner of the screen.
+struct ToastOverlay<'a, 'b, Message>
+where
+    Message: 'a + Clone,
+{
+    /// The [state](Tree) of the toasts.
+    state: &'b mut Tree,
+
+    /// The stack of toasts.
+    toasts: &'b mut Element<'a, Message, Theme, Renderer>,
+
+    /// The translation of the overlay.
+    translation: Vector,
+}
+
+impl<'a, 'b, Message> Overlay<Message, Theme, Renderer> for ToastOverlay<'a, 'b, Message>
+where
+    Message: 'a + Clone,
+{
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> Node {
+        let limits = Limits::new(Size::ZERO, bounds).shrink(Padding::new(TOAST_MARGIN));
+        let node = self
+            .toasts
+            .as_widget()
+            .layout(self.state, renderer, &limits);
+        let size = node.size();
+
+        node.move_to(Point::new(
+            bounds.width - size.width - TOAST_MARGIN + self.translation.x,
+            bounds.height - size.height - TOAST_MARGIN + self.translation.y,
+        ))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+    ) {
+        self.toasts.as_widget().draw(
+            self.state,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            &layout.bounds(),
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> Status {
+        self.toasts.as_widget_mut().on_event(
+            self.state,
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            &layout.bounds(),
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> Interaction {
+        self.toasts
+            .as_widget()
+            .mouse_interaction(self.state, layout, cursor, viewport, renderer)
+    }
+}
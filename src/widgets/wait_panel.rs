@@ -1,8 +1,27 @@
 use crate::utils::icons::{Icon, ICON};
+use crate::widgets::progress_ring::ProgressRing;
 use iced::alignment::{Horizontal, Vertical};
-use iced::widget::{Column, Container, Text};
+use iced::widget::{Column, Container, ProgressBar, Text};
 use iced::{Alignment, Element, Length, Pixels};
 
+/// The width of the progress bar of a [WaitPanel].
+const PROGRESS_BAR_WIDTH: f32 = 200.0;
+
+/// The height of the progress bar of a [WaitPanel].
+const PROGRESS_BAR_HEIGHT: f32 = 10.0;
+
+/// The progress shown by a [WaitPanel].
+enum Progress {
+    /// The progress is unknown; a spinner is shown.
+    Indeterminate,
+
+    /// The completed fraction of the operation, between 0 and 1; a filled bar is shown.
+    Determinate(f32),
+
+    /// The completed fraction of the operation, between 0 and 1; a filled ring is shown.
+    Ring(f32),
+}
+
 /// A widget that blocks user input. Displays a custom text.
 pub struct WaitPanel {
     /// The width of the [panel](WaitPanel).
@@ -14,6 +33,9 @@ pub struct WaitPanel {
     /// The custom text to be displayed in the center of the [panel](WaitPanel).
     text: String,
 
+    /// The progress of the operation being waited on.
+    progress: Progress,
+
     /// The [styling](Appearance) of the [panel](WaitPanel).
     style: Appearance,
 }
@@ -25,6 +47,7 @@ impl WaitPanel {
             width: Length::Fill,
             height: Length::Fill,
             text: text.into(),
+            progress: Progress::Indeterminate,
             style: Appearance::default(),
         }
     }
@@ -43,6 +66,21 @@ impl WaitPanel {
         self
     }
 
+    /// Shows a bar filled up to the given progress, between 0 and 1, instead of a spinner.
+    pub fn with_progress(mut self, progress: f32) -> Self {
+        self.progress = Progress::Determinate(progress.clamp(0.0, 1.0));
+
+        self
+    }
+
+    /// Shows a ring filled up to the given progress, between 0 and 1, instead of a spinner. It
+    /// takes less space than the bar.
+    pub fn with_progress_ring(mut self, progress: f32) -> Self {
+        self.progress = Progress::Ring(progress.clamp(0.0, 1.0));
+
+        self
+    }
+
     /// Sets the style of the [wait panel](WaitPanel).
     pub fn style(mut self, style: impl Into<Appearance>) -> Self {
         self.style = style.into();
@@ -54,10 +92,27 @@ impl WaitPanel {
 impl<'a, Message, Theme, Renderer> From<WaitPanel> for Element<'a, Message, Theme, Renderer>
 where
     Message: 'a + Clone,
-    Theme: 'a + iced::widget::text::Catalog + iced::widget::container::Catalog,
+    Theme: 'a
+        + iced::widget::text::Catalog
+        + iced::widget::container::Catalog
+        + iced::widget::progress_bar::Catalog,
     Renderer: 'a + iced::advanced::Renderer + iced::advanced::text::Renderer<Font = iced::Font>,
 {
     fn from(value: WaitPanel) -> Self {
+        let progress: Element<'a, Message, Theme, Renderer> = match value.progress {
+            Progress::Indeterminate => Text::new(Icon::Loading.to_string())
+                .font(ICON)
+                .size(value.style.text_size)
+                .into(),
+            Progress::Determinate(progress) => ProgressBar::new(0.0..=1.0, progress)
+                .width(Length::Fixed(PROGRESS_BAR_WIDTH))
+                .height(Length::Fixed(PROGRESS_BAR_HEIGHT))
+                .into(),
+            Progress::Ring(progress) => ProgressRing::new(progress)
+                .size(value.style.text_size.0 * 1.5)
+                .into(),
+        };
+
         Container::new(
             Column::with_children(vec![
                 Text::new(value.text)
@@ -65,10 +120,7 @@ where
                     .horizontal_alignment(Horizontal::Center)
                     .vertical_alignment(Vertical::Center)
                     .into(),
-                Text::new(Icon::Loading.to_string())
-                    .font(ICON)
-                    .size(value.style.text_size)
-                    .into(),
+                progress,
             ])
             .spacing(10.0)
             .align_items(Alignment::Center),
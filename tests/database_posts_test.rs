@@ -0,0 +1,356 @@
+//! Integration tests for the aggregation pipelines in [database::posts].
+//!
+//! The tests need a MongoDB instance, whose address is read from `CHARTSY_TEST_MONGODB_URI`
+//! and defaults to a local server. They only run with the `test` feature enabled:
+//! `cargo test --features test`.
+
+use chartsy::database;
+use chartsy::scenes::data::posts::{Post, SortOrder};
+use mongodb::bson::{doc, DateTime, Uuid};
+use mongodb::{Client, Database};
+
+/// The address of the MongoDB instance used when none is given.
+const DEFAULT_URI: &str = "mongodb://localhost:27017";
+
+/// A database filled with fixture data, dropped once the test that created it finishes.
+///
+/// The fixtures consist of:
+/// - the users `viewer`, `author` and `deleted`, the last one having an expiration date;
+/// - two posts of `author`, tagged `cat, cute` and `cat`, and one post of `deleted`, tagged
+///   `cat`;
+/// - a rating of `viewer` and one of `deleted` on the first post;
+/// - a like of `viewer` on the second post, and `viewer` following `author`;
+/// - a similarity between `viewer` and `author` that always passes the random filter;
+/// - a comment of `author` on the first post, mentioning `viewer`, and a reply to it.
+struct TestDatabase {
+    db: Database,
+    viewer: Uuid,
+    author: Uuid,
+    posts: [Uuid; 3],
+    comments: [Uuid; 2],
+}
+
+impl TestDatabase {
+    async fn new() -> Self {
+        let uri = std::env::var("CHARTSY_TEST_MONGODB_URI").unwrap_or(DEFAULT_URI.into());
+        let client = Client::with_uri_str(uri)
+            .await
+            .expect("Could not connect to the test database.");
+        // Every test gets its own database, so that they can run in parallel.
+        let db = client.database(&format!("chartsy_test_{}", Uuid::new()));
+
+        let viewer = Uuid::new();
+        let author = Uuid::new();
+        let deleted = Uuid::new();
+        let posts = [Uuid::new(), Uuid::new(), Uuid::new()];
+        let comments = [Uuid::new(), Uuid::new()];
+
+        db.collection("users")
+            .insert_many(
+                vec![
+                    doc! { "id": viewer, "username": "viewer", "user_tag": "viewer" },
+                    doc! { "id": author, "username": "author", "user_tag": "author" },
+                    doc! {
+                        "id": deleted,
+                        "username": "deleted",
+                        "user_tag": "deleted",
+                        "expiration_date": DateTime::now()
+                    },
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+
+        // The posts are inserted one by one, so that their ObjectIds follow the insertion order.
+        for post in [
+            doc! { "id": posts[0], "user_id": author, "description": "first", "tags": ["cat", "cute"] },
+            doc! { "id": posts[1], "user_id": author, "description": "second", "tags": ["cat"] },
+            doc! { "id": posts[2], "user_id": deleted, "description": "third", "tags": ["cat"] },
+        ] {
+            db.collection("posts").insert_one(post, None).await.unwrap();
+        }
+
+        db.collection("ratings")
+            .insert_many(
+                vec![
+                    doc! { "post_id": posts[0], "user_id": viewer, "rating": 4.0 },
+                    // Older ratings were stored as integers.
+                    doc! { "post_id": posts[0], "user_id": deleted, "rating": 5 },
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+        db.collection("likes")
+            .insert_one(doc! { "post_id": posts[1], "user_id": viewer }, None)
+            .await
+            .unwrap();
+        db.collection("follows")
+            .insert_one(doc! { "follower_id": viewer, "followed_id": author }, None)
+            .await
+            .unwrap();
+        db.collection("similarities")
+            .insert_one(doc! { "user_id": [viewer, author], "score": 1.0 }, None)
+            .await
+            .unwrap();
+
+        db.collection("comments")
+            .insert_many(
+                vec![
+                    doc! {
+                        "id": comments[0],
+                        "post_id": posts[0],
+                        "user_id": author,
+                        "content": "Nice cat!",
+                        "mentions": [viewer],
+                        "created": DateTime::now()
+                    },
+                    doc! {
+                        "id": comments[1],
+                        "post_id": posts[0],
+                        "user_id": viewer,
+                        "content": "Thanks!",
+                        "reply_to": comments[0],
+                        "mentions": [],
+                        "created": DateTime::now()
+                    },
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+
+        TestDatabase {
+            db,
+            viewer,
+            author,
+            posts,
+            comments,
+        }
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        let db = self.db.clone();
+
+        // The runtime of the test can't be blocked on from inside it, so the database is dropped
+        // on a separate runtime.
+        let _ = std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map(|runtime| runtime.block_on(db.drop(None)))
+        })
+        .join();
+    }
+}
+
+/// Returns the ids of the given posts, sorted.
+fn sorted_ids(posts: &[Post]) -> Vec<Uuid> {
+    let mut ids = posts.iter().map(Post::get_id).collect::<Vec<Uuid>>();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn recommendations_contain_posts_of_similar_users() {
+    let test_db = TestDatabase::new().await;
+
+    let posts = database::posts::get_recommendations(&test_db.db, test_db.viewer)
+        .await
+        .unwrap();
+
+    let mut expected = vec![test_db.posts[0], test_db.posts[1]];
+    expected.sort();
+    assert_eq!(sorted_ids(&posts), expected);
+
+    for post in &posts {
+        assert_eq!(post.get_user().get_id(), test_db.author);
+        assert!(post.is_followed());
+    }
+
+    let first = posts
+        .iter()
+        .find(|post| post.get_id() == test_db.posts[0])
+        .unwrap();
+    assert_eq!(*first.get_rating(), 4.0);
+    assert_eq!(first.get_avg_rating(), 5.0);
+    assert_eq!(first.get_rating_count(), 1);
+    assert!(!first.is_liked());
+
+    let second = posts
+        .iter()
+        .find(|post| post.get_id() == test_db.posts[1])
+        .unwrap();
+    assert_eq!(second.get_rating_count(), 0);
+    assert!(second.is_liked());
+}
+
+#[tokio::test]
+async fn recommendations_skip_similar_users_without_posts() {
+    let test_db = TestDatabase::new().await;
+
+    let posts = database::posts::get_recommendations(&test_db.db, test_db.author)
+        .await
+        .unwrap();
+
+    assert!(posts.is_empty());
+}
+
+#[tokio::test]
+async fn filtered_posts_contain_all_tags() {
+    let test_db = TestDatabase::new().await;
+
+    let posts = database::posts::get_filtered(
+        &test_db.db,
+        test_db.viewer,
+        vec![String::from("cat"), String::from("cute")],
+        SortOrder::Date,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(sorted_ids(&posts), vec![test_db.posts[0]]);
+    assert_eq!(posts[0].get_description(), "first");
+    assert_eq!(
+        posts[0].get_tags(),
+        &vec![String::from("cat"), String::from("cute")]
+    );
+}
+
+#[tokio::test]
+async fn filtered_posts_skip_deleted_users_and_follow_sort_order() {
+    let test_db = TestDatabase::new().await;
+
+    let by_date = database::posts::get_filtered(
+        &test_db.db,
+        test_db.viewer,
+        vec![String::from("cat")],
+        SortOrder::Date,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        by_date.iter().map(Post::get_id).collect::<Vec<Uuid>>(),
+        vec![test_db.posts[1], test_db.posts[0]]
+    );
+
+    let by_rating = database::posts::get_filtered(
+        &test_db.db,
+        test_db.viewer,
+        vec![String::from("cat")],
+        SortOrder::Rating,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        by_rating.iter().map(Post::get_id).collect::<Vec<Uuid>>(),
+        vec![test_db.posts[0], test_db.posts[1]]
+    );
+
+    let by_relevance = database::posts::get_filtered(
+        &test_db.db,
+        test_db.viewer,
+        vec![String::from("cat")],
+        SortOrder::Relevance,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        by_relevance.iter().map(Post::get_id).collect::<Vec<Uuid>>(),
+        vec![test_db.posts[1], test_db.posts[0]]
+    );
+}
+
+#[tokio::test]
+async fn random_posts_skip_denied_posts_and_deleted_users() {
+    let test_db = TestDatabase::new().await;
+
+    let posts = database::posts::get_random_posts(&test_db.db, 10, test_db.viewer, vec![])
+        .await
+        .unwrap();
+    let mut expected = vec![test_db.posts[0], test_db.posts[1]];
+    expected.sort();
+    assert_eq!(sorted_ids(&posts), expected);
+
+    let posts =
+        database::posts::get_random_posts(&test_db.db, 10, test_db.viewer, vec![test_db.posts[0]])
+            .await
+            .unwrap();
+    assert_eq!(sorted_ids(&posts), vec![test_db.posts[1]]);
+    assert_eq!(posts[0].get_description(), "second");
+    assert!(posts[0].is_liked());
+
+    let posts = database::posts::get_random_posts(&test_db.db, 1, test_db.viewer, vec![])
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 1);
+}
+
+#[tokio::test]
+async fn comments_are_loaded_by_parent() {
+    let test_db = TestDatabase::new().await;
+
+    let comments = database::posts::get_comments(
+        &test_db.db,
+        doc! {
+            "post_id": test_db.posts[0],
+            "reply_to": { "$exists": false }
+        },
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(*comments[0].get_id(), test_db.comments[0]);
+    assert_eq!(comments[0].get_content(), "Nice cat!");
+    assert_eq!(comments[0].get_user().get_id(), test_db.author);
+    assert_eq!(
+        comments[0]
+            .get_mentions()
+            .iter()
+            .map(|user| user.get_id())
+            .collect::<Vec<Uuid>>(),
+        vec![test_db.viewer]
+    );
+
+    let replies = database::posts::get_comments(
+        &test_db.db,
+        doc! {
+            "reply_to": test_db.comments[0]
+        },
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(replies.len(), 1);
+    assert_eq!(*replies[0].get_id(), test_db.comments[1]);
+    assert_eq!(replies[0].get_user().get_id(), test_db.viewer);
+    assert!(replies[0].get_mentions().is_empty());
+}
+
+#[tokio::test]
+async fn comments_past_the_last_page_are_empty() {
+    let test_db = TestDatabase::new().await;
+    let filter = doc! {
+        "post_id": test_db.posts[0],
+        "reply_to": { "$exists": false }
+    };
+
+    let comments = database::posts::get_comments(&test_db.db, filter.clone(), None)
+        .await
+        .unwrap();
+    let last = comments.last().unwrap();
+
+    let comments = database::posts::get_comments(
+        &test_db.db,
+        filter,
+        Some((last.get_created(), *last.get_id())),
+    )
+    .await
+    .unwrap();
+
+    assert!(comments.is_empty());
+}